@@ -35,6 +35,12 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Print information about the chain database without starting the node.
+	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Check that the chain spec builds the genesis block of a network.
+	VerifySpec(sc_cli::VerifySpecCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
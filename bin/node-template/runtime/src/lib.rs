@@ -14,6 +14,7 @@ use sp_runtime::{
 };
 use sp_runtime::traits::{
 	BlakeTwo256, Block as BlockT, AccountIdLookup, Verify, IdentifyAccount, NumberFor,
+	SignedExtension,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -203,6 +204,21 @@ impl frame_system::Config for Runtime {
 
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
+	type DisabledValidators = ();
+
+	type KeyOwnerProofSystem = ();
+
+	type KeyOwnerProof =
+		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, AuraId)>>::Proof;
+
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		AuraId,
+	)>>::IdentificationTuple;
+
+	type HandleEquivocation = ();
+
+	type WeightInfo = ();
 }
 
 impl pallet_grandpa::Config for Runtime {
@@ -226,6 +242,7 @@ impl pallet_grandpa::Config for Runtime {
 
 parameter_types! {
 	pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+	pub const MaxDrift: u64 = 30_000;
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -233,6 +250,7 @@ impl pallet_timestamp::Config for Runtime {
 	type Moment = u64;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 
@@ -396,11 +414,35 @@ impl_runtime_apis! {
 		fn authorities() -> Vec<AuraId> {
 			Aura::authorities()
 		}
+
+		fn generate_key_ownership_proof(
+			_slot: sp_consensus_aura::Slot,
+			_authority_id: AuraId,
+		) -> Option<sp_consensus_aura::OpaqueKeyOwnershipProof> {
+			// NOTE: this is the only implementation possible since we've
+			// defined our key owner proof type as a bottom type (i.e. a type
+			// with no values).
+			None
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_aura::EquivocationProof<
+				<Block as BlockT>::Header,
+				AuraId,
+			>,
+			_key_owner_proof: sp_consensus_aura::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
+		}
 	}
 
 	impl sp_session::SessionKeys<Block> for Runtime {
-		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
-			opaque::SessionKeys::generate(seed)
+		fn generate_session_keys(
+			owner: Vec<u8>,
+			seed: Option<Vec<u8>>,
+		) -> sp_session::OpaqueGeneratedSessionKeys {
+			let (keys, proof) = opaque::SessionKeys::generate_with_ownership_proof(&owner, seed);
+			sp_session::OpaqueGeneratedSessionKeys { keys, proof }
 		}
 
 		fn decode_session_keys(
@@ -415,6 +457,25 @@ impl_runtime_apis! {
 			Grandpa::grandpa_authorities()
 		}
 
+		fn current_set_id() -> fg_primitives::SetId {
+			Grandpa::current_set_id()
+		}
+
+		fn pending_change() -> Option<(
+			NumberFor<Block>,
+			fg_primitives::ScheduledChange<NumberFor<Block>>,
+		)> {
+			Grandpa::pending_standard_change()
+		}
+
+		fn pending_forced_change() -> Option<(
+			NumberFor<Block>,
+			NumberFor<Block>,
+			fg_primitives::ScheduledChange<NumberFor<Block>>,
+		)> {
+			Grandpa::pending_forced_change()
+		}
+
 		fn submit_report_equivocation_unsigned_extrinsic(
 			_equivocation_proof: fg_primitives::EquivocationProof<
 				<Block as BlockT>::Hash,
@@ -440,6 +501,25 @@ impl_runtime_apis! {
 		fn account_nonce(account: AccountId) -> Index {
 			System::account_nonce(account)
 		}
+
+		fn signing_info(
+			account: AccountId,
+		) -> frame_system_rpc_runtime_api::SigningInfo<Hash, BlockNumber, Index> {
+			let (era, birth, birth_hash) = System::signing_era(64);
+			let version = System::runtime_version();
+			frame_system_rpc_runtime_api::SigningInfo {
+				nonce: System::account_nonce(account),
+				genesis_hash: System::block_hash(0),
+				era,
+				checkpoint: (birth, birth_hash),
+				spec_version: version.spec_version,
+				transaction_version: version.transaction_version,
+				extensions: SignedExtra::identifier()
+					.into_iter()
+					.map(|identifier| identifier.as_bytes().to_vec())
+					.collect(),
+			}
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
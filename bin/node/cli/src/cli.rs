@@ -81,4 +81,16 @@ pub enum Subcommand {
 
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
+
+	/// Re-execute imported blocks to populate the offchain database.
+	ReindexOffchain(sc_cli::ReindexOffchainCmd),
+
+	/// Remove entries of offchain workers from the offchain database.
+	PurgeOffchain(sc_cli::PurgeOffchainCmd),
+
+	/// Print information about the chain database without starting the node.
+	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Check that the chain spec builds the genesis block of a network.
+	VerifySpec(sc_cli::VerifySpecCmd),
 }
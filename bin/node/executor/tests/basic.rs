@@ -606,7 +606,7 @@ fn deploying_wasm_contract_should_work() {
 		&[],
 	);
 
-	let subsistence = pallet_contracts::Pallet::<Runtime>::subsistence_threshold();
+	let min_balance = <Runtime as pallet_contracts::Config>::Currency::minimum_balance();
 
 	let time = 42 * 1000;
 	let b = construct_block(
@@ -622,8 +622,9 @@ fn deploying_wasm_contract_should_work() {
 				signed: Some((charlie(), signed_extra(0, 0))),
 				function: Call::Contracts(
 					pallet_contracts::Call::instantiate_with_code::<Runtime>(
-						1000 * DOLLARS + subsistence,
+						1000 * DOLLARS + min_balance,
 						500_000_000,
+						None,
 						transfer_code,
 						Vec::new(),
 						Vec::new(),
@@ -637,6 +638,7 @@ fn deploying_wasm_contract_should_work() {
 						sp_runtime::MultiAddress::Id(addr.clone()),
 						10,
 						500_000_000,
+						None,
 						vec![0x00, 0x01, 0x02, 0x03]
 					)
 				),
@@ -20,10 +20,13 @@ use node_runtime::{
 	Executive, Indices, Runtime, UncheckedExtrinsic,
 };
 use sp_application_crypto::AppKey;
+use sp_runtime::traits::BlakeTwo256;
 use sp_core::{
 	offchain::{
+		OffchainDbExt,
+		OffchainWorkerExt,
 		TransactionPoolExt,
-		testing::TestTransactionPoolExt,
+		testing::{TestOffchainExt, TestTransactionPoolExt},
 	},
 };
 use sp_keystore::{KeystoreExt, SyncCryptoStore, testing::KeyStore};
@@ -49,7 +52,6 @@ fn should_submit_unsigned_transaction() {
 		let signature = Default::default();
 		let heartbeat_data = pallet_im_online::Heartbeat {
 			block_number: 1,
-			network_state: Default::default(),
 			session_index: 1,
 			authority_index: 0,
 			validators_len: 0,
@@ -63,6 +65,14 @@ fn should_submit_unsigned_transaction() {
 	});
 }
 
+/// Registers the offchain extensions that are required to track the nonces of the signed
+/// transactions.
+fn register_offchain_ext(t: &mut TestExternalities<BlakeTwo256>) {
+	let (offchain, _state) = TestOffchainExt::new();
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.register_extension(OffchainDbExt::new(offchain));
+}
+
 const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
 
 #[test]
@@ -70,6 +80,7 @@ fn should_submit_signed_transaction() {
 	let mut t = new_test_ext(compact_code_unwrap(), false);
 	let (pool, state) = TestTransactionPoolExt::new();
 	t.register_extension(TransactionPoolExt::new(pool));
+	register_offchain_ext(&mut t);
 
 	let keystore = KeyStore::new();
 	SyncCryptoStore::sr25519_generate_new(
@@ -107,6 +118,7 @@ fn should_submit_signed_twice_from_the_same_account() {
 	let mut t = new_test_ext(compact_code_unwrap(), false);
 	let (pool, state) = TestTransactionPoolExt::new();
 	t.register_extension(TransactionPoolExt::new(pool));
+	register_offchain_ext(&mut t);
 
 	let keystore = KeyStore::new();
 	SyncCryptoStore::sr25519_generate_new(
@@ -159,6 +171,7 @@ fn should_submit_signed_twice_from_all_accounts() {
 	let mut t = new_test_ext(compact_code_unwrap(), false);
 	let (pool, state) = TestTransactionPoolExt::new();
 	t.register_extension(TransactionPoolExt::new(pool));
+	register_offchain_ext(&mut t);
 
 	let keystore = KeyStore::new();
 	keystore.sr25519_generate_new(
@@ -223,6 +236,7 @@ fn submitted_transaction_should_be_valid() {
 	let mut t = new_test_ext(compact_code_unwrap(), false);
 	let (pool, state) = TestTransactionPoolExt::new();
 	t.register_extension(TransactionPoolExt::new(pool));
+	register_offchain_ext(&mut t);
 
 	let keystore = KeyStore::new();
 	SyncCryptoStore::sr25519_generate_new(
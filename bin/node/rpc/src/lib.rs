@@ -49,7 +49,7 @@ use sp_consensus::SelectChain;
 use sp_consensus_babe::BabeApi;
 use sc_rpc::SubscriptionTaskExecutor;
 use sp_transaction_pool::TransactionPool;
-use sc_client_api::AuxStore;
+use sc_client_api::{AuxStore, BlockBackend};
 
 /// Light client extra dependencies.
 pub struct LightDeps<C, F, P> {
@@ -112,7 +112,7 @@ pub type IoHandler = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 pub fn create_full<C, P, SC, B>(
 	deps: FullDeps<C, P, SC, B>,
 ) -> jsonrpc_core::IoHandler<sc_rpc_api::Metadata> where
-	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore +
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore + BlockBackend<Block> +
 		HeaderMetadata<Block, Error=BlockChainError> + Sync + Send + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
@@ -193,6 +193,10 @@ pub fn create_full<C, P, SC, B>(
 		)
 	);
 
+	io.extend_with(
+		sc_rpc::dev::DevApi::to_delegate(sc_rpc::dev::Dev::new(client.clone(), deny_unsafe))
+	);
+
 	io.extend_with(
 		sc_sync_state_rpc::SyncStateRpcApi::to_delegate(
 			sc_sync_state_rpc::SyncStateRpcHandler::new(
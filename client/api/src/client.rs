@@ -18,7 +18,7 @@
 
 //! A set of APIs supported by the client along with their primitives.
 
-use std::{fmt, collections::HashSet, sync::Arc, convert::TryFrom};
+use std::{fmt, collections::{HashMap, HashSet}, sync::Arc, convert::TryFrom};
 use sp_core::storage::StorageKey;
 use sp_runtime::{
 	traits::{Block as BlockT, NumberFor},
@@ -28,7 +28,7 @@ use sp_runtime::{
 use sp_consensus::BlockOrigin;
 
 use crate::blockchain::Info;
-use crate::notifications::StorageEventStream;
+use crate::notifications::{StorageEventStream, StorageKeyFilter};
 use sp_utils::mpsc::TracingUnboundedReceiver;
 use sp_blockchain;
 
@@ -74,6 +74,17 @@ pub trait BlockchainEvents<Block: BlockT> {
 		filter_keys: Option<&[StorageKey]>,
 		child_filter_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
 	) -> sp_blockchain::Result<StorageEventStream<Block::Hash>>;
+
+	/// Get a stream of the storage changes passing `filter`, and of the changes of the child
+	/// tries in `child_filters` passing their filter.
+	///
+	/// Passing `None` as a filter subscribes to all changes of the (child) trie. Child tries are
+	/// identified by their unprefixed storage key.
+	fn storage_changes_notification_stream_filtered(
+		&self,
+		filter: Option<StorageKeyFilter>,
+		child_filters: Option<HashMap<StorageKey, Option<StorageKeyFilter>>>,
+	) -> sp_blockchain::Result<StorageEventStream<Block::Hash>>;
 }
 
 /// Interface for fetching block data.
@@ -109,6 +120,16 @@ pub trait BlockBackend<Block: BlockT> {
 	fn has_indexed_transaction(&self, hash: &Block::Hash) -> sp_blockchain::Result<bool> {
 		Ok(self.indexed_transaction(hash)?.is_some())
 	}
+
+	/// Get all indexed transactions for a block,
+	/// including renewed transactions.
+	///
+	/// Note that this will only fetch transactions
+	/// that are indexed by the runtime with `storage_index_transaction`.
+	fn block_indexed_body(
+		&self,
+		id: &BlockId<Block>,
+	) -> sp_blockchain::Result<Option<Vec<Vec<u8>>>>;
 }
 
 /// Provide a list of potential uncle headers for a given block.
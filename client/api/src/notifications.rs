@@ -24,18 +24,44 @@ use std::{
 };
 
 use fnv::{FnvHashSet, FnvHashMap};
+use futures::channel::mpsc;
 use sp_core::storage::{StorageKey, StorageData};
 use sp_runtime::traits::Block as BlockT;
-use sp_utils::mpsc::{TracingUnboundedSender, TracingUnboundedReceiver, tracing_unbounded};
 use prometheus_endpoint::{Registry, CounterVec, Opts, U64, register};
 
+/// Maximum number of change sets buffered for a single listener.
+///
+/// A listener that falls this far behind is dropped, which ends its stream.
+const LISTENER_BUFFER_SIZE: usize = 1024;
+
+/// Filter of the keys of a trie that a listener is interested in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageKeyFilter {
+	/// Keys that are matched exactly.
+	pub keys: HashSet<StorageKey>,
+	/// Prefixes of the keys to match.
+	pub prefixes: Vec<StorageKey>,
+}
+
+impl StorageKeyFilter {
+	/// Create a filter matching exactly the given `keys`.
+	pub fn keys(keys: impl IntoIterator<Item = StorageKey>) -> Self {
+		StorageKeyFilter { keys: keys.into_iter().collect(), prefixes: Vec::new() }
+	}
+
+	/// Returns whether `key` passes the filter.
+	pub fn matches(&self, key: &StorageKey) -> bool {
+		self.keys.contains(key) || self.prefixes.iter().any(|prefix| key.0.starts_with(&prefix.0))
+	}
+}
+
 /// Storage change set
 #[derive(Debug)]
 pub struct StorageChangeSet {
 	changes: Arc<Vec<(StorageKey, Option<StorageData>)>>,
 	child_changes: Arc<Vec<(StorageKey, Vec<(StorageKey, Option<StorageData>)>)>>,
-	filter: Option<HashSet<StorageKey>>,
-	child_filters: Option<HashMap<StorageKey, Option<HashSet<StorageKey>>>>,
+	filter: Option<StorageKeyFilter>,
+	child_filters: Option<HashMap<StorageKey, Option<StorageKeyFilter>>>,
 }
 
 impl StorageChangeSet {
@@ -45,7 +71,7 @@ impl StorageChangeSet {
 		let top = self.changes
 			.iter()
 			.filter(move |&(key, _)| match self.filter {
-				Some(ref filter) => filter.contains(key),
+				Some(ref filter) => filter.matches(key),
 				None => true,
 			})
 			.map(move |(k,v)| (None, k, v.as_ref()));
@@ -56,7 +82,7 @@ impl StorageChangeSet {
 					cf.get(sk).map(|filter| changes
 							.iter()
 							.filter(move |&(key, _)| match filter {
-								Some(ref filter) => filter.contains(key),
+								Some(ref filter) => filter.matches(key),
 								None => true,
 							})
 							.map(move |(k,v)| (Some(sk), k, v.as_ref()))
@@ -69,27 +95,107 @@ impl StorageChangeSet {
 }
 
 /// Type that implements `futures::Stream` of storage change events.
-pub type StorageEventStream<H> = TracingUnboundedReceiver<(H, StorageChangeSet)>;
+pub type StorageEventStream<H> = mpsc::Receiver<(H, StorageChangeSet)>;
 
 type SubscriberId = u64;
 
 type SubscribersGauge = CounterVec<U64>;
 
+/// Subscribers of a trie, by the filter they registered.
+#[derive(Debug, Default)]
+struct Listeners {
+	/// Subscribers of all the changes.
+	wildcards: FnvHashSet<SubscriberId>,
+	/// Subscribers of the changes of a key.
+	keys: HashMap<StorageKey, FnvHashSet<SubscriberId>>,
+	/// Subscribers of the changes of all the keys with a prefix.
+	prefixes: HashMap<StorageKey, FnvHashSet<SubscriberId>>,
+}
+
+impl Listeners {
+	fn is_empty(&self) -> bool {
+		self.wildcards.is_empty() && self.keys.is_empty() && self.prefixes.is_empty()
+	}
+
+	/// Add the subscribers interested in changes of `key` to `subscribers`.
+	///
+	/// Returns whether there are any.
+	fn collect(&self, key: &StorageKey, subscribers: &mut FnvHashSet<SubscriberId>) -> bool {
+		let mut interested = !self.wildcards.is_empty();
+		subscribers.extend(self.wildcards.iter());
+		if let Some(listeners) = self.keys.get(key) {
+			subscribers.extend(listeners.iter());
+			interested = true;
+		}
+		for (prefix, listeners) in self.prefixes.iter() {
+			if key.0.starts_with(&prefix.0) {
+				subscribers.extend(listeners.iter());
+				interested = true;
+			}
+		}
+		interested
+	}
+
+	fn insert(&mut self, subscriber: SubscriberId, filter: &Option<StorageKeyFilter>) {
+		match filter {
+			None => {
+				self.wildcards.insert(subscriber);
+			},
+			Some(filter) => {
+				for key in filter.keys.iter() {
+					self.keys.entry(key.clone()).or_default().insert(subscriber);
+				}
+				for prefix in filter.prefixes.iter() {
+					self.prefixes.entry(prefix.clone()).or_default().insert(subscriber);
+				}
+			},
+		}
+	}
+
+	fn remove(&mut self, subscriber: &SubscriberId, filter: &Option<StorageKeyFilter>) {
+		fn remove_from<'a>(
+			listeners: &mut HashMap<StorageKey, FnvHashSet<SubscriberId>>,
+			subscriber: &SubscriberId,
+			keys: impl Iterator<Item = &'a StorageKey>,
+		) {
+			for key in keys {
+				let remove_key = match listeners.get_mut(key) {
+					Some(ref mut set) => {
+						set.remove(subscriber);
+						set.is_empty()
+					},
+					None => false,
+				};
+
+				if remove_key {
+					listeners.remove(key);
+				}
+			}
+		}
+
+		match filter {
+			None => {
+				self.wildcards.remove(subscriber);
+			},
+			Some(filter) => {
+				remove_from(&mut self.keys, subscriber, filter.keys.iter());
+				remove_from(&mut self.prefixes, subscriber, filter.prefixes.iter());
+			},
+		}
+	}
+}
+
 /// Manages storage listeners.
 #[derive(Debug)]
 pub struct StorageNotifications<Block: BlockT> {
 	metrics: Option<SubscribersGauge>,
 	next_id: SubscriberId,
-	wildcard_listeners: FnvHashSet<SubscriberId>,
-	listeners: HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-	child_listeners: HashMap<StorageKey, (
-		HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-		FnvHashSet<SubscriberId>
-	)>,
+	listeners: Listeners,
+	child_listeners: HashMap<StorageKey, Listeners>,
 	sinks: FnvHashMap<SubscriberId, (
-		TracingUnboundedSender<(Block::Hash, StorageChangeSet)>,
-		Option<HashSet<StorageKey>>,
-		Option<HashMap<StorageKey, Option<HashSet<StorageKey>>>>,
+		mpsc::Sender<(Block::Hash, StorageChangeSet)>,
+		Option<StorageKeyFilter>,
+		Option<HashMap<StorageKey, Option<StorageKeyFilter>>>,
 	)>,
 }
 
@@ -98,7 +204,6 @@ impl<Block: BlockT> Default for StorageNotifications<Block> {
 		Self {
 			metrics: Default::default(),
 			next_id: Default::default(),
-			wildcard_listeners: Default::default(),
 			listeners: Default::default(),
 			child_listeners: Default::default(),
 			sinks: Default::default(),
@@ -124,7 +229,6 @@ impl<Block: BlockT> StorageNotifications<Block> {
 		StorageNotifications {
 			metrics,
 			next_id: Default::default(),
-			wildcard_listeners: Default::default(),
 			listeners: Default::default(),
 			child_listeners: Default::default(),
 			sinks: Default::default(),
@@ -134,6 +238,8 @@ impl<Block: BlockT> StorageNotifications<Block> {
 	///
 	/// Note the changes are going to be filtered by listener's filter key.
 	/// In fact no event might be sent if clients are not interested in the changes.
+	///
+	/// Listeners whose buffer is full are dropped.
 	pub fn trigger(
 		&mut self,
 		hash: &Block::Hash,
@@ -142,46 +248,29 @@ impl<Block: BlockT> StorageNotifications<Block> {
 			Item=(Vec<u8>, impl Iterator<Item=(Vec<u8>, Option<Vec<u8>>)>)
 		>,
 	) {
-
-		let has_wildcard = !self.wildcard_listeners.is_empty();
-
 		// early exit if no listeners
-		if !has_wildcard && self.listeners.is_empty() && self.child_listeners.is_empty() {
+		if self.listeners.is_empty() && self.child_listeners.is_empty() {
 			return;
 		}
 
-		let mut subscribers = self.wildcard_listeners.clone();
+		let mut subscribers = FnvHashSet::default();
 		let mut changes = Vec::new();
 		let mut child_changes = Vec::new();
 
 		// Collect subscribers and changes
 		for (k, v) in changeset {
 			let k = StorageKey(k);
-			let listeners = self.listeners.get(&k);
-
-			if let Some(ref listeners) = listeners {
-				subscribers.extend(listeners.iter());
-			}
-
-			if has_wildcard || listeners.is_some() {
+			if self.listeners.collect(&k, &mut subscribers) {
 				changes.push((k, v.map(StorageData)));
 			}
 		}
 		for (sk, changeset) in child_changeset {
 			let sk = StorageKey(sk);
-			if let Some((cl, cw)) = self.child_listeners.get(&sk) {
+			if let Some(listeners) = self.child_listeners.get(&sk) {
 				let mut changes = Vec::new();
 				for (k, v) in changeset {
 					let k = StorageKey(k);
-					let listeners = cl.get(&k);
-
-					if let Some(ref listeners) = listeners {
-						subscribers.extend(listeners.iter());
-					}
-
-					subscribers.extend(cw.iter());
-
-					if !cw.is_empty() || listeners.is_some() {
+					if listeners.collect(&k, &mut subscribers) {
 						changes.push((k, v.map(StorageData)));
 					}
 				}
@@ -201,16 +290,25 @@ impl<Block: BlockT> StorageNotifications<Block> {
 		// Trigger the events
 
 		let to_remove = self.sinks
-			.iter()
-			.filter_map(|(subscriber, &(ref sink, ref filter, ref child_filters))| {
+			.iter_mut()
+			.filter_map(|(subscriber, (sink, filter, child_filters))| {
 				let should_remove = {
 					if subscribers.contains(subscriber) {
-						sink.unbounded_send((hash.clone(), StorageChangeSet {
+						let result = sink.try_send((hash.clone(), StorageChangeSet {
 							changes: changes.clone(),
 							child_changes: child_changes.clone(),
 							filter: filter.clone(),
 							child_filters: child_filters.clone(),
-						})).is_err()
+						}));
+						if let Err(ref e) = result {
+							if e.is_full() {
+								log::debug!(
+									"Dropping storage notification subscriber {}: buffer is full",
+									subscriber,
+								);
+							}
+						}
+						result.is_err()
 					} else {
 						sink.is_closed()
 					}
@@ -228,56 +326,16 @@ impl<Block: BlockT> StorageNotifications<Block> {
 		}
 	}
 
-	fn remove_subscriber_from(
-		subscriber: &SubscriberId,
-		filters: &Option<HashSet<StorageKey>>,
-		listeners: &mut HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-		wildcards: &mut FnvHashSet<SubscriberId>,
-	){
-		match filters {
-			None => {
-				wildcards.remove(subscriber);
-			},
-			Some(filters) => {
-
-				for key in filters.iter() {
-					let remove_key = match listeners.get_mut(key) {
-						Some(ref mut set) => {
-							set.remove(subscriber);
-							set.is_empty()
-						},
-						None => false,
-					};
-
-					if remove_key {
-						listeners.remove(key);
-					}
-				}
-			}
-		}
-	}
-
 	fn remove_subscriber(&mut self, subscriber: SubscriberId) {
-		if let Some((_, filters, child_filters)) = self.sinks.remove(&subscriber) {
-			Self::remove_subscriber_from(
-				&subscriber,
-				&filters,
-				&mut self.listeners,
-				&mut self.wildcard_listeners,
-			);
+		if let Some((_, filter, child_filters)) = self.sinks.remove(&subscriber) {
+			self.listeners.remove(&subscriber, &filter);
 			if let Some(child_filters) = child_filters.as_ref() {
-				for (c_key, filters) in child_filters {
-
-					if let Some((listeners, wildcards)) = self.child_listeners.get_mut(&c_key) {
-						Self::remove_subscriber_from(
-							&subscriber,
-							&filters,
-							&mut *listeners,
-							&mut *wildcards,
-						);
-
-						if listeners.is_empty() && wildcards.is_empty() {
-							self.child_listeners.remove(&c_key);
+				for (c_key, filter) in child_filters {
+					if let Some(listeners) = self.child_listeners.get_mut(c_key) {
+						listeners.remove(&subscriber, filter);
+
+						if listeners.is_empty() {
+							self.child_listeners.remove(c_key);
 						}
 					}
 				}
@@ -288,63 +346,47 @@ impl<Block: BlockT> StorageNotifications<Block> {
 		}
 	}
 
-	fn listen_from(
-		current_id: SubscriberId,
-		filter_keys: &Option<impl AsRef<[StorageKey]>>,
-		listeners: &mut HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-		wildcards: &mut FnvHashSet<SubscriberId>,
-	) -> Option<HashSet<StorageKey>>
-	{
-		match filter_keys {
-			None => {
-				wildcards.insert(current_id);
-				None
-			},
-			Some(keys) => Some(keys.as_ref().iter().map(|key| {
-				listeners
-					.entry(key.clone())
-					.or_insert_with(Default::default)
-					.insert(current_id);
-				key.clone()
-			}).collect())
-		}
-	}
-
 	/// Start listening for particular storage keys.
 	pub fn listen(
 		&mut self,
 		filter_keys: Option<&[StorageKey]>,
 		filter_child_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
 	) -> StorageEventStream<Block::Hash> {
-		self.next_id += 1;
-		let current_id = self.next_id;
-
-		// add subscriber for every key
-		let keys = Self::listen_from(
-			current_id,
-			&filter_keys,
-			&mut self.listeners,
-			&mut self.wildcard_listeners,
-		);
-		let child_keys = filter_child_keys.map(|filter_child_keys| {
+		let filter = filter_keys.map(|keys| StorageKeyFilter::keys(keys.iter().cloned()));
+		let child_filters = filter_child_keys.map(|filter_child_keys| {
 			filter_child_keys.iter().map(|(c_key, o_keys)| {
-				let (c_listeners, c_wildcards) = self.child_listeners
-					.entry(c_key.clone())
-					.or_insert_with(Default::default);
-
-				(c_key.clone(), Self::listen_from(
-					current_id,
-					o_keys,
-					&mut *c_listeners,
-					&mut *c_wildcards,
-				))
+				(c_key.clone(), o_keys.clone().map(StorageKeyFilter::keys))
 			}).collect()
 		});
 
+		self.listen_filtered(filter, child_filters)
+	}
+
+	/// Start listening for the storage keys passing `filter`, and the keys of the child tries
+	/// passing their filter in `child_filters`.
+	///
+	/// `None` as a filter subscribes to all changes of the trie. Child tries are identified by
+	/// their unprefixed storage key.
+	pub fn listen_filtered(
+		&mut self,
+		filter: Option<StorageKeyFilter>,
+		child_filters: Option<HashMap<StorageKey, Option<StorageKeyFilter>>>,
+	) -> StorageEventStream<Block::Hash> {
+		self.next_id += 1;
+		let current_id = self.next_id;
+
+		// add subscriber for every key
+		self.listeners.insert(current_id, &filter);
+		for (c_key, filter) in child_filters.iter().flatten() {
+			self.child_listeners
+				.entry(c_key.clone())
+				.or_default()
+				.insert(current_id, filter);
+		}
 
 		// insert sink
-		let (tx, rx) = tracing_unbounded("mpsc_storage_notification_items");
-		self.sinks.insert(current_id, (tx, keys, child_keys));
+		let (tx, rx) = mpsc::channel(LISTENER_BUFFER_SIZE);
+		self.sinks.insert(current_id, (tx, filter, child_filters));
 
 		if let Some(m) = self.metrics.as_ref() {
 			m.with_label_values(&[&"added"]).inc();
@@ -490,8 +532,8 @@ mod tests {
 			let _recv4 = futures::executor::block_on_stream(
 				notifications.listen(None, Some(&child_filter))
 			);
-			assert_eq!(notifications.listeners.len(), 2);
-			assert_eq!(notifications.wildcard_listeners.len(), 2);
+			assert_eq!(notifications.listeners.keys.len(), 2);
+			assert_eq!(notifications.listeners.wildcards.len(), 2);
 			assert_eq!(notifications.child_listeners.len(), 1);
 		}
 
@@ -504,8 +546,7 @@ mod tests {
 		notifications.trigger(&Hash::from_low_u64_be(1), changeset.into_iter(), c_changeset);
 
 		// then
-		assert_eq!(notifications.listeners.len(), 0);
-		assert_eq!(notifications.wildcard_listeners.len(), 0);
+		assert!(notifications.listeners.is_empty());
 		assert_eq!(notifications.child_listeners.len(), 0);
 	}
 
@@ -526,4 +567,71 @@ mod tests {
 		// then
 		assert_eq!(recv.next(), None);
 	}
+
+	#[test]
+	fn should_notify_prefix_listeners() {
+		// given
+		let mut notifications = StorageNotifications::<Block>::default();
+		let filter = StorageKeyFilter {
+			keys: Default::default(),
+			prefixes: vec![StorageKey(vec![1])],
+		};
+		let child_filter = StorageKeyFilter {
+			keys: Some(StorageKey(vec![7])).into_iter().collect(),
+			prefixes: vec![StorageKey(vec![5, 1])],
+		};
+		let mut recv = futures::executor::block_on_stream(notifications.listen_filtered(
+			Some(filter),
+			Some(Some((StorageKey(vec![4]), Some(child_filter))).into_iter().collect()),
+		));
+
+		// when
+		let changeset = vec![
+			(vec![1], Some(vec![3])),
+			(vec![1, 2], None),
+			(vec![2, 1], None),
+		];
+		let c_changeset_1 = vec![
+			(vec![5, 1, 1], Some(vec![4])),
+			(vec![5, 2], None),
+			(vec![7], None),
+		];
+		let c_changeset = vec![(vec![4], c_changeset_1)];
+		notifications.trigger(
+			&Hash::from_low_u64_be(1),
+			changeset.into_iter(),
+			c_changeset.into_iter().map(|(a,b)| (a, b.into_iter())),
+		);
+
+		// then
+		let (hash, changes) = recv.next().unwrap();
+		assert_eq!(hash, Hash::from_low_u64_be(1));
+		assert_eq!(changes.iter().collect::<Vec<_>>(), vec![
+			(None, &StorageKey(vec![1]), Some(&StorageData(vec![3]))),
+			(None, &StorageKey(vec![1, 2]), None),
+			(Some(&StorageKey(vec![4])), &StorageKey(vec![5, 1, 1]), Some(&StorageData(vec![4]))),
+			(Some(&StorageKey(vec![4])), &StorageKey(vec![7]), None),
+		]);
+	}
+
+	#[test]
+	fn should_drop_listeners_that_fall_behind() {
+		// given
+		let mut notifications = StorageNotifications::<Block>::default();
+		let mut recv = notifications.listen(None, None);
+
+		// when
+		for i in 0..=LISTENER_BUFFER_SIZE as u64 + 1 {
+			let changeset = vec![(vec![1], Some(vec![2]))];
+			let c_changeset = empty::<(_, Empty<_>)>();
+			notifications.trigger(&Hash::from_low_u64_be(i), changeset.into_iter(), c_changeset);
+		}
+
+		// then
+		assert!(notifications.sinks.is_empty());
+		assert!(notifications.listeners.is_empty());
+		recv.close();
+		let received = futures::executor::block_on_stream(recv).count();
+		assert!(received <= LISTENER_BUFFER_SIZE + 1);
+	}
 }
@@ -21,7 +21,7 @@ use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT},
 };
-use crate::{StorageProof, ChangesProof};
+use crate::{StorageProof, CompactProof, ChangesProof};
 use sp_storage::{ChildInfo, StorageKey, PrefixedStorageKey};
 
 /// Interface for providing block proving utilities.
@@ -70,4 +70,26 @@ pub trait ProofProvider<Block: BlockT> {
 		storage_key: Option<&PrefixedStorageKey>,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<ChangesProof<Block::Header>>;
+
+	/// Reads the storage values that follow `start_key` at a given block, returning a compact
+	/// read proof together with the number of keys included.
+	///
+	/// An empty `start_key` starts at the beginning of the state. Keys are added until the proof
+	/// reaches `size_limit` bytes. Child tries are not included.
+	fn read_proof_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[u8],
+		size_limit: usize,
+	) -> sp_blockchain::Result<(CompactProof, u32)>;
+
+	/// Verifies a proof generated by [`Self::read_proof_collection`] against the given state root.
+	///
+	/// Returns the proven key-value pairs and whether they reach the end of the state.
+	fn verify_range_proof(
+		&self,
+		root: Block::Hash,
+		proof: CompactProof,
+		start_key: &[u8],
+	) -> sp_blockchain::Result<(Vec<(Vec<u8>, Vec<u8>)>, bool)>;
 }
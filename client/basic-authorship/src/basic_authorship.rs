@@ -28,7 +28,7 @@ use sp_core::traits::SpawnNamed;
 use sp_inherents::InherentData;
 use log::{error, info, debug, trace, warn};
 use sp_runtime::{
-	generic::BlockId,
+	generic::BlockId, Percent, SaturatedConversion,
 	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, DigestFor, BlakeTwo256},
 };
 use sp_transaction_pool::{TransactionPool, InPoolTransaction};
@@ -51,6 +51,12 @@ use sc_proposer_metrics::MetricsLink as PrometheusMetrics;
 /// transferred to other nodes.
 pub const DEFAULT_BLOCK_SIZE_LIMIT: usize = 4 * 1024 * 1024 + 512;
 
+/// Default soft deadline used by [`Proposer`], as a fraction of the time available to push
+/// transactions.
+///
+/// Can be overwritten by [`ProposerFactory::set_soft_deadline`].
+const DEFAULT_SOFT_DEADLINE_PERCENT: Percent = Percent::from_percent(50);
+
 /// Proposer factory.
 pub struct ProposerFactory<A, B, C, PR> {
 	spawn_handle: Box<dyn SpawnNamed>,
@@ -65,9 +71,17 @@ pub struct ProposerFactory<A, B, C, PR> {
 	/// If no `block_size_limit` is passed to [`Proposer::propose`], this block size limit will be
 	/// used.
 	default_block_size_limit: usize,
+	/// The soft deadline, as a fraction of the time available to push transactions.
+	///
+	/// See [`ProposerFactory::set_soft_deadline`].
+	soft_deadline_percent: Percent,
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
+	/// The maximum size of the storage proof (PoV) of a block.
+	///
+	/// Only used when proof recording is enabled.
+	pov_size_limit: Option<usize>,
 	/// phantom member to pin the `Backend`/`ProofRecording` type.
 	_phantom: PhantomData<(B, PR)>,
 }
@@ -88,9 +102,11 @@ impl<A, B, C> ProposerFactory<A, B, C, DisableProofRecording> {
 			transaction_pool,
 			metrics: PrometheusMetrics::new(prometheus),
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
+			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
+			pov_size_limit: None,
 			_phantom: PhantomData,
 		}
 	}
@@ -116,8 +132,10 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 			transaction_pool,
 			metrics: PrometheusMetrics::new(prometheus),
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
+			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
+			pov_size_limit: None,
 			_phantom: PhantomData,
 		}
 	}
@@ -126,6 +144,14 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 	pub fn disable_proof_in_block_size_estimation(&mut self) {
 		self.include_proof_in_block_size_estimation = false;
 	}
+
+	/// Set the maximum size in bytes of the storage proof recorded while building a block.
+	///
+	/// Once the estimated size of the recorded proof reaches this limit, no further
+	/// transactions are pushed into the block, independent of the remaining block weight.
+	pub fn set_pov_size_limit(&mut self, limit: usize) {
+		self.pov_size_limit = Some(limit);
+	}
 }
 
 impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
@@ -138,6 +164,18 @@ impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
 	pub fn set_default_block_size_limit(&mut self, limit: usize) {
 		self.default_block_size_limit = limit;
 	}
+
+	/// Set the soft deadline, as a fraction of the time available to push transactions.
+	///
+	/// Once the block is full, either by weight or by size, a few more transactions
+	/// (`MAX_SKIPPED_TRANSACTIONS`) are tried in case a smaller one still fits. Until the soft
+	/// deadline is reached, the proposer keeps trying beyond that number.
+	///
+	/// A low value limits how hard the proposer tries to fill the block, a high value lets
+	/// transactions that never fit use most of the proposal time. Defaults to 50%.
+	pub fn set_soft_deadline(&mut self, percent: Percent) {
+		self.soft_deadline_percent = percent;
+	}
 }
 
 impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
@@ -171,9 +209,11 @@ impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
 			now,
 			metrics: self.metrics.clone(),
 			default_block_size_limit: self.default_block_size_limit,
+			soft_deadline_percent: self.soft_deadline_percent,
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			pov_size_limit: self.pov_size_limit,
 		};
 
 		proposer
@@ -215,7 +255,9 @@ pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
 	now: Box<dyn Fn() -> time::Instant + Send + Sync>,
 	metrics: PrometheusMetrics,
 	default_block_size_limit: usize,
+	soft_deadline_percent: Percent,
 	include_proof_in_block_size_estimation: bool,
+	pov_size_limit: Option<usize>,
 	telemetry: Option<TelemetryHandle>,
 	_phantom: PhantomData<(B, PR)>,
 }
@@ -319,10 +361,15 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 		let mut skipped = 0;
 		let mut unqueue_invalid = Vec::new();
 
+		let now = (self.now)();
+		let left = deadline.saturating_duration_since(now);
+		let left_micros: u64 = left.as_micros().saturated_into();
+		let soft_deadline = now + time::Duration::from_micros(
+			self.soft_deadline_percent.mul_floor(left_micros),
+		);
+
 		let mut t1 = self.transaction_pool.ready_at(self.parent_number).fuse();
-		let mut t2 = futures_timer::Delay::new(
-			deadline.saturating_duration_since((self.now)()) / 8,
-		).fuse();
+		let mut t2 = futures_timer::Delay::new(left / 8).fuse();
 
 		let pending_iterator = select! {
 			res = t1 => res,
@@ -352,6 +399,19 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 				break;
 			}
 
+			if let Some(pov_size_limit) = self.pov_size_limit {
+				let proof_size = block_builder.estimate_proof_size().unwrap_or(0);
+				if proof_size >= pov_size_limit {
+					debug!(
+						"Reached PoV size limit of `{}` (estimated proof size `{}`), \
+						 proceeding with proposing.",
+						pov_size_limit,
+						proof_size,
+					);
+					break;
+				}
+			}
+
 			let pending_tx_data = pending_tx.data().clone();
 			let pending_tx_hash = pending_tx.hash().clone();
 
@@ -367,6 +427,12 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 						MAX_SKIPPED_TRANSACTIONS - skipped,
 					);
 					continue;
+				} else if (self.now)() < soft_deadline {
+					debug!(
+						"Transaction would overflow the block size limit, \
+						 but the soft deadline is not reached yet, trying more transactions.",
+					);
+					continue;
 				} else {
 					debug!("Reached block size limit, proceeding with proposing.");
 					hit_block_size_limit = true;
@@ -388,6 +454,11 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 							"Block seems full, but will try {} more transactions before quitting.",
 							MAX_SKIPPED_TRANSACTIONS - skipped,
 						);
+					} else if (self.now)() < soft_deadline {
+						debug!(
+							"Block seems full, but the soft deadline is not reached yet, \
+							 trying more transactions.",
+						);
 					} else {
 						debug!("Block is full, proceed with proposing.");
 						break;
@@ -418,10 +489,15 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 
 		let (block, storage_changes, proof) = block_builder.build()?.into_inner();
 
+		let time_used = (self.now)().saturating_duration_since(now);
 		self.metrics.report(
 			|metrics| {
 				metrics.number_of_transactions.set(block.extrinsics().len() as u64);
 				metrics.block_constructed.observe(block_timer.elapsed().as_secs_f64());
+				if left > time::Duration::from_secs(0) {
+					metrics.proposal_time_used
+						.observe(time_used.as_secs_f64() / left.as_secs_f64());
+				}
 			}
 		);
 
@@ -840,5 +916,86 @@ mod tests {
 		// The block limit didn't changed, but we now include the proof in the estimation of the
 		// block size and thus, one less transaction should fit into the limit.
 		assert_eq!(block.extrinsics().len(), extrinsics_num - 2);
+
+		let mut proposer_factory = ProposerFactory::with_proof_recording(
+			spawner.clone(),
+			client.clone(),
+			txpool.clone(),
+			None,
+			None,
+		);
+		// Any proof recorded while initializing the block exceeds this limit.
+		proposer_factory.set_pov_size_limit(1);
+
+		let proposer = block_on(proposer_factory.init(&genesis_header)).unwrap();
+
+		let block = block_on(
+			proposer.propose(Default::default(), Default::default(), deadline, None)
+		).map(|r| r.block).unwrap();
+
+		// The PoV size limit is hit before the first transaction is pushed.
+		assert_eq!(block.extrinsics().len(), 0);
+	}
+
+	#[test]
+	fn should_keep_trying_transactions_until_soft_deadline() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+		let genesis_header = client.header(&BlockId::Number(0u64))
+			.expect("header get error")
+			.expect("there should be header");
+
+		// More transactions than `MAX_SKIPPED_TRANSACTIONS` that never fit, then one that does.
+		let small = Extrinsic::IncludeData(vec![0xff; 10]);
+		let extrinsics = (0..10)
+			.map(|v| Extrinsic::IncludeData(vec![v as u8; 1000]))
+			.chain(std::iter::once(small.clone()))
+			.collect::<Vec<_>>();
+		let block_limit = genesis_header.encoded_size()
+			+ Vec::<Extrinsic>::new().encoded_size()
+			+ small.encoded_size()
+			+ 100;
+
+		block_on(txpool.submit_at(&BlockId::number(0), SOURCE, extrinsics)).unwrap();
+		block_on(txpool.maintain(chain_event(genesis_header.clone())));
+
+		let mut proposer_factory = ProposerFactory::new(
+			spawner.clone(),
+			client.clone(),
+			txpool.clone(),
+			None,
+			None,
+		);
+		let propose_block = |proposer_factory: &mut ProposerFactory<_, _, _, _>| {
+			let cell = Mutex::new(time::Instant::now());
+			let proposer = proposer_factory.init_with_now(
+				&genesis_header,
+				Box::new(move || {
+					let mut value = cell.lock();
+					let old = *value;
+					*value = old + time::Duration::from_secs(1);
+					old
+				}),
+			);
+			// Give it enough time so that the deadline is never reached.
+			let deadline = time::Duration::from_secs(900);
+			block_on(
+				proposer.propose(Default::default(), Default::default(), deadline, Some(block_limit))
+			).map(|r| r.block).unwrap()
+		};
+
+		// The small transaction is found before the soft deadline.
+		assert_eq!(propose_block(&mut proposer_factory).extrinsics().len(), 1);
+
+		// Without a soft deadline, the proposer gives up after `MAX_SKIPPED_TRANSACTIONS`.
+		proposer_factory.set_soft_deadline(Percent::from_percent(0));
+		assert_eq!(propose_block(&mut proposer_factory).extrinsics().len(), 0);
 	}
 }
@@ -276,6 +276,21 @@ where
 		}).map_err(|e| Error::Application(Box::new(e)))
 	}
 
+	/// Returns the storage proof recorded so far, if proof recording is enabled.
+	///
+	/// The proof covers all trie nodes accessed while initializing the block and applying the
+	/// extrinsics pushed until now.
+	pub fn recorded_proof(&self) -> Option<StorageProof> {
+		self.api.proof_recorder().map(|pr| pr.to_storage_proof())
+	}
+
+	/// Estimate the encoded size of the storage proof recorded so far.
+	///
+	/// Returns `None` when proof recording is disabled.
+	pub fn estimate_proof_size(&self) -> Option<usize> {
+		self.api.proof_recorder().map(|pr| pr.estimate_encoded_size())
+	}
+
 	/// Estimate the size of the block in the current state.
 	///
 	/// If `include_proof` is `true`, the estimated size of the storage proof will be added
@@ -284,7 +299,7 @@ where
 		let size = self.estimated_header_size + self.extrinsics.encoded_size();
 
 		if include_proof {
-			size + self.api.proof_recorder().map(|pr| pr.estimate_encoded_size()).unwrap_or(0)
+			size + self.estimate_proof_size().unwrap_or(0)
 		} else {
 			size
 		}
@@ -327,4 +342,36 @@ mod tests {
 				.contains("Database missing expected key"),
 		);
 	}
+
+	#[test]
+	fn recorded_proof_is_only_available_with_proof_recording() {
+		let builder = substrate_test_runtime_client::TestClientBuilder::new();
+		let backend = builder.backend();
+		let client = builder.build();
+
+		let block_builder = BlockBuilder::new(
+			&client,
+			client.info().best_hash,
+			client.info().best_number,
+			RecordProof::No,
+			Default::default(),
+			&*backend,
+		).unwrap();
+
+		assert!(block_builder.recorded_proof().is_none());
+		assert!(block_builder.estimate_proof_size().is_none());
+
+		let block_builder = BlockBuilder::new(
+			&client,
+			client.info().best_hash,
+			client.info().best_number,
+			RecordProof::Yes,
+			Default::default(),
+			&*backend,
+		).unwrap();
+
+		let proof_size = block_builder.estimate_proof_size().expect("Proof is recorded");
+		assert!(proof_size > 0);
+		assert!(!block_builder.recorded_proof().expect("Proof is recorded").is_empty());
+	}
 }
@@ -19,9 +19,14 @@
 //! Substrate chain configurations.
 #![warn(missing_docs)]
 
-use std::{borrow::Cow, fs::File, path::PathBuf, sync::Arc, collections::HashMap};
+use std::{
+	borrow::Cow, fs::File, path::{Path, PathBuf}, sync::Arc, collections::{BTreeMap, HashMap},
+};
 use serde::{Serialize, Deserialize};
-use sp_core::storage::{StorageKey, StorageData, ChildInfo, Storage, StorageChild};
+use sp_core::{
+	storage::{StorageKey, StorageData, ChildInfo, Storage, StorageChild, well_known_keys},
+	Bytes, H256,
+};
 use sp_runtime::BuildStorage;
 use serde_json as json;
 use crate::{RuntimeGenesis, ChainType, extension::GetExtension, Properties};
@@ -60,35 +65,15 @@ impl<G: RuntimeGenesis> GenesisSource<G> {
 					.map_err(|e| format!("Error opening spec file: {}", e))?;
 				let genesis: GenesisContainer<G> = json::from_reader(file)
 					.map_err(|e| format!("Error parsing spec file: {}", e))?;
-				Ok(genesis.genesis)
+				genesis.genesis.with_code_sidecar(path.parent().unwrap_or_else(|| Path::new("")))
 			},
 			Self::Binary(buf) => {
 				let genesis: GenesisContainer<G> = json::from_reader(buf.as_ref())
 					.map_err(|e| format!("Error parsing embedded file: {}", e))?;
-				Ok(genesis.genesis)
+				genesis.genesis.with_code_sidecar(Path::new(""))
 			},
 			Self::Factory(f) => Ok(Genesis::Runtime(f())),
-			Self::Storage(storage) => {
-				let top = storage.top
-					.iter()
-					.map(|(k, v)| (StorageKey(k.clone()), StorageData(v.clone())))
-					.collect();
-
-				let children_default = storage.children_default
-					.iter()
-					.map(|(k, child)|
-						 (
-							 StorageKey(k.clone()),
-							 child.data
-								.iter()
-								.map(|(k, v)| (StorageKey(k.clone()), StorageData(v.clone())))
-								.collect()
-						 )
-					)
-					.collect();
-
-				Ok(Genesis::Raw(RawGenesis { top, children_default }))
-			},
+			Self::Storage(storage) => Ok(Genesis::Raw(RawGenesis::from_storage(storage.clone()))),
 		}
 	}
 }
@@ -97,7 +82,7 @@ impl<G: RuntimeGenesis, E> BuildStorage for ChainSpec<G, E> {
 	fn build_storage(&self) -> Result<Storage, String> {
 		match self.genesis.resolve()? {
 			Genesis::Runtime(gc) => gc.build_storage(),
-			Genesis::Raw(RawGenesis { top: map, children_default: children_map }) => Ok(Storage {
+			Genesis::Raw(RawGenesis { top: map, children_default: children_map, .. }) => Ok(Storage {
 				top: map.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
 				children_default: children_map.into_iter().map(|(storage_key, child_content)| {
 					let child_info = ChildInfo::new_default(storage_key.0.as_slice());
@@ -121,7 +106,8 @@ impl<G: RuntimeGenesis, E> BuildStorage for ChainSpec<G, E> {
 	}
 }
 
-pub type GenesisStorage = HashMap<StorageKey, StorageData>;
+/// Sorted by key, so that the same storage always results in the same spec.
+pub type GenesisStorage = BTreeMap<StorageKey, StorageData>;
 
 /// Raw storage content for genesis block.
 #[derive(Serialize, Deserialize)]
@@ -129,7 +115,40 @@ pub type GenesisStorage = HashMap<StorageKey, StorageData>;
 #[serde(deny_unknown_fields)]
 pub struct RawGenesis {
 	pub top: GenesisStorage,
-	pub children_default: HashMap<StorageKey, GenesisStorage>,
+	pub children_default: BTreeMap<StorageKey, GenesisStorage>,
+	/// The file the `:code` of `top` was moved to.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub code_sidecar: Option<CodeSidecar>,
+}
+
+impl RawGenesis {
+	fn from_storage(storage: Storage) -> Self {
+		let top = storage.top.into_iter()
+			.map(|(k, v)| (StorageKey(k), StorageData(v)))
+			.collect();
+		let children_default = storage.children_default.into_iter()
+			.map(|(sk, child)| (
+				StorageKey(sk),
+				child.data.into_iter()
+					.map(|(k, v)| (StorageKey(k), StorageData(v)))
+					.collect(),
+			))
+			.collect();
+
+		RawGenesis { top, children_default, code_sidecar: None }
+	}
+}
+
+/// A file holding the wasm code of a raw genesis, which is kept out of the spec to keep it
+/// readable and diff-able.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct CodeSidecar {
+	/// The path of the file, relative to the directory of the spec file.
+	pub path: PathBuf,
+	/// The blake2-256 hash of the code.
+	pub hash: H256,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -140,6 +159,26 @@ enum Genesis<G> {
 	Raw(RawGenesis),
 }
 
+impl<G> Genesis<G> {
+	/// Put the code of the code sidecar back into the raw storage, resolving the path of the
+	/// sidecar relative to `base`.
+	fn with_code_sidecar(self, base: &Path) -> Result<Self, String> {
+		match self {
+			Genesis::Raw(RawGenesis { mut top, children_default, code_sidecar: Some(sidecar) }) => {
+				let path = base.join(&sidecar.path);
+				let code = std::fs::read(&path)
+					.map_err(|e| format!("Error reading code sidecar {}: {}", path.display(), e))?;
+				if H256(sp_core::hashing::blake2_256(&code)) != sidecar.hash {
+					return Err(format!("Code sidecar {} doesn't match its hash", path.display()))
+				}
+				top.insert(StorageKey(well_known_keys::CODE.to_vec()), StorageData(code));
+				Ok(Genesis::Raw(RawGenesis { top, children_default, code_sidecar: None }))
+			},
+			genesis => Ok(genesis),
+		}
+	}
+}
+
 /// A configuration of a client. Does not include runtime storage initialization.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -160,6 +199,11 @@ struct ClientSpec<E> {
 	#[serde(skip_serializing)]
 	genesis: serde::de::IgnoredAny,
 	light_sync_state: Option<SerializableLightSyncState>,
+	/// Code substitutes that should be used for the on chain wasm.
+	///
+	/// Maps the hash of the block, from which on the substitute is used, to the wasm blob.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	code_substitutes: BTreeMap<String, Bytes>,
 }
 
 /// A type denoting empty extensions.
@@ -249,6 +293,7 @@ impl<G, E> ChainSpec<G, E> {
 			consensus_engine: (),
 			genesis: Default::default(),
 			light_sync_state: None,
+			code_substitutes: BTreeMap::new(),
 		};
 
 		ChainSpec {
@@ -303,22 +348,8 @@ struct JsonContainer<G, E> {
 impl<G: RuntimeGenesis, E: serde::Serialize + Clone + 'static> ChainSpec<G, E> {
 	fn json_container(&self, raw: bool) -> Result<JsonContainer<G, E>, String> {
 		let genesis = match (raw, self.genesis.resolve()?) {
-			(true, Genesis::Runtime(g)) => {
-				let storage = g.build_storage()?;
-				let top = storage.top.into_iter()
-					.map(|(k, v)| (StorageKey(k), StorageData(v)))
-					.collect();
-				let children_default = storage.children_default.into_iter()
-					.map(|(sk, child)| (
-						StorageKey(sk),
-						child.data.into_iter()
-							.map(|(k, v)| (StorageKey(k), StorageData(v)))
-							.collect(),
-					))
-					.collect();
-
-				Genesis::Raw(RawGenesis { top, children_default })
-			},
+			(true, Genesis::Runtime(g)) =>
+				Genesis::Raw(RawGenesis::from_storage(g.build_storage()?)),
 			(_, genesis) => genesis,
 		};
 		Ok(JsonContainer {
@@ -333,6 +364,32 @@ impl<G: RuntimeGenesis, E: serde::Serialize + Clone + 'static> ChainSpec<G, E> {
 		json::to_string_pretty(&container)
 			.map_err(|e| format!("Error generating spec json: {}", e))
 	}
+
+	/// Dump to raw json string, with the wasm code moved out of the genesis storage.
+	///
+	/// Returns the json and the code, which has to be written to `code_path`, relative to the
+	/// directory the json is written to. Loading the spec fails if the code isn't found there.
+	pub fn as_raw_json_with_code_sidecar(
+		&self,
+		code_path: &Path,
+	) -> Result<(String, Vec<u8>), String> {
+		let mut container = self.json_container(true)?;
+		let raw = match &mut container.genesis {
+			Genesis::Raw(raw) => raw,
+			Genesis::Runtime(_) => return Err("Raw genesis expected".into()),
+		};
+		let code = raw.top.remove(&StorageKey(well_known_keys::CODE.to_vec()))
+			.ok_or_else(|| "The genesis storage has no code".to_string())?
+			.0;
+		raw.code_sidecar = Some(CodeSidecar {
+			path: code_path.to_owned(),
+			hash: H256(sp_core::hashing::blake2_256(&code)),
+		});
+
+		let json = json::to_string_pretty(&container)
+			.map_err(|e| format!("Error generating spec json: {}", e))?;
+		Ok((json, code))
+	}
 }
 
 impl<G, E> crate::ChainSpec for ChainSpec<G, E>
@@ -380,6 +437,13 @@ where
 		ChainSpec::as_json(self, raw)
 	}
 
+	fn as_raw_json_with_code_sidecar(
+		&self,
+		code_path: &Path,
+	) -> Result<(String, Vec<u8>), String> {
+		ChainSpec::as_raw_json_with_code_sidecar(self, code_path)
+	}
+
 	fn as_storage_builder(&self) -> &dyn BuildStorage {
 		self
 	}
@@ -395,6 +459,14 @@ where
 	fn set_light_sync_state(&mut self, light_sync_state: SerializableLightSyncState) {
 		ChainSpec::set_light_sync_state(self, light_sync_state)
 	}
+
+	fn code_substitutes(&self) -> HashMap<String, Vec<u8>> {
+		self.client_spec
+			.code_substitutes
+			.iter()
+			.map(|(h, c)| (h.clone(), c.0.clone()))
+			.collect()
+	}
 }
 
 /// Hardcoded infomation that allows light clients to sync quickly.
@@ -500,4 +572,55 @@ mod tests {
 
 		assert_eq!(spec.extensions().my_property, "Test Extension");
 	}
+
+	fn test_spec() -> TestSpec {
+		TestSpec::from_genesis(
+			"Test",
+			"test",
+			ChainType::Local,
+			|| Genesis(vec![
+				(":code".to_string(), "wasm".to_string()),
+				("b".to_string(), "2".to_string()),
+				("a".to_string(), "1".to_string()),
+			].into_iter().collect()),
+			Vec::new(),
+			None,
+			None,
+			None,
+			None,
+		)
+	}
+
+	#[test]
+	fn raw_json_is_sorted() {
+		let spec = test_spec();
+		let json = spec.as_json(true).unwrap();
+
+		assert_eq!(json, spec.as_json(true).unwrap());
+		let code = json.find("\"0x3a636f6465\"").unwrap();
+		let a = json.find("\"0x61\"").unwrap();
+		let b = json.find("\"0x62\"").unwrap();
+		assert!(code < a && a < b);
+	}
+
+	#[test]
+	fn code_sidecar_round_trips() {
+		let dir = tempfile::tempdir().unwrap();
+		let (json, code) = test_spec()
+			.as_raw_json_with_code_sidecar(Path::new("code.wasm"))
+			.unwrap();
+		assert_eq!(code, b"wasm".to_vec());
+		assert!(!json.contains("0x3a636f6465"));
+		std::fs::write(dir.path().join("spec.json"), json).unwrap();
+		std::fs::write(dir.path().join("code.wasm"), code).unwrap();
+
+		let spec = TestSpec::from_json_file(dir.path().join("spec.json")).unwrap();
+		let storage = spec.build_storage().unwrap();
+		assert_eq!(storage.top.get(&b":code"[..]), Some(&b"wasm".to_vec()));
+		assert_eq!(storage.top.get(&b"a"[..]), Some(&b"1".to_vec()));
+
+		// The code has to match the hash in the spec.
+		std::fs::write(dir.path().join("code.wasm"), b"other").unwrap();
+		assert!(spec.build_storage().is_err());
+	}
 }
@@ -151,6 +151,12 @@ pub trait ChainSpec: BuildStorage + Send + Sync {
 	fn add_boot_node(&mut self, addr: MultiaddrWithPeerId);
 	/// Return spec as JSON.
 	fn as_json(&self, raw: bool) -> Result<String, String>;
+	/// Return spec as raw JSON, along with the wasm code which is moved out of it into a file
+	/// at `code_path`.
+	fn as_raw_json_with_code_sidecar(
+		&self,
+		code_path: &std::path::Path,
+	) -> Result<(String, Vec<u8>), String>;
 	/// Return StorageBuilder for this spec.
 	fn as_storage_builder(&self) -> &dyn BuildStorage;
 	/// Returns a cloned `Box<dyn ChainSpec>`.
@@ -161,6 +167,11 @@ pub trait ChainSpec: BuildStorage + Send + Sync {
 	fn set_storage(&mut self, storage: Storage);
 	/// Hardcode infomation to allow light clients to sync quickly into the chain spec.
 	fn set_light_sync_state(&mut self, light_sync_state: SerializableLightSyncState);
+	/// Returns the code substitutes.
+	///
+	/// The keys are the hex encoded block hashes from which on the substitute should be used,
+	/// the values are the wasm blobs.
+	fn code_substitutes(&self) -> std::collections::HashMap<String, Vec<u8>>;
 }
 
 impl std::fmt::Debug for dyn ChainSpec {
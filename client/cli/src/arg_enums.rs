@@ -64,6 +64,7 @@ arg_enum! {
 	#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 	pub enum TracingReceiver {
 		Log,
+		Prometheus,
 	}
 }
 
@@ -71,10 +72,23 @@ impl Into<sc_tracing::TracingReceiver> for TracingReceiver {
 	fn into(self) -> sc_tracing::TracingReceiver {
 		match self {
 			TracingReceiver::Log => sc_tracing::TracingReceiver::Log,
+			TracingReceiver::Prometheus => sc_tracing::TracingReceiver::Prometheus,
 		}
 	}
 }
 
+arg_enum! {
+	/// The format of the log output.
+	#[allow(missing_docs)]
+	#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+	pub enum LogFormat {
+		// Human readable text.
+		Text,
+		// One JSON object per line, with the target, level and fields of every log entry.
+		Json,
+	}
+}
+
 arg_enum! {
 	#[allow(missing_docs)]
 	#[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -178,7 +192,9 @@ impl std::str::FromStr for Database {
 	fn from_str(s: &str) -> Result<Self, String> {
 		if s.eq_ignore_ascii_case("rocksdb") {
 			Ok(Self::RocksDb)
-		} else if s.eq_ignore_ascii_case("paritydb-experimental") {
+		} else if s.eq_ignore_ascii_case("paritydb")
+			|| s.eq_ignore_ascii_case("paritydb-experimental")
+		{
 			Ok(Self::ParityDb)
 		} else {
 			Err(format!("Unknwon variant `{}`, known variants: {:?}", s, Self::variants()))
@@ -189,7 +205,7 @@ impl std::str::FromStr for Database {
 impl Database {
 	/// Returns all the variants of this enum to be shown in the cli.
 	pub fn variants() -> &'static [&'static str] {
-		&["rocksdb", "paritydb-experimental"]
+		&["rocksdb", "paritydb", "paritydb-experimental"]
 	}
 }
 
@@ -204,15 +220,30 @@ arg_enum! {
 	}
 }
 
-/// Default value for the `--execution-syncing` parameter.
-pub const DEFAULT_EXECUTION_SYNCING: ExecutionStrategy = ExecutionStrategy::NativeElseWasm;
-/// Default value for the `--execution-import-block` parameter.
-pub const DEFAULT_EXECUTION_IMPORT_BLOCK: ExecutionStrategy = ExecutionStrategy::NativeElseWasm;
-/// Default value for the `--execution-import-block` parameter when the node is a validator.
-pub const DEFAULT_EXECUTION_IMPORT_BLOCK_VALIDATOR: ExecutionStrategy = ExecutionStrategy::Wasm;
-/// Default value for the `--execution-block-construction` parameter.
-pub const DEFAULT_EXECUTION_BLOCK_CONSTRUCTION: ExecutionStrategy = ExecutionStrategy::Wasm;
-/// Default value for the `--execution-offchain-worker` parameter.
-pub const DEFAULT_EXECUTION_OFFCHAIN_WORKER: ExecutionStrategy = ExecutionStrategy::Native;
-/// Default value for the `--execution-other` parameter.
-pub const DEFAULT_EXECUTION_OTHER: ExecutionStrategy = ExecutionStrategy::Native;
+/// Default value for the `--execution` parameter.
+pub const DEFAULT_EXECUTION: ExecutionStrategy = ExecutionStrategy::Wasm;
+/// Default value for the `--execution` parameter on development chains.
+pub const DEFAULT_EXECUTION_DEV: ExecutionStrategy = ExecutionStrategy::Native;
+/// Default value for the `--execution` parameter when `--native-fallback` is given.
+pub const DEFAULT_EXECUTION_NATIVE_FALLBACK: ExecutionStrategy = ExecutionStrategy::NativeElseWasm;
+
+arg_enum! {
+	/// Syncing mode.
+	#[allow(missing_docs)]
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub enum SyncMode {
+		// Full sync. Download and verify all blocks.
+		Full,
+		// Download blocks without executing them. Download the latest state with proofs.
+		Fast,
+	}
+}
+
+impl Into<sc_network::config::SyncMode> for SyncMode {
+	fn into(self) -> sc_network::config::SyncMode {
+		match self {
+			SyncMode::Full => sc_network::config::SyncMode::Full,
+			SyncMode::Fast => sc_network::config::SyncMode::Fast,
+		}
+	}
+}
@@ -24,15 +24,26 @@ use log::info;
 use sc_network::config::build_multiaddr;
 use sc_service::{config::{MultiaddrWithPeerId, NetworkConfiguration}, ChainSpec};
 use structopt::StructOpt;
-use std::io::Write;
+use std::{io::Write, path::{Path, PathBuf}};
 
 /// The `build-spec` command used to build a specification.
 #[derive(Debug, StructOpt)]
 pub struct BuildSpecCmd {
 	/// Force raw genesis storage output.
+	///
+	/// The storage is sorted by key, so that building the spec of the same chain twice
+	/// results in the same output.
 	#[structopt(long = "raw")]
 	pub raw: bool,
 
+	/// Move the wasm code out of the raw genesis storage into the given file.
+	///
+	/// The spec refers to the file by its file name and by the hash of the code. The name is
+	/// resolved relative to the directory of the spec when it is loaded, so the spec has to be
+	/// written to the same directory as the file.
+	#[structopt(long = "code-sidecar", value_name = "PATH", requires = "raw", parse(from_os_str))]
+	pub code_sidecar: Option<PathBuf>,
+
 	/// Disable adding the default bootnode to the specification.
 	///
 	/// By default the `/ip4/127.0.0.1/tcp/30333/p2p/NODE_PEER_ID` bootnode is added to the
@@ -69,7 +80,16 @@ impl BuildSpecCmd {
 			spec.add_boot_node(addr)
 		}
 
-		let json = sc_service::chain_ops::build_spec(&*spec, raw_output)?;
+		let mut json = match &self.code_sidecar {
+			Some(path) => {
+				let name = path.file_name().ok_or("--code-sidecar must be the path of a file")?;
+				let (json, code) = spec.as_raw_json_with_code_sidecar(Path::new(name))?;
+				std::fs::write(path, code)?;
+				json
+			},
+			None => sc_service::chain_ops::build_spec(&*spec, raw_output)?,
+		};
+		json.push('\n');
 		if std::io::stdout().write_all(json.as_bytes()).is_err() {
 			let _ = std::io::stderr().write_all(b"Error writing to stdout\n");
 		}
@@ -0,0 +1,153 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+use crate::{
+	CliConfiguration, error, OutputType,
+	params::{DatabaseParams, OutputTypeFlag, PruningParams, SharedParams},
+};
+use sc_client_api::Backend as _;
+use sc_service::{Configuration, PruningMode};
+use serde_json::json;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{fs, io, path::Path};
+use structopt::StructOpt;
+
+/// The `chain-info` command used to print information about the database of a chain
+/// without starting the node.
+#[derive(Debug, StructOpt)]
+pub struct ChainInfoCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub output_scheme: OutputTypeFlag,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ChainInfoCmd {
+	/// Run the `chain-info` command
+	pub fn run<B: BlockT>(&self, config: Configuration) -> error::Result<()> {
+		let database_version = config.database.version()?;
+		let database_size = config.database.path().map(directory_size).transpose()?;
+
+		let db_config = sc_client_db::DatabaseSettings {
+			state_cache_size: config.state_cache_size,
+			state_cache_child_ratio: config.state_cache_child_ratio.map(|v| (v, 100)),
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			keep_blocks: config.keep_blocks,
+			transaction_storage: config.transaction_storage,
+		};
+		let backend = sc_service::new_db_backend::<B>(db_config)?;
+		let info = backend.blockchain().info();
+
+		match self.output_scheme.output_type {
+			OutputType::Json => {
+				let json = json!({
+					"genesisHash": info.genesis_hash,
+					"bestHash": info.best_hash,
+					"bestNumber": info.best_number,
+					"finalizedHash": info.finalized_hash,
+					"finalizedNumber": info.finalized_number,
+					"database": config.database.to_string(),
+					"databaseVersion": database_version,
+					"databaseSize": database_size,
+					"pruning": format_pruning(&config.state_pruning),
+				});
+				println!(
+					"{}",
+					serde_json::to_string_pretty(&json).expect("Json pretty print failed")
+				);
+			}
+			OutputType::Text => {
+				println!(
+					"Genesis hash:     {}\n\
+					Best block:       #{} ({})\n\
+					Finalized block:  #{} ({})\n\
+					Database:         {}\n\
+					Database version: {}\n\
+					Database size:    {}\n\
+					Pruning:          {}",
+					info.genesis_hash,
+					info.best_number,
+					info.best_hash,
+					info.finalized_number,
+					info.finalized_hash,
+					config.database,
+					database_version.map_or_else(|| "unknown".into(), |v| v.to_string()),
+					database_size.map_or_else(|| "unknown".into(), |s| format!("{} bytes", s)),
+					format_pruning(&config.state_pruning),
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Returns a human readable representation of the given pruning mode.
+fn format_pruning(pruning: &PruningMode) -> String {
+	match pruning {
+		PruningMode::ArchiveAll => "archive".into(),
+		PruningMode::ArchiveCanonical => "archive-canonical".into(),
+		PruningMode::Constrained(c) => match c.max_blocks {
+			Some(n) => format!("keep {} blocks", n),
+			None => "constrained".into(),
+		},
+	}
+}
+
+/// Returns the total size in bytes of all files in the given directory.
+fn directory_size(path: &Path) -> io::Result<u64> {
+	let mut size = 0;
+	for entry in fs::read_dir(path)? {
+		let entry = entry?;
+		let metadata = entry.metadata()?;
+		size += if metadata.is_dir() {
+			directory_size(&entry.path())?
+		} else {
+			metadata.len()
+		};
+	}
+	Ok(size)
+}
+
+impl CliConfiguration for ChainInfoCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -16,14 +16,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 mod build_spec_cmd;
+mod chain_info_cmd;
 mod check_block_cmd;
 mod export_blocks_cmd;
 mod export_state_cmd;
 mod import_blocks_cmd;
 mod purge_chain_cmd;
+mod purge_offchain_cmd;
 mod sign;
 mod verify;
+mod verify_spec_cmd;
 mod vanity;
+mod reindex_offchain_cmd;
 mod revert_cmd;
 mod run_cmd;
 mod generate_node_key;
@@ -36,11 +40,13 @@ pub mod utils;
 
 pub use self::{
 	build_spec_cmd::BuildSpecCmd,
+	chain_info_cmd::ChainInfoCmd,
 	check_block_cmd::CheckBlockCmd,
 	export_blocks_cmd::ExportBlocksCmd,
 	export_state_cmd::ExportStateCmd,
 	import_blocks_cmd::ImportBlocksCmd,
 	purge_chain_cmd::PurgeChainCmd,
+	purge_offchain_cmd::PurgeOffchainCmd,
 	sign::SignCmd,
 	generate::GenerateCmd,
 	insert_key::InsertKeyCmd,
@@ -50,6 +56,8 @@ pub use self::{
 	key::KeySubcommand,
 	vanity::VanityCmd,
 	verify::VerifyCmd,
+	verify_spec_cmd::VerifySpecCmd,
+	reindex_offchain_cmd::ReindexOffchainCmd,
 	revert_cmd::RevertCmd,
 	run_cmd::RunCmd,
 };
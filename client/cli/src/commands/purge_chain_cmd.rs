@@ -26,10 +26,13 @@ use std::io::{self, Write};
 use structopt::StructOpt;
 
 /// The `purge-chain` command used to remove the whole chain.
+///
+/// Only the chain database is removed. The keystore and the network key stored
+/// next to it are kept.
 #[derive(Debug, StructOpt)]
 pub struct PurgeChainCmd {
 	/// Skip interactive prompt by answering yes automatically.
-	#[structopt(short = "y")]
+	#[structopt(short = "y", long = "yes")]
 	pub yes: bool,
 
 	#[allow(missing_docs)]
@@ -0,0 +1,92 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error;
+use crate::params::{PruningParams, SharedParams};
+use crate::CliConfiguration;
+use sc_client_api::Backend;
+use sc_service::chain_ops::purge_offchain;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// The `purge-offchain` command used to remove entries from the persistent offchain storage.
+#[derive(Debug, StructOpt)]
+pub struct PurgeOffchainCmd {
+	/// Key to remove from the persistent offchain storage.
+	///
+	/// Keys starting with `0x` are interpreted as hex, all others are used as they are, e.g.
+	/// `parity/multi-phase-unsigned-election`. Can be passed multiple times.
+	#[structopt(
+		long = "key",
+		value_name = "KEY",
+		required = true,
+		parse(try_from_str = parse_key),
+	)]
+	pub keys: Vec<Vec<u8>>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl PurgeOffchainCmd {
+	/// Run the purge-offchain command
+	pub async fn run<B, BA>(&self, backend: Arc<BA>) -> error::Result<()>
+	where
+		B: BlockT,
+		BA: Backend<B>,
+	{
+		purge_offchain(backend, &self.keys)?;
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for PurgeOffchainCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
+
+fn parse_key(key: &str) -> Result<Vec<u8>, hex::FromHexError> {
+	match key.strip_prefix("0x") {
+		Some(key) => hex::decode(key),
+		None => Ok(key.as_bytes().to_vec()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_keys() {
+		assert_eq!(parse_key("0x0102").unwrap(), vec![1, 2]);
+		assert_eq!(parse_key("parity/key").unwrap(), b"parity/key".to_vec());
+		assert!(parse_key("0xzz").is_err());
+	}
+}
@@ -0,0 +1,86 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error;
+use crate::params::{GenericNumber, PruningParams, SharedParams};
+use crate::CliConfiguration;
+use sc_client_api::{Backend, BlockBackend};
+use sc_service::chain_ops::reindex_offchain;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// The `reindex-offchain` command used to populate the offchain database from already imported
+/// blocks.
+#[derive(Debug, StructOpt)]
+pub struct ReindexOffchainCmd {
+	/// Number of the first block to re-execute.
+	#[structopt(long, value_name = "NUMBER")]
+	pub from: GenericNumber,
+
+	/// Number of the last block to re-execute. Defaults to the best block.
+	#[structopt(long, value_name = "NUMBER")]
+	pub to: Option<GenericNumber>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl ReindexOffchainCmd {
+	/// Run the reindex-offchain command
+	pub async fn run<B, BA, C>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<BA>,
+	) -> error::Result<()>
+	where
+		B: BlockT,
+		BA: Backend<B>,
+		C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B>,
+		C::Api: Core<B> + ApiExt<B, StateBackend = BA::State>,
+		<<<B as BlockT>::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let from = self.from.parse()?;
+		let to = match self.to {
+			Some(ref to) => to.parse()?,
+			None => client.info().best_number,
+		};
+		reindex_offchain(client, backend, from, to)?;
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ReindexOffchainCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
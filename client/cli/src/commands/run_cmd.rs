@@ -27,11 +27,12 @@ use crate::params::TransactionPoolParams;
 use crate::CliConfiguration;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{BasePath, PrometheusConfig, RpcPolicy, StorageMonitorParams, TransactionPoolOptions},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
 use structopt::StructOpt;
 
 /// The `run` command used to run a node.
@@ -122,6 +123,38 @@ pub struct RunCmd {
 	#[structopt(long = "ws-max-connections", value_name = "COUNT")]
 	pub ws_max_connections: Option<usize>,
 
+	/// Only allow the given comma-separated list of RPC methods to be called.
+	///
+	/// All methods are allowed by default.
+	#[structopt(long = "rpc-allow-methods", value_name = "METHODS", use_delimiter = true)]
+	pub rpc_allow_methods: Option<Vec<String>>,
+
+	/// Deny the given comma-separated list of RPC methods.
+	///
+	/// Takes precedence over `--rpc-allow-methods`.
+	#[structopt(long = "rpc-deny-methods", value_name = "METHODS", use_delimiter = true)]
+	pub rpc_deny_methods: Vec<String>,
+
+	/// Maximum number of RPC calls per second and connection.
+	///
+	/// Calls made over HTTP share a single limit. Unlimited by default.
+	#[structopt(long = "rpc-rate-limit", value_name = "CALLS")]
+	pub rpc_rate_limit: Option<u32>,
+
+	/// Maximum number of calls in a single RPC batch request.
+	#[structopt(long = "rpc-max-batch-len", value_name = "COUNT")]
+	pub rpc_max_batch_len: Option<usize>,
+
+	/// Maximum size of an RPC response, in megabytes.
+	#[structopt(long = "rpc-max-response-size", value_name = "MEGABYTES")]
+	pub rpc_max_response_size: Option<usize>,
+
+	/// How long to wait, in seconds, for the RPC requests being processed when shutting down.
+	///
+	/// New requests are rejected meanwhile. Default is 5 seconds.
+	#[structopt(long = "rpc-shutdown-timeout", value_name = "SECONDS")]
+	pub rpc_shutdown_timeout: Option<u64>,
+
 	/// Specify browser Origins allowed to access the HTTP & WS RPC servers.
 	///
 	/// A comma-separated list of origins (protocol://domain or special `null`
@@ -214,7 +247,7 @@ pub struct RunCmd {
 	#[structopt(long, conflicts_with_all = &["alice", "bob", "charlie", "dave", "eve", "ferdie", "one"])]
 	pub two: bool,
 
-	/// Enable authoring even when offline.
+	/// Enable authoring even when offline or when finality is lagging behind.
 	#[structopt(long = "force-authoring")]
 	pub force_authoring: bool,
 
@@ -237,6 +270,23 @@ pub struct RunCmd {
 	/// which includes: database, node key and keystore.
 	#[structopt(long, conflicts_with = "base-path")]
 	pub tmp: bool,
+
+	/// Minimum available disk space on the database path, in MiB.
+	///
+	/// The node is stopped gracefully once the available space drops below this
+	/// threshold. `0` disables the check.
+	#[structopt(long, value_name = "MiB", default_value = "1024")]
+	pub db_storage_threshold: u64,
+
+	/// How often to check the available disk space on the database path, in seconds.
+	#[structopt(long, value_name = "SECONDS", default_value = "5")]
+	pub db_storage_polling_period: u32,
+
+	/// Disable the hardware benchmarks run at startup.
+	///
+	/// By default the CPU, memory and disk throughput are measured and reported to telemetry.
+	#[structopt(long)]
+	pub no_hardware_benchmarks: bool,
 }
 
 impl RunCmd {
@@ -372,10 +422,35 @@ impl CliConfiguration for RunCmd {
 		Ok(self.no_grandpa)
 	}
 
+	fn storage_monitor(&self) -> Result<StorageMonitorParams> {
+		Ok(StorageMonitorParams {
+			threshold: self.db_storage_threshold,
+			polling_period: self.db_storage_polling_period,
+		})
+	}
+
+	fn hardware_benchmarks(&self) -> Result<bool> {
+		Ok(!self.no_hardware_benchmarks)
+	}
+
 	fn rpc_ws_max_connections(&self) -> Result<Option<usize>> {
 		Ok(self.ws_max_connections)
 	}
 
+	fn rpc_shutdown_timeout(&self) -> Result<Option<Duration>> {
+		Ok(self.rpc_shutdown_timeout.map(Duration::from_secs))
+	}
+
+	fn rpc_policy(&self) -> Result<RpcPolicy> {
+		Ok(RpcPolicy {
+			allowed_methods: self.rpc_allow_methods.clone(),
+			denied_methods: self.rpc_deny_methods.clone(),
+			max_calls_per_second: self.rpc_rate_limit,
+			max_batch_len: self.rpc_max_batch_len,
+			max_response_size: self.rpc_max_response_size.map(|size| size * 1024 * 1024),
+		})
+	}
+
 	fn rpc_cors(&self, is_dev: bool) -> Result<Option<Vec<String>>> {
 		Ok(self
 			.rpc_cors
@@ -0,0 +1,73 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CliConfiguration, error, params::SharedParams};
+use log::info;
+use sc_client_api::{Backend as _, BlockImportOperation as _, in_mem};
+use sc_service::{ChainSpec, client::genesis::construct_genesis_block};
+use sp_runtime::traits::{Block as BlockT, Header as _};
+use structopt::StructOpt;
+
+/// The `verify-spec` command used to check that a chain spec builds the genesis block of a
+/// network.
+///
+/// The command doesn't connect to the network. The hash of its genesis block has to be fetched
+/// separately and passed in.
+#[derive(Debug, StructOpt)]
+pub struct VerifySpecCmd {
+	/// The hash of the genesis block of the network, as returned by `chain_getBlockHash(0)`.
+	///
+	/// It is only compared with the hash of the genesis block built from the spec; the network
+	/// itself is not queried.
+	#[structopt(long = "genesis-hash", value_name = "HASH")]
+	pub genesis_hash: String,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl VerifySpecCmd {
+	/// Run the `verify-spec` command
+	pub fn run<B: BlockT>(&self, spec: Box<dyn ChainSpec>) -> error::Result<()> {
+		let expected = hex::decode(self.genesis_hash.trim_start_matches("0x"))?;
+
+		let storage = spec.as_storage_builder().build_storage()?;
+		let backend = in_mem::Backend::<B>::new();
+		let mut op = backend.begin_operation()?;
+		let state_root = op.reset_storage(storage)?;
+		let genesis_hash = construct_genesis_block::<B>(state_root).header().hash();
+
+		if genesis_hash.as_ref() != &expected[..] {
+			return Err(format!(
+				"The spec builds the genesis block {:?}, but the network's is 0x{}",
+				genesis_hash,
+				hex::encode(&expected),
+			).into())
+		}
+
+		info!("The spec builds the genesis block {:?} of the network", genesis_hash);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for VerifySpecCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
@@ -18,7 +18,7 @@
 
 //! Configuration trait for a CLI based on substrate
 
-use crate::arg_enums::Database;
+use crate::arg_enums::{Database, LogFormat};
 use crate::error::Result;
 use crate::{
 	DatabaseParams, ImportParams, KeystoreParams, NetworkParams, NodeKeyParams,
@@ -29,13 +29,15 @@ use names::{Generator, Name};
 use sc_client_api::execution_extensions::ExecutionStrategies;
 use sc_service::config::{
 	BasePath, Configuration, DatabaseConfig, ExtTransport, KeystoreConfig, NetworkConfiguration,
-	NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcMethods,
-	TaskExecutor, TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+	NodeKeyConfig, OffchainWorkerConfig, OutputFormat, PrometheusConfig, PruningMode, Role,
+	RpcMethods, RpcPolicy, StorageMonitorParams, TaskExecutor, TelemetryEndpoints,
+	TransactionPoolOptions, WasmExecutionMethod,
 };
 use sc_service::{ChainSpec, TracingReceiver, KeepBlocks, TransactionStorageMode};
 use sc_tracing::logging::LoggerBuilder;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// The maximum number of characters for a node name.
 pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
@@ -307,18 +309,25 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			.unwrap_or_default()
 	}
 
+	/// Get the path where compiled WASM runtimes are cached.
+	///
+	/// By default this is `$config_dir/wasm-cache`, unless the cache is disabled in `ImportParams`.
+	fn wasm_runtime_cache(&self, config_dir: &PathBuf) -> Option<PathBuf> {
+		if self.import_params().map_or(false, |x| x.no_wasm_cache) {
+			None
+		} else {
+			Some(config_dir.join("wasm-cache"))
+		}
+	}
+
 	/// Get the execution strategies.
 	///
 	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its
 	/// `ExecutionStrategies::default()`.
-	fn execution_strategies(
-		&self,
-		is_dev: bool,
-		is_validator: bool,
-	) -> Result<ExecutionStrategies> {
+	fn execution_strategies(&self, is_dev: bool) -> Result<ExecutionStrategies> {
 		Ok(self
 			.import_params()
-			.map(|x| x.execution_strategies(is_dev, is_validator))
+			.map(|x| x.execution_strategies(is_dev))
 			.unwrap_or_default())
 	}
 
@@ -351,6 +360,21 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(Default::default())
 	}
 
+	/// Get the access control and limits enforced by the RPC servers.
+	///
+	/// By default this is `RpcPolicy::default()`, which does not restrict anything.
+	fn rpc_policy(&self) -> Result<RpcPolicy> {
+		Ok(Default::default())
+	}
+
+	/// Get how long the RPC requests being processed are awaited for on shutdown (`None` if
+	/// default).
+	///
+	/// By default this is `None`.
+	fn rpc_shutdown_timeout(&self) -> Result<Option<Duration>> {
+		Ok(None)
+	}
+
 	/// Get the RPC websockets maximum connections (`None` if unlimited).
 	///
 	/// By default this is `None`.
@@ -466,6 +490,20 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(true)
 	}
 
+	/// Get the storage monitor configuration
+	///
+	/// By default this is `StorageMonitorParams::default()`.
+	fn storage_monitor(&self) -> Result<StorageMonitorParams> {
+		Ok(Default::default())
+	}
+
+	/// Run the hardware benchmarks at startup or not
+	///
+	/// By default this is `false`.
+	fn hardware_benchmarks(&self) -> Result<bool> {
+		Ok(false)
+	}
+
 	/// Create a Configuration object from the current object
 	fn create_configuration<C: SubstrateCli>(
 		&self,
@@ -520,11 +558,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			transaction_storage: self.database_transaction_storage()?,
 			wasm_method: self.wasm_method()?,
 			wasm_runtime_overrides: self.wasm_runtime_overrides(),
-			execution_strategies: self.execution_strategies(is_dev, is_validator)?,
+			wasm_runtime_cache: self.wasm_runtime_cache(&config_dir),
+			execution_strategies: self.execution_strategies(is_dev)?,
 			rpc_http: self.rpc_http(DCV::rpc_http_listen_port())?,
 			rpc_ws: self.rpc_ws(DCV::rpc_ws_listen_port())?,
 			rpc_ipc: self.rpc_ipc()?,
 			rpc_methods: self.rpc_methods()?,
+			rpc_policy: self.rpc_policy()?,
+			rpc_shutdown_timeout: self.rpc_shutdown_timeout()?,
 			rpc_ws_max_connections: self.rpc_ws_max_connections()?,
 			rpc_cors: self.rpc_cors(is_dev)?,
 			prometheus_config: self.prometheus_config(DCV::prometheus_listen_port())?,
@@ -543,7 +584,9 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			announce_block: self.announce_block()?,
 			role,
 			base_path: Some(base_path),
-			informant_output_format: Default::default(),
+			informant_output_format: self.informant_output_format()?,
+			storage_monitor: self.storage_monitor()?,
+			hardware_benchmarks: self.hardware_benchmarks()?,
 		})
 	}
 
@@ -567,6 +610,26 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.shared_params().disable_log_color())
 	}
 
+	/// Get the format of the log output.
+	///
+	/// By default this is retrieved from [`SharedParams`].
+	fn log_format(&self) -> Result<LogFormat> {
+		Ok(self.shared_params().log_format())
+	}
+
+	/// Get the output format of the informant.
+	///
+	/// By default the status line is printed with its values as structured fields when logging
+	/// in the JSON format.
+	fn informant_output_format(&self) -> Result<OutputFormat> {
+		let structured = self.log_format()? == LogFormat::Json;
+		Ok(OutputFormat {
+			enable_color: !structured,
+			structured,
+			..Default::default()
+		})
+	}
+
 	/// Initialize substrate. This must be done only once per process.
 	///
 	/// This method:
@@ -589,6 +652,8 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			logger.with_colors(false);
 		}
 
+		logger.with_json(self.log_format()? == LogFormat::Json);
+
 		logger.init()?;
 
 		if let Some(new_limit) = fdlimit::raise_fd_limit() {
@@ -17,9 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::arg_enums::{
-	ExecutionStrategy, WasmExecutionMethod, DEFAULT_EXECUTION_BLOCK_CONSTRUCTION,
-	DEFAULT_EXECUTION_IMPORT_BLOCK, DEFAULT_EXECUTION_IMPORT_BLOCK_VALIDATOR,
-	DEFAULT_EXECUTION_OFFCHAIN_WORKER, DEFAULT_EXECUTION_OTHER, DEFAULT_EXECUTION_SYNCING,
+	ExecutionStrategy, WasmExecutionMethod, DEFAULT_EXECUTION, DEFAULT_EXECUTION_DEV,
+	DEFAULT_EXECUTION_NATIVE_FALLBACK,
 };
 use crate::params::DatabaseParams;
 use crate::params::PruningParams;
@@ -62,6 +61,13 @@ pub struct ImportParams {
 	#[structopt(long, value_name = "PATH", parse(from_os_str))]
 	pub wasm_runtime_overrides: Option<PathBuf>,
 
+	/// Disable the on-disk cache of compiled WASM runtimes.
+	///
+	/// By default the artifacts of the compiled execution method are cached, so the runtime does
+	/// not need to be compiled again after a restart.
+	#[structopt(long = "no-wasm-cache")]
+	pub no_wasm_cache: bool,
+
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub execution_strategies: ExecutionStrategiesParams,
@@ -93,100 +99,54 @@ impl ImportParams {
 		self.wasm_runtime_overrides.clone()
 	}
 
-	/// Get execution strategies for the parameters
-	pub fn execution_strategies(&self, is_dev: bool, is_validator: bool) -> ExecutionStrategies {
+	/// Get execution strategies for the parameters.
+	///
+	/// The same strategy is used for all execution contexts. See [`ExecutionStrategiesParams`]
+	/// for how it is chosen.
+	pub fn execution_strategies(&self, is_dev: bool) -> ExecutionStrategies {
 		let exec = &self.execution_strategies;
-		let exec_all_or = |strat: Option<ExecutionStrategy>, default: ExecutionStrategy| {
-			let default = if is_dev {
-				ExecutionStrategy::Native
+		let strategy: sc_client_api::ExecutionStrategy = exec.execution
+			.unwrap_or_else(|| if exec.native_fallback {
+				DEFAULT_EXECUTION_NATIVE_FALLBACK
+			} else if is_dev {
+				DEFAULT_EXECUTION_DEV
 			} else {
-				default
-			};
-
-			exec.execution.unwrap_or_else(|| strat.unwrap_or(default)).into()
-		};
-
-		let default_execution_import_block = if is_validator {
-			DEFAULT_EXECUTION_IMPORT_BLOCK_VALIDATOR
-		} else {
-			DEFAULT_EXECUTION_IMPORT_BLOCK
-		};
+				DEFAULT_EXECUTION
+			})
+			.into();
 
 		ExecutionStrategies {
-			syncing: exec_all_or(exec.execution_syncing, DEFAULT_EXECUTION_SYNCING),
-			importing: exec_all_or(exec.execution_import_block, default_execution_import_block),
-			block_construction:
-				exec_all_or(exec.execution_block_construction, DEFAULT_EXECUTION_BLOCK_CONSTRUCTION),
-			offchain_worker:
-				exec_all_or(exec.execution_offchain_worker, DEFAULT_EXECUTION_OFFCHAIN_WORKER),
-			other: exec_all_or(exec.execution_other, DEFAULT_EXECUTION_OTHER),
+			syncing: strategy,
+			importing: strategy,
+			block_construction: strategy,
+			offchain_worker: strategy,
+			other: strategy,
 		}
 	}
 }
 
 /// Execution strategies parameters.
+///
+/// Blocks are imported, built and called into with the on-chain WebAssembly runtime by default,
+/// in every execution context. This way the node always executes the code the chain agreed on,
+/// including the runtimes provided through `--wasm-runtime-overrides`.
+///
+/// `--native-fallback` allows to use the native runtime compiled into the node instead, as long
+/// as it is compatible with the on-chain runtime, and to fall back to WebAssembly otherwise.
+/// Development chains use the native runtime by default. `--execution` overrides both.
 #[derive(Debug, StructOpt)]
 pub struct ExecutionStrategiesParams {
-	/// The means of execution used when calling into the runtime for importing blocks as
-	/// part of an initial sync.
-	#[structopt(
-		long = "execution-syncing",
-		value_name = "STRATEGY",
-		possible_values = &ExecutionStrategy::variants(),
-		case_insensitive = true,
-	)]
-	pub execution_syncing: Option<ExecutionStrategy>,
-
-	/// The means of execution used when calling into the runtime for general block import
-	/// (including locally authored blocks).
-	#[structopt(
-		long = "execution-import-block",
-		value_name = "STRATEGY",
-		possible_values = &ExecutionStrategy::variants(),
-		case_insensitive = true,
-	)]
-	pub execution_import_block: Option<ExecutionStrategy>,
-
-	/// The means of execution used when calling into the runtime while constructing blocks.
-	#[structopt(
-		long = "execution-block-construction",
-		value_name = "STRATEGY",
-		possible_values = &ExecutionStrategy::variants(),
-		case_insensitive = true,
-	)]
-	pub execution_block_construction: Option<ExecutionStrategy>,
-
-	/// The means of execution used when calling into the runtime while using an off-chain worker.
-	#[structopt(
-		long = "execution-offchain-worker",
-		value_name = "STRATEGY",
-		possible_values = &ExecutionStrategy::variants(),
-		case_insensitive = true,
-	)]
-	pub execution_offchain_worker: Option<ExecutionStrategy>,
-
-	/// The means of execution used when calling into the runtime while not syncing, importing or constructing blocks.
-	#[structopt(
-		long = "execution-other",
-		value_name = "STRATEGY",
-		possible_values = &ExecutionStrategy::variants(),
-		case_insensitive = true,
-	)]
-	pub execution_other: Option<ExecutionStrategy>,
-
 	/// The execution strategy that should be used by all execution contexts.
 	#[structopt(
 		long = "execution",
 		value_name = "STRATEGY",
 		possible_values = &ExecutionStrategy::variants(),
 		case_insensitive = true,
-		conflicts_with_all = &[
-			"execution-other",
-			"execution-offchain-worker",
-			"execution-block-construction",
-			"execution-import-block",
-			"execution-syncing",
-		]
 	)]
 	pub execution: Option<ExecutionStrategy>,
+
+	/// Execute with the native runtime when it is compatible with the on-chain runtime, and with
+	/// the on-chain WebAssembly runtime otherwise.
+	#[structopt(long = "native-fallback", conflicts_with = "execution")]
+	pub native_fallback: bool,
 }
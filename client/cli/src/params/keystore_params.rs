@@ -30,6 +30,9 @@ const DEFAULT_KEYSTORE_CONFIG_PATH: &'static str = "keystore";
 #[derive(Debug, StructOpt)]
 pub struct KeystoreParams {
 	/// Specify custom URIs to connect to for keystore-services
+	///
+	/// Signing requests are forwarded to a signer listening on a local unix socket, for example
+	/// `unix:///run/signer.sock?key_types=aura,gran`. Only the listed key types are forwarded.
 	#[structopt(long = "keystore-uri")]
 	pub keystore_uri: Option<String>,
 
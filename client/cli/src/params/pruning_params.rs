@@ -23,17 +23,22 @@ use structopt::StructOpt;
 /// Parameters to define the pruning mode
 #[derive(Debug, StructOpt)]
 pub struct PruningParams {
-	/// Specify the state pruning mode, a number of blocks to keep or 'archive'.
+	/// Specify the state pruning mode, a number of blocks to keep, 'archive' or
+	/// 'archive-canonical'.
+	///
+	/// 'archive-canonical' keeps the state of all finalized blocks, but discards
+	/// the state of non-canonical forks.
 	///
 	/// Default is to keep all block states if the node is running as a
 	/// validator (i.e. 'archive'), otherwise state is only kept for the last
 	/// 256 blocks.
-	#[structopt(long = "pruning", value_name = "PRUNING_MODE")]
+	#[structopt(long = "pruning", alias = "state-pruning", value_name = "PRUNING_MODE")]
 	pub pruning: Option<String>,
 	/// Specify the number of finalized blocks to keep in the database.
 	///
+	/// Bodies and justifications of older finalized blocks are removed, headers are kept.
 	/// Default is to keep all blocks.
-	#[structopt(long, value_name = "COUNT")]
+	#[structopt(long, alias = "blocks-pruning", value_name = "COUNT")]
 	pub keep_blocks: Option<u32>,
 }
 
@@ -46,6 +51,7 @@ impl PruningParams {
 		// unless `unsafe_pruning` is set.
 		Ok(match &self.pruning {
 			Some(ref s) if s == "archive" => PruningMode::ArchiveAll,
+			Some(ref s) if s == "archive-canonical" => PruningMode::ArchiveCanonical,
 			None if role.is_authority() => PruningMode::ArchiveAll,
 			None => PruningMode::default(),
 			Some(s) => {
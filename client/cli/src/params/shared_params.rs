@@ -19,7 +19,7 @@
 use sc_service::config::BasePath;
 use std::path::PathBuf;
 use structopt::StructOpt;
-use crate::arg_enums::TracingReceiver;
+use crate::arg_enums::{LogFormat, TracingReceiver};
 
 /// Shared parameters used by all `CoreParams`.
 #[derive(Debug, StructOpt)]
@@ -50,6 +50,19 @@ pub struct SharedParams {
 	#[structopt(long)]
 	pub disable_log_color: bool,
 
+	/// The format of the log output.
+	///
+	/// With `json`, every log line is written as a JSON object containing the target, level
+	/// and fields of the entry.
+	#[structopt(
+		long = "log-format",
+		value_name = "FORMAT",
+		possible_values = &LogFormat::variants(),
+		case_insensitive = true,
+		default_value = "Text"
+	)]
+	pub log_format: LogFormat,
+
 	/// Disable feature to dynamically update and reload the log filter.
 	///
 	/// By default this feature is enabled, however it leads to a small performance decrease.
@@ -108,6 +121,11 @@ impl SharedParams {
 		self.disable_log_color
 	}
 
+	/// The format of the log output.
+	pub fn log_format(&self) -> LogFormat {
+		self.log_format
+	}
+
 	/// Is log reloading disabled
 	pub fn is_log_filter_reloading_disabled(&self) -> bool {
 		self.disable_log_reloading
@@ -29,6 +29,12 @@ pub struct TransactionPoolParams {
 	/// Maximum number of kilobytes of all transactions stored in the pool.
 	#[structopt(long = "pool-kbytes", value_name = "COUNT", default_value = "20480")]
 	pub pool_kbytes: usize,
+
+	/// How long a transaction is banned from re-entering the pool, in seconds.
+	///
+	/// Transactions are banned when they are found invalid or stale.
+	#[structopt(long = "tx-ban-seconds", value_name = "SECONDS")]
+	pub tx_ban_seconds: Option<u64>,
 }
 
 impl TransactionPoolParams {
@@ -45,6 +51,10 @@ impl TransactionPoolParams {
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
+		if let Some(ban_seconds) = self.tx_ban_seconds {
+			opts.ban_time = std::time::Duration::from_secs(ban_seconds);
+		}
+
 		opts
 	}
 }
@@ -179,7 +179,9 @@ impl<C: SubstrateCli> Runner<C> {
 	}
 
 	/// A helper function that runs a node with tokio and stops if the process receives the signal
-	/// `SIGTERM` or `SIGINT`.
+	/// `SIGTERM` or `SIGINT`, or if the shutdown is requested through the `system_shutdown` RPC.
+	///
+	/// The node is then shut down gracefully, see [`TaskManager::clean_shutdown`].
 	pub fn run_node_until_exit<F, E>(
 		mut self,
 		initialize: impl FnOnce(Configuration) -> F,
@@ -26,7 +26,7 @@ use std::{
 	sync::Arc, time::Duration, thread, marker::PhantomData, hash::Hash, fmt::Debug,
 	collections::HashMap,
 };
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 use prometheus_endpoint::Registry;
 use codec::{Encode, Decode, Codec};
 use sp_consensus::{
@@ -59,17 +59,14 @@ use sp_consensus_aura::{
 /// containing the seal.
 ///
 /// This digest item will always return `Some` when used with `as_aura_seal`.
-fn check_header<C, B: BlockT, P: Pair>(
-	client: &C,
+fn check_header<B: BlockT, P: Pair>(
 	slot_now: Slot,
 	mut header: B::Header,
 	hash: B::Hash,
 	authorities: &[AuthorityId<P>],
-	check_for_equivocation: CheckForEquivocation,
 ) -> Result<CheckedHeader<B::Header, (Slot, DigestItemFor<B>)>, Error<B>> where
 	DigestItemFor<B>: CompatibleDigestItem<P::Signature>,
 	P::Signature: Codec,
-	C: sc_client_api::backend::AuxStore,
 	P::Public: Encode + Decode + PartialEq + Clone,
 {
 	let seal = header.digest_mut().pop().ok_or_else(|| Error::HeaderUnsealed(hash))?;
@@ -92,24 +89,6 @@ fn check_header<C, B: BlockT, P: Pair>(
 		let pre_hash = header.hash();
 
 		if P::verify(&sig, pre_hash.as_ref(), expected_author) {
-			if check_for_equivocation.check_for_equivocation() {
-				if let Some(equivocation_proof) = check_equivocation(
-					client,
-					slot_now,
-					slot,
-					&header,
-					expected_author,
-				).map_err(Error::Client)? {
-					info!(
-						target: "aura",
-						"Slot author is equivocating at slot {} with headers {:?} and {:?}",
-						slot,
-						equivocation_proof.first_header.hash(),
-						equivocation_proof.second_header.hash(),
-					);
-				}
-			}
-
 			Ok(CheckedHeader::Checked(header, (slot, seal)))
 		} else {
 			Err(Error::BadSignature(hash))
@@ -150,6 +129,108 @@ impl<C, P, CAW> AuraVerifier<C, P, CAW> where
 	P: Send + Sync + 'static,
 	CAW: Send + Sync + 'static,
 {
+	/// Check if the given header is an equivocation of its author and, if so, report it to the
+	/// runtime.
+	///
+	/// The `header` must still contain its seal, so that the resulting equivocation proof can be
+	/// verified by the runtime.
+	fn check_and_report_equivocation<B: BlockT>(
+		&self,
+		slot_now: Slot,
+		slot: Slot,
+		header: &B::Header,
+		author: &AuthorityId<P>,
+		origin: &BlockOrigin,
+	) -> Result<(), Error<B>> where
+		C: ProvideRuntimeApi<B> + HeaderBackend<B> + AuxStore,
+		C::Api: AuraApi<B, AuthorityId<P>> + ApiExt<B>,
+		P: Pair,
+		P::Public: Encode + Decode + PartialEq + Clone + Debug,
+	{
+		if !self.check_for_equivocation.check_for_equivocation() {
+			return Ok(());
+		}
+
+		// don't report any equivocations during initial sync
+		// as they are most likely stale.
+		if *origin == BlockOrigin::NetworkInitialSync {
+			return Ok(());
+		}
+
+		// check if authorship of this header is an equivocation and return a proof if so.
+		let equivocation_proof =
+			match check_equivocation(&*self.client, slot_now, slot, header, author)
+				.map_err(Error::Client)?
+			{
+				Some(proof) => proof,
+				None => return Ok(()),
+			};
+
+		info!(
+			target: "aura",
+			"Slot author {:?} is equivocating at slot {} with headers {:?} and {:?}",
+			author,
+			slot,
+			equivocation_proof.first_header.hash(),
+			equivocation_proof.second_header.hash(),
+		);
+
+		// the equivocation report is submitted on top of our best block.
+		let best_id = BlockId::Hash(self.client.info().best_hash);
+
+		// runtimes before version 2 of the Aura api don't support equivocation reporting.
+		let can_report = self.client
+			.runtime_api()
+			.has_api_with::<dyn AuraApi<B, AuthorityId<P>>, _>(&best_id, |v| v >= 2)
+			.map_err(|e| Error::Client(e.into()))?;
+
+		if !can_report {
+			debug!(target: "aura", "Runtime does not support reporting equivocations.");
+			return Ok(());
+		}
+
+		// generate a key ownership proof. we start by trying to generate the
+		// key owernship proof at the parent of the equivocating header, this
+		// will make sure that proof generation is successful since it happens
+		// during the on-going session (i.e. session keys are available in the
+		// state to be able to generate the proof). this might fail if the
+		// equivocation happens on the first block of the session, in which case
+		// its parent would be on the previous session. if generation on the
+		// parent header fails we try with best block as well.
+		let generate_key_owner_proof = |block_id: &BlockId<B>| {
+			self.client
+				.runtime_api()
+				.generate_key_ownership_proof(block_id, slot, equivocation_proof.offender.clone())
+				.map_err(|e| Error::Client(e.into()))
+		};
+
+		let parent_id = BlockId::Hash(*header.parent_hash());
+		let key_owner_proof = match generate_key_owner_proof(&parent_id)? {
+			Some(proof) => proof,
+			None => match generate_key_owner_proof(&best_id)? {
+				Some(proof) => proof,
+				None => {
+					debug!(target: "aura", "Equivocation offender is not part of the authority set.");
+					return Ok(());
+				}
+			},
+		};
+
+		// submit equivocation report at best block.
+		self.client
+			.runtime_api()
+			.submit_report_equivocation_unsigned_extrinsic(
+				&best_id,
+				equivocation_proof,
+				key_owner_proof,
+			)
+			.map_err(|e| Error::Client(e.into()))?;
+
+		info!(target: "aura", "Submitted equivocation report for author {:?}", author);
+
+		Ok(())
+	}
+
 	fn check_inherents<B: BlockT>(
 		&self,
 		block: B,
@@ -204,6 +285,7 @@ impl<C, P, CAW> AuraVerifier<C, P, CAW> where
 						thread::sleep(Duration::from_secs(diff));
 						Ok(())
 					},
+					Some(TIError::TooFarInFuture(_)) => Err(Error::TooFarInFuture),
 					Some(TIError::Other(e)) => Err(Error::Runtime(e.into())),
 					None => Err(Error::DataProvider(
 						self.inherent_data_providers.error_to_string(&i, &e)
@@ -222,6 +304,7 @@ impl<B: BlockT, C, P, CAW> Verifier<B> for AuraVerifier<C, P, CAW> where
 		Sync +
 		sc_client_api::backend::AuxStore +
 		ProvideCache<B> +
+		HeaderBackend<B> +
 		BlockOf,
 	C::Api: BlockBuilderApi<B> + AuraApi<B, AuthorityId<P>> + ApiExt<B>,
 	DigestItemFor<B>: CompatibleDigestItem<P::Signature>,
@@ -250,16 +333,29 @@ impl<B: BlockT, C, P, CAW> Verifier<B> for AuraVerifier<C, P, CAW> where
 		// we add one to allow for some small drift.
 		// FIXME #1019 in the future, alter this queue to allow deferring of
 		// headers
-		let checked_header = check_header::<C, B, P>(
-			&self.client,
+		let checked_header = check_header::<B, P>(
 			slot_now + 1,
-			header,
+			header.clone(),
 			hash,
 			&authorities[..],
-			self.check_for_equivocation,
 		).map_err(|e| e.to_string())?;
 		match checked_header {
 			CheckedHeader::Checked(pre_header, (slot, seal)) => {
+				// the header is valid but let's check if there was something else already
+				// proposed at the same slot by the given author. if there was, we will
+				// report the equivocation to the runtime.
+				let author = slot_author::<P>(slot, &authorities)
+					.expect("`check_header` succeeded, thus the slot author exists; qed");
+				if let Err(err) = self.check_and_report_equivocation(
+					slot_now + 1,
+					slot,
+					&header,
+					author,
+					&origin,
+				) {
+					warn!(target: "aura", "Error checking/reporting Aura equivocation: {:?}", err);
+				}
+
 				// if the body is passed through, we need to use the runtime
 				// to check that the internally-set timestamp in the inherents
 				// actually matches the slot set in the seal.
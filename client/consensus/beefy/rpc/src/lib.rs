@@ -0,0 +1,230 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! RPC API for BEEFY.
+
+#![warn(missing_docs)]
+
+use std::sync::Arc;
+
+use futures::{StreamExt, TryStreamExt};
+use jsonrpc_core::futures::{
+	future::Executor as Executor01, future::Future as Future01, sink::Sink as Sink01,
+	stream::Stream as Stream01,
+};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use log::warn;
+
+use sc_consensus_beefy::BeefySignedCommitmentStream;
+use sp_runtime::traits::Block as BlockT;
+
+mod notification;
+
+pub use notification::SignedCommitment;
+
+/// Provides RPC methods for interacting with BEEFY.
+#[rpc]
+pub trait BeefyApi<Notification> {
+	/// RPC Metadata
+	type Metadata;
+
+	/// Returns the block most recently finalized by BEEFY, alongside its justification.
+	#[pubsub(
+		subscription = "beefy_justifications",
+		subscribe,
+		name = "beefy_subscribeJustifications"
+	)]
+	fn subscribe_justifications(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Notification>,
+	);
+
+	/// Unsubscribe from receiving notifications about recently finalized blocks.
+	#[pubsub(
+		subscription = "beefy_justifications",
+		unsubscribe,
+		name = "beefy_unsubscribeJustifications"
+	)]
+	fn unsubscribe_justifications(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> jsonrpc_core::Result<bool>;
+}
+
+/// Implements the BeefyApi RPC trait for interacting with BEEFY.
+pub struct BeefyRpcHandler<Block: BlockT> {
+	signed_commitment_stream: BeefySignedCommitmentStream<Block>,
+	manager: SubscriptionManager,
+}
+
+impl<Block: BlockT> BeefyRpcHandler<Block> {
+	/// Creates a new BeefyRpcHandler instance.
+	pub fn new<E>(signed_commitment_stream: BeefySignedCommitmentStream<Block>, executor: E) -> Self
+	where
+		E: Executor01<Box<dyn Future01<Item = (), Error = ()> + Send>> + Send + Sync + 'static,
+	{
+		let manager = SubscriptionManager::new(Arc::new(executor));
+		Self {
+			signed_commitment_stream,
+			manager,
+		}
+	}
+}
+
+impl<Block> BeefyApi<SignedCommitment> for BeefyRpcHandler<Block>
+where
+	Block: BlockT,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn subscribe_justifications(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<SignedCommitment>,
+	) {
+		let stream = self
+			.signed_commitment_stream
+			.subscribe()
+			.map(|x| Ok::<_, ()>(SignedCommitment::new::<Block>(x)))
+			.map_err(|e| warn!("Notification stream error: {:?}", e))
+			.compat();
+
+		self.manager.add(subscriber, |sink| {
+			let stream = stream.map(|res| Ok(res));
+			sink.sink_map_err(|e| warn!("Error sending notifications: {:?}", e))
+				.send_all(stream)
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_justifications(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> jsonrpc_core::Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpc_core::{types::Params, Notification, Output};
+	use parity_scale_codec::Encode;
+
+	use sc_consensus_beefy::{BeefySignedCommitmentSender, BeefySignedCommitmentStream};
+	use substrate_test_runtime_client::runtime::Block;
+
+	fn setup_io_handler() -> (
+		jsonrpc_core::MetaIoHandler<sc_rpc::Metadata>,
+		BeefySignedCommitmentSender<Block>,
+	) {
+		let (commitment_sender, commitment_stream) = BeefySignedCommitmentStream::channel();
+
+		let handler = BeefyRpcHandler::new(commitment_stream, sc_rpc::testing::TaskExecutor);
+
+		let mut io = jsonrpc_core::MetaIoHandler::default();
+		io.extend_with(BeefyApi::to_delegate(handler));
+
+		(io, commitment_sender)
+	}
+
+	fn setup_session() -> (sc_rpc::Metadata, jsonrpc_core::futures::sync::mpsc::Receiver<String>) {
+		let (tx, rx) = jsonrpc_core::futures::sync::mpsc::channel(1);
+		let meta = sc_rpc::Metadata::new(tx);
+		(meta, rx)
+	}
+
+	#[test]
+	fn subscribe_and_unsubscribe_to_justifications() {
+		let (io, _) = setup_io_handler();
+		let (meta, _) = setup_session();
+
+		// Subscribe
+		let sub_request =
+			r#"{"jsonrpc":"2.0","method":"beefy_subscribeJustifications","params":[],"id":1}"#;
+		let resp = io.handle_request_sync(sub_request, meta.clone());
+		let resp: Output = serde_json::from_str(&resp.unwrap()).unwrap();
+
+		let sub_id = match resp {
+			Output::Success(success) => success.result,
+			_ => panic!(),
+		};
+
+		// Unsubscribe
+		let unsub_req = format!(
+			"{{\"jsonrpc\":\"2.0\",\"method\":\"beefy_unsubscribeJustifications\",\"params\":[{}],\"id\":1}}",
+			sub_id
+		);
+		assert_eq!(
+			io.handle_request_sync(&unsub_req, meta.clone()),
+			Some(r#"{"jsonrpc":"2.0","result":true,"id":1}"#.into()),
+		);
+
+		// Unsubscribe again and fail
+		assert_eq!(
+			io.handle_request_sync(&unsub_req, meta),
+			Some(r#"{"jsonrpc":"2.0","result":false,"id":1}"#.into()),
+		);
+	}
+
+	#[test]
+	fn subscribe_and_listen_to_one_justification() {
+		let (io, commitment_sender) = setup_io_handler();
+		let (meta, receiver) = setup_session();
+
+		// Subscribe
+		let sub_request =
+			r#"{"jsonrpc":"2.0","method":"beefy_subscribeJustifications","params":[],"id":1}"#;
+
+		let resp = io.handle_request_sync(sub_request, meta.clone());
+		let mut resp: serde_json::Value = serde_json::from_str(&resp.unwrap()).unwrap();
+		let sub_id: String = serde_json::from_value(resp["result"].take()).unwrap();
+
+		// Notify with a signed commitment
+		let commitment = sp_consensus_beefy::SignedCommitment {
+			commitment: sp_consensus_beefy::Commitment {
+				payload: Default::default(),
+				block_number: 5,
+				validator_set_id: 0,
+			},
+			signatures: vec![],
+		};
+		commitment_sender.notify(commitment.clone());
+
+		// Inspect what we received
+		let recv = receiver.take(1).wait().flatten().collect::<Vec<_>>();
+		let recv: Notification = serde_json::from_str(&recv[0]).unwrap();
+		let mut json_map = match recv.params {
+			Params::Map(json_map) => json_map,
+			_ => panic!(),
+		};
+
+		let recv_sub_id: String = serde_json::from_value(json_map["subscription"].take()).unwrap();
+		let recv_commitment: sp_core::Bytes =
+			serde_json::from_value(json_map["result"].take()).unwrap();
+
+		assert_eq!(recv.method, "beefy_justifications");
+		assert_eq!(recv_sub_id, sub_id);
+		assert_eq!(recv_commitment, commitment.encode().into());
+	}
+}
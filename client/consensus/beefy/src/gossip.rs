@@ -0,0 +1,249 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+use codec::{Decode, Encode};
+use log::trace;
+use parking_lot::RwLock;
+
+use sc_network::PeerId;
+use sc_network_gossip::{MessageIntent, ValidationResult, Validator, ValidatorContext};
+use sp_runtime::traits::{Block as BlockT, Hash, Header as HeaderT, NumberFor};
+
+use sp_consensus_beefy::{
+	crypto::{Public, Signature},
+	MmrRootHash, VoteMessage,
+};
+
+use crate::keystore::BeefyKeystore;
+
+/// The maximum number of live gossip rounds allowed, i.e. we will expire messages older than this.
+const MAX_LIVE_GOSSIP_ROUNDS: usize = 5;
+
+/// A vote message as gossiped by BEEFY voters.
+pub(crate) type GossipVote<B> = VoteMessage<MmrRootHash, NumberFor<B>, Public, Signature>;
+
+/// Gossip engine messages topic
+pub(crate) fn topic<B>() -> B::Hash
+where
+	B: BlockT,
+{
+	<<B::Header as HeaderT>::Hashing as Hash>::hash(b"beefy")
+}
+
+/// BEEFY gossip validator
+///
+/// Validate BEEFY gossip messages and limit the number of live BEEFY voting rounds.
+///
+/// Allows messages for at most [`MAX_LIVE_GOSSIP_ROUNDS`] distinct rounds. Messages for rounds
+/// older than the oldest live round are considered expired and will be discarded.
+pub(crate) struct GossipValidator<B>
+where
+	B: BlockT,
+{
+	topic: B::Hash,
+	live_rounds: RwLock<Vec<NumberFor<B>>>,
+}
+
+impl<B> GossipValidator<B>
+where
+	B: BlockT,
+{
+	pub fn new() -> GossipValidator<B> {
+		GossipValidator {
+			topic: topic::<B>(),
+			live_rounds: RwLock::new(Vec::new()),
+		}
+	}
+
+	/// Note a voting round.
+	///
+	/// Noting `round` will keep `round` live, as long as at most [`MAX_LIVE_GOSSIP_ROUNDS`]
+	/// newer rounds are noted.
+	pub(crate) fn note_round(&self, round: NumberFor<B>) {
+		trace!(target: "beefy", "🥩 About to note round #{}", round);
+
+		let mut live = self.live_rounds.write();
+
+		if !live.contains(&round) {
+			live.push(round);
+			live.sort();
+
+			if live.len() > MAX_LIVE_GOSSIP_ROUNDS {
+				live.remove(0);
+			}
+		}
+	}
+
+	/// A round is expired if it is older than the oldest live round. As long as no round has
+	/// been noted, nothing is considered expired.
+	fn is_expired(live_rounds: &[NumberFor<B>], round: NumberFor<B>) -> bool {
+		live_rounds.first().map(|oldest| round < *oldest).unwrap_or(false)
+	}
+
+	fn expired_message(&self, mut data: &[u8]) -> bool {
+		match GossipVote::<B>::decode(&mut data) {
+			Ok(msg) => {
+				let live_rounds = self.live_rounds.read();
+				Self::is_expired(&live_rounds, msg.commitment.block_number)
+			}
+			Err(_) => true,
+		}
+	}
+}
+
+impl<B> Validator<B> for GossipValidator<B>
+where
+	B: BlockT,
+{
+	fn validate(
+		&self,
+		_context: &mut dyn ValidatorContext<B>,
+		sender: &PeerId,
+		mut data: &[u8],
+	) -> ValidationResult<B::Hash> {
+		if let Ok(msg) = GossipVote::<B>::decode(&mut data) {
+			if BeefyKeystore::verify(&msg.id, &msg.signature, &msg.commitment.encode()) {
+				return ValidationResult::ProcessAndKeep(self.topic);
+			} else {
+				trace!(
+					target: "beefy",
+					"🥩 Bad signature on message: {:?}, from: {:?}",
+					msg,
+					sender,
+				);
+			}
+		}
+
+		ValidationResult::Discard
+	}
+
+	fn message_expired<'a>(&'a self) -> Box<dyn FnMut(B::Hash, &[u8]) -> bool + 'a> {
+		Box::new(move |_topic, data| self.expired_message(data))
+	}
+
+	fn message_allowed<'a>(
+		&'a self,
+	) -> Box<dyn FnMut(&PeerId, MessageIntent, &B::Hash, &[u8]) -> bool + 'a> {
+		Box::new(move |_who, _intent, _topic, data| !self.expired_message(data))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use codec::Encode;
+	use sc_network_gossip::{ValidationResult, Validator, ValidatorContext};
+	use sp_keystore::{testing::KeyStore, SyncCryptoStore, SyncCryptoStorePtr};
+	use substrate_test_runtime_client::runtime::Block;
+
+	use sp_consensus_beefy::{crypto::Public, Commitment, MmrRootHash, VoteMessage, KEY_TYPE};
+
+	use super::{GossipValidator, GossipVote, MAX_LIVE_GOSSIP_ROUNDS};
+	use crate::keystore::BeefyKeystore;
+
+	struct TestContext;
+
+	impl<B: sp_runtime::traits::Block> ValidatorContext<B> for TestContext {
+		fn broadcast_topic(&mut self, _topic: B::Hash, _force: bool) {}
+
+		fn broadcast_message(&mut self, _topic: B::Hash, _message: Vec<u8>, _force: bool) {}
+
+		fn send_message(&mut self, _who: &sc_network::PeerId, _message: Vec<u8>) {}
+
+		fn send_topic(&mut self, _who: &sc_network::PeerId, _topic: B::Hash, _force: bool) {}
+
+		fn report_peer(
+			&mut self,
+			_who: &sc_network::PeerId,
+			_reputation: sc_network::ReputationChange,
+		) {}
+	}
+
+	fn vote(block_number: u64, tamper: bool) -> GossipVote<Block> {
+		let store: SyncCryptoStorePtr = Arc::new(KeyStore::new());
+		let id: Public = SyncCryptoStore::ecdsa_generate_new(&*store, KEY_TYPE, Some("//Alice"))
+			.unwrap()
+			.into();
+
+		let commitment = Commitment {
+			payload: MmrRootHash::repeat_byte(0x42),
+			block_number,
+			validator_set_id: 0,
+		};
+		let signature = BeefyKeystore::from(Some(store)).sign(&id, &commitment.encode()).unwrap();
+
+		let commitment = if tamper {
+			Commitment { block_number: block_number + 1, ..commitment }
+		} else {
+			commitment
+		};
+
+		VoteMessage { commitment, id, signature }
+	}
+
+	#[test]
+	fn note_round_keeps_the_latest_rounds() {
+		let gv = GossipValidator::<Block>::new();
+
+		gv.note_round(1u64);
+		gv.note_round(3u64);
+		gv.note_round(1u64);
+
+		assert_eq!(*gv.live_rounds.read(), vec![1, 3]);
+
+		for round in 4..(4 + MAX_LIVE_GOSSIP_ROUNDS as u64) {
+			gv.note_round(round);
+		}
+
+		assert_eq!(gv.live_rounds.read().len(), MAX_LIVE_GOSSIP_ROUNDS);
+		assert_eq!(*gv.live_rounds.read(), vec![4, 5, 6, 7, 8]);
+	}
+
+	#[test]
+	fn validate_checks_the_signature() {
+		let gv = GossipValidator::<Block>::new();
+		let sender = sc_network::PeerId::random();
+
+		let res = gv.validate(&mut TestContext, &sender, &vote(1, false).encode());
+		assert!(matches!(res, ValidationResult::ProcessAndKeep(_)));
+
+		let res = gv.validate(&mut TestContext, &sender, &vote(1, true).encode());
+		assert!(matches!(res, ValidationResult::Discard));
+
+		let res = gv.validate(&mut TestContext, &sender, b"garbage");
+		assert!(matches!(res, ValidationResult::Discard));
+	}
+
+	#[test]
+	fn messages_for_old_rounds_expire() {
+		let gv = GossipValidator::<Block>::new();
+		let topic = super::topic::<Block>();
+
+		// nothing is expired as long as no round was noted
+		assert!(!gv.message_expired()(topic, &vote(1, false).encode()));
+
+		gv.note_round(5u64);
+
+		assert!(gv.message_expired()(topic, &vote(4, false).encode()));
+		assert!(!gv.message_expired()(topic, &vote(5, false).encode()));
+		assert!(!gv.message_expired()(topic, &vote(6, false).encode()));
+		assert!(gv.message_expired()(topic, b"garbage"));
+	}
+}
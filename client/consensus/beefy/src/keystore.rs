@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::Decode;
+use sp_application_crypto::{Pair as _, Public as _};
+use sp_core::{crypto::CryptoTypePublicPair, ecdsa};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+
+use sp_consensus_beefy::{
+	crypto::{Pair, Public, Signature},
+	KEY_TYPE,
+};
+
+use crate::error;
+
+/// A BEEFY specific keystore implemented as a `Newtype`. This is basically a
+/// wrapper around [`sp_keystore::SyncCryptoStore`] and allows to customize
+/// common cryptographic functionality.
+pub(crate) struct BeefyKeystore(Option<SyncCryptoStorePtr>);
+
+impl BeefyKeystore {
+	/// Check if the keystore contains a private key for one of the public keys
+	/// contained in `keys`. A public key with a matching private key is known
+	/// as a local authority id.
+	///
+	/// Return the public key for which we also do have a private key. If no
+	/// matching private key is found, `None` will be returned.
+	pub fn authority_id(&self, keys: &[Public]) -> Option<Public> {
+		let store = self.0.clone()?;
+
+		keys.iter()
+			.find(|key| SyncCryptoStore::has_keys(&*store, &[(key.to_raw_vec(), KEY_TYPE)]))
+			.cloned()
+	}
+
+	/// Sign `message` with the `public` key.
+	///
+	/// Note that `message` is hashed before signing.
+	///
+	/// Return the message signature or an error in case of failure.
+	pub fn sign(&self, public: &Public, message: &[u8]) -> Result<Signature, error::Error> {
+		let store = self.0.clone().ok_or_else(|| error::Error::Keystore("no Keystore".into()))?;
+
+		let key = CryptoTypePublicPair(ecdsa::CRYPTO_ID, public.to_raw_vec());
+
+		let sig = SyncCryptoStore::sign_with(&*store, KEY_TYPE, &key, message)
+			.map_err(|e| error::Error::Keystore(e.to_string()))?
+			.ok_or_else(|| error::Error::Signature("sign_with() failed".to_string()))?;
+
+		Signature::decode(&mut &sig[..]).map_err(|_| {
+			error::Error::Signature("invalid signature encoding".to_string())
+		})
+	}
+
+	/// Use the `public` key to verify that `sig` is a valid signature for `message`.
+	///
+	/// Return `true` if the signature is authentic, `false` otherwise.
+	pub fn verify(public: &Public, sig: &Signature, message: &[u8]) -> bool {
+		Pair::verify(sig, message, public)
+	}
+}
+
+impl From<Option<SyncCryptoStorePtr>> for BeefyKeystore {
+	fn from(store: Option<SyncCryptoStorePtr>) -> BeefyKeystore {
+		BeefyKeystore(store)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use sp_core::{ecdsa, Pair as _};
+	use sp_keystore::{testing::KeyStore, SyncCryptoStore, SyncCryptoStorePtr};
+
+	use sp_consensus_beefy::{crypto::Public, KEY_TYPE};
+
+	use super::BeefyKeystore;
+	use crate::error::Error;
+
+	fn keystore() -> SyncCryptoStorePtr {
+		Arc::new(KeyStore::new())
+	}
+
+	fn add_key(store: &SyncCryptoStorePtr, seed: &str) -> Public {
+		SyncCryptoStore::ecdsa_generate_new(&**store, KEY_TYPE, Some(seed)).unwrap().into()
+	}
+
+	fn public(seed: &str) -> Public {
+		ecdsa::Pair::from_string(seed, None).unwrap().public().into()
+	}
+
+	#[test]
+	fn authority_id_works() {
+		let store = keystore();
+		let alice = add_key(&store, "//Alice");
+		let bob = public("//Bob");
+		let charlie = public("//Charlie");
+
+		let store: BeefyKeystore = Some(store).into();
+
+		assert_eq!(store.authority_id(&[bob.clone(), alice.clone()]), Some(alice));
+		assert_eq!(store.authority_id(&[bob, charlie]), None);
+	}
+
+	#[test]
+	fn sign_and_verify_works() {
+		let store = keystore();
+		let alice = add_key(&store, "//Alice");
+		let bob = public("//Bob");
+
+		let store: BeefyKeystore = Some(store).into();
+
+		let msg = b"are you involved or committed?";
+		let sig = store.sign(&alice, msg).unwrap();
+
+		assert!(BeefyKeystore::verify(&alice, &sig, msg));
+		assert!(!BeefyKeystore::verify(&alice, &sig, b"a different message"));
+		assert!(!BeefyKeystore::verify(&bob, &sig, msg));
+	}
+
+	#[test]
+	fn sign_without_keystore_or_key_fails() {
+		let alice = public("//Alice");
+
+		let store: BeefyKeystore = None.into();
+		assert_eq!(store.authority_id(&[alice.clone()]), None);
+		assert_eq!(
+			store.sign(&alice, b"message"),
+			Err(Error::Keystore("no Keystore".to_string())),
+		);
+
+		let store: BeefyKeystore = Some(keystore()).into();
+		assert_eq!(
+			store.sign(&alice, b"message"),
+			Err(Error::Signature("sign_with() failed".to_string())),
+		);
+	}
+}
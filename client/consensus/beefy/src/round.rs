@@ -0,0 +1,214 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+use std::collections::BTreeMap;
+
+use sp_consensus_beefy::{ValidatorSet, ValidatorSetId};
+
+/// Votes collected for a single round.
+struct RoundTracker<Id, Signature> {
+	votes: Vec<(Id, Signature)>,
+}
+
+impl<Id, Signature> Default for RoundTracker<Id, Signature> {
+	fn default() -> Self {
+		RoundTracker { votes: Vec::new() }
+	}
+}
+
+impl<Id, Signature> RoundTracker<Id, Signature>
+where
+	Id: PartialEq,
+	Signature: PartialEq,
+{
+	fn add_vote(&mut self, vote: (Id, Signature)) -> bool {
+		// this needs to handle equivocations in the future
+		if self.votes.contains(&vote) {
+			return false;
+		}
+
+		self.votes.push(vote);
+		true
+	}
+
+	fn is_done(&self, threshold: usize) -> bool {
+		self.votes.len() >= threshold
+	}
+}
+
+/// Keeps track of all voting rounds of the currently active validator set.
+///
+/// A round is identified by the payload and the block number being voted on.
+pub(crate) struct Rounds<Hash, Number, Id, Signature> {
+	rounds: BTreeMap<(Hash, Number), RoundTracker<Id, Signature>>,
+	validator_set: ValidatorSet<Id>,
+}
+
+impl<Hash, Number, Id, Signature> Rounds<Hash, Number, Id, Signature>
+where
+	Hash: Ord,
+	Number: Ord,
+	Id: PartialEq + Clone,
+	Signature: PartialEq + Clone,
+{
+	pub(crate) fn new(validator_set: ValidatorSet<Id>) -> Self {
+		Rounds {
+			rounds: BTreeMap::new(),
+			validator_set,
+		}
+	}
+
+	pub(crate) fn validator_set_id(&self) -> ValidatorSetId {
+		self.validator_set.id
+	}
+
+	pub(crate) fn validators(&self) -> &[Id] {
+		&self.validator_set.validators
+	}
+
+	/// Add a vote for `round`.
+	///
+	/// Returns `false` if the vote was cast by an id which is not part of the validator set,
+	/// or if the same vote was already added before.
+	pub(crate) fn add_vote(&mut self, round: (Hash, Number), vote: (Id, Signature)) -> bool {
+		if !self.validator_set.validators.contains(&vote.0) {
+			return false;
+		}
+
+		self.rounds.entry(round).or_default().add_vote(vote)
+	}
+
+	/// Whether `round` has collected enough votes to be concluded.
+	pub(crate) fn is_done(&self, round: &(Hash, Number)) -> bool {
+		self.rounds
+			.get(round)
+			.map(|tracker| tracker.is_done(self.validator_set.threshold()))
+			.unwrap_or(false)
+	}
+
+	/// Conclude `round` and return the collected signatures.
+	///
+	/// The signatures are ordered like the validators of the active set, with `None` for
+	/// validators which didn't vote. All rounds for blocks up to and including the
+	/// concluded one are dropped.
+	pub(crate) fn conclude(&mut self, round: &(Hash, Number)) -> Option<Vec<Option<Signature>>> {
+		let votes = self.rounds.remove(round)?.votes;
+		self.rounds = std::mem::take(&mut self.rounds)
+			.into_iter()
+			.filter(|((_, number), _)| *number > round.1)
+			.collect();
+
+		Some(
+			self.validator_set
+				.validators
+				.iter()
+				.map(|authority_id| {
+					votes.iter().find_map(|(id, sig)| {
+						if id == authority_id {
+							Some(sig.clone())
+						} else {
+							None
+						}
+					})
+				})
+				.collect(),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_consensus_beefy::ValidatorSet;
+
+	use super::{RoundTracker, Rounds};
+
+	fn validator_set() -> ValidatorSet<&'static str> {
+		ValidatorSet {
+			validators: vec!["alice", "bob", "charlie", "dave"],
+			id: 42,
+		}
+	}
+
+	#[test]
+	fn round_tracker_works() {
+		let mut rt = RoundTracker::default();
+
+		assert!(rt.add_vote(("alice", "sig_a")));
+		assert!(!rt.add_vote(("alice", "sig_a")));
+		assert!(rt.add_vote(("bob", "sig_b")));
+
+		assert!(!rt.is_done(3));
+		assert!(rt.is_done(2));
+	}
+
+	#[test]
+	fn new_rounds() {
+		let rounds = Rounds::<u8, u32, _, &str>::new(validator_set());
+
+		assert_eq!(42, rounds.validator_set_id());
+		assert_eq!(&["alice", "bob", "charlie", "dave"], rounds.validators());
+		assert!(!rounds.is_done(&(1, 1)));
+	}
+
+	#[test]
+	fn add_vote_requires_a_validator() {
+		let mut rounds = Rounds::<u8, u32, _, _>::new(validator_set());
+
+		assert!(!rounds.add_vote((1, 1), ("eve", "sig_e")));
+		assert!(rounds.add_vote((1, 1), ("alice", "sig_a")));
+		assert!(!rounds.add_vote((1, 1), ("alice", "sig_a")));
+	}
+
+	#[test]
+	fn conclude_round_works() {
+		let mut rounds = Rounds::<u8, u32, _, _>::new(validator_set());
+
+		assert!(rounds.add_vote((1, 1), ("alice", "sig_a")));
+		assert!(rounds.add_vote((1, 1), ("dave", "sig_d")));
+		assert!(rounds.add_vote((2, 2), ("bob", "sig_b")));
+		assert!(!rounds.is_done(&(1, 1)));
+
+		assert!(rounds.add_vote((1, 1), ("charlie", "sig_c")));
+		assert!(rounds.is_done(&(1, 1)));
+
+		assert_eq!(
+			rounds.conclude(&(1, 1)),
+			Some(vec![Some("sig_a"), None, Some("sig_c"), Some("sig_d")]),
+		);
+		assert_eq!(rounds.conclude(&(1, 1)), None);
+
+		// rounds for later blocks are kept
+		assert!(rounds.add_vote((2, 2), ("alice", "sig_a")));
+		assert!(rounds.add_vote((2, 2), ("dave", "sig_d")));
+		assert!(rounds.is_done(&(2, 2)));
+	}
+
+	#[test]
+	fn conclude_drops_older_rounds() {
+		let mut rounds = Rounds::<u8, u32, _, _>::new(validator_set());
+
+		assert!(rounds.add_vote((1, 1), ("alice", "sig_a")));
+		assert!(rounds.add_vote((3, 3), ("alice", "sig_a")));
+		assert!(rounds.add_vote((3, 3), ("bob", "sig_b")));
+		assert!(rounds.add_vote((3, 3), ("charlie", "sig_c")));
+
+		assert!(rounds.conclude(&(3, 3)).is_some());
+		assert_eq!(rounds.conclude(&(1, 1)), None);
+	}
+}
@@ -0,0 +1,324 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+use std::sync::Arc;
+
+use codec::{Codec, Decode, Encode};
+use futures::{future, FutureExt, StreamExt};
+use log::{debug, error, info, trace, warn};
+use parking_lot::Mutex;
+
+use sc_client_api::FinalityNotification;
+use sc_network_gossip::GossipEngine;
+
+use sp_runtime::{
+	generic::{BlockId, OpaqueDigestItemId},
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, Saturating},
+};
+
+use sp_consensus_beefy::{
+	crypto::{Public, Signature},
+	BeefyApi, Commitment, ConsensusLog, MmrRootHash, SignedCommitment, ValidatorSet,
+	VoteMessage, BEEFY_ENGINE_ID,
+};
+
+use crate::{
+	gossip::{topic, GossipValidator, GossipVote},
+	keystore::BeefyKeystore,
+	notification::BeefySignedCommitmentSender,
+	round::Rounds,
+	Client,
+};
+
+pub(crate) struct WorkerParams<B, C>
+where
+	B: BlockT,
+{
+	pub client: Arc<C>,
+	pub key_store: BeefyKeystore,
+	pub signed_commitment_sender: BeefySignedCommitmentSender<B>,
+	pub gossip_engine: GossipEngine<B>,
+	pub gossip_validator: Arc<GossipValidator<B>>,
+	pub min_block_delta: u32,
+}
+
+/// A BEEFY worker plays the BEEFY protocol
+pub(crate) struct BeefyWorker<B, C>
+where
+	B: BlockT,
+	C: Client<B>,
+{
+	client: Arc<C>,
+	key_store: BeefyKeystore,
+	signed_commitment_sender: BeefySignedCommitmentSender<B>,
+	gossip_engine: Arc<Mutex<GossipEngine<B>>>,
+	gossip_validator: Arc<GossipValidator<B>>,
+	/// Min delta in block numbers between two blocks, BEEFY should vote on
+	min_block_delta: u32,
+	/// Voting rounds of the active validator set, `None` until the first finalized block
+	/// has been seen.
+	rounds: Option<Rounds<MmrRootHash, NumberFor<B>, Public, Signature>>,
+	/// Best block a BEEFY voting round has been concluded for
+	best_beefy_block: Option<NumberFor<B>>,
+}
+
+impl<B, C> BeefyWorker<B, C>
+where
+	B: BlockT,
+	C: Client<B>,
+	C::Api: BeefyApi<B>,
+{
+	/// Return a new BEEFY worker instance.
+	///
+	/// Note that a BEEFY worker is only fully functional if a corresponding
+	/// BEEFY pallet has been deployed on-chain.
+	///
+	/// The BEEFY pallet is needed in order to keep track of the BEEFY authority set.
+	pub(crate) fn new(worker_params: WorkerParams<B, C>) -> Self {
+		let WorkerParams {
+			client,
+			key_store,
+			signed_commitment_sender,
+			gossip_engine,
+			gossip_validator,
+			min_block_delta,
+		} = worker_params;
+
+		BeefyWorker {
+			client,
+			key_store,
+			signed_commitment_sender,
+			gossip_engine: Arc::new(Mutex::new(gossip_engine)),
+			gossip_validator,
+			min_block_delta,
+			rounds: None,
+			best_beefy_block: None,
+		}
+	}
+
+	/// Return `true`, if we should vote on block `number`
+	fn should_vote_on(&self, number: NumberFor<B>) -> bool {
+		let best_beefy_block = match self.best_beefy_block {
+			Some(block) => block,
+			None => return true,
+		};
+
+		number > best_beefy_block &&
+			number.saturating_sub(best_beefy_block) >= self.min_block_delta.into()
+	}
+
+	/// Return the validator set to be used for voting on block `header`.
+	///
+	/// A new validator set is announced in the digest of the block enacting it. If the worker
+	/// doesn't know any validator set yet, it is fetched from the runtime.
+	fn new_validator_set(&self, header: &B::Header) -> Option<ValidatorSet<Public>> {
+		if let Some(validator_set) = find_authorities_change::<B, Public>(header) {
+			return Some(validator_set);
+		}
+
+		if self.rounds.is_some() {
+			return None;
+		}
+
+		self.client
+			.runtime_api()
+			.validator_set(&BlockId::hash(header.hash()))
+			.map_err(|e| warn!(target: "beefy", "🥩 Failed to fetch validator set: {:?}", e))
+			.ok()
+	}
+
+	fn handle_finality_notification(&mut self, notification: FinalityNotification<B>) {
+		trace!(target: "beefy", "🥩 Finality notification: {:?}", notification);
+
+		let header = notification.header;
+		let number = *header.number();
+
+		// the first block of a new validator set is mandatory and always voted on
+		let mandatory = if let Some(active) = self.new_validator_set(&header) {
+			debug!(target: "beefy", "🥩 New active validator set id: {:?}", active);
+			self.rounds = Some(Rounds::new(active));
+			true
+		} else {
+			false
+		};
+
+		if !mandatory && !self.should_vote_on(number) {
+			return;
+		}
+
+		let rounds = match self.rounds.as_ref() {
+			Some(rounds) => rounds,
+			None => return,
+		};
+
+		self.gossip_validator.note_round(number);
+
+		let authority_id = if let Some(id) = self.key_store.authority_id(rounds.validators()) {
+			debug!(target: "beefy", "🥩 Local authority id: {:?}", id);
+			id
+		} else {
+			debug!(
+				target: "beefy",
+				"🥩 Missing validator id - can't vote on: {:?}",
+				header.hash(),
+			);
+			return;
+		};
+
+		let mmr_root = if let Some(hash) = find_mmr_root_digest::<B, Public>(&header) {
+			hash
+		} else {
+			warn!(target: "beefy", "🥩 No MMR root digest found for: {:?}", header.hash());
+			return;
+		};
+
+		let commitment = Commitment {
+			payload: mmr_root,
+			block_number: number,
+			validator_set_id: rounds.validator_set_id(),
+		};
+
+		let signature = match self.key_store.sign(&authority_id, &commitment.encode()) {
+			Ok(sig) => sig,
+			Err(err) => {
+				warn!(target: "beefy", "🥩 Error signing commitment: {:?}", err);
+				return;
+			}
+		};
+
+		let message = VoteMessage {
+			commitment,
+			id: authority_id,
+			signature,
+		};
+
+		self.gossip_engine.lock().gossip_message(topic::<B>(), message.encode(), false);
+
+		debug!(target: "beefy", "🥩 Sent vote message: {:?}", message);
+
+		self.handle_vote(message);
+	}
+
+	fn handle_vote(&mut self, vote: GossipVote<B>) {
+		let rounds = match self.rounds.as_mut() {
+			Some(rounds) => rounds,
+			None => return,
+		};
+
+		// votes for other validator sets can't be counted
+		if vote.commitment.validator_set_id != rounds.validator_set_id() {
+			trace!(target: "beefy", "🥩 Ignoring vote for another validator set: {:?}", vote);
+			return;
+		}
+
+		let round = (vote.commitment.payload, vote.commitment.block_number);
+
+		if rounds.add_vote(round, (vote.id, vote.signature)) && rounds.is_done(&round) {
+			if let Some(signatures) = rounds.conclude(&round) {
+				let signed_commitment = SignedCommitment {
+					commitment: Commitment {
+						payload: round.0,
+						block_number: round.1,
+						validator_set_id: rounds.validator_set_id(),
+					},
+					signatures,
+				};
+
+				info!(
+					target: "beefy",
+					"🥩 Round #{} concluded, committed: {:?}.",
+					round.1,
+					signed_commitment,
+				);
+
+				self.signed_commitment_sender.notify(signed_commitment);
+				self.best_beefy_block = Some(round.1);
+			}
+		}
+	}
+
+	pub(crate) async fn run(mut self) {
+		let mut votes = Box::pin(self.gossip_engine.lock().messages_for(topic::<B>()).filter_map(
+			|notification| async move {
+				trace!(target: "beefy", "🥩 Got vote message: {:?}", notification);
+
+				GossipVote::<B>::decode(&mut &notification.message[..]).ok()
+			},
+		));
+
+		let mut finality_notifications = self.client.finality_notification_stream();
+
+		loop {
+			let engine = self.gossip_engine.clone();
+			let gossip_engine = future::poll_fn(|cx| engine.lock().poll_unpin(cx));
+
+			futures::select! {
+				notification = finality_notifications.next().fuse() => {
+					if let Some(notification) = notification {
+						self.handle_finality_notification(notification);
+					} else {
+						return;
+					}
+				},
+				vote = votes.next().fuse() => {
+					if let Some(vote) = vote {
+						self.handle_vote(vote);
+					} else {
+						return;
+					}
+				},
+				_ = gossip_engine.fuse() => {
+					error!(target: "beefy", "🥩 Gossip engine has terminated.");
+					return;
+				}
+			}
+		}
+	}
+}
+
+/// Extract the MMR root hash from a digest in the given header, if it exists.
+fn find_mmr_root_digest<B, Id>(header: &B::Header) -> Option<MmrRootHash>
+where
+	B: BlockT,
+	Id: Codec,
+{
+	header.digest().logs().iter().find_map(|log| {
+		match log.try_to::<ConsensusLog<Id>>(OpaqueDigestItemId::Consensus(&BEEFY_ENGINE_ID)) {
+			Some(ConsensusLog::MmrRoot(root)) => Some(root),
+			_ => None,
+		}
+	})
+}
+
+/// Scan the `header` digest log for a BEEFY validator set change. Return either the new
+/// validator set or `None` in case no validator set change has been signaled.
+fn find_authorities_change<B, Id>(header: &B::Header) -> Option<ValidatorSet<Id>>
+where
+	B: BlockT,
+	Id: Codec,
+{
+	let id = OpaqueDigestItemId::Consensus(&BEEFY_ENGINE_ID);
+
+	let filter = |log: ConsensusLog<Id>| match log {
+		ConsensusLog::AuthoritiesChange(validator_set) => Some(validator_set),
+		_ => None,
+	};
+
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter))
+}
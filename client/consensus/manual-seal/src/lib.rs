@@ -30,7 +30,7 @@ use sp_inherents::InherentDataProviders;
 use sp_runtime::{traits::Block as BlockT, Justifications, ConsensusEngineId};
 use sc_client_api::backend::{Backend as ClientBackend, Finalizer};
 use sc_transaction_pool::txpool;
-use std::{sync::Arc, marker::PhantomData};
+use std::{sync::Arc, marker::PhantomData, time::Duration};
 use prometheus_endpoint::Registry;
 
 mod error;
@@ -145,6 +145,36 @@ pub struct InstantSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, A: txpoo
 	pub inherent_data_providers: InherentDataProviders,
 }
 
+/// Params required to start the interval sealing authorship task.
+pub struct IntervalSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, A: txpool::ChainApi, SC> {
+	/// Block import instance for well. importing blocks.
+	pub block_import: BI,
+
+	/// The environment we are producing blocks for.
+	pub env: E,
+
+	/// Client instance
+	pub client: Arc<C>,
+
+	/// Shared reference to the transaction pool.
+	pub pool: Arc<txpool::Pool<A>>,
+
+	/// SelectChain strategy.
+	pub select_chain: SC,
+
+	/// Digest provider for inclusion in blocks.
+	pub consensus_data_provider: Option<Box<dyn ConsensusDataProvider<B, Transaction = TransactionFor<C, B>>>>,
+
+	/// Provider for inherents to include in blocks.
+	pub inherent_data_providers: InherentDataProviders,
+
+	/// Time between two blocks.
+	pub interval: Duration,
+
+	/// Whether the created blocks should be finalized right away.
+	pub finalize: bool,
+}
+
 /// Creates the background authorship task for the manual seal engine.
 pub async fn run_manual_seal<B, BI, CB, E, C, A, SC, CS>(
 	ManualSealParams {
@@ -266,6 +296,61 @@ pub async fn run_instant_seal<B, BI, CB, E, C, A, SC>(
 	).await
 }
 
+/// runs the background authorship task for the interval seal engine.
+/// interval-seal creates a new block every `interval`, even if there are
+/// no transactions in the transaction pool.
+pub async fn run_interval_seal<B, BI, CB, E, C, A, SC>(
+	IntervalSealParams {
+		block_import,
+		env,
+		client,
+		pool,
+		select_chain,
+		consensus_data_provider,
+		inherent_data_providers,
+		interval,
+		finalize,
+	}: IntervalSealParams<B, BI, E, C, A, SC>
+)
+	where
+		A: txpool::ChainApi<Block=B> + 'static,
+		B: BlockT + 'static,
+		BI: BlockImport<B, Error = sp_consensus::Error, Transaction = sp_api::TransactionFor<C, B>>
+			+ Send + Sync + 'static,
+		C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+		CB: ClientBackend<B> + 'static,
+		E: Environment<B> + 'static,
+		E::Proposer: Proposer<B, Transaction = TransactionFor<C, B>>,
+		SC: SelectChain<B> + 'static,
+		TransactionFor<C, B>: 'static,
+{
+	let commands_stream = stream::unfold((), move |_| async move {
+		futures_timer::Delay::new(interval).await;
+		Some((
+			EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize,
+				parent_hash: None,
+				sender: None,
+			},
+			(),
+		))
+	}).boxed();
+
+	run_manual_seal(
+		ManualSealParams {
+			block_import,
+			env,
+			client,
+			pool,
+			commands_stream,
+			select_chain,
+			consensus_data_provider,
+			inherent_data_providers,
+		}
+	).await
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -362,6 +447,53 @@ mod tests {
 		assert!(client.header(&BlockId::Number(1)).unwrap().is_some())
 	}
 
+	#[tokio::test]
+	async fn interval_seal() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let inherent_data_providers = InherentDataProviders::new();
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(), true.into(), api(), None, RevalidationType::Full, spawner.clone(),
+		));
+		let env = ProposerFactory::new(
+			spawner.clone(),
+			client.clone(),
+			pool.clone(),
+			None,
+			None,
+		);
+		// this test checks that empty blocks are created and finalized without any transaction.
+		let future = run_interval_seal(
+			IntervalSealParams {
+				block_import: client.clone(),
+				env,
+				client: client.clone(),
+				pool: pool.pool().clone(),
+				select_chain,
+				consensus_data_provider: None,
+				inherent_data_providers,
+				interval: Duration::from_millis(10),
+				finalize: true,
+			}
+		);
+		std::thread::spawn(|| {
+			let mut rt = tokio::runtime::Runtime::new().unwrap();
+			// spawn the background authorship task
+			rt.block_on(future);
+		});
+
+		for _ in 0..500 {
+			if client.info().finalized_number >= 2 {
+				break
+			}
+			futures_timer::Delay::new(Duration::from_millis(10)).await;
+		}
+		// assert that at least two blocks were created and finalized.
+		assert!(client.info().finalized_number >= 2);
+	}
+
 	#[tokio::test]
 	async fn manual_seal_and_finalization() {
 		let builder = TestClientBuilder::new();
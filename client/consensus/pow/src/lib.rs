@@ -32,15 +32,15 @@
 //! clients.
 
 mod worker;
+pub mod rpc;
 
-pub use crate::worker::{MiningWorker, MiningMetadata, MiningBuild};
+pub use crate::worker::{MiningHandle, MiningMetadata, MiningBuild, SubmitError};
 
 use std::{
 	sync::Arc, borrow::Cow, collections::HashMap, marker::PhantomData,
 	cmp::Ordering, time::Duration,
 };
 use futures::{prelude::*, future::Either};
-use parking_lot::Mutex;
 use sc_client_api::{BlockOf, backend::AuxStore, BlockchainEvents};
 use sp_blockchain::{HeaderBackend, ProvideCache, well_known_cache_keys::Id as CacheKeyId};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
@@ -48,7 +48,7 @@ use sp_runtime::{Justifications, RuntimeString};
 use sp_runtime::generic::{BlockId, Digest, DigestItem};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use sp_api::ProvideRuntimeApi;
-use sp_consensus_pow::{Seal, TotalDifficulty, POW_ENGINE_ID};
+use sp_consensus_pow::{Seal, TotalDifficulty, DifficultyApi, POW_ENGINE_ID};
 use sp_inherents::{InherentDataProviders, InherentData};
 use sp_consensus::{
 	BlockImportParams, BlockOrigin, ForkChoiceStrategy, SyncOracle, Environment, Proposer,
@@ -199,6 +199,26 @@ pub trait PowAlgorithm<B: BlockT> {
 	) -> Result<bool, Error<B>>;
 }
 
+/// Fetch the difficulty of the block built on top of `parent` from the runtime.
+///
+/// This is meant for [`PowAlgorithm::difficulty`] implementations of chains that put their
+/// difficulty adjustment algorithm into the runtime, exposed through [`DifficultyApi`].
+pub fn runtime_difficulty<B, C, Difficulty>(
+	client: &C,
+	parent: B::Hash,
+) -> Result<Difficulty, Error<B>> where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: DifficultyApi<B, Difficulty>,
+	Difficulty: Decode,
+{
+	client.runtime_api()
+		.difficulty(&BlockId::Hash(parent))
+		.map_err(|e| Error::Environment(
+			format!("Fetching difficulty from the runtime failed: {:?}", e),
+		))
+}
+
 /// A block importer for PoW.
 pub struct PowBlockImport<B: BlockT, I, C, S, Algorithm, CAW> {
 	algorithm: Algorithm,
@@ -533,9 +553,10 @@ pub fn import_queue<B, Transaction, Algorithm>(
 /// Start the mining worker for PoW. This function provides the necessary helper functions that can
 /// be used to implement a miner. However, it does not do the CPU-intensive mining itself.
 ///
-/// Two values are returned -- a worker, which contains functions that allows querying the current
+/// Two values are returned -- a handle, which contains functions that allows querying the current
 /// mining metadata and submitting mined blocks, and a future, which must be polled to fill in
-/// information in the worker.
+/// information in the handle. The handle can also be passed to [`rpc::Pow`] to let external
+/// miners drive the mining over RPC.
 ///
 /// `pre_runtime` is a parameter that allows a custom additional pre-runtime digest to be inserted
 /// for blocks being built. This can encode authorship information, or just be a graffiti.
@@ -552,7 +573,7 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, CAW>(
 	build_time: Duration,
 	can_author_with: CAW,
 ) -> (
-	Arc<Mutex<MiningWorker<Block, Algorithm, C, <E::Proposer as Proposer<Block>>::Proof>>>,
+	MiningHandle<Block, Algorithm, C, <E::Proposer as Proposer<Block>>::Proof>,
 	impl Future<Output = ()>,
 ) where
 	Block: BlockT,
@@ -560,6 +581,7 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, CAW>(
 	S: SelectChain<Block> + 'static,
 	Algorithm: PowAlgorithm<Block> + Clone,
 	Algorithm::Difficulty: Send + 'static,
+	sp_api::TransactionFor<C, Block>: Send + 'static,
 	E: Environment<Block> + Send + Sync + 'static,
 	E::Error: std::fmt::Debug,
 	E::Proposer: Proposer<Block, Transaction = sp_api::TransactionFor<C, Block>>,
@@ -571,11 +593,7 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, CAW>(
 	}
 
 	let timer = UntilImportedOrTimeout::new(client.import_notification_stream(), timeout);
-	let worker = Arc::new(Mutex::new(MiningWorker::<Block, Algorithm, C, _> {
-		build: None,
-		algorithm: algorithm.clone(),
-		block_import,
-	}));
+	let worker = MiningHandle::new(algorithm.clone(), block_import);
 	let worker_ret = worker.clone();
 
 	let task = timer.for_each(move |()| {
@@ -583,7 +601,7 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, CAW>(
 
 		if sync_oracle.is_major_syncing() {
 			debug!(target: "pow", "Skipping proposal due to sync.");
-			worker.lock().on_major_syncing();
+			worker.on_major_syncing();
 			return Either::Left(future::ready(()))
 		}
 
@@ -611,7 +629,7 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, CAW>(
 			return Either::Left(future::ready(()))
 		}
 
-		if worker.lock().best_hash() == Some(best_hash) {
+		if worker.best_hash() == Some(best_hash) {
 			return Either::Left(future::ready(()))
 		}
 
@@ -693,7 +711,7 @@ pub fn start_mining_worker<Block, C, S, Algorithm, E, SO, CAW>(
 				proposal,
 			};
 
-			worker.lock().on_build(build);
+			worker.on_build(build);
 		})
 	});
 
@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for external miners of the PoW engine.
+//!
+//! `pow_getWork` returns the pre-hash and difficulty of the block currently being mined and
+//! `pow_submitWork` submits a seal found for it. Seals for a pre-hash that is no longer the
+//! current mining target are rejected as stale.
+
+use futures::{FutureExt, TryFutureExt};
+use jsonrpc_core::{Error, ErrorCode};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{MiningHandle, PowAlgorithm, SubmitError};
+
+pub use self::gen_client::Client as PowClient;
+
+/// Future's type for jsonrpc
+type FutureResult<T> = Box<dyn jsonrpc_core::futures::Future<Item = T, Error = Error> + Send>;
+
+/// Error code for rpc
+mod codes {
+	pub const NO_BUILD: i64 = 20_000;
+	pub const STALE: i64 = 20_001;
+	pub const INVALID_SEAL: i64 = 20_002;
+	pub const SUBMIT_FAILED: i64 = 20_003;
+}
+
+impl std::convert::From<SubmitError> for Error {
+	fn from(error: SubmitError) -> Self {
+		let code = match error {
+			SubmitError::NoBuild => codes::NO_BUILD,
+			SubmitError::Stale => codes::STALE,
+			SubmitError::InvalidSeal => codes::INVALID_SEAL,
+			SubmitError::Verification(_) | SubmitError::Import(_) => codes::SUBMIT_FAILED,
+		};
+
+		Error {
+			code: ErrorCode::ServerError(code),
+			message: error.to_string(),
+			data: None,
+		}
+	}
+}
+
+/// The block currently being mined, as returned by `pow_getWork`.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Work<Hash, Difficulty> {
+	/// Hash of the block the mined block is built on.
+	pub best_hash: Hash,
+	/// The pre-hash to mine on.
+	pub pre_hash: Hash,
+	/// Data of the pre-runtime digest, if any.
+	pub pre_runtime: Option<Bytes>,
+	/// The target difficulty.
+	pub difficulty: Difficulty,
+}
+
+/// RPC trait that provides methods for external miners.
+#[rpc]
+pub trait PowApi<Hash, Difficulty> {
+	/// Returns the block currently being mined, or `None` if there is none, e.g. during major
+	/// syncing.
+	#[rpc(name = "pow_getWork")]
+	fn get_work(&self) -> jsonrpc_core::Result<Option<Work<Hash, Difficulty>>>;
+
+	/// Submits a seal mined on the given pre-hash.
+	///
+	/// Returns `true` when the mined block was imported.
+	#[rpc(name = "pow_submitWork")]
+	fn submit_work(&self, pre_hash: Hash, seal: Bytes) -> FutureResult<bool>;
+}
+
+/// A struct that implements the [`PowApi`].
+pub struct Pow<Block: BlockT, Algorithm: PowAlgorithm<Block>, C, Proof>
+	where C: sp_api::ProvideRuntimeApi<Block>
+{
+	handle: MiningHandle<Block, Algorithm, C, Proof>,
+}
+
+impl<Block, Algorithm, C, Proof> Pow<Block, Algorithm, C, Proof> where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	C: sp_api::ProvideRuntimeApi<Block>,
+{
+	/// Create new `Pow` serving the given mining handle.
+	pub fn new(handle: MiningHandle<Block, Algorithm, C, Proof>) -> Self {
+		Self { handle }
+	}
+}
+
+impl<Block, Algorithm, C, Proof> PowApi<Block::Hash, Algorithm::Difficulty>
+	for Pow<Block, Algorithm, C, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block> + Send + Sync + 'static,
+	Algorithm::Difficulty: Serialize + Send + 'static,
+	C: sp_api::ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	sp_api::TransactionFor<C, Block>: Send + 'static,
+	Proof: Send + 'static,
+{
+	fn get_work(&self) -> jsonrpc_core::Result<Option<Work<Block::Hash, Algorithm::Difficulty>>> {
+		Ok(self.handle.metadata().map(|metadata| Work {
+			best_hash: metadata.best_hash,
+			pre_hash: metadata.pre_hash,
+			pre_runtime: metadata.pre_runtime.map(Into::into),
+			difficulty: metadata.difficulty,
+		}))
+	}
+
+	fn submit_work(&self, pre_hash: Block::Hash, seal: Bytes) -> FutureResult<bool> {
+		let handle = self.handle.clone();
+		let future = async move {
+			handle.submit_work(pre_hash, seal.to_vec()).await.map(|()| true)
+		};
+
+		Box::new(future.boxed().map_err(Error::from).compat())
+	}
+}
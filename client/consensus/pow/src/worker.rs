@@ -16,11 +16,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{pin::Pin, time::Duration, collections::HashMap, borrow::Cow};
+use std::{
+	pin::Pin, time::Duration, collections::HashMap, borrow::Cow, sync::{Arc, atomic::{AtomicUsize, Ordering}},
+};
+use parking_lot::Mutex;
 use sc_client_api::ImportNotifications;
 use sp_runtime::{DigestItem, traits::Block as BlockT, generic::BlockId};
 use sp_consensus::{Proposal, BlockOrigin, BlockImportParams, import_queue::BoxBlockImport};
-use futures::{prelude::*, task::{Context, Poll}};
+use futures::{prelude::*, lock::Mutex as FutureMutex, task::{Context, Poll}};
 use futures_timer::Delay;
 use log::*;
 
@@ -52,117 +55,203 @@ pub struct MiningBuild<
 	pub proposal: Proposal<Block, sp_api::TransactionFor<C, Block>, Proof>,
 }
 
-/// Mining worker that exposes structs to query the current mining build and submit mined blocks.
-pub struct MiningWorker<
+/// Error returned when submitting a seal through [`MiningHandle::submit_work`].
+#[derive(derive_more::Display, Debug)]
+pub enum SubmitError {
+	/// There is currently no block being mined.
+	#[display(fmt = "No mining build is available")]
+	NoBuild,
+	/// The seal was mined for a pre-hash that is no longer the current mining target.
+	#[display(fmt = "Seal was mined on an outdated pre-hash")]
+	Stale,
+	/// The seal does not satisfy the current difficulty.
+	#[display(fmt = "Seal is invalid")]
+	InvalidSeal,
+	/// The PoW algorithm failed to verify the seal.
+	#[display(fmt = "Seal verification failed: {}", _0)]
+	Verification(String),
+	/// The mined block could not be imported.
+	#[display(fmt = "Unable to import mined block: {}", _0)]
+	Import(String),
+}
+
+/// Mining handle that exposes functions to query the current mining build and submit mined
+/// blocks.
+///
+/// The handle is cheap to clone, so that it can be shared between a local miner and the mining
+/// RPC.
+pub struct MiningHandle<
 	Block: BlockT,
 	Algorithm: PowAlgorithm<Block>,
 	C: sp_api::ProvideRuntimeApi<Block>,
 	Proof
 > {
-	pub(crate) build: Option<MiningBuild<Block, Algorithm, C, Proof>>,
-	pub(crate) algorithm: Algorithm,
-	pub(crate) block_import: BoxBlockImport<Block, sp_api::TransactionFor<C, Block>>,
+	version: Arc<AtomicUsize>,
+	algorithm: Arc<Algorithm>,
+	block_import: Arc<FutureMutex<BoxBlockImport<Block, sp_api::TransactionFor<C, Block>>>>,
+	build: Arc<Mutex<Option<MiningBuild<Block, Algorithm, C, Proof>>>>,
 }
 
-impl<Block, Algorithm, C, Proof> MiningWorker<Block, Algorithm, C, Proof> where
+impl<Block, Algorithm, C, Proof> MiningHandle<Block, Algorithm, C, Proof> where
 	Block: BlockT,
 	C: sp_api::ProvideRuntimeApi<Block>,
 	Algorithm: PowAlgorithm<Block>,
 	Algorithm::Difficulty: 'static + Send,
 	sp_api::TransactionFor<C, Block>: Send + 'static,
 {
+	pub(crate) fn new(
+		algorithm: Algorithm,
+		block_import: BoxBlockImport<Block, sp_api::TransactionFor<C, Block>>,
+	) -> Self {
+		Self {
+			version: Arc::new(AtomicUsize::new(0)),
+			algorithm: Arc::new(algorithm),
+			block_import: Arc::new(FutureMutex::new(block_import)),
+			build: Arc::new(Mutex::new(None)),
+		}
+	}
+
+	/// Get the version of the current mining build.
+	///
+	/// The version changes every time the build is replaced or consumed, so a miner can use it
+	/// to find out that it is working on an outdated build.
+	pub fn version(&self) -> usize {
+		self.version.load(Ordering::SeqCst)
+	}
+
 	/// Get the current best hash. `None` if the worker has just started or the client is doing
 	/// major syncing.
 	pub fn best_hash(&self) -> Option<Block::Hash> {
-		self.build.as_ref().map(|b| b.metadata.best_hash)
+		self.build.lock().as_ref().map(|b| b.metadata.best_hash)
 	}
 
-	pub(crate) fn on_major_syncing(&mut self) {
-		self.build = None;
+	pub(crate) fn on_major_syncing(&self) {
+		let mut build = self.build.lock();
+		*build = None;
+		self.increment_version();
 	}
 
 	pub(crate) fn on_build(
-		&mut self,
-		build: MiningBuild<Block, Algorithm, C, Proof>,
+		&self,
+		value: MiningBuild<Block, Algorithm, C, Proof>,
 	) {
-		self.build = Some(build);
+		let mut build = self.build.lock();
+		*build = Some(value);
+		self.increment_version();
+	}
+
+	fn increment_version(&self) {
+		self.version.fetch_add(1, Ordering::SeqCst);
 	}
 
 	/// Get a copy of the current mining metadata, if available.
 	pub fn metadata(&self) -> Option<MiningMetadata<Block::Hash, Algorithm::Difficulty>> {
-		self.build.as_ref().map(|b| b.metadata.clone())
+		self.build.lock().as_ref().map(|b| b.metadata.clone())
+	}
+
+	/// Submit a mined seal for the current mining build. The seal will be validated again.
+	/// Returns true if the submission is successful.
+	pub async fn submit(&self, seal: Seal) -> bool {
+		let pre_hash = match self.metadata() {
+			Some(metadata) => metadata.pre_hash,
+			None => {
+				warn!(
+					target: "pow",
+					"Unable to import mined block: build does not exist",
+				);
+				return false
+			},
+		};
+
+		match self.submit_work(pre_hash, seal).await {
+			Ok(()) => true,
+			Err(err) => {
+				warn!(target: "pow", "{}", err);
+				false
+			},
+		}
 	}
 
-	/// Submit a mined seal. The seal will be validated again. Returns true if the submission is
-	/// successful.
-	pub async fn submit(&mut self, seal: Seal) -> bool {
-		if let Some(build) = self.build.take() {
+	/// Submit a seal mined on the given `pre_hash`.
+	///
+	/// Submissions for a `pre_hash` that is not the current mining target anymore are rejected
+	/// as [`SubmitError::Stale`]. The seal is validated again before the block is imported.
+	pub async fn submit_work(
+		&self,
+		pre_hash: Block::Hash,
+		seal: Seal,
+	) -> Result<(), SubmitError> {
+		let build = {
+			let mut build = self.build.lock();
+			let metadata = match build.as_ref() {
+				Some(build) => &build.metadata,
+				None => return Err(SubmitError::NoBuild),
+			};
+
+			if metadata.pre_hash != pre_hash {
+				return Err(SubmitError::Stale)
+			}
+
 			match self.algorithm.verify(
-				&BlockId::Hash(build.metadata.best_hash),
-				&build.metadata.pre_hash,
-				build.metadata.pre_runtime.as_ref().map(|v| &v[..]),
+				&BlockId::Hash(metadata.best_hash),
+				&metadata.pre_hash,
+				metadata.pre_runtime.as_ref().map(|v| &v[..]),
 				&seal,
-				build.metadata.difficulty,
+				metadata.difficulty,
 			) {
 				Ok(true) => (),
-				Ok(false) => {
-					warn!(
-						target: "pow",
-						"Unable to import mined block: seal is invalid",
-					);
-					return false
-				},
-				Err(err) => {
-					warn!(
-						target: "pow",
-						"Unable to import mined block: {:?}",
-						err,
-					);
-					return false
-				},
+				Ok(false) => return Err(SubmitError::InvalidSeal),
+				Err(err) => return Err(SubmitError::Verification(err.to_string())),
 			}
 
-			let seal = DigestItem::Seal(POW_ENGINE_ID, seal);
-			let (header, body) = build.proposal.block.deconstruct();
+			let taken = build.take().expect("build was checked to exist above; qed");
+			self.increment_version();
+			taken
+		};
 
-			let mut import_block = BlockImportParams::new(BlockOrigin::Own, header);
-			import_block.post_digests.push(seal);
-			import_block.body = Some(body);
-			import_block.storage_changes = Some(build.proposal.storage_changes);
+		let seal = DigestItem::Seal(POW_ENGINE_ID, seal);
+		let (header, body) = build.proposal.block.deconstruct();
 
-			let intermediate = PowIntermediate::<Algorithm::Difficulty> {
-				difficulty: Some(build.metadata.difficulty),
-			};
+		let mut import_block = BlockImportParams::new(BlockOrigin::Own, header);
+		import_block.post_digests.push(seal);
+		import_block.body = Some(body);
+		import_block.storage_changes = Some(build.proposal.storage_changes);
 
-			import_block.intermediates.insert(
-				Cow::from(INTERMEDIATE_KEY),
-				Box::new(intermediate) as Box<_>,
-			);
-
-			match self.block_import.import_block(import_block, HashMap::default()).await {
-				Ok(_) => {
-					info!(
-						target: "pow",
-						"✅ Successfully mined block on top of: {}",
-						build.metadata.best_hash
-					);
-					true
-				},
-				Err(err) => {
-					warn!(
-						target: "pow",
-						"Unable to import mined block: {:?}",
-						err,
-					);
-					false
-				},
-			}
-		} else {
-			warn!(
-				target: "pow",
-				"Unable to import mined block: build does not exist",
-			);
-			false
+		let intermediate = PowIntermediate::<Algorithm::Difficulty> {
+			difficulty: Some(build.metadata.difficulty),
+		};
+
+		import_block.intermediates.insert(
+			Cow::from(INTERMEDIATE_KEY),
+			Box::new(intermediate) as Box<_>,
+		);
+
+		let mut block_import = self.block_import.lock().await;
+		match block_import.import_block(import_block, HashMap::default()).await {
+			Ok(_) => {
+				info!(
+					target: "pow",
+					"✅ Successfully mined block on top of: {}",
+					build.metadata.best_hash
+				);
+				Ok(())
+			},
+			Err(err) => Err(SubmitError::Import(format!("{:?}", err))),
+		}
+	}
+}
+
+impl<Block, Algorithm, C, Proof> Clone for MiningHandle<Block, Algorithm, C, Proof> where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	C: sp_api::ProvideRuntimeApi<Block>,
+{
+	fn clone(&self) -> Self {
+		Self {
+			version: self.version.clone(),
+			algorithm: self.algorithm.clone(),
+			block_import: self.block_import.clone(),
+			build: self.build.clone(),
 		}
 	}
 }
@@ -163,6 +163,8 @@ pub trait SimpleSlotWorker<B: BlockT> {
 	>;
 
 	/// Whether to force authoring if offline.
+	///
+	/// Forcing authorship also disables [`Self::should_backoff`].
 	fn force_authoring(&self) -> bool;
 
 	/// Returns whether the block production should back off.
@@ -258,7 +260,7 @@ pub trait SimpleSlotWorker<B: BlockT> {
 
 		let claim = self.claim_slot(&chain_head, slot, &epoch_data)?;
 
-		if self.should_backoff(slot, &chain_head) {
+		if !self.force_authoring() && self.should_backoff(slot, &chain_head) {
 			return None;
 		}
 
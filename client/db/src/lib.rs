@@ -340,6 +340,16 @@ impl DatabaseSettingsSrc {
 			_ => false,
 		}
 	}
+	/// Return the on-disk format version of the database.
+	///
+	/// Returns `None` for databases that are not versioned.
+	pub fn version(&self) -> ClientResult<Option<u32>> {
+		match self {
+			#[cfg(feature = "with-kvdb-rocksdb")]
+			DatabaseSettingsSrc::RocksDb { path, .. } => upgrade::current_version(path).map(Some),
+			_ => Ok(None),
+		}
+	}
 }
 
 impl std::fmt::Display for DatabaseSettingsSrc {
@@ -594,6 +604,37 @@ impl<Block: BlockT> sc_client_api::blockchain::Backend<Block> for BlockchainDb<B
 	fn has_indexed_transaction(&self, hash: &Block::Hash) -> ClientResult<bool> {
 		Ok(self.db.contains(columns::TRANSACTION, hash.as_ref()))
 	}
+
+	fn block_indexed_body(&self, id: BlockId<Block>) -> ClientResult<Option<Vec<Vec<u8>>>> {
+		match self.transaction_storage {
+			TransactionStorageMode::BlockBody => Ok(None),
+			TransactionStorageMode::StorageChain => {
+				let body = match read_db(&*self.db, columns::KEY_LOOKUP, columns::BODY, id)? {
+					Some(body) => body,
+					None => return Ok(None),
+				};
+				match Vec::<ExtrinsicHeader>::decode(&mut &body[..]) {
+					Ok(index) => {
+						let mut transactions = Vec::new();
+						for ExtrinsicHeader { indexed_hash, .. } in index.into_iter() {
+							if indexed_hash != Default::default() {
+								match self.db.get(columns::TRANSACTION, indexed_hash.as_ref()) {
+									Some(t) => transactions.push(t),
+									None => return Err(sp_blockchain::Error::Backend(
+										format!("Missing indexed transaction {:?}", indexed_hash))
+									)
+								}
+							}
+						}
+						Ok(Some(transactions))
+					}
+					Err(err) => return Err(sp_blockchain::Error::Backend(
+						format!("Error decoding body list: {}", err)
+					)),
+				}
+			}
+		}
+	}
 }
 
 impl<Block: BlockT> sc_client_api::blockchain::ProvideCache<Block> for BlockchainDb<Block> {
@@ -694,6 +735,7 @@ pub struct BlockImportOperation<Block: BlockT> {
 	finalized_blocks: Vec<(BlockId<Block>, Option<Justification>)>,
 	set_head: Option<BlockId<Block>>,
 	commit_state: bool,
+	reset_storage: bool,
 	index_ops: Vec<IndexOperation>,
 }
 
@@ -792,6 +834,7 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block> for Bloc
 		self.db_updates = transaction;
 		self.changes_trie_config_update = Some(changes_trie_config);
 		self.commit_state = true;
+		self.reset_storage = true;
 		Ok(root)
 	}
 
@@ -1190,6 +1233,10 @@ impl<Block: BlockT> Backend<Block> {
 					implies existence of blocks with all numbers before it; qed")
 			};
 
+			if !sc_client_api::Backend::have_state_at(self, &hash, new_canonical.saturated_into()) {
+				return Ok(())
+			}
+
 			trace!(target: "db", "Canonicalize block #{} ({:?})", new_canonical, hash);
 			let commit = self.storage.state_db.canonicalize_block(&hash)
 				.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
@@ -1236,6 +1283,13 @@ impl<Block: BlockT> Backend<Block> {
 			// blocks are keyed by number + hash.
 			let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
 
+			// The header is already known when importing the state of an existing block.
+			let existing_header = number <= self.blockchain.meta.read().best_number
+				&& sc_client_api::blockchain::HeaderBackend::header(
+					&self.blockchain,
+					BlockId::hash(hash),
+				)?.is_some();
+
 			let (enacted, retracted) = if pending_block.leaf_state.is_best() {
 				self.set_head_with_transaction(&mut transaction, parent_hash, (number, hash))?
 			} else {
@@ -1324,16 +1378,33 @@ impl<Block: BlockT> Backend<Block> {
 				}
 				self.state_usage.tally_writes(ops, bytes);
 				let number_u64 = number.saturated_into::<u64>();
-				let commit = self.storage.state_db.insert_block(
-					&hash,
-					number_u64,
-					&pending_block.header.parent_hash(),
-					changeset,
-				).map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
+				let commit = if operation.reset_storage && number_u64 != 0 {
+					// The state was imported without its history, e.g. by state sync.
+					self.storage.state_db.import_canonical_block(&hash, number_u64, changeset)
+				} else {
+					self.storage.state_db.insert_block(
+						&hash,
+						number_u64,
+						&pending_block.header.parent_hash(),
+						changeset,
+					)
+				}.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
 				apply_state_commit(&mut transaction, commit);
 
 				// Check if need to finalize. Genesis is always finalized instantly.
 				let finalized = number_u64 == 0 || pending_block.leaf_state.is_final();
+
+				// The state of an existing block that is already finalized is canonical
+				// right away.
+				if existing_header
+					&& !finalized
+					&& !operation.reset_storage
+					&& number <= self.blockchain.meta.read().finalized_number
+				{
+					let commit = self.storage.state_db.canonicalize_block(&hash)
+						.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
+					apply_state_commit(&mut transaction, commit);
+				}
 				finalized
 			} else {
 				false
@@ -1378,28 +1449,32 @@ impl<Block: BlockT> Backend<Block> {
 
 			debug!(target: "db", "DB Commit {:?} ({}), best = {}", hash, number, is_best);
 
-			let displaced_leaf = {
+			let displaced_leaf = if existing_header {
+				None
+			} else {
 				let mut leaves = self.blockchain.leaves.write();
 				let displaced_leaf = leaves.import(hash, number, parent_hash);
 				leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
 
-				displaced_leaf
+				Some(displaced_leaf)
 			};
 
-			let mut children = children::read_children(
-				&*self.storage.db,
-				columns::META,
-				meta_keys::CHILDREN_PREFIX,
-				parent_hash,
-			)?;
-			children.push(hash);
-			children::write_children(
-				&mut transaction,
-				columns::META,
-				meta_keys::CHILDREN_PREFIX,
-				parent_hash,
-				children,
-			);
+			if !existing_header {
+				let mut children = children::read_children(
+					&*self.storage.db,
+					columns::META,
+					meta_keys::CHILDREN_PREFIX,
+					parent_hash,
+				)?;
+				children.push(hash);
+				children::write_children(
+					&mut transaction,
+					columns::META,
+					meta_keys::CHILDREN_PREFIX,
+					parent_hash,
+					children,
+				);
+			}
 
 			meta_updates.push((hash, number, pending_block.leaf_state.is_best(), finalized));
 
@@ -1493,9 +1568,13 @@ impl<Block: BlockT> Backend<Block> {
 			let lookup_key = utils::number_and_hash_to_lookup_key(f_num, f_hash.clone())?;
 			transaction.set_from_vec(columns::META, meta_keys::FINALIZED_BLOCK, lookup_key);
 
-			let commit = self.storage.state_db.canonicalize_block(&f_hash)
-				.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
-			apply_state_commit(transaction, commit);
+			// Blocks that were imported without state (e.g. during state sync) are not
+			// known to the state database.
+			if sc_client_api::Backend::have_state_at(self, &f_hash, f_num) {
+				let commit = self.storage.state_db.canonicalize_block(&f_hash)
+					.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
+				apply_state_commit(transaction, commit);
+			}
 
 			if !f_num.is_zero() {
 				let new_changes_trie_cache_ops = self.changes_tries_storage.finalize(
@@ -1594,8 +1673,15 @@ impl<Block: BlockT> Backend<Block> {
 					}
 				}
 			}
-			None => return Ok(()),
+			None => {},
 		}
+		utils::remove_from_db(
+			transaction,
+			&*self.storage.db,
+			columns::KEY_LOOKUP,
+			columns::JUSTIFICATIONS,
+			id,
+		)?;
 		Ok(())
 	}
 }
@@ -1626,10 +1712,10 @@ fn apply_index_ops<Block: BlockT>(
 	let mut renewed_map = HashMap::new();
 	for op in ops {
 		match op {
-			IndexOperation::Insert { extrinsic, offset } => {
-				index_map.insert(extrinsic, offset);
+			IndexOperation::Insert { extrinsic, hash, size } => {
+				index_map.insert(extrinsic, (hash, size));
 			}
-			IndexOperation::Renew { extrinsic, hash, .. } => {
+			IndexOperation::Renew { extrinsic, hash } => {
 				renewed_map.insert(extrinsic, DbHash::from_slice(hash.as_ref()));
 			}
 		}
@@ -1645,9 +1731,8 @@ fn apply_index_ops<Block: BlockT>(
 			}
 		} else {
 			match index_map.get(&(index as u32)) {
-				Some(offset) if *offset as usize <= extrinsic.len() => {
-					let offset = *offset as usize;
-					let hash = HashFor::<Block>::hash(&extrinsic[offset..]);
+				Some((hash, size)) if *size as usize <= extrinsic.len() => {
+					let offset = extrinsic.len() - *size as usize;
 					transaction.store(
 						columns::TRANSACTION,
 						DbHash::from_slice(hash.as_ref()),
@@ -1725,6 +1810,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			finalized_blocks: Vec::new(),
 			set_head: None,
 			commit_state: false,
+			reset_storage: false,
 			index_ops: Default::default(),
 		})
 	}
@@ -2306,6 +2392,56 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn state_and_offchain_data_survive_shutdown() {
+		use sp_core::offchain::OffchainStorage;
+
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let open = || Backend::<Block>::new(DatabaseSettings {
+			state_cache_size: 16777216,
+			state_cache_child_ratio: Some((50, 100)),
+			state_pruning: PruningMode::keep_blocks(2),
+			source: DatabaseSettingsSrc::RocksDb { path: db_dir.path().to_owned(), cache_size: 128 },
+			keep_blocks: KeepBlocks::All,
+			transaction_storage: TransactionStorageMode::BlockBody,
+		}, 2).unwrap();
+
+		{
+			let db = open();
+			let mut op = db.begin_operation().unwrap();
+			db.begin_state_operation(&mut op, BlockId::Hash(Default::default())).unwrap();
+			let storage = vec![(vec![1, 3, 5], vec![2, 4, 6])];
+			let header = Header {
+				number: 0,
+				parent_hash: Default::default(),
+				state_root: op.old_state.storage_root(storage
+					.iter()
+					.map(|(x, y)| (&x[..], Some(&y[..])))
+				).0.into(),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			op.reset_storage(Storage {
+				top: storage.into_iter().collect(),
+				children_default: Default::default(),
+			}).unwrap();
+			op.set_block_data(header, Some(vec![]), None, NewBlockState::Best).unwrap();
+			db.commit_operation(op).unwrap();
+
+			db.offchain_storage().unwrap().set(b"prefix", b"key", b"value");
+			// The block is still in the non-canonical overlay of the state-db when the
+			// backend is dropped, as it is on shutdown.
+		}
+
+		let db = open();
+		let state = db.state_at(BlockId::Number(0)).unwrap();
+		assert_eq!(state.storage(&[1, 3, 5]).unwrap(), Some(vec![2, 4, 6]));
+		assert_eq!(
+			db.offchain_storage().unwrap().get(b"prefix", b"key"),
+			Some(b"value".to_vec()),
+		);
+	}
+
 	#[test]
 	fn delete_only_when_negative_rc() {
 		sp_tracing::try_init_simple();
@@ -2913,6 +3049,42 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn prune_justifications_on_finalize() {
+		let backend = Backend::<Block>::new_test_with_tx_storage(
+			2,
+			0,
+			TransactionStorageMode::BlockBody,
+		);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0 .. 5 {
+			let hash = insert_block(&backend, i, prev_hash, None, Default::default(), vec![i.into()], None);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		let justification = (CONS0_ENGINE_ID, vec![1, 2, 3]);
+		for i in 1 .. 5 {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+			op.mark_finalized(BlockId::Hash(blocks[i]), Some(justification.clone())).unwrap();
+			backend.commit_operation(op).unwrap();
+		}
+
+		let bc = backend.blockchain();
+		assert_eq!(None, bc.justifications(BlockId::hash(blocks[1])).unwrap());
+		assert_eq!(None, bc.justifications(BlockId::hash(blocks[2])).unwrap());
+		assert_eq!(
+			Some(Justifications::from(justification.clone())),
+			bc.justifications(BlockId::hash(blocks[3])).unwrap(),
+		);
+		assert_eq!(
+			Some(Justifications::from(justification)),
+			bc.justifications(BlockId::hash(blocks[4])).unwrap(),
+		);
+	}
+
 	#[test]
 	fn prune_blocks_on_finalize_with_fork() {
 		let backend = Backend::<Block>::new_test_with_tx_storage(
@@ -2973,13 +3145,16 @@ pub(crate) mod tests {
 		for i in 0 .. 10 {
 			let mut index = Vec::new();
 			if i == 0 {
-				index.push(IndexOperation::Insert { extrinsic: 0, offset: 1 });
+				index.push(IndexOperation::Insert {
+					extrinsic: 0,
+					hash: x1_hash.as_ref().to_vec(),
+					size: (x1.len() - 1) as u32,
+				});
 			} else if i < 5 {
 				// keep renewing 1st
 				index.push(IndexOperation::Renew {
 					extrinsic: 0,
 					hash: x1_hash.as_ref().to_vec(),
-					size: (x1.len() - 1) as u32,
 				});
 			} // else stop renewing
 			let hash = insert_block(
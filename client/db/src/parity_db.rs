@@ -43,20 +43,51 @@ pub fn open<H: Clone + AsRef<[u8]>>(path: &std::path::Path, db_type: DatabaseTyp
 		state_col.ref_counted = true;
 		state_col.preimage = true;
 		state_col.uniform = true;
+
+		let mut transaction_col = &mut config.columns[columns::TRANSACTION as usize];
+		transaction_col.ref_counted = true;
+		transaction_col.preimage = true;
+		transaction_col.uniform = true;
 	}
 	let db = parity_db::Db::open(&config)?;
 	Ok(std::sync::Arc::new(DbAdapter(db)))
 }
 
+/// Returns `true` if the given column is opened as a ref counted column.
+fn ref_counted_column(col: ColumnId) -> bool {
+	col == columns::TRANSACTION || col == columns::STATE
+}
+
 impl<H: Clone + AsRef<[u8]>> Database<H> for DbAdapter {
 	fn commit(&self, transaction: Transaction<H>) -> Result<(), DatabaseError> {
-		handle_err(self.0.commit(transaction.0.into_iter().map(|change|
-			match change {
-				Change::Set(col, key, value) => (col as u8, key, Some(value)),
-				Change::Remove(col, key) => (col as u8, key, None),
-				_ => unimplemented!(),
-			}))
-		);
+		let mut not_ref_counted_columns = Vec::new();
+		let changes = transaction.0.into_iter().filter_map(|change| match change {
+			Change::Set(col, key, value) => Some((col as u8, key, Some(value))),
+			Change::Remove(col, key) => Some((col as u8, key, None)),
+			Change::Store(col, key, value) if ref_counted_column(col) =>
+				Some((col as u8, key.as_ref().to_vec(), Some(value))),
+			// Inserting an already present value into a ref counted column increases its
+			// reference count.
+			Change::Reference(col, key) if ref_counted_column(col) => {
+				let value = <Self as Database<H>>::get(self, col, key.as_ref());
+				Some((col as u8, key.as_ref().to_vec(), value))
+			},
+			Change::Release(col, key) if ref_counted_column(col) =>
+				Some((col as u8, key.as_ref().to_vec(), None)),
+			Change::Store(col, ..) | Change::Reference(col, _) | Change::Release(col, _) => {
+				not_ref_counted_columns.push(col);
+				None
+			},
+		}).collect::<Vec<_>>();
+
+		if !not_ref_counted_columns.is_empty() {
+			return Err(DatabaseError(format!(
+				"Ref counted operation on non ref counted columns {:?}",
+				not_ref_counted_columns,
+			).into()));
+		}
+
+		handle_err(self.0.commit(changes));
 
 		Ok(())
 	}
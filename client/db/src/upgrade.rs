@@ -100,7 +100,7 @@ fn migrate_2_to_3<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> sp_b
 
 /// Reads current database version from the file at given path.
 /// If the file does not exist returns 0.
-fn current_version(path: &Path) -> sp_blockchain::Result<u32> {
+pub fn current_version(path: &Path) -> sp_blockchain::Result<u32> {
 	let unknown_version_err = || sp_blockchain::Error::Backend("Unknown database version".into());
 
 	match fs::File::open(version_file_path(path)) {
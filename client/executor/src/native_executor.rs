@@ -273,6 +273,27 @@ impl<D: NativeExecutionDispatch> NativeExecutor<D> {
 		fallback_method: WasmExecutionMethod,
 		default_heap_pages: Option<u64>,
 		max_runtime_instances: usize,
+	) -> Self {
+		Self::new_with_cache_path(fallback_method, default_heap_pages, max_runtime_instances, None)
+	}
+
+	/// Create new instance that caches compiled artifacts in the given directory.
+	///
+	/// # Parameters
+	///
+	/// `fallback_method` - Method used to execute fallback Wasm code.
+	///
+	/// `default_heap_pages` - Number of 64KB pages to allocate for Wasm execution.
+	/// 	Defaults to `DEFAULT_HEAP_PAGES` if `None` is provided.
+	///
+	/// `cache_path` - A path to a directory where the executor can place its files for purposes of
+	/// 	caching, e.g. compiled artifacts. They are keyed by the hash of the runtime code and the
+	/// 	compiler version, so a changed runtime or an upgraded compiler never reuses stale ones.
+	pub fn new_with_cache_path(
+		fallback_method: WasmExecutionMethod,
+		default_heap_pages: Option<u64>,
+		max_runtime_instances: usize,
+		cache_path: Option<PathBuf>,
 	) -> Self {
 		let extended =  D::ExtendHostFunctions::host_functions();
 		let mut host_functions = sp_io::SubstrateHostFunctions::host_functions()
@@ -293,7 +314,7 @@ impl<D: NativeExecutionDispatch> NativeExecutor<D> {
 			default_heap_pages,
 			host_functions,
 			max_runtime_instances,
-			None,
+			cache_path,
 		);
 
 		NativeExecutor {
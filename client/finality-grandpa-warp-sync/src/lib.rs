@@ -54,7 +54,7 @@ pub fn request_response_config_for_chain<TBlock: BlockT, TBackend: Backend<TBloc
 			backend.clone(),
 			authority_set,
 		);
-		spawn_handle.spawn("grandpa_warp_sync_request_handler", handler.run());
+		spawn_handle.spawn("grandpa_warp_sync_request_handler", Some("networking"), handler.run());
 		request_response_config
 	}
 }
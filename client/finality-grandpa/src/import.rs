@@ -446,6 +446,12 @@ impl<BE, Block: BlockT, Client, SC> BlockImport<Block>
 		// early exit if block already in chain, otherwise the check for
 		// authority changes will error when trying to re-import a change block
 		match self.inner.status(BlockId::Hash(hash)) {
+			Ok(BlockStatus::InChain) if block.import_existing => {
+				// An existing block is being re-imported (e.g. with its state), authority
+				// changes have already been tracked when its header was first imported.
+				let _justifications = block.justifications.take();
+				return (&*self.inner).import_block(block, new_cache).await
+			},
 			Ok(BlockStatus::InChain) => return Ok(ImportResult::AlreadyInChain),
 			Ok(BlockStatus::Unknown) => {},
 			Err(e) => return Err(ConsensusError::ClientImport(e.to_string())),
@@ -205,6 +205,20 @@ sp_api::mock_impl_runtime_apis! {
 			self.inner.genesis_authorities.clone()
 		}
 
+		fn current_set_id(&self) -> SetId {
+			0
+		}
+
+		fn pending_change(&self) -> Option<(BlockNumber, ScheduledChange<BlockNumber>)> {
+			None
+		}
+
+		fn pending_forced_change(
+			&self,
+		) -> Option<(BlockNumber, BlockNumber, ScheduledChange<BlockNumber>)> {
+			None
+		}
+
 		fn submit_report_equivocation_unsigned_extrinsic(
 			_equivocation_proof: EquivocationProof<Hash, BlockNumber>,
 			_key_owner_proof: OpaqueKeyOwnershipProof,
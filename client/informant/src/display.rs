@@ -103,10 +103,43 @@ impl<B: BlockT> InformantDisplay<B> {
 			),
 		};
 
-		if self.format.enable_color {
+		let extra_entries = self.format.status_line_extension.as_ref()
+			.map(|extension| extension.entries())
+			.unwrap_or_default();
+		let extra = extra_entries.iter()
+			.map(|(name, value)| format!(", {}: {}", name, value))
+			.collect::<String>();
+
+		if self.format.structured {
+			tracing::info!(
+				target: "substrate",
+				sync_state = %status.trim(),
+				sync_target = ?net_status.best_seen_block,
+				peers = num_connected_peers as u64,
+				best = %best_number,
+				best_hash = %best_hash,
+				finalized = %finalized_number,
+				finalized_hash = %info.chain.finalized_hash,
+				bytes_in_per_sec = avg_bytes_per_sec_inbound,
+				bytes_out_per_sec = avg_bytes_per_sec_outbound,
+				extra = %extra.trim_start_matches(", "),
+				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), ⬇ {} ⬆ {}{}",
+				level,
+				status,
+				target,
+				num_connected_peers,
+				best_number,
+				best_hash,
+				finalized_number,
+				info.chain.finalized_hash,
+				TransferRateFormat(avg_bytes_per_sec_inbound),
+				TransferRateFormat(avg_bytes_per_sec_outbound),
+				extra,
+			)
+		} else if self.format.enable_color {
 			info!(
 				target: "substrate",
-				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), {} {}",
+				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), {} {}{}",
 				level,
 				Colour::White.bold().paint(&status),
 				target,
@@ -117,11 +150,12 @@ impl<B: BlockT> InformantDisplay<B> {
 				info.chain.finalized_hash,
 				Colour::Green.paint(format!("⬇ {}", TransferRateFormat(avg_bytes_per_sec_inbound))),
 				Colour::Red.paint(format!("⬆ {}", TransferRateFormat(avg_bytes_per_sec_outbound))),
+				extra,
 			)
 		} else {
 			info!(
 				target: "substrate",
-				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), ⬇ {} ⬆ {}",
+				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), ⬇ {} ⬆ {}{}",
 				level,
 				status,
 				target,
@@ -132,6 +166,7 @@ impl<B: BlockT> InformantDisplay<B> {
 				info.chain.finalized_hash,
 				TransferRateFormat(avg_bytes_per_sec_inbound),
 				TransferRateFormat(avg_bytes_per_sec_outbound),
+				extra,
 			)
 		}
 	}
@@ -28,27 +28,55 @@ use sp_blockchain::HeaderMetadata;
 use sp_runtime::traits::{Block as BlockT, Header};
 use sp_transaction_pool::TransactionPool;
 use sp_utils::{status_sinks, mpsc::tracing_unbounded};
-use std::{fmt::Display, sync::Arc, time::Duration, collections::VecDeque};
+use std::{fmt::{self, Display}, sync::Arc, time::Duration, collections::VecDeque};
 
 mod display;
 
+/// Provides additional entries for the periodic status line.
+///
+/// This allows embedders, e.g. parachain collators, to display information the informant doesn't
+/// know about, like the current relay chain block.
+pub trait StatusLineExtension: Send + Sync {
+	/// Returns the entries to append to the status line, as `(name, value)` pairs.
+	fn entries(&self) -> Vec<(String, String)>;
+}
+
 /// The format to print telemetry output in.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct OutputFormat {
 	/// Enable color output in logs.
 	///
 	/// Is enabled by default.
 	pub enable_color: bool,
+	/// Print the status line with its values as structured fields, to be used together with
+	/// JSON logging.
+	///
+	/// Is disabled by default.
+	pub structured: bool,
+	/// Additional entries for the status line.
+	pub status_line_extension: Option<Arc<dyn StatusLineExtension>>,
 }
 
 impl Default for OutputFormat {
 	fn default() -> Self {
 		Self {
 			enable_color: true,
+			structured: false,
+			status_line_extension: None,
 		}
 	}
 }
 
+impl fmt::Debug for OutputFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("OutputFormat")
+			.field("enable_color", &self.enable_color)
+			.field("structured", &self.structured)
+			.field("status_line_extension", &self.status_line_extension.is_some())
+			.finish()
+	}
+}
+
 /// Marker trait for a type that implements `TransactionPool` and `MallocSizeOf` on `not(target_os = "unknown")`.
 #[cfg(target_os = "unknown")]
 pub trait TransactionPoolAndMaybeMallogSizeOf: TransactionPool {}
@@ -27,6 +27,12 @@ use sp_keystore::Error as TraitError;
 mod local;
 pub use local::LocalKeystore;
 
+/// Remote keystore implementation
+#[cfg(unix)]
+mod remote;
+#[cfg(unix)]
+pub use remote::RemoteKeystore;
+
 /// Keystore error.
 #[derive(Debug, derive_more::Display, derive_more::From)]
 pub enum Error {
@@ -49,6 +55,9 @@ pub enum Error {
 	/// Keystore unavailable
 	#[display(fmt="Keystore unavailable")]
 	Unavailable,
+	/// Remote signer error
+	#[display(fmt="Remote signer error: {}", _0)]
+	Remote(String),
 }
 
 /// Keystore Result
@@ -64,6 +73,7 @@ impl From<Error> for TraitError {
 			Error::Unavailable => TraitError::Unavailable,
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
+			Error::Remote(e) => TraitError::Other(e),
 		}
 	}
 }
@@ -0,0 +1,471 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+//! Remote keystore implementation
+//!
+//! Forwards all requests to an external signer service listening on a local unix socket, so that
+//! the private keys never have to be stored on the node's disk.
+//!
+//! The keystore speaks JSON-RPC 2.0 with one request and one response per line. Keys are encoded
+//! as `{ "cryptoType": "sr25", "public": "0x…" }`, key types as their four character string and
+//! binary data as `0x` prefixed hex. The signer has to support the following methods:
+//!
+//! - `keystore_publicKeys(keyType)`: returns the list of keys of the given key type.
+//! - `keystore_hasKeys([[public, keyType], …])`: returns whether all the keys exist.
+//! - `keystore_sign(keyType, key, message)`: returns the SCALE encoded signature or `null`.
+//! - `keystore_sr25519VrfSign(keyType, public, transcript)`: receives the SCALE encoded
+//!   [`VRFTranscriptData`] and returns `{ "output": "0x…", "proof": "0x…" }` or `null`.
+//!
+//! Only the key types given in the allow-list are forwarded to the signer. Keys can't be
+//! generated or inserted through the remote keystore, that has to be done on the signer itself.
+
+use std::{
+	convert::TryFrom,
+	io::{BufRead, BufReader, Write},
+	os::unix::net::UnixStream,
+	path::PathBuf,
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration,
+};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	Encode,
+};
+use sp_keystore::{
+	CryptoStore,
+	Error as TraitError,
+	SyncCryptoStore,
+	vrf::{VRFTranscriptData, VRFSignature},
+};
+use sp_application_crypto::{ed25519, sr25519, ecdsa};
+
+use crate::{Result, Error};
+
+/// How long to wait for the signer to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A keystore that forwards all requests to an external signer service.
+pub struct RemoteKeystore {
+	socket: PathBuf,
+	allowed_key_types: Vec<KeyTypeId>,
+	next_request_id: AtomicU64,
+}
+
+impl RemoteKeystore {
+	/// Create a remote keystore from the given URI.
+	///
+	/// The URI has the form `unix:///path/to/socket?key_types=aura,gran`, where `key_types` is
+	/// the allow-list of key types that are forwarded to the signer. Fails if the allow-list is
+	/// missing or the signer can't be reached.
+	pub fn open(uri: &str) -> Result<Self> {
+		let uri = uri.strip_prefix("unix://")
+			.ok_or_else(|| Error::Remote(format!("Unsupported keystore URI: {}", uri)))?;
+		let (path, query) = match uri.find('?') {
+			Some(pos) => (&uri[..pos], &uri[pos + 1..]),
+			None => (uri, ""),
+		};
+
+		let key_types = query.split('&')
+			.find_map(|param| param.strip_prefix("key_types="))
+			.ok_or_else(|| Error::Remote("Missing `key_types` allow-list in keystore URI".into()))?;
+		let allowed_key_types = key_types.split(',')
+			.map(|key_type| KeyTypeId::try_from(key_type)
+				.map_err(|_| Error::Remote(format!("Invalid key type: {}", key_type))))
+			.collect::<Result<Vec<_>>>()?;
+
+		let keystore = Self {
+			socket: path.into(),
+			allowed_key_types,
+			next_request_id: AtomicU64::new(0),
+		};
+
+		// Make sure the signer is reachable at startup instead of failing on the first signature.
+		keystore.connect()?;
+
+		Ok(keystore)
+	}
+
+	fn connect(&self) -> Result<UnixStream> {
+		let stream = UnixStream::connect(&self.socket)?;
+		stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+		stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+		Ok(stream)
+	}
+
+	fn is_allowed(&self, id: KeyTypeId) -> bool {
+		self.allowed_key_types.contains(&id)
+	}
+
+	/// Sends a request to the signer and returns the result.
+	fn request(&self, method: &str, params: Value) -> Result<Value> {
+		let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+		let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+		let mut stream = self.connect()?;
+		serde_json::to_writer(&mut stream, &request)?;
+		stream.write_all(b"\n")?;
+
+		let mut line = String::new();
+		BufReader::new(stream).read_line(&mut line)?;
+		let mut response: Value = serde_json::from_str(&line)?;
+
+		if response["id"] != json!(id) {
+			return Err(Error::Remote(format!("Unexpected response id: {}", response["id"])))
+		}
+		if let Some(error) = response.get("error") {
+			return Err(Error::Remote(format!("{} failed: {}", method, error)))
+		}
+
+		Ok(response["result"].take())
+	}
+
+	fn public_keys<T>(&self, id: KeyTypeId, crypto: CryptoTypeId) -> Vec<T>
+		where T: for<'a> TryFrom<&'a [u8]>
+	{
+		SyncCryptoStore::keys(self, id)
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|key| key.0 == crypto)
+			.filter_map(|key| T::try_from(&key.1[..]).ok())
+			.collect()
+	}
+}
+
+fn encode_hex(data: &[u8]) -> String {
+	format!("0x{}", hex::encode(data))
+}
+
+fn decode_hex(value: &Value) -> Result<Vec<u8>> {
+	let invalid = || Error::Remote(format!("Expected hex data, got: {}", value));
+	let data = value.as_str().ok_or_else(invalid)?;
+	hex::decode(data.strip_prefix("0x").unwrap_or(data)).map_err(|_| invalid())
+}
+
+fn encode_key_type(id: KeyTypeId) -> String {
+	String::from_utf8_lossy(&id.0).into_owned()
+}
+
+fn encode_key(key: &CryptoTypePublicPair) -> Value {
+	json!({
+		"cryptoType": String::from_utf8_lossy(&(key.0).0),
+		"public": encode_hex(&key.1),
+	})
+}
+
+fn decode_key(value: &Value) -> Result<CryptoTypePublicPair> {
+	let crypto_type = value["cryptoType"].as_str()
+		.map(str::as_bytes)
+		.and_then(|crypto_type| <[u8; 4]>::try_from(crypto_type).ok())
+		.ok_or_else(|| Error::Remote(format!("Invalid crypto type in: {}", value)))?;
+	Ok(CryptoTypePublicPair(CryptoTypeId(crypto_type), decode_hex(&value["public"])?))
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn keys(&self, id: KeyTypeId) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		SyncCryptoStore::keys(self, id)
+	}
+
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		SyncCryptoStore::sr25519_public_keys(self, id)
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		SyncCryptoStore::sr25519_generate_new(self, id, seed)
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		SyncCryptoStore::ed25519_public_keys(self, id)
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		SyncCryptoStore::ed25519_generate_new(self, id, seed)
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		SyncCryptoStore::ecdsa_public_keys(self, id)
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		SyncCryptoStore::ecdsa_generate_new(self, id, seed)
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> std::result::Result<(), ()> {
+		SyncCryptoStore::insert_unknown(self, id, suri, public)
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		SyncCryptoStore::has_keys(self, public_keys)
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		SyncCryptoStore::supported_keys(self, id, keys)
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError> {
+		SyncCryptoStore::sign_with(self, id, key, msg)
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> std::result::Result<Option<VRFSignature>, TraitError> {
+		SyncCryptoStore::sr25519_vrf_sign(self, key_type, public, transcript_data)
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn keys(
+		&self,
+		id: KeyTypeId
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		if !self.is_allowed(id) {
+			return Ok(Vec::new())
+		}
+
+		let keys = self.request("keystore_publicKeys", json!([encode_key_type(id)]))?;
+		keys.as_array()
+			.ok_or_else(|| Error::Remote(format!("Expected a list of keys, got: {}", keys)))?
+			.iter()
+			.map(|key| decode_key(key).map_err(Into::into))
+			.collect()
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let all_keys = SyncCryptoStore::keys(self, id)?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect::<Vec<_>>())
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError> {
+		if !self.is_allowed(id) {
+			return Err(TraitError::KeyNotSupported(id))
+		}
+
+		let signature = self.request(
+			"keystore_sign",
+			json!([encode_key_type(id), encode_key(key), encode_hex(msg)]),
+		)?;
+		if signature.is_null() {
+			return Ok(None)
+		}
+		Ok(Some(decode_hex(&signature)?))
+	}
+
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(key_type, sr25519::CRYPTO_ID)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		_id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		Err(TraitError::Unavailable)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(key_type, ed25519::CRYPTO_ID)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		_id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		Err(TraitError::Unavailable)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(key_type, ecdsa::CRYPTO_ID)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		_id: KeyTypeId,
+		_seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		Err(TraitError::Unavailable)
+	}
+
+	fn insert_unknown(&self, _key_type: KeyTypeId, _suri: &str, _public: &[u8]) -> std::result::Result<(), ()> {
+		Err(())
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		if !public_keys.iter().all(|(_, id)| self.is_allowed(*id)) {
+			return false
+		}
+
+		let public_keys = public_keys.iter()
+			.map(|(public, id)| json!([encode_hex(public), encode_key_type(*id)]))
+			.collect::<Vec<_>>();
+		self.request("keystore_hasKeys", json!([public_keys]))
+			.ok()
+			.and_then(|has_keys| has_keys.as_bool())
+			.unwrap_or(false)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> std::result::Result<Option<VRFSignature>, TraitError> {
+		if !self.is_allowed(key_type) {
+			return Err(TraitError::KeyNotSupported(key_type))
+		}
+
+		let signature = self.request(
+			"keystore_sr25519VrfSign",
+			json!([
+				encode_key_type(key_type),
+				encode_hex(public.as_ref()),
+				encode_hex(&transcript_data.encode()),
+			]),
+		)?;
+		if signature.is_null() {
+			return Ok(None)
+		}
+
+		let invalid = |_| TraitError::Other(format!("Invalid VRF signature: {}", signature));
+		let output = schnorrkel::vrf::VRFOutput::from_bytes(&decode_hex(&signature["output"])?)
+			.map_err(invalid)?;
+		let proof = schnorrkel::vrf::VRFProof::from_bytes(&decode_hex(&signature["proof"])?)
+			.map_err(invalid)?;
+		Ok(Some(VRFSignature { output, proof }))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use std::{os::unix::net::UnixListener, sync::Arc, thread};
+	use sp_core::{crypto::Public as _, testing::{ED25519, SR25519}, Decode, Pair as _};
+	use tempfile::TempDir;
+
+	/// Answers `keystore_publicKeys` and `keystore_sign` requests with the given keystore.
+	fn spawn_signer(listener: UnixListener, keystore: Arc<LocalKeystore>) {
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				let mut stream = stream.unwrap();
+				let mut line = String::new();
+				BufReader::new(&stream).read_line(&mut line).unwrap();
+				let request: Value = serde_json::from_str(&line).unwrap();
+				let key_type = KeyTypeId::try_from(request["params"][0].as_str().unwrap()).unwrap();
+
+				let result = match request["method"].as_str().unwrap() {
+					"keystore_publicKeys" => SyncCryptoStore::keys(&*keystore, key_type)
+						.unwrap()
+						.iter()
+						.filter(|key| key.0 == sr25519::CRYPTO_ID)
+						.map(encode_key)
+						.collect(),
+					"keystore_sign" => {
+						let key = decode_key(&request["params"][1]).unwrap();
+						let msg = decode_hex(&request["params"][2]).unwrap();
+						SyncCryptoStore::sign_with(&*keystore, key_type, &key, &msg)
+							.unwrap()
+							.map(|signature| json!(encode_hex(&signature)))
+							.unwrap_or(Value::Null)
+					},
+					method => panic!("Unexpected method: {}", method),
+				};
+
+				let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": result });
+				writeln!(stream, "{}", response).unwrap();
+			}
+		});
+	}
+
+	#[test]
+	fn signs_through_remote_signer() {
+		let temp_dir = TempDir::new().unwrap();
+		let socket = temp_dir.path().join("signer.sock");
+		let local = Arc::new(LocalKeystore::in_memory());
+		let public = SyncCryptoStore::sr25519_generate_new(&*local, SR25519, None).unwrap();
+		SyncCryptoStore::sr25519_generate_new(&*local, ED25519, None).unwrap();
+		spawn_signer(UnixListener::bind(&socket).unwrap(), local);
+
+		let remote = RemoteKeystore::open(
+			&format!("unix://{}?key_types={}", socket.display(), encode_key_type(SR25519)),
+		).unwrap();
+
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&remote, SR25519), vec![public]);
+		// Key types outside of the allow-list are never forwarded.
+		assert!(SyncCryptoStore::sr25519_public_keys(&remote, ED25519).is_empty());
+
+		let key = CryptoTypePublicPair(sr25519::CRYPTO_ID, public.to_raw_vec());
+		let signature = SyncCryptoStore::sign_with(&remote, SR25519, &key, b"hello")
+			.unwrap()
+			.unwrap();
+		let signature = sr25519::Signature::decode(&mut &signature[..]).unwrap();
+		assert!(sp_core::sr25519::Pair::verify(&signature, b"hello", &public));
+
+		assert!(matches!(
+			SyncCryptoStore::sign_with(&remote, ED25519, &key, b"hello"),
+			Err(TraitError::KeyNotSupported(_)),
+		));
+	}
+
+	#[test]
+	fn requires_key_type_allow_list() {
+		assert!(matches!(
+			RemoteKeystore::open("unix:///tmp/signer.sock"),
+			Err(Error::Remote(_)),
+		));
+		assert!(matches!(
+			RemoteKeystore::open("http://localhost:1234?key_types=aura"),
+			Err(Error::Remote(_)),
+		));
+	}
+}
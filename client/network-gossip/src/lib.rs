@@ -60,10 +60,18 @@
 //! opens the door for neighbor status packets to be baked into the gossip protocol.
 //! These status packets will typically contain light pieces of information
 //! used to inform peers of a current view of protocol state.
+//!
+//! Every protocol can tune its gossip through its validator: how often all messages are
+//! rebroadcast and how many message hashes are remembered in order to detect duplicates.
+//! Validators can also adjust the reputation of peers that misbehave, which is fed into the
+//! peerset.
 
 pub use self::bridge::GossipEngine;
 pub use self::state_machine::TopicNotification;
-pub use self::validator::{DiscardAll, MessageIntent, Validator, ValidatorContext, ValidationResult};
+pub use self::validator::{
+	DiscardAll, MessageIntent, Validator, ValidatorContext, ValidationResult,
+	DEFAULT_KNOWN_MESSAGES_CACHE_SIZE, DEFAULT_REBROADCAST_INTERVAL,
+};
 
 use futures::prelude::*;
 use sc_network::{multiaddr, Event, ExHashT, NetworkService, PeerId, ReputationChange};
@@ -27,21 +27,10 @@ use lru::LruCache;
 use libp2p::PeerId;
 use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
 use sp_runtime::traits::{Block as BlockT, Hash, HashFor};
-use sc_network::ObservedRole;
+use sc_network::{ObservedRole, ReputationChange};
 use wasm_timer::Instant;
 
 // FIXME: Add additional spam/DoS attack protection: https://github.com/paritytech/substrate/issues/1115
-// NOTE: The current value is adjusted based on largest production network deployment (Kusama) and
-// the current main gossip user (GRANDPA). Currently there are ~800 validators on Kusama, as such,
-// each GRANDPA round should generate ~1600 messages, and we currently keep track of the last 2
-// completed rounds and the current live one. That makes it so that at any point we will be holding
-// ~4800 live messages.
-//
-// Assuming that each known message is tracked with a 32 byte hash (common for `Block::Hash`), then
-// this cache should take about 256 KB of memory.
-const KNOWN_MESSAGES_CACHE_SIZE: usize = 8192;
-
-const REBROADCAST_INTERVAL: time::Duration = time::Duration::from_secs(30);
 
 pub(crate) const PERIODIC_MAINTENANCE_INTERVAL: time::Duration = time::Duration::from_millis(1100);
 
@@ -104,6 +93,11 @@ impl<'g, 'p, B: BlockT> ValidatorContext<B> for NetworkContext<'g, 'p, B> {
 	fn send_topic(&mut self, who: &PeerId, topic: B::Hash, force: bool) {
 		self.gossip.send_topic(self.network, who, topic, force);
 	}
+
+	/// Adjust the reputation of a peer.
+	fn report_peer(&mut self, who: &PeerId, reputation: ReputationChange) {
+		self.network.report_peer(who.clone(), reputation);
+	}
 }
 
 fn propagate<'a, B: BlockT, I>(
@@ -164,6 +158,7 @@ pub struct ConsensusGossip<B: BlockT> {
 	known_messages: LruCache<B::Hash, ()>,
 	protocol: Cow<'static, str>,
 	validator: Arc<dyn Validator<B>>,
+	rebroadcast_interval: time::Duration,
 	next_broadcast: Instant,
 	metrics: Option<Metrics>,
 }
@@ -184,13 +179,15 @@ impl<B: BlockT> ConsensusGossip<B> {
 			None => None,
 		};
 
+		let rebroadcast_interval = validator.rebroadcast_interval();
 		ConsensusGossip {
 			peers: HashMap::new(),
 			messages: Default::default(),
-			known_messages: LruCache::new(KNOWN_MESSAGES_CACHE_SIZE),
+			known_messages: LruCache::new(validator.known_messages_cache_size()),
 			protocol,
 			validator,
-			next_broadcast: Instant::now() + REBROADCAST_INTERVAL,
+			rebroadcast_interval,
+			next_broadcast: Instant::now() + rebroadcast_interval,
 			metrics,
 		}
 	}
@@ -266,7 +263,7 @@ impl<B: BlockT> ConsensusGossip<B> {
 		self.collect_garbage();
 		if Instant::now() >= self.next_broadcast {
 			self.rebroadcast(network);
-			self.next_broadcast = Instant::now() + REBROADCAST_INTERVAL;
+			self.next_broadcast = Instant::now() + self.rebroadcast_interval;
 		}
 	}
 
@@ -748,4 +745,61 @@ mod tests {
 			to_forward,
 		);
 	}
+
+	#[test]
+	fn uses_the_configuration_of_the_validator() {
+		struct Configured;
+		impl Validator<Block> for Configured {
+			fn validate(
+				&self,
+				_context: &mut dyn ValidatorContext<Block>,
+				_sender: &PeerId,
+				_data: &[u8],
+			) -> ValidationResult<H256> {
+				ValidationResult::ProcessAndKeep(H256::default())
+			}
+
+			fn rebroadcast_interval(&self) -> time::Duration {
+				time::Duration::from_secs(5)
+			}
+
+			fn known_messages_cache_size(&self) -> usize {
+				2
+			}
+		}
+
+		let mut consensus = ConsensusGossip::<Block>::new(Arc::new(Configured), "/foo".into(), None);
+		assert_eq!(consensus.rebroadcast_interval, time::Duration::from_secs(5));
+
+		for i in 0..3u8 {
+			consensus.register_message(H256::default(), vec![i]);
+		}
+		assert_eq!(consensus.known_messages.len(), 2);
+	}
+
+	#[test]
+	fn validator_can_report_peers() {
+		const INVALID_MESSAGE: ReputationChange = ReputationChange::new(-100, "Invalid message");
+
+		struct ReportAll;
+		impl Validator<Block> for ReportAll {
+			fn validate(
+				&self,
+				context: &mut dyn ValidatorContext<Block>,
+				sender: &PeerId,
+				_data: &[u8],
+			) -> ValidationResult<H256> {
+				context.report_peer(sender, INVALID_MESSAGE);
+				ValidationResult::Discard
+			}
+		}
+
+		let mut network = NoOpNetwork::default();
+		let remote = PeerId::random();
+		let mut consensus = ConsensusGossip::<Block>::new(Arc::new(ReportAll), "/foo".into(), None);
+		consensus.new_peer(&mut network, remote.clone(), ObservedRole::Full);
+		consensus.on_incoming(&mut network, remote.clone(), vec![vec![1, 2, 3]]);
+
+		assert_eq!(network.inner.lock().unwrap().peer_reports, vec![(remote, INVALID_MESSAGE)]);
+	}
 }
@@ -16,8 +16,23 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use sc_network::{ObservedRole, PeerId};
+use sc_network::{ObservedRole, PeerId, ReputationChange};
 use sp_runtime::traits::Block as BlockT;
+use std::time::Duration;
+
+/// The default interval in which all messages are rebroadcast to all peers.
+pub const DEFAULT_REBROADCAST_INTERVAL: Duration = Duration::from_secs(30);
+
+// NOTE: The current value is adjusted based on largest production network deployment (Kusama) and
+// the current main gossip user (GRANDPA). Currently there are ~800 validators on Kusama, as such,
+// each GRANDPA round should generate ~1600 messages, and we currently keep track of the last 2
+// completed rounds and the current live one. That makes it so that at any point we will be holding
+// ~4800 live messages.
+//
+// Assuming that each known message is tracked with a 32 byte hash (common for `Block::Hash`), then
+// this cache should take about 256 KB of memory.
+/// The default number of message hashes that are remembered in order to detect duplicates.
+pub const DEFAULT_KNOWN_MESSAGES_CACHE_SIZE: usize = 8192;
 
 /// Validates consensus messages.
 pub trait Validator<B: BlockT>: Send + Sync {
@@ -46,6 +61,21 @@ pub trait Validator<B: BlockT>: Send + Sync {
 	fn message_allowed<'a>(&'a self) -> Box<dyn FnMut(&PeerId, MessageIntent, &B::Hash, &[u8]) -> bool + 'a> {
 		Box::new(move |_who, _intent, _topic, _data| true)
 	}
+
+	/// The interval in which all messages of this protocol are rebroadcast to all peers.
+	///
+	/// This is only queried once when the gossip engine of the protocol is created.
+	fn rebroadcast_interval(&self) -> Duration {
+		DEFAULT_REBROADCAST_INTERVAL
+	}
+
+	/// The number of message hashes of this protocol that are remembered in order to detect
+	/// duplicate messages.
+	///
+	/// This is only queried once when the gossip engine of the protocol is created.
+	fn known_messages_cache_size(&self) -> usize {
+		DEFAULT_KNOWN_MESSAGES_CACHE_SIZE
+	}
 }
 
 /// Validation context. Allows reacting to incoming messages by sending out further messages.
@@ -58,6 +88,8 @@ pub trait ValidatorContext<B: BlockT> {
 	fn send_message(&mut self, who: &PeerId, message: Vec<u8>);
 	/// Send all messages with given topic to a peer.
 	fn send_topic(&mut self, who: &PeerId, topic: B::Hash, force: bool);
+	/// Adjust the reputation of a peer, e.g. because it sent an invalid message.
+	fn report_peer(&mut self, who: &PeerId, reputation: ReputationChange);
 }
 
 /// The reason for sending out the message.
@@ -115,6 +115,15 @@ pub struct Params<B: BlockT, H: ExHashT> {
 	/// incoming requests.
 	pub block_request_protocol_config: RequestResponseConfig,
 
+	/// Request response configuration for the state request protocol.
+	///
+	/// Can be constructed either via
+	/// [`crate::state_request_handler::generate_protocol_config`] allowing outgoing but not
+	/// incoming requests, or constructed via
+	/// [`crate::state_request_handler::StateRequestHandler::new`] allowing
+	/// both outgoing and incoming requests.
+	pub state_request_protocol_config: RequestResponseConfig,
+
 	/// Request response configuration for the light client request protocol.
 	///
 	/// Can be constructed either via
@@ -283,6 +292,16 @@ pub fn parse_addr(mut addr: Multiaddr)-> Result<(PeerId, Multiaddr), ParseErr> {
 /// assert_eq!(addr.peer_id.to_base58(), "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV");
 /// assert_eq!(addr.multiaddr.to_string(), "/ip4/198.51.100.19/tcp/30333");
 /// ```
+///
+/// DNS names (`/dns`, `/dns4` and `/dns6`) are accepted as well. They are resolved every time the
+/// node is dialed, so that a changed DNS record is picked up when reconnecting.
+///
+/// ```
+/// # use sc_network::config::MultiaddrWithPeerId;
+/// let addr: MultiaddrWithPeerId =
+/// 	"/dns/example.com/tcp/30333/p2p/QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV".parse().unwrap();
+/// assert_eq!(addr.multiaddr.to_string(), "/dns/example.com/tcp/30333");
+/// ```
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(try_from = "String", into = "String")]
 pub struct MultiaddrWithPeerId {
@@ -368,6 +387,21 @@ impl From<multiaddr::Error> for ParseErr {
 	}
 }
 
+/// Sync operation mode.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SyncMode {
+	/// Full block download and verification.
+	Full,
+	/// Download blocks without executing them and download the latest finalized state.
+	Fast,
+}
+
+impl Default for SyncMode {
+	fn default() -> Self {
+		SyncMode::Full
+	}
+}
+
 /// Network service configuration.
 #[derive(Clone, Debug)]
 pub struct NetworkConfiguration {
@@ -395,6 +429,15 @@ pub struct NetworkConfiguration {
 	pub transport: TransportConfig,
 	/// Maximum number of peers to ask the same blocks in parallel.
 	pub max_parallel_downloads: u32,
+	/// Initial syncing mode.
+	pub sync_mode: SyncMode,
+	/// Maximum number of outbound peers of the default set whose address shares the same IPv4
+	/// `/24` or IPv6 `/64` prefix. Reserved nodes don't count towards this limit. `None` for no
+	/// limit.
+	///
+	/// Limiting the number of peers within a single network makes it more expensive for an
+	/// attacker to fill all of our slots with its own nodes.
+	pub max_outbound_peers_per_ip_prefix: Option<u32>,
 
 	/// True if Kademlia random discovery should be enabled.
 	///
@@ -457,6 +500,8 @@ impl NetworkConfiguration {
 				wasm_external_transport: None,
 			},
 			max_parallel_downloads: 5,
+			sync_mode: SyncMode::Full,
+			max_outbound_peers_per_ip_prefix: None,
 			enable_dht_random_walk: true,
 			allow_non_globals_in_dht: false,
 			kademlia_disjoint_query_paths: false,
@@ -782,6 +827,17 @@ mod tests {
 		assert!(secret_bytes(&kp1) == secret_bytes(&kp2));
 	}
 
+	#[test]
+	fn parse_dns_addresses() {
+		let peer_id = "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV";
+		for proto in &["dns", "dns4", "dns6"] {
+			let addr = format!("/{}/example.com/tcp/30333", proto);
+			let parsed: MultiaddrWithPeerId = format!("{}/p2p/{}", addr, peer_id).parse().unwrap();
+			assert_eq!(parsed.multiaddr.to_string(), addr);
+			assert_eq!(parsed.peer_id.to_base58(), peer_id);
+		}
+	}
+
 	#[test]
 	fn test_secret_new() {
 		let kp1 = NodeKeyConfig::Ed25519(Secret::New).into_keypair().unwrap();
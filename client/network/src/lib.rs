@@ -129,6 +129,10 @@
 //! requests for information about blocks. Each request is the encoding of a `BlockRequest` and
 //! each response is the encoding of a `BlockResponse`, as defined in the `api.v1.proto` file in
 //! this source tree.
+//! - **`/<protocol-id>/state/1`** is a request-response protocol (see below) that lets one
+//! download a range of the storage of a block, along with a proof. Each request is the encoding
+//! of a `StateRequest` and each response is the encoding of a `StateResponse`, as defined in the
+//! `api.v1.proto` file in this source tree. It is used by the `Fast` sync mode.
 //! - **`/<protocol-id>/light/2`** is a request-response protocol (see below) that lets one perform
 //! light-client-related requests for information about the state. Each request is the encoding of
 //! a `light::Request` and each response is the encoding of a `light::Response`, as defined in the
@@ -260,6 +264,7 @@ mod utils;
 pub mod block_request_handler;
 pub mod bitswap;
 pub mod light_client_requests;
+pub mod state_request_handler;
 pub mod config;
 pub mod error;
 pub mod gossip;
@@ -331,4 +331,52 @@ mod tests {
 		assert_eq!(vec![(100, 2)], pool.run_until(chan.1).unwrap().unwrap());
 		//              ^--- from `DummyFetchChecker::check_changes_proof`
 	}
+
+	#[test]
+	fn read_response_carries_compact_proof_if_requested() {
+		use codec::{Decode, Encode};
+		use prost::Message;
+		use sc_client_api::CompactProof;
+		use sp_runtime::generic::BlockId;
+
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let header = client.header(&BlockId::Number(0)).unwrap().unwrap();
+		let (handler, protocol_config) =
+			handler::LightClientRequestHandler::new(&protocol_id(), client);
+		let mut pool = LocalPool::new();
+		pool.spawner().spawn_obj(handler.run().boxed().into()).unwrap();
+
+		let request = crate::schema::v1::light::Request {
+			request: Some(crate::schema::v1::light::request::Request::RemoteReadRequest(
+				crate::schema::v1::light::RemoteReadRequest {
+					block: sp_runtime::traits::Header::hash(&header).encode(),
+					keys: vec![sp_core::storage::well_known_keys::CODE.to_vec()],
+					compact_proof: true,
+				},
+			)),
+		};
+		let mut payload = Vec::new();
+		request.encode(&mut payload).unwrap();
+
+		let (tx, rx) = oneshot::channel();
+		block_on(protocol_config.inbound_queue.unwrap().send(IncomingRequest {
+			peer: PeerId::random(),
+			payload,
+			pending_response: tx,
+		})).unwrap();
+		let response = pool.run_until(rx).unwrap().result.unwrap();
+
+		let response = match crate::schema::v1::light::Response::decode(&response[..]).unwrap() {
+			crate::schema::v1::light::Response {
+				response: Some(crate::schema::v1::light::response::Response::RemoteReadResponse(r)),
+			} => r,
+			_ => panic!("Expect a remote read response."),
+		};
+		assert!(response.proof.is_empty());
+		let compact_proof = CompactProof::decode(&mut &response.compact_proof[..]).unwrap();
+		assert!(!StorageProof::from_compact_proof::<BlakeTwo256>(
+			&compact_proof,
+			Some(&header.state_root),
+		).unwrap().0.is_empty());
+	}
 }
@@ -41,7 +41,7 @@ use sp_core::{
 	hexdisplay::HexDisplay,
 };
 use sp_runtime::{
-	traits::{Block, Zero},
+	traits::{Block, HashFor, Header, Zero},
 	generic::BlockId,
 };
 use std::{
@@ -189,8 +189,9 @@ impl<B: Block> LightClientRequestHandler<B> {
 			}
 		};
 
+		let (proof, compact_proof) = self.encode_proof(block, proof, request.compact_proof);
 		let response = {
-			let r = schema::v1::light::RemoteCallResponse { proof: proof.encode() };
+			let r = schema::v1::light::RemoteCallResponse { proof, compact_proof };
 			schema::v1::light::response::Response::RemoteCallResponse(r)
 		};
 
@@ -228,8 +229,9 @@ impl<B: Block> LightClientRequestHandler<B> {
 			}
 		};
 
+		let (proof, compact_proof) = self.encode_proof(block, proof, request.compact_proof);
 		let response = {
-			let r = schema::v1::light::RemoteReadResponse { proof: proof.encode() };
+			let r = schema::v1::light::RemoteReadResponse { proof, compact_proof };
 			schema::v1::light::response::Response::RemoteReadResponse(r)
 		};
 
@@ -280,8 +282,9 @@ impl<B: Block> LightClientRequestHandler<B> {
 			}
 		};
 
+		let (proof, compact_proof) = self.encode_proof(block, proof, request.compact_proof);
 		let response = {
-			let r = schema::v1::light::RemoteReadResponse { proof: proof.encode() };
+			let r = schema::v1::light::RemoteReadResponse { proof, compact_proof };
 			schema::v1::light::response::Response::RemoteReadResponse(r)
 		};
 
@@ -378,6 +381,37 @@ impl<B: Block> LightClientRequestHandler<B> {
 
 		Ok(schema::v1::light::Response { response: Some(response) })
 	}
+
+	/// Encode a storage proof for the response, returning the `proof` and `compact_proof` fields.
+	///
+	/// The proof is only compacted if the requester supports it. It is sent in full if compacting
+	/// fails, e.g. because the proof is empty or the header of `block` is unknown.
+	fn encode_proof(
+		&self,
+		block: B::Hash,
+		proof: StorageProof,
+		compact: bool,
+	) -> (Vec<u8>, Vec<u8>) {
+		if !compact {
+			return (proof.encode(), Vec::new())
+		}
+
+		let state_root = match self.client.header(BlockId::Hash(block)) {
+			Ok(Some(header)) => *header.state_root(),
+			_ => return (proof.encode(), Vec::new()),
+		};
+
+		match proof.clone().into_compact_proof::<HashFor<B>>(state_root) {
+			Ok(compact_proof) => (Vec::new(), compact_proof.encode()),
+			Err(error) => {
+				log::trace!(
+					"Failed to compact proof at {:?}, sending it in full: {:?}",
+					block, error,
+				);
+				(proof.encode(), Vec::new())
+			}
+		}
+	}
 }
 
 #[derive(derive_more::Display, derive_more::From)]
@@ -41,12 +41,13 @@ use prost::Message;
 use sc_client_api::{
 	light::{
 		self, RemoteBodyRequest,
-	}
+	},
+	CompactProof, StorageProof,
 };
 use sc_peerset::ReputationChange;
 use sp_blockchain::{Error as ClientError};
 use sp_runtime::{
-	traits::{Block, Header, NumberFor},
+	traits::{Block, HashFor, Header, NumberFor},
 };
 use std::{
 	collections::{BTreeMap, VecDeque, HashMap},
@@ -222,7 +223,11 @@ where
 		match response.response {
 			Some(Response::RemoteCallResponse(response)) =>
 				if let Request::Call { request , .. } = request {
-					let proof = Decode::decode(&mut response.proof.as_ref())?;
+					let proof = decode_proof::<B>(
+						&request.header,
+						&response.proof,
+						&response.compact_proof,
+					)?;
 					let reply = self.checker.check_execution_proof(request, proof)?;
 					Ok(Reply::VecU8(reply))
 				} else {
@@ -231,12 +236,20 @@ where
 			Some(Response::RemoteReadResponse(response)) =>
 				match request {
 					Request::Read { request, .. } => {
-						let proof = Decode::decode(&mut response.proof.as_ref())?;
+						let proof = decode_proof::<B>(
+							&request.header,
+							&response.proof,
+							&response.compact_proof,
+						)?;
 						let reply = self.checker.check_read_proof(&request, proof)?;
 						Ok(Reply::MapVecU8OptVecU8(reply))
 					}
 					Request::ReadChild { request, .. } => {
-						let proof = Decode::decode(&mut response.proof.as_ref())?;
+						let proof = decode_proof::<B>(
+							&request.header,
+							&response.proof,
+							&response.compact_proof,
+						)?;
 						let reply = self.checker.check_read_child_proof(&request, proof)?;
 						Ok(Reply::MapVecU8OptVecU8(reply))
 					}
@@ -588,6 +601,27 @@ enum Error {
 	/// The chain client errored.
 	#[display(fmt = "client error: {}", _0)]
 	Client(ClientError),
+	/// The compact proof does not decode against the state root of the requested block.
+	#[display(fmt = "invalid compact proof")]
+	InvalidCompactProof,
+}
+
+/// Decode the storage proof of a response, which is in compact form if `compact_proof` is set.
+///
+/// A compact proof is checked against the state root of `header`.
+fn decode_proof<B: Block>(
+	header: &B::Header,
+	proof: &[u8],
+	compact_proof: &[u8],
+) -> Result<StorageProof, Error> {
+	if compact_proof.is_empty() {
+		return Ok(Decode::decode(&mut &proof[..])?)
+	}
+
+	let compact_proof = CompactProof::decode(&mut &compact_proof[..])?;
+	StorageProof::from_compact_proof::<HashFor<B>>(&compact_proof, Some(header.state_root()))
+		.map(|(proof, _)| proof)
+		.map_err(|_| Error::InvalidCompactProof)
 }
 
 /// The data to send back to the light client over the oneshot channel.
@@ -737,6 +771,7 @@ impl<B: Block> Request<B> {
 				let r = schema::v1::light::RemoteReadRequest {
 					block: request.block.encode(),
 					keys: request.keys.clone(),
+					compact_proof: true,
 				};
 				schema::v1::light::request::Request::RemoteReadRequest(r)
 			}
@@ -745,6 +780,7 @@ impl<B: Block> Request<B> {
 					block: request.block.encode(),
 					storage_key: request.storage_key.clone().into_inner(),
 					keys: request.keys.clone(),
+					compact_proof: true,
 				};
 				schema::v1::light::request::Request::RemoteReadChildRequest(r)
 			}
@@ -753,6 +789,7 @@ impl<B: Block> Request<B> {
 					block: request.block.encode(),
 					method: request.method.clone(),
 					data: request.call_data.clone(),
+					compact_proof: true,
 				};
 				schema::v1::light::request::Request::RemoteCallRequest(r)
 			}
@@ -1004,6 +1041,7 @@ mod tests {
 		let response = {
 			let r = schema::v1::light::RemoteCallResponse {
 				proof: empty_proof(),
+				compact_proof: Vec::new(),
 			};
 			let response = schema::v1::light::Response {
 				response: Some(schema::v1::light::response::Response::RemoteCallResponse(r)),
@@ -1065,6 +1103,7 @@ mod tests {
 		let response = {
 			let r = schema::v1::light::RemoteReadResponse {
 				proof: empty_proof(),
+				compact_proof: Vec::new(),
 			}; // Not a RemoteCallResponse!
 			let response = schema::v1::light::Response {
 				response: Some(schema::v1::light::response::Response::RemoteReadResponse(r)),
@@ -1133,6 +1172,7 @@ mod tests {
 			let response = {
 				let r = schema::v1::light::RemoteCallResponse {
 					proof: empty_proof(),
+					compact_proof: Vec::new(),
 				};
 				let response = schema::v1::light::Response {
 					response: Some(schema::v1::light::response::Response::RemoteCallResponse(r)),
@@ -1194,6 +1234,7 @@ mod tests {
 			Request::Read { .. } => {
 				let r = schema::v1::light::RemoteReadResponse {
 					proof: empty_proof(),
+					compact_proof: Vec::new(),
 				};
 				schema::v1::light::Response {
 					response: Some(schema::v1::light::response::Response::RemoteReadResponse(r)),
@@ -1202,6 +1243,7 @@ mod tests {
 			Request::ReadChild { .. } => {
 				let r = schema::v1::light::RemoteReadResponse {
 					proof: empty_proof(),
+					compact_proof: Vec::new(),
 				};
 				schema::v1::light::Response {
 					response: Some(schema::v1::light::response::Response::RemoteReadResponse(r)),
@@ -1210,6 +1252,7 @@ mod tests {
 			Request::Call { .. } => {
 				let r = schema::v1::light::RemoteCallResponse {
 					proof: empty_proof(),
+					compact_proof: Vec::new(),
 				};
 				schema::v1::light::Response {
 					response: Some(schema::v1::light::response::Response::RemoteCallResponse(r)),
@@ -1341,4 +1384,37 @@ mod tests {
 		});
 		assert_matches!(chan.1.try_recv(), Ok(Some(Ok(_))))
 	}
+
+	#[test]
+	fn compact_proof_is_checked_against_state_root() {
+		use sc_client_api::ProofProvider;
+		use sp_core::storage::well_known_keys;
+		use sp_runtime::generic::BlockId;
+
+		let client = substrate_test_runtime_client::new();
+		let mut header = client.header(&BlockId::Number(0)).unwrap().unwrap();
+		let proof = client.read_proof(
+			&BlockId::Number(0),
+			&mut std::iter::once(well_known_keys::CODE),
+		).unwrap();
+		let compact_proof = proof.clone()
+			.into_compact_proof::<BlakeTwo256>(header.state_root)
+			.unwrap()
+			.encode();
+
+		let decoded = decode_proof::<Block>(&header, &[], &compact_proof).unwrap();
+		assert_eq!(
+			decoded.iter_nodes().collect::<HashSet<_>>(),
+			proof.clone().iter_nodes().collect::<HashSet<_>>(),
+		);
+
+		// A full proof is used as is.
+		assert_eq!(decode_proof::<Block>(&header, &proof.encode(), &[]).unwrap(), proof);
+
+		header.state_root = Default::default();
+		assert_matches!(
+			decode_proof::<Block>(&header, &[], &compact_proof),
+			Err(Error::InvalidCompactProof)
+		);
+	}
 }
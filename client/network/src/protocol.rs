@@ -32,7 +32,7 @@ use libp2p::core::{ConnectedPoint, connection::{ConnectionId, ListenerId}};
 use libp2p::request_response::OutboundFailure;
 use libp2p::swarm::{NetworkBehaviour, NetworkBehaviourAction, PollParameters};
 use libp2p::swarm::{ProtocolsHandler, IntoProtocolsHandler};
-use libp2p::{Multiaddr, PeerId};
+use libp2p::{multiaddr, Multiaddr, PeerId};
 use log::{log, Level, trace, debug, warn, error};
 use message::{BlockAnnounce, Message};
 use message::generic::{Message as GenericMessage, Roles};
@@ -54,7 +54,7 @@ use std::borrow::Cow;
 use std::convert::TryFrom as _;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use std::{io, iter, num::NonZeroUsize, pin::Pin, task::Poll, time};
+use std::{io, iter, net::Ipv4Addr, num::NonZeroUsize, pin::Pin, task::Poll, time};
 
 mod notifications;
 
@@ -105,6 +105,39 @@ mod rep {
 	pub const BAD_ROLE: Rep = Rep::new_fatal("Unsupported role");
 	/// Peer send us a block announcement that failed at validation.
 	pub const BAD_BLOCK_ANNOUNCEMENT: Rep = Rep::new(-(1 << 12), "Bad block announcement");
+	/// We already have too many outbound peers in the same network as this peer.
+	pub const IP_PREFIX_LIMIT: Rep = Rep::new(-(1 << 8), "Too many peers in the same network");
+}
+
+/// Network prefix of an IP address: the `/24` of an IPv4 or the `/64` of an IPv6 address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum IpPrefix {
+	V4([u8; 3]),
+	V6([u16; 4]),
+}
+
+/// Returns the prefix of the IP address at the start of `address`, if any.
+///
+/// IPv4-mapped IPv6 addresses are grouped with their IPv4 counterpart. Addresses starting with a
+/// DNS name have no prefix.
+fn ip_prefix(address: &Multiaddr) -> Option<IpPrefix> {
+	let v4_prefix = |ip: Ipv4Addr| {
+		let [a, b, c, _] = ip.octets();
+		IpPrefix::V4([a, b, c])
+	};
+
+	match address.iter().next()? {
+		multiaddr::Protocol::Ip4(ip) => Some(v4_prefix(ip)),
+		multiaddr::Protocol::Ip6(ip) => {
+			let segments = ip.segments();
+			if segments[..6] == [0, 0, 0, 0, 0, 0xffff] {
+				ip.to_ipv4().map(v4_prefix)
+			} else {
+				Some(IpPrefix::V6([segments[0], segments[1], segments[2], segments[3]]))
+			}
+		},
+		_ => None,
+	}
 }
 
 struct Metrics {
@@ -177,6 +210,10 @@ pub struct Protocol<B: BlockT> {
 	boot_node_ids: HashSet<PeerId>,
 	/// A cache for the data that was associated to a block announcement.
 	block_announce_data_cache: lru::LruCache<B::Hash, Vec<u8>>,
+	/// Maximum number of outbound sync peers sharing the same [`IpPrefix`], if any.
+	max_outbound_peers_per_ip_prefix: Option<usize>,
+	/// IP prefix of the address of the peers we have dialed.
+	outbound_ip_prefixes: HashMap<PeerId, IpPrefix>,
 }
 
 /// Peer information
@@ -188,6 +225,8 @@ struct Peer<B: BlockT> {
 		message::BlockRequest<B>,
 		oneshot::Receiver<Result<Vec<u8>, RequestFailure>>,
 	)>,
+	/// Current state request, if any. Started by emitting [`CustomMessageOutcome::StateRequest`].
+	state_request: Option<oneshot::Receiver<Result<Vec<u8>, RequestFailure>>>,
 	/// Holds a set of blocks known to this peer.
 	known_blocks: LruHashSet<B::Hash>,
 }
@@ -210,6 +249,8 @@ pub struct ProtocolConfig {
 	pub roles: Roles,
 	/// Maximum number of peers to ask the same blocks in parallel.
 	pub max_parallel_downloads: u32,
+	/// Initial syncing mode.
+	pub sync_mode: config::SyncMode,
 }
 
 impl Default for ProtocolConfig {
@@ -217,6 +258,7 @@ impl Default for ProtocolConfig {
 		ProtocolConfig {
 			roles: Roles::FULL,
 			max_parallel_downloads: 5,
+			sync_mode: config::SyncMode::Full,
 		}
 	}
 }
@@ -264,6 +306,7 @@ impl<B: BlockT> Protocol<B> {
 		let info = chain.info();
 		let sync = ChainSync::new(
 			config.roles,
+			config.sync_mode,
 			chain.clone(),
 			&info,
 			block_announce_validator,
@@ -398,6 +441,9 @@ impl<B: BlockT> Protocol<B> {
 			},
 			boot_node_ids,
 			block_announce_data_cache,
+			max_outbound_peers_per_ip_prefix: network_config.max_outbound_peers_per_ip_prefix
+				.map(|max| max as usize),
+			outbound_ip_prefixes: HashMap::new(),
 		};
 
 		Ok((protocol, peerset_handle, known_addresses))
@@ -540,6 +586,27 @@ impl<B: BlockT> Protocol<B> {
 		self.peerset_handle.report_peer(who, reputation)
 	}
 
+	/// Must be called in response to a [`CustomMessageOutcome::StateRequest`] being emitted.
+	/// Must contain the same `PeerId` and request that have been emitted.
+	pub fn on_state_response(
+		&mut self,
+		peer_id: PeerId,
+		response: crate::schema::v1::StateResponse,
+	) -> CustomMessageOutcome<B> {
+		match self.sync.on_state_data(&peer_id, response) {
+			Ok(sync::OnStateData::Import(origin, block)) =>
+				CustomMessageOutcome::BlockImport(origin, vec![block]),
+			Ok(sync::OnStateData::Request(peer, req)) => {
+				prepare_state_request::<B>(&mut self.peers, peer, req)
+			}
+			Err(sync::BadPeer(id, repu)) => {
+				self.behaviour.disconnect_peer(&id, HARDCODED_PEERSETS_SYNC);
+				self.peerset_handle.report_peer(id, repu);
+				CustomMessageOutcome::None
+			}
+		}
+	}
+
 	/// Must be called in response to a [`CustomMessageOutcome::BlockRequest`] being emitted.
 	/// Must contain the same `PeerId` and request that have been emitted.
 	pub fn on_block_response(
@@ -692,6 +759,25 @@ impl<B: BlockT> Protocol<B> {
 			return Err(());
 		}
 
+		if let (Some(max), Some(prefix)) = (
+			self.max_outbound_peers_per_ip_prefix,
+			self.outbound_ip_prefixes.get(&who),
+		) {
+			let same_prefix = self.peers.keys()
+				.filter(|peer| self.outbound_ip_prefixes.get(peer) == Some(prefix))
+				.count();
+			if same_prefix >= max && !self.important_peers.contains(&who) {
+				debug!(
+					target: "sync",
+					"Refusing peer {}: already connected to {} peers in {:?}",
+					who, same_prefix, prefix,
+				);
+				self.peerset_handle.report_peer(who.clone(), rep::IP_PREFIX_LIMIT);
+				self.behaviour.disconnect_peer(&who, HARDCODED_PEERSETS_SYNC);
+				return Err(());
+			}
+		}
+
 		if self.config.roles.is_light() {
 			// we're not interested in light peers
 			if status.roles.is_light() {
@@ -725,6 +811,7 @@ impl<B: BlockT> Protocol<B> {
 				best_number: status.best_number
 			},
 			block_request: None,
+			state_request: None,
 			known_blocks: LruHashSet::new(NonZeroUsize::new(MAX_KNOWN_BLOCKS)
 				.expect("Constant is nonzero")),
 		};
@@ -1139,6 +1226,23 @@ fn prepare_block_request<B: BlockT>(
 	}
 }
 
+fn prepare_state_request<B: BlockT>(
+	peers: &mut HashMap<PeerId, Peer<B>>,
+	who: PeerId,
+	request: crate::schema::v1::StateRequest,
+) -> CustomMessageOutcome<B> {
+	let (tx, rx) = oneshot::channel();
+
+	if let Some(ref mut peer) = peers.get_mut(&who) {
+		peer.state_request = Some(rx);
+	}
+	CustomMessageOutcome::StateRequest {
+		target: who,
+		request: request,
+		pending_response: tx,
+	}
+}
+
 /// Outcome of an incoming custom message.
 #[derive(Debug)]
 #[must_use]
@@ -1168,6 +1272,12 @@ pub enum CustomMessageOutcome<B: BlockT> {
 		request: crate::schema::v1::BlockRequest,
 		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
 	},
+	/// A new state request must be emitted.
+	StateRequest {
+		target: PeerId,
+		request: crate::schema::v1::StateRequest,
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+	},
 	/// Peer has a reported a new head of chain.
 	PeerNewBest(PeerId, NumberFor<B>),
 	/// Now connected to a new peer for syncing purposes.
@@ -1190,6 +1300,11 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 	}
 
 	fn inject_connection_established(&mut self, peer_id: &PeerId, conn: &ConnectionId, endpoint: &ConnectedPoint) {
+		if let ConnectedPoint::Dialer { address } = endpoint {
+			if let Some(prefix) = ip_prefix(address) {
+				self.outbound_ip_prefixes.entry(peer_id.clone()).or_insert(prefix);
+			}
+		}
 		self.behaviour.inject_connection_established(peer_id, conn, endpoint)
 	}
 
@@ -1202,6 +1317,7 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 	}
 
 	fn inject_disconnected(&mut self, peer_id: &PeerId) {
+		self.outbound_ip_prefixes.remove(peer_id);
 		self.behaviour.inject_disconnected(peer_id)
 	}
 
@@ -1230,6 +1346,7 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 
 		// Check for finished outgoing requests.
 		let mut finished_block_requests = Vec::new();
+		let mut finished_state_requests = Vec::new();
 		for (id, peer) in self.peers.iter_mut() {
 			if let Peer { block_request: Some((_, pending_response)), .. } = peer {
 				match pending_response.poll_unpin(cx) {
@@ -1296,11 +1413,86 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 					Poll::Pending => {},
 				}
 			}
+			if let Peer { state_request: Some(pending_response), .. } = peer {
+				match pending_response.poll_unpin(cx) {
+					Poll::Ready(Ok(Ok(resp))) => {
+						peer.state_request.take();
+
+						let protobuf_response =
+							match crate::schema::v1::StateResponse::decode(&resp[..]) {
+								Ok(proto) => proto,
+								Err(e) => {
+									debug!(
+										target: "sync",
+										"Failed to decode state response from peer {:?}: {:?}.",
+										id,
+										e,
+									);
+									self.peerset_handle.report_peer(id.clone(), rep::BAD_MESSAGE);
+									self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+									continue;
+								}
+							};
+
+						finished_state_requests.push((id.clone(), protobuf_response));
+					},
+					Poll::Ready(Ok(Err(e))) => {
+						peer.state_request.take();
+						debug!(target: "sync", "State request to peer {:?} failed: {:?}.", id, e);
+
+						match e {
+							RequestFailure::Network(OutboundFailure::Timeout) => {
+								self.peerset_handle.report_peer(id.clone(), rep::TIMEOUT);
+								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+							}
+							RequestFailure::Network(OutboundFailure::UnsupportedProtocols) => {
+								self.peerset_handle.report_peer(id.clone(), rep::BAD_PROTOCOL);
+								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+							}
+							RequestFailure::Network(OutboundFailure::DialFailure) => {
+								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+							}
+							RequestFailure::Refused => {
+								self.peerset_handle.report_peer(id.clone(), rep::REFUSED);
+								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+							}
+							RequestFailure::Network(OutboundFailure::ConnectionClosed)
+							| RequestFailure::NotConnected => {
+								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+							},
+							RequestFailure::UnknownProtocol => {
+								debug_assert!(false, "State request protocol should always be known.");
+							}
+							RequestFailure::Obsolete => {
+								debug_assert!(
+									false,
+									"Can not receive `RequestFailure::Obsolete` after dropping the \
+									 response receiver.",
+								);
+							}
+						}
+					},
+					Poll::Ready(Err(oneshot::Canceled)) => {
+						peer.state_request.take();
+						trace!(
+							target: "sync",
+							"State request to peer {:?} failed due to oneshot being canceled.",
+							id,
+						);
+						self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+					},
+					Poll::Pending => {},
+				}
+			}
 		}
 		for (id, req, protobuf_response) in finished_block_requests {
 			let ev = self.on_block_response(id, req, protobuf_response);
 			self.pending_messages.push_back(ev);
 		}
+		for (id, protobuf_response) in finished_state_requests {
+			let ev = self.on_state_response(id, protobuf_response);
+			self.pending_messages.push_back(ev);
+		}
 
 		while let Poll::Ready(Some(())) = self.tick_timeout.poll_next_unpin(cx) {
 			self.tick();
@@ -1314,6 +1506,10 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 			let event = prepare_block_request(&mut self.peers, id, request);
 			self.pending_messages.push_back(event);
 		}
+		if let Some((id, request)) = self.sync.state_request() {
+			let event = prepare_state_request(&mut self.peers, id, request);
+			self.pending_messages.push_back(event);
+		}
 
 		// Check if there is any block announcement validation finished.
 		while let Poll::Ready(result) = self.sync.poll_block_announce_validation(cx) {
@@ -1558,3 +1754,20 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 		self.behaviour.inject_listener_closed(id, reason);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ip_prefix_groups_networks() {
+		let prefix = |addr: &str| ip_prefix(&addr.parse().unwrap());
+
+		assert_eq!(prefix("/ip4/198.51.100.19/tcp/30333"), prefix("/ip4/198.51.100.200/tcp/1"));
+		assert_ne!(prefix("/ip4/198.51.100.19/tcp/30333"), prefix("/ip4/198.51.101.19/tcp/30333"));
+		assert_eq!(prefix("/ip6/2001:db8::1/tcp/30333"), prefix("/ip6/2001:db8::ffff:1/tcp/30333"));
+		assert_ne!(prefix("/ip6/2001:db8::1/tcp/30333"), prefix("/ip6/2001:db8:0:1::1/tcp/30333"));
+		assert_eq!(prefix("/ip6/::ffff:198.51.100.1/tcp/1"), prefix("/ip4/198.51.100.2/tcp/1"));
+		assert_eq!(prefix("/dns/example.com/tcp/30333"), None);
+	}
+}
@@ -39,8 +39,11 @@ use sp_consensus::{BlockOrigin, BlockStatus,
 use crate::protocol::message::{
 	self, BlockAnnounce, BlockAttributes, BlockRequest, BlockResponse, Roles,
 };
+use crate::config::SyncMode;
+use crate::schema::v1::{StateRequest, StateResponse};
 use either::Either;
 use extra_requests::ExtraRequests;
+use state::{StateSync, ImportResult as StateImportResult};
 use libp2p::PeerId;
 use log::{debug, trace, warn, info, error};
 use sp_runtime::{
@@ -59,6 +62,7 @@ use futures::{task::Poll, Future, stream::FuturesUnordered, FutureExt, StreamExt
 
 mod blocks;
 mod extra_requests;
+mod state;
 
 /// Maximum blocks to request in a single packet.
 const MAX_BLOCKS_TO_REQUEST: usize = 128;
@@ -91,6 +95,10 @@ const MAX_CONCURRENT_BLOCK_ANNOUNCE_VALIDATIONS_PER_PEER: usize = 4;
 /// so far behind.
 const MAJOR_SYNC_BLOCKS: u8 = 5;
 
+/// In `Fast` sync mode, state sync starts once a block that is at most this many blocks behind
+/// the median best block of our peers has been finalized.
+const STATE_SYNC_FINALITY_THRESHOLD: u32 = 8;
+
 mod rep {
 	use sc_peerset::ReputationChange as Rep;
 	/// Reputation change when a peer sent us a message that led to a
@@ -209,6 +217,13 @@ pub struct ChainSync<B: BlockT> {
 	>,
 	/// Stats per peer about the number of concurrent block announce validations.
 	block_announce_validation_per_peer_stats: HashMap<PeerId, usize>,
+	/// Current sync mode.
+	mode: SyncMode,
+	/// State sync in progress, if any.
+	state_sync: Option<StateSync<B>>,
+	/// Blocks up to this number were imported without state and are downloaded again to be
+	/// executed.
+	reimport_until: Option<NumberFor<B>>,
 }
 
 /// All the data we have about a Peer that we are trying to sync with
@@ -281,6 +296,8 @@ pub enum PeerSyncState<B: BlockT> {
 	DownloadingStale(B::Hash),
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
+	/// Downloading state.
+	DownloadingState,
 }
 
 impl<B: BlockT> PeerSyncState<B> {
@@ -332,6 +349,15 @@ pub enum OnBlockData<B: BlockT> {
 	Request(PeerId, BlockRequest<B>)
 }
 
+/// Result of [`ChainSync::on_state_data`].
+#[derive(Debug)]
+pub enum OnStateData<B: BlockT> {
+	/// The block and state that should be imported.
+	Import(BlockOrigin, IncomingBlock<B>),
+	/// A new state request needs to be made to the given peer.
+	Request(PeerId, StateRequest)
+}
+
 impl<B: BlockT> OnBlockData<B> {
 	/// Returns `self` as request.
 	#[cfg(test)]
@@ -443,18 +469,30 @@ impl<B: BlockT> ChainSync<B> {
 	/// Create a new instance.
 	pub fn new(
 		role: Roles,
+		mut mode: SyncMode,
 		client: Arc<dyn crate::chain::Client<B>>,
 		info: &BlockchainInfo<B>,
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		max_parallel_downloads: u32,
 	) -> Self {
+		if mode == SyncMode::Fast
+			&& (!role.is_full() || Self::has_finalized_state(&*client, info))
+		{
+			warn!(
+				target: "sync",
+				"Fast sync is only supported by full nodes with an empty database. \
+				 Falling back to full sync.",
+			);
+			mode = SyncMode::Full;
+		}
+
 		let mut required_block_attributes = BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION;
 
-		if role.is_full() {
+		if role.is_full() && mode == SyncMode::Full {
 			required_block_attributes |= BlockAttributes::BODY
 		}
 
-		ChainSync {
+		let mut sync = ChainSync {
 			client,
 			peers: HashMap::new(),
 			blocks: BlockCollection::new(),
@@ -471,7 +509,22 @@ impl<B: BlockT> ChainSync<B> {
 			downloaded_blocks: 0,
 			block_announce_validation: Default::default(),
 			block_announce_validation_per_peer_stats: Default::default(),
-		}
+			mode,
+			state_sync: None,
+			reimport_until: None,
+		};
+		sync.reset_sync_start_point();
+		sync
+	}
+
+	/// Returns `true` if the state of a finalized block other than genesis is available.
+	fn has_finalized_state(
+		client: &dyn crate::chain::Client<B>,
+		info: &BlockchainInfo<B>,
+	) -> bool {
+		!info.finalized_number.is_zero() && client
+			.block_status(&BlockId::Hash(info.finalized_hash))
+			.map_or(false, |status| status == BlockStatus::InChainWithState)
 	}
 
 	/// Returns the state of the sync of the given peer.
@@ -605,9 +658,15 @@ impl<B: BlockT> ChainSync<B> {
 					best_hash,
 					best_number,
 				);
+				// Blocks that are re-imported with their state are not common yet.
+				let common_number = if self.reimport_until.is_some() {
+					std::cmp::min(best_number, self.best_queued_number)
+				} else {
+					best_number
+				};
 				self.peers.insert(who.clone(), PeerSync {
 					peer_id: who.clone(),
-					common_number: best_number,
+					common_number,
 					best_hash,
 					best_number,
 					state: PeerSyncState::Available,
@@ -720,6 +779,10 @@ impl<B: BlockT> ChainSync<B> {
 			trace!(target: "sync", "Too many blocks in the queue.");
 			return Either::Left(std::iter::empty())
 		}
+		if self.state_sync.is_some() {
+			trace!(target: "sync", "Skipped block requests while state sync is in progress.");
+			return Either::Left(std::iter::empty())
+		}
 		let major_sync = self.status().state == SyncState::Downloading;
 		let blocks = &mut self.blocks;
 		let attrs = &self.required_block_attributes;
@@ -799,6 +862,76 @@ impl<B: BlockT> ChainSync<B> {
 		Either::Right(iter)
 	}
 
+	/// Get a state request, if any.
+	pub fn state_request(&mut self) -> Option<(PeerId, StateRequest)> {
+		let state_sync = self.state_sync.as_ref()?;
+		if self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState) {
+			// Only one pending state request is allowed.
+			return None
+		}
+		if state_sync.is_complete() {
+			return None
+		}
+		let target = state_sync.target_block_num();
+		let (id, peer) = self.peers.iter_mut()
+			.find(|(_, peer)| peer.state.is_available() && peer.best_number >= target)?;
+		trace!(target: "sync", "New StateRequest for {}", id);
+		peer.state = PeerSyncState::DownloadingState;
+		Some((id.clone(), state_sync.next_request()))
+	}
+
+	/// Handle a response from the remote to a state request that we made.
+	///
+	/// Returns the block and state to import once the state is complete.
+	pub fn on_state_data(
+		&mut self,
+		who: &PeerId,
+		response: StateResponse,
+	) -> Result<OnStateData<B>, BadPeer> {
+		if let Some(peer) = self.peers.get_mut(who) {
+			if let PeerSyncState::DownloadingState = peer.state {
+				peer.state = PeerSyncState::Available;
+				self.pending_requests.add(who);
+			}
+		}
+
+		let state_sync = match self.state_sync.as_mut() {
+			Some(state_sync) => state_sync,
+			None => {
+				debug!(target: "sync", "Ignored obsolete state response from {}", who);
+				return Err(BadPeer(who.clone(), rep::NOT_REQUESTED));
+			},
+		};
+
+		match state_sync.import(response) {
+			StateImportResult::Import(hash, header, state) => {
+				info!(
+					target: "sync",
+					"✅ Downloaded state of block #{} ({} bytes), queued for import.",
+					header.number(),
+					state_sync.imported_bytes(),
+				);
+				let origin = BlockOrigin::NetworkInitialSync;
+				let block = IncomingBlock {
+					hash,
+					header: Some(header),
+					body: None,
+					justifications: None,
+					origin: None,
+					allow_missing_state: true,
+					import_existing: true,
+					state: Some(state),
+				};
+				Ok(OnStateData::Import(origin, block))
+			},
+			StateImportResult::Continue(request) => Ok(OnStateData::Request(who.clone(), request)),
+			StateImportResult::BadResponse => {
+				debug!(target: "sync", "Bad state data received from {}", who);
+				Err(BadPeer(who.clone(), rep::BAD_BLOCK))
+			},
+		}
+	}
+
 	/// Handle a response from the remote to a block request that we made.
 	///
 	/// `request` must be the original request that triggered `response`.
@@ -844,6 +977,7 @@ impl<B: BlockT> ChainSync<B> {
 										origin: block_data.origin,
 										allow_missing_state: true,
 										import_existing: false,
+										state: None,
 									}
 								}).collect()
 						}
@@ -866,6 +1000,7 @@ impl<B: BlockT> ChainSync<B> {
 									origin: Some(who.clone()),
 									allow_missing_state: true,
 									import_existing: false,
+									state: None,
 								}
 							}).collect()
 						}
@@ -961,7 +1096,8 @@ impl<B: BlockT> ChainSync<B> {
 						}
 
 						| PeerSyncState::Available
-						| PeerSyncState::DownloadingJustification(..) => Vec::new()
+						| PeerSyncState::DownloadingJustification(..)
+						| PeerSyncState::DownloadingState => Vec::new()
 					}
 				} else {
 					// When request.is_none() this is a block announcement. Just accept blocks.
@@ -978,6 +1114,7 @@ impl<B: BlockT> ChainSync<B> {
 							origin: Some(who.clone()),
 							allow_missing_state: true,
 							import_existing: false,
+							state: None,
 						}
 					}).collect()
 				}
@@ -999,6 +1136,16 @@ impl<B: BlockT> ChainSync<B> {
 			debug!(target: "sync", "Ignoring {} blocks that are already queued", orig_len - new_blocks.len());
 		}
 
+		if let Some(reimport_until) = self.reimport_until {
+			for block in new_blocks.iter_mut() {
+				let number = block.header.as_ref().map(|h| *h.number());
+				if number.map_or(false, |n| n <= reimport_until) {
+					block.import_existing = self.client.block_status(&BlockId::Hash(block.hash))
+						.map_or(false, |status| status == BlockStatus::InChainPruned);
+				}
+			}
+		}
+
 		let origin = if self.status().state != SyncState::Downloading {
 			BlockOrigin::NetworkBroadcast
 		} else {
@@ -1103,6 +1250,20 @@ impl<B: BlockT> ChainSync<B> {
 				has_error = true;
 			}
 
+			if self.state_sync.as_ref().map_or(false, |s| s.target() == hash) {
+				if result.is_ok() {
+					info!(target: "sync", "✅ State sync is complete, switching to full sync.");
+					self.mode = SyncMode::Full;
+					self.required_block_attributes |= BlockAttributes::BODY;
+					self.state_sync = None;
+					output.extend(self.restart());
+				} else {
+					// Try again with a later finalized block.
+					warn!(target: "sync", "💔 Error importing downloaded state of {:?}", hash);
+					self.state_sync = None;
+				}
+			}
+
 			match result {
 				Ok(BlockImportResult::ImportedKnown(number, who)) => {
 					if let Some(peer) = who.and_then(|p| self.peers.get_mut(&p)) {
@@ -1216,6 +1377,19 @@ impl<B: BlockT> ChainSync<B> {
 				err,
 			);
 		}
+
+		if self.mode == SyncMode::Fast && self.state_sync.is_none() && !self.peers.is_empty() {
+			// Start state sync once a recent block is finalized.
+			let mut heads: Vec<_> = self.peers.iter().map(|(_, peer)| peer.best_number).collect();
+			heads.sort();
+			let median = heads[heads.len() / 2];
+			if number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median {
+				if let Ok(Some(header)) = self.client.header(BlockId::Hash(*hash)) {
+					info!(target: "sync", "Starting state sync for #{} ({})", number, hash);
+					self.state_sync = Some(StateSync::new(self.client.clone(), header));
+				}
+			}
+		}
 	}
 
 	/// Called when a block has been queued for import.
@@ -1584,6 +1758,7 @@ impl<B: BlockT> ChainSync<B> {
 					origin: block_data.origin,
 					allow_missing_state: true,
 					import_existing: false,
+					state: None,
 				}
 			}).collect();
 		if !blocks.is_empty() {
@@ -1600,20 +1775,18 @@ impl<B: BlockT> ChainSync<B> {
 		&'a mut self,
 	) -> impl Iterator<Item = Result<(PeerId, BlockRequest<B>), BadPeer>> + 'a {
 		self.blocks.clear();
-		let info = self.client.info();
-		self.best_queued_hash = info.best_hash;
-		self.best_queued_number = info.best_number;
+		self.reset_sync_start_point();
 		self.pending_requests.set_all();
 		debug!(target:"sync", "Restarted with {} ({})", self.best_queued_number, self.best_queued_hash);
 		let old_peers = std::mem::take(&mut self.peers);
 
 		old_peers.into_iter().filter_map(move |(id, mut p)| {
-			// peers that were downloading justifications
+			// peers that were downloading justifications or state
 			// should be kept in that state.
 			match p.state {
-				PeerSyncState::DownloadingJustification(_) => {
+				PeerSyncState::DownloadingJustification(_) | PeerSyncState::DownloadingState => {
 					// We make sure our commmon number is at least something we have.
-					p.common_number = info.best_number;
+					p.common_number = self.best_queued_number;
 					self.peers.insert(id, p);
 					return None;
 				}
@@ -1629,6 +1802,52 @@ impl<B: BlockT> ChainSync<B> {
 		})
 	}
 
+	/// Reset the best queued block to our best block.
+	///
+	/// Blocks that were imported without state after a state sync are downloaded again, starting
+	/// from the best block that has state.
+	fn reset_sync_start_point(&mut self) {
+		let info = self.client.info();
+		self.best_queued_hash = info.best_hash;
+		self.best_queued_number = info.best_number;
+		self.reimport_until = None;
+
+		if !self.role.is_full() || self.mode != SyncMode::Full {
+			return
+		}
+
+		let mut hash = info.best_hash;
+		let mut number = info.best_number;
+		while !number.is_zero() {
+			match self.client.block_status(&BlockId::Hash(hash)) {
+				Ok(BlockStatus::InChainPruned) => {},
+				_ => break,
+			}
+			match self.client.header_metadata(hash) {
+				Ok(header) => {
+					hash = header.parent;
+					number = header.number.saturating_sub(One::one());
+				},
+				Err(_) => return,
+			}
+		}
+
+		if hash != info.best_hash
+			&& self.client.block_status(&BlockId::Hash(hash)).ok()
+				== Some(BlockStatus::InChainWithState)
+		{
+			debug!(
+				target: "sync",
+				"State of blocks #{}..=#{} is missing, importing them again.",
+				number + One::one(),
+				info.best_number,
+			);
+			self.best_queued_hash = hash;
+			self.best_queued_number = number;
+			self.reimport_until = Some(info.best_number);
+		}
+	}
+
 	/// What is the status of the block corresponding to the given hash?
 	fn block_status(&self, hash: &B::Hash) -> Result<BlockStatus, ClientError> {
 		if self.queue_blocks.contains(hash) {
@@ -1989,6 +2208,7 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			block_announce_validator,
@@ -2060,6 +2280,7 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
@@ -2235,6 +2456,7 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
@@ -2349,6 +2571,7 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
@@ -2471,6 +2694,7 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
@@ -0,0 +1,210 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! State sync support.
+
+use std::sync::Arc;
+use codec::{Encode, Decode};
+use log::debug;
+use sc_client_api::CompactProof;
+use sp_consensus::ImportedState;
+use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+use crate::chain::Client;
+use crate::schema::v1::{StateRequest, StateResponse};
+
+/// State sync support.
+///
+/// Downloads the state of the target block in key ranges, verifying each range against the
+/// state root of the target header.
+pub struct StateSync<B: BlockT> {
+	target_block: B::Hash,
+	target_header: B::Header,
+	target_root: B::Hash,
+	last_key: Vec<u8>,
+	state: Vec<(Vec<u8>, Vec<u8>)>,
+	complete: bool,
+	client: Arc<dyn Client<B>>,
+	imported_bytes: u64,
+}
+
+/// Import state chunk result.
+pub enum ImportResult<B: BlockT> {
+	/// State is complete and ready for import.
+	Import(B::Hash, B::Header, ImportedState<B>),
+	/// Continue downloading.
+	Continue(StateRequest),
+	/// Bad state chunk.
+	BadResponse,
+}
+
+impl<B: BlockT> StateSync<B> {
+	/// Create a new instance.
+	pub fn new(client: Arc<dyn Client<B>>, target: B::Header) -> Self {
+		StateSync {
+			client,
+			target_block: target.hash(),
+			target_root: *target.state_root(),
+			target_header: target,
+			last_key: Vec::default(),
+			state: Vec::default(),
+			complete: false,
+			imported_bytes: 0,
+		}
+	}
+
+	/// Validate and import a state response.
+	pub fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+		if response.proof.is_empty() {
+			debug!(target: "sync", "Bad state response: empty proof");
+			return ImportResult::BadResponse;
+		}
+		if self.complete {
+			debug!(target: "sync", "Unexpected state response: state is already complete");
+			return ImportResult::BadResponse;
+		}
+		let proof = match CompactProof::decode(&mut response.proof.as_ref()) {
+			Ok(proof) => proof,
+			Err(e) => {
+				debug!(target: "sync", "Error decoding proof: {:?}", e);
+				return ImportResult::BadResponse;
+			}
+		};
+		let (values, complete) = match self.client.verify_range_proof(
+			self.target_root,
+			proof,
+			&self.last_key,
+		) {
+			Ok(result) => result,
+			Err(e) => {
+				debug!(target: "sync", "StateResponse failed proof verification: {:?}", e);
+				return ImportResult::BadResponse;
+			},
+		};
+		if values.is_empty() && !complete {
+			debug!(target: "sync", "Bad state response: no progress");
+			return ImportResult::BadResponse;
+		}
+		debug!(target: "sync", "Imported {} state keys", values.len());
+		if let Some((last, _)) = values.last() {
+			self.last_key = last.clone();
+		}
+		for (key, value) in values {
+			self.imported_bytes += (key.len() + value.len()) as u64;
+			self.state.push((key, value));
+		}
+		self.complete = complete;
+
+		if self.complete {
+			ImportResult::Import(
+				self.target_block,
+				self.target_header.clone(),
+				ImportedState {
+					block: self.target_block,
+					state: std::mem::take(&mut self.state),
+				},
+			)
+		} else {
+			ImportResult::Continue(self.next_request())
+		}
+	}
+
+	/// Produce next state request.
+	pub fn next_request(&self) -> StateRequest {
+		StateRequest {
+			block: self.target_block.encode(),
+			start: self.last_key.clone(),
+		}
+	}
+
+	/// Check if the state is complete.
+	pub fn is_complete(&self) -> bool {
+		self.complete
+	}
+
+	/// Returns target block number.
+	pub fn target_block_num(&self) -> NumberFor<B> {
+		*self.target_header.number()
+	}
+
+	/// Returns target block hash.
+	pub fn target(&self) -> B::Hash {
+		self.target_block
+	}
+
+	/// Returns the number of state bytes downloaded so far.
+	pub fn imported_bytes(&self) -> u64 {
+		self.imported_bytes
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{StateSync, ImportResult};
+	use crate::schema::v1::StateResponse;
+	use codec::Encode;
+	use sc_client_api::ProofProvider;
+	use sp_blockchain::HeaderBackend;
+	use sp_runtime::generic::BlockId;
+	use sp_runtime::traits::Header;
+	use std::sync::Arc;
+	use substrate_test_runtime_client::{
+		DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	#[test]
+	fn downloads_and_verifies_state_in_chunks() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let header = client.header(&BlockId::Number(0)).unwrap().unwrap();
+		let mut sync = StateSync::new(client.clone(), header.clone());
+
+		let mut requests = 0;
+		let state = loop {
+			let request = sync.next_request();
+			let (proof, _) = client.read_proof_collection(
+				&BlockId::Number(0),
+				&request.start,
+				1024,
+			).unwrap();
+			requests += 1;
+			match sync.import(StateResponse { proof: proof.encode() }) {
+				ImportResult::Continue(_) => {},
+				ImportResult::Import(hash, _, state) => {
+					assert_eq!(hash, header.hash());
+					break state
+				},
+				ImportResult::BadResponse => panic!("Valid state response is rejected"),
+			}
+		};
+
+		assert!(sync.is_complete());
+		assert!(requests > 1);
+		assert!(!state.state.is_empty());
+	}
+
+	#[test]
+	fn rejects_empty_proof() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let header = client.header(&BlockId::Number(0)).unwrap().unwrap();
+		let mut sync = StateSync::new(client, header);
+
+		assert!(matches!(
+			sync.import(StateResponse { proof: Vec::new() }),
+			ImportResult::BadResponse,
+		));
+	}
+}
@@ -45,6 +45,7 @@ impl MetricsLink {
 pub struct Metrics {
 	pub block_constructed: Histogram,
 	pub number_of_transactions: Gauge<U64>,
+	pub proposal_time_used: Histogram,
 }
 
 impl Metrics {
@@ -64,6 +65,13 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			proposal_time_used: register(
+				Histogram::with_opts(HistogramOpts::new(
+					"proposer_proposal_time_used",
+					"Proportion of the time available to build a block that was actually used",
+				).buckets(vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0]))?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -0,0 +1,62 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Error helpers for Archive RPC module.
+
+use jsonrpc_core as rpc;
+
+/// Archive RPC Result type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Archive RPC errors.
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum Error {
+	/// Client error.
+	#[display(fmt="Client error: {}", _0)]
+	Client(Box<dyn std::error::Error + Send>),
+	/// The state of the requested block is not available, e.g. because it was pruned.
+	#[display(fmt="The state of the requested block is not available: {}", _0)]
+	StateUnavailable(String),
+	/// The given child storage key is not the key of a default child trie.
+	#[display(fmt="Invalid child storage key")]
+	InvalidChildStorageKey,
+	/// The runtime call failed.
+	#[display(fmt="Runtime call failed: {}", _0)]
+	CallFailed(String),
+}
+
+impl std::error::Error for Error {}
+
+/// Base code for all archive errors.
+const BASE_ERROR: i64 = 7000;
+
+impl From<Error> for rpc::Error {
+	fn from(e: Error) -> Self {
+		let code = match e {
+			Error::Client(_) => BASE_ERROR + 1,
+			Error::StateUnavailable(_) => BASE_ERROR + 2,
+			Error::InvalidChildStorageKey => BASE_ERROR + 3,
+			Error::CallFailed(_) => BASE_ERROR + 4,
+		};
+		rpc::Error {
+			code: rpc::ErrorCode::ServerError(code),
+			message: e.to_string(),
+			data: None,
+		}
+	}
+}
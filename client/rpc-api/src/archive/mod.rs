@@ -0,0 +1,68 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate archive API.
+//!
+//! Queries against any block whose data is still stored by the node, without the pinning
+//! semantics of a live-follow API. Blocks can be pruned at any time, in which case the methods
+//! return an error or nothing, and are meant to be used with archive nodes.
+
+pub mod error;
+
+use self::error::Result;
+use jsonrpc_derive::rpc;
+use sp_core::{
+	Bytes,
+	storage::{PrefixedStorageKey, StorageData, StorageKey},
+};
+
+pub use self::gen_client::Client as ArchiveClient;
+
+/// Substrate archive API.
+///
+/// All methods are unstable and may change without notice.
+#[rpc]
+pub trait ArchiveApi<Hash, Number> {
+	/// Get the hashes of the blocks at the given height.
+	///
+	/// Only the block of the canonical chain is returned. The result is empty if the node
+	/// doesn't know of a block at that height.
+	#[rpc(name = "archive_unstable_hashByHeight")]
+	fn hash_by_height(&self, height: Number) -> Result<Vec<Hash>>;
+
+	/// Get the encoded extrinsics of the given block, or `None` if its body isn't stored.
+	#[rpc(name = "archive_unstable_body")]
+	fn body(&self, hash: Hash) -> Result<Option<Vec<Bytes>>>;
+
+	/// Get the value of `key` in the state of the given block.
+	///
+	/// If `child_key` is given, the key is looked up in that default child trie instead of the
+	/// main trie.
+	#[rpc(name = "archive_unstable_storage")]
+	fn storage(
+		&self,
+		hash: Hash,
+		key: StorageKey,
+		child_key: Option<PrefixedStorageKey>,
+	) -> Result<Option<StorageData>>;
+
+	/// Call the runtime `function` with the SCALE encoded `call_parameters` on top of the state
+	/// of the given block and return its SCALE encoded result.
+	#[rpc(name = "archive_unstable_call")]
+	fn call(&self, hash: Hash, function: String, call_parameters: Bytes) -> Result<Bytes>;
+}
@@ -60,6 +60,9 @@ pub enum Error {
 	/// Invalid session keys encoding.
 	#[display(fmt="Session keys are not encoded correctly")]
 	InvalidSessionKeys,
+	/// The runtime can not prove the ownership of session keys.
+	#[display(fmt="The runtime does not support session keys ownership proofs")]
+	OwnershipProofUnsupported,
 	/// Call to an unsafe RPC was denied.
 	UnsafeRpcCalled(crate::policy::UnsafeRpcError),
 }
@@ -0,0 +1,141 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Transaction events reported by the `transactionWatch` subscription.
+
+use serde::{Serialize, Deserialize};
+use sp_transaction_pool::TransactionStatus;
+
+/// The lifecycle event of a watched transaction.
+///
+/// The `finalized`, `invalid` and `dropped` events are terminal, the subscription is
+/// closed after one of them was reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "event")]
+pub enum TransactionEvent<BlockHash> {
+	/// The transaction was validated and entered the transaction pool.
+	Validated,
+	/// The transaction was broadcast to the given number of peers.
+	#[serde(rename_all = "camelCase")]
+	Broadcasted {
+		/// Number of peers the transaction was broadcast to.
+		num_peers: usize,
+	},
+	/// The transaction was included in a block of the best chain.
+	///
+	/// `None` if the block it was included in was retracted.
+	BestChainBlockIncluded {
+		/// Hash of the block the transaction was included in.
+		block: Option<BlockHash>,
+	},
+	/// The transaction was included in a finalized block.
+	Finalized {
+		/// Hash of the finalized block the transaction was included in.
+		block: BlockHash,
+	},
+	/// The transaction is invalid and will not be included in a block.
+	Invalid {
+		/// The reason the transaction is invalid.
+		error: String,
+	},
+	/// The transaction was dropped from the pool or is no longer watched.
+	Dropped {
+		/// Whether the transaction was broadcast before it was dropped.
+		broadcasted: bool,
+		/// The reason the transaction was dropped.
+		error: String,
+	},
+}
+
+impl<BlockHash> TransactionEvent<BlockHash> {
+	/// Returns `true` if no further events are reported after this one.
+	pub fn is_terminal(&self) -> bool {
+		matches!(
+			self,
+			TransactionEvent::Finalized { .. } |
+				TransactionEvent::Invalid { .. } |
+				TransactionEvent::Dropped { .. }
+		)
+	}
+
+	/// Converts the transaction status reported by the pool into a transaction event.
+	///
+	/// `broadcasted` tells whether the transaction was broadcast before. Returns `None`
+	/// for statuses that do not map to a new event.
+	pub fn from_status<Hash>(
+		status: TransactionStatus<Hash, BlockHash>,
+		validated: bool,
+		broadcasted: bool,
+	) -> Option<Self> {
+		Some(match status {
+			TransactionStatus::Future | TransactionStatus::Ready if validated => return None,
+			TransactionStatus::Future | TransactionStatus::Ready => TransactionEvent::Validated,
+			TransactionStatus::Broadcast(peers) =>
+				TransactionEvent::Broadcasted { num_peers: peers.len() },
+			TransactionStatus::InBlock(block) =>
+				TransactionEvent::BestChainBlockIncluded { block: Some(block) },
+			TransactionStatus::Retracted(_) =>
+				TransactionEvent::BestChainBlockIncluded { block: None },
+			TransactionStatus::Finalized(block) => TransactionEvent::Finalized { block },
+			TransactionStatus::FinalityTimeout(_) => TransactionEvent::Dropped {
+				broadcasted,
+				error: "Maximum number of finality watchers has been reached".into(),
+			},
+			TransactionStatus::Usurped(_) => TransactionEvent::Invalid {
+				error: "Transaction was replaced by another transaction".into(),
+			},
+			TransactionStatus::Dropped => TransactionEvent::Dropped {
+				broadcasted,
+				error: "Transaction dropped from the pool because of the limit".into(),
+			},
+			TransactionStatus::Invalid => TransactionEvent::Invalid {
+				error: "Transaction is no longer valid in the current state".into(),
+			},
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_events() {
+		let event = TransactionEvent::<u64>::Validated;
+		assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"event":"validated"}"#);
+
+		let event = TransactionEvent::<u64>::Broadcasted { num_peers: 2 };
+		assert_eq!(
+			serde_json::to_string(&event).unwrap(),
+			r#"{"event":"broadcasted","numPeers":2}"#,
+		);
+
+		let event = TransactionEvent::<u64>::BestChainBlockIncluded { block: None };
+		assert_eq!(
+			serde_json::to_string(&event).unwrap(),
+			r#"{"event":"bestChainBlockIncluded","block":null}"#,
+		);
+
+		let event = TransactionEvent::<u64>::Dropped { broadcasted: true, error: "x".into() };
+		assert_eq!(
+			serde_json::to_string(&event).unwrap(),
+			r#"{"event":"dropped","broadcasted":true,"error":"x"}"#,
+		);
+	}
+}
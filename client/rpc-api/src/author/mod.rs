@@ -19,16 +19,29 @@
 //! Substrate block-author/full-node API.
 
 pub mod error;
+pub mod event;
 pub mod hash;
 
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+use serde::{Serialize, Deserialize};
 use sp_core::Bytes;
 use sp_transaction_pool::TransactionStatus;
 use self::error::{FutureResult, Result};
+use self::event::TransactionEvent;
 
 pub use self::gen_client::Client as AuthorClient;
 
+/// Session keys generated by `author_rotateKeysWithOwner`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedSessionKeys {
+	/// The SCALE encoded public keys.
+	pub keys: Bytes,
+	/// The proof that the owner holds the keys.
+	pub proof: Bytes,
+}
+
 /// Substrate authoring RPC API
 #[rpc]
 pub trait AuthorApi<Hash, BlockHash> {
@@ -49,9 +62,20 @@ pub trait AuthorApi<Hash, BlockHash> {
 	) -> Result<()>;
 
 	/// Generate new session keys and returns the corresponding public keys.
+	///
+	/// Deprecated: the returned keys come without an ownership proof, which runtimes that check
+	/// ownership require to register them. Use `author_rotateKeysWithOwner` instead.
 	#[rpc(name = "author_rotateKeys")]
 	fn rotate_keys(&self) -> Result<Bytes>;
 
+	/// Generate new session keys and returns the corresponding public keys, together with a proof
+	/// that `owner` holds them.
+	///
+	/// `owner` is the SCALE encoded account that is going to register the keys, which needs to
+	/// pass the proof alongside the keys.
+	#[rpc(name = "author_rotateKeysWithOwner")]
+	fn rotate_keys_with_owner(&self, owner: Bytes) -> Result<GeneratedSessionKeys>;
+
 	/// Checks if the keystore has private keys for the given session public keys.
 	///
 	/// `session_keys` is the SCALE encoded session keys object from the runtime.
@@ -101,4 +125,31 @@ pub trait AuthorApi<Hash, BlockHash> {
 		metadata: Option<Self::Metadata>,
 		id: SubscriptionId
 	) -> Result<bool>;
+
+	/// Submit a transaction and watch its lifecycle.
+	///
+	/// Unlike `author_submitAndWatchExtrinsic`, a submission error is reported as an
+	/// `invalid` event and every subscription ends with exactly one terminal event.
+	/// See [`TransactionEvent`](self::event::TransactionEvent) for details.
+	#[pubsub(
+		subscription = "transactionWatch_watchEvent",
+		subscribe,
+		name = "transactionWatch_submitAndWatch"
+	)]
+	fn watch_transaction(&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<TransactionEvent<BlockHash>>,
+		bytes: Bytes
+	);
+
+	/// Stop watching a transaction.
+	#[pubsub(
+		subscription = "transactionWatch_watchEvent",
+		unsubscribe,
+		name = "transactionWatch_unwatch"
+	)]
+	fn unwatch_transaction(&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId
+	) -> Result<bool>;
 }
@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Substrate chain spec API.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sp_chain_spec::Properties;
+
+pub use self::gen_client::Client as ChainSpecClient;
+
+/// Substrate chain spec RPC API
+#[rpc]
+pub trait ChainSpecApi<Hash> {
+	/// Get the chain name, as present in the chain specification.
+	#[rpc(name = "chainSpec_chainName")]
+	fn chain_name(&self) -> Result<String>;
+
+	/// Get the hash of the genesis block.
+	#[rpc(name = "chainSpec_genesisHash")]
+	fn genesis_hash(&self) -> Result<Hash>;
+
+	/// Get a custom set of properties as a JSON object, defined in the chain specification.
+	#[rpc(name = "chainSpec_properties")]
+	fn properties(&self) -> Result<Properties>;
+}
@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Error helpers for Dev RPC module.
+
+use jsonrpc_core as rpc;
+
+/// Dev RPC Result type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Dev RPC errors.
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum Error {
+	/// Failed to query specified block or its parent: Probably an invalid hash.
+	#[display(fmt="Error while querying block: {}", _0)]
+	BlockQueryError(Box<dyn std::error::Error + Send>),
+	/// The re-execution of the specified block failed.
+	#[display(fmt="Failed to re-execute the specified block: {}", _0)]
+	BlockExecutionFailed(String),
+	/// The witness could not be extracted after re-executing the block.
+	#[display(fmt="Failed to extract the proof recorded during re-execution.")]
+	WitnessExtractionFailed,
+	/// Call to an unsafe RPC was denied.
+	UnsafeRpcCalled(crate::policy::UnsafeRpcError),
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::UnsafeRpcCalled(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+/// Base code for all dev errors.
+const BASE_ERROR: i64 = 6000;
+
+impl From<Error> for rpc::Error {
+	fn from(e: Error) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			Error::BlockQueryError(_) => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 1),
+				message: msg,
+				data: None,
+			},
+			Error::BlockExecutionFailed(_) => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 2),
+				message: msg,
+				data: None,
+			},
+			Error::WitnessExtractionFailed => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 3),
+				message: msg,
+				data: None,
+			},
+			Error::UnsafeRpcCalled(e) => e.into(),
+		}
+	}
+}
@@ -0,0 +1,74 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Substrate dev API containing RPCs that are mainly meant for debugging and stats collection
+//! for developers. The endpoints in this RPC module are not meant to be available to non-local
+//! users and are all marked `unsafe`.
+
+pub mod error;
+
+use self::error::Result;
+use codec::{Decode, Encode};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+pub use self::gen_client::Client as DevClient;
+
+/// Statistics of a block returned by the `dev_getBlockStats` RPC.
+#[derive(Eq, PartialEq, Clone, Encode, Decode, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStats {
+	/// The length in bytes of the storage proof produced by executing the block.
+	pub witness_len: u64,
+	/// The length in bytes of the block.
+	pub block_len: u64,
+	/// Number of extrinsics in the block.
+	///
+	/// This is included here to make it easier to interpret the other stats.
+	pub num_extrinsics: u64,
+	/// Outcome of every extrinsic of the block, in order.
+	pub extrinsics: Vec<ExtrinsicStats>,
+}
+
+/// Outcome of a single extrinsic when re-executing a block.
+#[derive(Eq, PartialEq, Clone, Encode, Decode, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicStats {
+	/// The length in bytes of the encoded extrinsic.
+	pub len: u64,
+	/// Whether the extrinsic was applied and dispatched successfully.
+	pub success: bool,
+	/// The error returned by the runtime if the extrinsic failed.
+	pub error: Option<String>,
+}
+
+/// Substrate dev API.
+///
+/// This API contains unstable and unsafe methods only meant for development nodes. They
+/// are all flagged as unsafe for this reason.
+#[rpc]
+pub trait DevApi<Hash> {
+	/// Reexecute the specified `block_hash` and gather statistics while doing so.
+	///
+	/// This function requires the specified block and its parent to be available
+	/// at the queried node. If either the specified block or the parent is pruned,
+	/// this function will return `None`.
+	#[rpc(name = "dev_getBlockStats")]
+	fn block_stats(&self, block_hash: Hash) -> Result<Option<BlockStats>>;
+}
@@ -32,8 +32,11 @@ pub use jsonrpc_core::IoHandlerExtension as RpcExtension;
 pub use metadata::Metadata;
 pub use policy::DenyUnsafe;
 
+pub mod archive;
 pub mod author;
 pub mod chain;
+pub mod chain_spec;
+pub mod dev;
 pub mod offchain;
 pub mod state;
 pub mod child_state;
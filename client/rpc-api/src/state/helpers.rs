@@ -18,7 +18,7 @@
 
 //! Substrate state API helpers.
 
-use sp_core::Bytes;
+use sp_core::{Bytes, storage::{StorageKey, PrefixedStorageKey}};
 use serde::{Serialize, Deserialize};
 
 /// ReadProof struct returned by the RPC
@@ -30,3 +30,31 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Additional filter of a storage subscription.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StorageFilter {
+	/// Prefixes of the keys to subscribe to, in addition to the exact keys.
+	#[serde(default)]
+	pub prefixes: Vec<StorageKey>,
+	/// Default child tries to subscribe to.
+	#[serde(default)]
+	pub child_tries: Vec<ChildTrieFilter>,
+}
+
+/// Filter of the changes of a default child trie.
+///
+/// All changes of the child trie are reported if neither keys nor prefixes are given.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChildTrieFilter {
+	/// Prefixed storage key of the child trie.
+	pub child_storage_key: PrefixedStorageKey,
+	/// Keys of the child trie to subscribe to.
+	#[serde(default)]
+	pub keys: Option<Vec<StorageKey>>,
+	/// Prefixes of the keys of the child trie to subscribe to.
+	#[serde(default)]
+	pub prefixes: Vec<StorageKey>,
+}
@@ -30,7 +30,7 @@ use sp_version::RuntimeVersion;
 use self::error::FutureResult;
 
 pub use self::gen_client::Client as StateClient;
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, StorageFilter, ChildTrieFilter};
 
 /// Substrate state API
 #[rpc]
@@ -107,6 +107,23 @@ pub trait StateApi<Hash> {
 	#[rpc(name = "state_getReadProof")]
 	fn read_proof(&self, keys: Vec<StorageKey>, hash: Option<Hash>) -> FutureResult<ReadProof<Hash>>;
 
+	/// Re-execute the given block and return the spans and events recorded on the way.
+	///
+	/// `targets` is a comma separated list of tracing targets to record, optionally with a
+	/// level (e.g. `pallet,frame=debug,state`). `storage_keys` is a comma separated list of
+	/// hex encoded storage key prefixes; only storage accesses matching one of them are
+	/// recorded. Runtime spans are only available if the runtime was built with the
+	/// `with-tracing` feature.
+	///
+	/// This method is considered unsafe, as re-executing blocks is expensive.
+	#[rpc(name = "state_traceBlock")]
+	fn trace_block(
+		&self,
+		block: Hash,
+		targets: Option<String>,
+		storage_keys: Option<String>,
+	) -> FutureResult<sp_rpc::tracing::TraceBlockResponse>;
+
 	/// New runtime version subscription
 	#[pubsub(
 		subscription = "state_runtimeVersion",
@@ -126,9 +143,17 @@ pub trait StateApi<Hash> {
 	fn unsubscribe_runtime_version(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool>;
 
 	/// New storage subscription
+	///
+	/// Without `keys` and prefixes in `filter`, all changes of the top trie are reported. The
+	/// changes of child tries are only reported for the child tries listed in `filter`. Initial
+	/// values are only sent for the exact `keys`.
 	#[pubsub(subscription = "state_storage", subscribe, name = "state_subscribeStorage")]
 	fn subscribe_storage(
-		&self, metadata: Self::Metadata, subscriber: Subscriber<StorageChangeSet<Hash>>, keys: Option<Vec<StorageKey>>
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<StorageChangeSet<Hash>>,
+		keys: Option<Vec<StorageKey>>,
+		filter: Option<StorageFilter>,
 	);
 
 	/// Unsubscribe from storage subscription
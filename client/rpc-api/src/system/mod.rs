@@ -124,4 +124,11 @@ pub trait SystemApi<Hash, Number> {
 	#[rpc(name = "system_resetLogFilter", returns = "()")]
 	fn system_reset_log_filter(&self)
 		-> Result<(), jsonrpc_core::Error>;
+
+	/// Requests the node to shut down gracefully.
+	///
+	/// The node stops accepting RPC requests shortly after, the ones being processed are given
+	/// some time to complete.
+	#[rpc(name = "system_shutdown", returns = "()")]
+	fn system_shutdown(&self) -> Result<(), jsonrpc_core::Error>;
 }
@@ -37,7 +37,7 @@ const WS_MAX_CONNECTIONS: usize = 100;
 pub type RpcHandler<T> = pubsub::PubSubHandler<T, RpcMiddleware>;
 
 pub use self::inner::*;
-pub use middleware::{RpcMiddleware, RpcMetrics};
+pub use middleware::{RequestTracker, RpcMiddleware, RpcMetrics, RpcPolicy, ServerMetrics};
 
 /// Construct rpc `IoHandler`
 pub fn rpc_handler<M: PubSubMetadata>(
@@ -121,9 +121,11 @@ mod inner {
 		max_connections: Option<usize>,
 		cors: Option<&Vec<String>>,
 		io: RpcHandler<M>,
+		server_metrics: ServerMetrics,
 	) -> io::Result<ws::Server> {
 		ws::ServerBuilder::with_meta_extractor(io, |context: &ws::RequestContext| context.sender().into())
 			.max_payload(MAX_PAYLOAD)
+			.session_stats(server_metrics)
 			.max_connections(max_connections.unwrap_or(WS_MAX_CONNECTIONS))
 			.allowed_origins(map_cors(cors))
 			.allowed_hosts(hosts_filtering(cors.is_some()))
@@ -138,6 +140,20 @@ mod inner {
 			})
 	}
 
+	impl ws::SessionStats for ServerMetrics {
+		fn open_session(&self, _id: ws::SessionId) {
+			if let Some(ref sessions_opened) = self.sessions_opened {
+				sessions_opened.inc();
+			}
+		}
+
+		fn close_session(&self, _id: ws::SessionId) {
+			if let Some(ref sessions_closed) = self.sessions_closed {
+				sessions_closed.inc();
+			}
+		}
+	}
+
 	fn map_cors<T: for<'a> From<&'a str>>(
 		cors: Option<&Vec<String>>
 	) -> http::DomainsValidation<T> {
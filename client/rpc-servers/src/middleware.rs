@@ -18,21 +18,232 @@
 
 //! Middleware for RPC requests.
 
+use std::{
+	collections::HashMap,
+	sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}},
+	time::{Duration, Instant},
+};
+
 use jsonrpc_core::{
-	Middleware as RequestMiddleware, Metadata,
+	Middleware as RequestMiddleware, Call, Error, ErrorCode, Failure, Output,
 	Request, Response, FutureResponse, FutureOutput
 };
+use parking_lot::Mutex;
 use prometheus_endpoint::{
-	Registry, CounterVec, PrometheusError,
-	Opts, register, U64
+	Registry, Counter, CounterVec, Gauge, GaugeVec, HistogramOpts, HistogramVec, PrometheusError,
+	Opts, exponential_buckets, register, U64
 };
+use pubsub::{PubSubMetadata, Session};
+
+use futures::{future::{self, Either}, Future};
+
+/// Error code returned when the rate limit of a connection is exceeded.
+const RATE_LIMIT_EXCEEDED: i64 = -32029;
+/// Error code returned when a batch request contains too many calls.
+const BATCH_TOO_LARGE: i64 = -32030;
+/// Error code returned when a response exceeds the maximum response size.
+const RESPONSE_TOO_LARGE: i64 = -32031;
+/// Error code returned when a request is received while the node is shutting down.
+const SHUTTING_DOWN: i64 = -32032;
+
+/// Access control and limits enforced on incoming RPC requests.
+#[derive(Debug, Clone, Default)]
+pub struct RpcPolicy {
+	/// If `Some`, only the listed methods can be called.
+	pub allowed_methods: Option<Vec<String>>,
+	/// Methods that can never be called. Takes precedence over `allowed_methods`.
+	pub denied_methods: Vec<String>,
+	/// Maximum number of calls per second and connection. `None` if unlimited.
+	///
+	/// Transports without a notion of connection (HTTP) share a single limit.
+	pub max_calls_per_second: Option<u32>,
+	/// Maximum number of calls in a batch request. `None` if unlimited.
+	pub max_batch_len: Option<usize>,
+	/// Maximum size in bytes of a serialized response. `None` if unlimited.
+	pub max_response_size: Option<usize>,
+}
+
+impl RpcPolicy {
+	/// Returns `true` if `method` may be called according to this policy.
+	pub fn is_method_allowed(&self, method: &str) -> bool {
+		if self.denied_methods.iter().any(|m| m == method) {
+			return false
+		}
+		self.allowed_methods.as_ref().map_or(true, |allowed| allowed.iter().any(|m| m == method))
+	}
+}
+
+/// Fixed window rate limiter, keyed by connection.
+#[derive(Debug)]
+struct RateLimiter {
+	max_calls: u32,
+	windows: Mutex<HashMap<usize, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+	fn new(max_calls: u32) -> Self {
+		RateLimiter {
+			max_calls,
+			windows: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Records `calls` new calls for the connection of `meta`.
+	///
+	/// Returns `false` if the connection exceeded its limit.
+	fn check<M: PubSubMetadata>(self: &Arc<Self>, meta: &M, calls: usize) -> bool {
+		let session = meta.session();
+		let key = session.as_ref().map_or(0, |session| Arc::as_ptr(session) as usize);
+		let now = Instant::now();
+		let mut windows = self.windows.lock();
+		let (start, count) = windows.entry(key).or_insert_with(|| {
+			if let Some(session) = &session {
+				let limiter = Arc::downgrade(self);
+				session.on_drop(move || {
+					if let Some(limiter) = limiter.upgrade() {
+						limiter.windows.lock().remove(&key);
+					}
+				});
+			}
+			(now, 0)
+		});
+		if now.duration_since(*start) >= Duration::from_secs(1) {
+			*start = now;
+			*count = 0;
+		}
+		*count = count.saturating_add(calls as u32);
+		*count <= self.max_calls
+	}
+}
 
-use futures::{future::Either, Future};
+/// Keeps track of the requests being processed, so that they can be drained on shutdown.
+///
+/// Clones share the same state, a single tracker is usually given to the middleware of every
+/// server.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTracker {
+	inner: Arc<RequestTrackerInner>,
+}
+
+#[derive(Debug, Default)]
+struct RequestTrackerInner {
+	in_flight: AtomicUsize,
+	closed: AtomicBool,
+}
+
+impl RequestTracker {
+	/// Create a new tracker, accepting requests.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Reject all the requests received from now on. The requests being processed are not
+	/// affected.
+	pub fn close(&self) {
+		self.inner.closed.store(true, Ordering::SeqCst);
+	}
+
+	/// Returns `true` if `close()` was called.
+	pub fn is_closed(&self) -> bool {
+		self.inner.closed.load(Ordering::SeqCst)
+	}
+
+	/// Number of requests being processed.
+	pub fn in_flight(&self) -> usize {
+		self.inner.in_flight.load(Ordering::SeqCst)
+	}
+
+	/// Counts a request as in flight until the returned guard is dropped.
+	///
+	/// Returns `None` if the tracker is closed.
+	fn start(&self) -> Option<InFlightGuard> {
+		// Counted before checking `closed`, so that once `close()` returned, no request can be
+		// processed without being seen by `in_flight()`.
+		self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+		let guard = InFlightGuard(self.inner.clone());
+		if self.is_closed() {
+			None
+		} else {
+			Some(guard)
+		}
+	}
+}
+
+/// Counts a request as in flight for as long as it is alive.
+struct InFlightGuard(Arc<RequestTrackerInner>);
+
+impl Drop for InFlightGuard {
+	fn drop(&mut self) {
+		self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+/// Label used instead of the method name for calls to methods that do not exist,
+/// so that clients cannot create an unbounded number of time series.
+const UNKNOWN_METHOD: &str = "<unknown>";
 
 /// Metrics for RPC middleware
 #[derive(Debug, Clone)]
 pub struct RpcMetrics {
 	rpc_calls: Option<CounterVec<U64>>,
+	calls: Option<CallMetrics>,
+}
+
+/// Per method metrics of the RPC calls.
+#[derive(Debug, Clone)]
+struct CallMetrics {
+	time: HistogramVec,
+	finished: CounterVec<U64>,
+	in_flight: GaugeVec<U64>,
+	subscriptions: GaugeVec<U64>,
+}
+
+impl CallMetrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			time: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"rpc_calls_time",
+						"Total time [s] to process an rpc call",
+					).buckets(exponential_buckets(0.001, 4.0, 9)
+						.expect("function parameters are constant and always valid; qed")),
+					&["protocol", "method"]
+				)?,
+				registry,
+			)?,
+			finished: register(
+				CounterVec::new(
+					Opts::new(
+						"rpc_calls_finished",
+						"Number of processed rpc calls",
+					),
+					&["protocol", "method", "is_error"]
+				)?,
+				registry,
+			)?,
+			in_flight: register(
+				GaugeVec::new(
+					Opts::new(
+						"rpc_calls_in_flight",
+						"Number of rpc calls currently being processed",
+					),
+					&["protocol"]
+				)?,
+				registry,
+			)?,
+			subscriptions: register(
+				GaugeVec::new(
+					Opts::new(
+						"rpc_subscriptions",
+						"Number of active rpc subscriptions",
+					),
+					&["protocol"]
+				)?,
+				registry,
+			)?,
+		})
+	}
 }
 
 impl RpcMetrics {
@@ -51,14 +262,119 @@ impl RpcMetrics {
 					r,
 				)
 			).transpose()?,
+			calls: metrics_registry.map(CallMetrics::register).transpose()?,
 		})
 	}
 }
 
+/// Metrics of the sessions opened on the RPC servers.
+#[derive(Debug, Clone, Default)]
+pub struct ServerMetrics {
+	pub(crate) sessions_opened: Option<Counter<U64>>,
+	pub(crate) sessions_closed: Option<Counter<U64>>,
+}
+
+impl ServerMetrics {
+	/// Create an instance of metrics
+	pub fn new(metrics_registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			sessions_opened: metrics_registry.map(|r|
+				register(
+					Counter::new(
+						"rpc_sessions_opened",
+						"Number of persistent RPC sessions opened",
+					)?,
+					r,
+				)
+			).transpose()?,
+			sessions_closed: metrics_registry.map(|r|
+				register(
+					Counter::new(
+						"rpc_sessions_closed",
+						"Number of persistent RPC sessions closed",
+					)?,
+					r,
+				)
+			).transpose()?,
+		})
+	}
+}
+
+/// Whether a call opens or closes a subscription.
+///
+/// Based on the naming conventions of the Substrate RPC methods.
+#[derive(Debug, PartialEq)]
+enum SubscriptionCall {
+	Subscribe,
+	Unsubscribe,
+}
+
+impl SubscriptionCall {
+	fn from_method(method: &str) -> Option<Self> {
+		let method = method.to_lowercase();
+		if method.contains("unsubscribe") || method.contains("unwatch") {
+			Some(SubscriptionCall::Unsubscribe)
+		} else if method.contains("subscribe") || method.contains("andwatch") {
+			Some(SubscriptionCall::Subscribe)
+		} else {
+			None
+		}
+	}
+}
+
+/// Keeps track of the active subscriptions of every session, so that the
+/// subscriptions of a closed session can be removed from the gauge.
+#[derive(Debug)]
+struct SubscriptionTracker {
+	gauge: Gauge<U64>,
+	sessions: Mutex<HashMap<usize, u64>>,
+}
+
+impl SubscriptionTracker {
+	fn new(gauge: Gauge<U64>) -> Self {
+		SubscriptionTracker {
+			gauge,
+			sessions: Mutex::new(HashMap::new()),
+		}
+	}
+
+	fn subscribed(self: &Arc<Self>, session: &Arc<Session>) {
+		let key = Arc::as_ptr(session) as usize;
+		let mut sessions = self.sessions.lock();
+		let count = sessions.entry(key).or_insert_with(|| {
+			let tracker = Arc::downgrade(self);
+			session.on_drop(move || {
+				if let Some(tracker) = tracker.upgrade() {
+					if let Some(count) = tracker.sessions.lock().remove(&key) {
+						tracker.gauge.sub(count);
+					}
+				}
+			});
+			0
+		});
+		*count += 1;
+		self.gauge.inc();
+	}
+
+	fn unsubscribed(&self, session: &Arc<Session>) {
+		let key = Arc::as_ptr(session) as usize;
+		if let Some(count) = self.sessions.lock().get_mut(&key) {
+			if *count > 0 {
+				*count -= 1;
+				self.gauge.dec();
+			}
+		}
+	}
+}
+
 /// Middleware for RPC calls
 pub struct RpcMiddleware {
 	metrics: RpcMetrics,
 	transport_label: String,
+	policy: RpcPolicy,
+	rate_limiter: Option<Arc<RateLimiter>>,
+	subscriptions: Option<Arc<SubscriptionTracker>>,
+	request_tracker: Option<RequestTracker>,
 }
 
 impl RpcMiddleware {
@@ -67,14 +383,34 @@ impl RpcMiddleware {
 	/// - `metrics`: Will be used to report statistics.
 	/// - `transport_label`: The label that is used when reporting the statistics.
 	pub fn new(metrics: RpcMetrics, transport_label: &str) -> Self {
+		let subscriptions = metrics.calls.as_ref().map(|calls| Arc::new(SubscriptionTracker::new(
+			calls.subscriptions.with_label_values(&[transport_label])
+		)));
 		RpcMiddleware {
 			metrics,
 			transport_label: String::from(transport_label),
+			policy: Default::default(),
+			rate_limiter: None,
+			subscriptions,
+			request_tracker: None,
 		}
 	}
+
+	/// Enforce the given access control and limits on all requests.
+	pub fn with_policy(mut self, policy: RpcPolicy) -> Self {
+		self.rate_limiter = policy.max_calls_per_second.map(|max| Arc::new(RateLimiter::new(max)));
+		self.policy = policy;
+		self
+	}
+
+	/// Count the requests in `tracker`, and reject them once it is closed.
+	pub fn with_request_tracker(mut self, tracker: RequestTracker) -> Self {
+		self.request_tracker = Some(tracker);
+		self
+	}
 }
 
-impl<M: Metadata> RequestMiddleware<M> for RpcMiddleware {
+impl<M: PubSubMetadata> RequestMiddleware<M> for RpcMiddleware {
 	type Future = FutureResponse;
 	type CallFuture = FutureOutput;
 
@@ -87,6 +423,244 @@ impl<M: Metadata> RequestMiddleware<M> for RpcMiddleware {
 			rpc_calls.with_label_values(&[self.transport_label.as_str()]).inc();
 		}
 
-		Either::B(next(request, meta))
+		let in_flight = match self.request_tracker.as_ref().map(RequestTracker::start) {
+			Some(None) => return Either::A(error_response(Error {
+				code: ErrorCode::ServerError(SHUTTING_DOWN),
+				message: "Node is shutting down".into(),
+				data: None,
+			})),
+			Some(guard) => guard,
+			None => None,
+		};
+
+		let calls = match request {
+			Request::Single(_) => 1,
+			Request::Batch(ref calls) => calls.len(),
+		};
+		if let Some(max) = self.policy.max_batch_len {
+			if calls > max {
+				return Either::A(error_response(Error {
+					code: ErrorCode::ServerError(BATCH_TOO_LARGE),
+					message: format!("Batch contains {} calls, the maximum is {}", calls, max),
+					data: None,
+				}))
+			}
+		}
+		if let Some(ref rate_limiter) = self.rate_limiter {
+			if !rate_limiter.check(&meta, calls) {
+				return Either::A(error_response(Error {
+					code: ErrorCode::ServerError(RATE_LIMIT_EXCEEDED),
+					message: "Rate limit exceeded".into(),
+					data: None,
+				}))
+			}
+		}
+
+		let max_response_size = self.policy.max_response_size;
+		if max_response_size.is_none() && in_flight.is_none() {
+			return Either::B(next(request, meta))
+		}
+		Either::A(Box::new(next(request, meta).map(move |response| {
+			drop(in_flight);
+			match max_response_size {
+				Some(max) => response.map(|r| limit_response_size(r, max)),
+				None => response,
+			}
+		})))
+	}
+
+	fn on_call<F, X>(&self, call: Call, meta: M, next: F) -> Either<FutureOutput, X>
+	where
+		F: Fn(Call, M) -> X + Send + Sync,
+		X: Future<Item = Option<Output>, Error = ()> + Send + 'static,
+	{
+		let allowed = match call {
+			Call::MethodCall(ref call) => self.policy.is_method_allowed(&call.method),
+			Call::Notification(ref call) => self.policy.is_method_allowed(&call.method),
+			Call::Invalid { .. } => true,
+		};
+		if allowed {
+			return self.call_with_metrics(call, meta, next)
+		}
+
+		match call {
+			Call::MethodCall(method_call) => {
+				let failure = Output::Failure(Failure {
+					jsonrpc: method_call.jsonrpc,
+					error: Error::method_not_found(),
+					id: method_call.id,
+				});
+				Either::A(Box::new(future::ok(Some(failure))))
+			},
+			_ => Either::A(Box::new(future::ok(None))),
+		}
+	}
+}
+
+impl RpcMiddleware {
+	/// Process `call`, recording its latency and outcome.
+	fn call_with_metrics<M, F, X>(&self, call: Call, meta: M, next: F) -> Either<FutureOutput, X>
+	where
+		M: PubSubMetadata,
+		F: Fn(Call, M) -> X + Send + Sync,
+		X: Future<Item = Option<Output>, Error = ()> + Send + 'static,
+	{
+		let metrics = match self.metrics.calls {
+			Some(ref metrics) => metrics.clone(),
+			None => return Either::B(next(call, meta)),
+		};
+		let method = match call {
+			Call::MethodCall(ref call) => call.method.clone(),
+			Call::Notification(ref call) => call.method.clone(),
+			Call::Invalid { .. } => return Either::B(next(call, meta)),
+		};
+		let protocol = self.transport_label.clone();
+		let session = meta.session();
+		let subscriptions = self.subscriptions.clone();
+
+		metrics.in_flight.with_label_values(&[&protocol]).inc();
+		let start = Instant::now();
+		Either::A(Box::new(next(call, meta).map(move |output| {
+			metrics.in_flight.with_label_values(&[&protocol]).dec();
+			let (method, is_error) = match output {
+				Some(Output::Failure(ref f)) if f.error.code == ErrorCode::MethodNotFound =>
+					(UNKNOWN_METHOD, true),
+				Some(Output::Failure(_)) => (method.as_str(), true),
+				_ => (method.as_str(), false),
+			};
+			metrics.time
+				.with_label_values(&[&protocol, method])
+				.observe(start.elapsed().as_secs_f64());
+			metrics.finished
+				.with_label_values(&[&protocol, method, if is_error { "true" } else { "false" }])
+				.inc();
+
+			if let (Some(Output::Success(success)), Some(session), Some(subscriptions)) =
+				(&output, &session, &subscriptions)
+			{
+				let unsubscribed = success.result == serde_json::Value::Bool(true);
+				match SubscriptionCall::from_method(method) {
+					Some(SubscriptionCall::Subscribe) => subscriptions.subscribed(session),
+					Some(SubscriptionCall::Unsubscribe) if unsubscribed =>
+						subscriptions.unsubscribed(session),
+					_ => {},
+				}
+			}
+			output
+		})))
+	}
+}
+
+fn error_response(error: Error) -> FutureResponse {
+	Box::new(future::ok(Some(Response::from(error, Some(jsonrpc_core::Version::V2)))))
+}
+
+/// Replace all outputs of `response` by an error if its serialized size exceeds `max`.
+fn limit_response_size(response: Response, max: usize) -> Response {
+	let size = serde_json::to_string(&response).map(|r| r.len()).unwrap_or(0);
+	if size <= max {
+		return response
+	}
+
+	let too_large = |output: Output| Output::Failure(Failure {
+		jsonrpc: output.version(),
+		error: Error {
+			code: ErrorCode::ServerError(RESPONSE_TOO_LARGE),
+			message: format!("Response of {} bytes exceeds the maximum of {} bytes", size, max),
+			data: None,
+		},
+		id: output.id().clone(),
+	});
+	match response {
+		Response::Single(output) => Response::Single(too_large(output)),
+		Response::Batch(outputs) => Response::Batch(outputs.into_iter().map(too_large).collect()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn denied_methods_take_precedence() {
+		let policy = RpcPolicy {
+			allowed_methods: Some(vec!["system_name".into(), "author_rotateKeys".into()]),
+			denied_methods: vec!["author_rotateKeys".into()],
+			..Default::default()
+		};
+		assert!(policy.is_method_allowed("system_name"));
+		assert!(!policy.is_method_allowed("author_rotateKeys"));
+		assert!(!policy.is_method_allowed("state_getStorage"));
+		assert!(RpcPolicy::default().is_method_allowed("state_getStorage"));
+	}
+
+	#[test]
+	fn rate_limiter_shares_limit_without_session() {
+		#[derive(Clone, Default)]
+		struct NoSession;
+		impl jsonrpc_core::Metadata for NoSession {}
+		impl PubSubMetadata for NoSession {
+			fn session(&self) -> Option<Arc<pubsub::Session>> {
+				None
+			}
+		}
+
+		let limiter = Arc::new(RateLimiter::new(3));
+		assert!(limiter.check(&NoSession, 2));
+		assert!(limiter.check(&NoSession, 1));
+		assert!(!limiter.check(&NoSession, 1));
+	}
+
+	#[test]
+	fn request_tracker_counts_and_rejects_requests() {
+		let tracker = RequestTracker::new();
+		let first = tracker.start().expect("tracker is open");
+		let second = tracker.clone().start().expect("tracker is open");
+		assert_eq!(tracker.in_flight(), 2);
+
+		tracker.close();
+		assert!(tracker.start().is_none());
+		assert_eq!(tracker.in_flight(), 2);
+		drop(first);
+		drop(second);
+		assert_eq!(tracker.in_flight(), 0);
+	}
+
+	#[test]
+	fn subscription_calls_are_detected() {
+		assert_eq!(
+			SubscriptionCall::from_method("chain_subscribeNewHeads"),
+			Some(SubscriptionCall::Subscribe),
+		);
+		assert_eq!(
+			SubscriptionCall::from_method("author_submitAndWatchExtrinsic"),
+			Some(SubscriptionCall::Subscribe),
+		);
+		assert_eq!(
+			SubscriptionCall::from_method("state_unsubscribeStorage"),
+			Some(SubscriptionCall::Unsubscribe),
+		);
+		assert_eq!(
+			SubscriptionCall::from_method("author_unwatchExtrinsic"),
+			Some(SubscriptionCall::Unsubscribe),
+		);
+		assert_eq!(SubscriptionCall::from_method("system_name"), None);
+	}
+
+	#[test]
+	fn large_responses_are_replaced() {
+		let response = Response::Single(Output::from(
+			Ok(serde_json::Value::String("a".repeat(100))),
+			jsonrpc_core::Id::Num(1),
+			Some(jsonrpc_core::Version::V2),
+		));
+		assert_eq!(limit_response_size(response.clone(), 1024), response);
+		match limit_response_size(response, 50) {
+			Response::Single(Output::Failure(failure)) => {
+				assert_eq!(failure.id, jsonrpc_core::Id::Num(1));
+				assert_eq!(failure.error.code, ErrorCode::ServerError(RESPONSE_TOO_LARGE));
+			},
+			r => panic!("Unexpected response: {:?}", r),
+		}
 	}
 }
@@ -0,0 +1,119 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the [`ArchiveApi`] trait for querying historical blocks of full nodes.
+
+#[cfg(test)]
+mod tests;
+
+pub use sc_rpc_api::archive::ArchiveApi;
+
+use sc_client_api::{Backend, BlockBackend, CallExecutor, ExecutorProvider, StorageProvider};
+use sc_rpc_api::archive::error::{Error, Result};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	Bytes, Encode,
+	storage::{ChildInfo, ChildType, PrefixedStorageKey, StorageData, StorageKey},
+};
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, NumberFor}};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The Archive API.
+pub struct Archive<BE, Block: BlockT, Client> {
+	client: Arc<Client>,
+	_phantom: PhantomData<(BE, Block)>,
+}
+
+impl<BE, Block: BlockT, Client> Archive<BE, Block, Client> {
+	/// Create a new Archive API.
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client, _phantom: PhantomData }
+	}
+}
+
+fn client_err(err: sp_blockchain::Error) -> Error {
+	match err {
+		sp_blockchain::Error::UnknownBlock(e) => Error::StateUnavailable(e),
+		e => Error::Client(Box::new(e)),
+	}
+}
+
+impl<BE, Block, Client> ArchiveApi<Block::Hash, NumberFor<Block>> for Archive<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, BE>
+		+ ExecutorProvider<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	fn hash_by_height(&self, height: NumberFor<Block>) -> Result<Vec<Block::Hash>> {
+		Ok(self.client.hash(height).map_err(client_err)?.into_iter().collect())
+	}
+
+	fn body(&self, hash: Block::Hash) -> Result<Option<Vec<Bytes>>> {
+		let body = self.client.block_body(&BlockId::Hash(hash)).map_err(client_err)?;
+		Ok(body.map(|extrinsics| extrinsics.iter().map(|xt| xt.encode().into()).collect()))
+	}
+
+	fn storage(
+		&self,
+		hash: Block::Hash,
+		key: StorageKey,
+		child_key: Option<PrefixedStorageKey>,
+	) -> Result<Option<StorageData>> {
+		let block = BlockId::Hash(hash);
+		match child_key {
+			Some(child_key) => {
+				let child_info = match ChildType::from_prefixed_key(&child_key) {
+					Some((ChildType::ParentKeyId, storage_key)) =>
+						ChildInfo::new_default(storage_key),
+					None => return Err(Error::InvalidChildStorageKey),
+				};
+				self.client.child_storage(&block, &child_info, &key)
+			},
+			None => self.client.storage(&block, &key),
+		}.map_err(client_err)
+	}
+
+	fn call(&self, hash: Block::Hash, function: String, call_parameters: Bytes) -> Result<Bytes> {
+		// Make sure the block is known, so that a pruned or unknown block is reported as such
+		// instead of as a failed call.
+		if self.client.header(BlockId::Hash(hash)).map_err(client_err)?.is_none() {
+			return Err(Error::StateUnavailable(format!("{:?}", hash)))
+		}
+
+		self.client
+			.executor()
+			.call(
+				&BlockId::Hash(hash),
+				&function,
+				&*call_parameters,
+				self.client.execution_extensions().strategies().other,
+				None,
+			)
+			.map(Into::into)
+			.map_err(|e| match e {
+				sp_blockchain::Error::UnknownBlock(e) => Error::StateUnavailable(e),
+				e => Error::CallFailed(e.to_string()),
+			})
+	}
+}
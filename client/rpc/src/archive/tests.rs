@@ -0,0 +1,114 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use assert_matches::assert_matches;
+use codec::Decode;
+use futures::executor;
+use sc_block_builder::BlockBuilderProvider;
+use substrate_test_runtime_client::{
+	prelude::*,
+	runtime::{self, Block},
+	sp_consensus::BlockOrigin,
+};
+
+fn new_api(client: Arc<TestClient>) -> Archive<Backend, Block, TestClient> {
+	Archive::new(client)
+}
+
+#[test]
+fn hash_by_height_and_body_work() {
+	let mut client = Arc::new(substrate_test_runtime_client::new());
+	let api = new_api(client.clone());
+
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_transfer(runtime::Transfer {
+		from: AccountKeyring::Alice.into(),
+		to: AccountKeyring::Ferdie.into(),
+		amount: 42,
+		nonce: 0,
+	}).unwrap();
+	let block = builder.build().unwrap().block;
+	let hash = block.header.hash();
+	executor::block_on(client.import(BlockOrigin::Own, block.clone())).unwrap();
+
+	assert_eq!(api.hash_by_height(0).unwrap(), vec![client.genesis_hash()]);
+	assert_eq!(api.hash_by_height(1).unwrap(), vec![hash]);
+	assert_eq!(api.hash_by_height(2).unwrap(), vec![]);
+
+	assert_eq!(
+		api.body(hash).unwrap(),
+		Some(block.extrinsics.iter().map(|xt| xt.encode().into()).collect()),
+	);
+	assert_eq!(api.body(Default::default()).unwrap(), None);
+}
+
+#[test]
+fn storage_works() {
+	const KEY: &[u8] = b":mock";
+	const VALUE: &[u8] = b"hello world";
+	const CHILD_VALUE: &[u8] = b"hello world !";
+
+	let child_info = ChildInfo::new_default(b"child");
+	let client = Arc::new(TestClientBuilder::new()
+		.add_extra_storage(KEY.to_vec(), VALUE.to_vec())
+		.add_extra_child_storage(&child_info, KEY.to_vec(), CHILD_VALUE.to_vec())
+		.build());
+	let api = new_api(client.clone());
+	let genesis_hash = client.genesis_hash();
+	let key = StorageKey(KEY.to_vec());
+
+	assert_eq!(
+		api.storage(genesis_hash, key.clone(), None).unwrap(),
+		Some(StorageData(VALUE.to_vec())),
+	);
+	assert_eq!(
+		api.storage(genesis_hash, key.clone(), Some(child_info.prefixed_storage_key())).unwrap(),
+		Some(StorageData(CHILD_VALUE.to_vec())),
+	);
+	assert_eq!(api.storage(genesis_hash, StorageKey(b":unknown".to_vec()), None).unwrap(), None);
+	assert_matches!(
+		api.storage(genesis_hash, key.clone(), Some(PrefixedStorageKey::new(b"child".to_vec()))),
+		Err(Error::InvalidChildStorageKey)
+	);
+	assert_matches!(
+		api.storage(Default::default(), key, None),
+		Err(Error::StateUnavailable(_))
+	);
+}
+
+#[test]
+fn call_works() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let api = new_api(client.clone());
+	let genesis_hash = client.genesis_hash();
+
+	let version = api.call(genesis_hash, "Core_version".into(), Bytes(Vec::new())).unwrap();
+	assert_eq!(
+		sp_version::RuntimeVersion::decode(&mut &version[..]).unwrap(),
+		runtime::VERSION,
+	);
+	assert_matches!(
+		api.call(genesis_hash, "Core_unknown".into(), Bytes(Vec::new())),
+		Err(Error::CallFailed(_))
+	);
+	assert_matches!(
+		api.call(Default::default(), "Core_version".into(), Bytes(Vec::new())),
+		Err(Error::StateUnavailable(_))
+	);
+}
@@ -34,17 +34,18 @@ use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionMan
 use codec::{Encode, Decode};
 use sp_core::Bytes;
 use sp_keystore::{SyncCryptoStorePtr, SyncCryptoStore};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_runtime::generic;
 use sp_transaction_pool::{
-	TransactionPool, InPoolTransaction, TransactionStatus, TransactionSource,
-	BlockHash, TxHash, TransactionFor, error::IntoPoolError,
+	TransactionPool, InPoolTransaction, TransactionStatus, TransactionStatusStream,
+	TransactionSource, BlockHash, TxHash, TransactionFor, error::IntoPoolError,
 };
 use sp_session::SessionKeys;
 
 /// Re-export the API for backward compatibility.
 pub use sc_rpc_api::author::*;
 use self::error::{Error, FutureResult, Result};
+use self::event::TransactionEvent;
 
 /// Authoring API
 pub struct Author<P, Client> {
@@ -111,11 +112,34 @@ impl<P, Client> AuthorApi<TxHash<P>, BlockHash<P>> for Author<P, Client>
 	fn rotate_keys(&self) -> Result<Bytes> {
 		self.deny_unsafe.check_if_safe()?;
 
-		let best_block_hash = self.client.info().best_hash;
-		self.client.runtime_api().generate_session_keys(
-			&generic::BlockId::Hash(best_block_hash),
-			None,
-		).map(Into::into).map_err(|e| Error::Client(Box::new(e)))
+		let at = generic::BlockId::Hash(self.client.info().best_hash);
+		let runtime_api = self.client.runtime_api();
+		let has_api_v2 = runtime_api.has_api_with::<dyn SessionKeys<P::Block>, _>(&at, |v| v >= 2)
+			.map_err(|e| Error::Client(Box::new(e)))?;
+
+		if has_api_v2 {
+			runtime_api.generate_session_keys(&at, Vec::new(), None)
+				.map(|generated| generated.keys.into())
+		} else {
+			#[allow(deprecated)]
+			runtime_api.generate_session_keys_before_version_2(&at, None).map(Into::into)
+		}.map_err(|e| Error::Client(Box::new(e)))
+	}
+
+	fn rotate_keys_with_owner(&self, owner: Bytes) -> Result<GeneratedSessionKeys> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let at = generic::BlockId::Hash(self.client.info().best_hash);
+		let runtime_api = self.client.runtime_api();
+		let has_api_v2 = runtime_api.has_api_with::<dyn SessionKeys<P::Block>, _>(&at, |v| v >= 2)
+			.map_err(|e| Error::Client(Box::new(e)))?;
+		if !has_api_v2 {
+			return Err(Error::OwnershipProofUnsupported)
+		}
+
+		let generated = runtime_api.generate_session_keys(&at, owner.to_vec(), None)
+			.map_err(|e| Error::Client(Box::new(e)))?;
+		Ok(GeneratedSessionKeys { keys: generated.keys.into(), proof: generated.proof.into() })
 	}
 
 	fn has_session_keys(&self, session_keys: Bytes) -> Result<bool> {
@@ -234,4 +258,88 @@ impl<P, Client> AuthorApi<TxHash<P>, BlockHash<P>> for Author<P, Client>
 	fn unwatch_extrinsic(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
 		Ok(self.subscriptions.cancel(id))
 	}
+
+	fn watch_transaction(&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<TransactionEvent<BlockHash<P>>>,
+		xt: Bytes,
+	) {
+		let best_block_hash = self.client.info().best_hash;
+		let submit = TransactionFor::<P>::decode(&mut &xt[..])
+			.map_err(|e| error::Error::from(e).to_string())
+			.map(|dxt| {
+				self.pool.submit_and_watch(&generic::BlockId::hash(best_block_hash), TX_SOURCE, dxt)
+			});
+
+		let subscriptions = self.subscriptions.clone();
+		let future = async move {
+			let watcher = match submit {
+				Ok(submit) => submit.await.map_err(|e| e.to_string()),
+				Err(e) => Err(e),
+			};
+			let events = match watcher {
+				Ok(watcher) => transaction_events(watcher).boxed(),
+				Err(error) => {
+					warn!("Failed to submit extrinsic: {}", error);
+					futures::stream::once(ready(TransactionEvent::Invalid { error })).boxed()
+				},
+			};
+
+			subscriptions.add(subscriber, move |sink| {
+				sink
+					.sink_map_err(|e| log::debug!("Subscription sink failed: {:?}", e))
+					.send_all(Compat::new(events.map(|v| Ok::<_, ()>(Ok(v)))))
+					.map(|_| ())
+			});
+
+			Ok::<_, ()>(())
+		};
+
+		let res = self.subscriptions.executor()
+			.execute(Box::new(Compat::new(future.boxed())));
+		if res.is_err() {
+			warn!("Error spawning subscription RPC task.");
+		}
+	}
+
+	fn unwatch_transaction(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
+
+/// Converts the statuses of a watched transaction into transaction events.
+///
+/// If the pool stops reporting statuses before a terminal event, the stream is ended
+/// with a `dropped` event.
+fn transaction_events<Hash: Send, BlockHash: Send>(
+	statuses: Box<TransactionStatusStream<Hash, BlockHash>>,
+) -> impl futures::Stream<Item = TransactionEvent<BlockHash>> + Send {
+	futures::stream::unfold(
+		(statuses, false, false, false),
+		|(mut statuses, mut validated, mut broadcasted, finished)| async move {
+			if finished {
+				return None
+			}
+
+			while let Some(status) = statuses.next().await {
+				let is_broadcast = matches!(status, TransactionStatus::Broadcast(_));
+				if let Some(event) = TransactionEvent::from_status(status, validated, broadcasted) {
+					validated = true;
+					broadcasted |= is_broadcast;
+					let finished = event.is_terminal();
+					return Some((event, (statuses, validated, broadcasted, finished)))
+				}
+			}
+
+			let event = TransactionEvent::Dropped {
+				broadcasted,
+				error: "Transaction is no longer watched by the pool".into(),
+			};
+			Some((event, (statuses, validated, broadcasted, true)))
+		},
+	)
 }
@@ -27,6 +27,7 @@ use sp_core::{
 	crypto::{CryptoTypePublicPair, Pair, Public},
 };
 use sp_keystore::testing::KeyStore;
+use sp_runtime::traits::OpaqueKeys;
 use rpc::futures::Stream as _;
 use substrate_test_runtime_client::{
 	self, AccountKeyring, runtime::{Extrinsic, Transfer, SessionKeys, Block},
@@ -173,6 +174,53 @@ fn should_watch_extrinsic() {
 	assert_eq!(res, expected);
 }
 
+#[test]
+fn should_watch_transaction() {
+	//given
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let (subscriber, id_rx, data) = jsonrpc_pubsub::typed::Subscriber::new_test("test");
+
+	// when
+	p.watch_transaction(
+		Default::default(),
+		subscriber,
+		uxt(AccountKeyring::Alice, 0).encode().into(),
+	);
+
+	let id = match executor::block_on(id_rx.compat()).unwrap().unwrap() {
+		SubscriptionId::String(id) => id,
+		_ => unreachable!(),
+	};
+
+	// check notifications
+	let replacement = {
+		let tx = Transfer {
+			amount: 5,
+			nonce: 0,
+			from: AccountKeyring::Alice.into(),
+			to: Default::default(),
+		};
+		tx.into_signed_tx()
+	};
+	AuthorApi::submit_extrinsic(&p, replacement.encode().into()).wait().unwrap();
+	let (res, data) = executor::block_on(data.into_future().compat()).unwrap();
+
+	let expected = Some(format!(
+		r#"{{"jsonrpc":"2.0","method":"test","params":{{"result":{{"event":"validated"}},"subscription":"{}"}}}}"#,
+		id,
+	));
+	assert_eq!(res, expected);
+
+	let expected = Some(format!(
+		r#"{{"jsonrpc":"2.0","method":"test","params":{{"result":{{"event":"invalid","error":"Transaction was replaced by another transaction"}},"subscription":"{}"}}}}"#,
+		id,
+	));
+	let res = executor::block_on(data.into_future().compat()).unwrap().0;
+	assert_eq!(res, expected);
+}
+
 #[test]
 fn should_return_watch_validation_error() {
 	//given
@@ -259,6 +307,22 @@ fn should_rotate_keys() {
 	assert!(sr25519_public_keys.contains(&CryptoTypePublicPair(sr25519::CRYPTO_ID, session_keys.sr25519.to_raw_vec())));
 }
 
+#[test]
+fn should_rotate_keys_with_owner() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let owner = AccountKeyring::Alice.to_account_id().encode();
+	let generated = p.rotate_keys_with_owner(owner.clone().into()).expect("Rotates the keys");
+
+	let session_keys = SessionKeys::decode(&mut &generated.keys[..])
+		.expect("SessionKeys decode successfully");
+
+	assert!(session_keys.ownership_proof_is_valid(&owner, &generated.proof));
+	assert!(!session_keys.ownership_proof_is_valid(&[1, 2, 3], &generated.proof));
+	assert!(p.has_session_keys(generated.keys).expect("Checks the keys"));
+}
+
 #[test]
 fn test_has_session_keys() {
 	let setup = TestSetup::default();
@@ -0,0 +1,63 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Substrate chain spec API.
+
+#[cfg(test)]
+mod tests;
+
+use rpc::Result;
+use sp_chain_spec::Properties;
+
+pub use sc_rpc_api::chain_spec::*;
+
+/// Chain spec API
+pub struct ChainSpec<Hash> {
+	name: String,
+	genesis_hash: Hash,
+	properties: Properties,
+}
+
+impl<Hash> ChainSpec<Hash> {
+	/// Create new instance of the chain spec API.
+	pub fn new(name: String, genesis_hash: Hash, properties: Properties) -> Self {
+		ChainSpec {
+			name,
+			genesis_hash,
+			properties,
+		}
+	}
+}
+
+impl<Hash> ChainSpecApi<Hash> for ChainSpec<Hash>
+where
+	Hash: Clone + Send + Sync + 'static,
+{
+	fn chain_name(&self) -> Result<String> {
+		Ok(self.name.clone())
+	}
+
+	fn genesis_hash(&self) -> Result<Hash> {
+		Ok(self.genesis_hash.clone())
+	}
+
+	fn properties(&self) -> Result<Properties> {
+		Ok(self.properties.clone())
+	}
+}
@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+fn api() -> ChainSpec<u64> {
+	let mut properties = Properties::new();
+	properties.insert("tokenDecimals".into(), 12.into());
+	ChainSpec::new("Local Testnet".into(), 42, properties)
+}
+
+#[test]
+fn chain_name_works() {
+	assert_eq!(api().chain_name().unwrap(), "Local Testnet");
+}
+
+#[test]
+fn genesis_hash_works() {
+	assert_eq!(api().genesis_hash().unwrap(), 42);
+}
+
+#[test]
+fn properties_works() {
+	let properties = api().properties().unwrap();
+	assert_eq!(properties.len(), 1);
+	assert_eq!(properties["tokenDecimals"], 12);
+}
@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Implementation of the [`DevApi`] trait providing debug utilities for Substrate based
+//! blockchains.
+
+#[cfg(test)]
+mod tests;
+
+pub use sc_rpc_api::dev::{BlockStats, ExtrinsicStats, DevApi};
+
+use sc_client_api::BlockBackend;
+use sc_rpc_api::{dev::error::{Error, Result}, DenyUnsafe};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::Encode;
+use sp_runtime::{
+	generic::{BlockId, DigestItem},
+	traits::{Block as BlockT, Header},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The Dev API. All methods are unsafe.
+pub struct Dev<Block: BlockT, Client> {
+	client: Arc<Client>,
+	deny_unsafe: DenyUnsafe,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block: BlockT, Client> Dev<Block, Client> {
+	/// Create a new Dev API.
+	pub fn new(client: Arc<Client>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _phantom: PhantomData::default() }
+	}
+}
+
+impl<Block, Client> DevApi<Block::Hash> for Dev<Block, Client>
+where
+	Block: BlockT + 'static,
+	Client: BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: BlockBuilder<Block>,
+{
+	fn block_stats(&self, hash: Block::Hash) -> Result<Option<BlockStats>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let block = {
+			let block = self
+				.client
+				.block(&BlockId::Hash(hash))
+				.map_err(|e| Error::BlockQueryError(Box::new(e)))?;
+			if let Some(block) = block {
+				let (mut header, body) = block.block.deconstruct();
+				// Remove the `Seal` to ensure we have the number of digests as expected by the
+				// runtime.
+				header.digest_mut().logs.retain(|item| !matches!(item, DigestItem::Seal(_, _)));
+				Block::new(header, body)
+			} else {
+				return Ok(None)
+			}
+		};
+		let parent_header = {
+			let parent_hash = *block.header().parent_hash();
+			let parent_header = self
+				.client
+				.header(BlockId::Hash(parent_hash))
+				.map_err(|e| Error::BlockQueryError(Box::new(e)))?;
+			if let Some(header) = parent_header {
+				header
+			} else {
+				return Ok(None)
+			}
+		};
+		let block_len = block.encoded_size() as u64;
+		let num_extrinsics = block.extrinsics().len() as u64;
+
+		let parent_id = BlockId::Hash(parent_header.hash());
+		let (header, body) = block.deconstruct();
+		let mut runtime_api = self.client.runtime_api();
+		runtime_api.record_proof();
+		runtime_api
+			.initialize_block(&parent_id, &header)
+			.map_err(|e| Error::BlockExecutionFailed(e.to_string()))?;
+
+		let mut extrinsics = Vec::with_capacity(body.len());
+		for extrinsic in body {
+			let len = extrinsic.encoded_size() as u64;
+			let error = match runtime_api
+				.apply_extrinsic(&parent_id, extrinsic)
+				.map_err(|e| Error::BlockExecutionFailed(e.to_string()))?
+			{
+				Ok(Ok(())) => None,
+				Ok(Err(e)) => Some(format!("{:?}", e)),
+				Err(e) => Some(format!("{:?}", e)),
+			};
+			extrinsics.push(ExtrinsicStats { len, success: error.is_none(), error });
+		}
+
+		runtime_api
+			.finalize_block(&parent_id)
+			.map_err(|e| Error::BlockExecutionFailed(e.to_string()))?;
+		let witness = runtime_api.extract_proof().ok_or(Error::WitnessExtractionFailed)?;
+
+		Ok(Some(BlockStats {
+			witness_len: witness.encoded_size() as u64,
+			block_len,
+			num_extrinsics,
+			extrinsics,
+		}))
+	}
+}
@@ -30,8 +30,11 @@ use std::sync::Arc;
 pub use sc_rpc_api::{DenyUnsafe, Metadata};
 pub use rpc::IoHandlerExtension as RpcExtension;
 
+pub mod archive;
 pub mod author;
 pub mod chain;
+pub mod chain_spec;
+pub mod dev;
 pub mod offchain;
 pub mod state;
 pub mod system;
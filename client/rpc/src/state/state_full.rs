@@ -26,10 +26,10 @@ use log::warn;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionManager};
 use rpc::{Result as RpcResult, futures::{stream, Future, Sink, Stream, future::result}};
 
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, StorageFilter};
 use sc_client_api::backend::Backend;
 use sp_blockchain::{Result as ClientResult, Error as ClientError, HeaderMetadata, CachedHeaderMetadata, HeaderBackend};
-use sc_client_api::BlockchainEvents;
+use sc_client_api::{BlockchainEvents, StorageKeyFilter};
 use sp_core::{
 	Bytes, storage::{well_known_keys, StorageKey, StorageData, StorageChangeSet,
 	ChildInfo, ChildType, PrefixedStorageKey},
@@ -43,7 +43,7 @@ use sp_api::{Metadata, ProvideRuntimeApi, CallApiAt};
 
 use super::{StateBackend, ChildStateBackend, error::{FutureResult, Error, Result}, client_err};
 use std::marker::PhantomData;
-use sc_client_api::{CallExecutor, StorageProvider, ExecutorProvider, ProofProvider};
+use sc_client_api::{CallExecutor, StorageProvider, ExecutorProvider, ProofProvider, BlockBackend};
 
 /// Ranges to query in state_queryStorage.
 struct QueryStorageRange<Block: BlockT> {
@@ -149,7 +149,11 @@ impl<BE, Block: BlockT, Client> FullState<BE, Block, Client>
 	) -> Result<()> {
 		for block in range.unfiltered_range.start..range.unfiltered_range.end {
 			let block_hash = range.hashes[block].clone();
-			let mut block_changes = StorageChangeSet { block: block_hash.clone(), changes: Vec::new() };
+			let mut block_changes = StorageChangeSet {
+				block: block_hash.clone(),
+				changes: Vec::new(),
+				child_changes: Vec::new(),
+			};
 			let id = BlockId::hash(block_hash);
 			for key in keys {
 				let (has_changed, data) = {
@@ -204,7 +208,11 @@ impl<BE, Block: BlockT, Client> FullState<BE, Block, Client>
 				}
 
 				changes_map.entry(block)
-					.or_insert_with(|| StorageChangeSet { block: block_hash, changes: Vec::new() })
+					.or_insert_with(|| StorageChangeSet {
+						block: block_hash,
+						changes: Vec::new(),
+						child_changes: Vec::new(),
+					})
 					.changes.push((key.clone(), value_at_block.clone()));
 				last_block = Some(block);
 				last_value = value_at_block;
@@ -223,7 +231,7 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 	BE: Backend<Block> + 'static,
 	Client: ExecutorProvider<Block> + StorageProvider<Block, BE> + ProofProvider<Block> + HeaderBackend<Block>
 		+ HeaderMetadata<Block, Error = sp_blockchain::Error> + BlockchainEvents<Block>
-		+ CallApiAt<Block> + ProvideRuntimeApi<Block>
+		+ CallApiAt<Block> + ProvideRuntimeApi<Block> + BlockBackend<Block>
 		+ Send + Sync + 'static,
 	Client::Api: Metadata<Block>,
 {
@@ -409,6 +417,23 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 		))
 	}
 
+	fn trace_block(
+		&self,
+		block: Block::Hash,
+		targets: Option<String>,
+		storage_keys: Option<String>,
+	) -> FutureResult<sp_rpc::tracing::TraceBlockResponse> {
+		let block_executor = sc_tracing::block::BlockExecutor::new(
+			self.client.clone(),
+			block,
+			targets,
+			storage_keys,
+		);
+		Box::new(result(
+			block_executor.trace_block().map_err(|e| Error::Client(Box::new(e)))
+		))
+	}
+
 	fn subscribe_runtime_version(
 		&self,
 		_meta: crate::Metadata,
@@ -473,11 +498,27 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 		_meta: crate::Metadata,
 		subscriber: Subscriber<StorageChangeSet<Block::Hash>>,
 		keys: Option<Vec<StorageKey>>,
+		filter: Option<StorageFilter>,
 	) {
 		let keys = Into::<Option<Vec<_>>>::into(keys);
-		let stream = match self.client.storage_changes_notification_stream(
-			keys.as_ref().map(|x| &**x),
-			None
+		let StorageFilter { prefixes, child_tries } = filter.unwrap_or_default();
+		let mut child_filters = HashMap::new();
+		for child_trie in child_tries {
+			let storage_key = match ChildType::from_prefixed_key(&child_trie.child_storage_key) {
+				Some((ChildType::ParentKeyId, storage_key)) => StorageKey(storage_key.to_vec()),
+				None => {
+					let _ = subscriber.reject(
+						client_err(sp_blockchain::Error::InvalidChildStorageKey).into(),
+					);
+					return;
+				},
+			};
+			child_filters.insert(storage_key, key_filter(child_trie.keys, child_trie.prefixes));
+		}
+
+		let stream = match self.client.storage_changes_notification_stream_filtered(
+			key_filter(keys.clone(), prefixes),
+			Some(child_filters),
 		) {
 			Ok(stream) => stream,
 			Err(err) => {
@@ -498,18 +539,36 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 						.unwrap_or_else(|_| (key, None))
 					)
 					.collect();
-				vec![Ok(Ok(StorageChangeSet { block, changes }))]
+				vec![Ok(Ok(StorageChangeSet { block, changes, child_changes: Vec::new() }))]
 			}).unwrap_or_default());
 
 		self.subscriptions.add(subscriber, |sink| {
 			let stream = stream
-				.map(|(block, changes)| Ok::<_, ()>(Ok(StorageChangeSet {
-					block,
-					changes: changes.iter()
-						.filter_map(|(o_sk, k, v)| if o_sk.is_none() {
-							Some((k.clone(),v.cloned()))
-						} else { None }).collect(),
-				})))
+				.map(|(block, changes)| {
+					let mut top_changes = Vec::new();
+					let mut child_changes: Vec<(StorageKey, Vec<_>)> = Vec::new();
+					for (o_sk, k, v) in changes.iter() {
+						let change = (k.clone(), v.cloned());
+						match o_sk {
+							None => top_changes.push(change),
+							Some(sk) => match child_changes.last_mut() {
+								Some((last, last_changes)) if *last == *sk =>
+									last_changes.push(change),
+								_ => child_changes.push((sk.clone(), vec![change])),
+							},
+						}
+					}
+
+					Ok::<_, ()>(Ok(StorageChangeSet {
+						block,
+						changes: top_changes,
+						child_changes: child_changes.into_iter()
+							.map(|(sk, changes)| {
+								(ChildInfo::new_default(&sk.0).prefixed_storage_key(), changes)
+							})
+							.collect(),
+					}))
+				})
 				.compat();
 
 			sink
@@ -651,3 +710,16 @@ fn invalid_block<B: BlockT>(
 		details,
 	}
 }
+
+/// Filter of the exact `keys` and `prefixes` of a subscription.
+///
+/// Without keys and prefixes, all keys pass.
+fn key_filter(
+	keys: Option<Vec<StorageKey>>,
+	prefixes: Vec<StorageKey>,
+) -> Option<StorageKeyFilter> {
+	if keys.is_none() && prefixes.is_empty() {
+		return None;
+	}
+	Some(StorageKeyFilter { keys: keys.into_iter().flatten().collect(), prefixes })
+}
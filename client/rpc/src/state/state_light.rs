@@ -40,7 +40,7 @@ use rpc::{
 	futures::stream::Stream,
 };
 
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, StorageFilter};
 use sp_blockchain::{Error as ClientError, HeaderBackend};
 use sc_client_api::{
 	BlockchainEvents,
@@ -297,12 +297,28 @@ impl<Block, F, Client> StateBackend<Block, Client> for LightState<Block, F, Clie
 		Box::new(result(Err(client_err(ClientError::NotAvailableOnLightClient))))
 	}
 
+	fn trace_block(
+		&self,
+		_block: Block::Hash,
+		_targets: Option<String>,
+		_storage_keys: Option<String>,
+	) -> FutureResult<sp_rpc::tracing::TraceBlockResponse> {
+		Box::new(result(Err(client_err(ClientError::NotAvailableOnLightClient))))
+	}
+
 	fn subscribe_storage(
 		&self,
 		_meta: crate::Metadata,
 		subscriber: Subscriber<StorageChangeSet<Block::Hash>>,
-		keys: Option<Vec<StorageKey>>
+		keys: Option<Vec<StorageKey>>,
+		filter: Option<StorageFilter>,
 	) {
+		if filter.map_or(false, |filter| filter != StorageFilter::default()) {
+			warn!("Cannot subscribe to prefixes or child tries on light client. \
+				Subscription rejected.");
+			return;
+		}
+
 		let keys = match keys {
 			Some(keys) if !keys.is_empty() => keys,
 			_ => {
@@ -367,6 +383,7 @@ impl<Block, F, Client> StateBackend<Block, Client> for LightState<Block, F, Clie
 								.iter()
 								.map(|(k, v)| (k.clone(), v.clone()))
 								.collect(),
+							child_changes: Vec::new(),
 						}),
 						false => None,
 					}
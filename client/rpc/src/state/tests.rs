@@ -152,6 +152,44 @@ fn should_call_contract() {
 	)
 }
 
+#[test]
+fn should_trace_block() {
+	let mut client = Arc::new(substrate_test_runtime_client::new());
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_transfer(runtime::Transfer {
+		from: AccountKeyring::Alice.into(),
+		to: AccountKeyring::Ferdie.into(),
+		amount: 42,
+		nonce: 0,
+	}).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = block.hash();
+	executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+	let (unsafe_api, _child) = new_full(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::Yes,
+	);
+	assert_matches!(
+		unsafe_api.trace_block(block_hash, None, None).wait(),
+		Err(Error::UnsafeRpcCalled(_))
+	);
+
+	let (api, _child) = new_full(
+		client,
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+	);
+	match api.trace_block(block_hash, Some("state".into()), None).wait().unwrap() {
+		sp_rpc::tracing::TraceBlockResponse::BlockTrace(trace) => {
+			assert_eq!(trace.block_hash, format!("{:?}", block_hash));
+			assert_eq!(trace.tracing_targets, "state");
+		},
+		response => panic!("Unexpected response: {:?}", response),
+	}
+}
+
 #[test]
 fn should_notify_about_storage_changes() {
 	let (subscriber, id, transport) = Subscriber::new_test("test");
@@ -164,7 +202,7 @@ fn should_notify_about_storage_changes() {
 			DenyUnsafe::No,
 		);
 
-		api.subscribe_storage(Default::default(), subscriber, None.into());
+		api.subscribe_storage(Default::default(), subscriber, None.into(), None);
 
 		// assert id assigned
 		assert!(matches!(
@@ -206,7 +244,7 @@ fn should_send_initial_storage_changes_and_notifications() {
 
 		api.subscribe_storage(Default::default(), subscriber, Some(vec![
 			StorageKey(alice_balance_key.to_vec()),
-		]).into());
+		]).into(), None);
 
 		// assert id assigned
 		assert!(matches!(
@@ -235,6 +273,50 @@ fn should_send_initial_storage_changes_and_notifications() {
 	assert_eq!(executor::block_on(next.into_future().compat()).unwrap().0, None);
 }
 
+#[test]
+fn should_notify_about_storage_changes_with_prefix() {
+	let (subscriber, id, transport) = Subscriber::new_test("test");
+
+	{
+		let mut client = Arc::new(substrate_test_runtime_client::new());
+		let (api, _child) = new_full(
+			client.clone(),
+			SubscriptionManager::new(Arc::new(TaskExecutor)),
+			DenyUnsafe::No,
+		);
+
+		let alice_balance_key = blake2_256(&runtime::system::balance_of_key(AccountKeyring::Alice.into()));
+		let filter = StorageFilter {
+			prefixes: vec![StorageKey(alice_balance_key[..4].to_vec())],
+			child_tries: Vec::new(),
+		};
+
+		api.subscribe_storage(Default::default(), subscriber, None.into(), Some(filter));
+
+		// assert id assigned
+		assert!(matches!(
+			executor::block_on(id.compat()),
+			Ok(Ok(SubscriptionId::String(_)))
+		));
+
+		let mut builder = client.new_block(Default::default()).unwrap();
+		builder.push_transfer(runtime::Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Ferdie.into(),
+			amount: 42,
+			nonce: 0,
+		}).unwrap();
+		let block = builder.build().unwrap().block;
+		executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+	}
+
+	// assert notification sent to transport, without initial values
+	let (notification, next) = executor::block_on(transport.into_future().compat()).unwrap();
+	assert!(notification.is_some());
+	// no more notifications on this channel
+	assert_eq!(executor::block_on(next.into_future().compat()).unwrap().0, None);
+}
+
 #[test]
 fn should_query_storage() {
 	fn run_tests(mut client: Arc<TestClient>, has_changes_trie_config: bool) {
@@ -282,6 +364,7 @@ fn should_query_storage() {
 					(StorageKey(vec![4]), None),
 					(StorageKey(vec![5]), None),
 				],
+				child_changes: Vec::new(),
 			},
 			StorageChangeSet {
 				block: block1_hash,
@@ -290,6 +373,7 @@ fn should_query_storage() {
 					(StorageKey(vec![3]), Some(StorageData(vec![3]))),
 					(StorageKey(vec![5]), Some(StorageData(vec![0]))),
 				],
+				child_changes: Vec::new(),
 			},
 		];
 
@@ -317,6 +401,7 @@ fn should_query_storage() {
 				(StorageKey(vec![4]), Some(StorageData(vec![4]))),
 				(StorageKey(vec![5]), Some(StorageData(vec![1]))),
 			],
+			child_changes: Vec::new(),
 		});
 		assert_eq!(result.wait().unwrap(), expected);
 
@@ -429,7 +514,8 @@ fn should_query_storage() {
 						(StorageKey(vec![3_u8]), Some(StorageData(vec![3_u8]))),
 						(StorageKey(vec![4_u8]), None),
 						(StorageKey(vec![5_u8]), Some(StorageData(vec![0_u8]))),
-					]
+					],
+					child_changes: Vec::new(),
 				}
 			]
 		);
@@ -47,6 +47,7 @@ pub struct System<B: traits::Block> {
 	info: SystemInfo,
 	send_back: TracingUnboundedSender<Request<B>>,
 	deny_unsafe: DenyUnsafe,
+	shutdown: Option<TracingUnboundedSender<()>>,
 }
 
 /// Request to be processed.
@@ -86,8 +87,17 @@ impl<B: traits::Block> System<B> {
 			info,
 			send_back,
 			deny_unsafe,
+			shutdown: None,
 		}
 	}
+
+	/// Let `system_shutdown` request the shutdown of the node by sending on `shutdown`.
+	///
+	/// Without it, `system_shutdown` is reported as not found.
+	pub fn with_shutdown(mut self, shutdown: TracingUnboundedSender<()>) -> Self {
+		self.shutdown = Some(shutdown);
+		self
+	}
 }
 
 impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for System<B> {
@@ -209,4 +219,10 @@ impl<B: traits::Block> SystemApi<B::Hash, <B::Header as HeaderT>::Number> for Sy
 		self.deny_unsafe.check_if_safe()?;
 		logging::reset_log_filter().map_err(|_e| rpc::Error::internal_error())
 	}
+
+	fn system_shutdown(&self) -> std::result::Result<(), rpc::Error> {
+		self.deny_unsafe.check_if_safe()?;
+		let shutdown = self.shutdown.as_ref().ok_or_else(rpc::Error::method_not_found)?;
+		shutdown.unbounded_send(()).map_err(|_e| rpc::Error::internal_error())
+	}
 }
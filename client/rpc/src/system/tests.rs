@@ -337,6 +337,15 @@ fn system_network_remove_reserved() {
 	assert!(runtime.block_on(bad_fut).is_err());
 }
 
+#[test]
+fn system_shutdown() {
+	assert_eq!(api(None).system_shutdown(), Err(rpc::Error::method_not_found()));
+
+	let (tx, mut rx) = tracing_unbounded("rpc_system_shutdown_tests");
+	assert_eq!(api(None).with_shutdown(tx).system_shutdown(), Ok(()));
+	assert!(matches!(rx.try_next(), Ok(Some(()))));
+}
+
 #[test]
 fn test_add_reset_log_filter() {
 	const EXPECTED_BEFORE_ADD: &'static str = "EXPECTED_BEFORE_ADD";
@@ -18,7 +18,8 @@
 
 use crate::{
 	error::Error, MallocSizeOfWasm, RpcHandlers, NetworkStatusSinks,
-	start_rpc_servers, build_network_future, TransactionPoolAdapter, TaskManager, SpawnTaskHandle,
+	start_rpc_servers, stop_rpc_servers, build_network_future, TransactionPoolAdapter, TaskManager,
+	SpawnTaskHandle, DEFAULT_RPC_SHUTDOWN_TIMEOUT, NETWORKING_GROUP,
 	metrics::MetricsService,
 	client::{light, Client, ClientConfig},
 	config::{Configuration, KeystoreConfig, PrometheusConfig},
@@ -43,6 +44,7 @@ use log::info;
 use sc_network::config::{Role, OnDemand};
 use sc_network::NetworkService;
 use sc_network::block_request_handler::{self, BlockRequestHandler};
+use sc_network::state_request_handler::{self, StateRequestHandler};
 use sc_network::light_client_requests::{self, handler::LightClientRequestHandler};
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{
@@ -60,6 +62,7 @@ use sc_telemetry::{
 	SUBSTRATE_INFO,
 };
 use sp_transaction_pool::MaintainedTransactionPool;
+use codec::DecodeAll;
 use prometheus_endpoint::Registry;
 use sc_client_db::{Backend, DatabaseSettings};
 use sp_core::traits::{
@@ -150,6 +153,7 @@ pub type TFullBackend<TBl> = sc_client_db::Backend<TBl>;
 
 /// Full client call executor type.
 pub type TFullCallExecutor<TBl, TExecDisp> = crate::client::LocalCallExecutor<
+	TBl,
 	sc_client_db::Backend<TBl>,
 	NativeExecutor<TExecDisp>,
 >;
@@ -172,6 +176,7 @@ pub type TLightCallExecutor<TBl, TExecDisp> = sc_light::GenesisCallExecutor<
 		HashFor<TBl>
 	>,
 	crate::client::LocalCallExecutor<
+		TBl,
 		sc_light::Backend<
 			sc_client_db::light::LightStorage<TBl>,
 			HashFor<TBl>
@@ -206,7 +211,7 @@ pub type TLightClientWithBackend<TBl, TRtApi, TExecDisp, TBackend> = Client<
 	TBackend,
 	sc_light::GenesisCallExecutor<
 		TBackend,
-		crate::client::LocalCallExecutor<TBackend, NativeExecutor<TExecDisp>>,
+		crate::client::LocalCallExecutor<TBl, TBackend, NativeExecutor<TExecDisp>>,
 	>,
 	TBl,
 	TRtApi,
@@ -314,10 +319,11 @@ pub fn new_full_parts<TBl, TRtApi, TExecDisp>(
 		TaskManager::new(config.task_executor.clone(), registry)?
 	};
 
-	let executor = NativeExecutor::<TExecDisp>::new(
+	let executor = NativeExecutor::<TExecDisp>::new_with_cache_path(
 		config.wasm_method,
 		config.default_heap_pages,
 		config.max_runtime_instances,
+		config.wasm_runtime_cache.clone(),
 	);
 
 	let chain_spec = &config.chain_spec;
@@ -329,6 +335,20 @@ pub fn new_full_parts<TBl, TRtApi, TExecDisp>(
 		.cloned()
 		.unwrap_or_default();
 
+	let code_substitutes = chain_spec.code_substitutes()
+		.into_iter()
+		.map(|(h, c)| {
+			let hash = sp_core::bytes::from_hex(&h)
+				.ok()
+				.and_then(|bytes| <TBl::Hash as DecodeAll>::decode_all(&mut &bytes[..]).ok())
+				.ok_or_else(|| Error::Other(
+					format!("Failed to parse `{}` as block hash for code substitutes.", h),
+				))?;
+
+			Ok((hash, c))
+		})
+		.collect::<Result<std::collections::HashMap<TBl::Hash, Vec<u8>>, Error>>()?;
+
 	let (client, backend) = {
 		let db_config = sc_client_db::DatabaseSettings {
 			state_cache_size: config.state_cache_size,
@@ -363,6 +383,7 @@ pub fn new_full_parts<TBl, TRtApi, TExecDisp>(
 				offchain_worker_enabled : config.offchain_worker.enabled,
 				offchain_indexing_api: config.offchain_worker.indexing_enabled,
 				wasm_runtime_overrides: config.wasm_runtime_overrides.clone(),
+				wasm_runtime_substitutes: code_substitutes,
 			},
 		)?;
 
@@ -391,10 +412,11 @@ pub fn new_light_parts<TBl, TRtApi, TExecDisp>(
 		TaskManager::new(config.task_executor.clone(), registry)?
 	};
 
-	let executor = NativeExecutor::<TExecDisp>::new(
+	let executor = NativeExecutor::<TExecDisp>::new_with_cache_path(
 		config.wasm_method,
 		config.default_heap_pages,
 		config.max_runtime_instances,
+		config.wasm_runtime_cache.clone(),
 	);
 
 	let db_storage = {
@@ -453,11 +475,11 @@ pub fn new_client<E, Block, RA>(
 	spawn_handle: Box<dyn SpawnNamed>,
 	prometheus_registry: Option<Registry>,
 	telemetry: Option<TelemetryHandle>,
-	config: ClientConfig,
+	config: ClientConfig<Block>,
 ) -> Result<
 	crate::client::Client<
 		Backend<Block>,
-		crate::client::LocalCallExecutor<Backend<Block>, E>,
+		crate::client::LocalCallExecutor<Block, Backend<Block>, E>,
 		Block,
 		RA,
 	>,
@@ -530,6 +552,7 @@ pub fn build_offchain_workers<TBl, TCl>(
 	if let Some(offchain) = offchain_workers.clone() {
 		spawn_handle.spawn(
 			"offchain-notifications",
+			Some("offchain-worker"),
 			sc_offchain::notification_future(
 				config.role.is_authority(),
 				client.clone(),
@@ -588,6 +611,17 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 		config.dev_key_seed.clone().map(|s| vec![s]).unwrap_or_default(),
 	).map_err(|e| Error::Application(Box::new(e)))?;
 
+	let sysinfo = sc_sysinfo::gather_sysinfo();
+	sc_sysinfo::print_sysinfo(&sysinfo);
+
+	let hwbench = if config.hardware_benchmarks {
+		let hwbench = sc_sysinfo::gather_hwbench(config.database.path());
+		sc_sysinfo::print_hwbench(&hwbench);
+		Some(hwbench)
+	} else {
+		None
+	};
+
 	let telemetry = telemetry
 		.map(|telemetry| {
 			init_telemetry(
@@ -595,6 +629,8 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 				network.clone(),
 				client.clone(),
 				telemetry,
+				sysinfo,
+				hwbench,
 			)
 		})
 		.transpose()?;
@@ -606,11 +642,13 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 	// Inform the tx pool about imported and finalized blocks.
 	spawn_handle.spawn(
 		"txpool-notifications",
+		Some("transaction-pool"),
 		sc_transaction_pool::notification_future(client.clone(), transaction_pool.clone()),
 	);
 
 	spawn_handle.spawn(
 		"on-transaction-imported",
+		Some("transaction-pool"),
 		transaction_notifications(
 			transaction_pool.clone(),
 			network.clone(),
@@ -624,8 +662,12 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 	{
 		// Set static metrics.
 		let metrics = MetricsService::with_prometheus(telemetry.clone(), &registry, &config)?;
+		if let Err(e) = sc_tracing::register_span_metrics(&registry) {
+			log::warn!("Failed to register tracing span metrics: {:?}", e);
+		}
 		spawn_handle.spawn(
 			"prometheus-endpoint",
+			None,
 			prometheus_endpoint::init_prometheus(port, registry).map(drop)
 		);
 
@@ -635,7 +677,7 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 	};
 
 	// Periodically updated metrics and telemetry updates.
-	spawn_handle.spawn("telemetry-periodic-send",
+	spawn_handle.spawn("telemetry-periodic-send", None,
 		metrics_service.run(
 			client.clone(),
 			transaction_pool.clone(),
@@ -644,6 +686,7 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 	);
 
 	// RPC
+	let shutdown_requester = task_manager.shutdown_requester();
 	let gen_handler = |
 		deny_unsafe: sc_rpc::DenyUnsafe,
 		rpc_middleware: sc_rpc_server::RpcMiddleware
@@ -651,10 +694,18 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 		deny_unsafe, rpc_middleware, &config, task_manager.spawn_handle(),
 		client.clone(), transaction_pool.clone(), keystore.clone(),
 		on_demand.clone(), remote_blockchain.clone(), &*rpc_extensions_builder,
-		backend.offchain_storage(), system_rpc_tx.clone()
+		backend.offchain_storage(), system_rpc_tx.clone(), shutdown_requester.clone()
 	);
 	let rpc_metrics = sc_rpc_server::RpcMetrics::new(config.prometheus_registry())?;
-	let rpc = start_rpc_servers(&config, gen_handler, rpc_metrics.clone())?;
+	let server_metrics = sc_rpc_server::ServerMetrics::new(config.prometheus_registry())?;
+	let request_tracker = sc_rpc_server::RequestTracker::new();
+	let rpc = start_rpc_servers(
+		&config,
+		gen_handler,
+		rpc_metrics.clone(),
+		server_metrics,
+		&request_tracker,
+	)?;
 	// This is used internally, so don't restrict access to unsafe RPC
 	let rpc_handlers = RpcHandlers(Arc::new(gen_handler(
 		sc_rpc::DenyUnsafe::No,
@@ -662,14 +713,29 @@ pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 	).into()));
 
 	// Spawn informant task
-	spawn_handle.spawn("informant", sc_informant::build(
+	spawn_handle.spawn("informant", None, sc_informant::build(
 		client.clone(),
 		network_status_sinks.status.clone(),
 		transaction_pool.clone(),
 		config.informant_output_format,
 	));
 
-	task_manager.keep_alive((config.base_path, rpc, rpc_handlers.clone()));
+	// Spawn the storage monitor as an essential task, so that the node is stopped
+	// before the database runs out of disk space.
+	if let Some(storage_monitor) = config.database.path()
+		.and_then(|path| sc_storage_monitor::build(config.storage_monitor.clone(), path.to_owned()))
+	{
+		task_manager.spawn_essential_handle().spawn("storage-monitor", None, storage_monitor);
+	}
+
+	// The RPC servers are stopped before any task, so that the requests being processed can
+	// still complete.
+	task_manager.add_shutdown_hook(stop_rpc_servers(
+		rpc,
+		request_tracker,
+		config.rpc_shutdown_timeout.unwrap_or(DEFAULT_RPC_SHUTDOWN_TIMEOUT),
+	));
+	task_manager.keep_alive((config.base_path, rpc_handlers.clone()));
 
 	Ok(rpc_handlers)
 }
@@ -706,6 +772,8 @@ fn init_telemetry<TBl: BlockT, TCl: BlockBackend<TBl>>(
 	network: Arc<NetworkService<TBl, <TBl as BlockT>::Hash>>,
 	client: Arc<TCl>,
 	telemetry: &mut Telemetry,
+	sysinfo: sc_telemetry::SysInfo,
+	hwbench: Option<sc_telemetry::HwBench>,
 ) -> sc_telemetry::Result<TelemetryHandle> {
 	let genesis_hash = client.block_hash(Zero::zero()).ok().flatten().unwrap_or_default();
 	let connection_message = ConnectionMessage {
@@ -720,6 +788,8 @@ fn init_telemetry<TBl: BlockT, TCl: BlockBackend<TBl>>(
 			.map(|dur| dur.as_millis())
 			.unwrap_or(0).to_string(),
 		network_id: network.local_peer_id().to_base58(),
+		sysinfo: Some(sysinfo),
+		hwbench,
 	};
 
 	telemetry.start_telemetry(connection_message)?;
@@ -739,7 +809,8 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 	remote_blockchain: Option<Arc<dyn RemoteBlockchain<TBl>>>,
 	rpc_extensions_builder: &(dyn RpcExtensionBuilder<Output = TRpc> + Send),
 	offchain_storage: Option<<TBackend as sc_client_api::backend::Backend<TBl>>::OffchainStorage>,
-	system_rpc_tx: TracingUnboundedSender<sc_rpc::system::Request<TBl>>
+	system_rpc_tx: TracingUnboundedSender<sc_rpc::system::Request<TBl>>,
+	shutdown_requester: TracingUnboundedSender<()>,
 ) -> sc_rpc_server::RpcHandler<sc_rpc::Metadata>
 	where
 		TBl: BlockT,
@@ -754,7 +825,7 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 			sp_session::SessionKeys<TBl> +
 			sp_api::Metadata<TBl>,
 {
-	use sc_rpc::{chain, state, author, system, offchain};
+	use sc_rpc::{archive, chain, chain_spec, state, author, system, offchain};
 
 	let system_info = sc_rpc::system::SystemInfo {
 		chain_name: config.chain_spec.name().into(),
@@ -767,8 +838,10 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 	let task_executor = sc_rpc::SubscriptionTaskExecutor::new(spawn_handle);
 	let subscriptions = SubscriptionManager::new(Arc::new(task_executor.clone()));
 
-	let (chain, state, child_state) = if let (Some(remote_blockchain), Some(on_demand)) =
-		(remote_blockchain, on_demand) {
+	let (chain, state, child_state, maybe_archive_rpc) = if let (
+		Some(remote_blockchain),
+		Some(on_demand),
+	) = (remote_blockchain, on_demand) {
 		// Light clients
 		let chain = sc_rpc::chain::new_light(
 			client.clone(),
@@ -783,7 +856,7 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 			on_demand,
 			deny_unsafe,
 		);
-		(chain, state, child_state)
+		(chain, state, child_state, None)
 
 	} else {
 		// Full nodes
@@ -793,9 +866,16 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 			subscriptions.clone(),
 			deny_unsafe,
 		);
-		(chain, state, child_state)
+		let archive = archive::ArchiveApi::to_delegate(archive::Archive::new(client.clone()));
+		(chain, state, child_state, Some(archive))
 	};
 
+	let chain_spec = chain_spec::ChainSpec::new(
+		config.chain_spec.name().into(),
+		client.info().genesis_hash,
+		config.chain_spec.properties(),
+	);
+
 	let author = sc_rpc::author::Author::new(
 		client,
 		transaction_pool,
@@ -803,7 +883,8 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 		keystore,
 		deny_unsafe,
 	);
-	let system = system::System::new(system_info, system_rpc_tx, deny_unsafe);
+	let system = system::System::new(system_info, system_rpc_tx, deny_unsafe)
+		.with_shutdown(shutdown_requester);
 
 	let maybe_offchain_rpc = offchain_storage.map(|storage| {
 		let offchain = sc_rpc::offchain::Offchain::new(storage, deny_unsafe);
@@ -815,7 +896,9 @@ fn gen_handler<TBl, TBackend, TExPool, TRpc, TCl>(
 			state::StateApi::to_delegate(state),
 			state::ChildStateApi::to_delegate(child_state),
 			chain::ChainApi::to_delegate(chain),
+			chain_spec::ChainSpecApi::to_delegate(chain_spec),
 			maybe_offchain_rpc,
+			maybe_archive_rpc,
 			author::AuthorApi::to_delegate(author),
 			system::SystemApi::to_delegate(system),
 			rpc_extensions_builder.build(deny_unsafe, task_executor),
@@ -895,7 +978,24 @@ pub fn build_network<TBl, TExPool, TImpQu, TCl>(
 				config.network.default_peers_set.in_peers as usize
 					+ config.network.default_peers_set.out_peers as usize,
 			);
-			spawn_handle.spawn("block_request_handler", handler.run());
+			spawn_handle.spawn("block_request_handler", Some(NETWORKING_GROUP), handler.run());
+			protocol_config
+		}
+	};
+
+	let state_request_protocol_config = {
+		if matches!(config.role, Role::Light) {
+			// Allow outgoing requests but deny incoming requests.
+			state_request_handler::generate_protocol_config(&protocol_id)
+		} else {
+			// Allow both outgoing and incoming requests.
+			let (handler, protocol_config) = StateRequestHandler::new(
+				&protocol_id,
+				client.clone(),
+				config.network.default_peers_set.in_peers as usize
+					+ config.network.default_peers_set.out_peers as usize,
+			);
+			spawn_handle.spawn("state_request_handler", Some(NETWORKING_GROUP), handler.run());
 			protocol_config
 		}
 	};
@@ -910,7 +1010,7 @@ pub fn build_network<TBl, TExPool, TImpQu, TCl>(
 				&protocol_id,
 				client.clone(),
 			);
-			spawn_handle.spawn("light_client_request_handler", handler.run());
+			spawn_handle.spawn("light_client_request_handler", Some(NETWORKING_GROUP), handler.run());
 			protocol_config
 		}
 	};
@@ -920,13 +1020,13 @@ pub fn build_network<TBl, TExPool, TImpQu, TCl>(
 		executor: {
 			let spawn_handle = Clone::clone(&spawn_handle);
 			Some(Box::new(move |fut| {
-				spawn_handle.spawn("libp2p-node", fut);
+				spawn_handle.spawn("libp2p-node", Some(NETWORKING_GROUP), fut);
 			}))
 		},
 		transactions_handler_executor: {
 			let spawn_handle = Clone::clone(&spawn_handle);
 			Box::new(move |fut| {
-				spawn_handle.spawn("network-transactions-handler", fut);
+				spawn_handle.spawn("network-transactions-handler", Some(NETWORKING_GROUP), fut);
 			})
 		},
 		network_config: config.network.clone(),
@@ -938,6 +1038,7 @@ pub fn build_network<TBl, TExPool, TImpQu, TCl>(
 		block_announce_validator,
 		metrics_registry: config.prometheus_config.as_ref().map(|config| config.registry.clone()),
 		block_request_protocol_config,
+		state_request_protocol_config,
 		light_client_request_protocol_config,
 	};
 
@@ -981,7 +1082,7 @@ pub fn build_network<TBl, TExPool, TImpQu, TCl>(
 	// issue, and ideally we would like to fix the network future to take as little time as
 	// possible, but we also take the extra harm-prevention measure to execute the networking
 	// future using `spawn_blocking`.
-	spawn_handle.spawn_blocking("network-worker", async move {
+	spawn_handle.spawn_blocking("network-worker", Some(NETWORKING_GROUP), async move {
 		if network_start_rx.await.is_err() {
 			debug_assert!(false);
 			log::warn!(
@@ -22,10 +22,14 @@ mod check_block;
 mod export_blocks;
 mod export_raw_state;
 mod import_blocks;
+mod purge_offchain;
+mod reindex_offchain;
 mod revert_chain;
 
 pub use check_block::*;
 pub use export_blocks::*;
 pub use export_raw_state::*;
 pub use import_blocks::*;
+pub use purge_offchain::*;
+pub use reindex_offchain::*;
 pub use revert_chain::*;
@@ -0,0 +1,44 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error::Error;
+use log::info;
+use sp_core::offchain::OffchainStorage;
+use sp_runtime::traits::Block as BlockT;
+use sc_client_api::Backend;
+use std::sync::Arc;
+
+/// Removes the given keys from the persistent offchain storage.
+///
+/// Offchain workers keep their state under well-known keys, so this allows to reset the state of
+/// a single worker, e.g. to make it recompute a cached result.
+pub fn purge_offchain<B, BA>(backend: Arc<BA>, keys: &[Vec<u8>]) -> Result<(), Error>
+where
+	B: BlockT,
+	BA: Backend<B>,
+{
+	let mut offchain_storage = backend.offchain_storage()
+		.ok_or_else(|| Error::Other("Offchain storage is not available".into()))?;
+
+	for key in keys {
+		offchain_storage.remove(sc_offchain::STORAGE_PREFIX, key);
+	}
+
+	info!("Removed {} keys from the offchain storage", keys.len());
+	Ok(())
+}
@@ -0,0 +1,86 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error::Error;
+use log::info;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainOverlayedChange, OffchainStorage};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One};
+use sc_client_api::{Backend, BlockBackend};
+use std::sync::Arc;
+
+/// Re-executes the blocks `from..=to` and writes the values they index through the offchain
+/// indexing API into the offchain database.
+///
+/// This allows to populate the offchain database of a node that was synced without
+/// `--enable-offchain-indexing`. The state of the parent of every re-executed block has to be
+/// available.
+pub fn reindex_offchain<B, BA, C>(
+	client: Arc<C>,
+	backend: Arc<BA>,
+	from: NumberFor<B>,
+	to: NumberFor<B>,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B>,
+	C::Api: Core<B> + ApiExt<B, StateBackend = BA::State>,
+{
+	let mut offchain_storage = backend.offchain_storage()
+		.ok_or_else(|| Error::Other("Offchain storage is not available".into()))?;
+
+	let mut number = from;
+	while number <= to {
+		let hash = client.hash(number)?
+			.ok_or_else(|| Error::Other(format!("Unknown block #{}", number)))?;
+		let (mut header, extrinsics) = client.block(&BlockId::Hash(hash))?
+			.ok_or_else(|| Error::Other(format!("Missing body of block #{}", number)))?
+			.block
+			.deconstruct();
+
+		// The seal is added after the execution of the block.
+		if header.digest().logs().last().map_or(false, |item| item.as_seal().is_some()) {
+			header.digest_mut().pop();
+		}
+
+		let parent_hash = *header.parent_hash();
+		let runtime_api = client.runtime_api();
+		runtime_api.execute_block(&BlockId::Hash(parent_hash), B::new(header, extrinsics))
+			.map_err(sp_blockchain::Error::from)?;
+
+		let state = backend.state_at(BlockId::Hash(parent_hash))?;
+		let changes = runtime_api.into_storage_changes(&state, None, parent_hash)
+			.map_err(sp_blockchain::Error::Storage)?;
+
+		for ((prefix, key), change) in changes.offchain_storage_changes {
+			match change {
+				OffchainOverlayedChange::SetValue(value) =>
+					offchain_storage.set(&prefix, &key, &value),
+				OffchainOverlayedChange::Remove => offchain_storage.remove(&prefix, &key),
+			}
+		}
+
+		number += One::one();
+	}
+
+	info!("Reindexed the offchain data of blocks #{} to #{}", from, to);
+	Ok(())
+}
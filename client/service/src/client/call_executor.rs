@@ -32,82 +32,93 @@ use sp_core::{
 };
 use sp_api::{ProofRecorder, InitializeBlock, StorageTransactionCache};
 use sc_client_api::{backend, call_executor::CallExecutor};
-use super::{client::ClientConfig, wasm_override::WasmOverride};
+use super::{client::ClientConfig, wasm_override::WasmOverride, wasm_substitutes::WasmSubstitutes};
 
 /// Call executor that executes methods locally, querying all required
 /// data from local backend.
-pub struct LocalCallExecutor<B, E> {
+pub struct LocalCallExecutor<Block: BlockT, B, E> {
 	backend: Arc<B>,
 	executor: E,
 	wasm_override: Option<WasmOverride<E>>,
+	wasm_substitutes: WasmSubstitutes<Block, E, B>,
 	spawn_handle: Box<dyn SpawnNamed>,
-	client_config: ClientConfig,
+	client_config: ClientConfig<Block>,
 }
 
-impl<B, E> LocalCallExecutor<B, E>
+impl<Block: BlockT, B, E> LocalCallExecutor<Block, B, E>
 where
-	E: CodeExecutor + RuntimeInfo + Clone + 'static
+	E: CodeExecutor + RuntimeInfo + Clone + 'static,
+	B: backend::Backend<Block>,
 {
 	/// Creates new instance of local call executor.
 	pub fn new(
 		backend: Arc<B>,
 		executor: E,
 		spawn_handle: Box<dyn SpawnNamed>,
-		client_config: ClientConfig,
+		client_config: ClientConfig<Block>,
 	) -> sp_blockchain::Result<Self> {
 		let wasm_override = client_config.wasm_runtime_overrides
 			.as_ref()
 			.map(|p| WasmOverride::new(p.clone(), executor.clone()))
 			.transpose()?;
 
+		let wasm_substitutes = WasmSubstitutes::new(
+			client_config.wasm_runtime_substitutes.clone(),
+			executor.clone(),
+			backend.clone(),
+		)?;
+
 		Ok(LocalCallExecutor {
 			backend,
 			executor,
 			wasm_override,
+			wasm_substitutes,
 			spawn_handle,
 			client_config,
 		})
 	}
 
 	/// Check if local runtime code overrides are enabled and one is available
-	/// for the given `BlockId`. If yes, return it; otherwise return the same
-	/// `RuntimeCode` instance that was passed.
-	fn check_override<'a, Block>(
+	/// for the given `BlockId`. If yes, return it; otherwise check if a substitute
+	/// is registered for the on-chain runtime at the given `BlockId`. If neither
+	/// applies, return the same `RuntimeCode` instance that was passed.
+	fn check_override<'a>(
 		&'a self,
 		onchain_code: RuntimeCode<'a>,
 		id: &BlockId<Block>,
-	) -> sp_blockchain::Result<RuntimeCode<'a>>
-	where
-		Block: BlockT,
-		B: backend::Backend<Block>,
-	{
-		let code = self.wasm_override
+	) -> sp_blockchain::Result<RuntimeCode<'a>> {
+		let spec = self.runtime_version(id)?.spec_version;
+		let code = if let Some(d) = self.wasm_override
 			.as_ref()
-			.map::<sp_blockchain::Result<Option<RuntimeCode>>, _>(|o| {
-				let spec = self.runtime_version(id)?.spec_version;
-				Ok(o.get(&spec, onchain_code.heap_pages))
-			})
-			.transpose()?
+			.map(|o| o.get(&spec, onchain_code.heap_pages))
 			.flatten()
-			.unwrap_or(onchain_code);
+		{
+			d
+		} else if let Some(s) = self.wasm_substitutes.get(spec, onchain_code.heap_pages, id) {
+			log::debug!(target: "wasm_substitutes", "Using WASM substitute for block {:?}", id);
+			s
+		} else {
+			onchain_code
+		};
 
 		Ok(code)
 	}
 }
 
-impl<B, E> Clone for LocalCallExecutor<B, E> where E: Clone {
+impl<Block: BlockT, B, E> Clone for LocalCallExecutor<Block, B, E> where E: Clone {
 	fn clone(&self) -> Self {
 		LocalCallExecutor {
 			backend: self.backend.clone(),
 			executor: self.executor.clone(),
 			wasm_override: self.wasm_override.clone(),
+			wasm_substitutes: self.wasm_substitutes.clone(),
 			spawn_handle: self.spawn_handle.clone(),
 			client_config: self.client_config.clone(),
 		}
 	}
 }
 
-impl<B, E, Block> CallExecutor<Block> for LocalCallExecutor<B, E>
+impl<B, E, Block> CallExecutor<Block> for LocalCallExecutor<Block, B, E>
 where
 	B: backend::Backend<Block>,
 	E: CodeExecutor + RuntimeInfo + Clone + 'static,
@@ -305,7 +316,7 @@ where
 	}
 }
 
-impl<B, E, Block> sp_version::GetRuntimeVersion<Block> for LocalCallExecutor<B, E>
+impl<B, E, Block> sp_version::GetRuntimeVersion<Block> for LocalCallExecutor<Block, B, E>
 	where
 		B: backend::Backend<Block>,
 		E: CodeExecutor + RuntimeInfo + Clone + 'static,
@@ -352,6 +363,7 @@ mod tests {
 			offchain_worker_enabled: false,
 			offchain_indexing_api: false,
 			wasm_runtime_overrides: None,
+			wasm_runtime_substitutes: Default::default(),
 		};
 
 		// client is used for the convenience of creating and inserting the genesis block.
@@ -374,8 +386,13 @@ mod tests {
 
 		let call_executor = LocalCallExecutor {
 			backend: backend.clone(),
-			executor,
+			executor: executor.clone(),
 			wasm_override: Some(overrides),
+			wasm_substitutes: WasmSubstitutes::new(
+				Default::default(),
+				executor.clone(),
+				backend.clone(),
+			).unwrap(),
 			spawn_handle: Box::new(TaskExecutor::new()),
 			client_config,
 		};
@@ -36,6 +36,10 @@
 //! required to overrides multiple runtimes, multiple WASM blobs matching each of the spec versions
 //! needed must be provided in the given directory.
 //!
+//! Overrides only take effect where the runtime is executed as WASM, which is the default for all
+//! execution contexts unless the node runs with `--native-fallback` or a native `--execution`
+//! strategy.
+//!
 use std::{
 	fs, collections::{HashMap, hash_map::DefaultHasher},
 	path::{Path, PathBuf},
@@ -27,11 +27,16 @@ pub use sc_network::config::{ExtTransport, MultiaddrWithPeerId, NetworkConfigura
 pub use sc_executor::WasmExecutionMethod;
 use sc_client_api::execution_extensions::ExecutionStrategies;
 
-use std::{io, future::Future, path::{PathBuf, Path}, pin::Pin, net::SocketAddr, sync::Arc};
+use std::{
+	io, future::Future, path::{PathBuf, Path}, pin::Pin, net::SocketAddr, sync::Arc, time::Duration,
+};
 pub use sc_transaction_pool::txpool::Options as TransactionPoolOptions;
 use sc_chain_spec::ChainSpec;
 use sp_core::crypto::SecretString;
 pub use sc_telemetry::TelemetryEndpoints;
+pub use sc_storage_monitor::StorageMonitorParams;
+pub use sc_informant::{OutputFormat, StatusLineExtension};
+pub use sc_rpc_server::RpcPolicy;
 use prometheus_endpoint::Registry;
 #[cfg(not(target_os = "unknown"))]
 use tempfile::TempDir;
@@ -75,6 +80,10 @@ pub struct Configuration {
 	/// over on-chain runtimes when the spec version matches. Set to `None` to
 	/// disable overrides (default).
 	pub wasm_runtime_overrides: Option<PathBuf>,
+	/// Directory where the compiled artifacts of WASM runtimes are cached, so they don't need to
+	/// be compiled again after a restart. Only used by the compiled execution method. Set to
+	/// `None` to disable the cache.
+	pub wasm_runtime_cache: Option<PathBuf>,
 	/// Execution strategies.
 	pub execution_strategies: ExecutionStrategies,
 	/// RPC over HTTP binding address. `None` if disabled.
@@ -89,6 +98,10 @@ pub struct Configuration {
 	pub rpc_cors: Option<Vec<String>>,
 	/// RPC methods to expose (by default only a safe subset or all of them).
 	pub rpc_methods: RpcMethods,
+	/// Access control and limits enforced by the HTTP, WS and IPC RPC servers.
+	pub rpc_policy: RpcPolicy,
+	/// How long the RPC requests being processed are awaited for on shutdown. `None` if default.
+	pub rpc_shutdown_timeout: Option<Duration>,
 	/// Prometheus endpoint configuration. `None` if disabled.
 	pub prometheus_config: Option<PrometheusConfig>,
 	/// Telemetry service URL. `None` if disabled.
@@ -125,7 +138,11 @@ pub struct Configuration {
 	/// Base path of the configuration
 	pub base_path: Option<BasePath>,
 	/// Configuration of the output format that the informant uses.
-	pub informant_output_format: sc_informant::OutputFormat,
+	pub informant_output_format: OutputFormat,
+	/// Configuration of the storage monitor.
+	pub storage_monitor: StorageMonitorParams,
+	/// Run the hardware benchmarks at startup and report their results to telemetry.
+	pub hardware_benchmarks: bool,
 }
 
 /// Type for tasks spawned by the executor.
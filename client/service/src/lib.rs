@@ -77,7 +77,7 @@ pub use sc_network::config::{
 	TransactionImportFuture,
 };
 pub use sc_tracing::TracingReceiver;
-pub use task_manager::SpawnTaskHandle;
+pub use task_manager::{GroupName, SpawnTaskHandle, NETWORKING_GROUP};
 pub use task_manager::TaskManager;
 pub use sp_consensus::import_queue::ImportQueue;
 pub use self::client::{LocalCallExecutor, ClientConfig};
@@ -85,6 +85,11 @@ use sc_client_api::{blockchain::HeaderBackend, BlockchainEvents};
 
 const DEFAULT_PROTOCOL_ID: &str = "sup";
 
+/// How long the RPC requests being processed are awaited for on shutdown by default.
+const DEFAULT_RPC_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often the RPC requests being processed are checked while draining them on shutdown.
+const RPC_DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 /// A type that implements `MallocSizeOf` on native but not wasm.
 #[cfg(not(target_os = "unknown"))]
 pub trait MallocSizeOfWasm: MallocSizeOf {}
@@ -388,6 +393,8 @@ fn start_rpc_servers<
 	config: &Configuration,
 	mut gen_handler: H,
 	rpc_metrics: sc_rpc_server::RpcMetrics,
+	server_metrics: sc_rpc_server::ServerMetrics,
+	request_tracker: &sc_rpc_server::RequestTracker,
 ) -> Result<Box<dyn std::any::Any + Send + Sync>, error::Error> {
 	fn maybe_start_server<T, F>(address: Option<SocketAddr>, mut start: F) -> Result<Option<T>, io::Error>
 		where F: FnMut(&SocketAddr) -> Result<T, io::Error>,
@@ -419,6 +426,8 @@ fn start_rpc_servers<
 			&*path, gen_handler(
 				sc_rpc::DenyUnsafe::No,
 				sc_rpc_server::RpcMiddleware::new(rpc_metrics.clone(), "ipc")
+					.with_policy(config.rpc_policy.clone())
+					.with_request_tracker(request_tracker.clone())
 			)
 		)),
 		maybe_start_server(
@@ -429,6 +438,8 @@ fn start_rpc_servers<
 				gen_handler(
 					deny_unsafe(&address, &config.rpc_methods),
 					sc_rpc_server::RpcMiddleware::new(rpc_metrics.clone(), "http")
+						.with_policy(config.rpc_policy.clone())
+						.with_request_tracker(request_tracker.clone())
 				),
 			),
 		)?.map(|s| waiting::HttpServer(Some(s))),
@@ -441,7 +452,10 @@ fn start_rpc_servers<
 				gen_handler(
 					deny_unsafe(&address, &config.rpc_methods),
 					sc_rpc_server::RpcMiddleware::new(rpc_metrics.clone(), "ws")
+						.with_policy(config.rpc_policy.clone())
+						.with_request_tracker(request_tracker.clone())
 				),
+				server_metrics.clone(),
 			),
 		)?.map(|s| waiting::WsServer(Some(s))),
 	)))
@@ -456,10 +470,38 @@ fn start_rpc_servers<
 	_: &Configuration,
 	_: H,
 	_: sc_rpc_server::RpcMetrics,
+	_: sc_rpc_server::ServerMetrics,
+	_: &sc_rpc_server::RequestTracker,
 ) -> Result<Box<dyn std::any::Any + Send + Sync>, error::Error> {
 	Ok(Box::new(()))
 }
 
+/// Stops the RPC servers returned by `start_rpc_servers`.
+///
+/// The requests received from now on are rejected, the ones being processed are awaited for at
+/// most `timeout`, then the servers are closed.
+async fn stop_rpc_servers(
+	servers: Box<dyn std::any::Any + Send + Sync>,
+	request_tracker: sc_rpc_server::RequestTracker,
+	timeout: Duration,
+) {
+	request_tracker.close();
+	let drained = async {
+		while request_tracker.in_flight() > 0 {
+			futures_timer::Delay::new(RPC_DRAIN_POLL_INTERVAL).await;
+		}
+	};
+	futures::pin_mut!(drained);
+	let timeout = futures_timer::Delay::new(timeout);
+	if let futures::future::Either::Right(_) = futures::future::select(drained, timeout).await {
+		warn!(
+			"{} RPC requests were still being processed when closing the RPC servers",
+			request_tracker.in_flight(),
+		);
+	}
+	drop(servers);
+}
+
 /// An RPC session. Used to perform in-memory RPC queries (ie. RPC queries that don't go through
 /// the HTTP or WebSockets server).
 #[derive(Clone)]
@@ -18,18 +18,22 @@
 
 //! Substrate service tasks management module.
 
-use std::{panic, result::Result, pin::Pin};
+use std::{
+	collections::HashMap, fmt, mem, panic, result::Result, pin::Pin, sync::Arc, time::Duration,
+};
 use exit_future::Signal;
-use log::{debug, error};
+use log::{debug, error, warn};
 use futures::{
 	Future, FutureExt, StreamExt,
-	future::{select, Either, BoxFuture, join_all, try_join_all, pending},
+	future::{select, join, Either, BoxFuture, join_all, try_join_all, pending},
 	sink::SinkExt,
 };
+use futures_timer::Delay;
+use parking_lot::Mutex;
 use prometheus_endpoint::{
 	exponential_buckets, register,
 	PrometheusError,
-	CounterVec, HistogramOpts, HistogramVec, Opts, Registry, U64
+	CounterVec, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry, U64
 };
 use sp_utils::mpsc::{TracingUnboundedSender, TracingUnboundedReceiver, tracing_unbounded};
 use tracing_futures::Instrument;
@@ -39,62 +43,177 @@ mod prometheus_future;
 #[cfg(test)]
 mod tests;
 
+/// How long [`TaskManager::clean_shutdown`] waits for the tasks to finish by default.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The group of the tasks that are terminated last on shutdown, once all the other tasks have
+/// finished, so that those can still use the network while they are stopping.
+pub const NETWORKING_GROUP: &str = "networking";
+
+/// The group a task belongs to.
+///
+/// Like task names, group names are reported to the Prometheus endpoint (if enabled), so the set
+/// of possible group names must be bounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupName {
+	/// The group of the tasks that were not given one.
+	Default,
+	/// The group with the given name.
+	Specific(&'static str),
+}
+
+impl GroupName {
+	/// The name of the group, as reported in the metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			GroupName::Default => "default",
+			GroupName::Specific(name) => name,
+		}
+	}
+}
+
+impl fmt::Display for GroupName {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl From<Option<&'static str>> for GroupName {
+	fn from(name: Option<&'static str>) -> Self {
+		name.map_or(GroupName::Default, GroupName::Specific)
+	}
+}
+
+impl From<&'static str> for GroupName {
+	fn from(name: &'static str) -> Self {
+		GroupName::Specific(name)
+	}
+}
+
+/// The number of running tasks, by task name and group.
+type RunningTasks = Arc<Mutex<HashMap<(&'static str, GroupName), usize>>>;
+
+/// Counts a task as running for as long as it is alive.
+struct RunningTaskGuard {
+	running_tasks: RunningTasks,
+	key: (&'static str, GroupName),
+	tasks_active: Option<Gauge<U64>>,
+}
+
+impl RunningTaskGuard {
+	fn new(
+		running_tasks: RunningTasks,
+		key: (&'static str, GroupName),
+		tasks_active: Option<Gauge<U64>>,
+	) -> Self {
+		*running_tasks.lock().entry(key).or_default() += 1;
+		if let Some(tasks_active) = &tasks_active {
+			tasks_active.inc();
+		}
+		RunningTaskGuard { running_tasks, key, tasks_active }
+	}
+}
+
+impl Drop for RunningTaskGuard {
+	fn drop(&mut self) {
+		let mut running_tasks = self.running_tasks.lock();
+		if let Some(count) = running_tasks.get_mut(&self.key) {
+			*count -= 1;
+			if *count == 0 {
+				running_tasks.remove(&self.key);
+			}
+		}
+		if let Some(tasks_active) = &self.tasks_active {
+			tasks_active.dec();
+		}
+	}
+}
+
 /// An handle for spawning tasks in the service.
 #[derive(Clone)]
 pub struct SpawnTaskHandle {
 	on_exit: exit_future::Exit,
+	network_on_exit: exit_future::Exit,
 	executor: TaskExecutor,
 	metrics: Option<Metrics>,
 	task_notifier: TracingUnboundedSender<JoinFuture>,
+	network_task_notifier: TracingUnboundedSender<JoinFuture>,
+	running_tasks: RunningTasks,
 }
 
 impl SpawnTaskHandle {
-	/// Spawns the given task with the given name.
+	/// Spawns the given task with the given name, as part of the given group.
 	///
-	/// Note that the `name` is a `&'static str`. The reason for this choice is that statistics
-	/// about this task are getting reported to the Prometheus endpoint (if enabled), and that
-	/// therefore the set of possible task names must be bounded.
+	/// Note that the `name` and the `group` are `&'static str`s. The reason for this choice is
+	/// that statistics about this task are getting reported to the Prometheus endpoint (if
+	/// enabled), and that therefore the set of possible task names must be bounded.
 	///
 	/// In other words, it would be a bad idea for someone to do for example
-	/// `spawn(format!("{:?}", some_public_key))`.
-	pub fn spawn(&self, name: &'static str, task: impl Future<Output = ()> + Send + 'static) {
-		self.spawn_inner(name, task, TaskType::Async)
+	/// `spawn(format!("{:?}", some_public_key), None, task)`.
+	pub fn spawn(
+		&self,
+		name: &'static str,
+		group: impl Into<GroupName>,
+		task: impl Future<Output = ()> + Send + 'static,
+	) {
+		self.spawn_inner(name, group.into(), task, TaskType::Async)
 	}
 
-	/// Spawns the blocking task with the given name. See also `spawn`.
-	pub fn spawn_blocking(&self, name: &'static str, task: impl Future<Output = ()> + Send + 'static) {
-		self.spawn_inner(name, task, TaskType::Blocking)
+	/// Spawns the blocking task with the given name, as part of the given group. See also `spawn`.
+	pub fn spawn_blocking(
+		&self,
+		name: &'static str,
+		group: impl Into<GroupName>,
+		task: impl Future<Output = ()> + Send + 'static,
+	) {
+		self.spawn_inner(name, group.into(), task, TaskType::Blocking)
 	}
 
 	/// Helper function that implements the spawning logic. See `spawn` and `spawn_blocking`.
 	fn spawn_inner(
 		&self,
 		name: &'static str,
+		group: GroupName,
 		task: impl Future<Output = ()> + Send + 'static,
 		task_type: TaskType,
 	) {
-		if self.task_notifier.is_closed() {
+		// The networking tasks are terminated separately, see `TaskManager::clean_shutdown`.
+		let (on_exit, task_notifier) = if group == GroupName::Specific(NETWORKING_GROUP) {
+			(&self.network_on_exit, &self.network_task_notifier)
+		} else {
+			(&self.on_exit, &self.task_notifier)
+		};
+
+		if task_notifier.is_closed() {
 			debug!("Attempt to spawn a new task has been prevented: {}", name);
 			return;
 		}
 
-		let on_exit = self.on_exit.clone();
+		let on_exit = on_exit.clone();
 		let metrics = self.metrics.clone();
+		let running_tasks = self.running_tasks.clone();
+		let group_name = group.as_str();
 
 		// Note that we increase the started counter here and not within the future. This way,
 		// we could properly visualize on Prometheus situations where the spawning doesn't work.
 		if let Some(metrics) = &self.metrics {
-			metrics.tasks_spawned.with_label_values(&[name]).inc();
+			metrics.tasks_spawned.with_label_values(&[name, group_name]).inc();
 			// We do a dummy increase in order for the task to show up in metrics.
-			metrics.tasks_ended.with_label_values(&[name, "finished"]).inc_by(0);
+			metrics.tasks_ended.with_label_values(&[name, "finished", group_name]).inc_by(0);
 		}
 
 		let future = async move {
+			let _running = RunningTaskGuard::new(
+				running_tasks,
+				(name, group),
+				metrics.as_ref().map(|m| m.tasks_active.with_label_values(&[name, group_name])),
+			);
+
 			if let Some(metrics) = metrics {
 				// Add some wrappers around `task`.
 				let task = {
-					let poll_duration = metrics.poll_duration.with_label_values(&[name]);
-					let poll_start = metrics.poll_start.with_label_values(&[name]);
+					let poll_duration = metrics.poll_duration.with_label_values(&[name, group_name]);
+					let poll_start = metrics.poll_start.with_label_values(&[name, group_name]);
 					let inner = prometheus_future::with_poll_durations(poll_duration, poll_start, task);
 					// The logic of `AssertUnwindSafe` here is ok considering that we throw
 					// away the `Future` after it has panicked.
@@ -104,15 +223,17 @@ impl SpawnTaskHandle {
 
 				match select(on_exit, task).await {
 					Either::Right((Err(payload), _)) => {
-						metrics.tasks_ended.with_label_values(&[name, "panic"]).inc();
+						metrics.tasks_ended.with_label_values(&[name, "panic", group_name]).inc();
 						panic::resume_unwind(payload)
 					}
 					Either::Right((Ok(()), _)) => {
-						metrics.tasks_ended.with_label_values(&[name, "finished"]).inc();
+						metrics.tasks_ended.with_label_values(&[name, "finished", group_name]).inc();
 					}
 					Either::Left(((), _)) => {
 						// The `on_exit` has triggered.
-						metrics.tasks_ended.with_label_values(&[name, "interrupted"]).inc();
+						metrics.tasks_ended
+							.with_label_values(&[name, "interrupted", group_name])
+							.inc();
 					}
 				}
 
@@ -124,7 +245,7 @@ impl SpawnTaskHandle {
 
 		let join_handle = self.executor.spawn(future.in_current_span().boxed(), task_type);
 
-		let mut task_notifier = self.task_notifier.clone();
+		let mut task_notifier = task_notifier.clone();
 		self.executor.spawn(
 			Box::pin(async move {
 				if let Err(err) = task_notifier.send(join_handle).await {
@@ -138,28 +259,28 @@ impl SpawnTaskHandle {
 
 impl sp_core::traits::SpawnNamed for SpawnTaskHandle {
 	fn spawn_blocking(&self, name: &'static str, future: BoxFuture<'static, ()>) {
-		self.spawn_blocking(name, future);
+		self.spawn_blocking(name, None, future);
 	}
 
 	fn spawn(&self, name: &'static str, future: BoxFuture<'static, ()>) {
-		self.spawn(name, future);
+		self.spawn(name, None, future);
 	}
 }
 
 /// A wrapper over `SpawnTaskHandle` that will notify a receiver whenever any
-/// task spawned through it fails. The service should be on the receiver side
+/// task spawned through it ends. The service should be on the receiver side
 /// and will shut itself down whenever it receives any message, i.e. an
-/// essential task has failed.
+/// essential task has failed. The message describes which task failed and how.
 #[derive(Clone)]
 pub struct SpawnEssentialTaskHandle {
-	essential_failed_tx: TracingUnboundedSender<()>,
+	essential_failed_tx: TracingUnboundedSender<String>,
 	inner: SpawnTaskHandle,
 }
 
 impl SpawnEssentialTaskHandle {
 	/// Creates a new `SpawnEssentialTaskHandle`.
 	pub fn new(
-		essential_failed_tx: TracingUnboundedSender<()>,
+		essential_failed_tx: TracingUnboundedSender<String>,
 		spawn_task_handle: SpawnTaskHandle,
 	) -> SpawnEssentialTaskHandle {
 		SpawnEssentialTaskHandle {
@@ -168,49 +289,61 @@ impl SpawnEssentialTaskHandle {
 		}
 	}
 
-	/// Spawns the given task with the given name.
+	/// Spawns the given task with the given name, as part of the given group.
 	///
 	/// See also [`SpawnTaskHandle::spawn`].
-	pub fn spawn(&self, name: &'static str, task: impl Future<Output = ()> + Send + 'static) {
-		self.spawn_inner(name, task, TaskType::Async)
+	pub fn spawn(
+		&self,
+		name: &'static str,
+		group: impl Into<GroupName>,
+		task: impl Future<Output = ()> + Send + 'static,
+	) {
+		self.spawn_inner(name, group.into(), task, TaskType::Async)
 	}
 
-	/// Spawns the blocking task with the given name.
+	/// Spawns the blocking task with the given name, as part of the given group.
 	///
 	/// See also [`SpawnTaskHandle::spawn_blocking`].
 	pub fn spawn_blocking(
 		&self,
 		name: &'static str,
+		group: impl Into<GroupName>,
 		task: impl Future<Output = ()> + Send + 'static,
 	) {
-		self.spawn_inner(name, task, TaskType::Blocking)
+		self.spawn_inner(name, group.into(), task, TaskType::Blocking)
 	}
 
 	fn spawn_inner(
 		&self,
 		name: &'static str,
+		group: GroupName,
 		task: impl Future<Output = ()> + Send + 'static,
 		task_type: TaskType,
 	) {
 		let essential_failed = self.essential_failed_tx.clone();
 		let essential_task = std::panic::AssertUnwindSafe(task)
 			.catch_unwind()
-			.map(move |_| {
-				log::error!("Essential task `{}` failed. Shutting down service.", name);
-				let _ = essential_failed.close_channel();
+			.map(move |result| {
+				let reason = match result {
+					Ok(()) => "exited",
+					Err(_) => "panicked",
+				};
+				let message = format!("Essential task `{}` of group `{}` {}.", name, group, reason);
+				error!("{} Shutting down service.", message);
+				let _ = essential_failed.unbounded_send(message);
 			});
 
-		let _ = self.inner.spawn_inner(name, essential_task, task_type);
+		let _ = self.inner.spawn_inner(name, group, essential_task, task_type);
 	}
 }
 
 impl sp_core::traits::SpawnEssentialNamed for SpawnEssentialTaskHandle {
 	fn spawn_essential_blocking(&self, name: &'static str, future: BoxFuture<'static, ()>) {
-		self.spawn_blocking(name, future);
+		self.spawn_blocking(name, None, future);
 	}
 
 	fn spawn_essential(&self, name: &'static str, future: BoxFuture<'static, ()>) {
-		self.spawn(name, future);
+		self.spawn(name, None, future);
 	}
 }
 
@@ -221,21 +354,39 @@ pub struct TaskManager {
 	on_exit: exit_future::Exit,
 	/// A signal that makes the exit future above resolve, fired on service drop.
 	signal: Option<Signal>,
+	/// Like `on_exit`, but for the tasks of the [`NETWORKING_GROUP`].
+	network_on_exit: exit_future::Exit,
+	/// A signal that makes `network_on_exit` resolve.
+	network_signal: Option<Signal>,
 	/// How to spawn background tasks.
 	executor: TaskExecutor,
 	/// Prometheus metric where to report the polling times.
 	metrics: Option<Metrics>,
-	/// Send a signal when a spawned essential task has concluded. The next time
-	/// the service future is polled it should complete with an error.
-	essential_failed_tx: TracingUnboundedSender<()>,
+	/// Send a message describing the failure when a spawned essential task has concluded. The
+	/// next time the service future is polled it should complete with that message as error.
+	essential_failed_tx: TracingUnboundedSender<String>,
 	/// A receiver for spawned essential-tasks concluding.
-	essential_failed_rx: TracingUnboundedReceiver<()>,
+	essential_failed_rx: TracingUnboundedReceiver<String>,
 	/// Things to keep alive until the task manager is dropped.
 	keep_alive: Box<dyn std::any::Any + Send + Sync>,
 	/// A sender to a stream of background tasks. This is used for the completion future.
 	task_notifier: TracingUnboundedSender<JoinFuture>,
 	/// This future will complete when all the tasks are joined and the stream is closed.
 	completion_future: JoinFuture,
+	/// Like `task_notifier`, but for the tasks of the [`NETWORKING_GROUP`].
+	network_task_notifier: TracingUnboundedSender<JoinFuture>,
+	/// Like `completion_future`, but for the tasks of the [`NETWORKING_GROUP`].
+	network_completion_future: JoinFuture,
+	/// Futures run at the start of `clean_shutdown()`, before any task is terminated.
+	shutdown_hooks: Vec<BoxFuture<'static, ()>>,
+	/// Ends `future()` with success when it receives a message, see `shutdown_requester()`.
+	shutdown_tx: TracingUnboundedSender<()>,
+	/// A receiver for the shutdown requests.
+	shutdown_rx: TracingUnboundedReceiver<()>,
+	/// The tasks that are currently running, reported if they outlive the shutdown timeout.
+	running_tasks: RunningTasks,
+	/// How long `clean_shutdown()` waits for the tasks to finish.
+	shutdown_timeout: Duration,
 	/// A list of other `TaskManager`'s to terminate and gracefully shutdown when the parent
 	/// terminates and gracefully shutdown. Also ends the parent `future()` if a child's essential
 	/// task fails.
@@ -250,13 +401,16 @@ impl TaskManager {
 		prometheus_registry: Option<&Registry>,
 	) -> Result<Self, PrometheusError> {
 		let (signal, on_exit) = exit_future::signal();
+		let (network_signal, network_on_exit) = exit_future::signal();
 
 		// A side-channel for essential tasks to communicate shutdown.
 		let (essential_failed_tx, essential_failed_rx) = tracing_unbounded("mpsc_essential_tasks");
+		let (shutdown_tx, shutdown_rx) = tracing_unbounded("mpsc_shutdown_requests");
 
 		let metrics = prometheus_registry.map(Metrics::register).transpose()?;
 
 		let (task_notifier, background_tasks) = tracing_unbounded("mpsc_background_tasks");
+		let (network_task_notifier, network_tasks) = tracing_unbounded("mpsc_network_tasks");
 		// NOTE: for_each_concurrent will await on all the JoinHandle futures at the same time. It
 		// is possible to limit this but it's actually better for the memory foot print to await
 		// them all to not accumulate anything on that stream.
@@ -264,10 +418,16 @@ impl TaskManager {
 			Box::pin(background_tasks.for_each_concurrent(None, |x| x)),
 			TaskType::Async,
 		);
+		let network_completion_future = executor.spawn(
+			Box::pin(network_tasks.for_each_concurrent(None, |x| x)),
+			TaskType::Async,
+		);
 
 		Ok(Self {
 			on_exit,
 			signal: Some(signal),
+			network_on_exit,
+			network_signal: Some(network_signal),
 			executor,
 			metrics,
 			essential_failed_tx,
@@ -275,6 +435,13 @@ impl TaskManager {
 			keep_alive: Box::new(()),
 			task_notifier,
 			completion_future,
+			network_task_notifier,
+			network_completion_future,
+			shutdown_hooks: Vec::new(),
+			shutdown_tx,
+			shutdown_rx,
+			running_tasks: Default::default(),
+			shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
 			children: Vec::new(),
 		})
 	}
@@ -283,9 +450,12 @@ impl TaskManager {
 	pub fn spawn_handle(&self) -> SpawnTaskHandle {
 		SpawnTaskHandle {
 			on_exit: self.on_exit.clone(),
+			network_on_exit: self.network_on_exit.clone(),
 			executor: self.executor.clone(),
 			metrics: self.metrics.clone(),
 			task_notifier: self.task_notifier.clone(),
+			network_task_notifier: self.network_task_notifier.clone(),
+			running_tasks: self.running_tasks.clone(),
 		}
 	}
 
@@ -303,21 +473,76 @@ impl TaskManager {
 	///
 	/// This is an issue in some cases as some of our dependencies do require that we drop all the
 	/// objects properly otherwise it triggers a SIGABRT on exit.
+	///
+	/// The shutdown goes through the following steps:
+	///
+	/// 1. the shutdown hooks (see `add_shutdown_hook()`) are run, e.g. to drain the RPC servers;
+	/// 2. all the tasks but the ones of the [`NETWORKING_GROUP`] are terminated and awaited, along
+	///    with the children, so that the consensus tasks can still use the network while stopping;
+	/// 3. the networking tasks are terminated and awaited;
+	/// 4. what is kept alive (see `keep_alive()`) is dropped, which closes the database once the
+	///    last reference to the client is gone.
+	///
+	/// The tasks are awaited for at most the shutdown timeout (see `set_shutdown_timeout()`),
+	/// after which the tasks still running are logged and the object is dropped anyway.
 	pub fn clean_shutdown(mut self) -> Pin<Box<dyn Future<Output = ()> + Send>> {
-		self.terminate();
-		let children_shutdowns = self.children.into_iter().map(|x| x.clean_shutdown());
-		let keep_alive = self.keep_alive;
-		let completion_future = self.completion_future;
+		let shutdown_hooks = mem::take(&mut self.shutdown_hooks);
+		let children = mem::take(&mut self.children);
+		let running_tasks = self.running_tasks.clone();
+		let shutdown_timeout = self.shutdown_timeout;
 
 		Box::pin(async move {
-			join_all(children_shutdowns).await;
-			completion_future.await;
-			drop(keep_alive);
+			let shutdown = async move {
+				join_all(shutdown_hooks).await;
+
+				self.terminate_non_networking();
+				let children_shutdowns = join_all(children.into_iter().map(|x| x.clean_shutdown()));
+				join(children_shutdowns, &mut self.completion_future).await;
+
+				self.terminate();
+				(&mut self.network_completion_future).await;
+				drop(self);
+			}.boxed();
+
+			let timeout = Delay::new(shutdown_timeout);
+			if let Either::Right((_, shutdown)) = select(shutdown, timeout).await {
+				let running_tasks = running_tasks.lock()
+					.iter()
+					.map(|((name, group), count)| format!("{} ({}) x{}", name, group, count))
+					.collect::<Vec<_>>();
+				warn!(
+					"Tasks did not finish within {:?} of the shutdown: {}",
+					shutdown_timeout,
+					running_tasks.join(", "),
+				);
+				// Drops what is kept alive anyway.
+				drop(shutdown);
+			}
 		})
 	}
 
+	/// Set how long `clean_shutdown()` waits for the tasks to finish. Defaults to 60 seconds.
+	pub fn set_shutdown_timeout(&mut self, timeout: Duration) {
+		self.shutdown_timeout = timeout;
+	}
+
+	/// Register a future to run at the start of `clean_shutdown()`, before any task is terminated.
+	///
+	/// This is meant to stop the interfaces of the node, such as the RPC servers, so that nothing
+	/// new is started while the tasks are stopping. The hooks count towards the shutdown timeout.
+	pub fn add_shutdown_hook(&mut self, hook: impl Future<Output = ()> + Send + 'static) {
+		self.shutdown_hooks.push(hook.boxed());
+	}
+
+	/// Get a sender to request the shutdown of the service: `future()` ends with success when it
+	/// receives a message, after which `clean_shutdown()` must be called as usual.
+	pub fn shutdown_requester(&self) -> TracingUnboundedSender<()> {
+		self.shutdown_tx.clone()
+	}
+
 	/// Return a future that will end with success if the signal to terminate was sent
-	/// (`self.terminate()`) or with an error if an essential task fails.
+	/// (`self.terminate()`) or the shutdown was requested (see `shutdown_requester()`), or with an
+	/// error naming the essential task that failed.
 	///
 	/// # Warning
 	///
@@ -333,17 +558,31 @@ impl TaskManager {
 					// it must not stop
 					.chain(std::iter::once(pending().boxed()))
 			).fuse();
+			let mut t4 = self.shutdown_rx.next().fuse();
 
 			futures::select! {
-				_ = t1 => Err(Error::Other("Essential task failed.".into())),
+				reason = t1 => Err(Error::Other(
+					reason.unwrap_or_else(|| "Essential task failed.".into())
+				)),
 				_ = t2 => Ok(()),
 				res = t3 => Err(res.map(|_| ()).expect_err("this future never ends; qed")),
+				_ = t4 => Ok(()),
 			}
 		})
 	}
 
 	/// Signal to terminate all the running tasks.
 	pub fn terminate(&mut self) {
+		self.terminate_non_networking();
+		if let Some(signal) = self.network_signal.take() {
+			let _ = signal.fire();
+			self.network_task_notifier.close_channel();
+		}
+	}
+
+	/// Signal to terminate all the running tasks but the ones of the [`NETWORKING_GROUP`], and
+	/// all the tasks of the children.
+	fn terminate_non_networking(&mut self) {
 		if let Some(signal) = self.signal.take() {
 			let _ = signal.fire();
 			// NOTE: this will prevent new tasks to be spawned
@@ -357,7 +596,6 @@ impl TaskManager {
 	/// Set what the task manager should keep alive, can be called multiple times.
 	pub fn keep_alive<T: 'static + Send + Sync>(&mut self, to_keep_alive: T) {
 		// allows this fn to safely called multiple times.
-		use std::mem;
 		let old = mem::replace(&mut self.keep_alive, Box::new(()));
 		self.keep_alive = Box::new((to_keep_alive, old));
 	}
@@ -375,6 +613,7 @@ struct Metrics {
 	// This list is ordered alphabetically
 	poll_duration: HistogramVec,
 	poll_start: CounterVec<U64>,
+	tasks_active: GaugeVec<U64>,
 	tasks_spawned: CounterVec<U64>,
 	tasks_ended: CounterVec<U64>,
 }
@@ -391,28 +630,35 @@ impl Metrics {
 					buckets: exponential_buckets(0.001, 4.0, 9)
 						.expect("function parameters are constant and always valid; qed"),
 				},
-				&["task_name"]
+				&["task_name", "task_group"]
 			)?, registry)?,
 			poll_start: register(CounterVec::new(
 				Opts::new(
 					"tasks_polling_started_total",
 					"Total number of times we started invoking Future::poll"
 				),
-				&["task_name"]
+				&["task_name", "task_group"]
+			)?, registry)?,
+			tasks_active: register(GaugeVec::new(
+				Opts::new(
+					"tasks_active",
+					"Number of tasks that are currently running"
+				),
+				&["task_name", "task_group"]
 			)?, registry)?,
 			tasks_spawned: register(CounterVec::new(
 				Opts::new(
 					"tasks_spawned_total",
 					"Total number of tasks that have been spawned on the Service"
 				),
-				&["task_name"]
+				&["task_name", "task_group"]
 			)?, registry)?,
 			tasks_ended: register(CounterVec::new(
 				Opts::new(
 					"tasks_ended_total",
 					"Total number of tasks for which Future::poll has returned Ready(()) or panicked"
 				),
-				&["task_name", "reason"]
+				&["task_name", "reason", "task_group"]
 			)?, registry)?,
 		})
 	}
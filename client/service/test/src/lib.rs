@@ -257,13 +257,16 @@ fn node_config<G: RuntimeGenesis + 'static, E: ChainSpecExtension + Clone + 'sta
 		chain_spec: Box::new((*spec).clone()),
 		wasm_method: sc_service::config::WasmExecutionMethod::Interpreted,
 		wasm_runtime_overrides: Default::default(),
+		wasm_runtime_cache: Default::default(),
 		execution_strategies: Default::default(),
 		rpc_http: None,
 		rpc_ipc: None,
 		rpc_ws: None,
 		rpc_ws_max_connections: None,
+		rpc_shutdown_timeout: None,
 		rpc_cors: None,
 		rpc_methods: Default::default(),
+		rpc_policy: Default::default(),
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		telemetry_external_transport: None,
@@ -278,6 +281,8 @@ fn node_config<G: RuntimeGenesis + 'static, E: ChainSpecExtension + Clone + 'sta
 		announce_block: true,
 		base_path: Some(BasePath::new(root)),
 		informant_output_format: Default::default(),
+		storage_monitor: Default::default(),
+		hardware_benchmarks: false,
 		disable_log_reloading: false,
 	}
 }
@@ -308,6 +308,31 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 		Ok(commit)
 	}
 
+	fn import_canonical_block<E: fmt::Debug>(
+		&mut self,
+		hash: &BlockHash,
+		number: u64,
+		mut changeset: ChangeSet<Key>,
+	) -> Result<CommitSet<Key>, Error<E>> {
+		// There is no previous state that could be deleted.
+		changeset.deleted.clear();
+		let mut commit = CommitSet {
+			data: changeset,
+			meta: Default::default(),
+		};
+		match self.mode {
+			PruningMode::ArchiveAll => Ok(commit),
+			PruningMode::ArchiveCanonical | PruningMode::Constrained(_) => {
+				self.non_canonical.set_last_canonicalized(hash, number, &mut commit)?;
+				if let Some(ref mut pruning) = self.pruning {
+					pruning.note_canonical(hash, &mut commit);
+				}
+				self.prune(&mut commit);
+				Ok(commit)
+			}
+		}
+	}
+
 	fn best_canonical(&self) -> Option<u64> {
 		return self.non_canonical.last_canonicalized_block_number()
 	}
@@ -482,6 +507,19 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDb<BlockHash
 		self.db.write().canonicalize_block(hash)
 	}
 
+	/// Import the full state of a block as canonical, without its parent being known.
+	///
+	/// This is used for state downloaded from the network. It is only allowed when there are no
+	/// non-canonical blocks.
+	pub fn import_canonical_block<E: fmt::Debug>(
+		&self,
+		hash: &BlockHash,
+		number: u64,
+		changeset: ChangeSet<Key>,
+	) -> Result<CommitSet<Key>, Error<E>> {
+		self.db.write().import_canonical_block(hash, number, changeset)
+	}
+
 	/// Prevents pruning of specified block and its descendants.
 	pub fn pin(&self, hash: &BlockHash) -> Result<(), PinError> {
 		self.db.write().pin(hash)
@@ -656,6 +694,67 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 21, 3, 921, 922, 93, 94])));
 	}
 
+	#[test]
+	fn import_canonical_block_skips_history() {
+		let mut db = make_db(&[]);
+		let state_db: StateDb<H256, H256> = StateDb::new(
+			PruningMode::Constrained(Constraints { max_blocks: Some(2), max_mem: None }),
+			false,
+			&db,
+		).unwrap();
+		db.commit(
+			&state_db
+				.insert_block::<io::Error>(
+					&H256::from_low_u64_be(0),
+					0,
+					&H256::from_low_u64_be(0),
+					make_changeset(&[], &[]),
+				)
+				.unwrap(),
+		);
+		state_db.apply_pending();
+		db.commit(&state_db.canonicalize_block::<io::Error>(&H256::from_low_u64_be(0)).unwrap());
+		state_db.apply_pending();
+
+		db.commit(
+			&state_db
+				.import_canonical_block::<io::Error>(
+					&H256::from_low_u64_be(10),
+					10,
+					make_changeset(&[1, 2], &[3]),
+				)
+				.unwrap(),
+		);
+		state_db.apply_pending();
+		assert_eq!(state_db.best_canonical(), Some(10));
+		assert!(!state_db.is_pruned(&H256::from_low_u64_be(10), 10));
+		assert!(db.data_eq(&make_db(&[1, 2])));
+
+		// blocks on top of the imported one are accepted
+		db.commit(
+			&state_db
+				.insert_block::<io::Error>(
+					&H256::from_low_u64_be(11),
+					11,
+					&H256::from_low_u64_be(10),
+					make_changeset(&[4], &[]),
+				)
+				.unwrap(),
+		);
+		state_db.apply_pending();
+		assert!(!state_db.is_pruned(&H256::from_low_u64_be(11), 11));
+
+		// importing while there are non-canonical blocks is not allowed
+		assert!(state_db
+			.import_canonical_block::<io::Error>(
+				&H256::from_low_u64_be(20),
+				20,
+				make_changeset(&[5], &[]),
+			)
+			.is_err()
+		);
+	}
+
 	#[test]
 	fn detects_incompatible_mode() {
 		let mut db = make_db(&[]);
@@ -271,6 +271,29 @@ impl<BlockHash: Hash, Key: Hash> NonCanonicalOverlay<BlockHash, Key> {
 		Ok(commit)
 	}
 
+	/// Make the given block the last canonicalized one, skipping over any blocks in between.
+	///
+	/// This is used when a block state is imported without its history. It is only allowed while
+	/// there are no non-canonical blocks in the overlay.
+	pub fn set_last_canonicalized<E: fmt::Debug>(
+		&mut self,
+		hash: &BlockHash,
+		number: u64,
+		commit: &mut CommitSet<Key>,
+	) -> Result<(), Error<E>> {
+		if !self.levels.is_empty() || !self.pending_canonicalizations.is_empty() {
+			return Err(Error::InvalidBlock);
+		}
+		if self.last_canonicalized.as_ref().map_or(false, |&(_, n)| number <= n) {
+			return Err(Error::InvalidBlockNumber);
+		}
+		trace!(target: "state-db", "Setting last canonicalized block to #{} ({:?})", number, hash);
+		let last_canonicalized = (hash.clone(), number);
+		commit.meta.inserted.push((to_meta_key(LAST_CANONICAL, &()), last_canonicalized.encode()));
+		self.last_canonicalized = Some(last_canonicalized);
+		Ok(())
+	}
+
 	fn discard_journals(
 		&self,
 		level_index: usize,
@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Storage monitor. Periodically checks the available disk space on the database path and stops
+//! the node before the database runs out of space.
+
+use std::{path::PathBuf, time::Duration};
+
+/// Configuration of the storage monitor.
+#[derive(Clone, Debug)]
+pub struct StorageMonitorParams {
+	/// Minimum available space on the database path, in MiB.
+	///
+	/// The node is stopped once the available space drops below it. `0` disables the monitor.
+	pub threshold: u64,
+	/// How often the available space is checked, in seconds.
+	pub polling_period: u32,
+}
+
+impl Default for StorageMonitorParams {
+	fn default() -> Self {
+		Self {
+			threshold: 1024,
+			polling_period: 5,
+		}
+	}
+}
+
+/// Builds the storage monitor for the database at `path` and returns a `Future` that resolves
+/// once the available space on it drops below the configured threshold.
+///
+/// Returns `None` if the monitor is disabled or not supported on the target.
+pub fn build(
+	params: StorageMonitorParams,
+	path: PathBuf,
+) -> Option<impl futures::Future<Output = ()>> {
+	if params.threshold == 0 || cfg!(target_os = "unknown") {
+		return None
+	}
+
+	let threshold = params.threshold.saturating_mul(1024 * 1024);
+	let polling_period = Duration::from_secs(params.polling_period.max(1).into());
+
+	log::debug!(
+		target: "storage-monitor",
+		"Monitoring available space on {:?}, threshold: {} MiB",
+		path,
+		params.threshold,
+	);
+
+	Some(async move {
+		loop {
+			match available_space(&path) {
+				Ok(space) if space < threshold => {
+					log::error!(
+						target: "storage-monitor",
+						"Available space on {:?} dropped to {} MiB, below the threshold of {} MiB. \
+						Stopping the node.",
+						path,
+						space / (1024 * 1024),
+						params.threshold,
+					);
+					return
+				},
+				Ok(_) => {},
+				Err(e) => log::warn!(
+					target: "storage-monitor",
+					"Failed to get the available space on {:?}: {}",
+					path,
+					e,
+				),
+			}
+
+			wasm_timer::Delay::new(polling_period).await.ok();
+		}
+	})
+}
+
+#[cfg(not(target_os = "unknown"))]
+fn available_space(path: &std::path::Path) -> std::io::Result<u64> {
+	fs2::available_space(path)
+}
+
+#[cfg(target_os = "unknown")]
+fn available_space(_: &std::path::Path) -> std::io::Result<u64> {
+	Ok(u64::max_value())
+}
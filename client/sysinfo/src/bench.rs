@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The hardware benchmarks.
+//!
+//! Every benchmark repeats its workload until `BENCHMARK_DURATION` has elapsed and reports the
+//! achieved throughput in MiB/s.
+
+use std::{
+	fs::{self, OpenOptions},
+	io::{self, Write},
+	path::Path,
+	time::{Duration, Instant},
+};
+
+const BENCHMARK_DURATION: Duration = Duration::from_millis(500);
+const MIB: usize = 1024 * 1024;
+
+/// Runs `f` until `BENCHMARK_DURATION` has elapsed, where every call processes `chunk_size`
+/// bytes, and returns the throughput in MiB/s.
+fn benchmark<E>(chunk_size: usize, mut f: impl FnMut() -> Result<(), E>) -> Result<u64, E> {
+	let start = Instant::now();
+	let mut processed = 0;
+	loop {
+		f()?;
+		processed += chunk_size;
+
+		let elapsed = start.elapsed();
+		if elapsed >= BENCHMARK_DURATION {
+			return Ok(((processed / MIB) as f64 / elapsed.as_secs_f64()) as u64)
+		}
+	}
+}
+
+/// Measures the blake2 hashing throughput of a single core.
+pub(crate) fn benchmark_cpu() -> u64 {
+	let mut data = vec![0u8; MIB];
+	benchmark::<std::convert::Infallible>(MIB, || {
+		// Feed the previous hash back in so the loop can't be optimized away.
+		let hash = sp_core::blake2_256(&data);
+		data[..32].copy_from_slice(&hash);
+		Ok(())
+	}).unwrap_or_default()
+}
+
+/// Measures the memory copy throughput.
+pub(crate) fn benchmark_memory() -> u64 {
+	const SIZE: usize = 64 * MIB;
+
+	let mut source = vec![0xffu8; SIZE];
+	let mut destination = vec![0u8; SIZE];
+	benchmark::<std::convert::Infallible>(SIZE, || {
+		destination.copy_from_slice(&source);
+		// Make every copy depend on the previous one so the loop can't be optimized away.
+		source[0] = destination[SIZE - 1].wrapping_add(1);
+		Ok(())
+	}).unwrap_or_default()
+}
+
+/// Measures the sequential write throughput of the disk backing `directory`.
+///
+/// Every chunk is flushed to the disk before the next one is written.
+pub(crate) fn benchmark_disk_sequential_writes(directory: &Path) -> io::Result<u64> {
+	const CHUNK_SIZE: usize = 8 * MIB;
+
+	let path = directory.join(".disk_bench_seq_wr.tmp");
+	let mut file = OpenOptions::new().create(true).truncate(true).write(true).open(&path)?;
+	let chunk = vec![0xffu8; CHUNK_SIZE];
+	let result = benchmark(CHUNK_SIZE, || {
+		file.write_all(&chunk)?;
+		file.sync_data()
+	});
+
+	drop(file);
+	let _ = fs::remove_file(&path);
+
+	result
+}
@@ -0,0 +1,108 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Collects information about the node's hardware and operating system and runs a set of short
+//! hardware benchmarks.
+//!
+//! The results are printed to the log at startup and sent along with the telemetry connection
+//! message, so that network operators can reason about the hardware the nodes are running on.
+
+use std::path::Path;
+
+mod bench;
+#[cfg(target_os = "linux")]
+mod linux;
+
+pub use sc_telemetry::{HwBench, SysInfo};
+
+const LOG_TARGET: &str = "sysinfo";
+
+/// Gathers information about the node's hardware and operating system.
+///
+/// Everything that cannot be detected on the current platform is left as `None`.
+pub fn gather_sysinfo() -> SysInfo {
+	#[cfg(target_os = "linux")]
+	{
+		linux::gather_sysinfo()
+	}
+
+	#[cfg(not(target_os = "linux"))]
+	{
+		SysInfo::default()
+	}
+}
+
+/// Runs the hardware benchmarks.
+///
+/// The disk benchmark writes a temporary file into `scratch_directory` and is skipped if no
+/// directory is given or the file cannot be written.
+pub fn gather_hwbench(scratch_directory: Option<&Path>) -> HwBench {
+	let disk_sequential_write_score = scratch_directory.and_then(|directory| {
+		bench::benchmark_disk_sequential_writes(directory)
+			.map_err(|error| log::warn!(
+				target: LOG_TARGET,
+				"Failed to run the disk benchmark in {:?}: {}",
+				directory,
+				error,
+			))
+			.ok()
+	});
+
+	HwBench {
+		cpu_hashrate_score: bench::benchmark_cpu(),
+		memory_memcpy_score: bench::benchmark_memory(),
+		disk_sequential_write_score,
+	}
+}
+
+/// Prints the gathered system information to the log.
+pub fn print_sysinfo(sysinfo: &SysInfo) {
+	fn or_unknown<T: ToString>(value: &Option<T>) -> String {
+		value.as_ref().map(ToString::to_string).unwrap_or_else(|| "Unknown".into())
+	}
+
+	log::info!(target: LOG_TARGET, "💻 Operating system: {}", std::env::consts::OS);
+	log::info!(target: LOG_TARGET, "💻 CPU architecture: {}", std::env::consts::ARCH);
+	if let Some(ref linux_distro) = sysinfo.linux_distro {
+		log::info!(target: LOG_TARGET, "💻 Target environment: {}", linux_distro);
+	}
+	if let Some(ref linux_kernel) = sysinfo.linux_kernel {
+		log::info!(target: LOG_TARGET, "💻 Linux kernel: {}", linux_kernel);
+	}
+	log::info!(target: LOG_TARGET, "💻 CPU: {}", or_unknown(&sysinfo.cpu));
+	log::info!(target: LOG_TARGET, "💻 CPU cores: {}", or_unknown(&sysinfo.core_count));
+	log::info!(
+		target: LOG_TARGET,
+		"💻 Memory: {}",
+		or_unknown(&sysinfo.memory.map(|memory| format!("{}MB", memory / (1024 * 1024)))),
+	);
+	log::info!(
+		target: LOG_TARGET,
+		"💻 Virtual machine: {}",
+		or_unknown(&sysinfo.is_virtual_machine.map(|is_vm| if is_vm { "yes" } else { "no" })),
+	);
+}
+
+/// Prints the hardware benchmark results to the log.
+pub fn print_hwbench(hwbench: &HwBench) {
+	log::info!(target: LOG_TARGET, "🏁 CPU score: {}MB/s", hwbench.cpu_hashrate_score);
+	log::info!(target: LOG_TARGET, "🏁 Memory score: {}MB/s", hwbench.memory_memcpy_score);
+	if let Some(score) = hwbench.disk_sequential_write_score {
+		log::info!(target: LOG_TARGET, "🏁 Disk score (seq. writes): {}MB/s", score);
+	}
+}
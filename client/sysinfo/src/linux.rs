@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Gathering of the system information on Linux, from `/proc` and `/etc/os-release`.
+
+use crate::SysInfo;
+use std::collections::HashSet;
+
+fn read_file(path: &str) -> Option<String> {
+	match std::fs::read_to_string(path) {
+		Ok(data) => Some(data),
+		Err(error) => {
+			log::warn!(target: crate::LOG_TARGET, "Failed to read {:?}: {}", path, error);
+			None
+		},
+	}
+}
+
+/// Returns the trimmed value of the first `key: value` line whose key is `key`.
+fn extract<'a>(data: &'a str, key: &str) -> Option<&'a str> {
+	data.lines()
+		.filter_map(|line| {
+			let mut parts = line.splitn(2, ':');
+			Some((parts.next()?.trim(), parts.next()?.trim()))
+		})
+		.find(|(line_key, _)| *line_key == key)
+		.map(|(_, value)| value)
+}
+
+fn parse_cpuinfo(cpuinfo: &str, sysinfo: &mut SysInfo) {
+	sysinfo.cpu = extract(cpuinfo, "model name").map(String::from);
+	sysinfo.is_virtual_machine = extract(cpuinfo, "flags")
+		.map(|flags| flags.split_whitespace().any(|flag| flag == "hypervisor"));
+
+	// Every logical CPU has its own block; physical cores are identified by the pair of
+	// physical package and core id.
+	let cores: HashSet<_> = cpuinfo
+		.split("\n\n")
+		.filter_map(|block| Some((extract(block, "physical id")?, extract(block, "core id")?)))
+		.collect();
+	sysinfo.core_count = if cores.is_empty() {
+		None
+	} else {
+		Some(cores.len() as u32)
+	};
+}
+
+fn parse_meminfo(meminfo: &str, sysinfo: &mut SysInfo) {
+	sysinfo.memory = extract(meminfo, "MemTotal")
+		.and_then(|value| value.strip_suffix("kB"))
+		.and_then(|value| value.trim().parse::<u64>().ok())
+		.map(|kib| kib * 1024);
+}
+
+fn parse_os_release(os_release: &str, sysinfo: &mut SysInfo) {
+	sysinfo.linux_distro = os_release
+		.lines()
+		.find_map(|line| line.strip_prefix("PRETTY_NAME="))
+		.map(|value| value.trim_matches('"').to_owned());
+}
+
+pub(crate) fn gather_sysinfo() -> SysInfo {
+	let mut sysinfo = SysInfo::default();
+
+	if let Some(cpuinfo) = read_file("/proc/cpuinfo") {
+		parse_cpuinfo(&cpuinfo, &mut sysinfo);
+	}
+	if let Some(meminfo) = read_file("/proc/meminfo") {
+		parse_meminfo(&meminfo, &mut sysinfo);
+	}
+	if let Some(os_release) = read_file("/etc/os-release") {
+		parse_os_release(&os_release, &mut sysinfo);
+	}
+	sysinfo.linux_kernel = read_file("/proc/sys/kernel/osrelease")
+		.map(|kernel| kernel.trim().to_owned());
+
+	sysinfo
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_cpuinfo() {
+		let block = |physical_id, core_id| format!(
+			"processor\t: 0\nmodel name\t: Test CPU @ 3.00GHz\nphysical id\t: {}\n\
+			core id\t\t: {}\nflags\t\t: fpu sse2 hypervisor\n",
+			physical_id,
+			core_id,
+		);
+		// Two hyperthreads per core.
+		let cpuinfo = [block(0, 0), block(0, 0), block(0, 1), block(0, 1)].join("\n");
+
+		let mut sysinfo = SysInfo::default();
+		parse_cpuinfo(&cpuinfo, &mut sysinfo);
+
+		assert_eq!(sysinfo.cpu.as_deref(), Some("Test CPU @ 3.00GHz"));
+		assert_eq!(sysinfo.core_count, Some(2));
+		assert_eq!(sysinfo.is_virtual_machine, Some(true));
+	}
+
+	#[test]
+	fn parses_meminfo_and_os_release() {
+		let mut sysinfo = SysInfo::default();
+		parse_meminfo("MemTotal:       16384 kB\nMemFree:         1024 kB\n", &mut sysinfo);
+		parse_os_release("NAME=\"Debian\"\nPRETTY_NAME=\"Debian GNU/Linux 11\"\n", &mut sysinfo);
+
+		assert_eq!(sysinfo.memory, Some(16384 * 1024));
+		assert_eq!(sysinfo.linux_distro.as_deref(), Some("Debian GNU/Linux 11"));
+	}
+}
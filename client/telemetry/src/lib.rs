@@ -100,6 +100,38 @@ pub struct ConnectionMessage {
 	pub startup_time: String,
 	/// Node's network ID.
 	pub network_id: String,
+	/// Information about the node's hardware and operating system.
+	pub sysinfo: Option<SysInfo>,
+	/// Results of the hardware benchmarks run at startup.
+	pub hwbench: Option<HwBench>,
+}
+
+/// Hardware and operating system information of the node.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SysInfo {
+	/// CPU model.
+	pub cpu: Option<String>,
+	/// Number of physical CPU cores.
+	pub core_count: Option<u32>,
+	/// Total amount of memory, in bytes.
+	pub memory: Option<u64>,
+	/// Whether the node runs inside a virtual machine.
+	pub is_virtual_machine: Option<bool>,
+	/// Version of the Linux kernel.
+	pub linux_kernel: Option<String>,
+	/// Name and version of the Linux distribution.
+	pub linux_distro: Option<String>,
+}
+
+/// Scores of the hardware benchmarks, in MiB/s.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct HwBench {
+	/// Hashing throughput of a single CPU core.
+	pub cpu_hashrate_score: u64,
+	/// Memory copy throughput.
+	pub memory_memcpy_score: u64,
+	/// Sequential disk write throughput, if it could be measured.
+	pub disk_sequential_write_score: Option<u64>,
 }
 
 /// Telemetry worker.
@@ -37,7 +37,8 @@ pub(crate) fn connection_notifier_channel() -> (ConnectionNotifierSender, Connec
 ///
 /// This is a wrapper `Sink` around a network `Sink` with 3 particularities:
 ///  -  It is infallible: if the connection stops, it will reconnect automatically when the server
-///     becomes available again.
+///     becomes available again. Consecutive failed attempts back off exponentially, up to
+///     [`MAX_RECONNECT_DELAY`].
 ///  -  It holds a list of "connection messages" which are sent automatically when the connection is
 ///     (re-)established. This is used for the "system.connected" message that needs to be send for
 ///     every substrate node that connects.
@@ -56,6 +57,8 @@ pub(crate) struct Node<TTrans: Transport> {
 	pub(crate) connection_messages: Vec<TelemetryPayload>,
 	/// Notifier for when the connection (re-)establishes.
 	pub(crate) telemetry_connection_notifier: Vec<ConnectionNotifierSender>,
+	/// Number of consecutive failed connection attempts.
+	reconnect_attempts: u32,
 }
 
 enum NodeSocket<TTrans: Transport> {
@@ -71,12 +74,18 @@ enum NodeSocket<TTrans: Transport> {
 	Poisoned,
 }
 
-impl<TTrans: Transport> NodeSocket<TTrans> {
-	fn wait_reconnect() -> NodeSocket<TTrans> {
-		let random_delay = rand::thread_rng().gen_range(5, 10);
-		let delay = Delay::new(Duration::from_secs(random_delay));
-		NodeSocket::WaitingReconnect(delay)
-	}
+/// Delay before the first reconnection attempt, doubled after every failed attempt.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Upper bound of the delay between two reconnection attempts.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Returns the delay to wait before the next attempt after `attempts` consecutive failures,
+/// without the random jitter.
+fn reconnect_delay(attempts: u32) -> Duration {
+	INITIAL_RECONNECT_DELAY
+		.checked_mul(1u32.checked_shl(attempts).unwrap_or(u32::MAX))
+		.map_or(MAX_RECONNECT_DELAY, |delay| delay.min(MAX_RECONNECT_DELAY))
 }
 
 struct NodeSocketConnected<TTrans: Transport> {
@@ -100,8 +109,18 @@ impl<TTrans: Transport> Node<TTrans> {
 			transport,
 			connection_messages,
 			telemetry_connection_notifier,
+			reconnect_attempts: 0,
 		}
 	}
+
+	/// Schedules the next reconnection attempt, backing off with every consecutive failure.
+	fn wait_reconnect(&mut self) -> NodeSocket<TTrans> {
+		let jitter = Duration::from_secs(rand::thread_rng().gen_range(0, 5));
+		let delay = reconnect_delay(self.reconnect_attempts) + jitter;
+		self.reconnect_attempts = self.reconnect_attempts.saturating_add(1);
+		log::trace!(target: "telemetry", "Reconnecting to {} in {:?}", self.addr, delay);
+		NodeSocket::WaitingReconnect(Delay::new(delay))
+	}
 }
 
 impl<TTrans: Transport, TSinkErr> Node<TTrans>
@@ -150,7 +169,7 @@ where
 						match self.as_mut().try_send_connection_messages(cx, &mut conn) {
 							Poll::Ready(Err(err)) => {
 								log::warn!(target: "telemetry", "⚠️  Disconnected from {}: {:?}", self.addr, err);
-								socket = NodeSocket::wait_reconnect();
+								socket = self.wait_reconnect();
 							}
 							Poll::Ready(Ok(())) => {
 								self.socket = NodeSocket::Connected(conn);
@@ -164,7 +183,7 @@ where
 					}
 					Poll::Ready(Err(err)) => {
 						log::warn!(target: "telemetry", "⚠️  Disconnected from {}: {:?}", self.addr, err);
-						socket = NodeSocket::wait_reconnect();
+						socket = self.wait_reconnect();
 					}
 					Poll::Pending => {
 						self.socket = NodeSocket::Connected(conn);
@@ -174,6 +193,7 @@ where
 				NodeSocket::Dialing(mut s) => match Future::poll(Pin::new(&mut s), cx) {
 					Poll::Ready(Ok(sink)) => {
 						log::debug!(target: "telemetry", "✅ Connected to {}", self.addr);
+						self.reconnect_attempts = 0;
 
 						for sender in self.telemetry_connection_notifier.iter_mut() {
 							let _ = sender.send(());
@@ -209,7 +229,7 @@ where
 					Poll::Pending => break NodeSocket::Dialing(s),
 					Poll::Ready(Err(err)) => {
 						log::warn!(target: "telemetry", "❌ Error while dialing {}: {:?}", self.addr, err);
-						socket = NodeSocket::wait_reconnect();
+						socket = self.wait_reconnect();
 					}
 				},
 				NodeSocket::ReconnectNow => match self.transport.clone().dial(self.addr.clone()) {
@@ -219,7 +239,7 @@ where
 					}
 					Err(err) => {
 						log::warn!(target: "telemetry", "❌ Error while dialing {}: {:?}", self.addr, err);
-						socket = NodeSocket::wait_reconnect();
+						socket = self.wait_reconnect();
 					}
 				},
 				NodeSocket::WaitingReconnect(mut s) => {
@@ -274,7 +294,7 @@ where
 		match &mut self.socket {
 			NodeSocket::Connected(conn) => match conn.sink.poll_flush_unpin(cx) {
 				Poll::Ready(Err(_)) => {
-					self.socket = NodeSocket::wait_reconnect();
+					self.socket = self.wait_reconnect();
 					Poll::Ready(Ok(()))
 				}
 				Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
@@ -304,3 +324,17 @@ impl<TTrans: Transport> fmt::Debug for NodeSocket<TTrans> {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reconnect_delay_backs_off_exponentially_up_to_max() {
+		assert_eq!(reconnect_delay(0), Duration::from_secs(5));
+		assert_eq!(reconnect_delay(1), Duration::from_secs(10));
+		assert_eq!(reconnect_delay(3), Duration::from_secs(40));
+		assert_eq!(reconnect_delay(6), MAX_RECONNECT_DELAY);
+		assert_eq!(reconnect_delay(u32::MAX), MAX_RECONNECT_DELAY);
+	}
+}
@@ -0,0 +1,354 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+
+//! Utilities for tracing block execution
+
+use std::{collections::HashMap, sync::{Arc, atomic::{AtomicU64, Ordering}}, time::Instant};
+
+use parking_lot::Mutex;
+use tracing::{
+	dispatcher,
+	Dispatch,
+	Level,
+	span::{Attributes, Id, Record},
+	subscriber::Subscriber,
+};
+use tracing_subscriber::CurrentSpan;
+
+use sc_client_api::BlockBackend;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_rpc::tracing::{BlockTrace, Data, Span, TraceBlockResponse, TraceError};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header},
+};
+use sp_tracing::{WASM_NAME_KEY, WASM_TARGET_KEY, WASM_TRACE_IDENTIFIER};
+
+use crate::{SpanDatum, TraceEvent, Values};
+
+/// Targets that are traced when the caller does not provide any.
+pub const DEFAULT_TARGETS: &str = "pallet,frame,state";
+
+/// Target of the storage access events emitted by `sp-state-machine`.
+const STORAGE_TARGET: &str = "state";
+
+/// Tracing Block Result type alias
+pub type TraceBlockResult<T> = Result<T, Error>;
+
+/// Tracing Block error
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum Error {
+	#[error("Invalid block Id: {0}")]
+	InvalidBlockId(#[from] sp_blockchain::Error),
+	#[error("Missing block component: {0}")]
+	MissingBlockComponent(String),
+	#[error("Dispatch error: {0}")]
+	Dispatch(String),
+}
+
+/// Subscriber that records all spans and events emitted while a single block is executed.
+struct BlockSubscriber {
+	targets: Vec<(String, Level)>,
+	next_id: AtomicU64,
+	current_span: CurrentSpan,
+	spans: Mutex<HashMap<Id, SpanDatum>>,
+	events: Mutex<Vec<TraceEvent>>,
+}
+
+impl BlockSubscriber {
+	fn new(targets: &str) -> Self {
+		let mut targets: Vec<_> = targets.split(',').map(crate::parse_target).collect();
+		// WASM traces are always enabled here, they are filtered once the actual
+		// target and level are decoded from the recorded values.
+		targets.push((WASM_TRACE_IDENTIFIER.to_owned(), Level::TRACE));
+		BlockSubscriber {
+			targets,
+			next_id: AtomicU64::new(1),
+			current_span: CurrentSpan::default(),
+			spans: Mutex::new(HashMap::new()),
+			events: Mutex::new(Vec::new()),
+		}
+	}
+}
+
+impl Subscriber for BlockSubscriber {
+	fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+		check_target(&self.targets, metadata.target(), metadata.level())
+	}
+
+	fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+		let id = Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed));
+		let mut values = Values::default();
+		attrs.record(&mut values);
+		let span_datum = SpanDatum {
+			id: id.clone(),
+			parent_id: attrs.parent().cloned().or_else(|| self.current_span.id()),
+			name: attrs.metadata().name().to_owned(),
+			target: attrs.metadata().target().to_owned(),
+			level: *attrs.metadata().level(),
+			line: attrs.metadata().line().unwrap_or(0),
+			start_time: Instant::now(),
+			overall_time: Default::default(),
+			values,
+		};
+		self.spans.lock().insert(id.clone(), span_datum);
+		id
+	}
+
+	fn record(&self, span: &Id, values: &Record<'_>) {
+		if let Some(s) = self.spans.lock().get_mut(span) {
+			values.record(&mut s.values);
+		}
+	}
+
+	fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+	fn event(&self, event: &tracing::Event<'_>) {
+		let mut values = Values::default();
+		event.record(&mut values);
+		let trace_event = TraceEvent {
+			name: event.metadata().name(),
+			target: event.metadata().target().to_owned(),
+			level: *event.metadata().level(),
+			values,
+			parent_id: event.parent().cloned().or_else(|| self.current_span.id()),
+		};
+		self.events.lock().push(trace_event);
+	}
+
+	fn enter(&self, id: &Id) {
+		self.current_span.enter(id.clone());
+	}
+
+	fn exit(&self, _span: &Id) {
+		self.current_span.exit();
+	}
+}
+
+/// Holds a reference to the client in order to execute the given block.
+/// Records spans & events for the supplied targets (eg. "pallet,frame,state") and
+/// only records storage accesses for the given storage key prefixes.
+pub struct BlockExecutor<Block: BlockT, Client> {
+	client: Arc<Client>,
+	block: Block::Hash,
+	targets: Option<String>,
+	storage_keys: Option<String>,
+}
+
+impl<Block, Client> BlockExecutor<Block, Client>
+	where
+		Block: BlockT + 'static,
+		Client: HeaderBackend<Block> + BlockBackend<Block> + ProvideRuntimeApi<Block>
+			+ Send + Sync + 'static,
+		Client::Api: Core<Block>,
+{
+	/// Create a new `BlockExecutor`.
+	///
+	/// `targets` and `storage_keys` are comma separated lists. When `targets` is `None`,
+	/// [`DEFAULT_TARGETS`] are traced. When `storage_keys` is `None`, all storage accesses
+	/// are recorded.
+	pub fn new(
+		client: Arc<Client>,
+		block: Block::Hash,
+		targets: Option<String>,
+		storage_keys: Option<String>,
+	) -> Self {
+		Self { client, block, targets, storage_keys }
+	}
+
+	/// Execute block, record all spans and events belonging to `Self::targets`
+	/// and filter out events which do not have keys starting with one of the
+	/// prefixes in `Self::storage_keys`.
+	///
+	/// Runtime spans and events are only available when the runtime was built
+	/// with the `with-tracing` feature. Storage accesses are only available when
+	/// the `state` log target is enabled at `trace` level.
+	///
+	/// A failure to execute the block is reported as [`TraceBlockResponse::TraceError`].
+	pub fn trace_block(&self) -> TraceBlockResult<TraceBlockResponse> {
+		let id = BlockId::Hash(self.block);
+		let mut header = self.client.header(id)?
+			.ok_or_else(|| Error::MissingBlockComponent("Header not found".to_string()))?;
+		let extrinsics = self.client.block_body(&id)?
+			.ok_or_else(|| Error::MissingBlockComponent("Extrinsics not found".to_string()))?;
+		let parent_hash = *header.parent_hash();
+		let parent_id = BlockId::Hash(parent_hash);
+		// Remove all `Seal`s as they are added by the consensus engines after building the block.
+		// On import they are normally removed by the consensus engine.
+		header.digest_mut().logs.retain(|d| d.as_seal().is_none());
+		let block = Block::new(header, extrinsics);
+
+		let targets = self.targets.as_deref().unwrap_or(DEFAULT_TARGETS);
+		let dispatch = Dispatch::new(BlockSubscriber::new(targets));
+		if let Err(e) = dispatcher::with_default(&dispatch, || {
+			self.client.runtime_api().execute_block(&parent_id, block)
+		}) {
+			return Ok(TraceBlockResponse::TraceError(TraceError {
+				error: format!("Failed to collect traces and execute block: {:?}", e),
+			}))
+		}
+
+		let block_subscriber = dispatch.downcast_ref::<BlockSubscriber>()
+			.ok_or_else(|| Error::Dispatch(
+				"Cannot downcast Dispatch to BlockSubscriber after tracing block".to_string()
+			))?;
+		let tracing_targets = targets.to_owned();
+		let targets: Vec<_> = targets.split(',').map(crate::parse_target).collect();
+		let storage_keys: Vec<_> = self.storage_keys.as_deref()
+			.map(|keys| keys.split(',').map(normalize_key).collect())
+			.unwrap_or_default();
+
+		let mut spans: Vec<_> = block_subscriber.spans.lock()
+			.drain()
+			.map(|(_, span)| patch_wasm_span(span))
+			.filter(|span| check_target(&targets, &span.target, &span.level))
+			.map(|span| Span {
+				id: span.id.into_u64(),
+				parent_id: span.parent_id.map(|id| id.into_u64()),
+				name: span.name,
+				target: span.target,
+				wasm: span.values.bool_values.get("wasm").copied().unwrap_or(false),
+			})
+			.collect();
+		spans.sort_by_key(|span| span.id);
+		let events = block_subscriber.events.lock()
+			.drain(..)
+			.map(patch_wasm_event)
+			.filter(|event| check_target(&targets, &event.target, &event.level))
+			.filter(|event| {
+				event.target != STORAGE_TARGET || storage_key_filter(event, &storage_keys)
+			})
+			.map(|event| sp_rpc::tracing::Event {
+				target: event.target,
+				data: values_to_data(event.values),
+				parent_id: event.parent_id.map(|id| id.into_u64()),
+			})
+			.collect();
+
+		Ok(TraceBlockResponse::BlockTrace(BlockTrace {
+			block_hash: format!("{:?}", self.block),
+			parent_hash: format!("{:?}", parent_hash),
+			tracing_targets,
+			storage_keys: self.storage_keys.clone().unwrap_or_default(),
+			spans,
+			events,
+		}))
+	}
+}
+
+fn check_target(targets: &[(String, Level)], target: &str, level: &Level) -> bool {
+	targets.iter().any(|(t, l)| target.starts_with(t.as_str()) && level <= l)
+}
+
+/// Replace the generic name and target of a span coming from the runtime with
+/// the ones recorded in its values.
+fn patch_wasm_span(mut span: SpanDatum) -> SpanDatum {
+	if span.name == WASM_TRACE_IDENTIFIER {
+		span.values.bool_values.insert("wasm".to_owned(), true);
+		if let Some(n) = span.values.string_values.remove(WASM_NAME_KEY) {
+			span.name = n;
+		}
+		if let Some(t) = span.values.string_values.remove(WASM_TARGET_KEY) {
+			span.target = t;
+		}
+	}
+	span
+}
+
+/// Replace the generic target of an event coming from the runtime with the
+/// one recorded in its values.
+fn patch_wasm_event(mut event: TraceEvent) -> TraceEvent {
+	if event.target == WASM_TRACE_IDENTIFIER {
+		if let Some(t) = event.values.string_values.remove(WASM_TARGET_KEY) {
+			event.target = t;
+		}
+	}
+	event
+}
+
+fn normalize_key(key: &str) -> String {
+	key.trim().trim_start_matches("0x").to_lowercase()
+}
+
+/// Extract the hex encoded storage key from a storage access message,
+/// e.g. `0001: Get 3a636f6465=None`.
+fn storage_key(message: &str) -> Option<&str> {
+	message.split_whitespace().nth(2).and_then(|s| s.split('=').next())
+}
+
+fn storage_key_filter(event: &TraceEvent, storage_keys: &[String]) -> bool {
+	if storage_keys.is_empty() {
+		return true
+	}
+	event.values.string_values.get("message")
+		.and_then(|message| storage_key(message))
+		.map_or(false, |key| storage_keys.iter().any(|prefix| key.starts_with(prefix.as_str())))
+}
+
+fn values_to_data(values: Values) -> Data {
+	let string_values = values.string_values.into_iter()
+		.chain(values.bool_values.into_iter().map(|(k, v)| (k, v.to_string())))
+		.chain(values.i64_values.into_iter().map(|(k, v)| (k, v.to_string())))
+		.chain(values.u64_values.into_iter().map(|(k, v)| (k, v.to_string())))
+		.collect();
+	Data { string_values }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn storage_key_is_extracted_from_message() {
+		assert_eq!(storage_key("0001: Get 3a636f6465=None"), Some("3a636f6465"));
+		assert_eq!(storage_key("0001: ClearPrefix 26aa394e"), Some("26aa394e"));
+		assert_eq!(storage_key("0001: GetChild(6368696c64) 0102=None"), Some("0102"));
+		assert_eq!(storage_key("garbage"), None);
+	}
+
+	#[test]
+	fn storage_keys_are_normalized() {
+		assert_eq!(normalize_key(" 0x26AA394E"), "26aa394e");
+	}
+
+	#[test]
+	fn block_subscriber_records_spans_and_events() {
+		let dispatch = Dispatch::new(BlockSubscriber::new("pallet"));
+		dispatcher::with_default(&dispatch, || {
+			let span = tracing::info_span!(target: "pallet_balances", "transfer");
+			let _enter = span.enter();
+			tracing::info!(target: "pallet_balances", "inside");
+			tracing::info!(target: "other", "ignored");
+		});
+
+		let subscriber = dispatch.downcast_ref::<BlockSubscriber>().unwrap();
+		let spans = subscriber.spans.lock();
+		assert_eq!(spans.len(), 1);
+		let span = spans.values().next().unwrap();
+		assert_eq!(span.name, "transfer");
+
+		let events = subscriber.events.lock();
+		assert_eq!(events.len(), 1);
+		assert_eq!(events[0].target, "pallet_balances");
+		assert_eq!(events[0].parent_id, Some(span.id.clone()));
+	}
+}
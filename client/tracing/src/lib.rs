@@ -28,6 +28,7 @@
 
 #![warn(missing_docs)]
 
+pub mod block;
 pub mod logging;
 
 use rustc_hash::FxHashMap;
@@ -46,6 +47,9 @@ use tracing_subscriber::{
 	CurrentSpan,
 	layer::{Layer, Context},
 };
+use prometheus_endpoint::{
+	exponential_buckets, register, HistogramOpts, HistogramVec, PrometheusError, Registry,
+};
 use sp_tracing::{WASM_NAME_KEY, WASM_TARGET_KEY, WASM_TRACE_IDENTIFIER};
 
 #[doc(hidden)]
@@ -66,6 +70,8 @@ pub struct ProfilingLayer {
 pub enum TracingReceiver {
 	/// Output to logger
 	Log,
+	/// Record span durations in a Prometheus histogram, see [`register_span_metrics`]
+	Prometheus,
 }
 
 impl Default for TracingReceiver {
@@ -211,6 +217,8 @@ impl ProfilingLayer {
 	pub fn new(receiver: TracingReceiver, targets: &str) -> Self {
 		match receiver {
 			TracingReceiver::Log => Self::new_with_handler(Box::new(LogTraceHandler), targets),
+			TracingReceiver::Prometheus =>
+				Self::new_with_handler(Box::new(PrometheusTraceHandler), targets),
 		}
 	}
 
@@ -385,6 +393,40 @@ impl TraceHandler for LogTraceHandler {
 	}
 }
 
+lazy_static::lazy_static! {
+	static ref SPAN_DURATION: HistogramVec = HistogramVec::new(
+		HistogramOpts::new(
+			"tracing_span_duration_seconds",
+			"Time spent in spans matched by `--tracing-targets`, including runtime (wasm) spans",
+		).buckets(exponential_buckets(0.000_001, 4.0, 12).expect("parameters are valid; qed")),
+		&["target", "name"],
+	).expect("metric is valid; qed");
+}
+
+/// Register the span duration histogram filled by [`TracingReceiver::Prometheus`]
+/// in the given registry.
+///
+/// The tracing layer is set up before the node's registry exists, hence the
+/// histogram is kept globally and registered here once the registry is available.
+pub fn register_span_metrics(registry: &Registry) -> Result<(), PrometheusError> {
+	register(SPAN_DURATION.clone(), registry).map(drop)
+}
+
+/// TraceHandler for recording span durations as Prometheus metrics
+///
+/// Events are not recorded.
+pub struct PrometheusTraceHandler;
+
+impl TraceHandler for PrometheusTraceHandler {
+	fn handle_span(&self, span_datum: SpanDatum) {
+		SPAN_DURATION
+			.with_label_values(&[&span_datum.target, &span_datum.name])
+			.observe(span_datum.overall_time.as_secs_f64());
+	}
+
+	fn handle_event(&self, _event: TraceEvent) {}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
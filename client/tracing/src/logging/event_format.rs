@@ -18,7 +18,7 @@
 
 use ansi_term::Colour;
 use regex::Regex;
-use std::fmt::{self, Write};
+use std::{borrow::Cow, fmt::{self, Write}};
 use tracing::{Event, Level, Subscriber};
 use tracing_log::NormalizeEvent;
 use tracing_subscriber::{
@@ -45,6 +45,10 @@ pub struct EventFormat<T = SystemTime> {
 	pub enable_color: bool,
 	/// Duplicate INFO, WARN and ERROR messages to stdout.
 	pub dup_to_stdout: bool,
+	/// Write every event as a single line JSON object instead of human readable text.
+	///
+	/// Colors are always stripped from JSON output.
+	pub json: bool,
 }
 
 impl<T> EventFormat<T>
@@ -64,6 +68,10 @@ where
 		S: Subscriber + for<'a> LookupSpan<'a>,
 		N: for<'a> FormatFields<'a> + 'static,
 	{
+		if self.json {
+			return self.format_event_json(ctx, writer, event)
+		}
+
 		let writer = &mut MaybeColorWriter::new(self.enable_color, writer);
 		let normalized_meta = event.normalized_metadata();
 		let meta = normalized_meta.as_ref().unwrap_or_else(|| event.metadata());
@@ -92,15 +100,8 @@ where
 		}
 
 		// Custom code to display node name
-		if let Some(span) = ctx.lookup_current() {
-			let parents = span.parents();
-			for span in std::iter::once(span).chain(parents) {
-				let exts = span.extensions();
-				if let Some(prefix) = exts.get::<super::layers::Prefix>() {
-					write!(writer, "{}", prefix.as_str())?;
-					break;
-				}
-			}
+		if let Some(prefix) = node_prefix(&ctx) {
+			write!(writer, "{}", prefix)?;
 		}
 
 		ctx.format_fields(writer, event)?;
@@ -108,6 +109,111 @@ where
 
 		writer.write()
 	}
+
+	/// Writes `event` as a JSON object with the timestamp, level, target, node name and all
+	/// the fields of the event.
+	fn format_event_json<'b, S, N>(
+		&self,
+		ctx: CustomFmtContext<'b, S, N>,
+		writer: &mut dyn fmt::Write,
+		event: &Event,
+	) -> fmt::Result
+	where
+		S: Subscriber + for<'a> LookupSpan<'a>,
+		N: for<'a> FormatFields<'a> + 'static,
+	{
+		let normalized_meta = event.normalized_metadata();
+		let meta = normalized_meta.as_ref().unwrap_or_else(|| event.metadata());
+
+		let mut timestamp = String::new();
+		self.timer.format_time(&mut timestamp)?;
+
+		let mut visitor = JsonVisitor::default();
+		event.record(&mut visitor);
+
+		let mut line = serde_json::Map::new();
+		line.insert("timestamp".into(), timestamp.into());
+		line.insert("level".into(), meta.level().to_string().into());
+		line.insert("target".into(), meta.target().into());
+		if let Some(prefix) = node_prefix(&ctx) {
+			let name = prefix.trim().trim_start_matches('[').trim_end_matches(']');
+			line.insert("node".into(), name.into());
+		}
+		if let Some(message) = visitor.message {
+			line.insert("message".into(), strip_colors(&message).into());
+		}
+		if !visitor.fields.is_empty() {
+			line.insert("fields".into(), visitor.fields.into());
+		}
+
+		writeln!(writer, "{}", serde_json::Value::Object(line))
+	}
+}
+
+/// Returns the prefix of the closest span that has one, i.e. the node name.
+fn node_prefix<S, N>(ctx: &CustomFmtContext<S, N>) -> Option<String>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+	N: for<'a> FormatFields<'a> + 'static,
+{
+	let span = ctx.lookup_current()?;
+	let parents = span.parents();
+	for span in std::iter::once(span).chain(parents) {
+		let exts = span.extensions();
+		if let Some(prefix) = exts.get::<super::layers::Prefix>() {
+			return Some(prefix.as_str().to_owned())
+		}
+	}
+	None
+}
+
+/// Collects the fields of an event as JSON values.
+///
+/// The `message` field is kept apart and the `log.*` fields added by `tracing-log` are skipped,
+/// as they only duplicate the metadata.
+#[derive(Default)]
+struct JsonVisitor {
+	message: Option<String>,
+	fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl JsonVisitor {
+	fn insert(&mut self, field: &tracing::field::Field, value: serde_json::Value) {
+		if !field.name().starts_with("log.") {
+			self.fields.insert(field.name().into(), value);
+		}
+	}
+}
+
+impl tracing::field::Visit for JsonVisitor {
+	fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+		self.insert(field, value.into());
+	}
+
+	fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+		self.insert(field, value.into());
+	}
+
+	fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+		self.insert(field, value.into());
+	}
+
+	fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+		if field.name() == "message" {
+			self.message = Some(value.into());
+		} else {
+			self.insert(field, strip_colors(value).into());
+		}
+	}
+
+	fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+		let value = format!("{:?}", value);
+		if field.name() == "message" {
+			self.message = Some(value);
+		} else {
+			self.insert(field, strip_colors(&value).into());
+		}
+	}
 }
 
 // NOTE: the following code took inspiration from tracing-subscriber
@@ -330,15 +436,19 @@ impl<'a> MaybeColorWriter<'a> {
 
 	/// Write the buffered content to the `inner_writer`.
 	fn write(&mut self) -> fmt::Result {
-		lazy_static::lazy_static! {
-			static ref RE: Regex = Regex::new("\x1b\\[[^m]+m").expect("Error initializing color regex");
-		}
-
 		if !self.enable_color {
-			let replaced = RE.replace_all(&self.buffer, "");
-			self.inner_writer.write_str(&replaced)
+			self.inner_writer.write_str(&strip_colors(&self.buffer))
 		} else {
 			self.inner_writer.write_str(&self.buffer)
 		}
 	}
 }
+
+/// Removes all ANSI color escape sequences from `input`.
+fn strip_colors(input: &str) -> Cow<str> {
+	lazy_static::lazy_static! {
+		static ref RE: Regex = Regex::new("\x1b\\[[^m]+m").expect("Error initializing color regex");
+	}
+
+	RE.replace_all(input, "")
+}
@@ -75,6 +75,7 @@ fn prepare_subscriber<N, E, F, W>(
 	directives: &str,
 	profiling_targets: Option<&str>,
 	force_colors: Option<bool>,
+	json: bool,
 	builder_hook: impl Fn(
 		SubscriberBuilder<
 			format::DefaultFields,
@@ -168,6 +169,7 @@ where
 		display_thread_name: !simple,
 		enable_color,
 		dup_to_stdout: !atty::is(atty::Stream::Stderr) && atty::is(atty::Stream::Stdout),
+		json,
 	};
 	let builder = FmtSubscriber::builder().with_env_filter(env_filter);
 
@@ -197,6 +199,7 @@ pub struct LoggerBuilder {
 	profiling: Option<(crate::TracingReceiver, String)>,
 	log_reloading: bool,
 	force_colors: Option<bool>,
+	json: bool,
 }
 
 impl LoggerBuilder {
@@ -207,6 +210,7 @@ impl LoggerBuilder {
 			profiling: None,
 			log_reloading: true,
 			force_colors: None,
+			json: false,
 		}
 	}
 
@@ -232,6 +236,12 @@ impl LoggerBuilder {
 		self
 	}
 
+	/// Write every log line as a JSON object instead of human readable text.
+	pub fn with_json(&mut self, enable: bool) -> &mut Self {
+		self.json = enable;
+		self
+	}
+
 	/// Initialize the global logger
 	///
 	/// This sets various global logging and tracing instances and thus may only be called once.
@@ -242,6 +252,7 @@ impl LoggerBuilder {
 					&self.directives,
 					Some(&profiling_targets),
 					self.force_colors,
+					self.json,
 					|builder| enable_log_reloading!(builder),
 				)?;
 				let profiling = crate::ProfilingLayer::new(tracing_receiver, &profiling_targets);
@@ -254,6 +265,7 @@ impl LoggerBuilder {
 					&self.directives,
 					Some(&profiling_targets),
 					self.force_colors,
+					self.json,
 					|builder| builder,
 				)?;
 				let profiling = crate::ProfilingLayer::new(tracing_receiver, &profiling_targets);
@@ -268,6 +280,7 @@ impl LoggerBuilder {
 					&self.directives,
 					None,
 					self.force_colors,
+					self.json,
 					|builder| enable_log_reloading!(builder),
 				)?;
 
@@ -279,6 +292,7 @@ impl LoggerBuilder {
 					&self.directives,
 					None,
 					self.force_colors,
+					self.json,
 					|builder| builder,
 				)?;
 
@@ -422,6 +436,47 @@ mod tests {
 		log::info!("{}", EXPECTED_LOG_MESSAGE);
 	}
 
+	#[test]
+	fn json_log_lines() {
+		let executable = env::current_exe().unwrap();
+		let output = Command::new(executable)
+			.env("ENABLE_LOGGING", "1")
+			.args(&["--nocapture", "json_log_lines_entrypoint"])
+			.output()
+			.unwrap();
+
+		let output = String::from_utf8(output.stderr).unwrap();
+		let line: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+		assert_eq!(line["level"], "INFO");
+		assert_eq!(line["target"], "test-target");
+		assert_eq!(line["node"], EXPECTED_NODE_NAME);
+		assert_eq!(line["message"], EXPECTED_LOG_MESSAGE);
+		assert_eq!(line["fields"]["best"], 42);
+	}
+
+	/// This is not an actual test, it is used by the `json_log_lines` test.
+	/// The given test will call the test executable and only execute this one test that
+	/// only prints a colored event with a field through a JSON logger.
+	#[test]
+	fn json_log_lines_entrypoint() {
+		if env::var("ENABLE_LOGGING").is_ok() {
+			let mut builder = LoggerBuilder::new("");
+			builder.with_json(true);
+			builder.init().unwrap();
+			json_log_lines_process();
+		}
+	}
+
+	#[crate::logging::prefix_logs_with(EXPECTED_NODE_NAME)]
+	fn json_log_lines_process() {
+		tracing::info!(
+			target: "test-target",
+			best = 42u64,
+			"{}",
+			ansi_term::Colour::Yellow.paint(EXPECTED_LOG_MESSAGE),
+		);
+	}
+
 	/// This is not an actual test, it is used by the `do_not_write_with_colors_on_tty` test.
 	/// The given test will call the test executable and only execute this one test that
 	/// only prints a log line with some colors in it.
@@ -19,6 +19,7 @@
 use std::{
 	collections::HashMap,
 	sync::Arc,
+	time::Duration,
 };
 
 use futures::Future;
@@ -106,6 +107,8 @@ pub struct Options {
 	pub future: base::Limit,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
+	/// How long a transaction is banned from re-entering the pool.
+	pub ban_time: Duration,
 }
 
 impl Default for Options {
@@ -120,6 +123,7 @@ impl Default for Options {
 				total_bytes: 1 * 1024 * 1024,
 			},
 			reject_future_transactions: false,
+			ban_time: Duration::from_secs(60 * 30),
 		}
 	}
 }
@@ -48,8 +48,15 @@ pub struct PoolRotator<Hash> {
 
 impl<Hash: hash::Hash + Eq> Default for PoolRotator<Hash> {
 	fn default() -> Self {
+		Self::new(Duration::from_secs(60 * 30))
+	}
+}
+
+impl<Hash: hash::Hash + Eq> PoolRotator<Hash> {
+	/// Creates a new rotator that bans extrinsics for the given `ban_time`.
+	pub fn new(ban_time: Duration) -> Self {
 		Self {
-			ban_time: Duration::from_secs(60 * 30),
+			ban_time,
 			banned_until: Default::default(),
 		}
 	}
@@ -27,7 +27,7 @@ use frame_benchmarking::{
 	benchmarks_instance_pallet, account, whitelisted_caller, whitelist_account, impl_benchmark_test_suite
 };
 use frame_support::traits::Get;
-use frame_support::{traits::EnsureOrigin, dispatch::UnfilteredDispatchable};
+use frame_support::{traits::{EnsureOrigin, EnsureOriginWithArg}, dispatch::UnfilteredDispatchable};
 
 use crate::Pallet as Assets;
 
@@ -137,10 +137,12 @@ fn assert_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event
 
 benchmarks_instance_pallet! {
 	create {
-		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = Default::default();
+		let origin = T::CreateOrigin::successful_origin(&asset_id);
+		let caller = T::CreateOrigin::ensure_origin(origin.clone(), &asset_id).unwrap();
 		let caller_lookup = T::Lookup::unlookup(caller.clone());
 		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T, I>::max_value());
-	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup, 1u32.into())
+	}: _<T::Origin>(origin, asset_id, caller_lookup, 1u32.into())
 	verify {
 		assert_last_event::<T, I>(Event::Created(Default::default(), caller.clone(), caller).into());
 	}
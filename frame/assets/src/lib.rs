@@ -147,7 +147,9 @@ use sp_runtime::{
 };
 use codec::{Encode, Decode, HasCompact};
 use frame_support::{ensure, dispatch::{DispatchError, DispatchResult}};
-use frame_support::traits::{Currency, ReservableCurrency, BalanceStatus::Reserved, StoredMap};
+use frame_support::traits::{
+	Currency, ReservableCurrency, BalanceStatus::Reserved, StoredMap, EnsureOriginWithArg,
+};
 use frame_support::traits::tokens::{WithdrawConsequence, DepositConsequence, fungibles};
 use frame_system::Config as SystemConfig;
 
@@ -182,6 +184,14 @@ pub mod pallet {
 		/// The currency mechanism.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
+		/// The origin which may create an asset with the given id. Its success value is the
+		/// account which owns the new asset and pays the deposit.
+		type CreateOrigin: EnsureOriginWithArg<
+			Self::Origin,
+			Self::AssetId,
+			Success = Self::AccountId,
+		>;
+
 		/// The origin which may forcibly create or destroy an asset or otherwise alter privileged
 		/// attributes.
 		type ForceOrigin: EnsureOrigin<Self::Origin>;
@@ -347,7 +357,8 @@ pub mod pallet {
 		///
 		/// This new asset class has no assets initially and its owner is the origin.
 		///
-		/// The origin must be Signed and the sender must have sufficient funds free.
+		/// The origin must conform to `CreateOrigin` and the sender must have sufficient funds
+		/// free.
 		///
 		/// Funds of sender are reserved by `AssetDeposit`.
 		///
@@ -369,7 +380,7 @@ pub mod pallet {
 			admin: <T::Lookup as StaticLookup>::Source,
 			min_balance: T::Balance,
 		) -> DispatchResult {
-			let owner = ensure_signed(origin)?;
+			let owner = T::CreateOrigin::ensure_origin(origin, &id)?;
 			let admin = T::Lookup::lookup(admin)?;
 
 			ensure!(!Asset::<T, I>::contains_key(id), Error::<T, I>::InUse);
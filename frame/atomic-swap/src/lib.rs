@@ -29,6 +29,10 @@
 //! is used to allow the target to approve (claim) the swap. If the swap is not
 //! claimed within a specified duration of time, the sender may cancel it.
 //!
+//! What is sent is defined by the [`SwapAction`] of the runtime: balances with
+//! [`BalanceSwapAction`], fungible assets with [`AssetSwapAction`] or non-fungible
+//! items with [`NftSwapAction`].
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -45,13 +49,13 @@ mod tests;
 use sp_std::{prelude::*, marker::PhantomData, ops::{Deref, DerefMut}};
 use sp_io::hashing::blake2_256;
 use frame_support::{
-	RuntimeDebugNoBound,
-	traits::{Get, Currency, ReservableCurrency, BalanceStatus},
+	CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	traits::{Get, Currency, ReservableCurrency, BalanceStatus, tokens::{fungibles, nonfungibles}},
 	weights::Weight,
-	dispatch::DispatchResult,
+	dispatch::{DispatchResult, Parameter},
 };
 use codec::{Encode, Decode};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, DispatchError};
 
 /// Pending atomic swap operation.
 #[derive(Clone, Eq, PartialEq, RuntimeDebugNoBound, Encode, Decode)]
@@ -134,6 +138,112 @@ impl<T: Config, AccountId, C> SwapAction<AccountId, T> for BalanceSwapAction<Acc
 	}
 }
 
+/// A swap action that transfers an amount of one of a set of fungible assets.
+///
+/// Assets can not be reserved in general, so they are held by the `Escrow` account while the swap
+/// is pending, which should be an account nobody controls, such as one derived from a `PalletId`.
+#[derive(CloneNoBound, RuntimeDebugNoBound, EqNoBound, PartialEqNoBound, Encode, Decode)]
+pub struct AssetSwapAction<AccountId, A, Escrow> where
+	A: fungibles::Transfer<AccountId>,
+	A::AssetId: Parameter,
+	A::Balance: Parameter,
+{
+	asset: A::AssetId,
+	amount: A::Balance,
+	_marker: PhantomData<(AccountId, Escrow)>,
+}
+
+impl<AccountId, A, Escrow> AssetSwapAction<AccountId, A, Escrow> where
+	A: fungibles::Transfer<AccountId>,
+	A::AssetId: Parameter,
+	A::Balance: Parameter,
+{
+	/// Create a new swap action of `amount` of `asset`.
+	pub fn new(asset: A::AssetId, amount: A::Balance) -> Self {
+		Self { asset, amount, _marker: PhantomData }
+	}
+}
+
+impl<T, AccountId, A, Escrow> SwapAction<AccountId, T> for AssetSwapAction<AccountId, A, Escrow>
+where
+	T: Config,
+	A: fungibles::Transfer<AccountId>,
+	A::AssetId: Parameter,
+	A::Balance: Parameter,
+	Escrow: Get<AccountId>,
+{
+	fn reserve(&self, source: &AccountId) -> DispatchResult {
+		A::transfer(self.asset, source, &Escrow::get(), self.amount, false).map(|_| ())
+	}
+
+	fn claim(&self, _source: &AccountId, target: &AccountId) -> bool {
+		A::transfer(self.asset, &Escrow::get(), target, self.amount, false).is_ok()
+	}
+
+	fn weight(&self) -> Weight {
+		T::DbWeight::get().reads_writes(4, 4)
+	}
+
+	fn cancel(&self, source: &AccountId) {
+		let _ = A::transfer(self.asset, &Escrow::get(), source, self.amount, false);
+	}
+}
+
+/// A swap action that transfers a single item of a set of non-fungible collections.
+///
+/// The item is held by the `Escrow` account while the swap is pending, which should be an account
+/// nobody controls, such as one derived from a `PalletId`.
+#[derive(CloneNoBound, RuntimeDebugNoBound, EqNoBound, PartialEqNoBound, Encode, Decode)]
+pub struct NftSwapAction<AccountId, N, Escrow> where
+	N: nonfungibles::Transfer<AccountId>,
+	N::CollectionId: Parameter,
+	N::ItemId: Parameter,
+{
+	collection: N::CollectionId,
+	item: N::ItemId,
+	_marker: PhantomData<(AccountId, Escrow)>,
+}
+
+impl<AccountId, N, Escrow> NftSwapAction<AccountId, N, Escrow> where
+	N: nonfungibles::Transfer<AccountId>,
+	N::CollectionId: Parameter,
+	N::ItemId: Parameter,
+{
+	/// Create a new swap action of `item` of `collection`.
+	pub fn new(collection: N::CollectionId, item: N::ItemId) -> Self {
+		Self { collection, item, _marker: PhantomData }
+	}
+}
+
+impl<T, AccountId, N, Escrow> SwapAction<AccountId, T> for NftSwapAction<AccountId, N, Escrow>
+where
+	T: Config,
+	N: nonfungibles::Transfer<AccountId>,
+	N::CollectionId: Parameter,
+	N::ItemId: Parameter,
+	AccountId: PartialEq,
+	Escrow: Get<AccountId>,
+{
+	fn reserve(&self, source: &AccountId) -> DispatchResult {
+		if N::owner(&self.collection, &self.item).as_ref() != Some(source) {
+			return Err(DispatchError::Other("Swapped item not owned by source"))
+		}
+		N::transfer(&self.collection, &self.item, &Escrow::get())
+	}
+
+	fn claim(&self, _source: &AccountId, target: &AccountId) -> bool {
+		N::transfer(&self.collection, &self.item, target).is_ok()
+	}
+
+	fn weight(&self) -> Weight {
+		T::DbWeight::get().reads_writes(3, 3)
+	}
+
+	fn cancel(&self, source: &AccountId) {
+		let _ = N::transfer(&self.collection, &self.item, source);
+	}
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -3,7 +3,10 @@
 use super::*;
 use crate as pallet_atomic_swap;
 
-use frame_support::parameter_types;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{AsEnsureOriginWithArg, UnfilteredDispatchable},
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -22,6 +25,8 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		AtomicSwap: pallet_atomic_swap::{Pallet, Call, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -67,6 +72,52 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const AssetDeposit: u64 = 1;
+	pub const ApprovalDeposit: u64 = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+}
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type WeightInfo = ();
+	type Extra = ();
+}
+parameter_types! {
+	pub const CollectionDeposit: u64 = 2;
+	pub const ItemDeposit: u64 = 1;
+	pub const KeyLimit: u32 = 50;
+	pub const ValueLimit: u32 = 50;
+	pub const AttributeDepositBase: u64 = 1;
+}
+impl pallet_uniques::Config for Test {
+	type Event = Event;
+	type CollectionId = u32;
+	type ItemId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type CollectionDeposit = CollectionDeposit;
+	type ItemDeposit = ItemDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = MetadataDepositPerByte;
+	type StringLimit = StringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = ();
+}
 parameter_types! {
 	pub const ProofLimit: u32 = 1024;
 	pub const ExpireDuration: u64 = 100;
@@ -77,6 +128,10 @@ impl Config for Test {
 	type ProofLimit = ProofLimit;
 }
 
+parameter_types! {
+	pub const Escrow: u64 = 99;
+}
+
 const A: u64 = 1;
 const B: u64 = 2;
 
@@ -154,3 +209,54 @@ fn two_party_successful_swap() {
 		assert_eq!(Balances::free_balance(B), 200 + 50);
 	});
 }
+
+#[test]
+fn asset_swap_action_works() {
+	new_test_ext().execute_with(|| {
+		use fungibles::Mutate;
+		type Action = AssetSwapAction<u64, Assets, Escrow>;
+
+		assert_ok!(pallet_assets::Call::<Test>::force_create(0, A, true, 1)
+			.dispatch_bypass_filter(Origin::root()));
+		assert_ok!(<Assets as Mutate<u64>>::mint_into(0, &A, 100));
+
+		let action = Action::new(0, 60);
+		assert_ok!(SwapAction::<u64, Test>::reserve(&action, &A));
+		assert_eq!(Assets::balance(0, &A), 40);
+		assert_eq!(Assets::balance(0, &Escrow::get()), 60);
+		assert!(SwapAction::<u64, Test>::reserve(&action, &A).is_err());
+
+		assert!(SwapAction::<u64, Test>::claim(&action, &A, &B));
+		assert_eq!(Assets::balance(0, &Escrow::get()), 0);
+		assert_eq!(Assets::balance(0, &B), 60);
+
+		let action = Action::new(0, 40);
+		assert_ok!(SwapAction::<u64, Test>::reserve(&action, &A));
+		SwapAction::<u64, Test>::cancel(&action, &A);
+		assert_eq!(Assets::balance(0, &A), 40);
+	});
+}
+
+#[test]
+fn nft_swap_action_works() {
+	new_test_ext().execute_with(|| {
+		use nonfungibles::{Inspect, Mutate};
+		type Action = NftSwapAction<u64, Uniques, Escrow>;
+
+		assert_ok!(pallet_uniques::Call::<Test>::force_create(0, A, true)
+			.dispatch_bypass_filter(Origin::root()));
+		assert_ok!(<Uniques as Mutate<u64>>::mint_into(&0, &42, &A));
+
+		let action = Action::new(0, 42);
+		assert!(SwapAction::<u64, Test>::reserve(&action, &B).is_err());
+		assert_ok!(SwapAction::<u64, Test>::reserve(&action, &A));
+		assert_eq!(Uniques::owner(&0, &42), Some(Escrow::get()));
+
+		SwapAction::<u64, Test>::cancel(&action, &A);
+		assert_eq!(Uniques::owner(&0, &42), Some(A));
+
+		assert_ok!(SwapAction::<u64, Test>::reserve(&action, &A));
+		assert!(SwapAction::<u64, Test>::claim(&action, &A, &B));
+		assert_eq!(Uniques::owner(&0, &42), Some(B));
+	});
+}
@@ -19,11 +19,15 @@
 
 #![cfg(test)]
 
+use std::cell::RefCell;
+
 use crate as pallet_aura;
-use sp_consensus_aura::ed25519::AuthorityId;
+use sp_consensus_aura::{ed25519::AuthorityId, AuthorityIndex};
 use sp_runtime::{traits::IdentityLookup, testing::{Header, UintAuthorityId}};
-use frame_support::{parameter_types, traits::GenesisBuild};
-use sp_core::H256;
+use frame_support::{
+	parameter_types, traits::{DisabledValidators, GenesisBuild, KeyOwnerProofSystem},
+};
+use sp_core::{crypto::KeyTypeId, H256};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -45,6 +49,7 @@ parameter_types! {
 	pub BlockWeights: frame_system::limits::BlockWeights =
 		frame_system::limits::BlockWeights::simple_max(1024);
 	pub const MinimumPeriod: u64 = 1;
+	pub const MaxDrift: u64 = 30_000;
 }
 
 impl frame_system::Config for Test {
@@ -77,11 +82,45 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 
+thread_local! {
+	static DISABLED_VALIDATORS: RefCell<Vec<AuthorityIndex>> = RefCell::new(Default::default());
+}
+
+pub struct MockDisabledValidators;
+
+impl MockDisabledValidators {
+	pub fn disable_validator(index: AuthorityIndex) {
+		DISABLED_VALIDATORS.with(|v| {
+			let mut disabled = v.borrow_mut();
+			if let Err(i) = disabled.binary_search(&index) {
+				disabled.insert(i, index);
+			}
+		})
+	}
+}
+
+impl DisabledValidators for MockDisabledValidators {
+	fn is_disabled(index: AuthorityIndex) -> bool {
+		DISABLED_VALIDATORS.with(|v| v.borrow().binary_search(&index).is_ok())
+	}
+}
+
 impl pallet_aura::Config for Test {
 	type AuthorityId = AuthorityId;
+	type DisabledValidators = MockDisabledValidators;
+	type KeyOwnerProofSystem = ();
+	type KeyOwnerProof =
+		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, AuthorityId)>>::Proof;
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		AuthorityId,
+	)>>::IdentificationTuple;
+	type HandleEquivocation = ();
+	type WeightInfo = ();
 }
 
 pub fn new_test_ext(authorities: Vec<u64>) -> sp_io::TestExternalities {
@@ -19,7 +19,13 @@
 
 #![cfg(test)]
 
-use crate::mock::{Aura, new_test_ext};
+use codec::Encode;
+use frame_support::traits::OnInitialize;
+use frame_system::InitKind;
+use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_runtime::{Digest, DigestItem};
+
+use crate::mock::{Aura, MockDisabledValidators, System, new_test_ext};
 
 #[test]
 fn initial_values() {
@@ -28,3 +34,25 @@ fn initial_values() {
 		assert_eq!(Aura::authorities().len(), 4);
 	});
 }
+
+#[test]
+#[should_panic(
+	expected = "Validator with index 1 is disabled and should not be attempting to author blocks."
+)]
+fn disabled_validators_cannot_author_blocks() {
+	new_test_ext(vec![0, 1, 2, 3]).execute_with(|| {
+		// slot 1 should be authored by validator at index 1
+		let slot = Slot::from(1);
+		let pre_digest = Digest {
+			logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())],
+		};
+
+		System::initialize(&42, &System::parent_hash(), &pre_digest, InitKind::Full);
+
+		// let's disable the validator
+		MockDisabledValidators::disable_validator(1);
+
+		// and we should not be able to initialize the block
+		Aura::on_initialize(42);
+	});
+}
@@ -24,7 +24,10 @@
 use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::DispatchResultWithPostInfo,
-	traits::{FindAuthor, Get, KeyOwnerProofSystem, OneSessionHandler, OnTimestampSet},
+	traits::{
+		DisabledValidators, FindAuthor, Get, KeyOwnerProofSystem, OneSessionHandler,
+		OnTimestampSet,
+	},
 	weights::{Pays, Weight},
 };
 use sp_application_crypto::Public;
@@ -161,6 +164,11 @@ pub mod pallet {
 		/// definition.
 		type HandleEquivocation: HandleEquivocation<Self>;
 
+		/// A way to check whether a given validator is disabled and should not be authoring blocks.
+		/// Blocks authored by a disabled validator will lead to a panic as part of this module's
+		/// initialization.
+		type DisabledValidators: DisabledValidators;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -343,6 +351,20 @@ pub mod pallet {
 			// remove temporary "environment" entry from storage
 			Lateness::<T>::kill();
 		}
+
+		fn integrity_test() {
+			let slot_duration = Self::slot_duration();
+			assert!(!slot_duration.is_zero(), "Babe slot duration cannot be zero.");
+			// The slot duration saturates if `MinimumPeriod` is too large.
+			assert!(
+				slot_duration / 2u32.into() == <T as pallet_timestamp::Config>::MinimumPeriod::get(),
+				"Babe slot duration must be exactly twice the timestamp `MinimumPeriod`.",
+			);
+			assert!(
+				T::ExpectedBlockTime::get() >= slot_duration,
+				"`ExpectedBlockTime` cannot be shorter than the Babe slot duration.",
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -653,6 +675,17 @@ impl<T: Config> Pallet<T> {
 			})
 			.next();
 
+		// ensure that the block author isn't disabled
+		if let Some(ref pre_digest) = maybe_pre_digest {
+			let authority_index = pre_digest.authority_index();
+			if T::DisabledValidators::is_disabled(authority_index) {
+				panic!(
+					"Validator with index {:?} is disabled and should not be attempting to author blocks.",
+					authority_index,
+				);
+			}
+		}
+
 		let is_primary = matches!(maybe_pre_digest, Some(PreDigest::Primary(..)));
 
 		let maybe_randomness: MaybeRandomness = maybe_pre_digest.and_then(|digest| {
@@ -140,12 +140,14 @@ impl pallet_authorship::Config for Test {
 
 parameter_types! {
 	pub const MinimumPeriod: u64 = 1;
+	pub const MaxDrift: u64 = 30_000;
 }
 
 impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = Babe;
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 
@@ -211,6 +213,7 @@ impl pallet_staking::Config for Test {
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type NextNewSession = Session;
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
 	type WeightInfo = ();
 }
 
@@ -228,7 +231,7 @@ impl pallet_offences::Config for Test {
 
 parameter_types! {
 	pub const EpochDuration: u64 = 3;
-	pub const ExpectedBlockTime: u64 = 1;
+	pub const ExpectedBlockTime: u64 = 2;
 	pub const ReportLongevity: u64 =
 		BondingDuration::get() as u64 * SessionsPerEra::get() as u64 * EpochDuration::get();
 }
@@ -251,6 +254,7 @@ impl Config for Test {
 	type HandleEquivocation =
 		super::EquivocationHandler<Self::KeyOwnerIdentification, Offences, ReportLongevity>;
 
+	type DisabledValidators = Session;
 	type WeightInfo = ();
 }
 
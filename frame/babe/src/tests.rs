@@ -894,6 +894,23 @@ fn valid_equivocation_reports_dont_pay_fees() {
 	})
 }
 
+#[test]
+#[should_panic(
+	expected = "Validator with index 0 is disabled and should not be attempting to author blocks."
+)]
+fn disabled_validators_cannot_author_blocks() {
+	new_test_ext(4).execute_with(|| {
+		start_era(1);
+
+		// let's disable the validator at index 0
+		Session::disable_index(0);
+
+		// the mocking infrastructure always authors all blocks using authority index 0,
+		// so we should panic when we try to author the next block
+		progress_to_block(System::block_number() + 1);
+	});
+}
+
 #[test]
 fn add_epoch_configurations_migration_works() {
 	use frame_support::storage::migration::{
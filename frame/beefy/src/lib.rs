@@ -0,0 +1,213 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # BEEFY Pallet
+//!
+//! Manages the BEEFY authority set and announces it to the client side gadget.
+//!
+//! The authority set follows the session authorities. Whenever it changes, a
+//! `ConsensusLog::AuthoritiesChange` digest is deposited together with the new, incremented
+//! validator set id. [`DepositBeefyDigest`] can be used as the `OnNewRoot` hook of the MMR pallet
+//! to put the MMR root into the block digest, so that BEEFY voters can sign it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Encode;
+use frame_support::{Parameter, traits::OneSessionHandler};
+use sp_runtime::{
+	generic::DigestItem,
+	traits::{IsMember, Member},
+	RuntimeAppPublic,
+};
+use sp_std::prelude::*;
+
+use sp_consensus_beefy::{
+	AuthorityIndex, ConsensusLog, MmrRootHash, ValidatorSet, BEEFY_ENGINE_ID,
+	GENESIS_AUTHORITY_SET_ID,
+};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Authority identifier type
+		type BeefyId: Member + Parameter + RuntimeAppPublic + Default + MaybeSerializeDeserialize;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+
+	/// The current authorities set
+	#[pallet::storage]
+	#[pallet::getter(fn authorities)]
+	pub(super) type Authorities<T: Config> = StorageValue<_, Vec<T::BeefyId>, ValueQuery>;
+
+	/// The current validator set id
+	#[pallet::storage]
+	#[pallet::getter(fn validator_set_id)]
+	pub(super) type ValidatorSetId<T: Config> =
+		StorageValue<_, sp_consensus_beefy::ValidatorSetId, ValueQuery>;
+
+	/// Authorities set scheduled to be used with the next session
+	#[pallet::storage]
+	#[pallet::getter(fn next_authorities)]
+	pub(super) type NextAuthorities<T: Config> = StorageValue<_, Vec<T::BeefyId>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub authorities: Vec<T::BeefyId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { authorities: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			Pallet::<T>::initialize_authorities(&self.authorities);
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Return the current active BEEFY validator set.
+	pub fn validator_set() -> ValidatorSet<T::BeefyId> {
+		ValidatorSet::<T::BeefyId> {
+			validators: Self::authorities(),
+			id: Self::validator_set_id(),
+		}
+	}
+
+	fn change_authorities(new: Vec<T::BeefyId>, queued: Vec<T::BeefyId>) {
+		// As in GRANDPA, we trigger a validator set change only if the the validator
+		// set has actually changed.
+		if new != Self::authorities() {
+			<Authorities<T>>::put(&new);
+
+			let next_id = Self::validator_set_id() + 1u64;
+			<ValidatorSetId<T>>::put(next_id);
+
+			let log: DigestItem<T::Hash> = DigestItem::Consensus(
+				BEEFY_ENGINE_ID,
+				ConsensusLog::AuthoritiesChange(ValidatorSet { validators: new, id: next_id })
+					.encode(),
+			);
+			<frame_system::Pallet<T>>::deposit_log(log);
+		}
+
+		<NextAuthorities<T>>::put(&queued);
+	}
+
+	fn initialize_authorities(authorities: &[T::BeefyId]) {
+		if authorities.is_empty() {
+			return;
+		}
+
+		assert!(
+			<Authorities<T>>::get().is_empty(),
+			"Authorities are already initialized!"
+		);
+
+		<Authorities<T>>::put(authorities);
+		<ValidatorSetId<T>>::put(GENESIS_AUTHORITY_SET_ID);
+		// Like `pallet_session`, initialize the next validator set as well.
+		<NextAuthorities<T>>::put(authorities);
+	}
+}
+
+impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
+	type Public = T::BeefyId;
+}
+
+impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
+	type Key = T::BeefyId;
+
+	fn on_genesis_session<'a, I: 'a>(validators: I)
+	where
+		I: Iterator<Item = (&'a T::AccountId, T::BeefyId)>,
+	{
+		let authorities = validators.map(|(_, k)| k).collect::<Vec<_>>();
+		// we panic here as runtime maintainers can simply reconfigure genesis and restart the
+		// chain easily
+		Self::initialize_authorities(&authorities);
+	}
+
+	fn on_new_session<'a, I: 'a>(changed: bool, validators: I, queued_validators: I)
+	where
+		I: Iterator<Item = (&'a T::AccountId, T::BeefyId)>,
+	{
+		if changed {
+			let next_authorities = validators.map(|(_, k)| k).collect::<Vec<_>>();
+			let next_queued_authorities = queued_validators.map(|(_, k)| k).collect::<Vec<_>>();
+
+			Self::change_authorities(next_authorities, next_queued_authorities);
+		}
+	}
+
+	fn on_disabled(i: usize) {
+		let log: DigestItem<T::Hash> = DigestItem::Consensus(
+			BEEFY_ENGINE_ID,
+			ConsensusLog::<T::BeefyId>::OnDisabled(i as AuthorityIndex).encode(),
+		);
+
+		<frame_system::Pallet<T>>::deposit_log(log);
+	}
+}
+
+impl<T: Config> IsMember<T::BeefyId> for Pallet<T> {
+	fn is_member(authority_id: &T::BeefyId) -> bool {
+		Self::authorities().iter().any(|id| id == authority_id)
+	}
+}
+
+/// A BEEFY consensus digest item with MMR root hash.
+///
+/// Use it as the `OnNewRoot` hook of the MMR pallet to deposit each new MMR root as a
+/// `ConsensusLog::MmrRoot` digest. BEEFY voters sign this root.
+pub struct DepositBeefyDigest<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> pallet_mmr_primitives::OnNewRoot<MmrRootHash> for DepositBeefyDigest<T> {
+	fn on_new_root(root: &MmrRootHash) {
+		let digest = DigestItem::Consensus(
+			BEEFY_ENGINE_ID,
+			ConsensusLog::<T::BeefyId>::MmrRoot(*root).encode(),
+		);
+		<frame_system::Pallet<T>>::deposit_log(digest);
+	}
+}
@@ -71,6 +71,10 @@
 //! - `claim_bounty` - Claim a specific bounty amount from the Payout Address.
 //! - `unassign_curator` - Unassign an accepted curator from a specific earmark.
 //! - `close_bounty` - Cancel the earmark for a specific treasury amount and close the bounty.
+//!
+//! An active bounty may be subdivided into child-bounties by its curator through a
+//! `ChildBountyManager` such as `pallet-child-bounties`. A bounty cannot be awarded or closed while
+//! it has active child-bounties.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -131,6 +135,9 @@ pub trait Config: frame_system::Config + pallet_treasury::Config {
 
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
+
+	/// The child-bounty manager.
+	type ChildBountyManager: ChildBountyManager<BalanceOf<Self>>;
 }
 
 /// An index of a bounty. Just a `u32`.
@@ -153,6 +160,13 @@ pub struct Bounty<AccountId, Balance, BlockNumber> {
 	status: BountyStatus<AccountId, BlockNumber>,
 }
 
+impl<AccountId: Clone, Balance, BlockNumber: Clone> Bounty<AccountId, Balance, BlockNumber> {
+	/// Getter for bounty status, to be used for child bounties.
+	pub fn get_status(&self) -> BountyStatus<AccountId, BlockNumber> {
+		self.status.clone()
+	}
+}
+
 /// The status of a bounty proposal.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub enum BountyStatus<AccountId, BlockNumber> {
@@ -185,6 +199,25 @@ pub enum BountyStatus<AccountId, BlockNumber> {
 	},
 }
 
+/// The child-bounty manager.
+pub trait ChildBountyManager<Balance> {
+	/// Get the active child-bounties for a parent bounty.
+	fn child_bounties_count(bounty_id: BountyIndex) -> BountyIndex;
+
+	/// Take total curator fees of children-bounty curators.
+	fn children_curator_fees(bounty_id: BountyIndex) -> Balance;
+}
+
+impl<Balance: Zero> ChildBountyManager<Balance> for () {
+	fn child_bounties_count(_bounty_id: BountyIndex) -> BountyIndex {
+		Default::default()
+	}
+
+	fn children_curator_fees(_bounty_id: BountyIndex) -> Balance {
+		Zero::zero()
+	}
+}
+
 // Note :: For backward compatibility reasons,
 // pallet-bounties uses Treasury for storage.
 // This is temporary solution, soon will get replaced with
@@ -253,6 +286,8 @@ decl_error! {
 		PendingPayout,
 		/// The bounties cannot be claimed/closed because it's still in the countdown period.
 		Premature,
+		/// The bounty cannot be closed because it has active child-bounties.
+		HasActiveChildBounty,
 	}
 }
 
@@ -516,6 +551,13 @@ decl_module! {
 					},
 					_ => return Err(Error::<T>::UnexpectedStatus.into()),
 				}
+
+				// Ensure no active child-bounties before processing the call.
+				ensure!(
+					T::ChildBountyManager::child_bounties_count(bounty_id) == 0,
+					Error::<T>::HasActiveChildBounty
+				);
+
 				bounty.status = BountyStatus::PendingPayout {
 					curator: signer,
 					beneficiary: beneficiary.clone(),
@@ -548,6 +590,10 @@ decl_module! {
 					let bounty_account = Self::bounty_account_id(bounty_id);
 					let balance = T::Currency::free_balance(&bounty_account);
 					let fee = bounty.fee.min(balance); // just to be safe
+					// Fees already paid to child-bounty curators come out of the parent curator's fee.
+					let children_fee = T::ChildBountyManager::children_curator_fees(bounty_id);
+					debug_assert!(children_fee <= fee);
+					let fee = fee.saturating_sub(children_fee);
 					let payout = balance.saturating_sub(fee);
 					let err_amount = T::Currency::unreserve(&curator, bounty.curator_deposit);
 					debug_assert!(err_amount.is_zero());
@@ -585,6 +631,12 @@ decl_module! {
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResultWithPostInfo {
 				let bounty = maybe_bounty.as_ref().ok_or(Error::<T>::InvalidIndex)?;
 
+				// Ensure no active child-bounties before processing the call.
+				ensure!(
+					T::ChildBountyManager::child_bounties_count(bounty_id) == 0,
+					Error::<T>::HasActiveChildBounty
+				);
+
 				match &bounty.status {
 					BountyStatus::Proposed => {
 						// The reject origin would like to cancel a proposed bounty.
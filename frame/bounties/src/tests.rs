@@ -25,7 +25,7 @@ use std::cell::RefCell;
 
 use frame_support::{
 	assert_noop, assert_ok, parameter_types, weights::Weight, traits::OnInitialize,
-	PalletId
+	traits::tokens::{PayFromAccount, UnityAssetBalanceConversion}, PalletId
 };
 
 use sp_core::H256;
@@ -106,6 +106,8 @@ parameter_types! {
 	pub const DataDepositPerByte: u64 = 1;
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
 	pub const MaxApprovals: u32 = 100;
+	pub const SpendPayoutPeriod: u64 = 5;
+	pub TreasuryAccount: u128 = Treasury::account_id();
 }
 // impl pallet_treasury::Config for Test {
 impl pallet_treasury::Config for Test {
@@ -123,6 +125,13 @@ impl pallet_treasury::Config for Test {
 	type WeightInfo = ();
 	type SpendFunds = Bounties;
 	type MaxApprovals = MaxApprovals;
+	type SpendOrigin = frame_system::EnsureNever<u64>;
+	type AssetKind = ();
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type Paymaster = PayFromAccount<Balances, TreasuryAccount, u128>;
+	type BalanceConverter = UnityAssetBalanceConversion;
+	type PayoutPeriod = SpendPayoutPeriod;
 }
 parameter_types! {
 	pub const BountyDepositBase: u64 = 80;
@@ -142,6 +151,7 @@ impl Config for Test {
 	type DataDepositPerByte = DataDepositPerByte;
 	type MaximumReasonLength = MaximumReasonLength;
 	type WeightInfo = ();
+	type ChildBountyManager = ();
 }
 
 type TreasuryError = pallet_treasury::Error::<Test, pallet_treasury::DefaultInstance>;
@@ -0,0 +1,529 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Child-bounties pallet tests.
+
+use crate as pallet_child_bounties;
+use super::*;
+
+use frame_support::{
+	assert_noop, assert_ok, parameter_types, traits::OnInitialize, weights::Weight, PalletId,
+	traits::tokens::{PayFromAccount, UnityAssetBalanceConversion},
+};
+
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		ChildBounties: pallet_child_bounties::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u128; // u64 is not enough to hold bytes used to generate bounty account
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u64 = 1;
+	pub const SpendPeriod: u64 = 2;
+	pub const Burn: Permill = Permill::from_percent(50);
+	pub const DataDepositPerByte: u64 = 1;
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxApprovals: u32 = 100;
+	pub const SpendPayoutPeriod: u64 = 5;
+	pub TreasuryAccount: u128 = Treasury::account_id();
+}
+
+impl pallet_treasury::Config for Test {
+	type PalletId = TreasuryPalletId;
+	type Currency = pallet_balances::Pallet<Test>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type Event = Event;
+	type OnSlash = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type WeightInfo = ();
+	type SpendFunds = Bounties;
+	type MaxApprovals = MaxApprovals;
+	type SpendOrigin = frame_system::EnsureNever<u64>;
+	type AssetKind = ();
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type Paymaster = PayFromAccount<Balances, TreasuryAccount, u128>;
+	type BalanceConverter = UnityAssetBalanceConversion;
+	type PayoutPeriod = SpendPayoutPeriod;
+}
+
+parameter_types! {
+	pub const BountyDepositBase: u64 = 80;
+	pub const BountyDepositPayoutDelay: u64 = 3;
+	pub const BountyUpdatePeriod: u32 = 10;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: u64 = 5;
+	pub const MaximumReasonLength: u32 = 300;
+}
+
+impl pallet_bounties::Config for Test {
+	type Event = Event;
+	type BountyDepositBase = BountyDepositBase;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = ();
+	type ChildBountyManager = ChildBounties;
+}
+
+parameter_types! {
+	pub const MaxActiveChildBountyCount: u32 = 2;
+	pub const ChildBountyValueMinimum: u64 = 1;
+	pub const ChildBountyCuratorDepositBase: Permill = Permill::from_percent(50);
+}
+
+impl Config for Test {
+	type Event = Event;
+	type MaxActiveChildBountyCount = MaxActiveChildBountyCount;
+	type ChildBountyValueMinimum = ChildBountyValueMinimum;
+	type ChildBountyCuratorDepositBase = ChildBountyCuratorDepositBase;
+	type WeightInfo = ();
+}
+
+type BountiesError = pallet_bounties::Error<Test>;
+type ChildBountiesEvent<T> = pallet_child_bounties::Event<T>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		// Total issuance will be 200 with treasury account initialized at ED.
+		balances: vec![(0, 100), (1, 98), (2, 1)],
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test, _>(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn last_event() -> ChildBountiesEvent<Test> {
+	System::events().into_iter().map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::pallet_child_bounties(inner) = e { Some(inner) } else { None }
+		})
+		.last()
+		.unwrap()
+}
+
+/// Create bounty 0 of value 50 with curator 4 and a curator fee of 6, and make it active.
+fn create_active_parent_bounty() {
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	Balances::make_free_balance_be(&4, 10);
+	assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+	assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+	System::set_block_number(2);
+	<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+	assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 6));
+	assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+
+	// Initial 10 - 3 deposit.
+	assert_eq!(Balances::free_balance(4), 7);
+	assert_eq!(Balances::reserved_balance(4), 3);
+	assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+}
+
+#[test]
+fn add_child_bounty() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		// Only the parent curator may add a child-bounty.
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(0), 0, 10, b"12345-p1".to_vec()),
+			BountiesError::RequireCurator,
+		);
+
+		// The parent bounty must hold the value of the child-bounty.
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 50, b"12345-p1".to_vec()),
+			pallet_balances::Error::<Test>::KeepAlive,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 51, b"12345-p1".to_vec()),
+			Error::<Test>::InsufficientBountyBalance,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 0, b"12345-p1".to_vec()),
+			BountiesError::InvalidValue,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, vec![0; 301]),
+			BountiesError::ReasonTooBig,
+		);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_eq!(last_event(), ChildBountiesEvent::Added(0, 0));
+
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 40);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 10);
+		assert_eq!(ChildBounties::child_bounty_count(), 1);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 1);
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap(),
+			ChildBounty {
+				parent_bounty: 0,
+				value: 10,
+				fee: 0,
+				curator_deposit: 0,
+				status: ChildBountyStatus::Added,
+			},
+		);
+		assert_eq!(
+			ChildBounties::child_bounty_descriptions(0).unwrap().into_inner(),
+			b"12345-p1".to_vec(),
+		);
+	});
+}
+
+#[test]
+fn add_child_bounty_requires_active_parent() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()),
+			Error::<Test>::ParentBountyNotActive,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 1, 10, b"12345-p1".to_vec()),
+			BountiesError::InvalidIndex,
+		);
+	});
+}
+
+#[test]
+fn add_child_bounty_respects_max_active_count() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p2".to_vec()));
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p3".to_vec()),
+			Error::<Test>::TooManyChildBounties,
+		);
+
+		// Closing a child-bounty makes room for a new one.
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 0));
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p3".to_vec()));
+		assert_eq!(ChildBounties::parent_child_bounties(0), 2);
+		assert_eq!(ChildBounties::child_bounty_count(), 3);
+	});
+}
+
+#[test]
+fn child_bounty_curator_lifecycle_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		Balances::make_free_balance_be(&8, 10);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+
+		assert_noop!(
+			ChildBounties::propose_curator(Origin::signed(8), 0, 0, 8, 2),
+			BountiesError::RequireCurator,
+		);
+		assert_noop!(
+			ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 10),
+			BountiesError::InvalidFee,
+		);
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_eq!(ChildBounties::children_curator_fees(0), 2);
+
+		assert_noop!(
+			ChildBounties::accept_curator(Origin::signed(3), 0, 0),
+			BountiesError::RequireCurator,
+		);
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap(),
+			ChildBounty {
+				parent_bounty: 0,
+				value: 10,
+				fee: 2,
+				curator_deposit: 1,
+				status: ChildBountyStatus::Active { curator: 8 },
+			},
+		);
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 1);
+
+		assert_noop!(
+			ChildBounties::award_child_bounty(Origin::signed(3), 0, 0, 7),
+			BountiesError::RequireCurator,
+		);
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 7));
+		assert_eq!(last_event(), ChildBountiesEvent::Awarded(0, 0, 7));
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap().status,
+			ChildBountyStatus::PendingPayout { curator: 8, beneficiary: 7, unlock_at: 5 },
+		);
+
+		// The parent bounty cannot be awarded while it has active child-bounties.
+		assert_noop!(
+			Bounties::award_bounty(Origin::signed(4), 0, 5),
+			BountiesError::HasActiveChildBounty,
+		);
+
+		assert_noop!(
+			ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0),
+			BountiesError::Premature,
+		);
+
+		System::set_block_number(5);
+		assert_ok!(ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0));
+		assert_eq!(last_event(), ChildBountiesEvent::Claimed(0, 0, 8, 7));
+
+		// Curator gets back the deposit plus the fee, beneficiary gets the rest.
+		assert_eq!(Balances::free_balance(8), 12);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(Balances::free_balance(7), 8);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 0);
+		assert_eq!(ChildBounties::child_bounties(0, 0), None);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0), None);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+	});
+}
+
+#[test]
+fn parent_curator_fee_is_reduced_by_child_curator_fees() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		Balances::make_free_balance_be(&8, 10);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 7));
+		System::set_block_number(5);
+		assert_ok!(ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0));
+
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), 0, 5));
+		System::set_block_number(8);
+		assert_ok!(Bounties::claim_bounty(Origin::signed(5), 0));
+
+		// Parent curator gets back the deposit plus the fee less the child curator's fee.
+		assert_eq!(Balances::free_balance(4), 14);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(5), 36);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+	});
+}
+
+#[test]
+fn parent_curator_as_child_curator_pays_no_deposit() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 4, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(4), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().curator_deposit, 0);
+		assert_eq!(Balances::reserved_balance(4), 3);
+	});
+}
+
+#[test]
+fn close_child_bounty_added() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+
+		// The parent bounty cannot be closed while it has active child-bounties.
+		assert_noop!(Bounties::close_bounty(Origin::root(), 0), BountiesError::HasActiveChildBounty);
+
+		assert_noop!(ChildBounties::close_child_bounty(Origin::signed(8), 0, 0), BadOrigin);
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 0));
+		assert_eq!(last_event(), ChildBountiesEvent::Canceled(0, 0));
+
+		// The funds go back to the parent bounty.
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 0);
+		assert_eq!(ChildBounties::child_bounties(0, 0), None);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0), None);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+
+		assert_ok!(Bounties::close_bounty(Origin::root(), 0));
+	});
+}
+
+#[test]
+fn close_child_bounty_active() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		Balances::make_free_balance_be(&8, 10);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+
+		assert_ok!(ChildBounties::close_child_bounty(Origin::root(), 0, 0));
+
+		// The child curator gets their deposit back.
+		assert_eq!(Balances::free_balance(8), 10);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+	});
+}
+
+#[test]
+fn close_child_bounty_pending_payout_fails() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		Balances::make_free_balance_be(&8, 10);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 7));
+
+		assert_noop!(
+			ChildBounties::close_child_bounty(Origin::root(), 0, 0),
+			BountiesError::PendingPayout,
+		);
+
+		// Unassigning the curator slashes them and allows the child-bounty to be closed.
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(4), 0, 0));
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_ok!(ChildBounties::close_child_bounty(Origin::root(), 0, 0));
+	});
+}
+
+#[test]
+fn unassign_curator_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		Balances::make_free_balance_be(&8, 10);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_noop!(
+			ChildBounties::unassign_curator(Origin::signed(4), 0, 0),
+			BountiesError::UnexpectedStatus,
+		);
+
+		// A proposed curator may be unassigned by themselves.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_noop!(ChildBounties::unassign_curator(Origin::signed(3), 0, 0), BadOrigin);
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(8), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+
+		// An active curator giving up gets their deposit back.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_eq!(ChildBounties::children_curator_fees(0), 2);
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(8), 0, 0));
+		assert_eq!(Balances::free_balance(8), 10);
+		assert_eq!(Balances::reserved_balance(8), 0);
+
+		// Anyone else must wait for the parent bounty's update to be due.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_noop!(
+			ChildBounties::unassign_curator(Origin::signed(3), 0, 0),
+			BountiesError::Premature,
+		);
+		System::set_block_number(20);
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(3), 0, 0));
+
+		// The curator was slashed.
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().curator_deposit, 0);
+	});
+}
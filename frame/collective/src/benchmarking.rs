@@ -38,6 +38,11 @@ const SEED: u32 = 0;
 
 const MAX_BYTES: u32 = 1_024;
 
+fn fund<T: Config<I>, I: Instance>(who: &T::AccountId) {
+	// enough to reserve the deposits of all proposals.
+	T::Currency::make_free_balance_be(who, BalanceOf::<T, I>::max_value() / 2u32.into());
+}
+
 fn assert_last_event<T: Config<I>, I: Instance>(generic_event: <T as Config<I>>::Event) {
 	let events = System::<T>::events();
 	let system_event: <T as frame_system::Config>::Event = generic_event.into();
@@ -73,6 +78,7 @@ benchmarks_instance! {
 		let threshold = m.max(2);
 		// Length of the proposals should be irrelevant to `set_members`.
 		let length = 100;
+		fund::<T, I>(&last_old_member);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; length]).into();
@@ -193,6 +199,7 @@ benchmarks_instance! {
 
 		let threshold = m;
 		// Add previous proposals.
+		fund::<T, I>(&caller);
 		for i in 0 .. p - 1 {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; b as usize]).into();
@@ -241,6 +248,7 @@ benchmarks_instance! {
 
 		// Add previous proposals
 		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&proposer);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; b as usize]).into();
@@ -317,6 +325,7 @@ benchmarks_instance! {
 
 		// Add previous proposals
 		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&proposer);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; bytes as usize]).into();
@@ -395,6 +404,7 @@ benchmarks_instance! {
 
 		// Add previous proposals
 		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&caller);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; b as usize]).into();
@@ -481,6 +491,7 @@ benchmarks_instance! {
 
 		// Add proposals
 		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&caller);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; bytes as usize]).into();
@@ -553,6 +564,7 @@ benchmarks_instance! {
 
 		// Add proposals
 		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&caller);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; b as usize]).into();
@@ -616,6 +628,7 @@ benchmarks_instance! {
 
 		// Add proposals
 		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&caller);
 		for i in 0 .. p {
 			// Proposals should be different so that different proposal hashes are generated
 			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; b as usize]).into();
@@ -636,6 +649,55 @@ benchmarks_instance! {
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
 		assert_last_event::<T, I>(RawEvent::Disapproved(last_hash).into());
 	}
+
+	kill {
+		let p in 1 .. T::MaxProposals::get();
+
+		let m = 3;
+		let b = MAX_BYTES;
+		let bytes_in_storage = b + size_of::<u32>() as u32;
+
+		// Construct `members`.
+		let mut members = vec![];
+		for i in 0 .. m - 1 {
+			let member = account("member", i, SEED);
+			members.push(member);
+		}
+		let caller: T::AccountId = account("caller", 0, SEED);
+		members.push(caller.clone());
+		Collective::<T, _>::set_members(
+			SystemOrigin::Root.into(),
+			members.clone(),
+			Some(caller.clone()),
+			T::MaxMembers::get(),
+		)?;
+
+		// Threshold is one less than total members so that two nays will disapprove the vote
+		let threshold = m - 1;
+
+		// Add proposals
+		let mut last_hash = T::Hash::default();
+		fund::<T, I>(&caller);
+		for i in 0 .. p {
+			// Proposals should be different so that different proposal hashes are generated
+			let proposal: T::Proposal = SystemCall::<T>::remark(vec![i as u8; b as usize]).into();
+			Collective::<T, _>::propose(
+				SystemOrigin::Signed(caller.clone()).into(),
+				threshold,
+				Box::new(proposal.clone()),
+				bytes_in_storage,
+			)?;
+			last_hash = T::Hashing::hash_of(&proposal);
+		}
+
+		System::<T>::set_block_number(T::BlockNumber::max_value());
+		assert_eq!(Collective::<T, _>::proposals().len(), p as usize);
+
+	}: _(SystemOrigin::Root, last_hash)
+	verify {
+		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
+		assert_last_event::<T, I>(RawEvent::Killed(last_hash).into());
+	}
 }
 
 impl_benchmark_test_suite!(
@@ -45,7 +45,7 @@
 use sp_std::{prelude::*, result};
 use sp_core::u32_trait::Value as U32;
 use sp_io::storage;
-use sp_runtime::{RuntimeDebug, traits::Hash};
+use sp_runtime::{RuntimeDebug, traits::{Hash, Zero}};
 
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure, BoundedVec,
@@ -54,7 +54,10 @@ use frame_support::{
 		DispatchError, DispatchResult, DispatchResultWithPostInfo, Dispatchable, Parameter,
 		PostDispatchInfo,
 	},
-	traits::{ChangeMembers, EnsureOrigin, Get, InitializeMembers, GetBacking, Backing},
+	traits::{
+		ChangeMembers, EnsureOrigin, Get, InitializeMembers, GetBacking, Backing, Currency,
+		ReservableCurrency, OnUnbalanced,
+	},
 	weights::{DispatchClass, GetDispatchInfo, Weight, Pays},
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
@@ -68,6 +71,15 @@ pub use weights::WeightInfo;
 /// Simple index type for proposal counting.
 pub type ProposalIndex = u32;
 
+/// The balance type of the currency used for proposal deposits.
+pub type BalanceOf<T, I> =
+	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The negative imbalance type of the currency used for proposal deposits.
+pub type NegativeImbalanceOf<T, I> = <<T as Config<I>>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
 /// A number of members.
 ///
 /// This also serves as a number of voting members, and since for motions, each member may
@@ -149,6 +161,18 @@ pub trait Config<I: Instance=DefaultInstance>: frame_system::Config {
 	/// Default vote strategy of this collective.
 	type DefaultVote: DefaultVote;
 
+	/// The currency used for proposal deposits.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from the proposer when a proposal is put up for voting.
+	///
+	/// It is returned once the proposal is closed or disapproved, and slashed if the proposal is
+	/// killed. Set to zero to not require a deposit.
+	type ProposalDeposit: Get<BalanceOf<Self, I>>;
+
+	/// Handler for the deposits slashed from killed proposals.
+	type Slashed: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -198,6 +222,9 @@ decl_storage! {
 		/// Actual proposal for a given hash, if it's current.
 		pub ProposalOf get(fn proposal_of):
 			map hasher(identity) T::Hash => Option<<T as Config<I>>::Proposal>;
+		/// The proposer and deposit of a given proposal, if it is current and has a deposit.
+		pub DepositOf get(fn deposit_of):
+			map hasher(identity) T::Hash => Option<(T::AccountId, BalanceOf<T, I>)>;
 		/// Votes on a given proposal, if it is ongoing.
 		pub Voting get(fn voting):
 			map hasher(identity) T::Hash => Option<Votes<T::AccountId, T::BlockNumber>>;
@@ -243,6 +270,9 @@ decl_event! {
 		/// A proposal was closed because its threshold was reached or after its duration was up.
 		/// \[proposal_hash, yes, no\]
 		Closed(Hash, MemberCount, MemberCount),
+		/// A motion was killed and the deposit of its proposer was slashed.
+		/// \[proposal_hash\]
+		Killed(Hash),
 	}
 }
 
@@ -403,7 +433,8 @@ decl_module! {
 		/// Requires the sender to be member.
 		///
 		/// `threshold` determines whether `proposal` is executed directly (`threshold < 2`)
-		/// or put up for voting.
+		/// or put up for voting. A proposal that is put up for voting reserves
+		/// `ProposalDeposit` from the sender until it is closed.
 		///
 		/// # <weight>
 		/// ## Weight
@@ -468,11 +499,19 @@ decl_module! {
 					).saturating_add(w) // P1
 				}).into())
 			} else {
+				let deposit = T::ProposalDeposit::get();
+				T::Currency::reserve(&who, deposit)?;
 				let active_proposals =
 					<Proposals<T, I>>::try_mutate(|proposals| -> Result<usize, DispatchError> {
 						proposals.try_push(proposal_hash).map_err(|_| Error::<T, I>::TooManyProposals)?;
 						Ok(proposals.len())
+					}).map_err(|e| {
+						T::Currency::unreserve(&who, deposit);
+						e
 					})?;
+				if !deposit.is_zero() {
+					<DepositOf<T, I>>::insert(proposal_hash, (who.clone(), deposit));
+				}
 				let index = Self::proposal_count();
 				<ProposalCount<I>>::mutate(|i| *i += 1);
 				<ProposalOf<T, I>>::insert(proposal_hash, *proposal);
@@ -708,6 +747,34 @@ decl_module! {
 			let proposal_count = Self::do_disapprove_proposal(proposal_hash);
 			Ok(Some(T::WeightInfo::disapprove_proposal(proposal_count)).into())
 		}
+
+		/// Remove a proposal from the system, regardless of its current state, and slash the
+		/// deposit of its proposer.
+		///
+		/// Must be called by the Root origin.
+		///
+		/// Parameters:
+		/// * `proposal_hash`: The hash of the proposal that should be killed.
+		///
+		/// # <weight>
+		/// Complexity: O(P) where P is the number of max proposals
+		/// DB Weight:
+		/// * Reads: ProposalOf, Proposals, DepositOf
+		/// * Writes: Voting, Proposals, ProposalOf, DepositOf, proposer's account
+		/// # </weight>
+		#[weight = T::WeightInfo::kill(T::MaxProposals::get())]
+		fn kill(origin, proposal_hash: T::Hash) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(<ProposalOf<T, I>>::contains_key(&proposal_hash), Error::<T, I>::ProposalMissing);
+
+			if let Some((who, deposit)) = <DepositOf<T, I>>::take(&proposal_hash) {
+				let (imbalance, _) = T::Currency::slash_reserved(&who, deposit);
+				T::Slashed::on_unbalanced(imbalance);
+			}
+			Self::deposit_event(RawEvent::Killed(proposal_hash));
+			let proposal_count = Self::remove_proposal(proposal_hash);
+			Ok(Some(T::WeightInfo::kill(proposal_count)).into())
+		}
 	}
 }
 
@@ -780,11 +847,15 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		Self::remove_proposal(proposal_hash)
 	}
 
-	// Removes a proposal from the pallet, cleaning up votes and the vector of proposals.
+	// Removes a proposal from the pallet, cleaning up votes and the vector of proposals, and
+	// returns the deposit of the proposer if it still exists.
 	fn remove_proposal(proposal_hash: T::Hash) -> u32 {
 		// remove proposal and vote
 		ProposalOf::<T, I>::remove(&proposal_hash);
 		Voting::<T, I>::remove(&proposal_hash);
+		if let Some((who, deposit)) = DepositOf::<T, I>::take(&proposal_hash) {
+			T::Currency::unreserve(&who, deposit);
+		}
 		let num_proposals = Proposals::<T, I>::mutate(|proposals| {
 			proposals.retain(|h| h != &proposal_hash);
 			proposals.len() + 1 // calculate weight based on original length
@@ -982,6 +1053,8 @@ mod tests {
 		pub const MotionDuration: u64 = 3;
 		pub const MaxProposals: u32 = 100;
 		pub const MaxMembers: u32 = 100;
+		pub const ExistentialDeposit: u64 = 1;
+		pub const ProposalDeposit: u64 = 5;
 		pub BlockWeights: frame_system::limits::BlockWeights =
 			frame_system::limits::BlockWeights::simple_max(1024);
 	}
@@ -1003,13 +1076,22 @@ mod tests {
 		type BlockHashCount = BlockHashCount;
 		type Version = ();
 		type PalletInfo = PalletInfo;
-		type AccountData = ();
+		type AccountData = pallet_balances::AccountData<u64>;
 		type OnNewAccount = ();
 		type OnKilledAccount = ();
 		type SystemWeightInfo = ();
 		type SS58Prefix = ();
 		type OnSetCode = ();
 	}
+	impl pallet_balances::Config for Test {
+		type MaxLocks = ();
+		type Balance = u64;
+		type Event = Event;
+		type DustRemoval = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = System;
+		type WeightInfo = ();
+	}
 	impl Config<Instance1> for Test {
 		type Origin = Origin;
 		type Proposal = Call;
@@ -1018,6 +1100,9 @@ mod tests {
 		type MaxProposals = MaxProposals;
 		type MaxMembers = MaxMembers;
 		type DefaultVote = PrimeDefaultVote;
+		type Currency = Balances;
+		type ProposalDeposit = ();
+		type Slashed = ();
 		type WeightInfo = ();
 	}
 	impl Config<Instance2> for Test {
@@ -1028,6 +1113,9 @@ mod tests {
 		type MaxProposals = MaxProposals;
 		type MaxMembers = MaxMembers;
 		type DefaultVote = MoreThanMajorityThenPrimeDefaultVote;
+		type Currency = Balances;
+		type ProposalDeposit = ();
+		type Slashed = ();
 		type WeightInfo = ();
 	}
 	impl Config for Test {
@@ -1038,6 +1126,9 @@ mod tests {
 		type MaxProposals = MaxProposals;
 		type MaxMembers = MaxMembers;
 		type DefaultVote = PrimeDefaultVote;
+		type Currency = Balances;
+		type ProposalDeposit = ProposalDeposit;
+		type Slashed = ();
 		type WeightInfo = ();
 	}
 
@@ -1051,6 +1142,7 @@ mod tests {
 			UncheckedExtrinsic = UncheckedExtrinsic
 		{
 			System: system::{Pallet, Call, Event<T>},
+			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 			Collective: collective::<Instance1>::{Pallet, Call, Event<T>, Origin<T>, Config<T>},
 			CollectiveMajority: collective::<Instance2>::{Pallet, Call, Event<T>, Origin<T>, Config<T>},
 			DefaultCollective: collective::{Pallet, Call, Event<T>, Origin<T>, Config<T>},
@@ -1059,6 +1151,9 @@ mod tests {
 
 	pub fn new_test_ext() -> sp_io::TestExternalities {
 		let mut ext: sp_io::TestExternalities = GenesisConfig {
+			pallet_balances: pallet_balances::GenesisConfig {
+				balances: vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)],
+			},
 			collective_Instance1: collective::GenesisConfig {
 				members: vec![1, 2, 3],
 				phantom: Default::default(),
@@ -1718,6 +1813,64 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn proposal_deposit_is_returned_on_close() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(DefaultCollective::set_members(Origin::root(), vec![1, 2, 3], None, MaxMembers::get()));
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			let proposal_weight = proposal.get_dispatch_info().weight;
+			let hash: H256 = proposal.blake2_256().into();
+
+			assert_ok!(DefaultCollective::propose(Origin::signed(1), 2, Box::new(proposal), proposal_len));
+			assert_eq!(Balances::reserved_balance(1), 5);
+			assert_eq!(DefaultCollective::deposit_of(&hash), Some((1, 5)));
+
+			assert_ok!(DefaultCollective::vote(Origin::signed(2), hash, 0, true));
+			assert_ok!(DefaultCollective::close(Origin::signed(4), hash, 0, proposal_weight, proposal_len));
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 100);
+			assert_eq!(DefaultCollective::deposit_of(&hash), None);
+		});
+	}
+
+	#[test]
+	fn propose_fails_without_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(DefaultCollective::set_members(Origin::root(), vec![1, 6], None, MaxMembers::get()));
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			assert_noop!(
+				DefaultCollective::propose(Origin::signed(6), 2, Box::new(proposal), proposal_len),
+				pallet_balances::Error::<Test>::InsufficientBalance,
+			);
+		});
+	}
+
+	#[test]
+	fn kill_slashes_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(DefaultCollective::set_members(Origin::root(), vec![1, 2, 3], None, MaxMembers::get()));
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			let hash: H256 = proposal.blake2_256().into();
+			assert_ok!(DefaultCollective::propose(Origin::signed(1), 2, Box::new(proposal), proposal_len));
+
+			assert_noop!(DefaultCollective::kill(Origin::signed(1), hash), DispatchError::BadOrigin);
+			assert_ok!(DefaultCollective::kill(Origin::root(), hash));
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 95);
+			assert_eq!(*DefaultCollective::proposals(), Vec::<H256>::new());
+			assert_eq!(DefaultCollective::proposal_of(&hash), None);
+			System::assert_last_event(Event::collective(RawEvent::Killed(hash)));
+
+			assert_noop!(
+				DefaultCollective::kill(Origin::root(), hash),
+				Error::<Test, DefaultInstance>::ProposalMissing,
+			);
+		});
+	}
+
 	#[test]
 	fn disapprove_proposal_works() {
 		new_test_ext().execute_with(|| {
@@ -19,6 +19,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
 //! DATE: 2020-10-27, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weight of `kill` is a hand-written placeholder and was not generated by the benchmark
+//! CLI.
 
 // Executed Command:
 // target/release/substrate
@@ -53,6 +56,7 @@ pub trait WeightInfo {
 	fn close_disapproved(_m: u32, _p: u32, ) -> Weight;
 	fn close_approved(_b: u32, _m: u32, _p: u32, ) -> Weight;
 	fn disapprove_proposal(_p: u32, ) -> Weight;
+	fn kill(_p: u32, ) -> Weight;
 
 }
 
@@ -140,6 +144,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 
 	}
+	// Hand-written placeholder until the `kill` benchmark is run.
+	fn kill(p: u32, ) -> Weight {
+		(52_419_000 as Weight)
+			.saturating_add((642_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+
+	}
 
 }
 
@@ -226,5 +238,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 
 	}
+	// Hand-written placeholder until the `kill` benchmark is run.
+	fn kill(p: u32, ) -> Weight {
+		(52_419_000 as Weight)
+			.saturating_add((642_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+
+	}
 
 }
@@ -0,0 +1,17 @@
+;; Module that uses floating point types and instructions.
+;; It is only accepted when indeterminism is allowed.
+(module
+	(import "env" "memory" (memory 1 1))
+
+	(func $add_one (param f32) (result f32)
+		(f32.add
+			(local.get 0)
+			(f32.const 1)
+		)
+	)
+
+	(func (export "call")
+		(drop (call $add_one (f32.const 1)))
+	)
+	(func (export "deploy"))
+)
@@ -22,7 +22,6 @@ use std::sync::Arc;
 use codec::Codec;
 use jsonrpc_core::{Error, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use pallet_contracts_primitives::RentProjection;
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -33,13 +32,14 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 };
 use std::convert::{TryFrom, TryInto};
-use pallet_contracts_primitives::{Code, ContractExecResult, ContractInstantiateResult};
+use pallet_contracts_primitives::{
+	Code, CodeUploadResult, ContractExecResult, ContractInstantiateResult,
+};
 
 pub use pallet_contracts_rpc_runtime_api::ContractsApi as ContractsRuntimeApi;
 
 const RUNTIME_ERROR: i64 = 1;
 const CONTRACT_DOESNT_EXIST: i64 = 2;
-const CONTRACT_IS_A_TOMBSTONE: i64 = 3;
 
 pub type Weight = u64;
 
@@ -68,11 +68,6 @@ impl From<ContractAccessError> for Error {
 				message: "The specified contract doesn't exist.".into(),
 				data: None,
 			},
-			IsTombstone => Error {
-				code: ErrorCode::ServerError(CONTRACT_IS_A_TOMBSTONE),
-				message: "The contract is a tombstone and doesn't have any storage.".into(),
-				data: None,
-			},
 		}
 	}
 }
@@ -86,6 +81,7 @@ pub struct CallRequest<AccountId> {
 	dest: AccountId,
 	value: NumberOrHex,
 	gas_limit: NumberOrHex,
+	storage_deposit_limit: Option<NumberOrHex>,
 	input_data: Bytes,
 }
 
@@ -97,11 +93,20 @@ pub struct InstantiateRequest<AccountId, Hash> {
 	origin: AccountId,
 	endowment: NumberOrHex,
 	gas_limit: NumberOrHex,
+	storage_deposit_limit: Option<NumberOrHex>,
 	code: Code<Hash>,
 	data: Bytes,
 	salt: Bytes,
 }
 
+/// A struct that encodes RPC parameters required for a call to upload a new code.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct CodeUploadRequest {
+	code: Bytes,
+}
+
 /// Contracts RPC methods.
 #[rpc]
 pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
@@ -116,7 +121,7 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		&self,
 		call_request: CallRequest<AccountId>,
 		at: Option<BlockHash>,
-	) -> Result<ContractExecResult>;
+	) -> Result<ContractExecResult<Balance>>;
 
 	/// Instantiate a new contract.
 	///
@@ -129,7 +134,20 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		&self,
 		instantiate_request: InstantiateRequest<AccountId, Hash>,
 		at: Option<BlockHash>,
-	) -> Result<ContractInstantiateResult<AccountId, BlockNumber>>;
+	) -> Result<ContractInstantiateResult<AccountId, Balance>>;
+
+	/// Validate and instrument new contract code.
+	///
+	/// This call is performed locally without submitting any transactions. Thus the code
+	/// is not actually stored.
+	///
+	/// This method is useful for UIs to find out whether some code would be accepted on chain.
+	#[rpc(name = "contracts_uploadCode")]
+	fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest,
+		at: Option<BlockHash>,
+	) -> Result<CodeUploadResult<Hash>>;
 
 	/// Returns the value under a specified storage `key` in a contract given by `address` param,
 	/// or `None` if it is not set.
@@ -140,19 +158,6 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		key: H256,
 		at: Option<BlockHash>,
 	) -> Result<Option<Bytes>>;
-
-	/// Returns the projected time a given contract will be able to sustain paying its rent.
-	///
-	/// The returned projection is relevant for the given block, i.e. it is as if the contract was
-	/// accessed at the beginning of that block.
-	///
-	/// Returns `None` if the contract is exempted from rent.
-	#[rpc(name = "contracts_rentProjection")]
-	fn rent_projection(
-		&self,
-		address: AccountId,
-		at: Option<BlockHash>,
-	) -> Result<Option<BlockNumber>>;
 }
 
 /// An implementation of contract specific RPC methods.
@@ -196,7 +201,7 @@ where
 		&self,
 		call_request: CallRequest<AccountId>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<ContractExecResult> {
+	) -> Result<ContractExecResult<Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
@@ -207,15 +212,19 @@ where
 			dest,
 			value,
 			gas_limit,
+			storage_deposit_limit,
 			input_data,
 		} = call_request;
 
 		let value: Balance = decode_hex(value, "balance")?;
 		let gas_limit: Weight = decode_hex(gas_limit, "weight")?;
+		let storage_deposit_limit: Option<Balance> = storage_deposit_limit
+			.map(|l| decode_hex(l, "balance"))
+			.transpose()?;
 		limit_gas(gas_limit)?;
 
 		let exec_result = api
-			.call(&at, origin, dest, value, gas_limit, input_data.to_vec())
+			.call(&at, origin, dest, value, gas_limit, storage_deposit_limit, input_data.to_vec())
 			.map_err(runtime_error_into_rpc_err)?;
 
 		Ok(exec_result)
@@ -225,7 +234,7 @@ where
 		&self,
 		instantiate_request: InstantiateRequest<AccountId, Hash>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<ContractInstantiateResult<AccountId, <<Block as BlockT>::Header as HeaderT>::Number>> {
+	) -> Result<ContractInstantiateResult<AccountId, Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
@@ -235,6 +244,7 @@ where
 			origin,
 			endowment,
 			gas_limit,
+			storage_deposit_limit,
 			code,
 			data,
 			salt,
@@ -242,54 +252,64 @@ where
 
 		let endowment: Balance = decode_hex(endowment, "balance")?;
 		let gas_limit: Weight = decode_hex(gas_limit, "weight")?;
+		let storage_deposit_limit: Option<Balance> = storage_deposit_limit
+			.map(|l| decode_hex(l, "balance"))
+			.transpose()?;
 		limit_gas(gas_limit)?;
 
 		let exec_result = api
-			.instantiate(&at, origin, endowment, gas_limit, code, data.to_vec(), salt.to_vec())
+			.instantiate(
+				&at,
+				origin,
+				endowment,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data.to_vec(),
+				salt.to_vec(),
+			)
 			.map_err(runtime_error_into_rpc_err)?;
 
 		Ok(exec_result)
 	}
 
-	fn get_storage(
+	fn upload_code(
 		&self,
-		address: AccountId,
-		key: H256,
+		upload_request: CodeUploadRequest,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<Option<Bytes>> {
+	) -> Result<CodeUploadResult<Hash>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
 
+		let CodeUploadRequest { code } = upload_request;
+
 		let result = api
-			.get_storage(&at, address, key.into())
-			.map_err(runtime_error_into_rpc_err)?
-			.map_err(ContractAccessError)?
-			.map(Bytes);
+			.upload_code(&at, code.to_vec())
+			.map_err(runtime_error_into_rpc_err)?;
 
 		Ok(result)
 	}
 
-	fn rent_projection(
+	fn get_storage(
 		&self,
 		address: AccountId,
+		key: H256,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<Option<<<Block as BlockT>::Header as HeaderT>::Number>> {
+	) -> Result<Option<Bytes>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
 
 		let result = api
-			.rent_projection(&at, address)
+			.get_storage(&at, address, key.into())
 			.map_err(runtime_error_into_rpc_err)?
-			.map_err(ContractAccessError)?;
+			.map_err(ContractAccessError)?
+			.map(Bytes);
 
-		Ok(match result {
-			RentProjection::NoEviction => None,
-			RentProjection::EvictionAt(block_num) => Some(block_num),
-		})
+		Ok(result)
 	}
 }
 
@@ -343,10 +363,12 @@ mod tests {
 			"dest": "5DRakbLVnjVrW6niwLfHGW24EeCEvDAFGEXrtaYS5M4ynoom",
 			"value": "0x112210f4B16c1cb1",
 			"gasLimit": 1000000000000,
+			"storageDepositLimit": 5000,
 			"inputData": "0x8c97db39"
 		}
 		"#).unwrap();
 		assert_eq!(req.gas_limit.into_u256(), U256::from(0xe8d4a51000u64));
+		assert_eq!(req.storage_deposit_limit.map(|l| l.into_u256()), Some(5000.into()));
 		assert_eq!(req.value.into_u256(), U256::from(1234567890987654321u128));
 	}
 
@@ -367,6 +389,7 @@ mod tests {
 		assert_eq!(req.origin, "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL");
 		assert_eq!(req.endowment.into_u256(), 0x88.into());
 		assert_eq!(req.gas_limit.into_u256(), 42.into());
+		assert!(req.storage_deposit_limit.is_none());
 		assert_eq!(&*req.data, [0x42, 0x99].as_ref());
 		assert_eq!(&*req.salt, [0x99, 0x88].as_ref());
 		let code = match req.code {
@@ -376,15 +399,28 @@ mod tests {
 		assert_eq!(&code, "0x1122");
 	}
 
+	#[test]
+	fn code_upload_request_should_serialize_deserialize_properly() {
+		let req: CodeUploadRequest = serde_json::from_str(r#"
+		{
+			"code": "0x8c97db39"
+		}
+		"#).unwrap();
+		assert_eq!(&*req.code, [0x8c, 0x97, 0xdb, 0x39].as_ref());
+	}
+
 	#[test]
 	fn call_result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: ContractExecResult = serde_json::from_str(expected).unwrap();
+			let res: ContractExecResult<u32> = serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, trim(expected).as_str());
 		}
 		test(r#"{
 			"gasConsumed": 5000,
+			"storageDeposit": {
+				"charge": 42000
+			},
 			"debugMessage": "0x68656c704f6b",
 			"result": {
 			  "Ok": {
@@ -395,6 +431,9 @@ mod tests {
 		}"#);
 		test(r#"{
 			"gasConsumed": 3400,
+			"storageDeposit": {
+				"refund": 12000
+			},
 			"debugMessage": "0x68656c70457272",
 			"result": {
 			  "Err": "BadOrigin"
@@ -405,12 +444,16 @@ mod tests {
 	#[test]
 	fn instantiate_result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: ContractInstantiateResult<String, u64> = serde_json::from_str(expected).unwrap();
+			let res: ContractInstantiateResult<String, u64> =
+				serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, trim(expected).as_str());
 		}
 		test(r#"{
 			"gasConsumed": 5000,
+			"storageDeposit": {
+				"charge": 42000
+			},
 			"debugMessage": "0x68656c704f6b",
 			"result": {
 			   "Ok": {
@@ -418,13 +461,15 @@ mod tests {
 					 "flags": 5,
 					 "data": "0x1234"
 				  },
-				  "accountId": "5CiPP",
-				  "rentProjection": null
+				  "accountId": "5CiPP"
 			   }
 			}
 		}"#);
 		test(r#"{
 			"gasConsumed": 3400,
+			"storageDeposit": {
+				"refund": 12000
+			},
 			"debugMessage": "0x68656c70457272",
 			"result": {
 			  "Err": "BadOrigin"
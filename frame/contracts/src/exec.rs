@@ -17,20 +17,21 @@
 
 use crate::{
 	CodeHash, Event, Config, Pallet as Contracts,
-	TrieId, BalanceOf, ContractInfo, gas::GasMeter, rent::Rent, storage::{self, Storage},
-	Error, ContractInfoOf, Schedule, AliveContractInfo,
+	TrieId, BalanceOf, gas::GasMeter, storage::{Storage, meter::{Meter, DepositOf}},
+	Error, ContractInfoOf, Schedule, wasm::Determinism,
 };
 use sp_core::crypto::UncheckedFrom;
 use sp_std::{
 	prelude::*,
 	marker::PhantomData,
+	mem,
 };
-use sp_runtime::{Perbill, traits::{Bounded, Zero, Convert, Saturating}};
+use sp_runtime::{Perbill, traits::{Zero, Convert}};
 use frame_support::{
 	dispatch::{DispatchResult, DispatchError},
 	traits::{ExistenceRequirement, Currency, Time, Randomness, Get},
 	weights::Weight,
-	ensure,
+	ensure, DefaultNoBound,
 };
 use pallet_contracts_primitives::{ExecReturnValue, ReturnFlags};
 
@@ -78,84 +79,27 @@ impl<T: Into<DispatchError>> From<T> for ExecError {
 	}
 }
 
-/// Information needed for rent calculations that can be requested by a contract.
-#[derive(codec::Encode)]
+/// Information about rent calculations that can be requested by a contract.
+///
+/// Contracts don't pay rent anymore. This type only exists so that contracts which still
+/// call `seal_rent_params` keep working. All of its fields are zero.
+#[derive(codec::Encode, DefaultNoBound)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct RentParams<T: Config> {
-	/// The total balance of the contract. Includes the balance transferred from the caller.
 	total_balance: BalanceOf<T>,
-	/// The free balance of the contract. Includes the balance transferred from the caller.
 	free_balance: BalanceOf<T>,
-	/// See crate [`Contracts::subsistence_threshold()`].
 	subsistence_threshold: BalanceOf<T>,
-	/// See crate [`Config::DepositPerContract`].
 	deposit_per_contract: BalanceOf<T>,
-	/// See crate [`Config::DepositPerStorageByte`].
 	deposit_per_storage_byte: BalanceOf<T>,
-	/// See crate [`Config::DepositPerStorageItem`].
 	deposit_per_storage_item: BalanceOf<T>,
-	/// See crate [`Ext::rent_allowance()`].
 	rent_allowance: BalanceOf<T>,
-	/// See crate [`Config::RentFraction`].
 	rent_fraction: Perbill,
-	/// See crate [`AliveContractInfo::storage_size`].
 	storage_size: u32,
-	/// See crate [`Executable::aggregate_code_len()`].
 	code_size: u32,
-	/// See crate [`Executable::refcount()`].
 	code_refcount: u32,
-	/// Reserved for backwards compatible changes to this data structure.
 	_reserved: Option<()>,
 }
 
-impl<T> RentParams<T>
-where
-	T: Config,
-	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
-{
-	fn new<E: Executable<T>>(
-		account_id: &T::AccountId,
-		contract: &AliveContractInfo<T>,
-		executable: &E
-	) -> Self {
-		Self {
-			total_balance: T::Currency::total_balance(account_id),
-			free_balance: T::Currency::free_balance(account_id),
-			subsistence_threshold: <Contracts<T>>::subsistence_threshold(),
-			deposit_per_contract: T::DepositPerContract::get(),
-			deposit_per_storage_byte: T::DepositPerStorageByte::get(),
-			deposit_per_storage_item: T::DepositPerStorageItem::get(),
-			rent_allowance: contract.rent_allowance,
-			rent_fraction: T::RentFraction::get(),
-			storage_size: contract.storage_size,
-			code_size: executable.aggregate_code_len(),
-			code_refcount: executable.refcount(),
-			_reserved: None,
-		}
-	}
-}
-
-/// We cannot derive `Default` because `T` does not necessarily implement `Default`.
-#[cfg(test)]
-impl<T: Config> Default for RentParams<T> {
-	fn default() -> Self {
-		Self {
-			total_balance: Default::default(),
-			free_balance: Default::default(),
-			subsistence_threshold: Default::default(),
-			deposit_per_contract: Default::default(),
-			deposit_per_storage_byte: Default::default(),
-			deposit_per_storage_item: Default::default(),
-			rent_allowance: Default::default(),
-			rent_fraction: Default::default(),
-			storage_size: Default::default(),
-			code_size: Default::default(),
-			code_refcount: Default::default(),
-			_reserved: Default::default(),
-		}
-	}
-}
-
 /// An interface that provides access to the external environment in which the
 /// smart-contract is executed.
 ///
@@ -235,25 +179,6 @@ pub trait Ext: sealing::Sealed {
 		input_data: Vec<u8>,
 	) -> Result<(ExecReturnValue, u32), (ExecError, u32)>;
 
-	/// Restores the given destination contract sacrificing the current one.
-	///
-	/// Since this function removes the self contract eagerly, if succeeded, no further actions should
-	/// be performed on this `Ext` instance.
-	///
-	/// This function will fail if the same contract is present
-	/// on the contract call stack.
-	///
-	/// # Return Value
-	///
-	/// Result<(CallerCodeSize, DestCodeSize), (DispatchError, CallerCodeSize, DestCodesize)>
-	fn restore_to(
-		&mut self,
-		dest: AccountIdOf<Self::T>,
-		code_hash: CodeHash<Self::T>,
-		rent_allowance: BalanceOf<Self::T>,
-		delta: Vec<StorageKey>,
-	) -> Result<(u32, u32), (DispatchError, u32, u32)>;
-
 	/// Returns a reference to the account id of the caller.
 	fn caller(&self) -> &AccountIdOf<Self::T>;
 
@@ -274,9 +199,6 @@ pub trait Ext: sealing::Sealed {
 	/// Returns the minimum balance that is required for creating an account.
 	fn minimum_balance(&self) -> BalanceOf<Self::T>;
 
-	/// Returns the deposit required to create a tombstone upon contract eviction.
-	fn tombstone_deposit(&self) -> BalanceOf<Self::T>;
-
 	/// Returns a random number for the current block with the given subject.
 	fn random(&self, subject: &[u8]) -> (SeedOf<Self::T>, BlockNumberOf<Self::T>);
 
@@ -285,12 +207,6 @@ pub trait Ext: sealing::Sealed {
 	/// There should not be any duplicates in `topics`.
 	fn deposit_event(&mut self, topics: Vec<TopicOf<Self::T>>, data: Vec<u8>);
 
-	/// Set rent allowance of the contract
-	fn set_rent_allowance(&mut self, rent_allowance: BalanceOf<Self::T>);
-
-	/// Rent allowance of the contract
-	fn rent_allowance(&self) -> BalanceOf<Self::T>;
-
 	/// Returns the current block number.
 	fn block_number(&self) -> BlockNumberOf<Self::T>;
 
@@ -302,9 +218,6 @@ pub trait Ext: sealing::Sealed {
 
 	/// Get a reference to the schedule used by the current call.
 	fn schedule(&self) -> &Schedule<Self::T>;
-
-	/// Information needed for rent calculations.
-	fn rent_params(&self) -> &RentParams<Self::T>;
 }
 
 /// Describes the different functions that can be exported by an [`Executable`].
@@ -368,18 +281,6 @@ pub trait Executable<T: Config>: Sized {
 	/// The code hash of the executable.
 	fn code_hash(&self) -> &CodeHash<T>;
 
-	/// The storage that is occupied by the instrumented executable and its pristine source.
-	///
-	/// The returned size is already divided by the number of users who share the code.
-	/// This is essentially `aggregate_code_len() / refcount()`.
-	///
-	/// # Note
-	///
-	/// This works with the current in-memory value of refcount. When calling any contract
-	/// without refetching this from storage the result can be inaccurate as it might be
-	/// working with a stale value. Usually this inaccuracy is tolerable.
-	fn occupied_storage(&self) -> u32;
-
 	/// Size of the instrumented code in bytes.
 	fn code_len(&self) -> u32;
 
@@ -388,6 +289,11 @@ pub trait Executable<T: Config>: Sized {
 
 	// The number of contracts using this executable.
 	fn refcount(&self) -> u32;
+
+	/// Whether the executable is guaranteed to behave deterministically.
+	///
+	/// Only deterministic executables can be executed on-chain.
+	fn is_deterministic(&self) -> bool;
 }
 
 pub struct ExecutionContext<'a, T: Config + 'a, E> {
@@ -398,6 +304,11 @@ pub struct ExecutionContext<'a, T: Config + 'a, E> {
 	schedule: &'a Schedule<T>,
 	timestamp: MomentOf<T>,
 	block_number: T::BlockNumber,
+	storage_meter: Meter<T>,
+	/// The deposit that was settled with the origin once the top level call finished.
+	storage_deposit: DepositOf<T>,
+	/// Whether executables which are not deterministic may be executed.
+	determinism: Determinism,
 	_phantom: PhantomData<E>,
 }
 
@@ -410,8 +321,13 @@ where
 	/// Create the top level execution context.
 	///
 	/// The specified `origin` address will be used as `sender` for. The `origin` must be a regular
-	/// account (not a contract).
-	pub fn top_level(origin: T::AccountId, schedule: &'a Schedule<T>) -> Self {
+	/// account (not a contract). It pays for all the storage deposit that is charged during
+	/// the call, up to the specified `storage_deposit_limit`.
+	pub fn top_level(
+		origin: T::AccountId,
+		schedule: &'a Schedule<T>,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+	) -> Self {
 		ExecutionContext {
 			caller: None,
 			self_trie_id: None,
@@ -420,10 +336,30 @@ where
 			schedule,
 			timestamp: T::Time::now(),
 			block_number: <frame_system::Pallet<T>>::block_number(),
+			storage_meter: Meter::new(storage_deposit_limit),
+			storage_deposit: Default::default(),
+			determinism: Determinism::Deterministic,
 			_phantom: Default::default(),
 		}
 	}
 
+	/// Set whether executables which are not deterministic may be executed.
+	///
+	/// Execution is restricted to deterministic executables by default. Indeterminism must
+	/// only be allowed when the results of the execution are never committed on-chain.
+	pub fn with_determinism(mut self, determinism: Determinism) -> Self {
+		self.determinism = determinism;
+		self
+	}
+
+	/// Fails if the `executable` is not deterministic but indeterminism is not allowed.
+	fn ensure_determinism(&self, executable: &E) -> Result<(), DispatchError> {
+		if self.determinism == Determinism::Deterministic && !executable.is_deterministic() {
+			Err(Error::<T>::Indeterministic)?
+		}
+		Ok(())
+	}
+
 	fn nested<'b, 'c: 'b>(&'c self, dest: T::AccountId, trie_id: TrieId)
 		-> ExecutionContext<'b, T, E>
 	{
@@ -435,10 +371,21 @@ where
 			schedule: self.schedule,
 			timestamp: self.timestamp.clone(),
 			block_number: self.block_number.clone(),
+			storage_meter: self.storage_meter.nested(),
+			storage_deposit: Default::default(),
+			determinism: self.determinism,
 			_phantom: Default::default(),
 		}
 	}
 
+	/// The storage deposit that was charged from or refunded to the origin.
+	///
+	/// This is only meaningful for the top level context after the call or instantiation
+	/// finished successfully. It is zero otherwise.
+	pub fn storage_deposit(&self) -> DepositOf<T> {
+		self.storage_deposit.clone()
+	}
+
 	/// Make a call to the specified address, optionally transferring some funds.
 	///
 	/// # Return Value
@@ -456,39 +403,26 @@ where
 		}
 
 		let contract = <ContractInfoOf<T>>::get(&dest)
-			.and_then(|contract| contract.get_alive())
 			.ok_or((Error::<T>::NotCallable.into(), 0))?;
 
 		let executable = E::from_storage(contract.code_hash, &self.schedule, gas_meter)
 			.map_err(|e| (e.into(), 0))?;
 		let code_len = executable.code_len();
+		self.ensure_determinism(&executable).map_err(|e| (e.into(), code_len))?;
 
-		// This charges the rent and denies access to a contract that is in need of
-		// eviction by returning `None`. We cannot evict eagerly here because those
-		// changes would be rolled back in case this contract is called by another
-		// contract.
-		// See: https://github.com/paritytech/substrate/issues/6439#issuecomment-648754324
-		let contract = Rent::<T, E>::charge(&dest, contract, executable.occupied_storage())
-			.map_err(|e| (e.into(), code_len))?
-			.ok_or((Error::<T>::NotCallable.into(), code_len))?;
-
-		let transactor_kind = self.transactor_kind();
 		let caller = self.self_account.clone();
 
-		let result = self.with_nested_context(dest.clone(), contract.trie_id.clone(), |nested| {
+		let result = self.with_nested_context(dest.clone(), contract.trie_id, |nested| {
 			if value > BalanceOf::<T>::zero() {
 				transfer::<T>(
 					TransferCause::Call,
-					transactor_kind,
 					&caller,
 					&dest,
 					value,
 				)?
 			}
 
-			let call_context = nested.new_call_context(
-				caller, &dest, value, &contract, &executable,
-			);
+			let call_context = nested.new_call_context(caller, value);
 
 			let output = executable.execute(
 				call_context,
@@ -512,8 +446,8 @@ where
 		if self.depth == T::MaxDepth::get() as usize {
 			Err(Error::<T>::MaxCallDepthReached)?
 		}
+		self.ensure_determinism(&executable)?;
 
-		let transactor_kind = self.transactor_kind();
 		let caller = self.self_account.clone();
 		let dest = Contracts::<T>::contract_address(&caller, executable.code_hash(), salt);
 
@@ -522,7 +456,7 @@ where
 			let dest_trie_id = Storage::<T>::generate_trie_id(&dest);
 
 			let output = self.with_nested_context(dest.clone(), dest_trie_id, |nested| {
-				let contract = Storage::<T>::place_contract(
+				Storage::<T>::place_contract(
 					&dest,
 					nested
 						.self_trie_id
@@ -530,30 +464,18 @@ where
 						.expect("the nested context always has to have self_trie_id"),
 					executable.code_hash().clone()
 				)?;
+				nested.storage_meter.charge_instantiate(&dest);
 
 				// Send funds unconditionally here. If the `endowment` is below existential_deposit
 				// then error will be returned here.
 				transfer::<T>(
 					TransferCause::Instantiate,
-					transactor_kind,
 					&caller,
 					&dest,
 					endowment,
 				)?;
 
-				// Cache the value before calling into the constructor because that
-				// consumes the value. If the constructor creates additional contracts using
-				// the same code hash we still charge the "1 block rent" as if they weren't
-				// spawned. This is OK as overcharging is always safe.
-				let occupied_storage = executable.occupied_storage();
-
-				let call_context = nested.new_call_context(
-					caller.clone(),
-					&dest,
-					endowment,
-					&contract,
-					&executable,
-				);
+				let call_context = nested.new_call_context(caller.clone(), endowment);
 
 				let output = executable.execute(
 					call_context,
@@ -562,19 +484,8 @@ where
 					gas_meter,
 				).map_err(|e| ExecError { error: e.error, origin: ErrorOrigin::Callee })?;
 
-				// We need to re-fetch the contract because changes are written to storage
-				// eagerly during execution.
-				let contract = <ContractInfoOf<T>>::get(&dest)
-					.and_then(|contract| contract.get_alive())
-					.ok_or(Error::<T>::NotCallable)?;
-
-				// Collect the rent for the first block to prevent the creation of very large
-				// contracts that never intended to pay for even one block.
-				// This also makes sure that it is above the subsistence threshold
-				// in order to keep up the guarantuee that we always leave a tombstone behind
-				// with the exception of a contract that called `seal_terminate`.
-				Rent::<T, E>::charge(&dest, contract, occupied_storage)?
-					.ok_or(Error::<T>::NewContractNotFunded)?;
+				// The contract could have terminated itself during construction.
+				ensure!(<ContractInfoOf<T>>::contains_key(&dest), Error::<T>::NotCallable);
 
 				// Deposit an instantiation event.
 				deposit_event::<T>(vec![], Event::Instantiated(caller.clone(), dest.clone()));
@@ -595,10 +506,7 @@ where
 	fn new_call_context<'b>(
 		&'b mut self,
 		caller: T::AccountId,
-		dest: &T::AccountId,
 		value: BalanceOf<T>,
-		contract: &AliveContractInfo<T>,
-		executable: &E,
 	) -> CallContext<'b, 'a, T, E> {
 		let timestamp = self.timestamp.clone();
 		let block_number = self.block_number.clone();
@@ -608,20 +516,38 @@ where
 			value_transferred: value,
 			timestamp,
 			block_number,
-			rent_params: RentParams::new(dest, contract, executable),
 			_phantom: Default::default(),
 		}
 	}
 
 	/// Execute the given closure within a nested execution context.
+	///
+	/// The storage deposit recorded by the nested context is only merged into this context
+	/// when the closure succeeds. The top level context settles the deposit with the origin
+	/// before the outermost transaction is committed.
 	fn with_nested_context<F>(&mut self, dest: T::AccountId, trie_id: TrieId, func: F)
 		-> ExecResult
 		where F: FnOnce(&mut ExecutionContext<T, E>) -> ExecResult
 	{
 		use frame_support::storage::TransactionOutcome::*;
-		let mut nested = self.nested(dest, trie_id);
 		frame_support::storage::with_transaction(|| {
-			let output = func(&mut nested);
+			let (output, storage_meter) = {
+				let mut nested = self.nested(dest, trie_id);
+				let output = func(&mut nested);
+				(output, nested.storage_meter)
+			};
+			let output = output.and_then(|rv| {
+				if rv.flags.contains(ReturnFlags::REVERT) {
+					return Ok(rv);
+				}
+				storage_meter.enforce_limit()?;
+				self.storage_meter.absorb(storage_meter);
+				if self.depth == 0 {
+					self.storage_deposit = mem::replace(&mut self.storage_meter, Meter::new(None))
+						.settle(&self.self_account)?;
+				}
+				Ok(rv)
+			});
 			match output {
 				Ok(ref rv) if !rv.flags.contains(ReturnFlags::REVERT) => Commit(output),
 				_ => Rollback(output),
@@ -635,26 +561,6 @@ where
 		&self.self_account == account ||
 			self.caller.map_or(false, |caller| caller.is_live(account))
 	}
-
-	fn transactor_kind(&self) -> TransactorKind {
-		if self.depth == 0 {
-			debug_assert!(self.self_trie_id.is_none());
-			debug_assert!(self.caller.is_none());
-			debug_assert!(ContractInfoOf::<T>::get(&self.self_account).is_none());
-			TransactorKind::PlainAccount
-		} else {
-			TransactorKind::Contract
-		}
-	}
-}
-
-/// Describes whether we deal with a contract or a plain account.
-enum TransactorKind {
-	/// Transaction was initiated from a plain account. That can be either be through a
-	/// signed transaction or through RPC.
-	PlainAccount,
-	/// The call was initiated by a contract account.
-	Contract,
 }
 
 /// Describes possible transfer causes.
@@ -668,11 +574,9 @@ enum TransferCause {
 ///
 /// We only allow allow for draining all funds of the sender if `cause` is
 /// is specified as `Terminate`. Otherwise, any transfer that would bring the sender below the
-/// subsistence threshold (for contracts) or the existential deposit (for plain accounts)
-/// results in an error.
+/// existential deposit results in an error.
 fn transfer<T: Config>(
 	cause: TransferCause,
-	origin: TransactorKind,
 	transactor: &T::AccountId,
 	dest: &T::AccountId,
 	value: BalanceOf<T>,
@@ -680,22 +584,10 @@ fn transfer<T: Config>(
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
 {
-	use self::TransferCause::*;
-	use self::TransactorKind::*;
-
-	// Only seal_terminate is allowed to bring the sender below the subsistence
-	// threshold or even existential deposit.
-	let existence_requirement = match (cause, origin) {
-		(Terminate, _) => ExistenceRequirement::AllowDeath,
-		(_, Contract) => {
-			ensure!(
-				T::Currency::total_balance(transactor).saturating_sub(value) >=
-					Contracts::<T>::subsistence_threshold(),
-				Error::<T>::BelowSubsistenceThreshold,
-			);
-			ExistenceRequirement::KeepAlive
-		},
-		(_, PlainAccount) => ExistenceRequirement::KeepAlive,
+	// Only seal_terminate is allowed to bring the sender below the existential deposit.
+	let existence_requirement = match cause {
+		TransferCause::Terminate => ExistenceRequirement::AllowDeath,
+		TransferCause::Call | TransferCause::Instantiate => ExistenceRequirement::KeepAlive,
 	};
 
 	T::Currency::transfer(transactor, dest, value, existence_requirement)
@@ -721,7 +613,6 @@ struct CallContext<'a, 'b: 'a, T: Config + 'b, E> {
 	value_transferred: BalanceOf<T>,
 	timestamp: MomentOf<T>,
 	block_number: T::BlockNumber,
-	rent_params: RentParams<T>,
 	_phantom: PhantomData<E>,
 }
 
@@ -750,12 +641,17 @@ where
 				expect can't fail;\
 				qed",
 		);
-		// write panics if the passed account is not alive.
-		// the contract must be in the alive state within the `CallContext`;\
-		// the contract cannot be absent in storage;
+		// write panics if the passed account is not a contract.
+		// the contract cannot be absent in storage within the `CallContext`;
 		// write cannot return `None`;
 		// qed
-		Storage::<T>::write(&self.ctx.self_account, trie_id, &key, value)
+		Storage::<T>::write(
+			&self.ctx.self_account,
+			trie_id,
+			&key,
+			value,
+			Some(&mut self.ctx.storage_meter),
+		)
 	}
 
 	fn instantiate(
@@ -781,7 +677,6 @@ where
 	) -> DispatchResult {
 		transfer::<T>(
 			TransferCause::Call,
-			TransactorKind::Contract,
 			&self.ctx.self_account.clone(),
 			to,
 			value,
@@ -801,20 +696,20 @@ where
 		}
 		transfer::<T>(
 			TransferCause::Terminate,
-			TransactorKind::Contract,
 			&self_id,
 			beneficiary,
 			value,
 		).map_err(|e| (e, 0))?;
-		if let Some(ContractInfo::Alive(info)) = ContractInfoOf::<T>::take(&self_id) {
+		if let Some(info) = ContractInfoOf::<T>::take(&self_id) {
 			Storage::<T>::queue_trie_for_deletion(&info).map_err(|e| (e, 0))?;
+			self.ctx.storage_meter.terminate(&self_id, &info);
 			let code_len = E::remove_user(info.code_hash);
 			Contracts::<T>::deposit_event(Event::Terminated(self_id, beneficiary.clone()));
 			Ok(code_len)
 		} else {
 			panic!(
 				"this function is only invoked by in the context of a contract;\
-				this contract is therefore alive;\
+				this contract therefore exists;\
 				qed"
 			);
 		}
@@ -830,40 +725,6 @@ where
 		self.ctx.call(to.clone(), value, gas_meter, input_data)
 	}
 
-	fn restore_to(
-		&mut self,
-		dest: AccountIdOf<Self::T>,
-		code_hash: CodeHash<Self::T>,
-		rent_allowance: BalanceOf<Self::T>,
-		delta: Vec<StorageKey>,
-	) -> Result<(u32, u32), (DispatchError, u32, u32)> {
-		if let Some(caller_ctx) = self.ctx.caller {
-			if caller_ctx.is_live(&self.ctx.self_account) {
-				return Err((Error::<T>::ReentranceDenied.into(), 0, 0));
-			}
-		}
-
-		let result = Rent::<T, E>::restore_to(
-			self.ctx.self_account.clone(),
-			dest.clone(),
-			code_hash.clone(),
-			rent_allowance,
-			delta,
-		);
-		if let Ok(_) = result {
-			deposit_event::<Self::T>(
-				vec![],
-				Event::Restored(
-					self.ctx.self_account.clone(),
-					dest,
-					code_hash,
-					rent_allowance,
-				),
-			);
-		}
-		result
-	}
-
 	fn address(&self) -> &T::AccountId {
 		&self.ctx.self_account
 	}
@@ -892,10 +753,6 @@ where
 		T::Currency::minimum_balance()
 	}
 
-	fn tombstone_deposit(&self) -> BalanceOf<T> {
-		T::TombstoneDeposit::get()
-	}
-
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
 		deposit_event::<Self::T>(
 			topics,
@@ -903,22 +760,6 @@ where
 		);
 	}
 
-	fn set_rent_allowance(&mut self, rent_allowance: BalanceOf<T>) {
-		if let Err(storage::ContractAbsentError) =
-			Storage::<T>::set_rent_allowance(&self.ctx.self_account, rent_allowance)
-		{
-			panic!(
-				"`self_account` points to an alive contract within the `CallContext`;
-					set_rent_allowance cannot return `Err`; qed"
-			);
-		}
-	}
-
-	fn rent_allowance(&self) -> BalanceOf<T> {
-		Storage::<T>::rent_allowance(&self.ctx.self_account)
-			.unwrap_or_else(|_| <BalanceOf<T>>::max_value()) // Must never be triggered actually
-	}
-
 	fn block_number(&self) -> T::BlockNumber { self.block_number }
 
 	fn max_value_size(&self) -> u32 {
@@ -932,10 +773,6 @@ where
 	fn schedule(&self) -> &Schedule<Self::T> {
 		&self.ctx.schedule
 	}
-
-	fn rent_params(&self) -> &RentParams<Self::T> {
-		&self.rent_params
-	}
 }
 
 fn deposit_event<T: Config>(
@@ -978,10 +815,10 @@ mod tests {
 			test_utils::{place_contract, set_balance, get_balance},
 		},
 		exec::ExportedFunction::*,
-		Error, Weight, CurrentSchedule,
+		Error, Weight, CurrentSchedule, ContractInfoOf,
 	};
 	use sp_core::Bytes;
-	use frame_support::assert_noop;
+	use frame_support::{assert_noop, traits::ReservableCurrency};
 	use sp_runtime::DispatchError;
 	use assert_matches::assert_matches;
 	use std::{cell::RefCell, collections::HashMap, rc::Rc};
@@ -1141,10 +978,6 @@ mod tests {
 			&self.code_hash
 		}
 
-		fn occupied_storage(&self) -> u32 {
-			0
-		}
-
 		fn code_len(&self) -> u32 {
 			0
 		}
@@ -1156,6 +989,10 @@ mod tests {
 		fn refcount(&self) -> u32 {
 			self.refcount as u32
 		}
+
+		fn is_deterministic(&self) -> bool {
+			true
+		}
 	}
 
 	fn exec_success() -> ExecResult {
@@ -1177,7 +1014,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			place_contract(&BOB, exec_ch);
 
 			assert_matches!(
@@ -1202,7 +1039,6 @@ mod tests {
 
 			super::transfer::<Test>(
 				super::TransferCause::Call,
-				super::TransactorKind::PlainAccount,
 				&origin,
 				&dest,
 				55,
@@ -1227,7 +1063,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(origin.clone(), &schedule);
+			let mut ctx = MockContext::top_level(origin.clone(), &schedule, None);
 			place_contract(&BOB, return_ch);
 			set_balance(&origin, 100);
 			let balance = get_balance(&dest);
@@ -1241,9 +1077,7 @@ mod tests {
 
 			assert!(!output.0.is_success());
 			assert_eq!(get_balance(&origin), 100);
-
-			// the rent is still charged
-			assert!(get_balance(&dest) < balance);
+			assert_eq!(get_balance(&dest), balance);
 		});
 	}
 
@@ -1259,7 +1093,6 @@ mod tests {
 
 			let result = super::transfer::<Test>(
 				super::TransferCause::Call,
-				super::TransactorKind::PlainAccount,
 				&origin,
 				&dest,
 				100,
@@ -1287,7 +1120,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(origin, &schedule);
+			let mut ctx = MockContext::top_level(origin, &schedule, None);
 			place_contract(&BOB, return_ch);
 
 			let result = ctx.call(
@@ -1316,7 +1149,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(origin, &schedule);
+			let mut ctx = MockContext::top_level(origin, &schedule, None);
 			place_contract(&BOB, return_ch);
 
 			let result = ctx.call(
@@ -1342,7 +1175,7 @@ mod tests {
 		// This one tests passing the input data into a contract via call.
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			place_contract(&BOB, input_data_ch);
 
 			let result = ctx.call(
@@ -1365,17 +1198,17 @@ mod tests {
 		// This one tests passing the input data into a contract via instantiate.
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let min_balance = <Test as Config>::Currency::minimum_balance();
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 			let executable = MockExecutable::from_storage(
 				input_data_ch, &schedule, &mut gas_meter
 			).unwrap();
 
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 
 			let result = ctx.instantiate(
-				subsistence * 3,
+				min_balance * 3,
 				&mut gas_meter,
 				executable,
 				vec![1, 2, 3, 4],
@@ -1418,7 +1251,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			set_balance(&BOB, 1);
 			place_contract(&BOB, recurse_ch);
 
@@ -1466,7 +1299,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(origin.clone(), &schedule);
+			let mut ctx = MockContext::top_level(origin.clone(), &schedule, None);
 			place_contract(&dest, bob_ch);
 			place_contract(&CHARLIE, charlie_ch);
 
@@ -1504,7 +1337,7 @@ mod tests {
 
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			place_contract(&BOB, bob_ch);
 			place_contract(&CHARLIE, charlie_ch);
 
@@ -1525,7 +1358,7 @@ mod tests {
 
 		ExtBuilder::default().existential_deposit(15).build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 			let executable = MockExecutable::from_storage(
 				dummy_ch, &schedule, &mut gas_meter
@@ -1553,7 +1386,7 @@ mod tests {
 
 		ExtBuilder::default().existential_deposit(15).build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 			let executable = MockExecutable::from_storage(
 				dummy_ch, &schedule, &mut gas_meter
@@ -1589,7 +1422,7 @@ mod tests {
 
 		ExtBuilder::default().existential_deposit(15).build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 			let executable = MockExecutable::from_storage(
 				dummy_ch, &schedule, &mut gas_meter
@@ -1627,7 +1460,7 @@ mod tests {
 				// Instantiate a contract and save it's address in `instantiated_contract_address`.
 				let (address, output, _) = ctx.ext.instantiate(
 					dummy_ch,
-					Contracts::<Test>::subsistence_threshold() * 3,
+					<Test as Config>::Currency::minimum_balance() * 3,
 					ctx.gas_meter,
 					vec![],
 					&[48, 49, 50],
@@ -1640,8 +1473,8 @@ mod tests {
 
 		ExtBuilder::default().existential_deposit(15).build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
-			set_balance(&ALICE, Contracts::<Test>::subsistence_threshold() * 100);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
+			set_balance(&ALICE, <Test as Config>::Currency::minimum_balance() * 100);
 			place_contract(&BOB, instantiator_ch);
 
 			assert_matches!(
@@ -1689,7 +1522,7 @@ mod tests {
 
 		ExtBuilder::default().existential_deposit(15).build().execute_with(|| {
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
+			let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 			set_balance(&ALICE, 1000);
 			set_balance(&BOB, 100);
 			place_contract(&BOB, instantiator_ch);
@@ -1717,7 +1550,7 @@ mod tests {
 			.build()
 			.execute_with(|| {
 				let schedule = <CurrentSchedule<Test>>::get();
-				let mut ctx = MockContext::top_level(ALICE, &schedule);
+				let mut ctx = MockContext::top_level(ALICE, &schedule, None);
 				let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 				let executable = MockExecutable::from_storage(
 					terminate_ch, &schedule, &mut gas_meter
@@ -1742,110 +1575,132 @@ mod tests {
 			});
 	}
 
-	#[test]
-	fn rent_allowance() {
-		let rent_allowance_ch = MockLoader::insert(Constructor, |ctx, _| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let allowance = subsistence * 3;
-			assert_eq!(ctx.ext.rent_allowance(), <BalanceOf<Test>>::max_value());
-			ctx.ext.set_rent_allowance(allowance);
-			assert_eq!(ctx.ext.rent_allowance(), allowance);
+	fn storage_deposit_for_item(len: u64) -> u64 {
+		<Test as Config>::DepositPerItem::get() + <Test as Config>::DepositPerByte::get() * len
+	}
+
+	/// Writes its input to storage. Empty input removes the storage item.
+	fn storage_writer() -> CodeHash<Test> {
+		MockLoader::insert(Call, |ctx, _| {
+			let value = Some(ctx.input_data).filter(|data| !data.is_empty());
+			ctx.ext.set_storage([1; 32], value).unwrap();
 			exec_success()
-		});
+		})
+	}
+
+	#[test]
+	fn storage_deposit_is_charged_and_refunded() {
+		let code_hash = storage_writer();
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			let executable = MockExecutable::from_storage(
-				rent_allowance_ch, &schedule, &mut gas_meter
-			).unwrap();
-			set_balance(&ALICE, subsistence * 10);
+			let deposit = storage_deposit_for_item(4);
+			place_contract(&BOB, code_hash);
+			set_balance(&ALICE, 1000);
 
-			let result = ctx.instantiate(
-				subsistence * 5,
-				&mut gas_meter,
-				executable,
-				vec![],
-				&[],
+			assert_matches!(
+				MockContext::top_level(ALICE, &schedule, None).call(
+					BOB, 0, &mut GasMeter::<Test>::new(GAS_LIMIT), vec![1, 2, 3, 4],
+				),
+				Ok(_)
 			);
-			assert_matches!(result, Ok(_));
+			assert_eq!(get_balance(&ALICE), 1000 - deposit);
+			assert_eq!(<Test as Config>::Currency::reserved_balance(&BOB), deposit);
+			assert_eq!(ContractInfoOf::<Test>::get(&BOB).unwrap().storage_deposit, deposit);
+
+			// Removing the item refunds the whole deposit.
+			assert_matches!(
+				MockContext::top_level(ALICE, &schedule, None).call(
+					BOB, 0, &mut GasMeter::<Test>::new(GAS_LIMIT), vec![],
+				),
+				Ok(_)
+			);
+			assert_eq!(get_balance(&ALICE), 1000);
+			assert_eq!(<Test as Config>::Currency::reserved_balance(&BOB), 0);
+			assert_eq!(ContractInfoOf::<Test>::get(&BOB).unwrap().storage_deposit, 0);
 		});
 	}
 
 	#[test]
-	fn rent_params_works() {
-		let code_hash = MockLoader::insert(Call, |ctx, executable| {
-			let address = ctx.ext.address();
-			let contract = <ContractInfoOf<Test>>::get(address)
-				.and_then(|c| c.get_alive())
-				.unwrap();
-			assert_eq!(ctx.ext.rent_params(), &RentParams::new(address, &contract, executable));
-			exec_success()
-		});
+	fn storage_deposit_limit_is_enforced() {
+		let code_hash = storage_writer();
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
+			let deposit = storage_deposit_for_item(4);
 			place_contract(&BOB, code_hash);
-			ctx.call(
-				BOB,
-				0,
-				&mut gas_meter,
-				vec![],
-			).unwrap();
+			set_balance(&ALICE, 1000);
+
+			assert_matches!(
+				MockContext::top_level(ALICE, &schedule, Some(deposit - 1)).call(
+					BOB, 0, &mut GasMeter::<Test>::new(GAS_LIMIT), vec![1, 2, 3, 4],
+				),
+				Err((ExecError { error, .. }, _))
+					if error == Error::<Test>::StorageDepositLimitExhausted.into()
+			);
+			assert_eq!(get_balance(&ALICE), 1000);
+			assert_eq!(ContractInfoOf::<Test>::get(&BOB).unwrap().pair_count, 0);
+
+			assert_matches!(
+				MockContext::top_level(ALICE, &schedule, Some(deposit)).call(
+					BOB, 0, &mut GasMeter::<Test>::new(GAS_LIMIT), vec![1, 2, 3, 4],
+				),
+				Ok(_)
+			);
+			assert_eq!(get_balance(&ALICE), 1000 - deposit);
 		});
 	}
 
 	#[test]
-	fn rent_params_snapshotted() {
-		let code_hash = MockLoader::insert(Call, |ctx, executable| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let address = ctx.ext.address();
-			let contract = <ContractInfoOf<Test>>::get(address)
-				.and_then(|c| c.get_alive())
-				.unwrap();
-			let rent_params = RentParams::new(address, &contract, executable);
-
-			// Changing the allowance during the call: rent params stay unchanged.
-			let allowance = 42;
-			assert_ne!(allowance, rent_params.rent_allowance);
-			ctx.ext.set_rent_allowance(allowance);
-			assert_eq!(ctx.ext.rent_params(), &rent_params);
-
-			// Creating another instance from the same code_hash increases the refcount.
-			// This is also not reflected in the rent params.
-			assert_eq!(MockLoader::refcount(&executable.code_hash), 1);
-			ctx.ext.instantiate(
-				executable.code_hash,
-				subsistence * 25,
-				&mut GasMeter::<Test>::new(GAS_LIMIT),
-				vec![],
-				&[],
-			).unwrap();
-			assert_eq!(MockLoader::refcount(&executable.code_hash), 2);
-			assert_eq!(ctx.ext.rent_params(), &rent_params);
+	fn storage_deposit_fails_without_funds() {
+		let code_hash = storage_writer();
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <CurrentSchedule<Test>>::get();
+			place_contract(&BOB, code_hash);
+			// Paying the deposit would kill the origin.
+			set_balance(&ALICE, storage_deposit_for_item(4));
 
+			assert_matches!(
+				MockContext::top_level(ALICE, &schedule, None).call(
+					BOB, 0, &mut GasMeter::<Test>::new(GAS_LIMIT), vec![1, 2, 3, 4],
+				),
+				Err((ExecError { error, .. }, _))
+					if error == Error::<Test>::StorageDepositNotEnoughFunds.into()
+			);
+			assert_eq!(ContractInfoOf::<Test>::get(&BOB).unwrap().pair_count, 0);
+		});
+	}
+
+	#[test]
+	fn storage_deposit_of_reverted_frame_is_discarded() {
+		let writer_ch = MockLoader::insert(Call, |ctx, _| {
+			ctx.ext.set_storage([1; 32], Some(vec![1, 2, 3, 4])).unwrap();
+			Ok(ExecReturnValue { flags: ReturnFlags::REVERT, data: Bytes(Vec::new()) })
+		});
+		let caller_ch = MockLoader::insert(Call, |ctx, _| {
+			ctx.ext.set_storage([1; 32], Some(vec![1])).unwrap();
+			assert_matches!(ctx.ext.call(&CHARLIE, 0, ctx.gas_meter, vec![]), Ok(_));
 			exec_success()
 		});
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
 			let schedule = <CurrentSchedule<Test>>::get();
-			let mut ctx = MockContext::top_level(ALICE, &schedule);
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 100);
-			place_contract(&BOB, code_hash);
-			ctx.call(
-				BOB,
-				subsistence * 50,
-				&mut gas_meter,
-				vec![],
-			).unwrap();
+			place_contract(&BOB, caller_ch);
+			place_contract(&CHARLIE, writer_ch);
+			set_balance(&ALICE, 1000);
+
+			assert_matches!(
+				MockContext::top_level(ALICE, &schedule, None).call(
+					BOB, 0, &mut GasMeter::<Test>::new(GAS_LIMIT), vec![],
+				),
+				Ok(_)
+			);
+
+			// Only the storage of the frame that was not reverted is paid for.
+			assert_eq!(get_balance(&ALICE), 1000 - storage_deposit_for_item(1));
+			assert_eq!(<Test as Config>::Currency::reserved_balance(&CHARLIE), 0);
+			assert_eq!(ContractInfoOf::<Test>::get(&CHARLIE).unwrap().pair_count, 0);
 		});
 	}
 }
@@ -32,6 +32,17 @@
 //! This means that multiple smart-contracts can be instantiated from the same hash, without replicating
 //! the code each time.
 //!
+//! Code deployed by instantiating a contract is removed once no contract uses it anymore. Code can
+//! also be uploaded on its own using `upload_code`, for example by a factory that instantiates
+//! many contracts from it. Such code is kept until its owner removes it.
+//!
+//! ### Determinism
+//!
+//! Code is rejected when it uses floating point types because their results may differ between
+//! machines. Code can be uploaded with [`Determinism::AllowIndeterminism`] to accept it anyway.
+//! Such code is refused by any on-chain execution and can only be executed off-chain, for
+//! example when dry-running calls through an RPC.
+//!
 //! When a smart-contract is called, its associated code is retrieved via the code hash and gets executed.
 //! This call can alter the storage entries of the smart-contract account, instantiate new smart-contracts,
 //! or call other smart-contracts.
@@ -49,6 +60,14 @@
 //! then all of B's calls are reverted. Assuming correct error handling by contract A, A's other calls and state
 //! changes still persist.
 //!
+//! ### Storage Deposit
+//!
+//! Contracts do not pay rent for the storage they occupy. Instead, whoever causes a contract to
+//! add storage pays a deposit proportional to the amount of added bytes and items. This deposit
+//! is reserved on the contract's account and refunded to whoever removes that storage again.
+//! Callers can bound the deposit they are willing to pay with the `storage_deposit_limit`
+//! parameter of every dispatchable that executes a contract.
+//!
 //! ### Notable Scenarios
 //!
 //! Contract call failures are not always cascading. When failures occur in a sub-call, they do not "bubble up",
@@ -69,7 +88,10 @@
 //! * [`Pallet::instantiate`] - The same as `instantiate_with_code` but instead of uploading new
 //! code an existing `code_hash` is supplied.
 //! * [`Pallet::call`] - Makes a call to an account, optionally transferring some balance.
-//! * [`Pallet::claim_surcharge`] - Evict a contract that cannot pay rent anymore.
+//! * [`Pallet::upload_code`] - Uploads a wasm binary without instantiating a contract from it.
+//! The uploader pays a deposit for the code and becomes its owner.
+//! * [`Pallet::remove_code`] - Removes code uploaded by the caller that is no longer used by any
+//! contract and refunds its deposit.
 //!
 //! ## Usage
 //!
@@ -92,7 +114,6 @@ mod gas;
 mod storage;
 mod exec;
 mod wasm;
-mod rent;
 mod benchmarking;
 mod schedule;
 mod migration;
@@ -103,39 +124,32 @@ pub mod weights;
 #[cfg(test)]
 mod tests;
 
-pub use crate::{pallet::*, schedule::Schedule};
+pub use crate::{pallet::*, schedule::Schedule, wasm::Determinism};
 use crate::{
 	gas::GasMeter,
 	exec::{ExecutionContext, Executable},
-	rent::Rent,
-	storage::{Storage, DeletedContract, ContractInfo, AliveContractInfo, TombstoneContractInfo},
+	storage::{Storage, DeletedContract, ContractInfo},
 	weights::WeightInfo,
-	wasm::PrefabWasmModule,
+	wasm::{PrefabWasmModule, OwnerInfo},
 };
 use sp_core::{Bytes, crypto::UncheckedFrom};
 use sp_std::prelude::*;
-use sp_runtime::{
-	traits::{
-		Hash, StaticLookup, Convert, Saturating, Zero,
-	},
-	Perbill,
-};
+use sp_runtime::traits::{Hash, StaticLookup, Convert};
 use frame_support::{
-	traits::{OnUnbalanced, Currency, Get, Time, Randomness},
-	weights::{Weight, PostDispatchInfo, WithPostDispatchInfo},
+	ensure,
+	traits::{Currency, ReservableCurrency, Get, Time, Randomness},
+	weights::Weight,
 };
 use frame_system::Pallet as System;
 use pallet_contracts_primitives::{
-	RentProjectionResult, GetStorageResult, ContractAccessError, ContractExecResult,
-	ContractInstantiateResult, Code, InstantiateReturnValue,
+	GetStorageResult, ContractAccessError, ContractExecResult, ContractInstantiateResult, Code,
+	InstantiateReturnValue, CodeUploadResult, CodeUploadReturnValue,
 };
 
 type CodeHash<T> = <T as frame_system::Config>::Hash;
 type TrieId = Vec<u8>;
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-type NegativeImbalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -152,63 +166,28 @@ pub mod pallet {
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
 
 		/// The currency in which fees are paid and contract balances are held.
-		type Currency: Currency<Self::AccountId>;
+		///
+		/// Storage deposits are reserved on the contract accounts using this currency.
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Handler for rent payments.
-		type RentPayment: OnUnbalanced<NegativeImbalanceOf<Self>>;
-
-		/// Number of block delay an extrinsic claim surcharge has.
+		/// The amount of balance a caller has to pay for each byte of storage.
 		///
-		/// When claim surcharge is called by an extrinsic the rent is checked
-		/// for current_block - delay
-		#[pallet::constant]
-		type SignedClaimHandicap: Get<Self::BlockNumber>;
-
-		/// The minimum amount required to generate a tombstone.
-		#[pallet::constant]
-		type TombstoneDeposit: Get<BalanceOf<Self>>;
-
-		/// The balance every contract needs to deposit to stay alive indefinitely.
-		///
-		/// This is different from the [`Self::TombstoneDeposit`] because this only needs to be
-		/// deposited while the contract is alive. Costs for additional storage are added to
-		/// this base cost.
-		///
-		/// This is a simple way to ensure that contracts with empty storage eventually get deleted by
-		/// making them pay rent. This creates an incentive to remove them early in order to save rent.
-		#[pallet::constant]
-		type DepositPerContract: Get<BalanceOf<Self>>;
-
-		/// The balance a contract needs to deposit per storage byte to stay alive indefinitely.
+		/// # Note
 		///
-		/// Let's suppose the deposit is 1,000 BU (balance units)/byte and the rent is 1 BU/byte/day,
-		/// then a contract with 1,000,000 BU that uses 1,000 bytes of storage would pay no rent.
-		/// But if the balance reduced to 500,000 BU and the storage stayed the same at 1,000,
-		/// then it would pay 500 BU/day.
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type DepositPerStorageByte: Get<BalanceOf<Self>>;
+		type DepositPerByte: Get<BalanceOf<Self>>;
 
-		/// The balance a contract needs to deposit per storage item to stay alive indefinitely.
+		/// The amount of balance a caller has to pay for each storage item.
 		///
-		/// It works the same as [`Self::DepositPerStorageByte`] but for storage items.
-		#[pallet::constant]
-		type DepositPerStorageItem: Get<BalanceOf<Self>>;
-
-		/// The fraction of the deposit that should be used as rent per block.
+		/// # Note
 		///
-		/// When a contract hasn't enough balance deposited to stay alive indefinitely it needs
-		/// to pay per block for the storage it consumes that is not covered by the deposit.
-		/// This determines how high this rent payment is per block as a fraction of the deposit.
-		#[pallet::constant]
-		type RentFraction: Get<Perbill>;
-
-		/// Reward that is received by the party whose touch has led
-		/// to removal of a contract.
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type SurchargeReward: Get<BalanceOf<Self>>;
+		type DepositPerItem: Get<BalanceOf<Self>>;
 
 		/// The maximum nesting level of a call/instantiate stack.
 		#[pallet::constant]
@@ -301,19 +280,26 @@ pub mod pallet {
 		/// * If the account is a regular account, any value will be transferred.
 		/// * If no account exists and the call value is not less than `existential_deposit`,
 		/// a regular account will be created and any value will be transferred.
+		///
+		/// The `storage_deposit_limit` is the maximum amount of balance that can be charged
+		/// from the caller to pay for the storage consumed. `None` means that only the free
+		/// balance of the caller limits the deposit.
 		#[pallet::weight(T::WeightInfo::call(T::MaxCodeSize::get() / 1024).saturating_add(*gas_limit))]
 		pub fn call(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] value: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			data: Vec<u8>
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
 			let schedule = <CurrentSchedule<T>>::get();
-			let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(origin, &schedule);
+			let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(
+				origin, &schedule, storage_deposit_limit,
+			);
 			let (result, code_len) = match ctx.call(dest, value, &mut gas_meter, data) {
 				Ok((output, len)) => (Ok(output), len),
 				Err((err, len)) => (Err(err), len),
@@ -330,6 +316,8 @@ pub mod pallet {
 		///
 		/// * `endowment`: The balance to transfer from the `origin` to the newly created contract.
 		/// * `gas_limit`: The gas limit enforced when executing the constructor.
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged
+		///   from the caller to pay for the storage consumed.
 		/// * `code`: The contract code to deploy in raw bytes.
 		/// * `data`: The input data to pass to the contract constructor.
 		/// * `salt`: Used for the address derivation. See [`Pallet::contract_address`].
@@ -353,6 +341,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			code: Vec<u8>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
@@ -362,10 +351,16 @@ pub mod pallet {
 			ensure!(code_len <= T::MaxCodeSize::get(), Error::<T>::CodeTooLarge);
 			let mut gas_meter = GasMeter::new(gas_limit);
 			let schedule = <CurrentSchedule<T>>::get();
-			let executable = PrefabWasmModule::from_code(code, &schedule)?;
+			let executable = PrefabWasmModule::from_code(
+				code,
+				&schedule,
+				Determinism::Deterministic,
+			)?;
 			let code_len = executable.code_len();
 			ensure!(code_len <= T::MaxCodeSize::get(), Error::<T>::CodeTooLarge);
-			let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(origin, &schedule);
+			let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(
+				origin, &schedule, storage_deposit_limit,
+			);
 			let result = ctx.instantiate(endowment, &mut gas_meter, executable, data, &salt)
 				.map(|(_address, output)| output);
 			gas_meter.into_dispatch_result(
@@ -387,6 +382,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			code_hash: CodeHash<T>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
@@ -395,7 +391,9 @@ pub mod pallet {
 			let mut gas_meter = GasMeter::new(gas_limit);
 			let schedule = <CurrentSchedule<T>>::get();
 			let executable = PrefabWasmModule::from_storage(code_hash, &schedule, &mut gas_meter)?;
-			let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(origin, &schedule);
+			let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(
+				origin, &schedule, storage_deposit_limit,
+			);
 			let code_len = executable.code_len();
 			let result = ctx.instantiate(endowment, &mut gas_meter, executable, data, &salt)
 				.map(|(_address, output)| output);
@@ -405,72 +403,58 @@ pub mod pallet {
 			)
 		}
 
-		/// Allows block producers to claim a small reward for evicting a contract. If a block
-		/// producer fails to do so, a regular users will be allowed to claim the reward.
+		/// Upload new `code` without instantiating a contract from it.
 		///
-		/// In case of a successful eviction no fees are charged from the sender. However, the
-		/// reward is capped by the total amount of rent that was payed by the contract while
-		/// it was alive.
+		/// If the code does not already exist a deposit is reserved from the caller which
+		/// becomes the owner of the code. The code is kept until the owner removes it with
+		/// [`Self::remove_code`], even if no contract uses it. Contracts can then be instantiated
+		/// from the code with [`Self::instantiate`].
 		///
-		/// If contract is not evicted as a result of this call, [`Error::ContractNotEvictable`]
-		/// is returned and the sender is not eligible for the reward.
-		#[pallet::weight(T::WeightInfo::claim_surcharge(T::MaxCodeSize::get() / 1024))]
-		pub fn claim_surcharge(
+		/// The `storage_deposit_limit` is the maximum amount of balance that can be charged
+		/// for the deposit. Nothing is charged if the code is already owned by someone.
+		///
+		/// Code uploaded with [`Determinism::AllowIndeterminism`] may use floating point types.
+		/// It can not be executed on-chain but only by off-chain dry-runs. Code that doesn't use
+		/// them is stored as deterministic regardless of `determinism`.
+		#[pallet::weight(T::WeightInfo::upload_code(code.len() as u32 / 1024))]
+		pub fn upload_code(
 			origin: OriginFor<T>,
-			dest: T::AccountId,
-			aux_sender: Option<T::AccountId>
+			code: Vec<u8>,
+			storage_deposit_limit: Option<BalanceOf<T>>,
+			determinism: Determinism,
 		) -> DispatchResultWithPostInfo {
-			let origin = origin.into();
-			let (signed, rewarded) = match (origin, aux_sender) {
-				(Ok(frame_system::RawOrigin::Signed(account)), None) => {
-					(true, account)
-				},
-				(Ok(frame_system::RawOrigin::None), Some(aux_sender)) => {
-					(false, aux_sender)
-				},
-				_ => Err(Error::<T>::InvalidSurchargeClaim)?,
-			};
-
-			// Add some advantage for block producers (who send unsigned extrinsics) by
-			// adding a handicap: for signed extrinsics we use a slightly older block number
-			// for the eviction check. This can be viewed as if we pushed regular users back in past.
-			let handicap = if signed {
-				T::SignedClaimHandicap::get()
-			} else {
-				Zero::zero()
-			};
+			let origin = ensure_signed(origin)?;
+			ensure!(code.len() as u32 <= T::MaxCodeSize::get(), Error::<T>::CodeTooLarge);
+			let schedule = <CurrentSchedule<T>>::get();
+			let executable = PrefabWasmModule::from_code(code, &schedule, determinism)?;
+			ensure!(executable.code_len() <= T::MaxCodeSize::get(), Error::<T>::CodeTooLarge);
+			executable.store_uploaded(&origin, storage_deposit_limit)?;
+			Ok(().into())
+		}
 
-			// If poking the contract has lead to eviction of the contract, give out the rewards.
-			match Rent::<T, PrefabWasmModule<T>>::try_eviction(&dest, handicap)? {
-				(Some(rent_payed), code_len) => {
-					T::Currency::deposit_into_existing(
-						&rewarded,
-						T::SurchargeReward::get().min(rent_payed),
-					)
-					.map(|_| PostDispatchInfo {
-						actual_weight: Some(T::WeightInfo::claim_surcharge(code_len / 1024)),
-						pays_fee: Pays::No,
-					})
-					.map_err(Into::into)
-				}
-				(None, code_len) => Err(Error::<T>::ContractNotEvictable.with_weight(
-					T::WeightInfo::claim_surcharge(code_len / 1024)
-				)),
-			}
+		/// Remove the code stored under `code_hash` and refund the deposit to its owner.
+		///
+		/// Only code uploaded with [`Self::upload_code`] can be removed and only by its owner.
+		/// The removal fails as long as any contract uses the code.
+		#[pallet::weight(T::WeightInfo::remove_code())]
+		pub fn remove_code(
+			origin: OriginFor<T>,
+			code_hash: CodeHash<T>,
+		) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+			PrefabWasmModule::<T>::remove(&origin, code_hash)?;
+			Ok(().into())
 		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	#[pallet::metadata(T::AccountId = "AccountId", T::Hash = "Hash", BalanceOf<T> = "Balance")]
+	#[pallet::metadata(T::AccountId = "AccountId", T::Hash = "Hash")]
 	pub enum Event<T: Config> {
 		/// Contract deployed by address at the specified address. \[deployer, contract\]
 		Instantiated(T::AccountId, T::AccountId),
 
-		/// Contract has been evicted and is now in tombstone state. \[contract\]
-		Evicted(T::AccountId),
-
-		/// Contract has been terminated without leaving a tombstone.
+		/// Contract has been removed.
 		/// \[contract, beneficiary\]
 		///
 		/// # Params
@@ -480,21 +464,10 @@ pub mod pallet {
 		///
 		/// # Note
 		///
-		/// The only way for a contract to be removed without a tombstone and emitting
-		/// this event is by calling `seal_terminate`.
+		/// The only way for a contract to be removed and emitting this event is by calling
+		/// `seal_terminate`.
 		Terminated(T::AccountId, T::AccountId),
 
-		/// Restoration of a contract has been successful.
-		/// \[restorer, dest, code_hash, rent_allowance\]
-		///
-		/// # Params
-		///
-		/// - `restorer`: Account ID of the restoring contract.
-		/// - `dest`: Account ID of the restored contract.
-		/// - `code_hash`: Code hash of the restored contract.
-		/// - `rent_allowance`: Rent allowance of the restored contract.
-		Restored(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
-
 		/// Code with the specified hash has been stored. \[code_hash\]
 		CodeStored(T::Hash),
 
@@ -519,7 +492,8 @@ pub mod pallet {
 		/// A code with the specified hash was removed.
 		/// \[code_hash\]
 		///
-		/// This happens when the last contract that uses this code hash was removed or evicted.
+		/// This happens when the last contract that uses this code hash was removed or
+		/// when the owner of an uploaded code removed it.
 		CodeRemoved(T::Hash),
 	}
 
@@ -527,28 +501,10 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// A new schedule must have a greater version than the current one.
 		InvalidScheduleVersion,
-		/// An origin must be signed or inherent and auxiliary sender only provided on inherent.
-		InvalidSurchargeClaim,
-		/// Cannot restore from nonexisting or tombstone contract.
-		InvalidSourceContract,
-		/// Cannot restore to nonexisting or alive contract.
-		InvalidDestinationContract,
-		/// Tombstones don't match.
-		InvalidTombstone,
-		/// An origin TrieId written in the current block.
-		InvalidContractOrigin,
 		/// The executed contract exhausted its gas limit.
 		OutOfGas,
 		/// The output buffer supplied to a contract API call was too small.
 		OutputBufferTooSmall,
-		/// Performing the requested transfer would have brought the contract below
-		/// the subsistence threshold. No transfer is allowed to do this in order to allow
-		/// for a tombstone to be created. Use `seal_terminate` to remove a contract without
-		/// leaving a tombstone behind.
-		BelowSubsistenceThreshold,
-		/// The newly created contract is below the subsistence threshold after executing
-		/// its contructor. No contracts are allowed to exist below that threshold.
-		NewContractNotFunded,
 		/// Performing the requested transfer failed for a reason originating in the
 		/// chosen currency implementation of the runtime. Most probably the balance is
 		/// too low or locks are placed on it.
@@ -556,8 +512,7 @@ pub mod pallet {
 		/// Performing a call was denied because the calling depth reached the limit
 		/// of what is specified in the schedule.
 		MaxCallDepthReached,
-		/// The contract that was called is either no contract at all (a plain account)
-		/// or is a tombstone.
+		/// The contract that was called is no contract (a plain account).
 		NotCallable,
 		/// The code supplied to `instantiate_with_code` exceeds the limit specified in the
 		/// current schedule.
@@ -573,8 +528,7 @@ pub mod pallet {
 		/// The size defined in `T::MaxValueSize` was exceeded.
 		ValueTooLarge,
 		/// The action performed is not allowed while the contract performing it is already
-		/// on the call stack. Those actions are contract self destruction and restoration
-		/// of a tombstone.
+		/// on the call stack. This applies to contract self destruction.
 		ReentranceDenied,
 		/// `seal_input` was called twice from the same contract execution context.
 		InputAlreadyRead,
@@ -590,15 +544,10 @@ pub mod pallet {
 		NoChainExtension,
 		/// Removal of a contract failed because the deletion queue is full.
 		///
-		/// This can happen when either calling [`Pallet::claim_surcharge`] or `seal_terminate`.
+		/// This can happen when calling `seal_terminate`.
 		/// The queue is filled by deleting contracts and emptied by a fixed amount each block.
 		/// Trying again during another block is the only way to resolve this issue.
 		DeletionQueueFull,
-		/// A contract could not be evicted because it has enough balance to pay rent.
-		///
-		/// This can be returned from [`Pallet::claim_surcharge`] because the target
-		/// contract has enough balance to pay for its rent.
-		ContractNotEvictable,
 		/// A storage modification exhausted the 32bit type that holds the storage size.
 		///
 		/// This can either happen when the accumulated storage in bytes is too large or
@@ -606,6 +555,15 @@ pub mod pallet {
 		StorageExhausted,
 		/// A contract with the same AccountId already exists.
 		DuplicateContract,
+		/// The origin doesn't have enough free balance to pay for the storage deposit
+		/// that is charged by the call.
+		StorageDepositNotEnoughFunds,
+		/// More storage was created than allowed by the storage deposit limit.
+		StorageDepositLimitExhausted,
+		/// The code can not be removed because a contract still uses it.
+		CodeInUse,
+		/// The code is not deterministic and can therefore not be executed on-chain.
+		Indeterministic,
 	}
 
 	/// Current cost schedule for contracts.
@@ -620,6 +578,10 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type CodeStorage<T: Config> = StorageMap<_, Identity, CodeHash<T>, PrefabWasmModule<T>>;
 
+	/// The owner of and the deposit paid for code that was uploaded with `upload_code`.
+	#[pallet::storage]
+	pub(crate) type OwnerInfoOf<T: Config> = StorageMap<_, Identity, CodeHash<T>, OwnerInfo<T>>;
+
 	/// The subtrie counter.
 	#[pallet::storage]
 	pub(crate) type AccountCounter<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -630,7 +592,7 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ContractInfoOf<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ContractInfo<T>>;
 
-	/// Evicted contracts that await child trie deletion.
+	/// Removed contracts that await child trie deletion.
 	///
 	/// Child trie deletion is a heavy operation depending on the amount of storage items
 	/// stored in said trie. Therefore this operation is performed lazily in `on_initialize`.
@@ -670,22 +632,33 @@ where
 	/// This function is similar to [`Self::call`], but doesn't perform any address lookups
 	/// and better suitable for calling directly from Rust.
 	///
-	/// It returns the execution result and the amount of used weight.
+	/// It returns the execution result, the amount of used weight and the storage deposit
+	/// that was charged from or refunded to the `origin`.
+	///
+	/// # Note
+	///
+	/// `determinism` must only allow indeterminism when the call is not executed on-chain,
+	/// for example when dry-running it through an RPC.
 	pub fn bare_call(
 		origin: T::AccountId,
 		dest: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		input_data: Vec<u8>,
-	) -> ContractExecResult {
+		determinism: Determinism,
+	) -> ContractExecResult<BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
 		let schedule = <CurrentSchedule<T>>::get();
-		let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(origin, &schedule);
+		let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(
+			origin, &schedule, storage_deposit_limit,
+		).with_determinism(determinism);
 		let result = ctx.call(dest, value, &mut gas_meter, input_data);
 		let gas_consumed = gas_meter.gas_spent();
 		ContractExecResult {
 			result: result.map(|r| r.0).map_err(|r| r.0.error),
 			gas_consumed,
+			storage_deposit: ctx.storage_deposit(),
 			debug_message: Bytes(Vec::new()),
 		}
 	}
@@ -695,25 +668,32 @@ where
 	/// This function is similar to [`Self::instantiate`], but doesn't perform any address lookups
 	/// and better suitable for calling directly from Rust.
 	///
-	/// It returns the execution result, account id and the amount of used weight.
+	/// It returns the execution result, account id, the amount of used weight and the
+	/// storage deposit that was charged from or refunded to the `origin`.
+	///
+	/// # Note
 	///
-	/// If `compute_projection` is set to `true` the result also contains the rent projection.
-	/// This is optional because some non trivial and stateful work is performed to compute
-	/// the projection. See [`Self::rent_projection`].
+	/// `determinism` must only allow indeterminism when the instantiation is not executed
+	/// on-chain, for example when dry-running it through an RPC. It also applies to the
+	/// checks of newly uploaded `code`.
 	pub fn bare_instantiate(
 		origin: T::AccountId,
 		endowment: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		code: Code<CodeHash<T>>,
 		data: Vec<u8>,
 		salt: Vec<u8>,
-		compute_projection: bool,
-	) -> ContractInstantiateResult<T::AccountId, T::BlockNumber> {
+		determinism: Determinism,
+	) -> ContractInstantiateResult<T::AccountId, BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
 		let schedule = <CurrentSchedule<T>>::get();
-		let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(origin, &schedule);
+		let mut ctx = ExecutionContext::<T, PrefabWasmModule<T>>::top_level(
+			origin, &schedule, storage_deposit_limit,
+		).with_determinism(determinism);
 		let executable = match code {
-			Code::Upload(Bytes(binary)) => PrefabWasmModule::from_code(binary, &schedule),
+			Code::Upload(Bytes(binary)) =>
+				PrefabWasmModule::from_code(binary, &schedule, determinism),
 			Code::Existing(hash) => PrefabWasmModule::from_storage(hash, &schedule, &mut gas_meter),
 		};
 		let executable = match executable {
@@ -721,48 +701,51 @@ where
 			Err(error) => return ContractInstantiateResult {
 				result: Err(error.into()),
 				gas_consumed: gas_meter.gas_spent(),
+				storage_deposit: Default::default(),
 				debug_message: Bytes(Vec::new()),
 			}
 		};
 		let result = ctx.instantiate(endowment, &mut gas_meter, executable, data, &salt)
-			.and_then(|(account_id, result)| {
-				let rent_projection = if compute_projection {
-					Some(Rent::<T, PrefabWasmModule<T>>::compute_projection(&account_id)
-						.map_err(|_| <Error<T>>::NewContractNotFunded)?)
-				} else {
-					None
-				};
-
-				Ok(InstantiateReturnValue {
-					result,
-					account_id,
-					rent_projection,
-				})
-		});
+			.map(|(account_id, result)| InstantiateReturnValue { result, account_id });
 		ContractInstantiateResult {
 			result: result.map_err(|e| e.error),
 			gas_consumed: gas_meter.gas_spent(),
+			storage_deposit: ctx.storage_deposit(),
 			debug_message: Bytes(Vec::new()),
 		}
 	}
 
+	/// Validate and instrument the supplied wasm `code`.
+	///
+	/// This performs the same checks as [`Self::instantiate_with_code`] does before deploying
+	/// the code. It allows to find out whether some code would be accepted and under which
+	/// hash it would be stored without instantiating a contract.
+	///
+	/// # Note
+	///
+	/// Nothing is written to storage and no deposit is charged. Use [`Self::upload_code`]
+	/// to actually store the code. Only deterministic code is accepted.
+	pub fn bare_upload_code(code: Vec<u8>) -> CodeUploadResult<CodeHash<T>> {
+		ensure!(code.len() as u32 <= T::MaxCodeSize::get(), Error::<T>::CodeTooLarge);
+		let schedule = <CurrentSchedule<T>>::get();
+		let executable = PrefabWasmModule::from_code(code, &schedule, Determinism::Deterministic)?;
+		let code_len = executable.code_len();
+		ensure!(code_len <= T::MaxCodeSize::get(), Error::<T>::CodeTooLarge);
+		Ok(CodeUploadReturnValue {
+			code_hash: executable.code_hash().clone(),
+			code_len,
+		})
+	}
+
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(address: T::AccountId, key: [u8; 32]) -> GetStorageResult {
 		let contract_info = ContractInfoOf::<T>::get(&address)
-			.ok_or(ContractAccessError::DoesntExist)?
-			.get_alive()
-			.ok_or(ContractAccessError::IsTombstone)?;
+			.ok_or(ContractAccessError::DoesntExist)?;
 
 		let maybe_value = Storage::<T>::read(&contract_info.trie_id, &key);
 		Ok(maybe_value)
 	}
 
-	/// Query how many blocks the contract stays alive given that the amount endowment
-	/// and consumed storage does not change.
-	pub fn rent_projection(address: T::AccountId) -> RentProjectionResult<T::BlockNumber> {
-		Rent::<T, PrefabWasmModule<T>>::compute_projection(&address)
-	}
-
 	/// Determine the address of a contract,
 	///
 	/// This is the address generation function used by contract instantiation. Its result
@@ -785,17 +768,6 @@ where
 		UncheckedFrom::unchecked_from(T::Hashing::hash(&buf))
 	}
 
-	/// Subsistence threshold is the extension of the minimum balance (aka existential deposit)
-	/// by the tombstone deposit, required for leaving a tombstone.
-	///
-	/// Rent or any contract initiated balance transfer mechanism cannot make the balance lower
-	/// than the subsistence threshold in order to guarantee that a tombstone is created.
-	///
-	/// The only way to completely kill a contract without a tombstone is calling `seal_terminate`.
-	pub fn subsistence_threshold() -> BalanceOf<T> {
-		T::Currency::minimum_balance().saturating_add(T::TombstoneDeposit::get())
-	}
-
 	/// The in-memory size in bytes of the data structure associated with each contract.
 	///
 	/// The data structure is also put into storage for each contract. The in-storage size
@@ -15,8 +15,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Config, Weight, CurrentSchedule, Pallet, Schedule};
+use crate::{
+	Config, Weight, CurrentSchedule, Pallet, Schedule, ContractInfoOf, ContractInfo, TrieId,
+	BalanceOf, CodeHash,
+};
+use codec::Decode;
 use frame_support::traits::{GetPalletVersion, PalletVersion, Get};
+use sp_runtime::traits::Zero;
+
+/// The contract info as it was stored before storage deposits replaced rent.
+#[derive(Decode)]
+enum OldContractInfo<CodeHash, Balance, BlockNumber, Hash> {
+	Alive(OldAliveContractInfo<CodeHash, Balance, BlockNumber>),
+	Tombstone(Hash),
+}
+
+#[derive(Decode)]
+struct OldAliveContractInfo<CodeHash, Balance, BlockNumber> {
+	trie_id: TrieId,
+	storage_size: u32,
+	pair_count: u32,
+	code_hash: CodeHash,
+	_rent_allowance: Balance,
+	_rent_payed: Balance,
+	_deduct_block: BlockNumber,
+	_last_write: Option<BlockNumber>,
+	_reserved: Option<()>,
+}
+
+type OldContractInfoOf<T> = OldContractInfo<
+	CodeHash<T>,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
 
 pub fn migrate<T: Config>() -> Weight {
 	let mut weight: Weight = 0;
@@ -33,6 +65,27 @@ pub fn migrate<T: Config>() -> Weight {
 						.. Default::default()
 					})
 			});
+
+			// Tombstones are removed because they cannot be restored anymore. Alive contracts
+			// start without any storage deposit as they never paid one.
+			//
+			// This only runs once: the pallet version is bumped to 4.0.0 after the upgrade.
+			let mut count: Weight = 0;
+			<ContractInfoOf<T>>::translate::<OldContractInfoOf<T>, _>(|_key, old| {
+				count = count.saturating_add(1);
+				match old {
+					OldContractInfo::Alive(info) => Some(ContractInfo::<T> {
+						trie_id: info.trie_id,
+						storage_size: info.storage_size,
+						pair_count: info.pair_count,
+						code_hash: info.code_hash,
+						storage_deposit: Zero::zero(),
+						_reserved: info._reserved,
+					}),
+					OldContractInfo::Tombstone(_) => None,
+				}
+			});
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(count, count));
 		}
 		_ => (),
 	}
@@ -18,7 +18,7 @@
 //! This module contains the cost schedule and supporting code that constructs a
 //! sane default schedule from a `WeightInfo` implementation.
 
-use crate::{Config, weights::WeightInfo};
+use crate::{Config, weights::WeightInfo, wasm::Determinism};
 
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
@@ -270,15 +270,6 @@ pub struct HostFnWeights<T: Config> {
 	/// Weight of calling `seal_restore_to`.
 	pub restore_to: Weight,
 
-	/// Weight per byte of the restoring contract.
-	pub restore_to_per_caller_code_byte: Weight,
-
-	/// Weight per byte of the restored contract.
-	pub restore_to_per_tombstone_code_byte: Weight,
-
-	/// Weight per delta key supplied to `seal_restore_to`.
-	pub restore_to_per_delta: Weight,
-
 	/// Weight of calling `seal_random`.
 	pub random: Weight,
 
@@ -561,10 +552,7 @@ impl<T: Config> Default for HostFnWeights<T> {
 			return_per_byte: cost_byte!(seal_return_per_kb),
 			terminate: cost!(seal_terminate),
 			terminate_per_code_byte: cost_byte!(seal_terminate_per_code_kb),
-			restore_to: cost!(seal_restore_to),
-			restore_to_per_caller_code_byte: cost_byte_args!(seal_restore_to_per_code_kb_delta, 1, 0, 0),
-			restore_to_per_tombstone_code_byte: cost_byte_args!(seal_restore_to_per_code_kb_delta, 0, 1, 0),
-			restore_to_per_delta: cost_batched_args!(seal_restore_to_per_code_kb_delta, 0, 0, 1),
+			restore_to: cost_batched!(seal_restore_to),
 			random: cost_batched!(seal_random),
 			deposit_event: cost_batched!(seal_deposit_event),
 			deposit_event_per_topic: cost_batched_args!(seal_deposit_event_per_topic_and_kb, 1, 0),
@@ -603,6 +591,7 @@ impl<T: Config> Default for HostFnWeights<T> {
 struct ScheduleRules<'a, T: Config> {
 	schedule: &'a Schedule<T>,
 	params: Vec<u32>,
+	determinism: Determinism,
 }
 
 impl<T: Config> Schedule<T> {
@@ -620,9 +609,14 @@ impl<T: Config> Schedule<T> {
 		self
 	}
 
-	pub(crate) fn rules(&self, module: &elements::Module) -> impl rules::Rules + '_ {
+	pub(crate) fn rules(
+		&self,
+		module: &elements::Module,
+		determinism: Determinism,
+	) -> impl rules::Rules + '_ {
 		ScheduleRules {
 			schedule: &self,
+			determinism,
 			params: module
 				.type_section()
 				.iter()
@@ -701,6 +695,10 @@ impl<'a, T: Config> rules::Rules for ScheduleRules<'a, T> {
 			I32Rotl | I64Rotl => w.i64rotl,
 			I32Rotr | I64Rotr => w.i64rotr,
 
+			// Code which may be indeterministic is never executed on-chain. Its floating
+			// point instructions are therefore only priced approximately.
+			_ if self.determinism == Determinism::AllowIndeterminism => w.i64divu,
+
 			// Returning None makes the gas instrumentation fail which we intend for
 			// unsupported or unknown instructions.
 			_ => return None,
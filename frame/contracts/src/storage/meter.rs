@@ -0,0 +1,238 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains functions to meter the storage deposit.
+//!
+//! Every execution frame owns a [`Meter`] which records the deposit changes caused by the
+//! storage modifications of that frame. When a frame finishes successfully its meter is
+//! absorbed into the meter of the calling frame. When it is rolled back the meter is dropped
+//! together with all the storage changes it describes. Balance is only moved when the meter
+//! of the top level frame is settled at the end of the call.
+
+use crate::{
+	storage::ContractInfo, BalanceOf, Config, ContractInfoOf, Error, Pallet as Contracts,
+};
+use frame_support::{
+	dispatch::DispatchError,
+	ensure,
+	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
+};
+use pallet_contracts_primitives::StorageDeposit as Deposit;
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+
+/// Deposit that uses the native currency's balance type.
+pub type DepositOf<T> = Deposit<BalanceOf<T>>;
+
+/// The amount of storage that was added or removed by a single storage modification.
+#[derive(Default)]
+pub struct Diff {
+	/// How many bytes were added to storage.
+	pub bytes_added: u32,
+	/// How many bytes were removed from storage.
+	pub bytes_removed: u32,
+	/// How many storage items were added to storage.
+	pub items_added: u32,
+	/// How many storage items were removed from storage.
+	pub items_removed: u32,
+}
+
+impl Diff {
+	/// Calculate how much of a charge or refund results from applying the diff.
+	pub fn to_deposit<T: Config>(&self) -> DepositOf<T> {
+		let per_byte = T::DepositPerByte::get();
+		let per_item = T::DepositPerItem::get();
+		let charge = per_byte.saturating_mul(self.bytes_added.into())
+			.saturating_add(per_item.saturating_mul(self.items_added.into()));
+		let refund = per_byte.saturating_mul(self.bytes_removed.into())
+			.saturating_add(per_item.saturating_mul(self.items_removed.into()));
+		Deposit::Charge(charge).saturating_sub(&Deposit::Charge(refund))
+	}
+}
+
+/// A charge or refund that was recorded against a specific contract.
+struct Charge<T: Config> {
+	/// The contract whose storage deposit is affected.
+	contract: T::AccountId,
+	/// How much deposit is charged or refunded.
+	amount: DepositOf<T>,
+	/// Whether the contract was removed.
+	///
+	/// The whole deposit of a terminated contract is refunded and the account is reaped.
+	terminated: bool,
+}
+
+/// Records the storage deposit of a single execution frame.
+pub struct Meter<T: Config> {
+	/// The maximum amount of balance this meter is allowed to charge.
+	///
+	/// `None` means that no limit was requested. In this case the balance of the origin
+	/// is the only limit and it is enforced when the top level meter is settled.
+	limit: Option<BalanceOf<T>>,
+	/// The sum of all charges and refunds recorded by this meter.
+	total_deposit: DepositOf<T>,
+	/// All charges and refunds recorded by this meter in the order they occurred.
+	charges: Vec<Charge<T>>,
+}
+
+impl<T> Meter<T>
+where
+	T: Config,
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+{
+	/// Create the meter of the top level frame.
+	///
+	/// Whether the origin can actually pay for the deposit is only checked when the
+	/// meter is settled.
+	pub fn new(limit: Option<BalanceOf<T>>) -> Self {
+		Self {
+			limit,
+			total_deposit: Default::default(),
+			charges: Vec::new(),
+		}
+	}
+
+	/// Create the meter for a frame that is called by the frame owning this meter.
+	///
+	/// The nested meter is limited by whatever is still available from this meter.
+	pub fn nested(&self) -> Self {
+		Self {
+			limit: self.limit.map(|limit| self.total_deposit.available(&limit)),
+			total_deposit: Default::default(),
+			charges: Vec::new(),
+		}
+	}
+
+	/// Record a storage deposit change of `contract`.
+	pub fn charge(&mut self, contract: &T::AccountId, amount: &DepositOf<T>) {
+		if amount.is_zero() {
+			return
+		}
+		self.record(contract, amount.clone(), false);
+	}
+
+	/// Charge the deposit for the contract info of a newly instantiated `contract`.
+	pub fn charge_instantiate(&mut self, contract: &T::AccountId) {
+		let diff = Diff {
+			bytes_added: <Contracts<T>>::contract_info_size(),
+			items_added: 1,
+			.. Default::default()
+		};
+		self.charge(contract, &diff.to_deposit::<T>());
+	}
+
+	/// Refund the whole storage deposit of a `contract` which is about to be removed.
+	pub fn terminate(&mut self, contract: &T::AccountId, info: &ContractInfo<T>) {
+		self.record(contract, Deposit::Refund(info.storage_deposit), true);
+	}
+
+	/// Returns an error if the deposit recorded by this meter exceeds its limit.
+	///
+	/// This is called when the owning frame finishes and before it is absorbed by its caller.
+	pub fn enforce_limit(&self) -> Result<(), DispatchError> {
+		if let Some(limit) = self.limit {
+			ensure!(
+				self.total_deposit.charge_or_zero() <= limit,
+				<Error<T>>::StorageDepositLimitExhausted,
+			);
+		}
+		Ok(())
+	}
+
+	/// Merge the meter of a successfully finished nested frame into this meter.
+	pub fn absorb(&mut self, nested: Self) {
+		self.total_deposit = self.total_deposit.saturating_add(&nested.total_deposit);
+		self.charges.extend(nested.charges);
+	}
+
+	/// Move the recorded deposits between `origin` and the involved contracts.
+	///
+	/// This must only be called on the meter of the top level frame and from within the
+	/// storage transaction of the call so that a failure reverts the whole call.
+	///
+	/// Returns the net deposit that was charged from (or refunded to) the `origin`.
+	pub fn settle(self, origin: &T::AccountId) -> Result<DepositOf<T>, DispatchError> {
+		self.enforce_limit()?;
+		for charge in self.charges {
+			Self::apply(origin, charge)?;
+		}
+		Ok(self.total_deposit)
+	}
+
+	fn record(&mut self, contract: &T::AccountId, amount: DepositOf<T>, terminated: bool) {
+		self.total_deposit = self.total_deposit.saturating_add(&amount);
+		self.charges.push(Charge { contract: contract.clone(), amount, terminated });
+	}
+
+	fn apply(origin: &T::AccountId, charge: Charge<T>) -> Result<(), DispatchError> {
+		if charge.terminated {
+			let amount = match charge.amount {
+				Deposit::Refund(amount) => amount,
+				Deposit::Charge(_) => return Ok(()),
+			};
+			let amount = amount.saturating_sub(T::Currency::unreserve(&charge.contract, amount));
+			// The contract info is already removed. Transferring the remaining balance reaps
+			// the account.
+			T::Currency::transfer(
+				&charge.contract,
+				origin,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			).map_err(|_| <Error<T>>::TransferFailed)?;
+			return Ok(())
+		}
+
+		// Charges of a contract which was terminated later in the same call are dropped.
+		// Those were never transferred and therefore there is nothing to refund.
+		let mut info = match <ContractInfoOf<T>>::get(&charge.contract) {
+			Some(info) => info,
+			None => return Ok(()),
+		};
+		match charge.amount {
+			Deposit::Charge(amount) => {
+				T::Currency::transfer(
+					origin,
+					&charge.contract,
+					amount,
+					ExistenceRequirement::KeepAlive,
+				).map_err(|_| <Error<T>>::StorageDepositNotEnoughFunds)?;
+				T::Currency::reserve(&charge.contract, amount)
+					.map_err(|_| <Error<T>>::StorageDepositNotEnoughFunds)?;
+				info.storage_deposit = info.storage_deposit.saturating_add(amount);
+			},
+			Deposit::Refund(amount) => {
+				// Contracts migrated from the rent based accounting never paid a deposit.
+				let amount = amount.min(info.storage_deposit);
+				if amount.is_zero() {
+					return Ok(())
+				}
+				let amount = amount
+					.saturating_sub(T::Currency::unreserve(&charge.contract, amount));
+				T::Currency::transfer(
+					&charge.contract,
+					origin,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				).map_err(|_| <Error<T>>::TransferFailed)?;
+				info.storage_deposit = info.storage_deposit.saturating_sub(amount);
+			},
+		}
+		<ContractInfoOf<T>>::insert(&charge.contract, info);
+		Ok(())
+	}
+}
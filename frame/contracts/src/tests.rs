@@ -16,17 +16,17 @@
 // limitations under the License.
 
 use crate::{
-	BalanceOf, ContractInfo, ContractInfoOf, Pallet,
-	Config, Schedule,
+	BalanceOf, ContractInfoOf, Pallet,
+	Config, Schedule, Determinism, CodeStorage, OwnerInfoOf, PristineCode,
 	Error, storage::Storage,
 	chain_extension::{
 		Result as ExtensionResult, Environment, ChainExtension, Ext, SysConfig, RetVal,
 		UncheckedFrom, InitState, ReturnFlags,
 	},
-	exec::{AccountIdOf, Executable}, wasm::PrefabWasmModule,
+	exec::AccountIdOf,
 	weights::WeightInfo,
 	wasm::ReturnCode as RuntimeReturnCode,
-	storage::RawAliveContractInfo,
+	storage::RawContractInfo,
 };
 use assert_matches::assert_matches;
 use codec::Encode;
@@ -34,12 +34,11 @@ use sp_core::Bytes;
 use sp_runtime::{
 	traits::{BlakeTwo256, Hash, IdentityLookup, Convert},
 	testing::{Header, H256},
-	AccountId32, Perbill,
+	AccountId32,
 };
 use sp_io::hashing::blake2_256;
 use frame_support::{
-	assert_ok, assert_err, assert_err_ignore_postinfo,
-	parameter_types, assert_storage_noop,
+	assert_ok, assert_err, assert_err_ignore_postinfo, parameter_types,
 	traits::{Currency, ReservableCurrency, OnInitialize, GenesisBuild},
 	weights::{Weight, PostDispatchInfo, DispatchClass, constants::WEIGHT_PER_SECOND},
 	dispatch::DispatchErrorWithPostInfo,
@@ -47,6 +46,7 @@ use frame_support::{
 };
 use frame_system::{self as system, EventRecord, Phase};
 use pretty_assertions::assert_eq;
+use pallet_contracts_primitives::{Code, StorageDeposit};
 
 use crate as pallet_contracts;
 
@@ -74,21 +74,20 @@ pub mod test_utils {
 		ContractInfoOf, CodeHash,
 		storage::Storage,
 		exec::{StorageKey, AccountIdOf},
-		Pallet as Contracts,
 	};
 	use frame_support::traits::Currency;
 
 	pub fn set_storage(addr: &AccountIdOf<Test>, key: &StorageKey, value: Option<Vec<u8>>) {
-		let contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
-		Storage::<Test>::write(addr, &contract_info.trie_id, key, value).unwrap();
+		let contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
+		Storage::<Test>::write(addr, &contract_info.trie_id, key, value, None).unwrap();
 	}
 	pub fn get_storage(addr: &AccountIdOf<Test>, key: &StorageKey) -> Option<Vec<u8>> {
-		let contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		Storage::<Test>::read(&contract_info.trie_id, key)
 	}
 	pub fn place_contract(address: &AccountIdOf<Test>, code_hash: CodeHash<Test>) {
 		let trie_id = Storage::<Test>::generate_trie_id(address);
-		set_balance(address, Contracts::<Test>::subsistence_threshold() * 10);
+		set_balance(address, Balances::minimum_balance() * 10);
 		Storage::<Test>::place_contract(&address, trie_id, code_hash).unwrap();
 	}
 	pub fn set_balance(who: &AccountIdOf<Test>, amount: u64) {
@@ -236,21 +235,18 @@ impl pallet_balances::Config for Test {
 }
 parameter_types! {
 	pub const MinimumPeriod: u64 = 1;
+	pub const MaxDrift: u64 = 30_000;
 }
 impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 parameter_types! {
-	pub const SignedClaimHandicap: u64 = 2;
-	pub const TombstoneDeposit: u64 = 16;
-	pub const DepositPerContract: u64 = 8 * DepositPerStorageByte::get();
-	pub const DepositPerStorageByte: u64 = 10_000;
-	pub const DepositPerStorageItem: u64 = 10_000;
-	pub RentFraction: Perbill = Perbill::from_rational(4u32, 10_000u32);
-	pub const SurchargeReward: u64 = 500_000;
+	pub const DepositPerByte: u64 = 1;
+	pub const DepositPerItem: u64 = 2;
 	pub const MaxDepth: u32 = 100;
 	pub const MaxValueSize: u32 = 16_384;
 	pub const DeletionQueueDepth: u32 = 1024;
@@ -273,14 +269,8 @@ impl Config for Test {
 	type Randomness = Randomness;
 	type Currency = Balances;
 	type Event = Event;
-	type RentPayment = ();
-	type SignedClaimHandicap = SignedClaimHandicap;
-	type TombstoneDeposit = TombstoneDeposit;
-	type DepositPerContract = DepositPerContract;
-	type DepositPerStorageByte = DepositPerStorageByte;
-	type DepositPerStorageItem = DepositPerStorageItem;
-	type RentFraction = RentFraction;
-	type SurchargeReward = SurchargeReward;
+	type DepositPerByte = DepositPerByte;
+	type DepositPerItem = DepositPerItem;
 	type MaxDepth = MaxDepth;
 	type MaxValueSize = MaxValueSize;
 	type WeightPrice = Self;
@@ -360,7 +350,7 @@ fn calling_plain_account_fails() {
 		let base_cost = <<Test as Config>::WeightInfo as WeightInfo>::call(0);
 
 		assert_eq!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, Vec::new()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, Vec::new()),
 			Err(
 				DispatchErrorWithPostInfo {
 					error: Error::<Test>::NotCallable.into(),
@@ -386,33 +376,27 @@ fn account_removal_does_not_remove_storage() {
 
 		// Set up two accounts with free balance above the existential threshold.
 		{
-			let alice_contract_info = ContractInfo::Alive(RawAliveContractInfo {
+			let alice_contract_info = RawContractInfo {
 				trie_id: trie_id1.clone(),
 				storage_size: 0,
 				pair_count: 0,
-				deduct_block: System::block_number(),
 				code_hash: H256::repeat_byte(1),
-				rent_allowance: 40,
-				rent_payed: 0,
-				last_write: None,
+				storage_deposit: 0,
 				_reserved: None,
-			});
+			};
 			let _ = Balances::deposit_creating(&ALICE, 110);
 			ContractInfoOf::<Test>::insert(ALICE, &alice_contract_info);
 			set_storage(&ALICE, &key1, Some(b"1".to_vec()));
 			set_storage(&ALICE, &key2, Some(b"2".to_vec()));
 
-			let bob_contract_info = ContractInfo::Alive(RawAliveContractInfo {
+			let bob_contract_info = RawContractInfo {
 				trie_id: trie_id2.clone(),
 				storage_size: 0,
 				pair_count: 0,
-				deduct_block: System::block_number(),
 				code_hash: H256::repeat_byte(2),
-				rent_allowance: 40,
-				rent_payed: 0,
-				last_write: None,
+				storage_deposit: 0,
 				_reserved: None,
-			});
+			};
 			let _ = Balances::deposit_creating(&BOB, 110);
 			ContractInfoOf::<Test>::insert(BOB, &bob_contract_info);
 			set_storage(&BOB, &key1, Some(b"3".to_vec()));
@@ -460,18 +444,20 @@ fn instantiate_and_call_and_deposit_event() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			let subsistence = Pallet::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 
 			// Check at the end to get hash on error easily
 			let creation = Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
 			);
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+			let deposit = Balances::reserved_balance(&addr);
 
 			assert_eq!(System::events(), vec![
 				EventRecord {
@@ -494,14 +480,14 @@ fn instantiate_and_call_and_deposit_event() {
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::pallet_balances(
-						pallet_balances::Event::Endowed(addr.clone(), subsistence * 100)
+						pallet_balances::Event::Endowed(addr.clone(), min_balance * 100)
 					),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::pallet_balances(
-						pallet_balances::Event::Transfer(ALICE, addr.clone(), subsistence * 100)
+						pallet_balances::Event::Transfer(ALICE, addr.clone(), min_balance * 100)
 					),
 					topics: vec![],
 				},
@@ -522,6 +508,20 @@ fn instantiate_and_call_and_deposit_event() {
 					event: Event::pallet_contracts(crate::Event::Instantiated(ALICE, addr.clone())),
 					topics: vec![],
 				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::pallet_balances(
+						pallet_balances::Event::Transfer(ALICE, addr.clone(), deposit)
+					),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::pallet_balances(
+						pallet_balances::Event::Reserved(addr.clone(), deposit)
+					),
+					topics: vec![],
+				},
 			]);
 
 			assert_ok!(creation);
@@ -543,6 +543,7 @@ fn deposit_event_max_value_limit() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -555,6 +556,7 @@ fn deposit_event_max_value_limit() {
 				addr.clone(),
 				0,
 				GAS_LIMIT * 2, // we are copying a huge buffer,
+				None,
 				<Test as Config>::MaxValueSize::get().encode(),
 			));
 
@@ -565,6 +567,7 @@ fn deposit_event_max_value_limit() {
 					addr,
 					0,
 					GAS_LIMIT,
+					None,
 					(<Test as Config>::MaxValueSize::get() + 1).encode(),
 				),
 				Error::<Test>::ValueTooLarge,
@@ -575,18 +578,19 @@ fn deposit_event_max_value_limit() {
 #[test]
 fn run_out_of_gas() {
 	let (wasm, code_hash) = compile_module::<Test>("run_out_of_gas").unwrap();
-	let subsistence = Pallet::<Test>::subsistence_threshold();
 
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				100 * subsistence,
+				100 * min_balance,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -601,6 +605,7 @@ fn run_out_of_gas() {
 					addr, // newly created account
 					0,
 					67_500_000,
+					None,
 					vec![],
 				),
 				Error::<Test>::OutOfGas,
@@ -635,6 +640,7 @@ fn storage_size() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				// rent_allowance
 				<Test as pallet_balances::Config>::Balance::from(10_000u32).encode(),
@@ -642,8 +648,6 @@ fn storage_size() {
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 			assert_eq!(
 				bob_contract.storage_size,
@@ -659,11 +663,10 @@ fn storage_size() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				call::set_storage_4_byte()
 			));
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 			assert_eq!(
 				bob_contract.storage_size,
@@ -679,11 +682,10 @@ fn storage_size() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				call::remove_storage_4_byte()
 			));
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 			assert_eq!(
 				bob_contract.storage_size,
@@ -697,10 +699,11 @@ fn storage_size() {
 }
 
 #[test]
-fn empty_kv_pairs() {
-	let (wasm, code_hash) = compile_module::<Test>("set_empty_storage").unwrap();
+fn storage_deposit_works() {
+	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
 
 	ExtBuilder::default()
+		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
@@ -708,806 +711,451 @@ fn empty_kv_pairs() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
-				vec![],
+				<Test as pallet_balances::Config>::Balance::from(10_000u32).encode(),
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
-				.unwrap();
 
-			assert_eq!(
-				bob_contract.storage_size,
-				0,
+			// The deposit for the contract info and the storage written by the constructor
+			// is reserved on the contract's account.
+			let deposit = Balances::reserved_balance(&addr);
+			assert!(deposit > 0);
+			assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().storage_deposit, deposit);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000_000 - 30_000 - deposit);
+
+			// Adding a new item costs 4 bytes plus the item itself which exceeds this limit.
+			let item_deposit = 4 * DepositPerByte::get() + DepositPerItem::get();
+			assert_err_ignore_postinfo!(
+				Contracts::call(
+					Origin::signed(ALICE),
+					addr.clone(),
+					0,
+					GAS_LIMIT,
+					Some(item_deposit - 1),
+					call::set_storage_4_byte(),
+				),
+				Error::<Test>::StorageDepositLimitExhausted,
 			);
+			assert_eq!(Balances::reserved_balance(&addr), deposit);
+
+			// A limit that is exactly the required deposit is fine.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				Some(item_deposit),
+				call::set_storage_4_byte(),
+			));
+			assert_eq!(Balances::reserved_balance(&addr), deposit + item_deposit);
 			assert_eq!(
-				bob_contract.pair_count,
-				1,
+				Balances::free_balance(&ALICE),
+				1_000_000 - 30_000 - deposit - item_deposit,
 			);
-		});
-}
 
-fn initialize_block(number: u64) {
-	System::initialize(
-		&number,
-		&[0u8; 32].into(),
-		&Default::default(),
-		Default::default(),
-	);
+			// Removing the item refunds the deposit to the caller.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				call::remove_storage_4_byte(),
+			));
+			assert_eq!(Balances::reserved_balance(&addr), deposit);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000_000 - 30_000 - deposit);
+		});
 }
 
 #[test]
-fn deduct_blocks() {
+fn bare_calls_report_storage_deposit() {
 	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
-	let endowment: BalanceOf<Test> = 100_000;
-	let allowance: BalanceOf<Test> = 70_000;
 
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				endowment,
+			let result = Contracts::bare_instantiate(
+				ALICE,
+				30_000,
 				GAS_LIMIT,
-				wasm,
-				allowance.encode(),
+				None,
+				Code::Upload(Bytes(wasm)),
+				<Test as pallet_balances::Config>::Balance::from(10_000u32).encode(),
 				vec![],
-			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			let code_len: BalanceOf<Test> =
-				PrefabWasmModule::<Test>::from_storage_noinstr(contract.code_hash)
-					.unwrap()
-					.occupied_storage()
-					.into();
-
-			// The instantiation deducted the rent for one block immediately
-			let rent0 = <Test as Config>::RentFraction::get()
-				// (base_deposit(8) + bytes in storage(4) + size of code) * byte_price
-				// + 1 storage item (10_000) - free_balance
-				.mul_ceil((8 + 4 + code_len) * 10_000 + 10_000 - endowment)
-				// blocks to rent
-				* 1;
-			assert!(rent0 > 0);
-			assert_eq!(contract.rent_allowance, allowance - rent0);
-			assert_eq!(contract.deduct_block, 1);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0);
-
-			// Advance 4 blocks
-			initialize_block(5);
-
-			// Trigger rent through call
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
+				Determinism::Deterministic,
 			);
-
-			// Check result
-			let rent = <Test as Config>::RentFraction::get()
-				.mul_ceil((8 + 4 + code_len) * 10_000 + 10_000 - (endowment - rent0))
-				* 4;
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			assert_eq!(contract.rent_allowance, allowance - rent0 - rent);
-			assert_eq!(contract.deduct_block, 5);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0 - rent);
-
-			// Advance 2 blocks more
-			initialize_block(7);
-
-			// Trigger rent through call
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
+			let addr = result.result.unwrap().account_id;
+			let deposit = Balances::reserved_balance(&addr);
+			assert_eq!(result.storage_deposit, StorageDeposit::Charge(deposit));
+			assert_eq!(Contracts::contract_address(&ALICE, &code_hash, &[]), addr);
+
+			let item_deposit = 4 * DepositPerByte::get() + DepositPerItem::get();
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				call::set_storage_4_byte(),
+				Determinism::Deterministic,
 			);
+			assert_ok!(result.result);
+			assert_eq!(result.storage_deposit, StorageDeposit::Charge(item_deposit));
 
-			// Check result
-			let rent_2 = <Test as Config>::RentFraction::get()
-				.mul_ceil((8 + 4 + code_len) * 10_000 + 10_000 - (endowment - rent0 - rent))
-				* 2;
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			assert_eq!(contract.rent_allowance, allowance - rent0 - rent - rent_2);
-			assert_eq!(contract.deduct_block, 7);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0 - rent - rent_2);
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				call::remove_storage_4_byte(),
+				Determinism::Deterministic,
+			);
+			assert_ok!(result.result);
+			assert_eq!(result.storage_deposit, StorageDeposit::Refund(item_deposit));
 
-			// Second call on same block should have no effect on rent
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
+			// Nothing is charged when the call fails.
+			let result = Contracts::bare_call(
+				ALICE,
+				addr,
+				0,
+				GAS_LIMIT,
+				Some(item_deposit - 1),
+				call::set_storage_4_byte(),
+				Determinism::Deterministic,
 			);
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			assert_eq!(contract.rent_allowance, allowance - rent0 - rent - rent_2);
-			assert_eq!(contract.deduct_block, 7);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0 - rent - rent_2)
+			assert_err!(result.result, Error::<Test>::StorageDepositLimitExhausted);
+			assert_eq!(result.storage_deposit, StorageDeposit::Charge(0));
 		});
 }
 
 #[test]
-fn inherent_claim_surcharge_contract_removals() {
-	removals(|addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok());
-}
-
-#[test]
-fn signed_claim_surcharge_contract_removals() {
-	removals(|addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok());
-}
-
-#[test]
-fn claim_surcharge_malus() {
-	// Test surcharge malus for inherent
-	claim_surcharge(8, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), true);
-	claim_surcharge(7, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), true);
-	claim_surcharge(6, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), true);
-	claim_surcharge(5, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), false);
-
-	// Test surcharge malus for signed
-	claim_surcharge(8, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), true);
-	claim_surcharge(7, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), false);
-	claim_surcharge(6, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), false);
-	claim_surcharge(5, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), false);
-}
-
-/// Claim surcharge with the given trigger_call at the given blocks.
-/// If `removes` is true then assert that the contract is a tombstone.
-fn claim_surcharge(blocks: u64, trigger_call: impl Fn(AccountIdOf<Test>) -> bool, removes: bool) {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
+fn bare_upload_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
 
 	ExtBuilder::default()
-		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				100_000,
-				GAS_LIMIT,
-				wasm,
-				<Test as pallet_balances::Config>::Balance::from(30_000u32).encode(), // rent allowance
-				vec![],
-			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+			let result = Contracts::bare_upload_code(wasm).unwrap();
+			assert_eq!(result.code_hash, code_hash);
+			assert!(result.code_len > 0);
 
-			// Advance blocks
-			initialize_block(blocks);
+			// The code is only validated but not stored.
+			assert!(!crate::CodeStorage::<Test>::contains_key(&code_hash));
+			assert!(!crate::PristineCode::<Test>::contains_key(&code_hash));
 
-			// Trigger rent through call
-			assert_eq!(trigger_call(addr.clone()), removes);
-
-			if removes {
-				assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-			} else {
-				assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().is_some());
-			}
+			// Invalid code is rejected.
+			assert!(Contracts::bare_upload_code(b"not a wasm module".to_vec()).is_err());
 		});
 }
 
-/// Test for all kind of removals for the given trigger:
-/// * if balance is reached and balance > subsistence threshold
-/// * if allowance is exceeded
-/// * if balance is reached and balance < subsistence threshold
-///	    * this case cannot be triggered by a contract: we check whether a tombstone is left
-fn removals(trigger_call: impl Fn(AccountIdOf<Test>) -> bool) {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
+#[test]
+fn upload_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
 
-	// Balance reached and superior to subsistence threshold
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
+			let _ = Balances::deposit_creating(&BOB, 1_000_000);
+
+			// The deposit limit is respected.
+			assert_err_ignore_postinfo!(
+				Contracts::upload_code(
+					Origin::signed(ALICE),
+					wasm.clone(),
+					Some(1),
+					Determinism::Deterministic,
+				),
+				Error::<Test>::StorageDepositLimitExhausted,
+			);
+
+			assert_ok!(Contracts::upload_code(
 				Origin::signed(ALICE),
-				70_000,
-				GAS_LIMIT,
 				wasm.clone(),
-				<Test as pallet_balances::Config>::Balance::from(100_000u32).encode(), // rent allowance
-				vec![],
+				None,
+				Determinism::Deterministic,
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let allowance = ContractInfoOf::<Test>::get(&addr)
-				.unwrap().get_alive().unwrap().rent_allowance;
-			let balance = Balances::free_balance(&addr);
-
-			let subsistence_threshold = Pallet::<Test>::subsistence_threshold();
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
+			assert!(Balances::reserved_balance(&ALICE) > 0);
+			assert!(OwnerInfoOf::<Test>::contains_key(&code_hash));
+			assert!(PristineCode::<Test>::contains_key(&code_hash));
+			assert_eq!(CodeStorage::<Test>::get(&code_hash).unwrap().refcount(), 0);
 			assert_eq!(
-				ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap().rent_allowance,
-				allowance,
+				System::events().last().unwrap().event,
+				Event::pallet_contracts(crate::Event::CodeStored(code_hash)),
 			);
-			assert_eq!(Balances::free_balance(&addr), balance);
-
-			// Advance blocks
-			initialize_block(27);
 
-			// Trigger rent through call (should remove the contract)
-			assert!(trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
-
-			// Advance blocks
-			initialize_block(30);
+			// Uploading code that already has an owner charges nothing.
+			assert_ok!(Contracts::upload_code(
+				Origin::signed(BOB),
+				wasm,
+				None,
+				Determinism::Deterministic,
+			));
+			assert_eq!(Balances::reserved_balance(&BOB), 0);
 
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
+			// Contracts can be instantiated from the uploaded code.
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(BOB),
+				100_000,
+				GAS_LIMIT,
+				None,
+				code_hash,
+				vec![],
+				vec![],
+			));
+			assert!(ContractInfoOf::<Test>::contains_key(
+				&Contracts::contract_address(&BOB, &code_hash, &[]),
+			));
+			assert_eq!(CodeStorage::<Test>::get(&code_hash).unwrap().refcount(), 1);
 		});
+}
+
+#[test]
+fn remove_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
 
-	// Allowance exceeded
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
+			let _ = Balances::deposit_creating(&DJANGO, 1_000_000);
+
+			// Code that was not uploaded can not be removed.
+			assert_err_ignore_postinfo!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash),
+				Error::<Test>::CodeNotFound,
+			);
+
+			assert_ok!(Contracts::upload_code(
+				Origin::signed(ALICE),
+				wasm,
+				None,
+				Determinism::Deterministic,
+			));
+			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
-				wasm.clone(),
-				<Test as pallet_balances::Config>::Balance::from(70_000u32).encode(), // rent allowance
+				None,
+				code_hash,
+				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let allowance = ContractInfoOf::<Test>::get(&addr)
-				.unwrap().get_alive().unwrap().rent_allowance;
-			let balance = Balances::free_balance(&addr);
 
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_eq!(
-				ContractInfoOf::<Test>::get(&addr)
-					.unwrap()
-					.get_alive()
-					.unwrap()
-					.rent_allowance,
-				allowance,
+			// Only the owner can remove the code and only if no contract uses it.
+			assert_err_ignore_postinfo!(
+				Contracts::remove_code(Origin::signed(BOB), code_hash),
+				sp_runtime::DispatchError::BadOrigin,
+			);
+			assert_err_ignore_postinfo!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash),
+				Error::<Test>::CodeInUse,
 			);
-			assert_eq!(Balances::free_balance(&addr), balance);
-
-			// Advance blocks
-			initialize_block(27);
-
-			// Trigger rent through call
-			assert!(trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_tombstone()
-				.is_some());
-			// Balance should be initial balance - initial rent_allowance
-			assert_eq!(Balances::free_balance(&addr), 30_000);
-
-			// Advance blocks
-			initialize_block(20);
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_tombstone()
-				.is_some());
-			assert_eq!(Balances::free_balance(&addr), 30_000);
-		});
 
-	// Balance reached and inferior to subsistence threshold
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			// Create
-			let subsistence_threshold = Pallet::<Test>::subsistence_threshold();
-			let _ = Balances::deposit_creating(&ALICE, subsistence_threshold * 1000);
-			assert_ok!(Contracts::instantiate_with_code(
+			// Terminating the last contract keeps the code which is owned by someone.
+			assert_ok!(Contracts::call(
 				Origin::signed(ALICE),
-				subsistence_threshold * 100,
+				addr.clone(),
+				0,
 				GAS_LIMIT,
-				wasm,
-				(subsistence_threshold * 100).encode(), // rent allowance
+				None,
 				vec![],
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let allowance = ContractInfoOf::<Test>::get(&addr)
-				.unwrap().get_alive().unwrap().rent_allowance;
-			let balance = Balances::free_balance(&addr);
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_eq!(
-				ContractInfoOf::<Test>::get(&addr)
-					.unwrap()
-					.get_alive()
-					.unwrap()
-					.rent_allowance,
-				allowance,
-			);
+			assert!(!ContractInfoOf::<Test>::contains_key(&addr));
+			assert_eq!(CodeStorage::<Test>::get(&code_hash).unwrap().refcount(), 0);
+
+			assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash));
+			assert_eq!(Balances::reserved_balance(&ALICE), 0);
+			assert!(!OwnerInfoOf::<Test>::contains_key(&code_hash));
+			assert!(!CodeStorage::<Test>::contains_key(&code_hash));
+			assert!(!PristineCode::<Test>::contains_key(&code_hash));
 			assert_eq!(
-				Balances::free_balance(&addr),
-				balance,
+				System::events().last().unwrap().event,
+				Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
 			);
-
-			// Make contract have exactly the subsistence threshold
-			Balances::make_free_balance_be(&addr, subsistence_threshold);
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
-
-			// Advance blocks (should remove as balance is exactly subsistence)
-			initialize_block(10);
-
-			// Trigger rent through call
-			assert!(trigger_call(addr.clone()));
-			assert_matches!(ContractInfoOf::<Test>::get(&addr), Some(ContractInfo::Tombstone(_)));
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
-
-			// Advance blocks
-			initialize_block(20);
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_matches!(ContractInfoOf::<Test>::get(&addr), Some(ContractInfo::Tombstone(_)));
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
 		});
 }
 
 #[test]
-fn call_removed_contract() {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
+fn indeterministic_code_only_runs_off_chain() {
+	let (wasm, code_hash) = compile_module::<Test>("float_instruction").unwrap();
 
-	// Balance reached and superior to subsistence threshold
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
+
+			assert_err_ignore_postinfo!(
+				Contracts::upload_code(
+					Origin::signed(ALICE),
+					wasm.clone(),
+					None,
+					Determinism::Deterministic,
+				),
+				"use of floating point type in function types is forbidden",
+			);
+			assert_ok!(Contracts::upload_code(
 				Origin::signed(ALICE),
-				30_000,
-				GAS_LIMIT,
 				wasm,
-				// rent allowance
-				<Test as pallet_balances::Config>::Balance::from(10_000u32).encode(),
-				vec![],
+				None,
+				Determinism::AllowIndeterminism,
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-
-			// Calling contract should succeed.
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
-			);
 
-			// Advance blocks
-			initialize_block(27);
-
-			// Calling contract should deny access because rent cannot be paid.
+			// The code can not be instantiated on-chain.
 			assert_err_ignore_postinfo!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null()),
-				Error::<Test>::NotCallable
+				Contracts::instantiate(
+					Origin::signed(ALICE),
+					100_000,
+					GAS_LIMIT,
+					None,
+					code_hash,
+					vec![],
+					vec![],
+				),
+				Error::<Test>::Indeterministic,
 			);
-			// No event is generated because the contract is not actually removed.
-			assert_eq!(System::events(), vec![]);
+			let result = Contracts::bare_instantiate(
+				ALICE,
+				100_000,
+				GAS_LIMIT,
+				None,
+				Code::Existing(code_hash),
+				vec![],
+				vec![],
+				Determinism::Deterministic,
+			);
+			assert_err!(result.result, Error::<Test>::Indeterministic);
 
-			// Subsequent contract calls should also fail.
-			assert_err_ignore_postinfo!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null()),
-				Error::<Test>::NotCallable
+			// But it can be instantiated and called when indeterminism is allowed.
+			let result = Contracts::bare_instantiate(
+				ALICE,
+				100_000,
+				GAS_LIMIT,
+				None,
+				Code::Existing(code_hash),
+				vec![],
+				vec![],
+				Determinism::AllowIndeterminism,
 			);
+			let addr = result.result.unwrap().account_id;
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				vec![],
+				Determinism::AllowIndeterminism,
+			);
+			assert_ok!(result.result);
 
-			// A snitch can now remove the contract
-			assert_ok!(Contracts::claim_surcharge(Origin::none(), addr.clone(), Some(ALICE)));
-			assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-		})
+			// Calling it requires indeterminism to be allowed, too.
+			let result = Contracts::bare_call(
+				ALICE,
+				addr,
+				0,
+				GAS_LIMIT,
+				None,
+				vec![],
+				Determinism::Deterministic,
+			);
+			assert_err!(result.result, Error::<Test>::Indeterministic);
+		});
 }
 
 #[test]
-fn default_rent_allowance_on_instantiate() {
-	let (wasm, code_hash) = compile_module::<Test>("check_default_rent_allowance").unwrap();
+fn deterministic_code_uploaded_as_indeterministic_runs_on_chain() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
 
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
+			let _ = Balances::deposit_creating(&BOB, 1_000_000);
+
+			// Uploading code without floating point types while allowing indeterminism
+			// still stores it as deterministic.
+			assert_ok!(Contracts::upload_code(
 				Origin::signed(ALICE),
-				30_000,
+				wasm.clone(),
+				None,
+				Determinism::AllowIndeterminism,
+			));
+
+			// So others can instantiate it on-chain.
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(BOB),
+				100_000,
 				GAS_LIMIT,
-				wasm,
+				None,
+				code_hash,
 				vec![],
 				vec![],
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			let code_len: BalanceOf<Test> =
-			PrefabWasmModule::<Test>::from_storage_noinstr(contract.code_hash)
-				.unwrap()
-				.occupied_storage()
-				.into();
-
-			// The instantiation deducted the rent for one block immediately
-			let first_rent = <Test as Config>::RentFraction::get()
-				// (base_deposit(8) + code_len) * byte_price - free_balance
-				.mul_ceil((8 + code_len) * 10_000 - 30_000)
-				// blocks to rent
-				* 1;
-			assert_eq!(contract.rent_allowance, <BalanceOf<Test>>::max_value() - first_rent);
-
-			// Advance blocks
-			initialize_block(5);
-
-			// Trigger rent through call
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
-			);
-
-			// Check contract is still alive
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive();
-			assert!(contract.is_some())
+			assert_ok!(Contracts::instantiate_with_code(
+				Origin::signed(BOB),
+				100_000,
+				GAS_LIMIT,
+				None,
+				wasm,
+				vec![],
+				vec![1],
+			));
+			assert_eq!(CodeStorage::<Test>::get(&code_hash).unwrap().refcount(), 2);
 		});
 }
 
 #[test]
-fn restorations_dirty_storage_and_different_storage() {
-	restoration(true, true, false);
-}
-
-#[test]
-fn restorations_dirty_storage() {
-	restoration(false, true, false);
-}
-
-#[test]
-fn restoration_different_storage() {
-	restoration(true, false, false);
-}
-
-#[test]
-fn restoration_code_evicted() {
-	restoration(false, false, true);
-}
-
-#[test]
-fn restoration_success() {
-	restoration(false, false, false);
-}
-
-fn restoration(
-	test_different_storage: bool,
-	test_restore_to_with_dirty_storage: bool,
-	test_code_evicted: bool
-) {
-	let (set_rent_wasm, set_rent_code_hash) = compile_module::<Test>("set_rent").unwrap();
-	let (restoration_wasm, restoration_code_hash) = compile_module::<Test>("restoration").unwrap();
-	let allowance: <Test as pallet_balances::Config>::Balance = 10_000;
+fn empty_kv_pairs() {
+	let (wasm, code_hash) = compile_module::<Test>("set_empty_storage").unwrap();
 
 	ExtBuilder::default()
-		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-
-			// Create an account with address `BOB` with code `CODE_SET_RENT`.
-			// The input parameter sets the rent allowance to 0.
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
-				set_rent_wasm.clone(),
-				allowance.encode(),
-				vec![],
-			));
-			let addr_bob = Contracts::contract_address(&ALICE, &set_rent_code_hash, &[]);
-
-			let mut events = vec![
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::frame_system(frame_system::Event::NewAccount(ALICE)),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_balances(
-						pallet_balances::Event::Endowed(ALICE, 1_000_000)
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::frame_system(frame_system::Event::NewAccount(addr_bob.clone())),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_balances(
-						pallet_balances::Event::Endowed(addr_bob.clone(), 30_000)
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_balances(
-						pallet_balances::Event::Transfer(ALICE, addr_bob.clone(), 30_000)
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_contracts(
-						crate::Event::CodeStored(set_rent_code_hash.into())
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_contracts(
-						crate::Event::Instantiated(ALICE, addr_bob.clone())
-					),
-					topics: vec![],
-				},
-			];
-
-			// Create another contract from the same code in order to increment the codes
-			// refcounter so that it stays on chain.
-			if !test_code_evicted {
-				assert_ok!(Contracts::instantiate_with_code(
-					Origin::signed(ALICE),
-					20_000,
-					GAS_LIMIT,
-					set_rent_wasm,
-					allowance.encode(),
-					vec![1],
-				));
-				assert_refcount!(set_rent_code_hash, 2);
-				let addr_dummy = Contracts::contract_address(&ALICE, &set_rent_code_hash, &[1]);
-				events.extend([
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::frame_system(frame_system::Event::NewAccount(addr_dummy.clone())),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_balances(
-							pallet_balances::Event::Endowed(addr_dummy.clone(), 20_000)
-						),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_balances(
-							pallet_balances::Event::Transfer(ALICE, addr_dummy.clone(), 20_000)
-						),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_contracts(
-							crate::Event::Instantiated(ALICE, addr_dummy.clone())
-						),
-						topics: vec![],
-					},
-				].iter().cloned());
-			}
-
-			assert_eq!(System::events(), events);
-
-			// Check if `BOB` was created successfully and that the rent allowance is below what
-			// we specified as the first rent was already collected.
-			let bob_contract = ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_alive().unwrap();
-			assert!(bob_contract.rent_allowance < allowance);
-
-			if test_different_storage {
-				assert_ok!(Contracts::call(
-					Origin::signed(ALICE),
-					addr_bob.clone(), 0, GAS_LIMIT,
-					call::set_storage_4_byte())
-				);
-			}
-
-			// Advance blocks in order to make the contract run out of money for rent.
-			initialize_block(27);
-
-			// Call `BOB`, which makes it pay rent. Since the rent allowance is set to 20_000
-			// we expect that it is no longer callable but keeps existing until someone
-			// calls `claim_surcharge`.
-			assert_err_ignore_postinfo!(
-				Contracts::call(
-					Origin::signed(ALICE), addr_bob.clone(), 0, GAS_LIMIT, call::null()
-				),
-				Error::<Test>::NotCallable
-			);
-			assert!(System::events().is_empty());
-			assert!(ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_alive().is_some());
-			assert_ok!(Contracts::claim_surcharge(Origin::none(), addr_bob.clone(), Some(ALICE)));
-			assert!(ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_tombstone().is_some());
-			if test_code_evicted {
-				assert_refcount!(set_rent_code_hash, 0);
-			} else {
-				assert_refcount!(set_rent_code_hash, 1);
-			}
-
-			// Create another account with the address `DJANGO` with `CODE_RESTORATION`.
-			//
-			// Note that we can't use `ALICE` for creating `DJANGO` so we create yet another
-			// account `CHARLIE` and create `DJANGO` with it.
-			let _ = Balances::deposit_creating(&CHARLIE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(CHARLIE),
-				30_000,
-				GAS_LIMIT,
-				restoration_wasm,
+				None,
+				wasm,
 				vec![],
 				vec![],
 			));
-			let addr_django = Contracts::contract_address(&CHARLIE, &restoration_code_hash, &[]);
-
-			// Before performing a call to `DJANGO` save its original trie id.
-			let django_trie_id = ContractInfoOf::<Test>::get(&addr_django).unwrap()
-				.get_alive().unwrap().trie_id;
-
-			// The trie is regarded as 'dirty' when it was written to in the current block.
-			if !test_restore_to_with_dirty_storage {
-				// Advance 1 block.
-				initialize_block(28);
-			}
-
-			// Perform a call to `DJANGO`. This should either perform restoration successfully or
-			// fail depending on the test parameters.
-			let perform_the_restoration = || {
-				Contracts::call(
-					Origin::signed(ALICE),
-					addr_django.clone(),
-					0,
-					GAS_LIMIT,
-					set_rent_code_hash
-						.as_ref()
-						.iter()
-						.chain(AsRef::<[u8]>::as_ref(&addr_bob))
-						.cloned()
-						.collect(),
-				)
-			};
-
-			// The key that is used in the restorer contract but is not in the target contract.
-			// Is supplied as delta to the restoration. We need it to check whether the key
-			// is properly removed on success but still there on failure.
-			let delta_key = {
-				let mut key = [0u8; 32];
-				key[0] = 1;
-				key
-			};
+			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+			let bob_contract = ContractInfoOf::<Test>::get(&addr)
+				.unwrap();
 
-			if test_different_storage || test_restore_to_with_dirty_storage || test_code_evicted {
-				// Parametrization of the test imply restoration failure. Check that `DJANGO` aka
-				// restoration contract is still in place and also that `BOB` doesn't exist.
-				let result = perform_the_restoration();
-				assert!(ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_tombstone().is_some());
-				let django_contract = ContractInfoOf::<Test>::get(&addr_django).unwrap()
-					.get_alive().unwrap();
-				assert_eq!(django_contract.storage_size, 8);
-				assert_eq!(django_contract.trie_id, django_trie_id);
-				assert_eq!(django_contract.deduct_block, System::block_number());
-				assert_eq!(
-					Storage::<Test>::read(&django_trie_id, &delta_key),
-					Some(vec![40, 0, 0, 0]),
-				);
-				match (
-					test_different_storage,
-					test_restore_to_with_dirty_storage,
-					test_code_evicted
-				) {
-					(true, false, false) => {
-						assert_err_ignore_postinfo!(
-							result, Error::<Test>::InvalidTombstone,
-						);
-						assert_eq!(System::events(), vec![]);
-					}
-					(_, true, false) => {
-						assert_err_ignore_postinfo!(
-							result, Error::<Test>::InvalidContractOrigin,
-						);
-						assert_eq!(System::events(), vec![
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_contracts(crate::Event::Evicted(addr_bob)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::frame_system(frame_system::Event::NewAccount(CHARLIE)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_balances(pallet_balances::Event::Endowed(CHARLIE, 1_000_000)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::frame_system(frame_system::Event::NewAccount(addr_django.clone())),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_balances(pallet_balances::Event::Endowed(addr_django.clone(), 30_000)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_balances(
-									pallet_balances::Event::Transfer(CHARLIE, addr_django.clone(), 30_000)
-								),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_contracts(
-									crate::Event::CodeStored(restoration_code_hash)
-								),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_contracts(
-									crate::Event::Instantiated(CHARLIE, addr_django.clone())
-								),
-								topics: vec![],
-							},
-
-						]);
-					},
-					(false, false, true) => {
-						assert_err_ignore_postinfo!(
-							result, Error::<Test>::CodeNotFound,
-						);
-						assert_refcount!(set_rent_code_hash, 0);
-						assert_eq!(System::events(), vec![]);
-					},
-					_ => unreachable!(),
-				}
-			} else {
-				assert_ok!(perform_the_restoration());
-				assert_refcount!(set_rent_code_hash, 2);
-
-				// Here we expect that the restoration is succeeded. Check that the restoration
-				// contract `DJANGO` ceased to exist and that `BOB` returned back.
-				let bob_contract = ContractInfoOf::<Test>::get(&addr_bob).unwrap()
-					.get_alive().unwrap();
-				assert_eq!(bob_contract.rent_allowance, 50);
-				assert_eq!(bob_contract.storage_size, 4);
-				assert_eq!(bob_contract.trie_id, django_trie_id);
-				assert_eq!(bob_contract.deduct_block, System::block_number());
-				assert!(ContractInfoOf::<Test>::get(&addr_django).is_none());
-				assert_matches!(Storage::<Test>::read(&django_trie_id, &delta_key), None);
-				assert_eq!(System::events(), vec![
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_contracts(crate::Event::CodeRemoved(restoration_code_hash)),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::frame_system(system::Event::KilledAccount(addr_django.clone())),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_contracts(
-							crate::Event::Restored(
-								addr_django, addr_bob, bob_contract.code_hash, 50
-							)
-						),
-						topics: vec![],
-					},
-				]);
-			}
+			assert_eq!(
+				bob_contract.storage_size,
+				0,
+			);
+			assert_eq!(
+				bob_contract.pair_count,
+				1,
+			);
 		});
 }
 
+fn initialize_block(number: u64) {
+	System::initialize(
+		&number,
+		&[0u8; 32].into(),
+		&Default::default(),
+		Default::default(),
+	);
+}
+
 #[test]
 fn storage_max_value_limit() {
 	let (wasm, code_hash) = compile_module::<Test>("storage_size").unwrap();
@@ -1522,12 +1170,13 @@ fn storage_max_value_limit() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
+			ContractInfoOf::<Test>::get(&addr).unwrap();
 
 			// Call contract with allowed storage value.
 			assert_ok!(Contracts::call(
@@ -1535,6 +1184,7 @@ fn storage_max_value_limit() {
 				addr.clone(),
 				0,
 				GAS_LIMIT * 2, // we are copying a huge buffer
+				None,
 				<Test as Config>::MaxValueSize::get().encode(),
 			));
 
@@ -1545,6 +1195,7 @@ fn storage_max_value_limit() {
 					addr,
 					0,
 					GAS_LIMIT,
+					None,
 					(<Test as Config>::MaxValueSize::get() + 1).encode(),
 				),
 				Error::<Test>::ValueTooLarge,
@@ -1567,6 +1218,7 @@ fn deploy_and_call_other_contract() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				caller_wasm,
 				vec![],
 				vec![],
@@ -1575,6 +1227,7 @@ fn deploy_and_call_other_contract() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				callee_wasm,
 				0u32.to_le_bytes().encode(),
 				vec![42],
@@ -1587,6 +1240,7 @@ fn deploy_and_call_other_contract() {
 				Contracts::contract_address(&ALICE, &caller_code_hash, &[]),
 				0,
 				GAS_LIMIT,
+				None,
 				callee_code_hash.as_ref().to_vec(),
 			));
 		});
@@ -1606,6 +1260,7 @@ fn cannot_self_destruct_through_draning() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1615,7 +1270,7 @@ fn cannot_self_destruct_through_draning() {
 			// Check that the BOB contract has been instantiated.
 			assert_matches!(
 				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
+				Some(_)
 			);
 
 			// Call BOB which makes it send all funds to the zero address
@@ -1626,6 +1281,7 @@ fn cannot_self_destruct_through_draning() {
 					addr,
 					0,
 					GAS_LIMIT,
+					None,
 					vec![],
 				)
 			);
@@ -1646,6 +1302,7 @@ fn cannot_self_destruct_while_live() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1655,7 +1312,7 @@ fn cannot_self_destruct_while_live() {
 			// Check that the BOB contract has been instantiated.
 			assert_matches!(
 				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
+				Some(_)
 			);
 
 			// Call BOB with input data, forcing it make a recursive call to itself to
@@ -1666,6 +1323,7 @@ fn cannot_self_destruct_while_live() {
 					addr.clone(),
 					0,
 					GAS_LIMIT,
+					None,
 					vec![0],
 				),
 				Error::<Test>::ContractTrapped,
@@ -1674,7 +1332,7 @@ fn cannot_self_destruct_while_live() {
 			// Check that BOB is still alive.
 			assert_matches!(
 				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
+				Some(_)
 			);
 		});
 }
@@ -1694,6 +1352,7 @@ fn self_destruct_works() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1703,9 +1362,11 @@ fn self_destruct_works() {
 			// Check that the BOB contract has been instantiated.
 			assert_matches!(
 				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
+				Some(_)
 			);
 
+			let deposit = Balances::reserved_balance(&addr);
+
 			// Drop all previous events
 			initialize_block(2);
 
@@ -1716,6 +1377,7 @@ fn self_destruct_works() {
 					addr.clone(),
 					0,
 					GAS_LIMIT,
+					None,
 					vec![],
 				),
 				Ok(_)
@@ -1724,27 +1386,41 @@ fn self_destruct_works() {
 			pretty_assertions::assert_eq!(System::events(), vec![
 				EventRecord {
 					phase: Phase::Initialization,
-					event: Event::frame_system(
-						frame_system::Event::KilledAccount(addr.clone())
+					event: Event::pallet_balances(
+						pallet_balances::Event::Transfer(addr.clone(), DJANGO, 100_000)
+					),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::pallet_contracts(
+						crate::Event::Terminated(addr.clone(), DJANGO)
 					),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::pallet_balances(
-						pallet_balances::Event::Transfer(addr.clone(), DJANGO, 93_086)
+						pallet_balances::Event::Unreserved(addr.clone(), deposit)
 					),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
-					event: Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
+					event: Event::frame_system(
+						frame_system::Event::KilledAccount(addr.clone())
+					),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
-					event: Event::pallet_contracts(
-						crate::Event::Terminated(addr.clone(), DJANGO)
+					event: Event::pallet_balances(
+						pallet_balances::Event::Transfer(addr.clone(), ALICE, deposit)
 					),
 					topics: vec![],
 				},
@@ -1752,10 +1428,13 @@ fn self_destruct_works() {
 
 			// Check that account is gone
 			assert!(ContractInfoOf::<Test>::get(&addr).is_none());
+			assert_eq!(Balances::total_balance(&addr), 0);
+
+			// check that the beneficiary (django) got the whole free balance
+			assert_eq!(Balances::free_balance(DJANGO), 1_100_000);
 
-			// check that the beneficiary (django) got remaining balance
-			// some rent was deducted before termination
-			assert_eq!(Balances::free_balance(DJANGO), 1_093_086);
+			// check that the storage deposit was refunded to the caller
+			assert_eq!(Balances::free_balance(ALICE), 1_000_000 - 100_000);
 		});
 }
 
@@ -1776,6 +1455,7 @@ fn destroy_contract_and_transfer_funds() {
 				Origin::signed(ALICE),
 				200_000,
 				GAS_LIMIT,
+				None,
 				callee_wasm,
 				vec![],
 				vec![42]
@@ -1787,6 +1467,7 @@ fn destroy_contract_and_transfer_funds() {
 				Origin::signed(ALICE),
 				200_000,
 				GAS_LIMIT,
+				None,
 				caller_wasm,
 				callee_code_hash.as_ref().to_vec(),
 				vec![],
@@ -1799,7 +1480,7 @@ fn destroy_contract_and_transfer_funds() {
 			// Check that the CHARLIE contract has been instantiated.
 			assert_matches!(
 				ContractInfoOf::<Test>::get(&addr_charlie),
-				Some(ContractInfo::Alive(_))
+				Some(_)
 			);
 
 			// Call BOB, which calls CHARLIE, forcing CHARLIE to self-destruct.
@@ -1808,6 +1489,7 @@ fn destroy_contract_and_transfer_funds() {
 				addr_bob,
 				0,
 				GAS_LIMIT,
+				None,
 				addr_charlie.encode(),
 			));
 
@@ -1831,6 +1513,7 @@ fn cannot_self_destruct_in_constructor() {
 					Origin::signed(ALICE),
 					100_000,
 					GAS_LIMIT,
+					None,
 					wasm,
 					vec![],
 					vec![],
@@ -1855,6 +1538,7 @@ fn crypto_hashes() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1886,7 +1570,9 @@ fn crypto_hashes() {
 					addr.clone(),
 					0,
 					GAS_LIMIT,
+					None,
 					params,
+					Determinism::Deterministic,
 				).result.unwrap();
 				assert!(result.is_success());
 				let expected = hash_fn(input.as_ref());
@@ -1899,14 +1585,15 @@ fn crypto_hashes() {
 fn transfer_return_code() {
 	let (wasm, code_hash) = compile_module::<Test>("transfer_return_code").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1914,28 +1601,31 @@ fn transfer_return_code() {
 		);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 
-		// Contract has only the minimal balance so any transfer will return BelowSubsistence.
-		Balances::make_free_balance_be(&addr, subsistence);
+		// Contract has only the minimal balance so any transfer will fail.
+		Balances::make_free_balance_be(&addr, min_balance);
 		let result = Contracts::bare_call(
 			ALICE,
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
+			Determinism::Deterministic,
 		).result.unwrap();
-		assert_return_code!(result, RuntimeReturnCode::BelowSubsistenceThreshold);
+		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
-		// Contract has enough total balance in order to not go below the subsistence
-		// threshold when transfering 100 balance but this balance is reserved so
-		// the transfer still fails but with another return code.
-		Balances::make_free_balance_be(&addr, subsistence + 100);
-		Balances::reserve(&addr, subsistence + 100).unwrap();
+		// Contract has enough total balance in order to not go below the minimum balance
+		// when transfering 100 balance but this balance is reserved so the transfer still fails.
+		Balances::make_free_balance_be(&addr, min_balance + 100);
+		Balances::reserve(&addr, min_balance + 100).unwrap();
 		let result = Contracts::bare_call(
 			ALICE,
 			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 	});
@@ -1946,22 +1636,23 @@ fn call_return_code() {
 	let (caller_code, caller_hash) = compile_module::<Test>("call_return_code").unwrap();
 	let (callee_code, callee_hash) = compile_module::<Test>("ok_trap_revert").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
-		let _ = Balances::deposit_creating(&CHARLIE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
+		let _ = Balances::deposit_creating(&CHARLIE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				caller_code,
 				vec![0],
 				vec![],
 			),
 		);
 		let addr_bob = Contracts::contract_address(&ALICE, &caller_hash, &[]);
-		Balances::make_free_balance_be(&addr_bob, subsistence);
+		Balances::make_free_balance_be(&addr_bob, min_balance);
 
 		// Contract calls into Django which is no valid contract
 		let result = Contracts::bare_call(
@@ -1969,55 +1660,63 @@ fn call_return_code() {
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&DJANGO).to_vec(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::NotCallable);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(CHARLIE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				callee_code,
 				vec![0],
 				vec![],
 			),
 		);
 		let addr_django = Contracts::contract_address(&CHARLIE, &callee_hash, &[]);
-		Balances::make_free_balance_be(&addr_django, subsistence);
+		Balances::make_free_balance_be(&addr_django, min_balance);
 
-		// Contract has only the minimal balance so any transfer will return BelowSubsistence.
+		// Contract has only the minimal balance so any transfer will fail.
 		let result = Contracts::bare_call(
 			ALICE,
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&0u32.to_le_bytes()).cloned().collect(),
+			Determinism::Deterministic,
 		).result.unwrap();
-		assert_return_code!(result, RuntimeReturnCode::BelowSubsistenceThreshold);
+		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
-		// Contract has enough total balance in order to not go below the subsistence
-		// threshold when transfering 100 balance but this balance is reserved so
-		// the transfer still fails but with another return code.
-		Balances::make_free_balance_be(&addr_bob, subsistence + 100);
-		Balances::reserve(&addr_bob, subsistence + 100).unwrap();
+		// Contract has enough total balance in order to not go below the minimum balance
+		// when transfering 100 balance but this balance is reserved so the transfer still fails.
+		Balances::make_free_balance_be(&addr_bob, min_balance + 100);
+		Balances::reserve(&addr_bob, min_balance + 100).unwrap();
 		let result = Contracts::bare_call(
 			ALICE,
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&0u32.to_le_bytes()).cloned().collect(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
 		// Contract has enough balance but callee reverts because "1" is passed.
-		Balances::make_free_balance_be(&addr_bob, subsistence + 1000);
+		Balances::make_free_balance_be(&addr_bob, min_balance + 1000);
 		let result = Contracts::bare_call(
 			ALICE,
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&1u32.to_le_bytes()).cloned().collect(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::CalleeReverted);
 
@@ -2027,7 +1726,9 @@ fn call_return_code() {
 			addr_bob,
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&2u32.to_le_bytes()).cloned().collect(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::CalleeTrapped);
 
@@ -2039,16 +1740,17 @@ fn instantiate_return_code() {
 	let (caller_code, caller_hash) = compile_module::<Test>("instantiate_return_code").unwrap();
 	let (callee_code, callee_hash) = compile_module::<Test>("ok_trap_revert").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
-		let _ = Balances::deposit_creating(&CHARLIE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
+		let _ = Balances::deposit_creating(&CHARLIE, 1000 * min_balance);
 		let callee_hash = callee_hash.as_ref().to_vec();
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				callee_code,
 				vec![],
 				vec![],
@@ -2058,8 +1760,9 @@ fn instantiate_return_code() {
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				caller_code,
 				vec![],
 				vec![],
@@ -2067,39 +1770,44 @@ fn instantiate_return_code() {
 		);
 		let addr = Contracts::contract_address(&ALICE, &caller_hash, &[]);
 
-		// Contract has only the minimal balance so any transfer will return BelowSubsistence.
-		Balances::make_free_balance_be(&addr, subsistence);
+		// Contract has only the minimal balance so any transfer will fail.
+		Balances::make_free_balance_be(&addr, min_balance);
 		let result = Contracts::bare_call(
 			ALICE,
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.clone(),
+			Determinism::Deterministic,
 		).result.unwrap();
-		assert_return_code!(result, RuntimeReturnCode::BelowSubsistenceThreshold);
+		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
-		// Contract has enough total balance in order to not go below the subsistence
-		// threshold when transfering the balance but this balance is reserved so
-		// the transfer still fails but with another return code.
-		Balances::make_free_balance_be(&addr, subsistence + 10_000);
-		Balances::reserve(&addr, subsistence + 10_000).unwrap();
+		// Contract has enough total balance in order to not go below the minimum balance
+		// when transfering the balance but this balance is reserved so the transfer still fails.
+		Balances::make_free_balance_be(&addr, min_balance + 10_000);
+		Balances::reserve(&addr, min_balance + 10_000).unwrap();
 		let result = Contracts::bare_call(
 			ALICE,
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.clone(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
 		// Contract has enough balance but the passed code hash is invalid
-		Balances::make_free_balance_be(&addr, subsistence + 10_000);
+		Balances::make_free_balance_be(&addr, min_balance + 10_000);
 		let result = Contracts::bare_call(
 			ALICE,
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![0; 33],
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::CodeNotFound);
 
@@ -2109,7 +1817,9 @@ fn instantiate_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.iter().chain(&1u32.to_le_bytes()).cloned().collect(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::CalleeReverted);
 
@@ -2119,7 +1829,9 @@ fn instantiate_return_code() {
 			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.iter().chain(&2u32.to_le_bytes()).cloned().collect(),
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::CalleeTrapped);
 
@@ -2130,14 +1842,15 @@ fn instantiate_return_code() {
 fn disabled_chain_extension_wont_deploy() {
 	let (code, _hash) = compile_module::<Test>("chain_extension").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 		TestExtension::disable();
 		assert_err_ignore_postinfo!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				3 * subsistence,
+				3 * min_balance,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2151,13 +1864,14 @@ fn disabled_chain_extension_wont_deploy() {
 fn disabled_chain_extension_errors_on_call() {
 	let (code, hash) = compile_module::<Test>("chain_extension").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2171,6 +1885,7 @@ fn disabled_chain_extension_errors_on_call() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			Error::<Test>::NoChainExtension,
@@ -2182,13 +1897,14 @@ fn disabled_chain_extension_errors_on_call() {
 fn chain_extension_works() {
 	let (code, hash) = compile_module::<Test>("chain_extension").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2206,7 +1922,9 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![0, 99],
+			Determinism::Deterministic,
 		);
 		let gas_consumed = result.gas_consumed;
 		assert_eq!(TestExtension::last_seen_buffer(), vec![0, 99]);
@@ -2218,7 +1936,9 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![1],
+			Determinism::Deterministic,
 		).result.unwrap();
 		// those values passed in the fixture
 		assert_eq!(TestExtension::last_seen_inputs(), (4, 1, 16, 12));
@@ -2229,7 +1949,9 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![2, 42],
+			Determinism::Deterministic,
 		);
 		assert_ok!(result.result);
 		assert_eq!(result.gas_consumed, gas_consumed + 42);
@@ -2240,7 +1962,9 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![3],
+			Determinism::Deterministic,
 		).result.unwrap();
 		assert_eq!(result.flags, ReturnFlags::REVERT);
 		assert_eq!(result.data, Bytes(vec![42, 99]));
@@ -2251,14 +1975,15 @@ fn chain_extension_works() {
 fn lazy_removal_works() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2266,7 +1991,7 @@ fn lazy_removal_works() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let trie = &info.child_trie_info();
 
 		// Put value into the contracts child trie
@@ -2278,6 +2003,7 @@ fn lazy_removal_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2311,14 +2037,15 @@ fn lazy_removal_partial_remove_works() {
 	let mut ext = ExtBuilder::default().existential_deposit(50).build();
 
 	let trie = ext.execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2326,7 +2053,7 @@ fn lazy_removal_partial_remove_works() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let trie = &info.child_trie_info();
 
 		// Put value into the contracts child trie
@@ -2336,6 +2063,7 @@ fn lazy_removal_partial_remove_works() {
 				&info.trie_id,
 				&val.0,
 				Some(val.2.clone()),
+				None,
 			).unwrap();
 		}
 
@@ -2345,6 +2073,7 @@ fn lazy_removal_partial_remove_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2392,14 +2121,15 @@ fn lazy_removal_partial_remove_works() {
 fn lazy_removal_does_no_run_on_full_block() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2407,7 +2137,7 @@ fn lazy_removal_does_no_run_on_full_block() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let trie = &info.child_trie_info();
 		let max_keys = 30;
 
@@ -2424,6 +2154,7 @@ fn lazy_removal_does_no_run_on_full_block() {
 				&info.trie_id,
 				&val.0,
 				Some(val.2.clone()),
+				None,
 			).unwrap();
 		}
 
@@ -2433,6 +2164,7 @@ fn lazy_removal_does_no_run_on_full_block() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2476,14 +2208,15 @@ fn lazy_removal_does_no_run_on_full_block() {
 fn lazy_removal_does_not_use_all_weight() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2491,7 +2224,7 @@ fn lazy_removal_does_not_use_all_weight() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let trie = &info.child_trie_info();
 		let weight_limit = 5_000_000_000;
 		let (weight_per_key, max_keys) = Storage::<Test>::deletion_budget(1, weight_limit);
@@ -2509,6 +2242,7 @@ fn lazy_removal_does_not_use_all_weight() {
 				&info.trie_id,
 				&val.0,
 				Some(val.2.clone()),
+				None,
 			).unwrap();
 		}
 
@@ -2518,6 +2252,7 @@ fn lazy_removal_does_not_use_all_weight() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2546,14 +2281,15 @@ fn lazy_removal_does_not_use_all_weight() {
 fn deletion_queue_full() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence * 100,
+				min_balance * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2572,98 +2308,14 @@ fn deletion_queue_full() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			Error::<Test>::DeletionQueueFull,
 		);
 
-		// Contract should be alive because removal failed
-		<ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
-
-		// make the contract ripe for eviction
-		initialize_block(5);
-
-		// eviction should fail for the same reason as termination
-		assert_err!(
-			Contracts::claim_surcharge(Origin::none(), addr.clone(), Some(ALICE)),
-			Error::<Test>::DeletionQueueFull,
-		);
-
-		// Contract should be alive because removal failed
-		<ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
-	});
-}
-
-#[test]
-fn not_deployed_if_endowment_too_low_for_first_rent() {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
-
-	// The instantiation deducted the rent for one block immediately
-	let first_rent = <Test as Config>::RentFraction::get()
-		// base_deposit + deploy_set_storage (4 bytes in 1 item) - free_balance
-		.mul_ceil(80_000u32 + 40_000 + 10_000 - 30_000)
-		// blocks to rent
-		* 1;
-
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		// Create
-		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		assert_storage_noop!(assert_err_ignore_postinfo!(
-			Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				30_000,
-				GAS_LIMIT,
-				wasm,
-				(BalanceOf::<Test>::from(first_rent) - BalanceOf::<Test>::from(1u32))
-					.encode(), // rent allowance
-				vec![],
-			),
-			Error::<Test>::NewContractNotFunded,
-		));
-		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		assert_matches!(ContractInfoOf::<Test>::get(&addr), None);
-	});
-}
-
-#[test]
-fn surcharge_reward_is_capped() {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		assert_ok!(Contracts::instantiate_with_code(
-			Origin::signed(ALICE),
-			30_000,
-			GAS_LIMIT,
-			wasm,
-			<BalanceOf<Test>>::from(10_000u32).encode(), // rent allowance
-			vec![],
-		));
-		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		let contract = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
-		let balance = Balances::free_balance(&ALICE);
-		let reward = <Test as Config>::SurchargeReward::get();
-
-		// some rent should have payed due to instantiation
-		assert_ne!(contract.rent_payed, 0);
-
-		// the reward should be parameterized sufficiently high to make this test useful
-		assert!(reward > contract.rent_payed);
-
-		// make contract eligible for eviction
-		initialize_block(40);
-
-		// this should have removed the contract
-		assert_ok!(Contracts::claim_surcharge(Origin::none(), addr.clone(), Some(ALICE)));
-
-		// this reward does not take into account the last rent payment collected during eviction
-		let capped_reward = reward.min(contract.rent_payed);
-
-		// this is smaller than the actual reward because it does not take into account the
-		// rent collected during eviction
-		assert!(Balances::free_balance(&ALICE) > balance + capped_reward);
-
-		// the full reward is not payed out because of the cap introduced by rent_payed
-		assert!(Balances::free_balance(&ALICE) < balance + reward);
+		// Contract should exist because removal failed
+		<ContractInfoOf::<Test>>::get(&addr).unwrap();
 	});
 }
 
@@ -2672,21 +2324,23 @@ fn refcounter() {
 	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
+		let min_balance = <Test as Config>::Currency::minimum_balance();
 
 		// Create two contracts with the same code and check that they do in fact share it.
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm.clone(),
 			vec![],
 			vec![0],
 		));
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm.clone(),
 			vec![],
 			vec![1],
@@ -2696,8 +2350,9 @@ fn refcounter() {
 		// Sharing should also work with the usual instantiate call
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code_hash,
 			vec![],
 			vec![2],
@@ -2715,22 +2370,34 @@ fn refcounter() {
 			addr0,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 		assert_refcount!(code_hash, 2);
 
-		// make remaining contracts eligible for eviction
-		initialize_block(40);
-
-		// remove one of them
-		assert_ok!(Contracts::claim_surcharge(Origin::none(), addr1, Some(ALICE)));
+		// remove another one
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr1,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+		));
 		assert_refcount!(code_hash, 1);
 
 		// Pristine code should still be there
 		crate::PristineCode::<Test>::get(code_hash).unwrap();
 
 		// remove the last contract
-		assert_ok!(Contracts::claim_surcharge(Origin::none(), addr2, Some(ALICE)));
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr2,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+		));
 		assert_refcount!(code_hash, 0);
 
 		// all code should be gone
@@ -2744,14 +2411,15 @@ fn reinstrument_does_charge() {
 	let (wasm, code_hash) = compile_module::<Test>("return_with_data").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
+		let min_balance = <Test as Config>::Currency::minimum_balance();
 		let zero = 0u32.to_le_bytes().encode();
 		let code_len = wasm.len() as u32;
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm,
 			zero.clone(),
 			vec![],
@@ -2766,7 +2434,9 @@ fn reinstrument_does_charge() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			zero.clone(),
+			Determinism::Deterministic,
 		);
 		assert!(result0.result.unwrap().is_success());
 
@@ -2775,7 +2445,9 @@ fn reinstrument_does_charge() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			zero.clone(),
+			Determinism::Deterministic,
 		);
 		assert!(result1.result.unwrap().is_success());
 
@@ -2793,7 +2465,9 @@ fn reinstrument_does_charge() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			zero.clone(),
+			Determinism::Deterministic,
 		);
 		assert!(result2.result.unwrap().is_success());
 		assert!(result2.gas_consumed > result1.gas_consumed);
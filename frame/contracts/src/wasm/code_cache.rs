@@ -26,15 +26,24 @@
 //! - When we update the schedule we want it to have strictly greater version than the current saved one:
 //! this guarantees that every instrumented contract code in cache cannot have the version equal to the current one.
 //! Thus, before executing a contract it should be reinstrument with new schedule.
+//! - Code that is uploaded on its own has an owner who paid a deposit for it. Such code is kept
+//! even if no contract uses it until its owner removes it. Other code is removed as soon as
+//! its refcount drops to zero.
 
 use crate::{
-	CodeHash, CodeStorage, PristineCode, Schedule, Config, Error, Weight,
-	wasm::{prepare, PrefabWasmModule}, Pallet as Contracts, Event,
+	CodeHash, CodeStorage, PristineCode, OwnerInfoOf, Schedule, Config, Error, Weight, BalanceOf,
+	wasm::{prepare, PrefabWasmModule, OwnerInfo}, Pallet as Contracts, Event,
+	exec::Executable,
 	gas::{GasMeter, Token},
 	weights::WeightInfo,
 };
 use sp_core::crypto::UncheckedFrom;
-use frame_support::dispatch::DispatchError;
+use sp_runtime::traits::{Saturating, Zero};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	traits::{Get, ReservableCurrency},
+};
 #[cfg(feature = "runtime-benchmarks")]
 pub use self::private::reinstrument as reinstrument;
 
@@ -64,15 +73,80 @@ where
 	});
 }
 
+/// Put the instrumented module in storage as code uploaded by `origin`.
+///
+/// The `origin` becomes the owner of the code and pays a deposit for the pristine and the
+/// instrumented code. The code is stored with a refcount of zero if it is not yet on-chain.
+/// Nothing is charged if the code is already owned by someone.
+///
+/// Returns the deposit that was charged.
+pub fn store_uploaded<T: Config>(
+	mut prefab_module: PrefabWasmModule<T>,
+	origin: &T::AccountId,
+	storage_deposit_limit: Option<BalanceOf<T>>,
+) -> Result<BalanceOf<T>, DispatchError>
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
+{
+	let code_hash = sp_std::mem::take(&mut prefab_module.code_hash);
+	if <OwnerInfoOf<T>>::contains_key(&code_hash) {
+		return Ok(Zero::zero())
+	}
+
+	// The pristine code, the instrumented code and the owner info.
+	let deposit = T::DepositPerByte::get()
+		.saturating_mul(prefab_module.aggregate_code_len().into())
+		.saturating_add(T::DepositPerItem::get().saturating_mul(3u32.into()));
+	if let Some(limit) = storage_deposit_limit {
+		ensure!(deposit <= limit, Error::<T>::StorageDepositLimitExhausted);
+	}
+	T::Currency::reserve(origin, deposit)
+		.map_err(|_| Error::<T>::StorageDepositNotEnoughFunds)?;
+	<OwnerInfoOf<T>>::insert(&code_hash, OwnerInfo { owner: origin.clone(), deposit });
+
+	if !<CodeStorage<T>>::contains_key(&code_hash) {
+		if let Some(code) = prefab_module.original_code.take() {
+			<PristineCode<T>>::insert(&code_hash, code);
+		}
+		prefab_module.refcount = 0;
+		<CodeStorage<T>>::insert(&code_hash, prefab_module);
+		Contracts::<T>::deposit_event(Event::CodeStored(code_hash));
+	}
+	Ok(deposit)
+}
+
+/// Remove the code that was uploaded by `origin` under `code_hash`.
+///
+/// Only the owner can remove the code and only when no contract uses it anymore. The deposit
+/// is refunded to the owner.
+pub fn try_remove<T: Config>(origin: &T::AccountId, code_hash: CodeHash<T>) -> DispatchResult
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
+{
+	let owner_info = <OwnerInfoOf<T>>::get(&code_hash).ok_or(Error::<T>::CodeNotFound)?;
+	ensure!(&owner_info.owner == origin, DispatchError::BadOrigin);
+	let refcount = <CodeStorage<T>>::get(&code_hash)
+		.map(|module| module.refcount)
+		.unwrap_or_default();
+	ensure!(refcount == 0, Error::<T>::CodeInUse);
+
+	T::Currency::unreserve(&owner_info.owner, owner_info.deposit);
+	<OwnerInfoOf<T>>::remove(&code_hash);
+	<CodeStorage<T>>::remove(&code_hash);
+	finish_removal::<T>(code_hash);
+	Ok(())
+}
+
 /// Decrement the refcount and store.
 ///
-/// Removes the code instead of storing it when the refcount drops to zero.
+/// Removes the code instead of storing it when the refcount drops to zero and the code
+/// has no owner.
 pub fn store_decremented<T: Config>(mut prefab_module: PrefabWasmModule<T>)
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
 {
 	prefab_module.refcount = prefab_module.refcount.saturating_sub(1);
-	if prefab_module.refcount > 0 {
+	if prefab_module.refcount > 0 || <OwnerInfoOf<T>>::contains_key(prefab_module.code_hash) {
 		<CodeStorage<T>>::insert(prefab_module.code_hash, prefab_module);
 	} else {
 		<CodeStorage<T>>::remove(prefab_module.code_hash);
@@ -95,7 +169,8 @@ where
 	})
 }
 
-/// Decrement the refcount of a code in-storage by one and remove the code when it drops to zero.
+/// Decrement the refcount of a code in-storage by one and remove the code when it drops to zero
+/// and the code has no owner.
 pub fn decrement_refcount<T: Config>(code_hash: CodeHash<T>) -> u32
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
@@ -104,7 +179,7 @@ where
 		if let Some(module) = existing {
 			let code_len = module.original_code_len;
 			module.refcount = module.refcount.saturating_sub(1);
-			if module.refcount == 0 {
+			if module.refcount == 0 && !<OwnerInfoOf<T>>::contains_key(code_hash) {
 				*existing = None;
 				finish_removal::<T>(code_hash);
 			}
@@ -157,7 +232,11 @@ mod private {
 	{
 		let original_code = <PristineCode<T>>::get(&prefab_module.code_hash)
 			.ok_or_else(|| Error::<T>::CodeNotFound)?;
-		prefab_module.code = prepare::reinstrument_contract::<T>(original_code, schedule)?;
+		prefab_module.code = prepare::reinstrument_contract::<T>(
+			original_code,
+			schedule,
+			prefab_module.determinism,
+		)?;
 		prefab_module.schedule_version = schedule.version;
 		<CodeStorage<T>>::insert(&prefab_module.code_hash, &*prefab_module);
 		Ok(())
@@ -25,7 +25,7 @@ mod prepare;
 mod runtime;
 
 use crate::{
-	CodeHash, Schedule, Config,
+	CodeHash, Schedule, Config, BalanceOf,
 	wasm::env_def::FunctionImplProvider,
 	exec::{Ext, Executable, ExportedFunction, ExecResult},
 	gas::GasMeter,
@@ -33,7 +33,8 @@ use crate::{
 use sp_std::prelude::*;
 use sp_core::crypto::UncheckedFrom;
 use codec::{Encode, Decode};
-use frame_support::dispatch::DispatchError;
+use sp_runtime::RuntimeDebug;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 pub use self::runtime::{ReturnCode, Runtime, RuntimeToken};
 #[cfg(feature = "runtime-benchmarks")]
 pub use self::code_cache::reinstrument;
@@ -66,20 +67,17 @@ pub struct PrefabWasmModule<T: Config> {
 	/// If this number drops to zero this module is removed from storage.
 	#[codec(compact)]
 	refcount: u64,
-	/// This field is reserved for future evolution of format.
+	/// Whether the code is allowed to behave indeterministically.
 	///
-	/// For now this field is serialized as `None`. In the future we are able to change the
-	/// type parameter to a new struct that contains the fields that we want to add.
-	/// That new struct would also contain a reserved field for its future extensions.
-	/// This works because in SCALE `None` is encoded independently from the type parameter
-	/// of the option.
-	_reserved: Option<()>,
+	/// This field takes the place of a formerly reserved `Option<()>` which was always `None`.
+	/// [`Determinism::Deterministic`] has the same encoding so no migration is necessary.
+	determinism: Determinism,
 	/// Code instrumented with the latest schedule.
 	code: Vec<u8>,
 	/// The size of the uninstrumented code.
 	///
 	/// We cache this value here in order to avoid the need to pull the pristine code
-	/// from storage when we only need its length for rent calculations.
+	/// from storage when we only need its length for weight calculations.
 	original_code_len: u32,
 	/// The uninstrumented, pristine version of the code.
 	///
@@ -96,6 +94,32 @@ pub struct PrefabWasmModule<T: Config> {
 	code_hash: CodeHash<T>,
 }
 
+/// Information about the owner of a code that was uploaded with [`crate::Pallet::upload_code`].
+#[derive(Encode, Decode)]
+pub struct OwnerInfo<T: Config> {
+	/// The account that uploaded the code and may remove it.
+	owner: T::AccountId,
+	/// The amount of balance that was reserved from the owner for storing the code.
+	#[codec(compact)]
+	deposit: BalanceOf<T>,
+}
+
+/// Determines whether a code is allowed to behave indeterministically.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+pub enum Determinism {
+	/// The code is checked to only contain deterministic instructions.
+	///
+	/// This is the only kind of code that can be executed on-chain.
+	Deterministic,
+	/// The code is allowed to use instructions whose result may differ between machines,
+	/// such as floating point operations.
+	///
+	/// Such code can be uploaded but is refused by any on-chain execution. It can only be
+	/// executed by off-chain callers of [`crate::Pallet::bare_call`] and
+	/// [`crate::Pallet::bare_instantiate`], for example in dry-runs of an RPC.
+	AllowIndeterminism,
+}
+
 impl ExportedFunction {
 	/// The wasm export name for the function.
 	fn identifier(&self) -> &str {
@@ -111,11 +135,30 @@ where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
 {
 	/// Create the module by checking and instrumenting `original_code`.
+	///
+	/// Floating point types are only accepted when `determinism` allows indeterminism.
 	pub fn from_code(
 		original_code: Vec<u8>,
-		schedule: &Schedule<T>
+		schedule: &Schedule<T>,
+		determinism: Determinism,
 	) -> Result<Self, DispatchError> {
-		prepare::prepare_contract(original_code, schedule).map_err(Into::into)
+		prepare::prepare_contract(original_code, schedule, determinism).map_err(Into::into)
+	}
+
+	/// Store the module as code uploaded by `origin` which is kept until it is removed.
+	///
+	/// See [`code_cache::store_uploaded`] for the deposit that is charged.
+	pub fn store_uploaded(
+		self,
+		origin: &T::AccountId,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		code_cache::store_uploaded(self, origin, storage_deposit_limit)
+	}
+
+	/// Remove the code uploaded under `code_hash` and refund the deposit to its owner.
+	pub fn remove(origin: &T::AccountId, code_hash: CodeHash<T>) -> DispatchResult {
+		code_cache::try_remove::<T>(origin, code_hash)
 	}
 
 	/// Create and store the module without checking nor instrumenting the passed code.
@@ -220,13 +263,6 @@ where
 		&self.code_hash
 	}
 
-	fn occupied_storage(&self) -> u32 {
-		// We disregard the size of the struct itself as the size is completely
-		// dominated by the code size.
-		let len = self.aggregate_code_len();
-		len.checked_div(self.refcount as u32).unwrap_or(len)
-	}
-
 	fn code_len(&self) -> u32 {
 		self.code.len() as u32
 	}
@@ -238,6 +274,10 @@ where
 	fn refcount(&self) -> u32 {
 		self.refcount as u32
 	}
+
+	fn is_deterministic(&self) -> bool {
+		matches!(self.determinism, Determinism::Deterministic)
+	}
 }
 
 #[cfg(test)]
@@ -266,14 +306,6 @@ mod tests {
 	#[derive(Debug, PartialEq, Eq)]
 	struct DispatchEntry(Call);
 
-	#[derive(Debug, PartialEq, Eq)]
-	struct RestoreEntry {
-		dest: AccountIdOf<Test>,
-		code_hash: H256,
-		rent_allowance: u64,
-		delta: Vec<StorageKey>,
-	}
-
 	#[derive(Debug, PartialEq, Eq)]
 	struct InstantiateEntry {
 		code_hash: H256,
@@ -298,15 +330,12 @@ mod tests {
 	#[derive(Default)]
 	pub struct MockExt {
 		storage: HashMap<StorageKey, Vec<u8>>,
-		rent_allowance: u64,
 		instantiates: Vec<InstantiateEntry>,
 		terminations: Vec<TerminationEntry>,
 		transfers: Vec<TransferEntry>,
-		restores: Vec<RestoreEntry>,
 		// (topics, data)
 		events: Vec<(Vec<H256>, Vec<u8>)>,
 		schedule: Schedule<Test>,
-		rent_params: RentParams<Test>,
 	}
 
 	impl Ext for MockExt {
@@ -380,21 +409,6 @@ mod tests {
 			});
 			Ok(0)
 		}
-		fn restore_to(
-			&mut self,
-			dest: AccountIdOf<Self::T>,
-			code_hash: H256,
-			rent_allowance: u64,
-			delta: Vec<StorageKey>,
-		) -> Result<(u32, u32), (DispatchError, u32, u32)> {
-			self.restores.push(RestoreEntry {
-				dest,
-				code_hash,
-				rent_allowance,
-				delta,
-			});
-			Ok((0, 0))
-		}
 		fn caller(&self) -> &AccountIdOf<Self::T> {
 			&ALICE
 		}
@@ -413,21 +427,12 @@ mod tests {
 		fn minimum_balance(&self) -> u64 {
 			666
 		}
-		fn tombstone_deposit(&self) -> u64 {
-			16
-		}
 		fn random(&self, subject: &[u8]) -> (SeedOf<Self::T>, BlockNumberOf<Self::T>) {
 			(H256::from_slice(subject), 42)
 		}
 		fn deposit_event(&mut self, topics: Vec<H256>, data: Vec<u8>) {
 			self.events.push((topics, data))
 		}
-		fn set_rent_allowance(&mut self, rent_allowance: u64) {
-			self.rent_allowance = rent_allowance;
-		}
-		fn rent_allowance(&self) -> u64 {
-			self.rent_allowance
-		}
 		fn block_number(&self) -> u64 { 121 }
 		fn max_value_size(&self) -> u32 { 16_384 }
 		fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T> {
@@ -436,9 +441,6 @@ mod tests {
 		fn schedule(&self) -> &Schedule<Self::T> {
 			&self.schedule
 		}
-		fn rent_params(&self) -> &RentParams<Self::T> {
-			&self.rent_params
-		}
 	}
 
 	impl Ext for &mut MockExt {
@@ -482,20 +484,6 @@ mod tests {
 		) -> Result<(ExecReturnValue, u32), (ExecError, u32)> {
 			(**self).call(to, value, gas_meter, input_data)
 		}
-		fn restore_to(
-			&mut self,
-			dest: AccountIdOf<Self::T>,
-			code_hash: H256,
-			rent_allowance: u64,
-			delta: Vec<StorageKey>,
-		) -> Result<(u32, u32), (DispatchError, u32, u32)> {
-			(**self).restore_to(
-				dest,
-				code_hash,
-				rent_allowance,
-				delta,
-			)
-		}
 		fn caller(&self) -> &AccountIdOf<Self::T> {
 			(**self).caller()
 		}
@@ -514,21 +502,12 @@ mod tests {
 		fn minimum_balance(&self) -> u64 {
 			(**self).minimum_balance()
 		}
-		fn tombstone_deposit(&self) -> u64 {
-			(**self).tombstone_deposit()
-		}
 		fn random(&self, subject: &[u8]) -> (SeedOf<Self::T>, BlockNumberOf<Self::T>) {
 			(**self).random(subject)
 		}
 		fn deposit_event(&mut self, topics: Vec<H256>, data: Vec<u8>) {
 			(**self).deposit_event(topics, data)
 		}
-		fn set_rent_allowance(&mut self, rent_allowance: u64) {
-			(**self).set_rent_allowance(rent_allowance)
-		}
-		fn rent_allowance(&self) -> u64 {
-			(**self).rent_allowance()
-		}
 		fn block_number(&self) -> u64 {
 			(**self).block_number()
 		}
@@ -541,9 +520,6 @@ mod tests {
 		fn schedule(&self) -> &Schedule<Self::T> {
 			(**self).schedule()
 		}
-		fn rent_params(&self) -> &RentParams<Self::T> {
-			(**self).rent_params()
-		}
 	}
 
 	fn execute<E: Ext>(
@@ -558,7 +534,11 @@ mod tests {
 	{
 		let wasm = wat::parse_str(wat).unwrap();
 		let schedule = crate::Schedule::default();
-		let executable = PrefabWasmModule::<E::T>::from_code(wasm, &schedule).unwrap();
+		let executable = PrefabWasmModule::<E::T>::from_code(
+			wasm,
+			&schedule,
+			Determinism::Deterministic,
+		).unwrap();
 		executable.execute(ext, &ExportedFunction::Call, input_data, gas_meter)
 	}
 
@@ -1444,11 +1424,12 @@ mod tests {
 			)
 		)
 
-		;; assert that contents of the buffer is equal to the i64 value of 16.
+		;; assert that contents of the buffer is equal to the i64 value of 0.
+		;; There is no tombstone deposit anymore.
 		(call $assert
 			(i64.eq
 				(i64.load (i32.const 0))
-				(i64.const 16)
+				(i64.const 0)
 			)
 		)
 	)
@@ -22,7 +22,7 @@
 use crate::{
 	Schedule, Config,
 	chain_extension::ChainExtension,
-	wasm::{PrefabWasmModule, env_def::ImportSatisfyCheck},
+	wasm::{PrefabWasmModule, Determinism, env_def::ImportSatisfyCheck},
 };
 use parity_wasm::elements::{self, Internal, External, MemoryType, Type, ValueType};
 use sp_runtime::traits::Hash;
@@ -185,8 +185,8 @@ impl<'a, T: Config> ContractModule<'a, T> {
 		Ok(())
 	}
 
-	fn inject_gas_metering(self) -> Result<Self, &'static str> {
-		let gas_rules = self.schedule.rules(&self.module);
+	fn inject_gas_metering(self, determinism: Determinism) -> Result<Self, &'static str> {
+		let gas_rules = self.schedule.rules(&self.module, determinism);
 		let contract_module = pwasm_utils::inject_gas_counter(
 			self.module,
 			&gas_rules,
@@ -405,13 +405,16 @@ fn get_memory_limits<T: Config>(module: Option<&MemoryType>, schedule: &Schedule
 fn check_and_instrument<C: ImportSatisfyCheck, T: Config>(
 	original_code: &[u8],
 	schedule: &Schedule<T>,
+	determinism: Determinism,
 ) -> Result<(Vec<u8>, (u32, u32)), &'static str> {
 	let contract_module = ContractModule::new(&original_code, schedule)?;
 	contract_module.scan_exports()?;
 	contract_module.ensure_no_internal_memory()?;
 	contract_module.ensure_table_size_limit(schedule.limits.table_size)?;
 	contract_module.ensure_global_variable_limit(schedule.limits.globals)?;
-	contract_module.ensure_no_floating_types()?;
+	if determinism == Determinism::Deterministic {
+		contract_module.ensure_no_floating_types()?;
+	}
 	contract_module.ensure_parameter_limit(schedule.limits.parameters)?;
 	contract_module.ensure_br_table_size_limit(schedule.limits.br_table_size)?;
 
@@ -423,7 +426,7 @@ fn check_and_instrument<C: ImportSatisfyCheck, T: Config>(
 	)?;
 
 	let code = contract_module
-		.inject_gas_metering()?
+		.inject_gas_metering(determinism)?
 		.inject_stack_height_metering()?
 		.into_wasm_code()?;
 
@@ -433,16 +436,18 @@ fn check_and_instrument<C: ImportSatisfyCheck, T: Config>(
 fn do_preparation<C: ImportSatisfyCheck, T: Config>(
 	original_code: Vec<u8>,
 	schedule: &Schedule<T>,
+	determinism: Determinism,
 ) -> Result<PrefabWasmModule<T>, &'static str> {
 	let (code, (initial, maximum)) = check_and_instrument::<C, T>(
 		original_code.as_ref(),
 		schedule,
+		determinism,
 	)?;
 	Ok(PrefabWasmModule {
 		schedule_version: schedule.version,
 		initial,
 		maximum,
-		_reserved: None,
+		determinism,
 		code,
 		original_code_len: original_code.len() as u32,
 		refcount: 1,
@@ -460,13 +465,29 @@ fn do_preparation<C: ImportSatisfyCheck, T: Config>(
 /// - the module doesn't define an internal memory instance,
 /// - imported memory (if any) doesn't reserve more memory than permitted by the `schedule`,
 /// - all imported functions from the external environment matches defined by `env` module,
+/// - no floating point types are used unless `determinism` allows indeterminism.
+///
+/// Code that passes the checks for deterministic code is always marked as deterministic, even
+/// if `determinism` allows indeterminism. Otherwise whoever uploads some code first could
+/// prevent it from being used on-chain.
 ///
 /// The preprocessing includes injecting code for gas metering and metering the height of stack.
 pub fn prepare_contract<T: Config>(
 	original_code: Vec<u8>,
 	schedule: &Schedule<T>,
+	determinism: Determinism,
 ) -> Result<PrefabWasmModule<T>, &'static str> {
-	do_preparation::<super::runtime::Env, T>(original_code, schedule)
+	if determinism == Determinism::AllowIndeterminism {
+		let deterministic = do_preparation::<super::runtime::Env, T>(
+			original_code.clone(),
+			schedule,
+			Determinism::Deterministic,
+		);
+		if deterministic.is_ok() {
+			return deterministic
+		}
+	}
+	do_preparation::<super::runtime::Env, T>(original_code, schedule, determinism)
 }
 
 /// The same as [`prepare_contract`] but without constructing a new [`PrefabWasmModule`]
@@ -477,8 +498,9 @@ pub fn prepare_contract<T: Config>(
 pub fn reinstrument_contract<T: Config>(
 	original_code: Vec<u8>,
 	schedule: &Schedule<T>,
+	determinism: Determinism,
 ) -> Result<Vec<u8>, &'static str> {
-	Ok(check_and_instrument::<super::runtime::Env, T>(&original_code, schedule)?.0)
+	Ok(check_and_instrument::<super::runtime::Env, T>(&original_code, schedule, determinism)?.0)
 }
 
 /// Alternate (possibly unsafe) preparation functions used only for benchmarking.
@@ -508,7 +530,7 @@ pub mod benchmarking {
 			schedule_version: schedule.version,
 			initial: memory_limits.0,
 			maximum: memory_limits.1,
-			_reserved: None,
+			determinism: Determinism::Deterministic,
 			code: contract_module.into_wasm_code()?,
 			original_code_len: original_code.len() as u32,
 			refcount: 1,
@@ -568,7 +590,11 @@ mod tests {
 					},
 					.. Default::default()
 				};
-				let r = do_preparation::<env::Test, crate::tests::Test>(wasm, &schedule);
+				let r = do_preparation::<env::Test, crate::tests::Test>(
+					wasm,
+					&schedule,
+					Determinism::Deterministic,
+				);
 				assert_matches::assert_matches!(r, $($expected)*);
 			}
 		};
@@ -1090,5 +1116,26 @@ mod tests {
 			"#,
 			Err("use of floating point type in function types is forbidden")
 		);
+
+		#[test]
+		fn floats_allowed_when_indeterministic() {
+			let wasm = wat::parse_str(r#"
+			(module
+				(global $x f32 (f32.const 0))
+				(func $foo (param f32) (result f32) (local f32)
+					(f32.add (local.get 0) (f32.const 1))
+				)
+				(func (export "call"))
+				(func (export "deploy"))
+			)
+			"#).unwrap();
+			let schedule = Schedule::default();
+			let module = do_preparation::<env::Test, crate::tests::Test>(
+				wasm,
+				&schedule,
+				Determinism::AllowIndeterminism,
+			).unwrap();
+			assert_eq!(module.determinism, Determinism::AllowIndeterminism);
+		}
 	}
 }
@@ -28,7 +28,7 @@ use parity_wasm::elements::ValueType;
 use frame_support::{dispatch::DispatchError, ensure, traits::Get, weights::Weight};
 use sp_std::prelude::*;
 use codec::{Decode, DecodeAll, Encode};
-use sp_runtime::traits::SaturatedConversion;
+use sp_runtime::traits::{Bounded, SaturatedConversion, Zero};
 use sp_core::{Bytes, crypto::UncheckedFrom};
 use sp_io::hashing::{
 	keccak_256,
@@ -58,19 +58,16 @@ pub enum ReturnCode {
 	CalleeReverted = 2,
 	/// The passed key does not exist in storage.
 	KeyNotFound = 3,
-	/// Transfer failed because it would have brought the sender's total balance below the
-	/// subsistence threshold.
+	/// Deprecated and no longer returned: There is only the minimum balance.
 	BelowSubsistenceThreshold = 4,
 	/// Transfer failed for other reasons. Most probably reserved or locked balance of the
 	/// sender prevents the transfer.
 	TransferFailed = 5,
-	/// The newly created contract is below the subsistence threshold after executing
-	/// its constructor.
+	/// Deprecated and no longer returned: Endowment is no longer required.
 	NewContractNotFunded = 6,
 	/// No code could be found at the supplied code hash.
 	CodeNotFound = 7,
-	/// The contract that was called is either no contract at all (a plain account)
-	/// or is a tombstone.
+	/// The contract that was called is no contract (a plain account).
 	NotCallable = 8,
 }
 
@@ -120,8 +117,6 @@ pub enum TrapReason {
 	/// Signals that a trap was generated in response to a successful call to the
 	/// `seal_terminate` host function.
 	Termination,
-	/// Signals that a trap was generated because of a successful restoration.
-	Restoration,
 }
 
 impl<T: Into<DispatchError>> From<T> for TrapReason {
@@ -168,10 +163,8 @@ pub enum RuntimeToken {
 	Terminate,
 	/// Weight that is added to `seal_terminate` for every byte of the terminated contract.
 	TerminateSurchargeCodeSize(u32),
-	/// Weight of calling `seal_restore_to` per number of supplied delta entries.
-	RestoreTo(u32),
-	/// Weight that is added to `seal_restore_to` for the involved code sizes.
-	RestoreToSurchargeCodeSize{caller_code: u32, tombstone_code: u32},
+	/// Weight of calling `seal_restore_to`.
+	RestoreTo,
 	/// Weight of calling `seal_random`. It includes the weight for copying the subject.
 	Random,
 	/// Weight of calling `seal_deposit_event` with the given number of topics and event size.
@@ -247,12 +240,7 @@ where
 				.saturating_add(s.return_per_byte.saturating_mul(len.into())),
 			Terminate => s.terminate,
 			TerminateSurchargeCodeSize(len) => s.terminate_per_code_byte.saturating_mul(len.into()),
-			RestoreTo(delta) => s.restore_to
-				.saturating_add(s.restore_to_per_delta.saturating_mul(delta.into())),
-			RestoreToSurchargeCodeSize{caller_code, tombstone_code} =>
-				s.restore_to_per_caller_code_byte.saturating_mul(caller_code.into()).saturating_add(
-					s.restore_to_per_tombstone_code_byte.saturating_mul(tombstone_code.into())
-				),
+			RestoreTo => s.restore_to,
 			Random => s.random,
 			DepositEvent{num_topic, len} => s.deposit_event
 				.saturating_add(s.deposit_event_per_topic.saturating_mul(num_topic.into()))
@@ -356,12 +344,6 @@ where
 						data: Bytes(Vec::new()),
 					})
 				},
-				TrapReason::Restoration => {
-					Ok(ExecReturnValue {
-						flags: ReturnFlags::empty(),
-						data: Bytes(Vec::new()),
-					})
-				},
 				TrapReason::SupervisorError(error) => Err(error)?,
 			}
 		}
@@ -578,16 +560,12 @@ where
 	fn err_into_return_code(from: DispatchError) -> Result<ReturnCode, DispatchError> {
 		use ReturnCode::*;
 
-		let below_sub = Error::<E::T>::BelowSubsistenceThreshold.into();
 		let transfer_failed = Error::<E::T>::TransferFailed.into();
-		let not_funded = Error::<E::T>::NewContractNotFunded.into();
 		let no_code = Error::<E::T>::CodeNotFound.into();
 		let invalid_contract = Error::<E::T>::NotCallable.into();
 
 		match from {
-			x if x == below_sub => Ok(BelowSubsistenceThreshold),
 			x if x == transfer_failed => Ok(TransferFailed),
-			x if x == not_funded => Ok(NewContractNotFunded),
 			x if x == no_code => Ok(CodeNotFound),
 			x if x == invalid_contract => Ok(NotCallable),
 			err => Err(err)
@@ -712,7 +690,6 @@ define_env!(Env, <E: Ext>,
 	//
 	// # Errors
 	//
-	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
 	[seal0] seal_transfer(
 		ctx,
@@ -765,7 +742,6 @@ define_env!(Env, <E: Ext>,
 	//
 	// `ReturnCode::CalleeReverted`: Output buffer is returned.
 	// `ReturnCode::CalleeTrapped`
-	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
 	// `ReturnCode::NotCallable`
 	[seal0] seal_call(
@@ -832,10 +808,6 @@ define_env!(Env, <E: Ext>,
 	// length to `output_len_ptr`. The copy of the output buffer and address can be skipped by
 	// supplying the sentinel value of `u32::max_value()` to `output_ptr` or `address_ptr`.
 	//
-	// After running the constructor it is verified that the contract account holds at
-	// least the subsistence threshold. If that is not the case the instantiation fails and
-	// the contract is not created.
-	//
 	// # Parameters
 	//
 	// - code_hash_ptr: a pointer to the buffer that contains the initializer code.
@@ -865,9 +837,7 @@ define_env!(Env, <E: Ext>,
 	//
 	// `ReturnCode::CalleeReverted`: Output buffer is returned.
 	// `ReturnCode::CalleeTrapped`
-	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
-	// `ReturnCode::NewContractNotFunded`
 	// `ReturnCode::CodeNotFound`
 	[seal0] seal_instantiate(
 		ctx,
@@ -1203,109 +1173,33 @@ define_env!(Env, <E: Ext>,
 	//
 	// # Note
 	//
-	// The tombstone deposit is on top of the existential deposit. So in order for
-	// a contract to leave a tombstone the balance of the contract must not go
-	// below the sum of existential deposit and the tombstone deposit. The sum
-	// is commonly referred as subsistence threshold in code.
+	// Tombstones were removed together with the state rent. This function only exists for
+	// backwards compatibility and always returns zero.
 	[seal0] seal_tombstone_deposit(ctx, out_ptr: u32, out_len_ptr: u32) => {
 		ctx.charge_gas(RuntimeToken::TombstoneDeposit)?;
-		Ok(ctx.write_sandbox_output(
-			out_ptr, out_len_ptr, &ctx.ext.tombstone_deposit().encode(), false, already_charged
-		)?)
+		let deposit = <BalanceOf<E::T>>::zero().encode();
+		Ok(ctx.write_sandbox_output(out_ptr, out_len_ptr, &deposit, false, already_charged)?)
 	},
 
-	// Try to restore the given destination contract sacrificing the caller.
-	//
-	// This function will compute a tombstone hash from the caller's storage and the given code hash
-	// and if the hash matches the hash found in the tombstone at the specified address - kill
-	// the caller contract and restore the destination contract and set the specified `rent_allowance`.
-	// All caller's funds are transferred to the destination.
-	//
-	// The tombstone hash is derived as `hash(code_hash, storage_root_hash)`. In order to match
-	// this hash to its own hash the restorer must make its storage equal to the one of the
-	// evicted destination contract. In order to allow for additional storage items in the
-	// restoring contract a delta can be specified to this function. All keys specified as
-	// delta are disregarded when calculating the storage root hash.
-	//
-	// On success, the destination contract is restored. This function is diverging and
-	// stops execution even on success.
+	// Was used to restore the given destination contract sacrificing the caller.
 	//
-	// - `dest_ptr`, `dest_len` - the pointer and the length of a buffer that encodes `T::AccountId`
-	//    with the address of the to be restored contract.
-	// - `code_hash_ptr`, `code_hash_len` - the pointer and the length of a buffer that encodes
-	//    a code hash of the to be restored contract.
-	// - `rent_allowance_ptr`, `rent_allowance_len` - the pointer and the length of a buffer that
-	//    encodes the rent allowance that must be set in the case of successful restoration.
-	// - `delta_ptr` is the pointer to the start of a buffer that has `delta_count` storage keys
-	//    laid out sequentially.
-	//
-	// # Traps
+	// # Note
 	//
-	// - There is no tombstone at the destination address.
-	// - Tombstone hashes do not match.
-	// - The calling contract is already present on the call stack.
-	// - The supplied code_hash does not exist on-chain.
+	// Tombstones were removed together with the state rent. This function only exists for
+	// backwards compatibility and does nothing.
 	[seal0] seal_restore_to(
 		ctx,
-		dest_ptr: u32,
-		dest_len: u32,
-		code_hash_ptr: u32,
-		code_hash_len: u32,
-		rent_allowance_ptr: u32,
-		rent_allowance_len: u32,
-		delta_ptr: u32,
-		delta_count: u32
+		_dest_ptr: u32,
+		_dest_len: u32,
+		_code_hash_ptr: u32,
+		_code_hash_len: u32,
+		_rent_allowance_ptr: u32,
+		_rent_allowance_len: u32,
+		_delta_ptr: u32,
+		_delta_count: u32
 	) => {
-		ctx.charge_gas(RuntimeToken::RestoreTo(delta_count))?;
-		let dest: <<E as Ext>::T as frame_system::Config>::AccountId =
-			ctx.read_sandbox_memory_as(dest_ptr, dest_len)?;
-		let code_hash: CodeHash<<E as Ext>::T> =
-			ctx.read_sandbox_memory_as(code_hash_ptr, code_hash_len)?;
-		let rent_allowance: BalanceOf<<E as Ext>::T> =
-			ctx.read_sandbox_memory_as(rent_allowance_ptr, rent_allowance_len)?;
-		let delta = {
-			const KEY_SIZE: usize = 32;
-
-			// We can eagerly allocate because we charged for the complete delta count already
-			// We still need to make sure that the allocation isn't larger than the memory
-			// allocator can handle.
-			ensure!(
-				delta_count
-					.saturating_mul(KEY_SIZE as u32) <= ctx.ext.schedule().limits.max_memory_size(),
-				Error::<E::T>::OutOfBounds,
-			);
-			let mut delta = vec![[0; KEY_SIZE]; delta_count as usize];
-			let mut key_ptr = delta_ptr;
-
-			for i in 0..delta_count {
-				// Read the delta into the provided buffer
-				// This cannot panic because of the loop condition
-				ctx.read_sandbox_memory_into_buf(key_ptr, &mut delta[i as usize])?;
-
-				// Offset key_ptr to the next element.
-				key_ptr = key_ptr.checked_add(KEY_SIZE as u32).ok_or(Error::<E::T>::OutOfBounds)?;
-			}
-
-			delta
-		};
-
-		let max_len = <E::T as Config>::MaxCodeSize::get();
-		let charged = ctx.charge_gas(RuntimeToken::RestoreToSurchargeCodeSize {
-			caller_code: max_len,
-			tombstone_code: max_len,
-		})?;
-		let (result, caller_code, tombstone_code) = match ctx.ext.restore_to(
-			dest, code_hash, rent_allowance, delta
-		) {
-			Ok((code, tomb)) => (Ok(()), code, tomb),
-			Err((err, code, tomb)) => (Err(err), code, tomb),
-		};
-		ctx.adjust_gas(charged, RuntimeToken::RestoreToSurchargeCodeSize {
-			caller_code,
-			tombstone_code,
-		});
-		result?;
-		Err(TrapReason::Restoration)
+		ctx.charge_gas(RuntimeToken::RestoreTo)?;
+		Ok(())
 	},
 
 	// Deposit a contract event with the data buffer and optional list of topics. There is a limit
@@ -1373,17 +1267,14 @@ define_env!(Env, <E: Ext>,
 		Ok(())
 	},
 
-	// Set rent allowance of the contract
+	// Was used to set the rent allowance of the contract.
 	//
-	// - value_ptr: a pointer to the buffer with value, how much to allow for rent
-	//   Should be decodable as a `T::Balance`. Traps otherwise.
-	// - value_len: length of the value buffer.
-	[seal0] seal_set_rent_allowance(ctx, value_ptr: u32, value_len: u32) => {
+	// # Note
+	//
+	// The state rent was removed. This function only exists for backwards compatibility
+	// and does nothing.
+	[seal0] seal_set_rent_allowance(ctx, _value_ptr: u32, _value_len: u32) => {
 		ctx.charge_gas(RuntimeToken::SetRentAllowance)?;
-		let value: BalanceOf<<E as Ext>::T> =
-			ctx.read_sandbox_memory_as(value_ptr, value_len)?;
-		ctx.ext.set_rent_allowance(value);
-
 		Ok(())
 	},
 
@@ -1395,11 +1286,15 @@ define_env!(Env, <E: Ext>,
 	// space at `out_ptr` is less than the size of the value a trap is triggered.
 	//
 	// The data is encoded as T::Balance.
+	//
+	// # Note
+	//
+	// The state rent was removed. This function only exists for backwards compatibility
+	// and always returns the maximum balance.
 	[seal0] seal_rent_allowance(ctx, out_ptr: u32, out_len_ptr: u32) => {
 		ctx.charge_gas(RuntimeToken::RentAllowance)?;
-		Ok(ctx.write_sandbox_output(
-			out_ptr, out_len_ptr, &ctx.ext.rent_allowance().encode(), false, already_charged
-		)?)
+		let allowance = <BalanceOf<E::T>>::max_value().encode();
+		Ok(ctx.write_sandbox_output(out_ptr, out_len_ptr, &allowance, false, already_charged)?)
 	},
 
 	// Prints utf8 encoded string from the data buffer.
@@ -1570,13 +1465,11 @@ define_env!(Env, <E: Ext>,
 	//
 	// # Note
 	//
-	// The returned information was collected and cached when the current contract call
-	// started execution. Any change to those values that happens due to actions of the
-	// current call or contracts that are called by this contract are not considered.
+	// The state rent was removed. This function only exists for backwards compatibility
+	// and always returns zeroed out parameters.
 	[seal0] seal_rent_params(ctx, out_ptr: u32, out_len_ptr: u32) => {
 		ctx.charge_gas(RuntimeToken::RentParams)?;
-		Ok(ctx.write_sandbox_output(
-			out_ptr, out_len_ptr, &ctx.ext.rent_params().encode(), false, already_charged
-		)?)
+		let rent_params = crate::exec::RentParams::<E::T>::default().encode();
+		Ok(ctx.write_sandbox_output(out_ptr, out_len_ptr, &rent_params, false, already_charged)?)
 	},
 );
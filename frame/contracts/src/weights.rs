@@ -20,6 +20,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
 //! DATE: 2021-02-18, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weights of `upload_code` and `remove_code` are hand-written placeholders and were not
+//! generated by the benchmark CLI.
 
 // Executed Command:
 // target/release/substrate
@@ -49,10 +52,11 @@ pub trait WeightInfo {
 	fn on_initialize_per_queue_item(q: u32, ) -> Weight;
 	fn instrument(c: u32, ) -> Weight;
 	fn update_schedule() -> Weight;
+	fn upload_code(c: u32, ) -> Weight;
+	fn remove_code() -> Weight;
 	fn instantiate_with_code(c: u32, s: u32, ) -> Weight;
 	fn instantiate(c: u32, s: u32, ) -> Weight;
 	fn call(c: u32, ) -> Weight;
-	fn claim_surcharge(c: u32, ) -> Weight;
 	fn seal_caller(r: u32, ) -> Weight;
 	fn seal_address(r: u32, ) -> Weight;
 	fn seal_gas_left(r: u32, ) -> Weight;
@@ -73,7 +77,6 @@ pub trait WeightInfo {
 	fn seal_terminate(r: u32, ) -> Weight;
 	fn seal_terminate_per_code_kb(c: u32, ) -> Weight;
 	fn seal_restore_to(r: u32, ) -> Weight;
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight;
 	fn seal_random(r: u32, ) -> Weight;
 	fn seal_deposit_event(r: u32, ) -> Weight;
 	fn seal_deposit_event_per_topic_and_kb(t: u32, n: u32, ) -> Weight;
@@ -183,6 +186,19 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Hand-written placeholder until the `upload_code` benchmark is run.
+	fn upload_code(c: u32, ) -> Weight {
+		(41_508_000 as Weight)
+			.saturating_add((124_917_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Hand-written placeholder until the `remove_code` benchmark is run.
+	fn remove_code() -> Weight {
+		(37_125_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 	fn instantiate_with_code(c: u32, s: u32, ) -> Weight {
 		(225_834_000 as Weight)
 			// Standard Error: 144_000
@@ -208,13 +224,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
-	fn claim_surcharge(c: u32, ) -> Weight {
-		(336_867_000 as Weight)
-			// Standard Error: 10_000
-			.saturating_add((5_262_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
-	}
 	fn seal_caller(r: u32, ) -> Weight {
 		(143_935_000 as Weight)
 			// Standard Error: 128_000
@@ -333,25 +342,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 	fn seal_restore_to(r: u32, ) -> Weight {
-		(232_291_000 as Weight)
-			// Standard Error: 301_000
-			.saturating_add((136_379_000 as Weight).saturating_mul(r as Weight))
+		(170_417_000 as Weight)
+			// Standard Error: 434_000
+			.saturating_add((721_511_000 as Weight).saturating_mul(r as Weight))
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(r as Weight)))
-	}
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 162_000
-			.saturating_add((8_619_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 162_000
-			.saturating_add((4_877_000 as Weight).saturating_mul(t as Weight))
-			// Standard Error: 1_433_000
-			.saturating_add((3_762_810_000 as Weight).saturating_mul(d as Weight))
-			.saturating_add(T::DbWeight::get().reads(8 as Weight))
-			.saturating_add(T::DbWeight::get().reads((100 as Weight).saturating_mul(d as Weight)))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn seal_random(r: u32, ) -> Weight {
 		(153_634_000 as Weight)
@@ -809,6 +804,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Hand-written placeholder until the `upload_code` benchmark is run.
+	fn upload_code(c: u32, ) -> Weight {
+		(41_508_000 as Weight)
+			.saturating_add((124_917_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Hand-written placeholder until the `remove_code` benchmark is run.
+	fn remove_code() -> Weight {
+		(37_125_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 	fn instantiate_with_code(c: u32, s: u32, ) -> Weight {
 		(225_834_000 as Weight)
 			// Standard Error: 144_000
@@ -834,13 +842,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
-	fn claim_surcharge(c: u32, ) -> Weight {
-		(336_867_000 as Weight)
-			// Standard Error: 10_000
-			.saturating_add((5_262_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
-	}
 	fn seal_caller(r: u32, ) -> Weight {
 		(143_935_000 as Weight)
 			// Standard Error: 128_000
@@ -959,25 +960,11 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
 	fn seal_restore_to(r: u32, ) -> Weight {
-		(232_291_000 as Weight)
-			// Standard Error: 301_000
-			.saturating_add((136_379_000 as Weight).saturating_mul(r as Weight))
+		(170_417_000 as Weight)
+			// Standard Error: 434_000
+			.saturating_add((721_511_000 as Weight).saturating_mul(r as Weight))
 			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(r as Weight)))
-	}
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 162_000
-			.saturating_add((8_619_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 162_000
-			.saturating_add((4_877_000 as Weight).saturating_mul(t as Weight))
-			// Standard Error: 1_433_000
-			.saturating_add((3_762_810_000 as Weight).saturating_mul(d as Weight))
-			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((100 as Weight).saturating_mul(d as Weight)))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	fn seal_random(r: u32, ) -> Weight {
 		(153_634_000 as Weight)
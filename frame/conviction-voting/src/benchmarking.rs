@@ -0,0 +1,247 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ConvictionVoting pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelist_account, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Pallet as ConvictionVoting;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn account_vote<T: Config>(b: BalanceOf<T>) -> AccountVote<BalanceOf<T>> {
+	let v = Vote { aye: true, conviction: Conviction::Locked1x };
+	AccountVote::Standard { vote: v, balance: b }
+}
+
+/// Create `n` ongoing polls in the class which allows the most of them at once.
+fn create_polls<T: Config>(n: u32) -> (ClassOf<T>, Vec<PollIndexOf<T>>) {
+	let (class, max) = T::Polls::max_ongoing();
+	assert!(n <= max, "not enough polls can be created in a single class");
+	let polls = (0..n)
+		.map(|_| T::Polls::create_ongoing(class.clone()).expect("poll can be created"))
+		.collect();
+	(class, polls)
+}
+
+fn votes_of<T: Config>(who: &T::AccountId, class: &ClassOf<T>) -> usize {
+	match VotingFor::<T>::get(who, class) {
+		Voting::Casting(Casting { votes, .. }) => votes.len(),
+		_ => 0,
+	}
+}
+
+benchmarks! {
+	vote_new {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = create_polls::<T>(T::MaxVotes::get());
+		let (&poll, others) = polls.split_last().expect("at least one poll");
+		// The caller already votes on all but one poll.
+		for &i in others {
+			ConvictionVoting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), i, account_vote)?;
+		}
+		assert_eq!(votes_of::<T>(&caller, &class), others.len());
+	}: vote(RawOrigin::Signed(caller.clone()), poll, account_vote)
+	verify {
+		assert_eq!(votes_of::<T>(&caller, &class), polls.len());
+	}
+
+	vote_existing {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let old_account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = create_polls::<T>(T::MaxVotes::get());
+		for &i in polls.iter() {
+			ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				old_account_vote,
+			)?;
+		}
+		let poll = polls[0];
+		let new_account_vote = account_vote::<T>(200u32.into());
+	}: vote(RawOrigin::Signed(caller.clone()), poll, new_account_vote)
+	verify {
+		assert_eq!(votes_of::<T>(&caller, &class), polls.len());
+		let tally = T::Polls::as_ongoing(poll).expect("poll is still ongoing").0;
+		assert_eq!(tally.ayes, 200u32.into());
+	}
+
+	remove_vote {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let old_account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = create_polls::<T>(T::MaxVotes::get());
+		for &i in polls.iter() {
+			ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				old_account_vote,
+			)?;
+		}
+		let poll = polls[0];
+	}: _(RawOrigin::Signed(caller.clone()), Some(class.clone()), poll)
+	verify {
+		assert_eq!(votes_of::<T>(&caller, &class), polls.len() - 1);
+	}
+
+	remove_other_vote {
+		let caller = funded_account::<T>("caller", 0);
+		let voter = funded_account::<T>("caller", 1);
+		whitelist_account!(caller);
+		let old_account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = create_polls::<T>(T::MaxVotes::get());
+		for &i in polls.iter() {
+			ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(voter.clone()).into(),
+				i,
+				old_account_vote,
+			)?;
+		}
+		// The vote was on the losing side, so it may be removed by anyone.
+		let poll = polls[0];
+		T::Polls::end_ongoing(poll, false).expect("poll can be ended");
+	}: _(RawOrigin::Signed(caller.clone()), voter.clone(), class.clone(), poll)
+	verify {
+		assert_eq!(votes_of::<T>(&voter, &class), polls.len() - 1);
+	}
+
+	delegate {
+		let r in 0 .. T::MaxVotes::get();
+
+		let initial_balance: BalanceOf<T> = 100u32.into();
+		let delegated_balance: BalanceOf<T> = 1000u32.into();
+
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let voter = funded_account::<T>("voter", 0);
+
+		let (class, polls) = create_polls::<T>(r);
+		// The delegate votes on `r` polls, all of whose tallies need updating.
+		for &i in polls.iter() {
+			let account_vote = account_vote::<T>(initial_balance);
+			ConvictionVoting::<T>::vote(RawOrigin::Signed(voter.clone()).into(), i, account_vote)?;
+		}
+		assert_eq!(votes_of::<T>(&voter, &class), r as usize);
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		class.clone(),
+		voter.clone(),
+		Conviction::Locked1x,
+		delegated_balance
+	)
+	verify {
+		assert!(matches!(VotingFor::<T>::get(&caller, &class), Voting::Delegating(_)));
+	}
+
+	undelegate {
+		let r in 0 .. T::MaxVotes::get();
+
+		let initial_balance: BalanceOf<T> = 100u32.into();
+		let delegated_balance: BalanceOf<T> = 1000u32.into();
+
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let voter = funded_account::<T>("voter", 0);
+
+		let (class, polls) = create_polls::<T>(r);
+		ConvictionVoting::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			class.clone(),
+			voter.clone(),
+			Conviction::Locked1x,
+			delegated_balance,
+		)?;
+		for &i in polls.iter() {
+			let account_vote = account_vote::<T>(initial_balance);
+			ConvictionVoting::<T>::vote(RawOrigin::Signed(voter.clone()).into(), i, account_vote)?;
+		}
+		assert_eq!(votes_of::<T>(&voter, &class), r as usize);
+	}: _(RawOrigin::Signed(caller.clone()), class.clone())
+	verify {
+		assert!(matches!(VotingFor::<T>::get(&caller, &class), Voting::Casting(_)));
+	}
+
+	unlock {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let free_balance = T::Currency::free_balance(&caller);
+		let normal_account_vote = account_vote::<T>(free_balance - 100u32.into());
+		let big_account_vote = account_vote::<T>(free_balance);
+
+		// Fill the caller's votes up to the maximum.
+		let (class, polls) = create_polls::<T>(T::MaxVotes::get());
+		assert!(polls.len() > 0, "need at least one poll");
+		for &i in polls.iter() {
+			ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				normal_account_vote,
+			)?;
+		}
+
+		// Vote big on the first poll, then remove the vote once it is over, leaving a lock which
+		// is no longer needed.
+		let poll = polls[0];
+		ConvictionVoting::<T>::vote(
+			RawOrigin::Signed(caller.clone()).into(),
+			poll,
+			big_account_vote,
+		)?;
+		T::Polls::end_ongoing(poll, false).expect("poll can be ended");
+		ConvictionVoting::<T>::remove_vote(
+			RawOrigin::Signed(caller.clone()).into(),
+			Some(class.clone()),
+			poll,
+		)?;
+
+		let polls = &polls[1..];
+		assert_eq!(votes_of::<T>(&caller, &class), polls.len());
+	}: _(RawOrigin::Signed(caller.clone()), class.clone(), caller.clone())
+	verify {
+		let locked = ClassLocksFor::<T>::get(&caller)
+			.into_iter()
+			.map(|x| x.1)
+			.max()
+			.unwrap_or_else(Zero::zero);
+		assert!(locked < T::Currency::free_balance(&caller));
+	}
+}
+
+impl_benchmark_test_suite!(
+	ConvictionVoting,
+	crate::tests::new_test_ext(),
+	crate::tests::Test,
+);
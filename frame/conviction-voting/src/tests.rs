@@ -0,0 +1,517 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use crate as pallet_conviction_voting;
+use super::*;
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	testing::Header,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Voting: pallet_conviction_voting::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 10;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TestPollState {
+	Ongoing(TallyOf<Test>, u8),
+	Completed(u64, bool),
+}
+use TestPollState::*;
+
+parameter_types! {
+	pub static Polls: BTreeMap<u8, TestPollState> = vec![
+		(1, Completed(1, true)),
+		(2, Completed(2, false)),
+		(3, Ongoing(Tally::from_parts(0, 0, 0), 0)),
+	].into_iter().collect();
+}
+
+/// A set of polls whose state is kept in `Polls` and altered directly by the tests.
+pub struct TestPolls;
+impl Polling<TallyOf<Test>> for TestPolls {
+	type Index = u8;
+	type Votes = u64;
+	type Moment = u64;
+	type Class = u8;
+
+	fn classes() -> Vec<u8> {
+		vec![0, 1, 2]
+	}
+
+	fn as_ongoing(index: u8) -> Option<(TallyOf<Test>, Self::Class)> {
+		Polls::get().remove(&index).and_then(|x| {
+			if let TestPollState::Ongoing(t, c) = x {
+				Some((t, c))
+			} else {
+				None
+			}
+		})
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> R,
+	) -> R {
+		let mut polls = Polls::get();
+		let entry = polls.get_mut(&index);
+		let r = match entry {
+			Some(Ongoing(ref mut tally_mut_ref, class)) =>
+				f(PollStatus::Ongoing(tally_mut_ref, *class)),
+			Some(Completed(when, succeeded)) => f(PollStatus::Completed(*when, *succeeded)),
+			None => f(PollStatus::None),
+		};
+		Polls::set(polls);
+		r
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let mut polls = Polls::get();
+		let entry = polls.get_mut(&index);
+		let r = match entry {
+			Some(Ongoing(ref mut tally_mut_ref, class)) =>
+				f(PollStatus::Ongoing(tally_mut_ref, *class)),
+			Some(Completed(when, succeeded)) => f(PollStatus::Completed(*when, *succeeded)),
+			None => f(PollStatus::None),
+		}?;
+		Polls::set(polls);
+		Ok(r)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()> {
+		let mut polls = Polls::get();
+		let i = polls.keys().rev().next().map_or(0, |x| x + 1);
+		polls.insert(i, Ongoing(Tally::from_parts(0, 0, 0), class));
+		Polls::set(polls);
+		Ok(i)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()> {
+		let mut polls = Polls::get();
+		match polls.get(&index) {
+			Some(Ongoing(..)) => {},
+			_ => return Err(()),
+		}
+		let now = frame_system::Pallet::<Test>::block_number();
+		polls.insert(index, Completed(now, approved));
+		Polls::set(polls);
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32) {
+		(0, 100)
+	}
+}
+
+/// The whole issuance of the test chain may be used for voting.
+pub struct TotalIssuance;
+impl Get<u64> for TotalIssuance {
+	fn get() -> u64 {
+		Balances::total_issuance()
+	}
+}
+
+parameter_types! {
+	pub const MaxVotes: u32 = 3;
+	pub const VoteLockingPeriod: u64 = 10;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = pallet_balances::Pallet<Self>;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = ();
+	type MaxTurnout = TotalIssuance;
+	type Polls = TestPolls;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn aye(amount: u64, conviction: u8) -> AccountVote<u64> {
+	let vote = Vote { aye: true, conviction: Conviction::try_from(conviction).unwrap() };
+	AccountVote::Standard { vote, balance: amount }
+}
+
+fn nay(amount: u64, conviction: u8) -> AccountVote<u64> {
+	let vote = Vote { aye: false, conviction: Conviction::try_from(conviction).unwrap() };
+	AccountVote::Standard { vote, balance: amount }
+}
+
+fn split(aye: u64, nay: u64) -> AccountVote<u64> {
+	AccountVote::Split { aye, nay }
+}
+
+fn tally(index: u8) -> TallyOf<Test> {
+	<TestPolls as Polling<TallyOf<Test>>>::as_ongoing(index).expect("No poll").0
+}
+
+fn class(index: u8) -> u8 {
+	<TestPolls as Polling<TallyOf<Test>>>::as_ongoing(index).expect("No poll").1
+}
+
+fn set_polls(polls: Vec<(u8, TestPollState)>) {
+	Polls::set(polls.into_iter().collect());
+}
+
+fn complete_poll(index: u8, approved: bool) {
+	let mut polls = Polls::get();
+	polls.insert(index, Completed(System::block_number(), approved));
+	Polls::set(polls);
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(42), 0);
+		assert_eq!(Balances::total_issuance(), 210);
+	});
+}
+
+#[test]
+fn basic_voting_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(2, 5)));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 2));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(2, 5)));
+		assert_eq!(tally(3), Tally::from_parts(0, 10, 0));
+		assert_eq!(Balances::usable_balance(1), 8);
+
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(5, 1)));
+		assert_eq!(tally(3), Tally::from_parts(5, 0, 5));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(5, 1)));
+		assert_eq!(tally(3), Tally::from_parts(0, 5, 0));
+		assert_eq!(Balances::usable_balance(1), 5);
+
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 0)));
+		assert_eq!(tally(3), Tally::from_parts(1, 0, 10));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(10, 0)));
+		assert_eq!(tally(3), Tally::from_parts(0, 1, 0));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 3));
+		assert_eq!(tally(3), Tally::from_parts(0, 0, 0));
+
+		assert_ok!(Voting::unlock(Origin::signed(1), class(3), 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn split_voting_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, split(10, 0)));
+		assert_eq!(tally(3), Tally::from_parts(1, 0, 10));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, split(5, 5)));
+		assert_eq!(tally(3), Tally::from_parts(0, 0, 5));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 3));
+		assert_eq!(tally(3), Tally::from_parts(0, 0, 0));
+
+		assert_ok!(Voting::unlock(Origin::signed(1), class(3), 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn voting_on_closed_or_unknown_polls_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Voting::vote(Origin::signed(1), 1, aye(10, 0)), Error::<Test>::NotOngoing);
+		assert_noop!(Voting::vote(Origin::signed(1), 2, aye(10, 0)), Error::<Test>::NotOngoing);
+		assert_noop!(Voting::vote(Origin::signed(1), 4, aye(10, 0)), Error::<Test>::NotOngoing);
+		assert_noop!(
+			Voting::vote(Origin::signed(1), 3, aye(11, 0)),
+			Error::<Test>::InsufficientFunds
+		);
+	});
+}
+
+#[test]
+fn voting_is_limited_by_max_votes() {
+	new_test_ext().execute_with(|| {
+		set_polls((0..4).map(|i| (i, Ongoing(Tally::from_parts(0, 0, 0), 0))).collect());
+		for i in 0..3 {
+			assert_ok!(Voting::vote(Origin::signed(1), i, aye(10, 0)));
+		}
+		assert_noop!(
+			Voting::vote(Origin::signed(1), 3, aye(10, 0)),
+			Error::<Test>::MaxVotesReached
+		);
+		// Changing an existing vote is still fine.
+		assert_ok!(Voting::vote(Origin::signed(1), 2, nay(10, 0)));
+	});
+}
+
+#[test]
+fn removing_votes_checks_class_and_voter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 0)));
+		assert_noop!(Voting::remove_vote(Origin::signed(2), None, 3), Error::<Test>::NotVoter);
+		complete_poll(3, true);
+		assert_noop!(Voting::remove_vote(Origin::signed(1), None, 3), Error::<Test>::ClassNeeded);
+		assert_ok!(Voting::remove_vote(Origin::signed(1), Some(0), 3));
+	});
+}
+
+#[test]
+fn successful_but_zero_conviction_vote_balance_can_be_unlocked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 1)));
+		assert_ok!(Voting::vote(Origin::signed(2), 3, nay(20, 0)));
+		complete_poll(3, false);
+		assert_ok!(Voting::remove_vote(Origin::signed(2), Some(0), 3));
+		assert_ok!(Voting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::usable_balance(2), 20);
+	});
+}
+
+#[test]
+fn unsuccessful_conviction_vote_balance_can_be_unlocked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 1)));
+		assert_ok!(Voting::vote(Origin::signed(2), 3, nay(20, 0)));
+		complete_poll(3, false);
+		assert_ok!(Voting::remove_vote(Origin::signed(1), Some(0), 3));
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn successful_conviction_vote_balance_stays_locked_for_correct_time() {
+	new_test_ext().execute_with(|| {
+		for i in 1..=5 {
+			assert_ok!(Voting::vote(Origin::signed(i), 3, aye(10, i as u8)));
+		}
+		complete_poll(3, true);
+		for i in 1..=5 {
+			assert_ok!(Voting::remove_vote(Origin::signed(i), Some(0), 3));
+		}
+		// Conviction `i` locks the balance for `2 ^ (i - 1)` periods after the poll ended.
+		for i in 1..=5 {
+			let unlock_at = 1 + 10 * (1 << (i - 1));
+			System::set_block_number(unlock_at - 1);
+			assert_ok!(Voting::unlock(Origin::signed(i), 0, i));
+			assert_eq!(Balances::usable_balance(i), 10 * i - 10);
+			System::set_block_number(unlock_at);
+			assert_ok!(Voting::unlock(Origin::signed(i), 0, i));
+			assert_eq!(Balances::usable_balance(i), 10 * i);
+		}
+	});
+}
+
+#[test]
+fn remove_other_vote_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 1)));
+		assert_ok!(Voting::vote(Origin::signed(2), 3, nay(20, 1)));
+		assert_noop!(
+			Voting::remove_other_vote(Origin::signed(3), 1, 0, 3),
+			Error::<Test>::NoPermission
+		);
+		complete_poll(3, true);
+
+		// The losing side may be removed by anyone right away.
+		assert_ok!(Voting::remove_other_vote(Origin::signed(3), 2, 0, 3));
+		// The winning side only after its conviction lock period.
+		assert_noop!(
+			Voting::remove_other_vote(Origin::signed(3), 1, 0, 3),
+			Error::<Test>::NoPermissionYet
+		);
+		System::set_block_number(11);
+		assert_ok!(Voting::remove_other_vote(Origin::signed(3), 1, 0, 3));
+		assert_ok!(Voting::unlock(Origin::signed(3), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn classwise_delegation_works() {
+	new_test_ext().execute_with(|| {
+		set_polls(vec![
+			(0, Ongoing(Tally::from_parts(0, 0, 0), 0)),
+			(1, Ongoing(Tally::from_parts(0, 0, 0), 1)),
+		]);
+		assert_ok!(Voting::delegate(Origin::signed(1), 0, 2, Conviction::Locked1x, 10));
+		assert_ok!(Voting::delegate(Origin::signed(3), 1, 2, Conviction::None, 30));
+
+		assert_ok!(Voting::vote(Origin::signed(2), 0, aye(20, 0)));
+		assert_ok!(Voting::vote(Origin::signed(2), 1, nay(20, 0)));
+		assert_eq!(tally(0), Tally::from_parts(12, 0, 30));
+		assert_eq!(tally(1), Tally::from_parts(0, 5, 0));
+
+		// Delegations follow the delegate's change of vote.
+		assert_ok!(Voting::vote(Origin::signed(2), 0, nay(20, 0)));
+		assert_eq!(tally(0), Tally::from_parts(0, 12, 0));
+
+		assert_ok!(Voting::undelegate(Origin::signed(1), 0));
+		assert_eq!(tally(0), Tally::from_parts(0, 2, 0));
+		assert_eq!(tally(1), Tally::from_parts(0, 5, 0));
+
+		// Delegations made after the delegate voted are counted too.
+		assert_ok!(Voting::delegate(Origin::signed(1), 0, 2, Conviction::Locked2x, 5));
+		assert_eq!(tally(0), Tally::from_parts(0, 12, 0));
+	});
+}
+
+#[test]
+fn delegation_errors_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 0, 1, Conviction::None, 10),
+			Error::<Test>::Nonsense
+		);
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 3, 2, Conviction::None, 10),
+			Error::<Test>::BadClass
+		);
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 0, 2, Conviction::None, 11),
+			Error::<Test>::InsufficientFunds
+		);
+		assert_noop!(Voting::undelegate(Origin::signed(1), 0), Error::<Test>::NotDelegating);
+
+		assert_ok!(Voting::delegate(Origin::signed(1), 0, 2, Conviction::None, 10));
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 0, 3, Conviction::None, 10),
+			Error::<Test>::AlreadyDelegating
+		);
+		assert_noop!(
+			Voting::vote(Origin::signed(1), 3, aye(10, 0)),
+			Error::<Test>::AlreadyDelegating
+		);
+
+		assert_ok!(Voting::vote(Origin::signed(3), 3, aye(10, 0)));
+		assert_noop!(
+			Voting::delegate(Origin::signed(3), 0, 2, Conviction::None, 10),
+			Error::<Test>::AlreadyVoting
+		);
+		// Delegating in another class is fine.
+		assert_ok!(Voting::delegate(Origin::signed(3), 1, 2, Conviction::None, 10));
+	});
+}
+
+#[test]
+fn undelegation_keeps_lock_for_conviction_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::delegate(Origin::signed(1), 0, 2, Conviction::Locked2x, 10));
+		assert_eq!(Balances::usable_balance(1), 0);
+		assert_ok!(Voting::undelegate(Origin::signed(1), 0));
+
+		// Locked for two periods from the point of undelegating.
+		System::set_block_number(20);
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 0);
+		System::set_block_number(21);
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn locks_are_the_maximum_across_classes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::delegate(Origin::signed(2), 0, 1, Conviction::None, 5));
+		assert_ok!(Voting::delegate(Origin::signed(2), 1, 1, Conviction::None, 15));
+		assert_eq!(Balances::usable_balance(2), 5);
+
+		assert_ok!(Voting::undelegate(Origin::signed(2), 1));
+		assert_ok!(Voting::unlock(Origin::signed(2), 1, 2));
+		assert_eq!(Balances::usable_balance(2), 15);
+		assert_ok!(Voting::undelegate(Origin::signed(2), 0));
+		assert_ok!(Voting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::usable_balance(2), 20);
+	});
+}
@@ -26,14 +26,13 @@ use frame_support::{
         schedule::DispatchTime},
 };
 use frame_system::{RawOrigin, Pallet as System, self, EventRecord};
-use sp_runtime::traits::{Bounded, One};
+use sp_runtime::traits::Bounded;
 
 use crate::Pallet as Democracy;
 
 const SEED: u32 = 0;
 const MAX_REFERENDUMS: u32 = 99;
 const MAX_SECONDERS: u32 = 100;
-const MAX_BYTES: u32 = 16_384;
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
 	let events = System::<T>::events();
@@ -49,27 +48,31 @@ fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
 	caller
 }
 
-fn add_proposal<T: Config>(n: u32) -> Result<T::Hash, &'static str> {
+fn make_proposal<T: Config>(n: u32) -> BoundedCallOf<T> {
+	let call: T::Proposal = Call::<T>::cancel_queued(n).into();
+	T::Preimages::bound(call).expect("small calls are kept inline; qed")
+}
+
+fn add_proposal<T: Config>(n: u32) -> Result<BoundedCallOf<T>, &'static str> {
 	let other = funded_account::<T>("proposer", n);
 	let value = T::MinimumDeposit::get();
-	let proposal_hash: T::Hash = T::Hashing::hash_of(&n);
+	let proposal = make_proposal::<T>(n);
 
 	Democracy::<T>::propose(
 		RawOrigin::Signed(other).into(),
-		proposal_hash,
+		proposal.clone(),
 		value.into(),
 	)?;
 
-	Ok(proposal_hash)
+	Ok(proposal)
 }
 
 fn add_referendum<T: Config>(n: u32) -> Result<ReferendumIndex, &'static str> {
-	let proposal_hash: T::Hash = T::Hashing::hash_of(&n);
 	let vote_threshold = VoteThreshold::SimpleMajority;
 
 	Democracy::<T>::inject_referendum(
 		T::LaunchPeriod::get(),
-		proposal_hash,
+		make_proposal::<T>(n),
 		vote_threshold,
 		0u32.into(),
 	);
@@ -80,7 +83,7 @@ fn add_referendum<T: Config>(n: u32) -> Result<ReferendumIndex, &'static str> {
 		None,
 		63,
 		system::RawOrigin::Root.into(),
-		Call::enact_proposal(proposal_hash, referendum_index).into(),
+		Call::cancel_queued(n).into(),
 	).map_err(|_| "failed to schedule named")?;
 	Ok(referendum_index)
 }
@@ -106,10 +109,10 @@ benchmarks! {
 		}
 
 		let caller = funded_account::<T>("caller", 0);
-		let proposal_hash: T::Hash = T::Hashing::hash_of(&0);
+		let proposal = make_proposal::<T>(0);
 		let value = T::MinimumDeposit::get();
 		whitelist_account!(caller);
-	}: _(RawOrigin::Signed(caller), proposal_hash, value.into())
+	}: _(RawOrigin::Signed(caller), proposal, value.into())
 	verify {
 		assert_eq!(Democracy::<T>::public_props().len(), p as usize, "Proposals not created.");
 	}
@@ -118,7 +121,7 @@ benchmarks! {
 		let s in 0 .. MAX_SECONDERS;
 
 		let caller = funded_account::<T>("caller", 0);
-		let proposal_hash = add_proposal::<T>(s)?;
+		add_proposal::<T>(s)?;
 
 		// Create s existing "seconds"
 		for i in 0 .. s {
@@ -227,16 +230,16 @@ benchmarks! {
 		// We should really add a lot of seconds here, but we're not doing it elsewhere.
 
 		// Place our proposal in the external queue, too.
-		let hash = T::Hashing::hash_of(&0);
+		let proposal = make_proposal::<T>(0);
 		assert_ok!(
-            Democracy::<T>::external_propose(T::ExternalOrigin::successful_origin(), hash.clone())
+            Democracy::<T>::external_propose(T::ExternalOrigin::successful_origin(), proposal.clone())
         );
 
 		// Add a referendum of our proposal.
 		let referendum_index = add_referendum::<T>(0)?;
 		assert_ok!(Democracy::<T>::referendum_status(referendum_index));
 
-		let call = Call::<T>::blacklist(hash, Some(referendum_index));
+		let call = Call::<T>::blacklist(proposal.hash(), Some(referendum_index));
 		let origin = T::BlacklistOrigin::successful_origin();
 	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
@@ -252,14 +255,14 @@ benchmarks! {
 		let v in 1 .. MAX_VETOERS as u32;
 
 		let origin = T::ExternalOrigin::successful_origin();
-		let proposal_hash = T::Hashing::hash_of(&0);
+		let proposal = make_proposal::<T>(0);
 		// Add proposal to blacklist with block number 0
 		Blacklist::<T>::insert(
-			proposal_hash,
+			proposal.hash(),
 			(T::BlockNumber::zero(), vec![T::AccountId::default(); v as usize])
 		);
 
-		let call = Call::<T>::external_propose(proposal_hash);
+		let call = Call::<T>::external_propose(proposal);
 	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
 		// External proposal created
@@ -268,8 +271,8 @@ benchmarks! {
 
 	external_propose_majority {
 		let origin = T::ExternalMajorityOrigin::successful_origin();
-		let proposal_hash = T::Hashing::hash_of(&0);
-		let call = Call::<T>::external_propose_majority(proposal_hash);
+		let proposal = make_proposal::<T>(0);
+		let call = Call::<T>::external_propose_majority(proposal);
 	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
 		// External proposal created
@@ -278,8 +281,8 @@ benchmarks! {
 
 	external_propose_default {
 		let origin = T::ExternalDefaultOrigin::successful_origin();
-		let proposal_hash = T::Hashing::hash_of(&0);
-		let call = Call::<T>::external_propose_default(proposal_hash);
+		let proposal = make_proposal::<T>(0);
+		let call = Call::<T>::external_propose_default(proposal);
 	}: { call.dispatch_bypass_filter(origin)? }
 	verify {
 		// External proposal created
@@ -288,8 +291,9 @@ benchmarks! {
 
 	fast_track {
 		let origin_propose = T::ExternalDefaultOrigin::successful_origin();
-		let proposal_hash: T::Hash = T::Hashing::hash_of(&0);
-		Democracy::<T>::external_propose_default(origin_propose, proposal_hash.clone())?;
+		let proposal = make_proposal::<T>(0);
+		let proposal_hash = proposal.hash();
+		Democracy::<T>::external_propose_default(origin_propose, proposal)?;
 
 		// NOTE: Instant origin may invoke a little bit more logic, but may not always succeed.
 		let origin_fast_track = T::FastTrackOrigin::successful_origin();
@@ -306,10 +310,11 @@ benchmarks! {
 		// Existing veto-ers
 		let v in 0 .. MAX_VETOERS as u32;
 
-		let proposal_hash: T::Hash = T::Hashing::hash_of(&v);
+		let proposal = make_proposal::<T>(v);
+		let proposal_hash = proposal.hash();
 
 		let origin_propose = T::ExternalDefaultOrigin::successful_origin();
-		Democracy::<T>::external_propose_default(origin_propose, proposal_hash.clone())?;
+		Democracy::<T>::external_propose_default(origin_propose, proposal)?;
 
 		let mut vetoers: Vec<T::AccountId> = Vec::new();
 		for i in 0 .. v {
@@ -367,8 +372,8 @@ benchmarks! {
 		LastTabledWasExternal::put(false);
 
 		let origin = T::ExternalMajorityOrigin::successful_origin();
-		let proposal_hash = T::Hashing::hash_of(&r);
-		let call = Call::<T>::external_propose_majority(proposal_hash);
+		let proposal = make_proposal::<T>(r);
+		let call = Call::<T>::external_propose_majority(proposal);
 		call.dispatch_bypass_filter(origin)?;
 		// External proposal created
 		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
@@ -558,68 +563,6 @@ benchmarks! {
 
 	}: _(RawOrigin::Root)
 
-	note_preimage {
-		// Num of bytes in encoded proposal
-		let b in 0 .. MAX_BYTES;
-
-		let caller = funded_account::<T>("caller", 0);
-		let encoded_proposal = vec![1; b as usize];
-		whitelist_account!(caller);
-	}: _(RawOrigin::Signed(caller), encoded_proposal.clone())
-	verify {
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		match Preimages::<T>::get(proposal_hash) {
-			Some(PreimageStatus::Available { .. }) => (),
-			_ => return Err("preimage not available")
-		}
-	}
-
-	note_imminent_preimage {
-		// Num of bytes in encoded proposal
-		let b in 0 .. MAX_BYTES;
-
-		// d + 1 to include the one we are testing
-		let encoded_proposal = vec![1; b as usize];
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		let block_number = T::BlockNumber::one();
-		Preimages::<T>::insert(&proposal_hash, PreimageStatus::Missing(block_number));
-
-		let caller = funded_account::<T>("caller", 0);
-		let encoded_proposal = vec![1; b as usize];
-		whitelist_account!(caller);
-	}: _(RawOrigin::Signed(caller), encoded_proposal.clone())
-	verify {
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		match Preimages::<T>::get(proposal_hash) {
-			Some(PreimageStatus::Available { .. }) => (),
-			_ => return Err("preimage not available")
-		}
-	}
-
-	reap_preimage {
-		// Num of bytes in encoded proposal
-		let b in 0 .. MAX_BYTES;
-
-		let encoded_proposal = vec![1; b as usize];
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-
-		let submitter = funded_account::<T>("submitter", b);
-		Democracy::<T>::note_preimage(RawOrigin::Signed(submitter.clone()).into(), encoded_proposal.clone())?;
-
-		// We need to set this otherwise we get `Early` error.
-		let block_number = T::VotingPeriod::get() + T::EnactmentPeriod::get() + T::BlockNumber::one();
-		System::<T>::set_block_number(block_number.into());
-
-		assert!(Preimages::<T>::contains_key(proposal_hash));
-
-		let caller = funded_account::<T>("caller", 0);
-		whitelist_account!(caller);
-	}: _(RawOrigin::Signed(caller), proposal_hash.clone(), u32::max_value())
-	verify {
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		assert!(!Preimages::<T>::contains_key(proposal_hash));
-	}
-
 	// Test when unlock will remove locks
 	unlock_remove {
 		let r in 1 .. MAX_REFERENDUMS;
@@ -744,50 +687,6 @@ benchmarks! {
 		};
 		assert_eq!(votes.len(), (r - 1) as usize, "Vote was not removed");
 	}
-
-	#[extra]
-	enact_proposal_execute {
-		// Num of bytes in encoded proposal
-		let b in 0 .. MAX_BYTES;
-
-		let proposer = funded_account::<T>("proposer", 0);
-		let raw_call = Call::note_preimage(vec![1; b as usize]);
-		let generic_call: T::Proposal = raw_call.into();
-		let encoded_proposal = generic_call.encode();
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		Democracy::<T>::note_preimage(RawOrigin::Signed(proposer).into(), encoded_proposal)?;
-
-		match Preimages::<T>::get(proposal_hash) {
-			Some(PreimageStatus::Available { .. }) => (),
-			_ => return Err("preimage not available")
-		}
-	}: enact_proposal(RawOrigin::Root, proposal_hash, 0)
-	verify {
-		// Fails due to mismatched origin
-		assert_last_event::<T>(RawEvent::Executed(0, false).into());
-	}
-
-	#[extra]
-	enact_proposal_slash {
-		// Num of bytes in encoded proposal
-		let b in 0 .. MAX_BYTES;
-
-		let proposer = funded_account::<T>("proposer", 0);
-		// Random invalid bytes
-		let encoded_proposal = vec![200; b as usize];
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		Democracy::<T>::note_preimage(RawOrigin::Signed(proposer).into(), encoded_proposal)?;
-
-		match Preimages::<T>::get(proposal_hash) {
-			Some(PreimageStatus::Available { .. }) => (),
-			_ => return Err("preimage not available")
-		}
-	}: {
-		assert_eq!(
-			Democracy::<T>::enact_proposal(RawOrigin::Root.into(), proposal_hash, 0),
-			Err(Error::<T>::PreimageInvalid.into())
-		);
-	}
 }
 
 
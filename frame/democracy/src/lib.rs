@@ -78,7 +78,7 @@
 //! a signed extrinsic.
 //!
 //! Basic actions:
-//! - `propose` - Submits a sensitive action, represented as a hash. Requires a deposit.
+//! - `propose` - Submits a sensitive action, represented as a bounded call. Requires a deposit.
 //! - `second` - Signals agreement with a proposal, moves it higher on the proposal queue, and
 //!   requires a matching deposit to the original.
 //! - `vote` - Votes in a referendum, either the vote is "Aye" to enact the proposal or "Nay" to
@@ -91,16 +91,10 @@
 //! - `reap_vote` - Remove some account's expired votes.
 //! - `unlock` - Redetermine the account's balance lock, potentially making tokens available.
 //!
-//! Preimage actions:
-//! - `note_preimage` - Registers the preimage for an upcoming proposal, requires
-//!   a deposit that is returned once the proposal is enacted.
-//! - `note_preimage_operational` - same but provided by `T::OperationalPreimageOrigin`.
-//! - `note_imminent_preimage` - Registers the preimage for an upcoming proposal.
-//!   Does not require a deposit, but the proposal must be in the dispatch queue.
-//! - `note_imminent_preimage_operational` - same but provided by `T::OperationalPreimageOrigin`.
-//! - `reap_preimage` - Removes the preimage for an expired proposal. Will only
-//!   work under the condition that it's the same account that noted it and
-//!   after the voting period, OR it's a different account after the enactment period.
+//! Proposals which are too large to be kept inline are referred to by hash, and their preimages
+//! are provided through `T::Preimages` (usually the preimage pallet). The pallet requests the
+//! preimage of every proposal it keeps track of, so that it may be noted without a deposit. The
+//! preimage only has to be available by the time the proposal is enacted.
 //!
 //! #### Cancellation Origin
 //!
@@ -155,15 +149,16 @@
 use sp_std::prelude::*;
 use sp_runtime::{
 	DispatchResult, DispatchError, RuntimeDebug,
-	traits::{Zero, Hash, Dispatchable, Saturating, Bounded},
+	traits::{Zero, Dispatchable, Saturating, Bounded as _},
 };
-use codec::{Encode, Decode, Input};
+use codec::{Encode, Decode};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
-	weights::{Weight, DispatchClass, Pays},
+	weights::{Weight, DispatchClass},
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReasons, LockIdentifier, Get,
-		OnUnbalanced, BalanceStatus, schedule::{Named as ScheduleNamed, DispatchTime}, EnsureOrigin
+		OnUnbalanced, schedule::{Named as ScheduleNamed, DispatchTime}, EnsureOrigin,
+		preimages::{Bounded, Hash as PreimageHash, QueryPreimage, StorePreimage},
 	},
 	dispatch::DispatchResultWithPostInfo,
 };
@@ -173,6 +168,7 @@ mod vote_threshold;
 mod vote;
 mod conviction;
 mod types;
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 pub use vote_threshold::{Approved, VoteThreshold};
@@ -202,6 +198,8 @@ pub type ReferendumIndex = u32;
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+/// A proposal, kept inline or referred to by the hash of its preimage.
+pub type BoundedCallOf<T> = Bounded<<T as Config>::Proposal>;
 
 pub trait Config: frame_system::Config + Sized {
 	type Proposal: Parameter + Dispatchable<Origin=Self::Origin> + From<Call<Self>>;
@@ -277,13 +275,10 @@ pub trait Config: frame_system::Config + Sized {
 	/// Period in blocks where an external proposal may not be re-submitted after being vetoed.
 	type CooloffPeriod: Get<Self::BlockNumber>;
 
-	/// The amount of balance that must be deposited per byte of preimage stored.
-	type PreimageByteDeposit: Get<BalanceOf<Self>>;
+	/// The preimage provider through which the preimages of proposals are looked up.
+	type Preimages: QueryPreimage + StorePreimage;
 
-	/// An origin that can provide a preimage using operational extrinsics.
-	type OperationalPreimageOrigin: EnsureOrigin<Self::Origin, Success=Self::AccountId>;
-
-	/// Handler for the unbalanced reduction when slashing a preimage deposit.
+	/// Handler for the unbalanced reduction when slashing a proposal deposit.
 	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
 	/// The Scheduler.
@@ -305,36 +300,13 @@ pub trait Config: frame_system::Config + Sized {
 	type MaxProposals: Get<u32>;
 }
 
-#[derive(Clone, Encode, Decode, RuntimeDebug)]
-pub enum PreimageStatus<AccountId, Balance, BlockNumber> {
-	/// The preimage is imminently needed at the argument.
-	Missing(BlockNumber),
-	/// The preimage is available.
-	Available {
-		data: Vec<u8>,
-		provider: AccountId,
-		deposit: Balance,
-		since: BlockNumber,
-		/// None if it's not imminent.
-		expiry: Option<BlockNumber>,
-	},
-}
-
-impl<AccountId, Balance, BlockNumber> PreimageStatus<AccountId, Balance, BlockNumber> {
-	fn to_missing_expiry(self) -> Option<BlockNumber> {
-		match self {
-			PreimageStatus::Missing(expiry) => Some(expiry),
-			_ => None,
-		}
-	}
-}
-
 // A value placed in storage that represents the current version of the Democracy storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
 // storage migration logic.
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
 enum Releases {
 	V1,
+	V2,
 }
 
 decl_storage! {
@@ -343,22 +315,14 @@ decl_storage! {
 		// https://github.com/paritytech/substrate/issues/5322
 		/// The number of (public) proposals that have been made so far.
 		pub PublicPropCount get(fn public_prop_count) build(|_| 0 as PropIndex) : PropIndex;
-		/// The public proposals. Unsorted. The second item is the proposal's hash.
-		pub PublicProps get(fn public_props): Vec<(PropIndex, T::Hash, T::AccountId)>;
+		/// The public proposals. Unsorted. The second item is the proposal.
+		pub PublicProps get(fn public_props): Vec<(PropIndex, BoundedCallOf<T>, T::AccountId)>;
 		/// Those who have locked a deposit.
 		///
 		/// TWOX-NOTE: Safe, as increasing integer keys are safe.
 		pub DepositOf get(fn deposit_of):
 			map hasher(twox_64_concat) PropIndex => Option<(Vec<T::AccountId>, BalanceOf<T>)>;
 
-		/// Map of hashes to the proposal preimage, along with who registered it and their deposit.
-		/// The block number is the block at which it was deposited.
-		// TODO: Refactor Preimages into its own pallet.
-		// https://github.com/paritytech/substrate/issues/5322
-		pub Preimages:
-			map hasher(identity) T::Hash
-			=> Option<PreimageStatus<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
-
 		/// The next free referendum index, aka the number of referenda started so far.
 		pub ReferendumCount get(fn referendum_count) build(|_| 0 as ReferendumIndex): ReferendumIndex;
 		/// The lowest referendum index representing an unbaked referendum. Equal to
@@ -370,7 +334,7 @@ decl_storage! {
 		/// TWOX-NOTE: SAFE as indexes are not under an attacker’s control.
 		pub ReferendumInfoOf get(fn referendum_info):
 			map hasher(twox_64_concat) ReferendumIndex
-			=> Option<ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>>;
+			=> Option<ReferendumInfo<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>>;
 
 		/// All votes for a particular voter. We store the balance for the number of votes that we
 		/// have recorded. The second item is the total amount of delegations, that will be added.
@@ -394,19 +358,20 @@ decl_storage! {
 		/// This happens when a referendum needs to be tabled and one of two conditions are met:
 		/// - `LastTabledWasExternal` is `false`; or
 		/// - `PublicProps` is empty.
-		pub NextExternal: Option<(T::Hash, VoteThreshold)>;
+		pub NextExternal: Option<(BoundedCallOf<T>, VoteThreshold)>;
 
 		/// A record of who vetoed what. Maps proposal hash to a possible existent block number
 		/// (until when it may not be resubmitted) and who vetoed it.
-		pub Blacklist: map hasher(identity) T::Hash => Option<(T::BlockNumber, Vec<T::AccountId>)>;
+		pub Blacklist:
+			map hasher(identity) PreimageHash => Option<(T::BlockNumber, Vec<T::AccountId>)>;
 
 		/// Record of all proposals that have been subject to emergency cancellation.
-		pub Cancellations: map hasher(identity) T::Hash => bool;
+		pub Cancellations: map hasher(identity) PreimageHash => bool;
 
 		/// Storage version of the pallet.
 		///
 		/// New networks start with last version.
-		StorageVersion build(|_| Some(Releases::V1)): Option<Releases>;
+		StorageVersion build(|_| Some(Releases::V2)): Option<Releases>;
 	}
 }
 
@@ -414,7 +379,6 @@ decl_event! {
 	pub enum Event<T> where
 		Balance = BalanceOf<T>,
 		<T as frame_system::Config>::AccountId,
-		<T as frame_system::Config>::Hash,
 		<T as frame_system::Config>::BlockNumber,
 	{
 		/// A motion has been proposed by a public account. \[proposal_index, deposit\]
@@ -438,25 +402,17 @@ decl_event! {
 		/// An \[account\] has cancelled a previous delegation operation.
 		Undelegated(AccountId),
 		/// An external proposal has been vetoed. \[who, proposal_hash, until\]
-		Vetoed(AccountId, Hash, BlockNumber),
-		/// A proposal's preimage was noted, and the deposit taken. \[proposal_hash, who, deposit\]
-		PreimageNoted(Hash, AccountId, Balance),
-		/// A proposal preimage was removed and used (the deposit was returned).
-		/// \[proposal_hash, provider, deposit\]
-		PreimageUsed(Hash, AccountId, Balance),
+		Vetoed(AccountId, PreimageHash, BlockNumber),
 		/// A proposal could not be executed because its preimage was invalid.
 		/// \[proposal_hash, ref_index\]
-		PreimageInvalid(Hash, ReferendumIndex),
+		PreimageInvalid(PreimageHash, ReferendumIndex),
 		/// A proposal could not be executed because its preimage was missing.
 		/// \[proposal_hash, ref_index\]
-		PreimageMissing(Hash, ReferendumIndex),
-		/// A registered preimage was removed and the deposit collected by the reaper.
-		/// \[proposal_hash, provider, deposit, reaper\]
-		PreimageReaped(Hash, AccountId, Balance, AccountId),
+		PreimageMissing(PreimageHash, ReferendumIndex),
 		/// An \[account\] has been unlocked successfully.
 		Unlocked(AccountId),
 		/// A proposal \[hash\] has been blacklisted permanently.
-		Blacklisted(Hash),
+		Blacklisted(PreimageHash),
 	}
 }
 
@@ -484,14 +440,6 @@ decl_error! {
 		AlreadyVetoed,
 		/// Not delegated
 		NotDelegated,
-		/// Preimage already noted
-		DuplicatePreimage,
-		/// Not imminent
-		NotImminent,
-		/// Too early
-		TooEarly,
-		/// Imminent
-		Imminent,
 		/// Preimage not found
 		PreimageMissing,
 		/// Vote given for invalid referendum
@@ -562,9 +510,6 @@ decl_module! {
 		/// Period in blocks where an external proposal may not be re-submitted after being vetoed.
 		const CooloffPeriod: T::BlockNumber = T::CooloffPeriod::get();
 
-		/// The amount of balance that must be deposited per byte of preimage stored.
-		const PreimageByteDeposit: BalanceOf<T> = T::PreimageByteDeposit::get();
-
 		/// The maximum number of votes for an account.
 		const MaxVotes: u32 = T::MaxVotes::get();
 
@@ -575,7 +520,7 @@ decl_module! {
 		/// The dispatch origin of this call must be _Signed_ and the sender must
 		/// have funds to cover the deposit.
 		///
-		/// - `proposal_hash`: The hash of the proposal preimage.
+		/// - `proposal`: The proposal.
 		/// - `value`: The amount of deposit (must be at least `MinimumDeposit`).
 		///
 		/// Emits `Proposed`.
@@ -583,7 +528,7 @@ decl_module! {
 		/// Weight: `O(p)`
 		#[weight = T::WeightInfo::propose()]
 		fn propose(origin,
-			proposal_hash: T::Hash,
+			proposal: BoundedCallOf<T>,
 			#[compact] value: BalanceOf<T>,
 		) {
 			let who = ensure_signed(origin)?;
@@ -594,7 +539,7 @@ decl_module! {
 			let max_proposals = T::MaxProposals::get();
 			ensure!(real_prop_count < max_proposals, Error::<T>::TooManyProposals);
 
-			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+			if let Some((until, _)) = <Blacklist<T>>::get(proposal.hash()) {
 				ensure!(
 					<frame_system::Pallet<T>>::block_number() >= until,
 					Error::<T>::ProposalBlacklisted,
@@ -605,7 +550,8 @@ decl_module! {
 			PublicPropCount::put(index + 1);
 			<DepositOf<T>>::insert(index, (&[&who][..], value));
 
-			<PublicProps<T>>::append((index, proposal_hash, who));
+			T::Preimages::hold(&proposal);
+			<PublicProps<T>>::append((index, proposal, who));
 
 			Self::deposit_event(RawEvent::Proposed(index, value));
 		}
@@ -666,7 +612,7 @@ decl_module! {
 			T::CancellationOrigin::ensure_origin(origin)?;
 
 			let status = Self::referendum_status(ref_index)?;
-			let h = status.proposal_hash;
+			let h = status.proposal.hash();
 			ensure!(!<Cancellations<T>>::contains_key(h), Error::<T>::AlreadyCanceled);
 
 			<Cancellations<T>>::insert(h, true);
@@ -678,21 +624,21 @@ decl_module! {
 		///
 		/// The dispatch origin of this call must be `ExternalOrigin`.
 		///
-		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `proposal`: The proposal.
 		///
 		/// Weight: `O(V)` with V number of vetoers in the blacklist of proposal.
 		///   Decoding vec of length V. Charged as maximum
 		#[weight = T::WeightInfo::external_propose(MAX_VETOERS)]
-		fn external_propose(origin, proposal_hash: T::Hash) {
+		fn external_propose(origin, proposal: BoundedCallOf<T>) {
 			T::ExternalOrigin::ensure_origin(origin)?;
 			ensure!(!<NextExternal<T>>::exists(), Error::<T>::DuplicateProposal);
-			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+			if let Some((until, _)) = <Blacklist<T>>::get(proposal.hash()) {
 				ensure!(
 					<frame_system::Pallet<T>>::block_number() >= until,
 					Error::<T>::ProposalBlacklisted,
 				);
 			}
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityApprove));
+			Self::set_next_external(proposal, VoteThreshold::SuperMajorityApprove);
 		}
 
 		/// Schedule a majority-carries referendum to be tabled next once it is legal to schedule
@@ -700,16 +646,16 @@ decl_module! {
 		///
 		/// The dispatch of this call must be `ExternalMajorityOrigin`.
 		///
-		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `proposal`: The proposal.
 		///
 		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
 		/// pre-scheduled `external_propose` call.
 		///
 		/// Weight: `O(1)`
 		#[weight = T::WeightInfo::external_propose_majority()]
-		fn external_propose_majority(origin, proposal_hash: T::Hash) {
+		fn external_propose_majority(origin, proposal: BoundedCallOf<T>) {
 			T::ExternalMajorityOrigin::ensure_origin(origin)?;
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SimpleMajority));
+			Self::set_next_external(proposal, VoteThreshold::SimpleMajority);
 		}
 
 		/// Schedule a negative-turnout-bias referendum to be tabled next once it is legal to
@@ -717,16 +663,16 @@ decl_module! {
 		///
 		/// The dispatch of this call must be `ExternalDefaultOrigin`.
 		///
-		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `proposal`: The proposal.
 		///
 		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
 		/// pre-scheduled `external_propose` call.
 		///
 		/// Weight: `O(1)`
 		#[weight = T::WeightInfo::external_propose_default()]
-		fn external_propose_default(origin, proposal_hash: T::Hash) {
+		fn external_propose_default(origin, proposal: BoundedCallOf<T>) {
 			T::ExternalDefaultOrigin::ensure_origin(origin)?;
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityAgainst));
+			Self::set_next_external(proposal, VoteThreshold::SuperMajorityAgainst);
 		}
 
 		/// Schedule the currently externally-proposed majority-carries referendum to be tabled
@@ -746,7 +692,7 @@ decl_module! {
 		/// Weight: `O(1)`
 		#[weight = T::WeightInfo::fast_track()]
 		fn fast_track(origin,
-			proposal_hash: T::Hash,
+			proposal_hash: PreimageHash,
 			voting_period: T::BlockNumber,
 			delay: T::BlockNumber,
 		) {
@@ -767,17 +713,17 @@ decl_module! {
 				ensure!(T::InstantAllowed::get(), Error::<T>::InstantNotAllowed);
 			}
 
-			let (e_proposal_hash, threshold) = <NextExternal<T>>::get()
+			let (ext_proposal, threshold) = <NextExternal<T>>::get()
 				.ok_or(Error::<T>::ProposalMissing)?;
 			ensure!(
 				threshold != VoteThreshold::SuperMajorityApprove,
 				Error::<T>::NotSimpleMajority,
 			);
-			ensure!(proposal_hash == e_proposal_hash, Error::<T>::InvalidHash);
+			ensure!(proposal_hash == ext_proposal.hash(), Error::<T>::InvalidHash);
 
 			<NextExternal<T>>::kill();
 			let now = <frame_system::Pallet<T>>::block_number();
-			Self::inject_referendum(now + voting_period, proposal_hash, threshold, delay);
+			Self::inject_referendum(now + voting_period, ext_proposal, threshold, delay);
 		}
 
 		/// Veto and blacklist the external proposal hash.
@@ -790,14 +736,11 @@ decl_module! {
 		///
 		/// Weight: `O(V + log(V))` where V is number of `existing vetoers`
 		#[weight = T::WeightInfo::veto_external(MAX_VETOERS)]
-		fn veto_external(origin, proposal_hash: T::Hash) {
+		fn veto_external(origin, proposal_hash: PreimageHash) {
 			let who = T::VetoOrigin::ensure_origin(origin)?;
 
-			if let Some((e_proposal_hash, _)) = <NextExternal<T>>::get() {
-				ensure!(proposal_hash == e_proposal_hash, Error::<T>::ProposalMissing);
-			} else {
-				Err(Error::<T>::NoProposal)?;
-			}
+			let (ext_proposal, _) = <NextExternal<T>>::get().ok_or(Error::<T>::NoProposal)?;
+			ensure!(proposal_hash == ext_proposal.hash(), Error::<T>::ProposalMissing);
 
 			let mut existing_vetoers = <Blacklist<T>>::get(&proposal_hash)
 				.map(|pair| pair.1)
@@ -811,6 +754,7 @@ decl_module! {
 
 			Self::deposit_event(RawEvent::Vetoed(who, proposal_hash, until));
 			<NextExternal<T>>::kill();
+			T::Preimages::drop(&ext_proposal);
 		}
 
 		/// Remove a referendum.
@@ -840,6 +784,15 @@ decl_module! {
 				.map_err(|_| Error::<T>::ProposalMissing)?;
 		}
 
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get() == Some(Releases::V1) {
+				StorageVersion::put(Releases::V2);
+				migrations::v2::migrate::<T>().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
 		/// Weight: see `begin_block`
 		fn on_initialize(n: T::BlockNumber) -> Weight {
 			Self::begin_block(n).unwrap_or_else(|e| {
@@ -912,108 +865,9 @@ decl_module! {
 		#[weight = T::WeightInfo::clear_public_proposals()]
 		fn clear_public_proposals(origin) {
 			ensure_root(origin)?;
-			<PublicProps<T>>::kill();
-		}
-
-		/// Register the preimage for an upcoming proposal. This doesn't require the proposal to be
-		/// in the dispatch queue but does require a deposit, returned once enacted.
-		///
-		/// The dispatch origin of this call must be _Signed_.
-		///
-		/// - `encoded_proposal`: The preimage of a proposal.
-		///
-		/// Emits `PreimageNoted`.
-		///
-		/// Weight: `O(E)` with E size of `encoded_proposal` (protected by a required deposit).
-		#[weight = T::WeightInfo::note_preimage(encoded_proposal.len() as u32)]
-		fn note_preimage(origin, encoded_proposal: Vec<u8>) {
-			Self::note_preimage_inner(ensure_signed(origin)?, encoded_proposal)?;
-		}
-
-		/// Same as `note_preimage` but origin is `OperationalPreimageOrigin`.
-		#[weight = (
-			T::WeightInfo::note_preimage(encoded_proposal.len() as u32),
-			DispatchClass::Operational,
-		)]
-		fn note_preimage_operational(origin, encoded_proposal: Vec<u8>) {
-			let who = T::OperationalPreimageOrigin::ensure_origin(origin)?;
-			Self::note_preimage_inner(who, encoded_proposal)?;
-		}
-
-		/// Register the preimage for an upcoming proposal. This requires the proposal to be
-		/// in the dispatch queue. No deposit is needed. When this call is successful, i.e.
-		/// the preimage has not been uploaded before and matches some imminent proposal,
-		/// no fee is paid.
-		///
-		/// The dispatch origin of this call must be _Signed_.
-		///
-		/// - `encoded_proposal`: The preimage of a proposal.
-		///
-		/// Emits `PreimageNoted`.
-		///
-		/// Weight: `O(E)` with E size of `encoded_proposal` (protected by a required deposit).
-		#[weight = T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32)]
-		fn note_imminent_preimage(origin, encoded_proposal: Vec<u8>) -> DispatchResultWithPostInfo {
-			Self::note_imminent_preimage_inner(ensure_signed(origin)?, encoded_proposal)?;
-			// We check that this preimage was not uploaded before in `note_imminent_preimage_inner`,
-			// thus this call can only be successful once. If successful, user does not pay a fee.
-			Ok(Pays::No.into())
-		}
-
-		/// Same as `note_imminent_preimage` but origin is `OperationalPreimageOrigin`.
-		#[weight = (
-			T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32),
-			DispatchClass::Operational,
-		)]
-		fn note_imminent_preimage_operational(origin, encoded_proposal: Vec<u8>) -> DispatchResultWithPostInfo {
-			let who = T::OperationalPreimageOrigin::ensure_origin(origin)?;
-			Self::note_imminent_preimage_inner(who, encoded_proposal)?;
-			// We check that this preimage was not uploaded before in `note_imminent_preimage_inner`,
-			// thus this call can only be successful once. If successful, user does not pay a fee.
-			Ok(Pays::No.into())
-		}
-
-		/// Remove an expired proposal preimage and collect the deposit.
-		///
-		/// The dispatch origin of this call must be _Signed_.
-		///
-		/// - `proposal_hash`: The preimage hash of a proposal.
-		/// - `proposal_length_upper_bound`: an upper bound on length of the proposal.
-		///   Extrinsic is weighted according to this value with no refund.
-		///
-		/// This will only work after `VotingPeriod` blocks from the time that the preimage was
-		/// noted, if it's the same account doing it. If it's a different account, then it'll only
-		/// work an additional `EnactmentPeriod` later.
-		///
-		/// Emits `PreimageReaped`.
-		///
-		/// Weight: `O(D)` where D is length of proposal.
-		#[weight = T::WeightInfo::reap_preimage(*proposal_len_upper_bound)]
-		fn reap_preimage(origin, proposal_hash: T::Hash, #[compact] proposal_len_upper_bound: u32) {
-			let who = ensure_signed(origin)?;
-
-			ensure!(
-				Self::pre_image_data_len(proposal_hash)? <= proposal_len_upper_bound,
-				Error::<T>::WrongUpperBound,
-			);
-
-			let (provider, deposit, since, expiry) = <Preimages<T>>::get(&proposal_hash)
-				.and_then(|m| match m {
-					PreimageStatus::Available { provider, deposit, since, expiry, .. }
-						=> Some((provider, deposit, since, expiry)),
-					_ => None,
-				}).ok_or(Error::<T>::PreimageMissing)?;
-
-			let now = <frame_system::Pallet<T>>::block_number();
-			let (voting, enactment) = (T::VotingPeriod::get(), T::EnactmentPeriod::get());
-			let additional = if who == provider { Zero::zero() } else { enactment };
-			ensure!(now >= since + voting + additional, Error::<T>::TooEarly);
-			ensure!(expiry.map_or(true, |e| now > e), Error::<T>::Imminent);
-
-			let res = T::Currency::repatriate_reserved(&provider, &who, deposit, BalanceStatus::Free);
-			debug_assert!(res.is_ok());
-			<Preimages<T>>::remove(&proposal_hash);
-			Self::deposit_event(RawEvent::PreimageReaped(proposal_hash, provider, deposit, who));
+			for (_, proposal, _) in <PublicProps<T>>::take() {
+				T::Preimages::drop(&proposal);
+			}
 		}
 
 		/// Unlock tokens that have an expired lock.
@@ -1086,11 +940,26 @@ decl_module! {
 			Ok(())
 		}
 
-		/// Enact a proposal from a referendum. For now we just make the weight be the maximum.
+		/// Enact a proposal whose preimage must be looked up. This is scheduled when a referendum
+		/// passes with a delay, so that the preimage only has to be available at the enactment.
+		/// For now we just make the weight be the maximum.
+		///
+		/// The preimage is looked up in `T::Preimages`, and its request is dropped. A missing or
+		/// undecodable preimage is reported by an event.
+		///
+		/// The dispatch origin of this call must be _Root_.
 		#[weight = T::BlockWeights::get().max_block]
-		fn enact_proposal(origin, proposal_hash: T::Hash, index: ReferendumIndex) -> DispatchResult {
+		fn enact_proposal(
+			origin,
+			proposal_hash: PreimageHash,
+			index: ReferendumIndex,
+		) -> DispatchResult {
 			ensure_root(origin)?;
-			Self::do_enact_proposal(proposal_hash, index)
+			let len = T::Preimages::len(&proposal_hash).unwrap_or(0);
+			if let Ok(proposal) = Self::take_proposal(&Bounded::lookup(proposal_hash, len), index) {
+				Self::do_enact_proposal(proposal, index);
+			}
+			Ok(())
 		}
 
 		/// Permanently place a proposal into the blacklist. This prevents it from ever being
@@ -1110,7 +979,7 @@ decl_module! {
 		///   reasonable value).
 		#[weight = (T::WeightInfo::blacklist(T::MaxProposals::get()), DispatchClass::Operational)]
 		fn blacklist(origin,
-			proposal_hash: T::Hash,
+			proposal_hash: PreimageHash,
 			maybe_ref_index: Option<ReferendumIndex>,
 		) {
 			T::BlacklistOrigin::ensure_origin(origin)?;
@@ -1121,8 +990,9 @@ decl_module! {
 
 			// Remove the queued proposal, if it's there.
 			PublicProps::<T>::mutate(|props| {
-				if let Some(index) = props.iter().position(|p| p.1 == proposal_hash) {
-					let (prop_index, ..) = props.remove(index);
+				if let Some(index) = props.iter().position(|p| p.1.hash() == proposal_hash) {
+					let (prop_index, proposal, _) = props.remove(index);
+					T::Preimages::drop(&proposal);
 					if let Some((whos, amount)) = DepositOf::<T>::take(prop_index) {
 						for who in whos.into_iter() {
 							T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
@@ -1132,14 +1002,17 @@ decl_module! {
 			});
 
 			// Remove the external queued referendum, if it's there.
-			if matches!(NextExternal::<T>::get(), Some((h, ..)) if h == proposal_hash) {
-				NextExternal::<T>::kill();
+			if let Some((ext_proposal, _)) = NextExternal::<T>::get() {
+				if ext_proposal.hash() == proposal_hash {
+					NextExternal::<T>::kill();
+					T::Preimages::drop(&ext_proposal);
+				}
 			}
 
 			// Remove the referendum, if it's there.
 			if let Some(ref_index) = maybe_ref_index {
 				if let Ok(status) = Self::referendum_status(ref_index) {
-					if status.proposal_hash == proposal_hash {
+					if status.proposal.hash() == proposal_hash {
 						Self::internal_cancel_referendum(ref_index);
 					}
 				}
@@ -1159,7 +1032,13 @@ decl_module! {
 		fn cancel_proposal(origin, #[compact] prop_index: PropIndex) {
 			T::CancelProposalOrigin::ensure_origin(origin)?;
 
-			PublicProps::<T>::mutate(|props| props.retain(|p| p.0 != prop_index));
+			PublicProps::<T>::mutate(|props| props.retain(|(index, proposal, _)| {
+				let keep = *index != prop_index;
+				if !keep {
+					T::Preimages::drop(proposal);
+				}
+				keep
+			}));
 			if let Some((whos, amount)) = DepositOf::<T>::take(prop_index) {
 				for who in whos.into_iter() {
 					T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
@@ -1181,7 +1060,7 @@ impl<T: Config> Module<T> {
 	/// Get all referenda ready for tally at block `n`.
 	pub fn maturing_referenda_at(
 		n: T::BlockNumber
-	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)> {
+	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>)> {
 		let next = Self::lowest_unbaked();
 		let last = Self::referendum_count();
 		Self::maturing_referenda_at_inner(n, next..last)
@@ -1190,7 +1069,7 @@ impl<T: Config> Module<T> {
 	fn maturing_referenda_at_inner(
 		n: T::BlockNumber,
 		range: core::ops::Range<PropIndex>,
-	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)> {
+	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>)> {
 		range.into_iter()
 			.map(|i| (i, Self::referendum_info(i)))
 			.filter_map(|(i, maybe_info)| match maybe_info {
@@ -1205,13 +1084,14 @@ impl<T: Config> Module<T> {
 
 	/// Start a referendum.
 	pub fn internal_start_referendum(
-		proposal_hash: T::Hash,
+		proposal: BoundedCallOf<T>,
 		threshold: VoteThreshold,
 		delay: T::BlockNumber
 	) -> ReferendumIndex {
+		T::Preimages::hold(&proposal);
 		<Module<T>>::inject_referendum(
 			<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get(),
-			proposal_hash,
+			proposal,
 			threshold,
 			delay
 		)
@@ -1220,14 +1100,26 @@ impl<T: Config> Module<T> {
 	/// Remove a referendum.
 	pub fn internal_cancel_referendum(ref_index: ReferendumIndex) {
 		Self::deposit_event(RawEvent::Cancelled(ref_index));
-		ReferendumInfoOf::<T>::remove(ref_index);
+		if let Some(ReferendumInfo::Ongoing(status)) = ReferendumInfoOf::<T>::take(ref_index) {
+			T::Preimages::drop(&status.proposal);
+		}
 	}
 
 	// private.
 
+	/// Set the next external proposal, dropping the request for the preimage of the proposal it
+	/// replaces, if any.
+	fn set_next_external(proposal: BoundedCallOf<T>, threshold: VoteThreshold) {
+		T::Preimages::hold(&proposal);
+		if let Some((replaced, _)) = <NextExternal<T>>::get() {
+			T::Preimages::drop(&replaced);
+		}
+		<NextExternal<T>>::put((proposal, threshold));
+	}
+
 	/// Ok if the given referendum is active, Err otherwise
-	fn ensure_ongoing(r: ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>)
-		-> Result<ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>, DispatchError>
+	fn ensure_ongoing(r: ReferendumInfo<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>)
+		-> Result<ReferendumStatus<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>, DispatchError>
 	{
 		match r {
 			ReferendumInfo::Ongoing(s) => Ok(s),
@@ -1236,7 +1128,7 @@ impl<T: Config> Module<T> {
 	}
 
 	fn referendum_status(ref_index: ReferendumIndex)
-		-> Result<ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>, DispatchError>
+		-> Result<ReferendumStatus<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>, DispatchError>
 	{
 		let info = ReferendumInfoOf::<T>::get(ref_index)
 			.ok_or(Error::<T>::ReferendumInvalid)?;
@@ -1469,13 +1361,13 @@ impl<T: Config> Module<T> {
 	/// Start a referendum
 	fn inject_referendum(
 		end: T::BlockNumber,
-		proposal_hash: T::Hash,
+		proposal: BoundedCallOf<T>,
 		threshold: VoteThreshold,
 		delay: T::BlockNumber,
 	) -> ReferendumIndex {
 		let ref_index = Self::referendum_count();
 		ReferendumCount::put(ref_index + 1);
-		let status = ReferendumStatus { end, proposal_hash, threshold, delay, tally: Default::default() };
+		let status = ReferendumStatus { end, proposal, threshold, delay, tally: Default::default() };
 		let item = ReferendumInfo::Ongoing(status);
 		<ReferendumInfoOf<T>>::insert(ref_index, item);
 		Self::deposit_event(RawEvent::Started(ref_index, threshold));
@@ -1538,33 +1430,34 @@ impl<T: Config> Module<T> {
 		}
 	}
 
-	fn do_enact_proposal(proposal_hash: T::Hash, index: ReferendumIndex) -> DispatchResult {
-		let preimage = <Preimages<T>>::take(&proposal_hash);
-		if let Some(PreimageStatus::Available { data, provider, deposit, .. }) = preimage {
-			if let Ok(proposal) = T::Proposal::decode(&mut &data[..]) {
-				let err_amount = T::Currency::unreserve(&provider, deposit);
-				debug_assert!(err_amount.is_zero());
-				Self::deposit_event(RawEvent::PreimageUsed(proposal_hash, provider, deposit));
-
-				let ok = proposal.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
-				Self::deposit_event(RawEvent::Executed(index, ok));
-
-				Ok(())
-			} else {
-				T::Slash::on_unbalanced(T::Currency::slash_reserved(&provider, deposit).0);
+	/// Look up the proposal of referendum `index` and drop the request for its preimage.
+	fn take_proposal(
+		proposal: &BoundedCallOf<T>,
+		index: ReferendumIndex,
+	) -> Result<T::Proposal, DispatchError> {
+		let proposal_hash = proposal.hash();
+		let result = if T::Preimages::have(proposal) {
+			T::Preimages::peek(proposal).map(|(call, _)| call).map_err(|_| {
 				Self::deposit_event(RawEvent::PreimageInvalid(proposal_hash, index));
-				Err(Error::<T>::PreimageInvalid.into())
-			}
+				Error::<T>::PreimageInvalid.into()
+			})
 		} else {
 			Self::deposit_event(RawEvent::PreimageMissing(proposal_hash, index));
 			Err(Error::<T>::PreimageMissing.into())
-		}
+		};
+		T::Preimages::drop(proposal);
+		result
+	}
+
+	fn do_enact_proposal(proposal: T::Proposal, index: ReferendumIndex) {
+		let ok = proposal.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
+		Self::deposit_event(RawEvent::Executed(index, ok));
 	}
 
 	fn bake_referendum(
 		now: T::BlockNumber,
 		index: ReferendumIndex,
-		status: ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>,
+		status: ReferendumStatus<T::BlockNumber, BoundedCallOf<T>, BalanceOf<T>>,
 	) -> Result<bool, DispatchError> {
 		let total_issuance = T::Currency::total_issuance();
 		let approved = status.threshold.approved(status.tally, total_issuance);
@@ -1572,27 +1465,33 @@ impl<T: Config> Module<T> {
 		if approved {
 			Self::deposit_event(RawEvent::Passed(index));
 			if status.delay.is_zero() {
-				let _ = Self::do_enact_proposal(status.proposal_hash, index);
+				// The proposal may be unavailable or undecodable by now, in which case there is
+				// nothing we can enact.
+				if let Ok(proposal) = Self::take_proposal(&status.proposal, index) {
+					Self::do_enact_proposal(proposal, index);
+				}
 			} else {
-				let when = now + status.delay;
-				// Note that we need the preimage now.
-				Preimages::<T>::mutate_exists(&status.proposal_hash, |maybe_pre| match *maybe_pre {
-					Some(PreimageStatus::Available { ref mut expiry, .. }) => *expiry = Some(when),
-					ref mut a => *a = Some(PreimageStatus::Missing(when)),
-				});
-
-				if T::Scheduler::schedule_named(
-					(DEMOCRACY_ID, index).encode(),
-					DispatchTime::At(when),
-					None,
-					63,
-					system::RawOrigin::Root.into(),
-					Call::enact_proposal(status.proposal_hash, index).into(),
-				).is_err() {
-					frame_support::print("LOGIC ERROR: bake_referendum/schedule_named failed");
+				// A preimage which must be looked up stays requested and is only taken by
+				// `enact_proposal`, so it may still be provided until the enactment.
+				let call = match status.proposal.lookup_hash() {
+					Some((hash, _)) => Some(Call::enact_proposal(hash, index).into()),
+					None => Self::take_proposal(&status.proposal, index).ok(),
+				};
+				if let Some(call) = call {
+					if T::Scheduler::schedule_named(
+						(DEMOCRACY_ID, index).encode(),
+						DispatchTime::At(now + status.delay),
+						None,
+						63,
+						system::RawOrigin::Root.into(),
+						call,
+					).is_err() {
+						frame_support::print("LOGIC ERROR: bake_referendum/schedule_named failed");
+					}
 				}
 			}
 		} else {
+			T::Preimages::drop(&status.proposal);
 			Self::deposit_event(RawEvent::NotPassed(index));
 		}
 
@@ -1644,111 +1543,6 @@ impl<T: Config> Module<T> {
 		// `Compact<u32>`.
 		decode_compact_u32_at(&<DepositOf<T>>::hashed_key_for(proposal))
 	}
-
-	/// Check that pre image exists and its value is variant `PreimageStatus::Missing`.
-	///
-	/// This check is done without getting the complete value in the runtime to avoid copying a big
-	/// value in the runtime.
-	fn check_pre_image_is_missing(proposal_hash: T::Hash) -> DispatchResult {
-		// To decode the enum variant we only need the first byte.
-		let mut buf = [0u8; 1];
-		let key = <Preimages<T>>::hashed_key_for(proposal_hash);
-		let bytes = sp_io::storage::read(&key, &mut buf, 0).ok_or_else(|| Error::<T>::NotImminent)?;
-		// The value may be smaller that 1 byte.
-		let mut input = &buf[0..buf.len().min(bytes as usize)];
-
-		match input.read_byte() {
-			Ok(0) => Ok(()), // PreimageStatus::Missing is variant 0
-			Ok(1) => Err(Error::<T>::DuplicatePreimage.into()),
-			_ => {
-				sp_runtime::print("Failed to decode `PreimageStatus` variant");
-				Err(Error::<T>::NotImminent.into())
-			}
-		}
-	}
-
-	/// Check that pre image exists, its value is variant `PreimageStatus::Available` and decode
-	/// the length of `data: Vec<u8>` fields.
-	///
-	/// This check is done without getting the complete value in the runtime to avoid copying a big
-	/// value in the runtime.
-	///
-	/// If the pre image is missing variant or doesn't exist then the error `PreimageMissing` is
-	/// returned.
-	fn pre_image_data_len(proposal_hash: T::Hash) -> Result<u32, DispatchError> {
-		// To decode the `data` field of Available variant we need:
-		// * one byte for the variant
-		// * at most 5 bytes to decode a `Compact<u32>`
-		let mut buf = [0u8; 6];
-		let key = <Preimages<T>>::hashed_key_for(proposal_hash);
-		let bytes = sp_io::storage::read(&key, &mut buf, 0).ok_or_else(|| Error::<T>::PreimageMissing)?;
-		// The value may be smaller that 6 bytes.
-		let mut input = &buf[0..buf.len().min(bytes as usize)];
-
-		match input.read_byte() {
-			Ok(1) => (), // Check that input exists and is second variant.
-			Ok(0) => return Err(Error::<T>::PreimageMissing.into()),
-			_ => {
-				sp_runtime::print("Failed to decode `PreimageStatus` variant");
-				return Err(Error::<T>::PreimageMissing.into());
-			}
-		}
-
-		// Decode the length of the vector.
-		let len = codec::Compact::<u32>::decode(&mut input).map_err(|_| {
-			sp_runtime::print("Failed to decode `PreimageStatus` variant");
-			DispatchError::from(Error::<T>::PreimageMissing)
-		})?.0;
-
-		Ok(len)
-	}
-
-	// See `note_preimage`
-	fn note_preimage_inner(who: T::AccountId, encoded_proposal: Vec<u8>) -> DispatchResult {
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		ensure!(!<Preimages<T>>::contains_key(&proposal_hash), Error::<T>::DuplicatePreimage);
-
-		let deposit = <BalanceOf<T>>::from(encoded_proposal.len() as u32)
-			.saturating_mul(T::PreimageByteDeposit::get());
-		T::Currency::reserve(&who, deposit)?;
-
-		let now = <frame_system::Pallet<T>>::block_number();
-		let a = PreimageStatus::Available {
-			data: encoded_proposal,
-			provider: who.clone(),
-			deposit,
-			since: now,
-			expiry: None,
-		};
-		<Preimages<T>>::insert(proposal_hash, a);
-
-		Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, deposit));
-
-		Ok(())
-	}
-
-	// See `note_imminent_preimage`
-	fn note_imminent_preimage_inner(who: T::AccountId, encoded_proposal: Vec<u8>) -> DispatchResult {
-		let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-		Self::check_pre_image_is_missing(proposal_hash)?;
-		let status = Preimages::<T>::get(&proposal_hash).ok_or(Error::<T>::NotImminent)?;
-		let expiry = status.to_missing_expiry().ok_or(Error::<T>::DuplicatePreimage)?;
-
-		let now = <frame_system::Pallet<T>>::block_number();
-		let free = <BalanceOf<T>>::zero();
-		let a = PreimageStatus::Available {
-			data: encoded_proposal,
-			provider: who.clone(),
-			deposit: Zero::zero(),
-			since: now,
-			expiry: Some(expiry),
-		};
-		<Preimages<T>>::insert(proposal_hash, a);
-
-		Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, free));
-
-		Ok(())
-	}
 }
 
 /// Decode `Compact<u32>` from the trie at given key.
@@ -0,0 +1,120 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the democracy pallet.
+
+use super::*;
+
+/// Migrate from proposals referred to by hash, with their preimages kept by this pallet, to
+/// bounded calls whose preimages are kept by `T::Preimages`.
+pub mod v2 {
+	use super::*;
+	use frame_support::{Identity, storage::migration::storage_key_iter};
+	use sp_std::collections::btree_map::BTreeMap;
+
+	/// The status of a preimage, as kept by the pallet before this migration.
+	#[derive(Decode)]
+	enum OldPreimageStatus<AccountId, Balance, BlockNumber> {
+		/// The preimage is imminently needed at the argument.
+		Missing(BlockNumber),
+		/// The preimage is available.
+		Available {
+			data: Vec<u8>,
+			provider: AccountId,
+			deposit: Balance,
+			since: BlockNumber,
+			/// None if it's not imminent.
+			expiry: Option<BlockNumber>,
+		},
+	}
+
+	/// Move the preimages into `T::Preimages` and turn every proposal hash into a bounded call.
+	///
+	/// The deposits of all preimage providers are returned. Preimages of proposals which are
+	/// still pending, as well as those of proposals already scheduled for enactment, are noted in
+	/// `T::Preimages` and requested on behalf of this pallet; all others are dropped.
+	///
+	/// This assumes that `T::Hash` is encoded like a [`PreimageHash`] and that `T::Hashing` is
+	/// blake2-256, so that the proposal hashes match the hashes of the preimages.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads_writes = 0;
+
+		// Preimages which are not yet needed, and only kept if a proposal refers to them.
+		let mut pending = BTreeMap::new();
+		for (hash, status) in storage_key_iter::<
+			PreimageHash,
+			OldPreimageStatus<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+			Identity,
+		>(b"Democracy", b"Preimages").drain() {
+			reads_writes += 1;
+			match status {
+				OldPreimageStatus::Available { data, provider, deposit, expiry, .. } => {
+					T::Currency::unreserve(&provider, deposit);
+					if expiry.is_some() {
+						// Kept requested until the scheduled `enact_proposal` takes it.
+						let _ = T::Preimages::note(data.into());
+					} else {
+						pending.insert(hash, data);
+					}
+				},
+				OldPreimageStatus::Missing(_) => T::Preimages::request(&hash),
+			}
+		}
+
+		let mut bound = |hash: PreimageHash| -> BoundedCallOf<T> {
+			reads_writes += 1;
+			let len = pending.get(&hash)
+				.map(|data| data.len() as u32)
+				.or_else(|| T::Preimages::len(&hash))
+				.unwrap_or(0);
+			let proposal = Bounded::lookup(hash, len);
+			T::Preimages::hold(&proposal);
+			proposal
+		};
+
+		let _ = PublicProps::<T>::translate::<Vec<(PropIndex, PreimageHash, T::AccountId)>, _>(
+			|maybe_props| maybe_props.map(|props| {
+				props.into_iter().map(|(index, hash, who)| (index, bound(hash), who)).collect()
+			}),
+		);
+		let _ = NextExternal::<T>::translate::<(PreimageHash, VoteThreshold), _>(
+			|maybe_next| maybe_next.map(|(hash, threshold)| (bound(hash), threshold)),
+		);
+		ReferendumInfoOf::<T>::translate::<
+			ReferendumInfo<T::BlockNumber, PreimageHash, BalanceOf<T>>,
+			_,
+		>(|_, info| Some(match info {
+			ReferendumInfo::Ongoing(status) => ReferendumInfo::Ongoing(ReferendumStatus {
+				end: status.end,
+				proposal: bound(status.proposal),
+				threshold: status.threshold,
+				delay: status.delay,
+				tally: status.tally,
+			}),
+			ReferendumInfo::Finished { approved, end } => ReferendumInfo::Finished { approved, end },
+		}));
+
+		for (hash, data) in pending {
+			if T::Preimages::is_requested(&hash) && T::Preimages::note(data.into()).is_ok() {
+				// Noting adds a request of its own; only those of the proposals should remain.
+				T::Preimages::unnote(&hash);
+			}
+		}
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+}
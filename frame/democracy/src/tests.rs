@@ -22,7 +22,7 @@ use super::*;
 use codec::Encode;
 use frame_support::{
 	assert_noop, assert_ok, parameter_types, ord_parameter_types,
-	traits::{SortedMembers, OnInitialize, Filter},
+	traits::{SortedMembers, OnInitialize, Filter, EqualPrivilegeOnly},
 	weights::Weight,
 };
 use sp_core::H256;
@@ -38,6 +38,7 @@ mod delegation;
 mod external_proposing;
 mod fast_tracking;
 mod lock_voting;
+mod migration;
 mod preimage;
 mod public_proposals;
 mod scheduling;
@@ -62,6 +63,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
 		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Config, Event<T>},
 		Democracy: pallet_democracy::{Pallet, Call, Storage, Config, Event<T>},
 	}
@@ -116,6 +118,7 @@ impl pallet_scheduler::Config for Test {
 	type MaximumWeight = MaximumSchedulerWeight;
 	type ScheduleOrigin = EnsureRoot<u64>;
 	type MaxScheduledPerBlock = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type WeightInfo = ();
 }
 parameter_types! {
@@ -131,6 +134,18 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const PreimageBaseDeposit: u64 = 0;
+	pub static PreimageByteDeposit: u64 = 0;
+}
+impl pallet_preimage::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
 parameter_types! {
 	pub const LaunchPeriod: u64 = 2;
 	pub const VotingPeriod: u64 = 2;
@@ -140,7 +155,6 @@ parameter_types! {
 	pub const CooloffPeriod: u64 = 2;
 	pub const MaxVotes: u32 = 100;
 	pub const MaxProposals: u32 = MAX_PROPOSALS;
-	pub static PreimageByteDeposit: u64 = 0;
 	pub static InstantAllowed: bool = false;
 }
 ord_parameter_types! {
@@ -178,13 +192,12 @@ impl Config for Test {
 	type CancelProposalOrigin = EnsureRoot<u64>;
 	type VetoOrigin = EnsureSignedBy<OneToFive, u64>;
 	type CooloffPeriod = CooloffPeriod;
-	type PreimageByteDeposit = PreimageByteDeposit;
 	type Slash = ();
 	type InstantOrigin = EnsureSignedBy<Six, u64>;
 	type InstantAllowed = InstantAllowed;
 	type Scheduler = Scheduler;
+	type Preimages = Preimage;
 	type MaxVotes = MaxVotes;
-	type OperationalPreimageOrigin = EnsureSignedBy<Six, u64>;
 	type PalletsOrigin = OriginCaller;
 	type WeightInfo = ();
 	type MaxProposals = MaxProposals;
@@ -232,29 +245,22 @@ fn set_balance_proposal_hash(value: u64) -> H256 {
 	BlakeTwo256::hash(&set_balance_proposal(value)[..])
 }
 
-fn set_balance_proposal_hash_and_note(value: u64) -> H256 {
-	let p = set_balance_proposal(value);
-	let h = BlakeTwo256::hash(&p[..]);
-	match Democracy::note_preimage(Origin::signed(6), p) {
-		Ok(_) => (),
-		Err(x) if x == Error::<Test>::DuplicatePreimage.into() => (),
-		Err(x) => panic!("{:?}", x),
-	}
-	h
+/// The proposal, small enough to be kept inline.
+fn set_balance_proposal_bounded(value: u64) -> BoundedCallOf<Test> {
+	let call = Call::Balances(pallet_balances::Call::set_balance(42, value, 0));
+	<Preimage as StorePreimage>::bound(call).unwrap()
 }
 
-fn propose_set_balance(who: u64, value: u64, delay: u64) -> DispatchResult {
-	Democracy::propose(
-		Origin::signed(who),
-		set_balance_proposal_hash(value),
-		delay,
-	)
+/// The proposal, referred to by its hash only; its preimage is not noted.
+fn set_balance_proposal_lookup(value: u64) -> BoundedCallOf<Test> {
+	let p = set_balance_proposal(value);
+	Bounded::lookup(BlakeTwo256::hash(&p[..]), p.len() as u32)
 }
 
-fn propose_set_balance_and_note(who: u64, value: u64, delay: u64) -> DispatchResult {
+fn propose_set_balance(who: u64, value: u64, delay: u64) -> DispatchResult {
 	Democracy::propose(
 		Origin::signed(who),
-		set_balance_proposal_hash_and_note(value),
+		set_balance_proposal_bounded(value),
 		delay,
 	)
 }
@@ -273,7 +279,7 @@ fn fast_forward_to(n: u64) {
 
 fn begin_referendum() -> ReferendumIndex {
 	System::set_block_number(0);
-	assert_ok!(propose_set_balance_and_note(1, 2, 1));
+	assert_ok!(propose_set_balance(1, 2, 1));
 	fast_forward_to(2);
 	0
 }
@@ -24,7 +24,7 @@ fn cancel_referendum_should_work() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -42,7 +42,7 @@ fn cancel_referendum_should_work() {
 fn cancel_queued_should_work() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		assert_ok!(propose_set_balance_and_note(1, 2, 1));
+		assert_ok!(propose_set_balance(1, 2, 1));
 
 		// start of 2 => next referendum scheduled.
 		fast_forward_to(2);
@@ -65,7 +65,7 @@ fn emergency_cancel_should_work() {
 		System::set_block_number(0);
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			2
 		);
@@ -79,7 +79,7 @@ fn emergency_cancel_should_work() {
 
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			2
 		);
@@ -25,32 +25,32 @@ fn veto_external_works() {
 		System::set_block_number(0);
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 		));
 		assert!(<NextExternal<Test>>::exists());
 
-		let h = set_balance_proposal_hash_and_note(2);
+		let h = set_balance_proposal_hash(2);
 		assert_ok!(Democracy::veto_external(Origin::signed(3), h.clone()));
 		// cancelled.
 		assert!(!<NextExternal<Test>>::exists());
 		// fails - same proposal can't be resubmitted.
 		assert_noop!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash(2),
+			set_balance_proposal_bounded(2),
 		), Error::<Test>::ProposalBlacklisted);
 
 		fast_forward_to(1);
 		// fails as we're still in cooloff period.
 		assert_noop!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash(2),
+			set_balance_proposal_bounded(2),
 		), Error::<Test>::ProposalBlacklisted);
 
 		fast_forward_to(2);
 		// works; as we're out of the cooloff period.
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 		));
 		assert!(<NextExternal<Test>>::exists());
 
@@ -69,12 +69,12 @@ fn veto_external_works() {
 		// same proposal fails as we're still in cooloff
 		assert_noop!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash(2),
+			set_balance_proposal_bounded(2),
 		), Error::<Test>::ProposalBlacklisted);
 		// different proposal works fine.
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(3),
+			set_balance_proposal_bounded(3),
 		));
 	});
 }
@@ -86,7 +86,7 @@ fn external_blacklisting_should_work() {
 
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 		));
 
 		let hash = set_balance_proposal_hash(2);
@@ -98,7 +98,7 @@ fn external_blacklisting_should_work() {
 		assert_noop!(
 			Democracy::external_propose(
 				Origin::signed(2),
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_bounded(2),
 			),
 			Error::<Test>::ProposalBlacklisted,
 		);
@@ -112,24 +112,24 @@ fn external_referendum_works() {
 		assert_noop!(
 			Democracy::external_propose(
 				Origin::signed(1),
-				set_balance_proposal_hash(2),
+				set_balance_proposal_bounded(2),
 			),
 			BadOrigin,
 		);
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 		));
 		assert_noop!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash(1),
+			set_balance_proposal_bounded(1),
 		), Error::<Test>::DuplicateProposal);
 		fast_forward_to(2);
 		assert_eq!(
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 4,
-				proposal_hash: set_balance_proposal_hash(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -145,20 +145,20 @@ fn external_majority_referendum_works() {
 		assert_noop!(
 			Democracy::external_propose_majority(
 				Origin::signed(1),
-				set_balance_proposal_hash(2)
+				set_balance_proposal_bounded(2)
 			),
 			BadOrigin,
 		);
 		assert_ok!(Democracy::external_propose_majority(
 			Origin::signed(3),
-			set_balance_proposal_hash_and_note(2)
+			set_balance_proposal_bounded(2)
 		));
 		fast_forward_to(2);
 		assert_eq!(
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 4,
-				proposal_hash: set_balance_proposal_hash(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SimpleMajority,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -174,20 +174,20 @@ fn external_default_referendum_works() {
 		assert_noop!(
 			Democracy::external_propose_default(
 				Origin::signed(3),
-				set_balance_proposal_hash(2)
+				set_balance_proposal_bounded(2)
 			),
 			BadOrigin,
 		);
 		assert_ok!(Democracy::external_propose_default(
 			Origin::signed(1),
-			set_balance_proposal_hash_and_note(2)
+			set_balance_proposal_bounded(2)
 		));
 		fast_forward_to(2);
 		assert_eq!(
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 4,
-				proposal_hash: set_balance_proposal_hash(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SuperMajorityAgainst,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -203,9 +203,9 @@ fn external_and_public_interleaving_works() {
 		System::set_block_number(0);
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(1),
+			set_balance_proposal_bounded(1),
 		));
-		assert_ok!(propose_set_balance_and_note(6, 2, 2));
+		assert_ok!(propose_set_balance(6, 2, 2));
 
 		fast_forward_to(2);
 
@@ -214,7 +214,7 @@ fn external_and_public_interleaving_works() {
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 4,
-				proposal_hash: set_balance_proposal_hash_and_note(1),
+				proposal: set_balance_proposal_bounded(1),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -223,7 +223,7 @@ fn external_and_public_interleaving_works() {
 		// replenish external
 		assert_ok!(Democracy::external_propose(
 				Origin::signed(2),
-				set_balance_proposal_hash_and_note(3),
+				set_balance_proposal_bounded(3),
 			));
 
 		fast_forward_to(4);
@@ -233,7 +233,7 @@ fn external_and_public_interleaving_works() {
 			Democracy::referendum_status(1),
 			Ok(ReferendumStatus {
 				end: 6,
-				proposal_hash: set_balance_proposal_hash_and_note(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -248,7 +248,7 @@ fn external_and_public_interleaving_works() {
 			Democracy::referendum_status(2),
 			Ok(ReferendumStatus {
 				end: 8,
-				proposal_hash: set_balance_proposal_hash_and_note(3),
+				proposal: set_balance_proposal_bounded(3),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -257,7 +257,7 @@ fn external_and_public_interleaving_works() {
 		// replenish external
 		assert_ok!(Democracy::external_propose(
 				Origin::signed(2),
-				set_balance_proposal_hash_and_note(5),
+				set_balance_proposal_bounded(5),
 			));
 
 		fast_forward_to(8);
@@ -267,7 +267,7 @@ fn external_and_public_interleaving_works() {
 			Democracy::referendum_status(3),
 			Ok(ReferendumStatus {
 				end: 10,
-				proposal_hash: set_balance_proposal_hash_and_note(5),
+				proposal: set_balance_proposal_bounded(5),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -276,9 +276,9 @@ fn external_and_public_interleaving_works() {
 		// replenish both
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(7),
+			set_balance_proposal_bounded(7),
 		));
-		assert_ok!(propose_set_balance_and_note(6, 4, 2));
+		assert_ok!(propose_set_balance(6, 4, 2));
 
 		fast_forward_to(10);
 
@@ -287,16 +287,16 @@ fn external_and_public_interleaving_works() {
 			Democracy::referendum_status(4),
 			Ok(ReferendumStatus {
 				end: 12,
-				proposal_hash: set_balance_proposal_hash_and_note(4),
+				proposal: set_balance_proposal_bounded(4),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
 			})
 		);
 		// replenish public again
-		assert_ok!(propose_set_balance_and_note(6, 6, 2));
+		assert_ok!(propose_set_balance(6, 6, 2));
 		// cancel external
-		let h = set_balance_proposal_hash_and_note(7);
+		let h = set_balance_proposal_hash(7);
 		assert_ok!(Democracy::veto_external(Origin::signed(3), h));
 
 		fast_forward_to(12);
@@ -306,7 +306,7 @@ fn external_and_public_interleaving_works() {
 			Democracy::referendum_status(5),
 			Ok(ReferendumStatus {
 				end: 14,
-				proposal_hash: set_balance_proposal_hash_and_note(6),
+				proposal: set_balance_proposal_bounded(6),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
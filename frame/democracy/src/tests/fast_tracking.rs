@@ -23,11 +23,11 @@ use super::*;
 fn fast_track_referendum_works() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		let h = set_balance_proposal_hash_and_note(2);
+		let h = set_balance_proposal_hash(2);
 		assert_noop!(Democracy::fast_track(Origin::signed(5), h, 3, 2), Error::<Test>::ProposalMissing);
 		assert_ok!(Democracy::external_propose_majority(
 			Origin::signed(3),
-			set_balance_proposal_hash_and_note(2)
+			set_balance_proposal_bounded(2)
 		));
 		assert_noop!(Democracy::fast_track(Origin::signed(1), h, 3, 2), BadOrigin);
 		assert_ok!(Democracy::fast_track(Origin::signed(5), h, 2, 0));
@@ -35,7 +35,7 @@ fn fast_track_referendum_works() {
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 2,
-				proposal_hash: set_balance_proposal_hash_and_note(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SimpleMajority,
 				delay: 0,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -48,11 +48,11 @@ fn fast_track_referendum_works() {
 fn instant_referendum_works() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		let h = set_balance_proposal_hash_and_note(2);
+		let h = set_balance_proposal_hash(2);
 		assert_noop!(Democracy::fast_track(Origin::signed(5), h, 3, 2), Error::<Test>::ProposalMissing);
 		assert_ok!(Democracy::external_propose_majority(
 			Origin::signed(3),
-			set_balance_proposal_hash_and_note(2)
+			set_balance_proposal_bounded(2)
 		));
 		assert_noop!(Democracy::fast_track(Origin::signed(1), h, 3, 2), BadOrigin);
 		assert_noop!(Democracy::fast_track(Origin::signed(5), h, 1, 0), BadOrigin);
@@ -63,7 +63,7 @@ fn instant_referendum_works() {
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 1,
-				proposal_hash: set_balance_proposal_hash_and_note(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SimpleMajority,
 				delay: 0,
 				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
@@ -76,10 +76,10 @@ fn instant_referendum_works() {
 fn fast_track_referendum_fails_when_no_simple_majority() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		let h = set_balance_proposal_hash_and_note(2);
+		let h = set_balance_proposal_hash(2);
 		assert_ok!(Democracy::external_propose(
 			Origin::signed(2),
-			set_balance_proposal_hash_and_note(2)
+			set_balance_proposal_bounded(2)
 		));
 		assert_noop!(
 			Democracy::fast_track(Origin::signed(5), h, 3, 2),
@@ -0,0 +1,100 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The tests for the storage migrations.
+
+use super::*;
+use frame_support::storage::{migration, unhashed, StorageMap, StorageValue};
+
+/// The status of a preimage, as kept by the pallet before `migrations::v2`.
+#[derive(Encode)]
+enum OldPreimageStatus {
+	Missing(u64),
+	Available { data: Vec<u8>, provider: u64, deposit: u64, since: u64, expiry: Option<u64> },
+}
+
+fn remark(byte: u8) -> Vec<u8> {
+	Call::System(frame_system::Call::remark(vec![byte; 200])).encode()
+}
+
+fn put_old_preimage(hash: H256, status: OldPreimageStatus) {
+	migration::put_storage_value(b"Democracy", b"Preimages", &hash.encode(), status);
+}
+
+#[test]
+fn migration_to_v2_works() {
+	new_test_ext().execute_with(|| {
+		let (proposed, scheduled, unused) = (remark(1), remark(2), remark(3));
+		let proposed_hash = BlakeTwo256::hash(&proposed[..]);
+		let scheduled_hash = BlakeTwo256::hash(&scheduled[..]);
+		let unused_hash = BlakeTwo256::hash(&unused[..]);
+		let missing_hash = BlakeTwo256::hash(&remark(4)[..]);
+		let referendum_hash = set_balance_proposal_hash(2);
+
+		assert_ok!(Balances::reserve(&6, 30));
+		let available = |data: &Vec<u8>, expiry| OldPreimageStatus::Available {
+			data: data.clone(), provider: 6, deposit: 10, since: 1, expiry,
+		};
+		put_old_preimage(proposed_hash, available(&proposed, None));
+		put_old_preimage(scheduled_hash, available(&scheduled, Some(5)));
+		put_old_preimage(unused_hash, available(&unused, None));
+		put_old_preimage(missing_hash, OldPreimageStatus::Missing(5));
+
+		unhashed::put(&PublicProps::<Test>::hashed_key(), &vec![(0u32, proposed_hash, 1u64)]);
+		let old_status = ReferendumStatus {
+			end: 5u64,
+			proposal: referendum_hash,
+			threshold: VoteThreshold::SuperMajorityApprove,
+			delay: 1u64,
+			tally: Tally::<u64>::default(),
+		};
+		unhashed::put(
+			&ReferendumInfoOf::<Test>::hashed_key_for(0),
+			&ReferendumInfo::Ongoing(old_status),
+		);
+
+		crate::migrations::v2::migrate::<Test>();
+
+		// All deposits are returned and the old preimages are gone.
+		assert_eq!(Balances::reserved_balance(6), 0);
+		assert!(!migration::have_storage_value(b"Democracy", b"Preimages", &proposed_hash.encode()));
+
+		// The proposal keeps its preimage.
+		let proposal = Bounded::lookup(proposed_hash, proposed.len() as u32);
+		assert_eq!(Democracy::public_props(), vec![(0, proposal.clone(), 1)]);
+		assert!(Preimage::have(&proposal));
+		assert!(Preimage::is_requested(&proposed_hash));
+
+		// The preimages of scheduled enactments are kept, or requested if missing, for
+		// `enact_proposal`.
+		assert_eq!(Preimage::len(&scheduled_hash), Some(scheduled.len() as u32));
+		assert!(Preimage::is_requested(&scheduled_hash));
+		assert!(Preimage::is_requested(&missing_hash));
+
+		// The preimage of the referendum was never provided, so it is requested.
+		match Democracy::referendum_info(0) {
+			Some(ReferendumInfo::Ongoing(status)) => {
+				assert_eq!(status.proposal, Bounded::lookup(referendum_hash, 0));
+			},
+			_ => panic!("referendum is ongoing"),
+		}
+		assert!(Preimage::is_requested(&referendum_hash));
+
+		// The unused preimage is dropped.
+		assert_eq!(Preimage::len(&unused_hash), None);
+	});
+}
@@ -24,7 +24,7 @@ fn missing_preimage_should_fail() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash(2),
+			set_balance_proposal_lookup(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -38,160 +38,120 @@ fn missing_preimage_should_fail() {
 }
 
 #[test]
-fn preimage_deposit_should_be_required_and_returned() {
-	new_test_ext_execute_with_cond(|operational| {
-		// fee of 100 is too much.
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 100);
-		assert_noop!(
-			if operational { Democracy::note_preimage_operational(Origin::signed(6), vec![0; 500]) }
-			else { Democracy::note_preimage(Origin::signed(6), vec![0; 500]) },
-			BalancesError::<Test, _>::InsufficientBalance,
-		);
-		// fee of 1 is reasonable.
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+fn preimage_should_be_requested_while_referendum_is_ongoing() {
+	new_test_ext().execute_with(|| {
+		let hash = set_balance_proposal_hash(2);
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_lookup(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
-		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
-
-		assert_eq!(Balances::reserved_balance(6), 12);
-
-		next_block();
-		next_block();
+		assert!(Preimage::is_requested(&hash));
 
-		assert_eq!(Balances::reserved_balance(6), 0);
-		assert_eq!(Balances::free_balance(6), 60);
-		assert_eq!(Balances::free_balance(42), 2);
+		assert_ok!(Democracy::cancel_referendum(Origin::root(), r.into()));
+		assert!(!Preimage::is_requested(&hash));
 	});
 }
 
 #[test]
-fn preimage_deposit_should_be_reapable_earlier_by_owner() {
-	new_test_ext_execute_with_cond(|operational| {
+fn noting_requested_preimage_for_free_should_work() {
+	new_test_ext().execute_with(|| {
 		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-		assert_ok!(
-			if operational { Democracy::note_preimage_operational(Origin::signed(6), set_balance_proposal(2)) }
-			else { Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2)) }
-		);
-
-		assert_eq!(Balances::reserved_balance(6), 12);
 
-		next_block();
-		assert_noop!(
-			Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), u32::max_value()),
-			Error::<Test>::TooEarly
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_lookup(2),
+			VoteThreshold::SuperMajorityApprove,
+			1
 		);
-		next_block();
-		assert_ok!(Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), u32::max_value()));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
-		assert_eq!(Balances::free_balance(6), 60);
+		// The referendum requested the preimage, so no deposit is taken.
+		assert_ok!(Preimage::note_preimage(Origin::signed(6), set_balance_proposal(2)));
 		assert_eq!(Balances::reserved_balance(6), 0);
-	});
-}
-
-#[test]
-fn preimage_deposit_should_be_reapable() {
-	new_test_ext_execute_with_cond(|operational| {
-		assert_noop!(
-				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()),
-				Error::<Test>::PreimageMissing
-			);
-
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-		assert_ok!(
-			if operational { Democracy::note_preimage_operational(Origin::signed(6), set_balance_proposal(2)) }
-			else { Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2)) }
-		);
-		assert_eq!(Balances::reserved_balance(6), 12);
 
 		next_block();
 		next_block();
-		next_block();
-		assert_noop!(
-				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()),
-				Error::<Test>::TooEarly
-			);
 
-		next_block();
-		assert_ok!(Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()));
-		assert_eq!(Balances::reserved_balance(6), 0);
-		assert_eq!(Balances::free_balance(6), 48);
-		assert_eq!(Balances::free_balance(5), 62);
+		assert_eq!(Balances::free_balance(42), 2);
+		// Once enacted, the preimage is no longer kept.
+		assert_eq!(Preimage::len(&set_balance_proposal_hash(2)), None);
 	});
 }
 
 #[test]
-fn noting_imminent_preimage_for_free_should_work() {
-	new_test_ext_execute_with_cond(|operational| {
+fn preimage_deposit_should_be_returned_after_enactment() {
+	new_test_ext().execute_with(|| {
 		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+		assert_ok!(Preimage::note_preimage(Origin::signed(6), set_balance_proposal(2)));
+		assert_eq!(Balances::reserved_balance(6), 12);
 
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash(2),
+			set_balance_proposal_lookup(2),
 			VoteThreshold::SuperMajorityApprove,
-			1
+			0
 		);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
-		assert_noop!(
-			if operational { Democracy::note_imminent_preimage_operational(Origin::signed(6), set_balance_proposal(2)) }
-			else { Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)) },
-			Error::<Test>::NotImminent
-		);
+		// The deposit is returned, but the preimage is kept while the referendum refers to it.
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(6), set_balance_proposal_hash(2)));
+		assert_eq!(Balances::reserved_balance(6), 0);
+		assert!(Preimage::have(&set_balance_proposal_lookup(2)));
 
 		next_block();
-
-		// Now we're in the dispatch queue it's all good.
-		assert_ok!(Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)));
-
 		next_block();
 
 		assert_eq!(Balances::free_balance(42), 2);
+		assert_eq!(Balances::free_balance(6), 60);
+		assert!(!Preimage::have(&set_balance_proposal_lookup(2)));
 	});
 }
 
 #[test]
-fn reaping_imminent_preimage_should_fail() {
+fn preimage_may_be_provided_until_enactment() {
 	new_test_ext().execute_with(|| {
-		let h = set_balance_proposal_hash_and_note(2);
-		let r = Democracy::inject_referendum(3, h, VoteThreshold::SuperMajorityApprove, 1);
+		let hash = set_balance_proposal_hash(2);
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_lookup(2),
+			VoteThreshold::SuperMajorityApprove,
+			1
+		);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
+		// The referendum passes and is scheduled for enactment without the preimage.
 		next_block();
+		assert!(Preimage::is_requested(&hash));
+
+		assert_ok!(Preimage::note_preimage(Origin::signed(6), set_balance_proposal(2)));
 		next_block();
-		assert_noop!(Democracy::reap_preimage(Origin::signed(6), h, u32::max_value()), Error::<Test>::Imminent);
+
+		assert_eq!(Balances::free_balance(42), 2);
+		assert!(!Preimage::is_requested(&hash));
 	});
 }
 
 #[test]
-fn note_imminent_preimage_can_only_be_successful_once() {
+fn preimage_missing_at_enactment_is_reported() {
 	new_test_ext().execute_with(|| {
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-
+		let hash = set_balance_proposal_hash(2);
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash(2),
+			set_balance_proposal_lookup(2),
 			VoteThreshold::SuperMajorityApprove,
 			1
 		);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
-		next_block();
-
-		// First time works
-		assert_ok!(Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)));
 
-		// Second time fails
-		assert_noop!(
-			Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)),
-			Error::<Test>::DuplicatePreimage
-		);
+		next_block();
+		next_block();
 
-		// Fails from any user
-		assert_noop!(
-			Democracy::note_imminent_preimage(Origin::signed(5), set_balance_proposal(2)),
-			Error::<Test>::DuplicatePreimage
-		);
+		assert_eq!(Balances::free_balance(42), 0);
+		assert!(!Preimage::is_requested(&hash));
+		assert!(System::events().iter().any(|record| {
+			record.event == Event::pallet_democracy(RawEvent::PreimageMissing(hash, r))
+		}));
 	});
 }
@@ -22,9 +22,9 @@ use super::*;
 #[test]
 fn backing_for_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(propose_set_balance_and_note(1, 2, 2));
-		assert_ok!(propose_set_balance_and_note(1, 4, 4));
-		assert_ok!(propose_set_balance_and_note(1, 3, 3));
+		assert_ok!(propose_set_balance(1, 2, 2));
+		assert_ok!(propose_set_balance(1, 4, 4));
+		assert_ok!(propose_set_balance(1, 3, 3));
 		assert_eq!(Democracy::backing_for(0), Some(2));
 		assert_eq!(Democracy::backing_for(1), Some(4));
 		assert_eq!(Democracy::backing_for(2), Some(3));
@@ -34,7 +34,7 @@ fn backing_for_should_work() {
 #[test]
 fn deposit_for_proposals_should_be_taken() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(propose_set_balance_and_note(1, 2, 5));
+		assert_ok!(propose_set_balance(1, 2, 5));
 		assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
 		assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
 		assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
@@ -48,7 +48,7 @@ fn deposit_for_proposals_should_be_taken() {
 #[test]
 fn deposit_for_proposals_should_be_returned() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(propose_set_balance_and_note(1, 2, 5));
+		assert_ok!(propose_set_balance(1, 2, 5));
 		assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
 		assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
 		assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
@@ -77,7 +77,7 @@ fn poor_proposer_should_not_work() {
 #[test]
 fn poor_seconder_should_not_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(propose_set_balance_and_note(2, 2, 11));
+		assert_ok!(propose_set_balance(2, 2, 11));
 		assert_noop!(
 			Democracy::second(Origin::signed(1), 0, u32::max_value()),
 			BalancesError::<Test, _>::InsufficientBalance
@@ -88,7 +88,7 @@ fn poor_seconder_should_not_work() {
 #[test]
 fn invalid_seconds_upper_bound_should_not_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(propose_set_balance_and_note(1, 2, 5));
+		assert_ok!(propose_set_balance(1, 2, 5));
 		assert_noop!(
 			Democracy::second(Origin::signed(2), 0, 0),
 			Error::<Test>::WrongUpperBound
@@ -100,8 +100,8 @@ fn invalid_seconds_upper_bound_should_not_work() {
 fn cancel_proposal_should_work() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		assert_ok!(propose_set_balance_and_note(1, 2, 2));
-		assert_ok!(propose_set_balance_and_note(1, 4, 4));
+		assert_ok!(propose_set_balance(1, 2, 2));
+		assert_ok!(propose_set_balance(1, 4, 4));
 		assert_noop!(Democracy::cancel_proposal(Origin::signed(1), 0), BadOrigin);
 		assert_ok!(Democracy::cancel_proposal(Origin::root(), 0));
 		assert_eq!(Democracy::backing_for(0), None);
@@ -115,8 +115,8 @@ fn blacklisting_should_work() {
 		System::set_block_number(0);
 		let hash = set_balance_proposal_hash(2);
 
-		assert_ok!(propose_set_balance_and_note(1, 2, 2));
-		assert_ok!(propose_set_balance_and_note(1, 4, 4));
+		assert_ok!(propose_set_balance(1, 2, 2));
+		assert_ok!(propose_set_balance(1, 4, 4));
 
 		assert_noop!(Democracy::blacklist(Origin::signed(1), hash.clone(), None), BadOrigin);
 		assert_ok!(Democracy::blacklist(Origin::root(), hash, None));
@@ -124,7 +124,7 @@ fn blacklisting_should_work() {
 		assert_eq!(Democracy::backing_for(0), None);
 		assert_eq!(Democracy::backing_for(1), Some(4));
 
-		assert_noop!(propose_set_balance_and_note(1, 2, 2), Error::<Test>::ProposalBlacklisted);
+		assert_noop!(propose_set_balance(1, 2, 2), Error::<Test>::ProposalBlacklisted);
 
 		fast_forward_to(2);
 
@@ -139,9 +139,9 @@ fn blacklisting_should_work() {
 fn runners_up_should_come_after() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		assert_ok!(propose_set_balance_and_note(1, 2, 2));
-		assert_ok!(propose_set_balance_and_note(1, 4, 4));
-		assert_ok!(propose_set_balance_and_note(1, 3, 3));
+		assert_ok!(propose_set_balance(1, 2, 2));
+		assert_ok!(propose_set_balance(1, 4, 4));
+		assert_ok!(propose_set_balance(1, 3, 3));
 		fast_forward_to(2);
 		assert_ok!(Democracy::vote(Origin::signed(1), 0, aye(1)));
 		fast_forward_to(4);
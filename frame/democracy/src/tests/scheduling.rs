@@ -24,7 +24,7 @@ fn simple_passing_should_work() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -41,7 +41,7 @@ fn simple_failing_should_work() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -60,13 +60,13 @@ fn ooo_inject_referendums_should_work() {
 	new_test_ext().execute_with(|| {
 		let r1 = Democracy::inject_referendum(
 			3,
-			set_balance_proposal_hash_and_note(3),
+			set_balance_proposal_bounded(3),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
 		let r2 = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -90,7 +90,7 @@ fn delayed_enactment_should_work() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			1
 		);
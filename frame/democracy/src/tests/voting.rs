@@ -57,7 +57,7 @@ fn split_vote_cancellation_should_work() {
 fn single_proposal_should_work() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(0);
-		assert_ok!(propose_set_balance_and_note(1, 2, 1));
+		assert_ok!(propose_set_balance(1, 2, 1));
 		let r = 0;
 		assert!(Democracy::referendum_info(r).is_none());
 
@@ -70,7 +70,7 @@ fn single_proposal_should_work() {
 			Democracy::referendum_status(0),
 			Ok(ReferendumStatus {
 				end: 4,
-				proposal_hash: set_balance_proposal_hash_and_note(2),
+				proposal: set_balance_proposal_bounded(2),
 				threshold: VoteThreshold::SuperMajorityApprove,
 				delay: 2,
 				tally: Tally { ayes: 1, nays: 0, turnout: 10 },
@@ -100,7 +100,7 @@ fn controversial_voting_should_work() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -126,7 +126,7 @@ fn controversial_low_turnout_voting_should_work() {
 	new_test_ext().execute_with(|| {
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
@@ -150,7 +150,7 @@ fn passing_low_turnout_voting_should_work() {
 
 		let r = Democracy::inject_referendum(
 			2,
-			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_bounded(2),
 			VoteThreshold::SuperMajorityApprove,
 			0
 		);
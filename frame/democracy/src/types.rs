@@ -162,11 +162,11 @@ impl<
 
 /// Info regarding an ongoing referendum.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct ReferendumStatus<BlockNumber, Hash, Balance> {
+pub struct ReferendumStatus<BlockNumber, Proposal, Balance> {
 	/// When voting on this referendum will end.
 	pub (crate) end: BlockNumber,
-	/// The hash of the proposal being voted on.
-	pub (crate) proposal_hash: Hash,
+	/// The proposal being voted on.
+	pub (crate) proposal: Proposal,
 	/// The thresholding mechanism to determine whether it passed.
 	pub (crate) threshold: VoteThreshold,
 	/// The delay (in blocks) to wait after a successful referendum before deploying.
@@ -177,22 +177,22 @@ pub struct ReferendumStatus<BlockNumber, Hash, Balance> {
 
 /// Info regarding a referendum, present or past.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub enum ReferendumInfo<BlockNumber, Hash, Balance> {
+pub enum ReferendumInfo<BlockNumber, Proposal, Balance> {
 	/// Referendum is happening, the arg is the block number at which it will end.
-	Ongoing(ReferendumStatus<BlockNumber, Hash, Balance>),
+	Ongoing(ReferendumStatus<BlockNumber, Proposal, Balance>),
 	/// Referendum finished at `end`, and has been `approved` or rejected.
 	Finished{approved: bool, end: BlockNumber},
 }
 
-impl<BlockNumber, Hash, Balance: Default> ReferendumInfo<BlockNumber, Hash, Balance> {
+impl<BlockNumber, Proposal, Balance: Default> ReferendumInfo<BlockNumber, Proposal, Balance> {
 	/// Create a new instance.
 	pub fn new(
 		end: BlockNumber,
-		proposal_hash: Hash,
+		proposal: Proposal,
 		threshold: VoteThreshold,
 		delay: BlockNumber,
 	) -> Self {
-		let s = ReferendumStatus{ end, proposal_hash, threshold, delay, tally: Tally::default() };
+		let s = ReferendumStatus{ end, proposal, threshold, delay, tally: Tally::default() };
 		ReferendumInfo::Ongoing(s)
 	}
 }
@@ -104,6 +104,10 @@
 //! than the best queued one (see [`pallet::Config::SolutionImprovementThreshold`]) and will limit
 //! the weigh of the solution to [`pallet::Config::MinerMaxWeight`].
 //!
+//! The mined solution is cached in the offchain database. Throughout the unsigned phase, the
+//! offchain worker periodically resubmits the cached solution, in case it got dropped from the
+//! transaction pool, instead of mining it again.
+//!
 //! The unsigned phase can be made passive depending on how the previous signed phase went, by
 //! setting the first inner value of [`Phase`] to `false`. For now, the signed phase is always
 //! active.
@@ -194,8 +198,7 @@
 //! **Score based on (byte) size**: We should always prioritize small solutions over bigger ones, if
 //! there is a tie. Even more harsh should be to enforce the bound of the `reduce` algorithm.
 //!
-//! **Offchain resubmit**: Essentially port <https://github.com/paritytech/substrate/pull/7976> to
-//! this pallet as well. The `OFFCHAIN_REPEAT` also needs to become an adjustable parameter of the
+//! **Offchain repeat**: The `OFFCHAIN_REPEAT` needs to become an adjustable parameter of the
 //! pallet.
 //!
 //! **Make the number of nominators configurable from the runtime**. Remove `sp_npos_elections`
@@ -639,15 +642,31 @@ pub mod pallet {
 		}
 
 		fn offchain_worker(n: T::BlockNumber) {
-			// We only run the OCW in the first block of the unsigned phase.
-			if Self::current_phase().is_unsigned_open_at(n) {
-				match Self::try_acquire_offchain_lock(n) {
-					Ok(_) => {
-						let outcome = Self::mine_check_and_submit().map_err(ElectionError::from);
-						log!(info, "mine_check_and_submit execution done: {:?}", outcome);
+			match Self::current_phase() {
+				// We mine a new solution in the first block of the unsigned phase, and resubmit the
+				// cached one in the following blocks, in case it got dropped.
+				Phase::Unsigned((true, opened)) if opened <= n => {
+					match Self::try_acquire_offchain_lock(n) {
+						Ok(_) if opened == n => {
+							let outcome = Self::mine_check_and_submit()
+								.map_err(ElectionError::from);
+							log!(info, "mine_check_and_submit execution done: {:?}", outcome);
+						}
+						Ok(_) => {
+							let outcome = Self::restore_or_compute_then_submit()
+								.map_err(ElectionError::from);
+							log!(
+								debug,
+								"restore_or_compute_then_submit execution done: {:?}",
+								outcome,
+							);
+						}
+						Err(why) => log!(debug, "denied offchain worker: {:?}", why),
 					}
-					Err(why) => log!(warn, "denied offchain worker: {:?}", why),
 				}
+				Phase::Unsigned(_) => {}
+				// The cached solution is useless outside of the unsigned phase.
+				_ => Self::kill_ocw_solution(),
 			}
 		}
 
@@ -30,6 +30,9 @@ use sp_std::cmp::Ordering;
 /// Storage key used to store the persistent offchain worker status.
 pub(crate) const OFFCHAIN_HEAD_DB: &[u8] = b"parity/multi-phase-unsigned-election";
 
+/// Storage key used to cache the solution `call` of the offchain worker.
+pub(crate) const OFFCHAIN_CACHED_CALL: &[u8] = b"parity/multi-phase-unsigned-election/call";
+
 /// The repeat threshold of the offchain worker. This means we won't run the offchain worker twice
 /// within a window of 5 blocks.
 pub(crate) const OFFCHAIN_REPEAT: u32 = 5;
@@ -63,15 +66,15 @@ impl From<FeasibilityError> for MinerError {
 }
 
 impl<T: Config> Pallet<T> {
-	/// Mine a new solution, and submit it back to the chain as an unsigned transaction.
+	/// Mine a new solution, cache it in the offchain storage, and submit it back to the chain as an
+	/// unsigned transaction.
 	pub fn mine_check_and_submit() -> Result<(), MinerError> {
 		let iters = Self::get_balancing_iters();
 		// get the solution, with a load of checks to ensure if submitted, IT IS ABSOLUTELY VALID.
 		let (raw_solution, witness) = Self::mine_and_check(iters)?;
 		let score = raw_solution.score.clone();
 
-		let call: <T as frame_system::offchain::SendTransactionTypes<Call<T>>>::OverarchingCall =
-			Call::submit_unsigned(raw_solution, witness).into();
+		let call = Call::submit_unsigned(raw_solution, witness);
 		log!(
 			info,
 			"mined a solution with score {:?} and size {}",
@@ -79,6 +82,46 @@ impl<T: Config> Pallet<T> {
 			call.using_encoded(|b| b.len())
 		);
 
+		StorageValueRef::persistent(&OFFCHAIN_CACHED_CALL).set(&call);
+		Self::submit_call(call)
+	}
+
+	/// Resubmit the solution cached by a previous run of the offchain worker in this round, or mine
+	/// a new one if there is none.
+	///
+	/// The cached solution is only resubmitted if it would still be accepted, i.e. if it is not
+	/// queued already and no better solution got queued in the meantime.
+	pub fn restore_or_compute_then_submit() -> Result<(), MinerError> {
+		match Self::restore_solution() {
+			Some((raw_solution, witness)) => {
+				Self::unsigned_pre_dispatch_checks(&raw_solution).map_err(|e| {
+					log!(debug, "pre-dispatch-checks failed for cached solution: {:?}", e);
+					MinerError::PreDispatchChecksFailed
+				})?;
+				Self::submit_call(Call::submit_unsigned(raw_solution, witness))
+			}
+			None => Self::mine_check_and_submit(),
+		}
+	}
+
+	/// Get the solution cached by the offchain worker, if it belongs to the current round.
+	fn restore_solution() -> Option<(RawSolution<CompactOf<T>>, SolutionOrSnapshotSize)> {
+		match StorageValueRef::persistent(&OFFCHAIN_CACHED_CALL).get::<Call<T>>() {
+			Some(Some(Call::submit_unsigned(raw_solution, witness)))
+				if raw_solution.round == Self::round() => Some((raw_solution, witness)),
+			_ => None,
+		}
+	}
+
+	/// Clear the solution cached by the offchain worker.
+	pub(crate) fn kill_ocw_solution() {
+		StorageValueRef::persistent(&OFFCHAIN_CACHED_CALL).clear();
+	}
+
+	/// Submit the given solution `call` as an unsigned transaction.
+	fn submit_call(call: Call<T>) -> Result<(), MinerError> {
+		let call: <T as frame_system::offchain::SendTransactionTypes<Call<T>>>::OverarchingCall =
+			call.into();
 		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call)
 			.map_err(|_| MinerError::PoolSubmissionFailed)
 	}
@@ -909,17 +952,8 @@ mod tests {
 			roll_to(25);
 			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
 
-			// we must clear the offchain storage to ensure the offchain execution check doesn't get
-			// in the way.
-			let mut storage = StorageValueRef::persistent(&OFFCHAIN_HEAD_DB);
-
 			MultiPhase::offchain_worker(24);
 			assert!(pool.read().transactions.len().is_zero());
-			storage.clear();
-
-			MultiPhase::offchain_worker(26);
-			assert!(pool.read().transactions.len().is_zero());
-			storage.clear();
 
 			// submits!
 			MultiPhase::offchain_worker(25);
@@ -927,6 +961,44 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn ocw_resubmits_cached_solution() {
+		let (mut ext, pool) = ExtBuilder::default().build_offchainify(0);
+		ext.execute_with(|| {
+			roll_to(25);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+
+			// the first run mines and caches the solution.
+			MultiPhase::offchain_worker(25);
+			let submitted = pool.write().transactions.pop().unwrap();
+			assert!(MultiPhase::restore_solution().is_some());
+
+			// the transaction got dropped, the cached solution is submitted again once the
+			// offchain worker is allowed to run.
+			MultiPhase::offchain_worker(26);
+			assert!(pool.read().transactions.is_empty());
+			MultiPhase::offchain_worker((26 + OFFCHAIN_REPEAT).into());
+			assert_eq!(pool.write().transactions.pop().unwrap(), submitted);
+
+			// the cached solution of a previous round is never used.
+			let (solution, witness) = MultiPhase::restore_solution().unwrap();
+			let stale = Call::<Runtime>::submit_unsigned(
+				RawSolution { round: solution.round - 1, ..solution },
+				witness,
+			);
+			StorageValueRef::persistent(&OFFCHAIN_CACHED_CALL).set(&stale);
+			assert!(MultiPhase::restore_solution().is_none());
+
+			// the cache is cleared once the unsigned phase is over.
+			let _ = MultiPhase::elect();
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+			MultiPhase::offchain_worker(32);
+			assert!(
+				StorageValueRef::persistent(&OFFCHAIN_CACHED_CALL).get::<Call<Runtime>>().is_none()
+			);
+		})
+	}
+
 	#[test]
 	fn ocw_can_submit_to_pool() {
 		let (mut ext, pool) = ExtBuilder::default().build_offchainify(0);
@@ -274,3 +274,40 @@ impl<AccountId, BlockNumber> ElectionProvider<AccountId, BlockNumber> for () {
 		Err("<() as ElectionProvider> cannot do anything.")
 	}
 }
+
+/// A list of voters, kept sorted by their [`VoteWeight`] in a best-effort manner.
+///
+/// An [`ElectionDataProvider`] that has more voters than fit in a snapshot can use this to only
+/// provide the voters with the highest weight, by taking the first `n` items of
+/// [`SortedListProvider::iter`]. The owner of the voters is responsible for calling the `on_*`
+/// hooks whenever a voter is added, removed, or its weight changes.
+pub trait SortedListProvider<AccountId> {
+	/// The error type of the list.
+	type Error: Debug;
+
+	/// An iterator over the list, starting with the voter with the highest weight.
+	fn iter() -> Box<dyn Iterator<Item = AccountId>>;
+
+	/// The number of voters in the list.
+	fn count() -> u32;
+
+	/// Return true if the list contains `id`.
+	fn contains(id: &AccountId) -> bool;
+
+	/// Hook for inserting a new voter `id` with `weight`.
+	fn on_insert(id: AccountId, weight: VoteWeight) -> Result<(), Self::Error>;
+
+	/// Hook for updating the weight of voter `id`, which is already in the list.
+	fn on_update(id: &AccountId, weight: VoteWeight);
+
+	/// Hook for removing voter `id` from the list.
+	fn on_remove(id: &AccountId);
+
+	/// Remove all voters from the list.
+	///
+	/// This is only meant to be used in testing and benchmarking.
+	fn clear();
+
+	/// Check the internal consistency of the list.
+	fn sanity_check() -> Result<(), &'static str>;
+}
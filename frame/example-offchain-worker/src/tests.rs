@@ -22,7 +22,7 @@ use codec::Decode;
 use frame_support::{assert_ok, parameter_types};
 use sp_core::{
 	H256,
-	offchain::{OffchainWorkerExt, TransactionPoolExt, testing},
+	offchain::{OffchainDbExt, OffchainWorkerExt, TransactionPoolExt, testing},
 	sr25519::Signature,
 };
 
@@ -218,7 +218,8 @@ fn should_submit_signed_transaction_on_chain() {
 
 
 	let mut t = sp_io::TestExternalities::default();
-	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.register_extension(OffchainDbExt::new(offchain));
 	t.register_extension(TransactionPoolExt::new(pool));
 	t.register_extension(KeystoreExt(Arc::new(keystore)));
 
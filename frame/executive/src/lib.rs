@@ -121,7 +121,7 @@ use frame_support::{
 	weights::{GetDispatchInfo, DispatchInfo, DispatchClass},
 	traits::{
 		OnInitialize, OnIdle, OnFinalize, OnRuntimeUpgrade, OffchainWorker, ExecuteBlock,
-		EnsureInherentsAreFirst,
+		EnsureInherentsAreFirst, TryState,
 	},
 	dispatch::PostDispatchInfo,
 };
@@ -165,7 +165,8 @@ impl<
 		OnInitialize<System::BlockNumber> +
 		OnIdle<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
-		OffchainWorker<System::BlockNumber>,
+		OffchainWorker<System::BlockNumber> +
+		TryState<System::BlockNumber>,
 	COnRuntimeUpgrade: OnRuntimeUpgrade,
 > ExecuteBlock<Block> for
 	Executive<System, Block, Context, UnsignedValidator, AllPallets, COnRuntimeUpgrade>
@@ -192,7 +193,8 @@ impl<
 			+ OnInitialize<System::BlockNumber>
 			+ OnIdle<System::BlockNumber>
 			+ OnFinalize<System::BlockNumber>
-			+ OffchainWorker<System::BlockNumber>,
+			+ OffchainWorker<System::BlockNumber>
+			+ TryState<System::BlockNumber>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
 	> Executive<System, Block, Context, UnsignedValidator, AllPallets, COnRuntimeUpgrade>
 where
@@ -234,9 +236,34 @@ where
 			OnRuntimeUpgrade
 		>::post_upgrade()?;
 
+		Self::try_state(<frame_system::Pallet<System>>::block_number())?;
+
 		Ok(weight)
 	}
 
+	/// Execute the given block and check the state invariants of all pallets afterwards, using
+	/// their `try_state` hooks.
+	///
+	/// This should only be used for testing.
+	#[cfg(feature = "try-runtime")]
+	pub fn try_execute_block(block: Block) -> Result<(), &'static str> {
+		let number = *block.header().number();
+		Self::execute_block(block);
+		Self::try_state(number)
+	}
+
+	/// Check the state invariants of all pallets, including `frame_system`, at block `n`.
+	///
+	/// This should only be used for testing.
+	#[cfg(feature = "try-runtime")]
+	pub fn try_state(n: System::BlockNumber) -> Result<(), &'static str> {
+		<
+			(frame_system::Pallet::<System>, AllPallets)
+			as
+			TryState<System::BlockNumber>
+		>::try_state(n)
+	}
+
 	/// Start the execution of a particular block.
 	pub fn initialize_block(header: &System::Header) {
 		sp_io::init_tracing();
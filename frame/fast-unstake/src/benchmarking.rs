@@ -0,0 +1,129 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fast unstake pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use sp_std::prelude::*;
+use frame_system::RawOrigin;
+use frame_support::{
+	StorageValue, StorageDoubleMap,
+	traits::{Currency, EnsureOrigin, Get},
+	weights::Weight,
+};
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use pallet_staking::{
+	EraIndex, Exposure, IndividualExposure, RewardDestination,
+	testing_utils::create_stash_controller,
+};
+
+use crate::Pallet as FastUnstake;
+
+const MAX_VALIDATORS: u32 = 128;
+
+/// Create a stash registered for fast unstake, returning it along with its controller.
+fn setup_registered<T: Config>() -> Result<(T::AccountId, T::AccountId), &'static str> {
+	let (stash, controller) = create_stash_controller::<T>(0, 100, RewardDestination::Staked)?;
+	T::DepositCurrency::deposit_creating(&stash, T::Deposit::get());
+	FastUnstake::<T>::register_fast_unstake(RawOrigin::Signed(controller.clone()).into())
+		.map_err(|e| e.error)?;
+	Ok((stash, controller))
+}
+
+/// Fill `era` with exposures of `validators` validators, each backed by
+/// `MaxBackersPerValidator` nominators, none of which is `stash`.
+fn fill_era<T: Config>(era: EraIndex, validators: u32, stash: &T::AccountId) {
+	for i in 0..validators {
+		let validator: T::AccountId = frame_benchmarking::account("validator", i, 0);
+		assert!(validator != *stash);
+		let others = (0..T::MaxBackersPerValidator::get())
+			.map(|j| {
+				let nominator: T::AccountId = frame_benchmarking::account("nominator", j, i);
+				assert!(nominator != *stash);
+				IndividualExposure { who: nominator, value: Default::default() }
+			})
+			.collect();
+		pallet_staking::ErasStakers::<T>::insert(era, validator, Exposure {
+			total: Default::default(),
+			own: Default::default(),
+			others,
+		});
+	}
+}
+
+benchmarks! {
+	register_fast_unstake {
+		let (stash, controller) = create_stash_controller::<T>(0, 100, RewardDestination::Staked)?;
+		T::DepositCurrency::deposit_creating(&stash, T::Deposit::get());
+	}: _(RawOrigin::Signed(controller))
+	verify {
+		assert!(Queue::<T>::contains_key(&stash));
+	}
+
+	deregister {
+		let (stash, controller) = setup_registered::<T>()?;
+	}: _(RawOrigin::Signed(controller))
+	verify {
+		assert!(!Queue::<T>::contains_key(&stash));
+	}
+
+	control {
+		let origin = T::ControlOrigin::successful_origin();
+	}: _<T::Origin>(origin, 16)
+	verify {
+		assert_eq!(ErasToCheckPerBlock::<T>::get(), 16);
+	}
+
+	// checking one era with `v` validators.
+	on_idle_check {
+		let v in 1 .. MAX_VALIDATORS;
+		let (stash, _) = setup_registered::<T>()?;
+		let current_era = T::BondingDuration::get();
+		pallet_staking::CurrentEra::put(current_era);
+		pallet_staking::ValidatorCount::put(v);
+		fill_era::<T>(current_era, v, &stash);
+		ErasToCheckPerBlock::<T>::put(1);
+	}: {
+		FastUnstake::<T>::process_head(Weight::max_value());
+	}
+	verify {
+		assert_eq!(Head::<T>::get().unwrap().checked, vec![current_era]);
+	}
+
+	on_idle_unstake {
+		let (_, controller) = setup_registered::<T>()?;
+		ErasToCheckPerBlock::<T>::put(T::BondingDuration::get() + 1);
+		// check all eras.
+		FastUnstake::<T>::process_head(Weight::max_value());
+		assert!(Head::<T>::get().is_some());
+	}: {
+		FastUnstake::<T>::process_head(Weight::max_value());
+	}
+	verify {
+		assert!(Head::<T>::get().is_none());
+		assert!(pallet_staking::Module::<T>::ledger(&controller).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	FastUnstake,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
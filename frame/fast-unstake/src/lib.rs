@@ -0,0 +1,366 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Fast Unstake Pallet
+//!
+//! A pallet that allows stashes which have not been exposed in the last `BondingDuration` eras to
+//! unbond immediately, without waiting for the full bonding duration.
+//!
+//! ## Overview
+//!
+//! A stash that is bonded but has never been (or is no longer) backing any validator does not
+//! pose any risk of being slashed, yet the normal unbonding path still makes it wait for
+//! `BondingDuration` eras. This is mostly an issue for accounts that nominated by accident.
+//!
+//! ## Design
+//!
+//! The controller of such a stash calls [`Call::register_fast_unstake`]. This chills the stash,
+//! unbonds all of its active balance and reserves a [`Config::Deposit`] from the stash. The stash
+//! is then put in the [`Queue`].
+//!
+//! In `on_idle`, one stash at a time is taken from the queue and becomes the [`Head`]. At most
+//! [`ErasToCheckPerBlock`] eras of `[current_era - BondingDuration, current_era]` are checked
+//! each block for any exposure of the head stash, either as a validator or as a nominator. Since
+//! this only ever uses left-over block weight, the checks never compete with transactions.
+//!
+//! Checking an era reads the exposure of each of its validators, so it is weighed by the
+//! validator count of staking and by [`Config::MaxBackersPerValidator`]. Fewer eras are checked
+//! in a block if the left-over weight does not allow for all [`ErasToCheckPerBlock`] of them.
+//!
+//! - If the stash is found to be exposed in any of the eras, its deposit is slashed and it is
+//!   removed from the pallet. Its funds remain in the regular unbonding queue of staking.
+//! - If all eras have been checked and no exposure was found, the stash is force-unstaked, its
+//!   lock is removed and its deposit is returned.
+//!
+//! A stash that is still in the queue (but not yet the head) can leave through
+//! [`Call::deregister`], which returns the deposit. Its funds stay unbonding.
+//!
+//! Setting [`ErasToCheckPerBlock`] to zero, through [`Call::control`], halts all processing.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+const LOG_TARGET: &'static str = "runtime::fast-unstake";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use sp_std::prelude::*;
+	use sp_runtime::traits::{Saturating, Zero};
+	use frame_support::{
+		pallet_prelude::*,
+		dispatch::UnfilteredDispatchable,
+		storage::IterableStorageDoubleMap,
+		traits::{Currency, ReservableCurrency},
+		transactional,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_staking::EraIndex;
+	pub use crate::weights::WeightInfo;
+
+	pub(crate) type BalanceOf<T> = <<T as Config>::DepositCurrency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_staking::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency used to hold the deposit of registered stashes.
+		type DepositCurrency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount reserved from a stash while it is registered for fast unstake. It is
+		/// slashed if the stash turns out to be exposed.
+		#[pallet::constant]
+		type Deposit: Get<BalanceOf<Self>>;
+
+		/// The origin that can change [`ErasToCheckPerBlock`].
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of nominators in the exposure of a validator.
+		///
+		/// The weight of checking an era assumes that each exposure is this large, so it should be
+		/// at least the number of nominators the election of staking can put behind one validator.
+		#[pallet::constant]
+		type MaxBackersPerValidator: Get<u32>;
+
+		/// Weight information for extrinsics and hooks in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// A stash that is being checked for exposure.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+	pub struct UnstakeRequest<AccountId, Balance> {
+		/// The stash being checked.
+		pub stash: AccountId,
+		/// The eras that have already been checked and found to have no exposure of `stash`.
+		pub checked: Vec<EraIndex>,
+		/// The deposit reserved from `stash`.
+		pub deposit: Balance,
+	}
+
+	/// The stash that is currently being checked, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn head)]
+	pub type Head<T: Config> =
+		StorageValue<_, UnstakeRequest<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+	/// The stashes waiting to be checked, along with the deposit reserved for each of them.
+	#[pallet::storage]
+	pub type Queue<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>>;
+
+	/// Number of eras checked for exposure in each `on_idle` call. Zero means processing is
+	/// halted.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_to_check_per_block)]
+	pub type ErasToCheckPerBlock<T> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		/// The initial value of [`ErasToCheckPerBlock`].
+		pub eras_to_check_per_block: u32,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { eras_to_check_per_block: 1 }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			ErasToCheckPerBlock::<T>::put(self.eras_to_check_per_block);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A stash was registered for fast unstake. \[stash, deposit\]
+		Registered(T::AccountId, BalanceOf<T>),
+		/// A stash left the queue before being checked. \[stash\]
+		Deregistered(T::AccountId),
+		/// Some eras were checked and no exposure of the stash was found. \[stash, eras\]
+		Checked(T::AccountId, Vec<EraIndex>),
+		/// A stash was found to be exposed and its deposit was slashed. \[stash, amount\]
+		Slashed(T::AccountId, BalanceOf<T>),
+		/// A stash was unstaked, with the result of the unstaking. \[stash, result\]
+		Unstaked(T::AccountId, DispatchResult),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin is not the controller of a bonded stash.
+		NotController,
+		/// The stash is already registered.
+		AlreadyQueued,
+		/// The stash is not registered.
+		NotQueued,
+		/// The stash is currently being checked and cannot deregister anymore.
+		AlreadyHead,
+		/// The stash has some balance unlocking already, or some of its bonded balance is not
+		/// active.
+		NotFullyBonded,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::process_head(remaining_weight)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the stash of the signing controller for fast unstake.
+		///
+		/// The stash is chilled, all of its active balance is unbonded and [`Config::Deposit`]
+		/// is reserved from it. The stash must not have any balance unlocking already.
+		///
+		/// If the stash is later found to be exposed in any of the last `BondingDuration` eras,
+		/// the deposit is slashed and the stash has to wait for the regular unbonding to finish.
+		#[pallet::weight(<T as Config>::WeightInfo::register_fast_unstake())]
+		#[transactional]
+		pub fn register_fast_unstake(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let controller = ensure_signed(origin.clone())?;
+			let ledger = pallet_staking::Module::<T>::ledger(&controller)
+				.ok_or(Error::<T>::NotController)?;
+			let stash = ledger.stash;
+
+			ensure!(!Queue::<T>::contains_key(&stash), Error::<T>::AlreadyQueued);
+			ensure!(
+				Head::<T>::get().map_or(true, |head| head.stash != stash),
+				Error::<T>::AlreadyQueued,
+			);
+			ensure!(
+				ledger.unlocking.is_empty() && ledger.active == ledger.total,
+				Error::<T>::NotFullyBonded,
+			);
+
+			let deposit = T::Deposit::get();
+			T::DepositCurrency::reserve(&stash, deposit)?;
+
+			pallet_staking::Call::<T>::chill()
+				.dispatch_bypass_filter(origin.clone())
+				.map_err(|e| e.error)?;
+			pallet_staking::Call::<T>::unbond(ledger.active)
+				.dispatch_bypass_filter(origin)
+				.map_err(|e| e.error)?;
+
+			Queue::<T>::insert(&stash, deposit);
+			Self::deposit_event(Event::<T>::Registered(stash, deposit));
+			Ok(().into())
+		}
+
+		/// Remove the stash of the signing controller from the queue and return its deposit.
+		///
+		/// This is only possible while the stash is waiting in the queue, not once its checks
+		/// have started. The stash remains unbonding; it can be rebonded through staking.
+		#[pallet::weight(<T as Config>::WeightInfo::deregister())]
+		pub fn deregister(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let controller = ensure_signed(origin)?;
+			let stash = pallet_staking::Module::<T>::ledger(&controller)
+				.ok_or(Error::<T>::NotController)?
+				.stash;
+
+			ensure!(
+				Head::<T>::get().map_or(true, |head| head.stash != stash),
+				Error::<T>::AlreadyHead,
+			);
+			let deposit = Queue::<T>::take(&stash).ok_or(Error::<T>::NotQueued)?;
+			let _ = T::DepositCurrency::unreserve(&stash, deposit);
+
+			Self::deposit_event(Event::<T>::Deregistered(stash));
+			Ok(().into())
+		}
+
+		/// Set the number of eras checked for exposure in each block.
+		///
+		/// Setting this to zero halts all processing.
+		///
+		/// The dispatch origin must be [`Config::ControlOrigin`].
+		#[pallet::weight(<T as Config>::WeightInfo::control())]
+		pub fn control(origin: OriginFor<T>, eras_to_check: u32) -> DispatchResultWithPostInfo {
+			T::ControlOrigin::ensure_origin(origin)?;
+			ErasToCheckPerBlock::<T>::put(eras_to_check);
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Make progress on the current head, if `remaining_weight` allows it.
+		///
+		/// Returns the weight consumed.
+		pub(crate) fn process_head(remaining_weight: Weight) -> Weight {
+			let eras_to_check = ErasToCheckPerBlock::<T>::get();
+			if eras_to_check.is_zero() {
+				return T::DbWeight::get().reads(1)
+			}
+
+			// checking an era reads the exposure of each of its validators.
+			let validator_count = pallet_staking::Module::<T>::validator_count();
+			let check_weight = |eras: u32| {
+				<T as Config>::WeightInfo::on_idle_check(validator_count.saturating_mul(eras))
+			};
+
+			// check as many eras as `remaining_weight` allows, there is never more to check than
+			// `BondingDuration + 1` of them.
+			let eras_to_check = (1..=eras_to_check.min(T::BondingDuration::get().saturating_add(1)))
+				.rev()
+				.find(|eras| check_weight(*eras) <= remaining_weight);
+			let eras_to_check = match eras_to_check {
+				Some(eras) if <T as Config>::WeightInfo::on_idle_unstake() <= remaining_weight =>
+					eras,
+				_ => return T::DbWeight::get().reads(2),
+			};
+
+			let UnstakeRequest { stash, mut checked, deposit } = match Head::<T>::take()
+				.or_else(|| {
+					Queue::<T>::drain()
+						.next()
+						.map(|(stash, deposit)| UnstakeRequest { stash, deposit, checked: vec![] })
+				}) {
+				Some(request) => request,
+				None => return T::DbWeight::get().reads(3),
+			};
+
+			let current_era = pallet_staking::Module::<T>::current_era().unwrap_or_default();
+			let first_era = current_era.saturating_sub(T::BondingDuration::get());
+			let unchecked_eras = (first_era..=current_era)
+				.rev()
+				.filter(|era| !checked.contains(era))
+				.take(eras_to_check as usize)
+				.collect::<Vec<_>>();
+
+			if unchecked_eras.is_empty() {
+				let num_slashing_spans = pallet_staking::Module::<T>::slashing_spans(&stash)
+					.map_or(0, |spans| spans.num_spans());
+				let result = pallet_staking::Call::<T>::force_unstake(stash.clone(), num_slashing_spans)
+					.dispatch_bypass_filter(RawOrigin::Root.into())
+					.map(|_| ())
+					.map_err(|e| e.error);
+				let _ = T::DepositCurrency::unreserve(&stash, deposit);
+
+				log::info!(
+					target: crate::LOG_TARGET,
+					"fast unstaked {:?}, result: {:?}",
+					stash,
+					result,
+				);
+				Self::deposit_event(Event::<T>::Unstaked(stash, result));
+				return <T as Config>::WeightInfo::on_idle_unstake()
+			}
+
+			let num_checked = unchecked_eras.len() as u32;
+			if unchecked_eras.iter().any(|era| Self::is_exposed_in_era(&stash, *era)) {
+				let (_, not_slashed) = T::DepositCurrency::slash_reserved(&stash, deposit);
+				Self::deposit_event(Event::<T>::Slashed(stash, deposit.saturating_sub(not_slashed)));
+			} else {
+				checked.extend(unchecked_eras.iter().cloned());
+				Head::<T>::put(UnstakeRequest { stash: stash.clone(), checked, deposit });
+				Self::deposit_event(Event::<T>::Checked(stash, unchecked_eras));
+			}
+
+			check_weight(num_checked)
+		}
+
+		/// Whether `who` was exposed in `era`, either as a validator or as one of the nominators
+		/// backing a validator.
+		///
+		/// This reads the exposures of all the validators of `era`.
+		pub(crate) fn is_exposed_in_era(who: &T::AccountId, era: EraIndex) -> bool {
+			pallet_staking::ErasStakers::<T>::iter_prefix(era).any(|(validator, exposure)| {
+				validator == *who || exposure.others.iter().any(|i| i.who == *who)
+			})
+		}
+	}
+}
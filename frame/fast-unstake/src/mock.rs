@@ -0,0 +1,283 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities
+
+use super::*;
+use crate as pallet_fast_unstake;
+
+use frame_support::{
+	parameter_types, StorageDoubleMap, StorageValue, weights::Weight,
+	traits::{OnIdle, OneSessionHandler},
+};
+use frame_election_provider_support::onchain;
+use pallet_staking::{EraIndex, Exposure, IndividualExposure, StakerStatus};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::{Header, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use frame_system::EnsureRoot;
+
+pub(crate) type AccountId = u64;
+pub(crate) type Balance = u128;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Staking: pallet_staking::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Session: pallet_session::{Pallet, Call, Storage, Event},
+		FastUnstake: pallet_fast_unstake::{Pallet, Call, Storage, Event<T>, Config},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(
+			frame_support::weights::constants::WEIGHT_PER_SECOND * 2
+		);
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+	pub const MaxDrift: u64 = 30_000;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const Period: u64 = 5;
+	pub const Offset: u64 = 0;
+	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(25);
+}
+
+/// A session handler that ignores all session changes.
+pub struct TestSessionHandler;
+impl OneSessionHandler<AccountId> for TestSessionHandler {
+	type Key = UintAuthorityId;
+
+	fn on_genesis_session<'a, I: 'a>(_: I)
+		where I: Iterator<Item=(&'a AccountId, Self::Key)>, AccountId: 'a {}
+
+	fn on_new_session<'a, I: 'a>(_: bool, _: I, _: I)
+		where I: Iterator<Item=(&'a AccountId, Self::Key)>, AccountId: 'a {}
+
+	fn on_disabled(_: usize) {}
+}
+
+impl sp_runtime::BoundToRuntimeAppPublic for TestSessionHandler {
+	type Public = UintAuthorityId;
+}
+
+sp_runtime::impl_opaque_keys! {
+	pub struct SessionKeys {
+		pub other: TestSessionHandler,
+	}
+}
+
+impl pallet_session::Config for Test {
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Test, Staking>;
+	type Keys = SessionKeys;
+	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+	type SessionHandler = (TestSessionHandler,);
+	type Event = Event;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = pallet_staking::StashOf<Test>;
+	type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Config for Test {
+	type FullIdentification = Exposure<AccountId, Balance>;
+	type FullIdentificationOf = pallet_staking::ExposureOf<Test>;
+}
+
+impl onchain::Config for Test {
+	type AccountId = AccountId;
+	type BlockNumber = u64;
+	type BlockWeights = BlockWeights;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+
+parameter_types! {
+	pub const SessionsPerEra: sp_staking::SessionIndex = 3;
+	pub const BondingDuration: EraIndex = 3;
+	pub const SlashDeferDuration: EraIndex = 0;
+	pub const MaxNominatorRewardedPerValidator: u32 = 64;
+}
+
+impl pallet_staking::Config for Test {
+	const MAX_NOMINATIONS: u32 = 16;
+	type Currency = Balances;
+	type UnixTime = Timestamp;
+	type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
+	type RewardRemainder = ();
+	type Event = Event;
+	type Slash = ();
+	type Reward = ();
+	type SessionsPerEra = SessionsPerEra;
+	type SlashDeferDuration = SlashDeferDuration;
+	type SlashCancelOrigin = EnsureRoot<AccountId>;
+	type BondingDuration = BondingDuration;
+	type SessionInterface = Self;
+	type EraPayout = ();
+	type NextNewSession = Session;
+	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
+	type WeightInfo = ();
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = TestXt<Call, ()>;
+}
+
+parameter_types! {
+	pub const Deposit: Balance = 7;
+	pub const MaxBackersPerValidator: u32 = 16;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type DepositCurrency = Balances;
+	type Deposit = Deposit;
+	type ControlOrigin = EnsureRoot<AccountId>;
+	type MaxBackersPerValidator = MaxBackersPerValidator;
+	type WeightInfo = ();
+}
+
+/// The validator stashes set up in genesis, with their controllers being `stash + 100`.
+pub(crate) const VALIDATORS: [AccountId; 2] = [1, 2];
+
+/// The nominator stashes set up in genesis, with their controllers being `stash + 100`.
+pub(crate) const NOMINATORS: [AccountId; 2] = [3, 4];
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let stashes = VALIDATORS.iter().chain(NOMINATORS.iter());
+	pallet_balances::GenesisConfig::<Test> {
+		balances: stashes.clone()
+			.flat_map(|stash| vec![(*stash, 100), (stash + 100, 10)])
+			.collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_staking::GenesisConfig::<Test> {
+		stakers: VALIDATORS.iter()
+			.map(|v| (*v, v + 100, 50, StakerStatus::Validator))
+			.chain(NOMINATORS.iter().map(|n| (*n, n + 100, 50, StakerStatus::Nominator(vec![1]))))
+			.collect(),
+		validator_count: 2,
+		..Default::default()
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_fast_unstake::GenesisConfig { eras_to_check_per_block: 1 }
+		.assimilate_storage::<Test>(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Set the current era of staking.
+pub(crate) fn set_current_era(era: EraIndex) {
+	pallet_staking::CurrentEra::put(era);
+}
+
+/// Expose `nominator` behind validator `1` in `era`.
+pub(crate) fn expose_in_era(nominator: AccountId, era: EraIndex) {
+	pallet_staking::ErasStakers::<Test>::insert(era, 1, Exposure {
+		total: 100,
+		own: 50,
+		others: vec![IndividualExposure { who: nominator, value: 50 }],
+	});
+}
+
+/// Call `on_idle` of the pallet with plenty of weight, `n` times.
+pub(crate) fn next_idle_blocks(n: u32) {
+	for _ in 0..n {
+		FastUnstake::on_idle(System::block_number(), Weight::max_value());
+	}
+}
+
+pub(crate) fn fast_unstake_events() -> Vec<pallet_fast_unstake::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| if let Event::pallet_fast_unstake(inner) = e { Some(inner) } else { None })
+		.collect()
+}
@@ -0,0 +1,272 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the fast unstake pallet.
+
+use super::{Error, Event, Queue, WeightInfo};
+use crate::mock::*;
+use frame_support::{
+	assert_noop, assert_ok, StorageValue, traits::{Currency, OnIdle, ReservableCurrency},
+};
+use sp_runtime::DispatchError;
+
+#[test]
+fn register_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+
+		assert_eq!(Queue::<Test>::get(3), Some(7));
+		assert_eq!(Balances::reserved_balance(3), 7);
+		assert!(Staking::nominators(3).is_none());
+		let ledger = Staking::ledger(103).unwrap();
+		assert_eq!(ledger.active, 0);
+		assert_eq!(ledger.unlocking.len(), 1);
+		assert_eq!(fast_unstake_events(), vec![Event::Registered(3, 7)]);
+	});
+}
+
+#[test]
+fn register_fails_for_invalid_stashes() {
+	new_test_ext().execute_with(|| {
+		// the stash itself is not a controller.
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(3)),
+			Error::<Test>::NotController,
+		);
+
+		// some balance is already unlocking.
+		assert_ok!(Staking::unbond(Origin::signed(104), 10));
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(104)),
+			Error::<Test>::NotFullyBonded,
+		);
+
+		// cannot register twice.
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(103)),
+			Error::<Test>::AlreadyQueued,
+		);
+	});
+}
+
+#[test]
+fn deregister_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(FastUnstake::deregister(Origin::signed(103)), Error::<Test>::NotQueued);
+
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+		assert_ok!(FastUnstake::deregister(Origin::signed(103)));
+
+		assert!(Queue::<Test>::get(3).is_none());
+		assert_eq!(Balances::reserved_balance(3), 0);
+		// the stash remains unbonding.
+		assert_eq!(Staking::ledger(103).unwrap().active, 0);
+	});
+}
+
+#[test]
+fn cannot_deregister_head() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+		next_idle_blocks(1);
+
+		assert_eq!(FastUnstake::head().unwrap().stash, 3);
+		assert_noop!(FastUnstake::deregister(Origin::signed(103)), Error::<Test>::AlreadyHead);
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(103)),
+			Error::<Test>::AlreadyQueued,
+		);
+	});
+}
+
+#[test]
+fn unexposed_stash_is_unstaked() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+
+		// eras 3, 2, 1 and 0 are checked, one per block.
+		next_idle_blocks(4);
+		assert_eq!(FastUnstake::head().unwrap().checked, vec![3, 2, 1, 0]);
+		assert!(Staking::ledger(103).is_some());
+
+		next_idle_blocks(1);
+		assert!(FastUnstake::head().is_none());
+		assert!(Staking::ledger(103).is_none());
+		assert!(Staking::bonded(3).is_none());
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 100);
+		// the staking lock is gone.
+		assert_ok!(Balances::transfer(Origin::signed(3), 1, 99));
+
+		assert_eq!(
+			fast_unstake_events(),
+			vec![
+				Event::Registered(3, 7),
+				Event::Checked(3, vec![3]),
+				Event::Checked(3, vec![2]),
+				Event::Checked(3, vec![1]),
+				Event::Checked(3, vec![0]),
+				Event::Unstaked(3, Ok(())),
+			],
+		);
+	});
+}
+
+#[test]
+fn multiple_eras_can_be_checked_per_block() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_ok!(FastUnstake::control(Origin::root(), 3));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+
+		next_idle_blocks(2);
+		assert_eq!(FastUnstake::head().unwrap().checked, vec![3, 2, 1, 0]);
+
+		next_idle_blocks(1);
+		assert!(Staking::ledger(103).is_none());
+	});
+}
+
+#[test]
+fn new_eras_are_checked_as_well() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_ok!(FastUnstake::control(Origin::root(), 4));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+		next_idle_blocks(1);
+		assert_eq!(FastUnstake::head().unwrap().checked, vec![3, 2, 1, 0]);
+
+		// a new era starts before the stash is unstaked, in which the stash is exposed.
+		set_current_era(4);
+		expose_in_era(3, 4);
+		next_idle_blocks(1);
+
+		assert!(FastUnstake::head().is_none());
+		assert!(Staking::ledger(103).is_some());
+		assert_eq!(fast_unstake_events().last(), Some(&Event::Slashed(3, 7)));
+	});
+}
+
+#[test]
+fn exposed_nominator_is_slashed() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		expose_in_era(3, 2);
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+
+		next_idle_blocks(2);
+		assert!(FastUnstake::head().is_none());
+		assert!(Queue::<Test>::get(3).is_none());
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 93);
+		// still bonded, and unbonding through the regular path.
+		assert_eq!(Staking::ledger(103).unwrap().unlocking.len(), 1);
+
+		assert_eq!(
+			fast_unstake_events(),
+			vec![Event::Registered(3, 7), Event::Checked(3, vec![3]), Event::Slashed(3, 7)],
+		);
+	});
+}
+
+#[test]
+fn exposed_validator_is_slashed() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		expose_in_era(3, 3);
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(101)));
+
+		next_idle_blocks(1);
+		assert_eq!(fast_unstake_events().last(), Some(&Event::Slashed(1, 7)));
+		assert!(Staking::ledger(101).is_some());
+	});
+}
+
+#[test]
+fn queue_is_processed_in_turn() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_ok!(FastUnstake::control(Origin::root(), 4));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(104)));
+
+		next_idle_blocks(4);
+		assert!(Staking::ledger(103).is_none());
+		assert!(Staking::ledger(104).is_none());
+		assert_eq!(Queue::<Test>::iter().count(), 0);
+		assert!(FastUnstake::head().is_none());
+	});
+}
+
+#[test]
+fn control_works() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_noop!(FastUnstake::control(Origin::signed(1), 0), DispatchError::BadOrigin);
+		assert_ok!(FastUnstake::control(Origin::root(), 0));
+		assert_eq!(FastUnstake::eras_to_check_per_block(), 0);
+
+		// nothing happens while halted.
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+		next_idle_blocks(10);
+		assert!(FastUnstake::head().is_none());
+		assert_eq!(Queue::<Test>::get(3), Some(7));
+	});
+}
+
+#[test]
+fn does_nothing_without_enough_weight() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+
+		FastUnstake::on_idle(System::block_number(), 0);
+		assert!(FastUnstake::head().is_none());
+		assert_eq!(Queue::<Test>::get(3), Some(7));
+	});
+}
+
+#[test]
+fn eras_to_check_are_limited_by_weight() {
+	new_test_ext().execute_with(|| {
+		set_current_era(3);
+		pallet_staking::ValidatorCount::put(100);
+		assert_ok!(FastUnstake::control(Origin::root(), 4));
+		assert_ok!(FastUnstake::register_fast_unstake(Origin::signed(103)));
+
+		// only two eras of 100 validators fit in the weight.
+		let weight = <() as WeightInfo>::on_idle_check(200);
+		assert_eq!(FastUnstake::on_idle(System::block_number(), weight), weight);
+		assert_eq!(FastUnstake::head().unwrap().checked, vec![3, 2]);
+	});
+}
+
+#[test]
+fn deposit_is_required() {
+	new_test_ext().execute_with(|| {
+		// leave the stash with nothing outside of its bonded funds.
+		let _ = Balances::make_free_balance_be(&3, 50);
+		assert!(!Balances::can_reserve(&3, 7));
+		assert_noop!(
+			FastUnstake::register_fast_unstake(Origin::signed(103)),
+			pallet_balances::Error::<Test>::LiquidityRestrictions,
+		);
+	});
+}
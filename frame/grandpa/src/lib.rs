@@ -378,6 +378,37 @@ impl<T: Config> Module<T> {
 		storage::unhashed::get_or_default::<VersionedAuthorityList>(GRANDPA_AUTHORITIES_KEY).into()
 	}
 
+	/// Get the pending standard change along with the block number at which it was signaled.
+	pub fn pending_standard_change() -> Option<(T::BlockNumber, ScheduledChange<T::BlockNumber>)> {
+		<PendingChange<T>>::get()
+			.filter(|pending_change| pending_change.forced.is_none())
+			.map(|pending_change| (
+				pending_change.scheduled_at,
+				ScheduledChange {
+					next_authorities: pending_change.next_authorities,
+					delay: pending_change.delay,
+				},
+			))
+	}
+
+	/// Get the pending forced change along with the block number at which it was signaled
+	/// and the median last finalized block number.
+	pub fn pending_forced_change()
+		-> Option<(T::BlockNumber, T::BlockNumber, ScheduledChange<T::BlockNumber>)>
+	{
+		<PendingChange<T>>::get().and_then(|pending_change| {
+			let median = pending_change.forced?;
+			Some((
+				pending_change.scheduled_at,
+				median,
+				ScheduledChange {
+					next_authorities: pending_change.next_authorities,
+					delay: pending_change.delay,
+				},
+			))
+		})
+	}
+
 	/// Set the current set of authorities, along with their respective weights.
 	fn set_grandpa_authorities(authorities: &AuthorityList) {
 		storage::unhashed::put(
@@ -127,6 +127,36 @@ fn cannot_schedule_change_when_one_pending() {
 	});
 }
 
+#[test]
+fn pending_changes_are_exposed() {
+	new_test_ext(vec![(1, 1), (2, 1), (3, 1)]).execute_with(|| {
+		initialize_block(1, Default::default());
+		assert_eq!(Grandpa::pending_standard_change(), None);
+		assert_eq!(Grandpa::pending_forced_change(), None);
+
+		Grandpa::schedule_change(to_authorities(vec![(4, 1), (5, 1), (6, 1)]), 1, None).unwrap();
+		assert_eq!(
+			Grandpa::pending_standard_change(),
+			Some((1, ScheduledChange {
+				next_authorities: to_authorities(vec![(4, 1), (5, 1), (6, 1)]),
+				delay: 1,
+			})),
+		);
+		assert_eq!(Grandpa::pending_forced_change(), None);
+
+		<PendingChange<Test>>::kill();
+		Grandpa::schedule_change(to_authorities(vec![(5, 1)]), 5, Some(0)).unwrap();
+		assert_eq!(Grandpa::pending_standard_change(), None);
+		assert_eq!(
+			Grandpa::pending_forced_change(),
+			Some((1, 0, ScheduledChange {
+				next_authorities: to_authorities(vec![(5, 1)]),
+				delay: 5,
+			})),
+		);
+	});
+}
+
 #[test]
 fn new_decodes_from_old() {
 	let old = OldStoredPendingChange {
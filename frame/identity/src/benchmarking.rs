@@ -44,10 +44,7 @@ fn add_registrars<T: Config>(r: u32) -> Result<(), &'static str> {
 		let _ = T::Currency::make_free_balance_be(&registrar, BalanceOf::<T>::max_value());
 		Identity::<T>::add_registrar(RawOrigin::Root.into(), registrar.clone())?;
 		Identity::<T>::set_fee(RawOrigin::Signed(registrar.clone()).into(), i.into(), 10u32.into())?;
-		let fields = IdentityFields(
-			IdentityField::Display | IdentityField::Legal | IdentityField::Web | IdentityField::Riot
-			| IdentityField::Email | IdentityField::PgpFingerprint | IdentityField::Image | IdentityField::Twitter
-		);
+		let fields = T::IdentityInformation::all_fields();
 		Identity::<T>::set_fields(RawOrigin::Signed(registrar.clone()).into(), i.into(), fields)?;
 	}
 
@@ -86,24 +83,24 @@ fn add_sub_accounts<T: Config>(who: &T::AccountId, s: u32) -> Result<Vec<(T::Acc
 	Ok(subs)
 }
 
-// This creates an `IdentityInfo` object with `num_fields` extra fields.
-// All data is pre-populated with some arbitrary bytes.
-fn create_identity_info<T: Config>(num_fields: u32) -> IdentityInfo {
-	let data = Data::Raw(vec![0; 32]);
+// This creates identity information with `num_fields` extra fields.
+fn create_identity_info<T: Config>(num_fields: u32) -> T::IdentityInformation {
+	T::IdentityInformation::create_identity_info(num_fields)
+}
 
-	let info = IdentityInfo {
-		additional: vec![(data.clone(), data.clone()); num_fields as usize],
-		display: data.clone(),
-		legal: data.clone(),
-		web: data.clone(),
-		riot: data.clone(),
-		email: data.clone(),
-		pgp_fingerprint: Some([0; 20]),
-		image: data.clone(),
-		twitter: data.clone(),
-	};
-
-	return info
+// Makes `authority` a username authority with the suffix `dot` and returns the username `name`
+// would be granted by it.
+fn setup_username_authority<T: Config>(
+	authority: &T::AccountId,
+	name: &[u8],
+) -> Result<Vec<u8>, &'static str> {
+	Identity::<T>::add_username_authority(
+		RawOrigin::Root.into(),
+		T::Lookup::unlookup(authority.clone()),
+		b"dot".to_vec(),
+		10,
+	)?;
+	Ok([name, &b".dot"[..]].concat())
 }
 
 benchmarks! {
@@ -286,10 +283,7 @@ benchmarks! {
 		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
 
 		Identity::<T>::add_registrar(RawOrigin::Root.into(), caller.clone())?;
-		let fields = IdentityFields(
-			IdentityField::Display | IdentityField::Legal | IdentityField::Web | IdentityField::Riot
-			| IdentityField::Email | IdentityField::PgpFingerprint | IdentityField::Image | IdentityField::Twitter
-		);
+		let fields = T::IdentityInformation::all_fields();
 		let registrars = Registrars::<T>::get();
 		ensure!(registrars[r as usize].as_ref().unwrap().fields == Default::default(), "fields already set.");
 	}: _(RawOrigin::Signed(caller), r, fields)
@@ -401,6 +395,77 @@ benchmarks! {
 		ensure!(!SuperOf::<T>::contains_key(&caller), "Sub not removed");
 	}
 
+	add_username_authority {
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let authority_lookup = T::Lookup::unlookup(authority.clone());
+	}: _(RawOrigin::Root, authority_lookup, b"dot".to_vec(), 10)
+	verify {
+		assert_last_event::<T>(Event::<T>::UsernameAuthorityAdded(authority).into());
+	}
+
+	remove_username_authority {
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let authority_lookup = T::Lookup::unlookup(authority.clone());
+		setup_username_authority::<T>(&authority, b"")?;
+	}: _(RawOrigin::Root, authority_lookup)
+	verify {
+		assert_last_event::<T>(Event::<T>::UsernameAuthorityRemoved(authority).into());
+	}
+
+	set_username_for {
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let who: T::AccountId = account("target", 0, SEED);
+		let username = setup_username_authority::<T>(&authority, b"alice")?;
+	}: _(RawOrigin::Signed(authority), T::Lookup::unlookup(who.clone()), b"alice".to_vec())
+	verify {
+		assert_last_event::<T>(Event::<T>::UsernameQueued(who, username).into());
+	}
+
+	accept_username {
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let username = setup_username_authority::<T>(&authority, b"alice")?;
+		Identity::<T>::set_username_for(
+			RawOrigin::Signed(authority).into(),
+			T::Lookup::unlookup(caller.clone()),
+			b"alice".to_vec(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), username.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::UsernameSet(caller, username).into());
+	}
+
+	remove_expired_approval {
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let username = setup_username_authority::<T>(&authority, b"alice")?;
+		Identity::<T>::set_username_for(
+			RawOrigin::Signed(authority).into(),
+			T::Lookup::unlookup(account("target", 0, SEED)),
+			b"alice".to_vec(),
+		)?;
+		let expired = frame_system::Pallet::<T>::block_number()
+			+ T::PendingUsernameExpiration::get() + 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(expired);
+	}: _(RawOrigin::Signed(caller), username.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::PreapprovalExpired(username).into());
+	}
+
+	remove_username {
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let caller: T::AccountId = whitelisted_caller();
+		let username = setup_username_authority::<T>(&authority, b"alice")?;
+		Identity::<T>::set_username_for(
+			RawOrigin::Signed(authority).into(),
+			T::Lookup::unlookup(caller.clone()),
+			b"alice".to_vec(),
+		)?;
+		Identity::<T>::accept_username(RawOrigin::Signed(caller.clone()).into(), username.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), username.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::UsernameRemoved(caller, username).into());
+	}
 }
 
 impl_benchmark_test_suite!(
@@ -40,6 +40,16 @@
 //! The number of registrars should be limited, and the deposit made sufficiently large, to ensure
 //! no state-bloat attack is viable.
 //!
+//! The set of fields making up an identity is defined by the runtime through
+//! `Config::IdentityInformation`. [`IdentityInfo`] provides the classic set of fields.
+//!
+//! ### Usernames
+//!
+//! A specified origin may appoint username authorities. Each authority has a suffix and an
+//! allocation of usernames that it may grant. An authority grants a username such as `alice.dot`
+//! to an account, which must then accept it within `PendingUsernameExpiration` blocks. Accepted
+//! usernames resolve to their account through `AccountOfUsername`.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -58,6 +68,14 @@
 //! * `rename_sub` - Rename a sub-identity of an identity.
 //! * `quit_sub` - Remove a sub-identity of an identity (called by the sub-identity).
 //!
+//! #### For general users with usernames
+//! * `accept_username` - Accept a username granted by an authority.
+//! * `remove_expired_approval` - Remove a granted username that was not accepted in time.
+//! * `remove_username` - Remove the username of an account.
+//!
+//! #### For username authorities
+//! * `set_username_for` - Grant a username to an account.
+//!
 //! #### For registrars
 //! * `set_fee` - Set the fee required to be paid for a judgement to be given by the registrar.
 //! * `set_fields` - Set the fields that a registrar cares about in their judgements.
@@ -66,6 +84,8 @@
 //! #### For super-users
 //! * `add_registrar` - Add a new registrar to the system.
 //! * `kill_identity` - Forcibly remove the associated identity; the deposit is lost.
+//! * `add_username_authority` - Add an account that may grant usernames.
+//! * `remove_username_authority` - Remove a username authority.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
@@ -78,13 +98,13 @@ mod benchmarking;
 pub mod weights;
 
 use sp_std::prelude::*;
-use sp_std::{fmt::Debug, ops::Add, iter::once};
+use sp_std::{fmt::Debug, ops::Add, iter::once, convert::TryFrom};
 use enumflags2::BitFlags;
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchError, RuntimeDebug, DispatchResult};
 use sp_runtime::traits::{StaticLookup, Zero, AppendZerosInput, Saturating};
 use frame_support::{
-	decl_module, decl_event, decl_storage, ensure, decl_error,
+	decl_module, decl_event, decl_storage, ensure, decl_error, Parameter, BoundedVec,
 	dispatch::DispatchResultWithPostInfo,
 	traits::{Currency, ReservableCurrency, OnUnbalanced, Get, BalanceStatus, EnsureOrigin},
 };
@@ -93,6 +113,14 @@ pub use weights::WeightInfo;
 
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+type FieldsIdentifierOf<T> =
+	<<T as Config>::IdentityInformation as IdentityInformationProvider>::FieldsIdentifier;
+
+/// A username, including the suffix of the authority that granted it, e.g. `alice.dot`.
+pub type Username<T> = BoundedVec<u8, <T as Config>::MaxUsernameLength>;
+
+/// The suffix that an authority appends to all usernames it grants, e.g. `dot`.
+pub type Suffix<T> = BoundedVec<u8, <T as Config>::MaxSuffixLength>;
 
 pub trait Config: frame_system::Config {
 	/// The overarching event type.
@@ -119,6 +147,10 @@ pub trait Config: frame_system::Config {
 	/// required to access an identity, but can be pretty high.
 	type MaxAdditionalFields: Get<u32>;
 
+	/// The information that makes up an identity. Use `IdentityInfo` for the classic set of
+	/// fields.
+	type IdentityInformation: IdentityInformationProvider;
+
 	/// Maxmimum number of registrars allowed in the system. Needed to bound the complexity
 	/// of, e.g., updating judgements.
 	type MaxRegistrars: Get<u32>;
@@ -132,6 +164,18 @@ pub trait Config: frame_system::Config {
 	/// The origin which may add or remove registrars. Root can always do this.
 	type RegistrarOrigin: EnsureOrigin<Self::Origin>;
 
+	/// The origin which may add or remove username authorities.
+	type UsernameAuthorityOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The number of blocks within which a granted username must be accepted.
+	type PendingUsernameExpiration: Get<Self::BlockNumber>;
+
+	/// The maximum length of a suffix.
+	type MaxSuffixLength: Get<u32>;
+
+	/// The maximum length of a username, including its suffix and the `.` separating the two.
+	type MaxUsernameLength: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -291,6 +335,30 @@ impl Decode for IdentityFields {
 	}
 }
 
+/// Information concerning the identity of the controller of an account.
+///
+/// Runtimes define their own set of fields by implementing this trait; `IdentityInfo` is the
+/// default implementation.
+pub trait IdentityInformationProvider: Parameter {
+	/// Type capable of identifying a set of fields. Registrars use it to declare which fields
+	/// their judgements are concerned with.
+	type FieldsIdentifier: Parameter + Default;
+
+	/// Returns `true` if all of `fields` are set in this identity.
+	fn has_identity(&self, fields: Self::FieldsIdentifier) -> bool;
+
+	/// The number of additional fields, each of which requires `FieldDeposit` to be reserved.
+	fn additional(&self) -> usize;
+
+	/// Create an identity with `num_fields` additional fields and all other fields set.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_identity_info(num_fields: u32) -> Self;
+
+	/// The identifier of all fields of this identity.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn all_fields() -> Self::FieldsIdentifier;
+}
+
 /// Information concerning the identity of the controller of an account.
 ///
 /// NOTE: This should be stored at the end of the storage item to facilitate the addition of extra
@@ -343,13 +411,58 @@ pub struct IdentityInfo {
 	pub twitter: Data,
 }
 
+impl IdentityInformationProvider for IdentityInfo {
+	type FieldsIdentifier = IdentityFields;
+
+	fn has_identity(&self, fields: Self::FieldsIdentifier) -> bool {
+		let set_fields = [
+			(IdentityField::Display, self.display != Data::None),
+			(IdentityField::Legal, self.legal != Data::None),
+			(IdentityField::Web, self.web != Data::None),
+			(IdentityField::Riot, self.riot != Data::None),
+			(IdentityField::Email, self.email != Data::None),
+			(IdentityField::PgpFingerprint, self.pgp_fingerprint.is_some()),
+			(IdentityField::Image, self.image != Data::None),
+			(IdentityField::Twitter, self.twitter != Data::None),
+		];
+		set_fields.iter().all(|(field, is_set)| *is_set || !fields.0.contains(*field))
+	}
+
+	fn additional(&self) -> usize {
+		self.additional.len()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_identity_info(num_fields: u32) -> Self {
+		let data = Data::Raw(vec![0; 32]);
+
+		IdentityInfo {
+			additional: vec![(data.clone(), data.clone()); num_fields as usize],
+			display: data.clone(),
+			legal: data.clone(),
+			web: data.clone(),
+			riot: data.clone(),
+			email: data.clone(),
+			pgp_fingerprint: Some([0; 20]),
+			image: data.clone(),
+			twitter: data,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn all_fields() -> Self::FieldsIdentifier {
+		IdentityFields(BitFlags::all())
+	}
+}
+
 /// Information concerning the identity of the controller of an account.
 ///
 /// NOTE: This is stored separately primarily to facilitate the addition of extra fields in a
 /// backwards compatible way through a specialized `Decode` impl.
 #[derive(Clone, Encode, Eq, PartialEq, RuntimeDebug)]
 pub struct Registration<
-	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq
+	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq,
+	Info: Encode + Decode + Clone + Debug + Eq + PartialEq,
 > {
 	/// Judgements from the registrars on this identity. Stored ordered by `RegistrarIndex`. There
 	/// may be only a single judgement from each registrar.
@@ -359,12 +472,13 @@ pub struct Registration<
 	pub deposit: Balance,
 
 	/// Information on the identity.
-	pub info: IdentityInfo,
+	pub info: Info,
 }
 
 impl <
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq + Zero + Add,
-> Registration<Balance> {
+	Info: Encode + Decode + Clone + Debug + Eq + PartialEq,
+> Registration<Balance, Info> {
 	fn total_deposit(&self) -> Balance {
 		self.deposit + self.judgements.iter()
 			.map(|(_, ref j)| if let Judgement::FeePaid(fee) = j { *fee } else { Zero::zero() })
@@ -374,7 +488,8 @@ impl <
 
 impl<
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq,
-> Decode for Registration<Balance> {
+	Info: Encode + Decode + Clone + Debug + Eq + PartialEq,
+> Decode for Registration<Balance, Info> {
 	fn decode<I: codec::Input>(input: &mut I) -> sp_std::result::Result<Self, codec::Error> {
 		let (judgements, deposit, info) = Decode::decode(&mut AppendZerosInput::new(input))?;
 		Ok(Self { judgements, deposit, info })
@@ -385,7 +500,8 @@ impl<
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
 pub struct RegistrarInfo<
 	Balance: Encode + Decode + Clone + Debug + Eq + PartialEq,
-	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq
+	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	FieldsIdentifier: Encode + Decode + Clone + Debug + Eq + PartialEq,
 > {
 	/// The account of the registrar.
 	pub account: AccountId,
@@ -395,7 +511,17 @@ pub struct RegistrarInfo<
 
 	/// Relevant fields for this registrar. Registrar judgements are limited to attestations on
 	/// these fields.
-	pub fields: IdentityFields,
+	pub fields: FieldsIdentifier,
+}
+
+/// Information concerning a username authority.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AuthorityProperties<Suffix> {
+	/// The suffix appended to all usernames granted by this authority.
+	pub suffix: Suffix,
+
+	/// The number of usernames that this authority may still grant.
+	pub allocation: u32,
 }
 
 decl_storage! {
@@ -404,7 +530,8 @@ decl_storage! {
 		///
 		/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
 		pub IdentityOf get(fn identity):
-			map hasher(twox_64_concat) T::AccountId => Option<Registration<BalanceOf<T>>>;
+			map hasher(twox_64_concat) T::AccountId
+			=> Option<Registration<BalanceOf<T>, T::IdentityInformation>>;
 
 		/// The super-identity of an alternative "sub" identity together with its name, within that
 		/// context. If the account is not some other account's sub-identity, then just `None`.
@@ -423,7 +550,28 @@ decl_storage! {
 		/// special origin (likely a council motion).
 		///
 		/// The index into this can be cast to `RegistrarIndex` to get a valid value.
-		pub Registrars get(fn registrars): Vec<Option<RegistrarInfo<BalanceOf<T>, T::AccountId>>>;
+		pub Registrars get(fn registrars):
+			Vec<Option<RegistrarInfo<BalanceOf<T>, T::AccountId, FieldsIdentifierOf<T>>>>;
+
+		/// The accounts that may grant usernames, together with their suffix and remaining
+		/// allocation.
+		pub UsernameAuthorities get(fn username_authority):
+			map hasher(blake2_128_concat) T::AccountId => Option<AuthorityProperties<Suffix<T>>>;
+
+		/// The account that an accepted username resolves to.
+		pub AccountOfUsername get(fn account_of_username):
+			map hasher(blake2_128_concat) Username<T> => Option<T::AccountId>;
+
+		/// The accepted username of an account.
+		///
+		/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+		pub UsernameOf get(fn username_of):
+			map hasher(twox_64_concat) T::AccountId => Option<Username<T>>;
+
+		/// Usernames that were granted by an authority but not yet accepted, together with the
+		/// account they were granted to and the last block at which they may be accepted.
+		pub PendingUsernames get(fn pending_username):
+			map hasher(blake2_128_concat) Username<T> => Option<(T::AccountId, T::BlockNumber)>;
 	}
 }
 
@@ -451,6 +599,18 @@ decl_event!(
 		/// A sub-identity was cleared, and the given deposit repatriated from the
 		/// main identity account to the sub-identity account. \[sub, main, deposit\]
 		SubIdentityRevoked(AccountId, AccountId, Balance),
+		/// A username authority was added. \[authority\]
+		UsernameAuthorityAdded(AccountId),
+		/// A username authority was removed. \[authority\]
+		UsernameAuthorityRemoved(AccountId),
+		/// A username was granted and awaits acceptance. \[who, username\]
+		UsernameQueued(AccountId, Vec<u8>),
+		/// A username was accepted. \[who, username\]
+		UsernameSet(AccountId, Vec<u8>),
+		/// A granted username expired without being accepted. \[username\]
+		PreapprovalExpired(Vec<u8>),
+		/// A username was removed. \[who, username\]
+		UsernameRemoved(AccountId, Vec<u8>),
 	}
 );
 
@@ -488,7 +648,25 @@ decl_error! {
 		/// Sender is not a sub-account.
 		NotSub,
 		/// Sub-account isn't owned by sender.
-		NotOwned
+		NotOwned,
+		/// The sender is not a username authority.
+		NotUsernameAuthority,
+		/// The authority cannot grant any more usernames.
+		NoAllocation,
+		/// The suffix is empty, too long or contains invalid characters.
+		InvalidSuffix,
+		/// The username is empty, too long or contains invalid characters.
+		InvalidUsername,
+		/// The username is already taken or awaiting acceptance.
+		UsernameTaken,
+		/// The username does not exist.
+		NoUsername,
+		/// The username was granted to or belongs to another account.
+		NotUsernameOwner,
+		/// The time to accept the username has passed.
+		UsernameExpired,
+		/// The username can still be accepted.
+		NotExpired,
 	}
 }
 
@@ -517,6 +695,15 @@ decl_module! {
 		/// of, e.g., updating judgements.
 		const MaxRegistrars: u32 = T::MaxRegistrars::get();
 
+		/// The number of blocks within which a granted username must be accepted.
+		const PendingUsernameExpiration: T::BlockNumber = T::PendingUsernameExpiration::get();
+
+		/// The maximum length of a suffix.
+		const MaxSuffixLength: u32 = T::MaxSuffixLength::get();
+
+		/// The maximum length of a username, including its suffix and the `.` separating the two.
+		const MaxUsernameLength: u32 = T::MaxUsernameLength::get();
+
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
@@ -576,9 +763,9 @@ decl_module! {
 			T::MaxRegistrars::get().into(), // R
 			T::MaxAdditionalFields::get().into(), // X
 		)]
-		fn set_identity(origin, info: IdentityInfo) -> DispatchResultWithPostInfo {
+		fn set_identity(origin, info: T::IdentityInformation) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			let extra_fields = info.additional.len() as u32;
+			let extra_fields = info.additional() as u32;
 			ensure!(extra_fields <= T::MaxAdditionalFields::get(), Error::<T>::TooManyFields);
 			let fd = <BalanceOf<T>>::from(extra_fields) * T::FieldDeposit::get();
 
@@ -723,7 +910,7 @@ decl_module! {
 			Ok(Some(T::WeightInfo::clear_identity(
 				id.judgements.len() as u32, // R
 				sub_ids.len() as u32, // S
-				id.info.additional.len() as u32 // X
+				id.info.additional() as u32 // X
 			)).into())
 		}
 
@@ -778,7 +965,7 @@ decl_module! {
 			T::Currency::reserve(&sender, registrar.fee)?;
 
 			let judgements = id.judgements.len();
-			let extra_fields = id.info.additional.len();
+			let extra_fields = id.info.additional();
 			<IdentityOf<T>>::insert(&sender, id);
 
 			Self::deposit_event(RawEvent::JudgementRequested(sender, reg_index));
@@ -825,7 +1012,7 @@ decl_module! {
 			let err_amount = T::Currency::unreserve(&sender, fee);
 			debug_assert!(err_amount.is_zero());
 			let judgements = id.judgements.len();
-			let extra_fields = id.info.additional.len();
+			let extra_fields = id.info.additional();
 			<IdentityOf<T>>::insert(&sender, id);
 
 			Self::deposit_event(RawEvent::JudgementUnrequested(sender, reg_index));
@@ -912,7 +1099,7 @@ decl_module! {
 		#[weight = T::WeightInfo::set_fields(T::MaxRegistrars::get())] // R
 		fn set_fields(origin,
 			#[compact] index: RegistrarIndex,
-			fields: IdentityFields,
+			fields: FieldsIdentifierOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
@@ -978,7 +1165,7 @@ decl_module! {
 			}
 
 			let judgements = id.judgements.len();
-			let extra_fields = id.info.additional.len();
+			let extra_fields = id.info.additional();
 			<IdentityOf<T>>::insert(&target, id);
 			Self::deposit_event(RawEvent::JudgementGiven(target, reg_index));
 
@@ -1032,7 +1219,7 @@ decl_module! {
 			Ok(Some(T::WeightInfo::kill_identity(
 				id.judgements.len() as u32, // R
 				sub_ids.len() as u32, // S
-				id.info.additional.len() as u32 // X
+				id.info.additional() as u32 // X
 			)).into())
 		}
 
@@ -1127,6 +1314,171 @@ decl_module! {
 				Self::deposit_event(RawEvent::SubIdentityRevoked(sender, sup.clone(), deposit));
 			});
 		}
+
+		/// Add an account that may grant usernames ending in `suffix`.
+		///
+		/// The dispatch origin for this call must be `T::UsernameAuthorityOrigin`.
+		///
+		/// - `authority`: the account of the authority.
+		/// - `suffix`: the suffix appended to all usernames it grants. It must only contain
+		///   lowercase ASCII letters and digits.
+		/// - `allocation`: the number of usernames the authority may grant.
+		///
+		/// Emits `UsernameAuthorityAdded` if successful.
+		#[weight = T::WeightInfo::add_username_authority()]
+		fn add_username_authority(origin,
+			authority: <T::Lookup as StaticLookup>::Source,
+			suffix: Vec<u8>,
+			allocation: u32,
+		) {
+			T::UsernameAuthorityOrigin::ensure_origin(origin)?;
+			let authority = T::Lookup::lookup(authority)?;
+			ensure!(Self::is_valid_username_part(&suffix), Error::<T>::InvalidSuffix);
+			let suffix = Suffix::<T>::try_from(suffix).map_err(|_| Error::<T>::InvalidSuffix)?;
+
+			UsernameAuthorities::<T>::insert(&authority, AuthorityProperties { suffix, allocation });
+			Self::deposit_event(RawEvent::UsernameAuthorityAdded(authority));
+		}
+
+		/// Remove a username authority. Usernames it granted are unaffected.
+		///
+		/// The dispatch origin for this call must be `T::UsernameAuthorityOrigin`.
+		///
+		/// - `authority`: the account of the authority.
+		///
+		/// Emits `UsernameAuthorityRemoved` if successful.
+		#[weight = T::WeightInfo::remove_username_authority()]
+		fn remove_username_authority(origin, authority: <T::Lookup as StaticLookup>::Source) {
+			T::UsernameAuthorityOrigin::ensure_origin(origin)?;
+			let authority = T::Lookup::lookup(authority)?;
+			ensure!(
+				UsernameAuthorities::<T>::contains_key(&authority),
+				Error::<T>::NotUsernameAuthority,
+			);
+
+			UsernameAuthorities::<T>::remove(&authority);
+			Self::deposit_event(RawEvent::UsernameAuthorityRemoved(authority));
+		}
+
+		/// Grant the username `username` followed by the authority's suffix to `who`.
+		///
+		/// The username must then be accepted by `who` through `accept_username` within
+		/// `PendingUsernameExpiration` blocks. One unit of the authority's allocation is used up,
+		/// whether or not the username is accepted.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must be a username
+		/// authority.
+		///
+		/// - `who`: the account the username is granted to.
+		/// - `username`: the username without suffix. It must only contain lowercase ASCII letters
+		///   and digits.
+		///
+		/// Emits `UsernameQueued` if successful.
+		#[weight = T::WeightInfo::set_username_for()]
+		fn set_username_for(origin,
+			who: <T::Lookup as StaticLookup>::Source,
+			username: Vec<u8>,
+		) {
+			let sender = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let mut authority = UsernameAuthorities::<T>::get(&sender)
+				.ok_or(Error::<T>::NotUsernameAuthority)?;
+			ensure!(authority.allocation > 0, Error::<T>::NoAllocation);
+			ensure!(Self::is_valid_username_part(&username), Error::<T>::InvalidUsername);
+
+			let mut full_username = username;
+			full_username.push(b'.');
+			full_username.extend_from_slice(&authority.suffix);
+			let username = Username::<T>::try_from(full_username)
+				.map_err(|_| Error::<T>::InvalidUsername)?;
+			ensure!(
+				!AccountOfUsername::<T>::contains_key(&username) &&
+					!PendingUsernames::<T>::contains_key(&username),
+				Error::<T>::UsernameTaken,
+			);
+
+			authority.allocation -= 1;
+			UsernameAuthorities::<T>::insert(&sender, authority);
+
+			let expiration = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::PendingUsernameExpiration::get());
+			PendingUsernames::<T>::insert(&username, (who.clone(), expiration));
+			Self::deposit_event(RawEvent::UsernameQueued(who, username.into_inner()));
+		}
+
+		/// Accept a username that was granted to the sender by an authority.
+		///
+		/// Any username the sender had before is removed.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must be the account
+		/// the username was granted to.
+		///
+		/// - `username`: the full username, including its suffix.
+		///
+		/// Emits `UsernameSet` if successful.
+		#[weight = T::WeightInfo::accept_username()]
+		fn accept_username(origin, username: Vec<u8>) {
+			let sender = ensure_signed(origin)?;
+			let username = Username::<T>::try_from(username).map_err(|_| Error::<T>::NoUsername)?;
+			let (approved_for, expiration) = PendingUsernames::<T>::get(&username)
+				.ok_or(Error::<T>::NoUsername)?;
+			ensure!(approved_for == sender, Error::<T>::NotUsernameOwner);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= expiration,
+				Error::<T>::UsernameExpired,
+			);
+
+			PendingUsernames::<T>::remove(&username);
+			if let Some(old_username) = UsernameOf::<T>::get(&sender) {
+				AccountOfUsername::<T>::remove(&old_username);
+			}
+			AccountOfUsername::<T>::insert(&username, &sender);
+			UsernameOf::<T>::insert(&sender, &username);
+			Self::deposit_event(RawEvent::UsernameSet(sender, username.into_inner()));
+		}
+
+		/// Remove a granted username that was not accepted in time.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `username`: the full username, including its suffix.
+		///
+		/// Emits `PreapprovalExpired` if successful.
+		#[weight = T::WeightInfo::remove_expired_approval()]
+		fn remove_expired_approval(origin, username: Vec<u8>) {
+			ensure_signed(origin)?;
+			let username = Username::<T>::try_from(username).map_err(|_| Error::<T>::NoUsername)?;
+			let (_, expiration) = PendingUsernames::<T>::get(&username)
+				.ok_or(Error::<T>::NoUsername)?;
+			ensure!(frame_system::Pallet::<T>::block_number() > expiration, Error::<T>::NotExpired);
+
+			PendingUsernames::<T>::remove(&username);
+			Self::deposit_event(RawEvent::PreapprovalExpired(username.into_inner()));
+		}
+
+		/// Remove an accepted username.
+		///
+		/// The dispatch origin for this call must be either `T::ForceOrigin` or _Signed_ by the
+		/// account the username resolves to.
+		///
+		/// - `username`: the full username, including its suffix.
+		///
+		/// Emits `UsernameRemoved` if successful.
+		#[weight = T::WeightInfo::remove_username()]
+		fn remove_username(origin, username: Vec<u8>) {
+			let maybe_sender = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+			let username = Username::<T>::try_from(username).map_err(|_| Error::<T>::NoUsername)?;
+			let who = AccountOfUsername::<T>::get(&username).ok_or(Error::<T>::NoUsername)?;
+			if let Some(sender) = maybe_sender {
+				ensure!(sender == who, Error::<T>::NotUsernameOwner);
+			}
+
+			AccountOfUsername::<T>::remove(&username);
+			UsernameOf::<T>::remove(&who);
+			Self::deposit_event(RawEvent::UsernameRemoved(who, username.into_inner()));
+		}
 	}
 }
 
@@ -1138,5 +1490,11 @@ impl<T: Config> Module<T> {
 			.filter_map(|a| SuperOf::<T>::get(&a).map(|x| (a, x.1)))
 			.collect()
 	}
+
+	/// Check that a username or suffix is non-empty and only consists of lowercase ASCII letters
+	/// and digits.
+	fn is_valid_username_part(part: &[u8]) -> bool {
+		!part.is_empty() && part.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+	}
 }
 
@@ -92,6 +92,9 @@ parameter_types! {
 	pub const MaxSubAccounts: u32 = 2;
 	pub const MaxAdditionalFields: u32 = 2;
 	pub const MaxRegistrars: u32 = 20;
+	pub const PendingUsernameExpiration: u64 = 10;
+	pub const MaxSuffixLength: u32 = 7;
+	pub const MaxUsernameLength: u32 = 16;
 }
 ord_parameter_types! {
 	pub const One: u64 = 1;
@@ -117,8 +120,13 @@ impl Config for Test {
 	type MaxSubAccounts = MaxSubAccounts;
 	type MaxAdditionalFields = MaxAdditionalFields;
 	type MaxRegistrars = MaxRegistrars;
+	type IdentityInformation = IdentityInfo;
 	type RegistrarOrigin = EnsureOneOrRoot;
 	type ForceOrigin = EnsureTwoOrRoot;
+	type UsernameAuthorityOrigin = EnsureOneOrRoot;
+	type PendingUsernameExpiration = PendingUsernameExpiration;
+	type MaxSuffixLength = MaxSuffixLength;
+	type MaxUsernameLength = MaxUsernameLength;
 	type WeightInfo = ();
 }
 
@@ -471,3 +479,150 @@ fn setting_account_id_should_work() {
 		assert_ok!(Identity::set_account_id(Origin::signed(4), 0, 3));
 	});
 }
+
+#[test]
+fn has_identity_checks_requested_fields() {
+	let info = ten();
+	assert!(info.has_identity(IdentityFields(IdentityField::Display | IdentityField::Legal)));
+	assert!(info.has_identity(IdentityFields::default()));
+	assert!(!info.has_identity(IdentityFields(IdentityField::Display | IdentityField::Email)));
+	assert!(!info.has_identity(IdentityFields(IdentityField::PgpFingerprint.into())));
+}
+
+#[test]
+fn adding_username_authority_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::add_username_authority(Origin::signed(2), 3, b"dot".to_vec(), 1),
+			BadOrigin,
+		);
+		assert_noop!(
+			Identity::add_username_authority(Origin::signed(1), 3, b"Dot".to_vec(), 1),
+			Error::<Test>::InvalidSuffix,
+		);
+		assert_noop!(
+			Identity::add_username_authority(Origin::signed(1), 3, b"toolongsuffix".to_vec(), 1),
+			Error::<Test>::InvalidSuffix,
+		);
+		assert_ok!(Identity::add_username_authority(Origin::signed(1), 3, b"dot".to_vec(), 1));
+		let suffix = Suffix::<Test>::try_from(b"dot".to_vec()).unwrap();
+		assert_eq!(
+			Identity::username_authority(3),
+			Some(AuthorityProperties { suffix, allocation: 1 }),
+		);
+
+		assert_ok!(Identity::remove_username_authority(Origin::root(), 3));
+		assert_eq!(Identity::username_authority(3), None);
+		assert_noop!(
+			Identity::remove_username_authority(Origin::root(), 3),
+			Error::<Test>::NotUsernameAuthority,
+		);
+	});
+}
+
+#[test]
+fn granting_and_accepting_username_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_username_authority(Origin::signed(1), 3, b"dot".to_vec(), 2));
+		assert_noop!(
+			Identity::set_username_for(Origin::signed(4), 10, b"ten".to_vec()),
+			Error::<Test>::NotUsernameAuthority,
+		);
+		assert_noop!(
+			Identity::set_username_for(Origin::signed(3), 10, b"ten.dot".to_vec()),
+			Error::<Test>::InvalidUsername,
+		);
+		assert_noop!(
+			Identity::set_username_for(Origin::signed(3), 10, b"muchtoolongname".to_vec()),
+			Error::<Test>::InvalidUsername,
+		);
+
+		assert_ok!(Identity::set_username_for(Origin::signed(3), 10, b"ten".to_vec()));
+		assert_eq!(Identity::username_authority(3).unwrap().allocation, 1);
+		assert_noop!(
+			Identity::set_username_for(Origin::signed(3), 20, b"ten".to_vec()),
+			Error::<Test>::UsernameTaken,
+		);
+		// Nothing resolves until the username is accepted.
+		assert_eq!(Identity::username_of(10), None);
+
+		assert_noop!(
+			Identity::accept_username(Origin::signed(20), b"ten.dot".to_vec()),
+			Error::<Test>::NotUsernameOwner,
+		);
+		assert_noop!(
+			Identity::accept_username(Origin::signed(10), b"ten".to_vec()),
+			Error::<Test>::NoUsername,
+		);
+		assert_ok!(Identity::accept_username(Origin::signed(10), b"ten.dot".to_vec()));
+		let username = Username::<Test>::try_from(b"ten.dot".to_vec()).unwrap();
+		assert_eq!(Identity::account_of_username(&username), Some(10));
+		assert_eq!(Identity::username_of(10), Some(username.clone()));
+		assert_eq!(Identity::pending_username(&username), None);
+
+		// A new username replaces the old one.
+		assert_ok!(Identity::set_username_for(Origin::signed(3), 10, b"tenner".to_vec()));
+		assert_noop!(
+			Identity::set_username_for(Origin::signed(3), 20, b"twenty".to_vec()),
+			Error::<Test>::NoAllocation,
+		);
+		assert_ok!(Identity::accept_username(Origin::signed(10), b"tenner.dot".to_vec()));
+		assert_eq!(Identity::account_of_username(&username), None);
+		let username = Username::<Test>::try_from(b"tenner.dot".to_vec()).unwrap();
+		assert_eq!(Identity::account_of_username(&username), Some(10));
+		assert_eq!(Identity::username_of(10), Some(username));
+	});
+}
+
+#[test]
+fn expired_username_approvals_can_be_removed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_username_authority(Origin::signed(1), 3, b"dot".to_vec(), 1));
+		assert_ok!(Identity::set_username_for(Origin::signed(3), 10, b"ten".to_vec()));
+		assert_noop!(
+			Identity::remove_expired_approval(Origin::signed(20), b"ten.dot".to_vec()),
+			Error::<Test>::NotExpired,
+		);
+
+		System::set_block_number(1 + PendingUsernameExpiration::get() + 1);
+		assert_noop!(
+			Identity::accept_username(Origin::signed(10), b"ten.dot".to_vec()),
+			Error::<Test>::UsernameExpired,
+		);
+		assert_ok!(Identity::remove_expired_approval(Origin::signed(20), b"ten.dot".to_vec()));
+		let username = Username::<Test>::try_from(b"ten.dot".to_vec()).unwrap();
+		assert_eq!(Identity::pending_username(&username), None);
+		assert_noop!(
+			Identity::remove_expired_approval(Origin::signed(20), b"ten.dot".to_vec()),
+			Error::<Test>::NoUsername,
+		);
+	});
+}
+
+#[test]
+fn removing_username_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_username_authority(Origin::signed(1), 3, b"dot".to_vec(), 2));
+		assert_ok!(Identity::set_username_for(Origin::signed(3), 10, b"ten".to_vec()));
+		assert_ok!(Identity::accept_username(Origin::signed(10), b"ten.dot".to_vec()));
+		assert_ok!(Identity::set_username_for(Origin::signed(3), 20, b"twenty".to_vec()));
+		assert_ok!(Identity::accept_username(Origin::signed(20), b"twenty.dot".to_vec()));
+
+		// Only the owner or the force origin can remove a username.
+		assert_noop!(
+			Identity::remove_username(Origin::signed(20), b"ten.dot".to_vec()),
+			Error::<Test>::NotUsernameOwner,
+		);
+		assert_ok!(Identity::remove_username(Origin::signed(10), b"ten.dot".to_vec()));
+		assert_eq!(Identity::username_of(10), None);
+		assert_ok!(Identity::remove_username(Origin::signed(2), b"twenty.dot".to_vec()));
+		assert_eq!(Identity::username_of(20), None);
+		let username = Username::<Test>::try_from(b"twenty.dot".to_vec()).unwrap();
+		assert_eq!(Identity::account_of_username(&username), None);
+		assert_noop!(
+			Identity::remove_username(Origin::signed(2), b"twenty.dot".to_vec()),
+			Error::<Test>::NoUsername,
+		);
+	});
+}
@@ -19,6 +19,10 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
 //! DATE: 2020-10-27, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weights of `add_username_authority`, `remove_username_authority`, `set_username_for`,
+//! `accept_username`, `remove_expired_approval` and `remove_username` are hand-written placeholders
+//! and were not generated by the benchmark CLI.
 
 // Executed Command:
 // target/release/substrate
@@ -59,6 +63,12 @@ pub trait WeightInfo {
 	fn rename_sub(_s: u32, ) -> Weight;
 	fn remove_sub(_s: u32, ) -> Weight;
 	fn quit_sub(_s: u32, ) -> Weight;
+	fn add_username_authority() -> Weight;
+	fn remove_username_authority() -> Weight;
+	fn set_username_for() -> Weight;
+	fn accept_username() -> Weight;
+	fn remove_expired_approval() -> Weight;
+	fn remove_username() -> Weight;
 	
 }
 
@@ -184,6 +194,41 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			
 	}
+	fn add_username_authority() -> Weight {
+		(14_128_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			
+	}
+	fn remove_username_authority() -> Weight {
+		(17_366_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			
+	}
+	fn set_username_for() -> Weight {
+		(32_455_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			
+	}
+	fn accept_username() -> Weight {
+		(30_712_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			
+	}
+	fn remove_expired_approval() -> Weight {
+		(22_908_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			
+	}
+	fn remove_username() -> Weight {
+		(27_561_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			
+	}
 	
 }
 
@@ -308,5 +353,40 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 			
 	}
+	fn add_username_authority() -> Weight {
+		(14_128_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			
+	}
+	fn remove_username_authority() -> Weight {
+		(17_366_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			
+	}
+	fn set_username_for() -> Weight {
+		(32_455_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			
+	}
+	fn accept_username() -> Weight {
+		(30_712_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			
+	}
+	fn remove_expired_approval() -> Weight {
+		(22_908_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			
+	}
+	fn remove_username() -> Weight {
+		(27_561_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			
+	}
 	
 }
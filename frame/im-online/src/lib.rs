@@ -26,10 +26,13 @@
 //! in the current era or session.
 //!
 //! The heartbeat is a signed transaction, which was signed using the session key
-//! and includes the recent best block number of the local validators chain as well
-//! as the [NetworkState](../../client/offchain/struct.NetworkState.html).
+//! and includes the recent best block number of the local validators chain.
 //! It is submitted as an Unsigned Transaction via off-chain workers.
 //!
+//! Validators which neither sent a heartbeat nor authored a block during a session are
+//! reported as unresponsive. The portion of the validator set that may be offline without
+//! being slashed and the maximum slash are configured through [`Config`].
+//!
 //! - [`Config`]
 //! - [`Call`]
 //! - [`Module`]
@@ -76,9 +79,7 @@ pub mod weights;
 
 use sp_application_crypto::RuntimeAppPublic;
 use codec::{Encode, Decode};
-use sp_core::offchain::OpaqueNetworkState;
-use sp_std::prelude::*;
-use sp_std::convert::TryInto;
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
 use sp_runtime::{
 	offchain::storage::StorageValueRef,
 	traits::{AtLeast32BitUnsigned, Convert, Member, Saturating},
@@ -186,7 +187,6 @@ enum OffchainErr<BlockNumber> {
 	AlreadyOnline(u32),
 	FailedSigning,
 	FailedToAcquireLock,
-	NetworkState,
 	SubmitTransaction,
 }
 
@@ -201,7 +201,6 @@ impl<BlockNumber: sp_std::fmt::Debug> sp_std::fmt::Debug for OffchainErr<BlockNu
 				write!(fmt, "Authority {} is already online", auth_idx),
 			OffchainErr::FailedSigning => write!(fmt, "Failed to sign heartbeat"),
 			OffchainErr::FailedToAcquireLock => write!(fmt, "Failed to acquire lock"),
-			OffchainErr::NetworkState => write!(fmt, "Failed to fetch network state"),
 			OffchainErr::SubmitTransaction => write!(fmt, "Failed to submit transaction"),
 		}
 	}
@@ -216,8 +215,6 @@ pub struct Heartbeat<BlockNumber>
 {
 	/// Block number at the time heartbeat is created..
 	pub block_number: BlockNumber,
-	/// A state of local network (peer id and external addresses)
-	pub network_state: OpaqueNetworkState,
 	/// Index of the current session.
 	pub session_index: SessionIndex,
 	/// An index of the authority on the list of validators.
@@ -261,9 +258,20 @@ pub trait Config: SendTransactionTypes<Call<Self>> + frame_system::Config {
 	type ReportUnresponsiveness: ReportOffence<
 		Self::AccountId,
 		IdentificationTuple<Self>,
-		UnresponsivenessOffence<IdentificationTuple<Self>>,
+		UnresponsivenessOffence<IdentificationTuple<Self>, Self>,
 	>;
 
+	/// The portion of the validator set which may be unresponsive during a session without
+	/// any of the unresponsive validators being slashed.
+	type UnresponsivenessThreshold: Get<Perbill>;
+
+	/// The maximum fraction slashed from each unresponsive validator.
+	///
+	/// The slash increases linearly with the number of unresponsive validators above the
+	/// `UnresponsivenessThreshold` and reaches this value once they make up another third of
+	/// the validator set.
+	type MaxUnresponsivenessSlash: Get<Perbill>;
+
 	/// A configuration for base priority of unsigned transactions.
 	///
 	/// This is exposed so that it can be tuned for particular runtime, when
@@ -306,11 +314,11 @@ decl_storage! {
 		/// The current set of keys that may issue a heartbeat.
 		Keys get(fn keys): Vec<T::AuthorityId>;
 
-		/// For each session index, we keep a mapping of `AuthIndex` to
-		/// `offchain::OpaqueNetworkState`.
+		/// For each session index, we keep track of the `AuthIndex`es which sent a
+		/// heartbeat.
 		ReceivedHeartbeats get(fn received_heartbeats):
 			double_map hasher(twox_64_concat) SessionIndex, hasher(twox_64_concat) AuthIndex
-			=> Option<Vec<u8>>;
+			=> bool;
 
 		/// For each session index, we keep a mapping of `ValidatorId<T>` to the
 		/// number of blocks authored by the given authority.
@@ -341,10 +349,8 @@ decl_module! {
 		fn deposit_event() = default;
 
 		/// # <weight>
-		/// - Complexity: `O(K + E)` where K is length of `Keys` (heartbeat.validators_len)
-		///   and E is length of `heartbeat.network_state.external_address`
+		/// - Complexity: `O(K)` where K is length of `Keys` (heartbeat.validators_len)
 		///   - `O(K)`: decoding of length `K`
-		///   - `O(E)`: decoding/encoding of length `E`
 		/// - DbReads: pallet_session `Validators`, pallet_session `CurrentIndex`, `Keys`,
 		///   `ReceivedHeartbeats`
 		/// - DbWrites: `ReceivedHeartbeats`
@@ -353,7 +359,6 @@ decl_module! {
 		// import block with such an extrinsic.
 		#[weight = <T as Config>::WeightInfo::validate_unsigned_and_then_heartbeat(
 			heartbeat.validators_len as u32,
-			heartbeat.network_state.external_addresses.len() as u32,
 		)]
 		fn heartbeat(
 			origin,
@@ -374,11 +379,10 @@ decl_module! {
 			if let (false, Some(public)) = (exists, public) {
 				Self::deposit_event(Event::<T>::HeartbeatReceived(public.clone()));
 
-				let network_state = heartbeat.network_state.encode();
 				<ReceivedHeartbeats>::insert(
 					&current_session,
 					&heartbeat.authority_index,
-					&network_state
+					true,
 				);
 			} else if exists {
 				Err(Error::<T>::DuplicatedHeartbeat)?
@@ -523,11 +527,8 @@ impl<T: Config> Module<T> {
 	) -> OffchainResult<T, ()> {
 		// A helper function to prepare heartbeat call.
 		let prepare_heartbeat = || -> OffchainResult<T, Call<T>> {
-			let network_state = sp_io::offchain::network_state()
-				.map_err(|_| OffchainErr::NetworkState)?;
 			let heartbeat_data = Heartbeat {
 				block_number,
-				network_state,
 				session_index,
 				authority_index,
 				validators_len,
@@ -702,7 +703,11 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Module<T> {
 			Self::deposit_event(RawEvent::SomeOffline(offenders.clone()));
 
 			let validator_set_count = keys.len() as u32;
-			let offence = UnresponsivenessOffence { session_index, validator_set_count, offenders };
+			let offence = UnresponsivenessOffence::new(
+				session_index,
+				validator_set_count,
+				offenders,
+			);
 			if let Err(e) = T::ReportUnresponsiveness::report_offence(vec![], offence) {
 				sp_runtime::print(e);
 			}
@@ -770,9 +775,12 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 }
 
 /// An offence that is filed if a validator didn't send a heartbeat message.
+///
+/// The slash fraction is derived from the `UnresponsivenessThreshold` and
+/// `MaxUnresponsivenessSlash` of the im-online configuration `T`.
 #[derive(RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Clone, PartialEq, Eq))]
-pub struct UnresponsivenessOffence<Offender> {
+pub struct UnresponsivenessOffence<Offender, T> {
 	/// The current session index in which we report the unresponsive validators.
 	///
 	/// It acts as a time measure for unresponsiveness reports and effectively will always point
@@ -782,9 +790,26 @@ pub struct UnresponsivenessOffence<Offender> {
 	pub validator_set_count: u32,
 	/// Authorities that were unresponsive during the current era.
 	pub offenders: Vec<Offender>,
+	_config: PhantomData<T>,
+}
+
+impl<Offender, T> UnresponsivenessOffence<Offender, T> {
+	/// Create a new unresponsiveness offence.
+	pub fn new(
+		session_index: SessionIndex,
+		validator_set_count: u32,
+		offenders: Vec<Offender>,
+	) -> Self {
+		UnresponsivenessOffence {
+			session_index,
+			validator_set_count,
+			offenders,
+			_config: PhantomData,
+		}
+	}
 }
 
-impl<Offender: Clone> Offence<Offender> for UnresponsivenessOffence<Offender> {
+impl<Offender: Clone, T: Config> Offence<Offender> for UnresponsivenessOffence<Offender, T> {
 	const ID: Kind = *b"im-online:offlin";
 	type TimeSlot = SessionIndex;
 
@@ -805,12 +830,14 @@ impl<Offender: Clone> Offence<Offender> for UnresponsivenessOffence<Offender> {
 	}
 
 	fn slash_fraction(offenders: u32, validator_set_count: u32) -> Perbill {
-		// the formula is min((3 * (k - (n / 10 + 1))) / n, 1) * 0.07
-		// basically, 10% can be offline with no slash, but after that, it linearly climbs up to 7%
-		// when 13/30 are offline (around 5% when 1/3 are offline).
-		if let Some(threshold) = offenders.checked_sub(validator_set_count / 10 + 1) {
+		// the formula is min((3 * (k - (n * t + 1))) / n, 1) * s where `t` is the
+		// `UnresponsivenessThreshold` and `s` the `MaxUnresponsivenessSlash`.
+		// e.g. with `t` = 10% and `s` = 7%, 10% can be offline with no slash, but after that,
+		// it linearly climbs up to 7% when 13/30 are offline (around 5% when 1/3 are offline).
+		let allowed_offline = T::UnresponsivenessThreshold::get() * validator_set_count;
+		if let Some(threshold) = offenders.checked_sub(allowed_offline + 1) {
 			let x = Perbill::from_rational(3 * threshold, validator_set_count);
-			x.saturating_mul(Perbill::from_percent(7))
+			x.saturating_mul(T::MaxUnresponsivenessSlash::get())
 		} else {
 			Perbill::default()
 		}
@@ -85,7 +85,7 @@ impl pallet_session::historical::SessionManager<u64, u64> for TestSessionManager
 /// An extrinsic type used for tests.
 pub type Extrinsic = TestXt<Call, ()>;
 type IdentificationTuple = (u64, u64);
-type Offence = crate::UnresponsivenessOffence<IdentificationTuple>;
+type Offence = crate::UnresponsivenessOffence<IdentificationTuple, Runtime>;
 
 thread_local! {
 	pub static OFFENCES: RefCell<Vec<(Vec<u64>, Offence)>> = RefCell::new(vec![]);
@@ -218,6 +218,8 @@ impl frame_support::traits::EstimateNextSessionRotation<u64> for TestNextSession
 
 parameter_types! {
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub static UnresponsivenessThreshold: Perbill = Perbill::from_percent(10);
+	pub static MaxUnresponsivenessSlash: Perbill = Perbill::from_percent(7);
 }
 
 impl Config for Runtime {
@@ -227,6 +229,8 @@ impl Config for Runtime {
 	type NextSessionRotation = TestNextSessionRotation;
 	type ReportUnresponsiveness = OffenceHandler;
 	type UnsignedPriority = UnsignedPriority;
+	type UnresponsivenessThreshold = UnresponsivenessThreshold;
+	type MaxUnresponsivenessSlash = MaxUnresponsivenessSlash;
 	type WeightInfo = ();
 }
 
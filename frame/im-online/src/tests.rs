@@ -21,7 +21,6 @@
 
 use super::*;
 use crate::mock::*;
-use sp_core::OpaquePeerId;
 use sp_core::offchain::{
 	OffchainDbExt,
 	OffchainWorkerExt,
@@ -35,27 +34,50 @@ use sp_runtime::{testing::UintAuthorityId, transaction_validity::TransactionVali
 fn test_unresponsiveness_slash_fraction() {
 	// A single case of unresponsiveness is not slashed.
 	assert_eq!(
-		UnresponsivenessOffence::<()>::slash_fraction(1, 50),
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(1, 50),
 		Perbill::zero(),
 	);
 
 	assert_eq!(
-		UnresponsivenessOffence::<()>::slash_fraction(5, 50),
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(5, 50),
 		Perbill::zero(), // 0%
 	);
 
 	assert_eq!(
-		UnresponsivenessOffence::<()>::slash_fraction(7, 50),
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(7, 50),
 		Perbill::from_parts(4200000), // 0.42%
 	);
 
 	// One third offline should be punished around 5%.
 	assert_eq!(
-		UnresponsivenessOffence::<()>::slash_fraction(17, 50),
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(17, 50),
 		Perbill::from_parts(46200000), // 4.62%
 	);
 }
 
+#[test]
+fn unresponsiveness_slash_fraction_is_configurable() {
+	UnresponsivenessThreshold::set(Perbill::from_percent(20));
+	MaxUnresponsivenessSlash::set(Perbill::from_percent(10));
+
+	// up to 20% of the validators can be offline without being slashed.
+	assert_eq!(
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(11, 50),
+		Perbill::zero(),
+	);
+
+	assert_eq!(
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(12, 50),
+		Perbill::from_parts(6000000), // 0.6%
+	);
+
+	// the slash is capped at the configured maximum.
+	assert_eq!(
+		UnresponsivenessOffence::<(), Runtime>::slash_fraction(50, 50),
+		Perbill::from_percent(10),
+	);
+}
+
 #[test]
 fn should_report_offline_validators() {
 	new_test_ext().execute_with(|| {
@@ -76,15 +98,15 @@ fn should_report_offline_validators() {
 		// then
 		let offences = OFFENCES.with(|l| l.replace(vec![]));
 		assert_eq!(offences, vec![
-			(vec![], UnresponsivenessOffence {
-				session_index: 2,
-				validator_set_count: 3,
-				offenders: vec![
+			(vec![], UnresponsivenessOffence::new(
+				2,
+				3,
+				vec![
 					(1, 1),
 					(2, 2),
 					(3, 3),
 				],
-			})
+			))
 		]);
 
 		// should not report when heartbeat is sent
@@ -96,14 +118,14 @@ fn should_report_offline_validators() {
 		// then
 		let offences = OFFENCES.with(|l| l.replace(vec![]));
 		assert_eq!(offences, vec![
-			(vec![], UnresponsivenessOffence {
-				session_index: 3,
-				validator_set_count: 6,
-				offenders: vec![
+			(vec![], UnresponsivenessOffence::new(
+				3,
+				6,
+				vec![
 					(5, 5),
 					(6, 6),
 				],
-			})
+			))
 		]);
 	});
 }
@@ -119,10 +141,6 @@ fn heartbeat(
 
 	let heartbeat = Heartbeat {
 		block_number,
-		network_state: OpaqueNetworkState {
-			peer_id: OpaquePeerId(vec![1]),
-			external_addresses: vec![],
-		},
 		session_index,
 		authority_index,
 		validators_len: validators.len() as u32,
@@ -238,7 +256,6 @@ fn should_generate_heartbeats() {
 
 		assert_eq!(heartbeat, Heartbeat {
 			block_number: block,
-			network_state: sp_io::offchain::network_state().unwrap(),
 			session_index: 2,
 			authority_index: 2,
 			validators_len: 3,
@@ -264,14 +281,14 @@ fn should_cleanup_received_heartbeats_on_session_end() {
 		let _ = heartbeat(1, 2, 0, 1.into(), Session::validators()).unwrap();
 
 		// the heartbeat is stored
-		assert!(!ImOnline::received_heartbeats(&2, &0).is_none());
+		assert!(ImOnline::received_heartbeats(&2, &0));
 
 		advance_session();
 
 		// after the session has ended we have already processed the heartbeat
 		// message, so any messages received on the previous session should have
 		// been pruned.
-		assert!(ImOnline::received_heartbeats(&2, &0).is_none());
+		assert!(!ImOnline::received_heartbeats(&2, &0));
 	});
 }
 
@@ -350,7 +367,6 @@ fn should_not_send_a_report_if_already_online() {
 
 		assert_eq!(heartbeat, Heartbeat {
 			block_number: 4,
-			network_state: sp_io::offchain::network_state().unwrap(),
 			session_index: 2,
 			authority_index: 0,
 			validators_len: 3,
@@ -22,17 +22,26 @@
 use super::*;
 use frame_system::RawOrigin;
 use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::Saturating;
 
 use crate::Pallet as Indices;
 
 const SEED: u32 = 0;
 
+// Gives `who` enough funds to claim a few indices.
+fn fund<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+	let amount = T::Currency::minimum_balance()
+		.saturating_add(T::Deposit::get())
+		.saturating_mul(10u32.into());
+	T::Currency::mint_into(who, amount)?;
+	Ok(())
+}
+
 benchmarks! {
 	claim {
 		let account_index = T::AccountIndex::from(SEED);
 		let caller: T::AccountId = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller)?;
 	}: _(RawOrigin::Signed(caller.clone()), account_index)
 	verify {
 		assert_eq!(Accounts::<T>::get(account_index).unwrap().0, caller);
@@ -42,9 +51,9 @@ benchmarks! {
 		let account_index = T::AccountIndex::from(SEED);
 		// Setup accounts
 		let caller: T::AccountId = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller)?;
 		let recipient: T::AccountId = account("recipient", 0, SEED);
-		T::Currency::make_free_balance_be(&recipient, BalanceOf::<T>::max_value());
+		fund::<T>(&recipient)?;
 		// Claim the index
 		Indices::<T>::claim(RawOrigin::Signed(caller.clone()).into(), account_index)?;
 	}: _(RawOrigin::Signed(caller.clone()), recipient.clone(), account_index)
@@ -56,7 +65,7 @@ benchmarks! {
 		let account_index = T::AccountIndex::from(SEED);
 		// Setup accounts
 		let caller: T::AccountId = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller)?;
 		// Claim the index
 		Indices::<T>::claim(RawOrigin::Signed(caller.clone()).into(), account_index)?;
 	}: _(RawOrigin::Signed(caller.clone()), account_index)
@@ -68,9 +77,9 @@ benchmarks! {
 		let account_index = T::AccountIndex::from(SEED);
 		// Setup accounts
 		let original: T::AccountId = account("original", 0, SEED);
-		T::Currency::make_free_balance_be(&original, BalanceOf::<T>::max_value());
+		fund::<T>(&original)?;
 		let recipient: T::AccountId = account("recipient", 0, SEED);
-		T::Currency::make_free_balance_be(&recipient, BalanceOf::<T>::max_value());
+		fund::<T>(&recipient)?;
 		// Claim the index
 		Indices::<T>::claim(RawOrigin::Signed(original).into(), account_index)?;
 	}: _(RawOrigin::Root, recipient.clone(), account_index, false)
@@ -82,7 +91,7 @@ benchmarks! {
 		let account_index = T::AccountIndex::from(SEED);
 		// Setup accounts
 		let caller: T::AccountId = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller)?;
 		// Claim the index
 		Indices::<T>::claim(RawOrigin::Signed(caller.clone()).into(), account_index)?;
 	}: _(RawOrigin::Signed(caller.clone()), account_index)
@@ -31,10 +31,10 @@ use sp_runtime::MultiAddress;
 use sp_runtime::traits::{
 	StaticLookup, LookupError, Zero, Saturating, AtLeast32Bit
 };
-use frame_support::traits::{Currency, ReservableCurrency, BalanceStatus::Reserved};
+use frame_support::traits::tokens::fungible::{Inspect, Mutate, MutateHold, BalancedHold};
 pub use weights::WeightInfo;
 
-type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type BalanceOf<T> = <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
 pub use pallet::*;
 
@@ -51,10 +51,10 @@ pub mod pallet {
 		/// can hold.
 		type AccountIndex: Parameter + Member + MaybeSerializeDeserialize + Codec + Default + AtLeast32Bit + Copy;
 
-		/// The currency trait.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency in which the deposits for indices are held.
+		type Currency: Mutate<Self::AccountId> + BalancedHold<Self::AccountId>;
 
-		/// The deposit needed for reserving an index.
+		/// The deposit held for claiming an index.
 		#[pallet::constant]
 		type Deposit: Get<BalanceOf<Self>>;
 
@@ -76,7 +76,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Assign an previously unassigned index.
 		///
-		/// Payment: `Deposit` is reserved from the sender account.
+		/// Payment: `Deposit` is held in the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
@@ -87,7 +87,7 @@ pub mod pallet {
 		/// # <weight>
 		/// - `O(1)`.
 		/// - One storage mutation (codec `O(1)`).
-		/// - One reserve operation.
+		/// - One hold operation.
 		/// - One event.
 		/// -------------------
 		/// - DB Weight: 1 Read/Write (Accounts)
@@ -99,14 +99,14 @@ pub mod pallet {
 			Accounts::<T>::try_mutate(index, |maybe_value| {
 				ensure!(maybe_value.is_none(), Error::<T>::InUse);
 				*maybe_value = Some((who.clone(), T::Deposit::get(), false));
-				T::Currency::reserve(&who, T::Deposit::get())
+				T::Currency::hold(&who, T::Deposit::get())
 			})?;
 			Self::deposit_event(Event::IndexAssigned(who, index));
 			Ok(())
 		}
 
-		/// Assign an index already owned by the sender to another account. The balance reservation
-		/// is effectively transferred to the new account.
+		/// Assign an index already owned by the sender to another account. The deposit is
+		/// transferred to the new account, where it stays on hold.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
@@ -138,8 +138,8 @@ pub mod pallet {
 				let (account, amount, perm) = maybe_value.take().ok_or(Error::<T>::NotAssigned)?;
 				ensure!(!perm, Error::<T>::Permanent);
 				ensure!(&account == &who, Error::<T>::NotOwner);
-				let lost = T::Currency::repatriate_reserved(&who, &new, amount, Reserved)?;
-				*maybe_value = Some((new.clone(), amount.saturating_sub(lost), false));
+				let moved = T::Currency::transfer_held(&who, &new, amount, true, true)?;
+				*maybe_value = Some((new.clone(), moved, false));
 				Ok(())
 			})?;
 			Self::deposit_event(Event::IndexAssigned(new, index));
@@ -148,7 +148,7 @@ pub mod pallet {
 
 		/// Free up an index owned by the sender.
 		///
-		/// Payment: Any previous deposit placed for the index is unreserved in the sender account.
+		/// Payment: Any previous deposit placed for the index is released in the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must own the index.
 		///
@@ -159,7 +159,7 @@ pub mod pallet {
 		/// # <weight>
 		/// - `O(1)`.
 		/// - One storage mutation (codec `O(1)`).
-		/// - One reserve operation.
+		/// - One release operation.
 		/// - One event.
 		/// -------------------
 		/// - DB Weight: 1 Read/Write (Accounts)
@@ -172,7 +172,7 @@ pub mod pallet {
 				let (account, amount, perm) = maybe_value.take().ok_or(Error::<T>::NotAssigned)?;
 				ensure!(!perm, Error::<T>::Permanent);
 				ensure!(&account == &who, Error::<T>::NotOwner);
-				T::Currency::unreserve(&who, amount);
+				T::Currency::release(&who, amount, true)?;
 				Ok(())
 			})?;
 			Self::deposit_event(Event::IndexFreed(index));
@@ -193,7 +193,7 @@ pub mod pallet {
 		/// # <weight>
 		/// - `O(1)`.
 		/// - One storage mutation (codec `O(1)`).
-		/// - Up to one reserve operation.
+		/// - Up to one release operation.
 		/// - One event.
 		/// -------------------
 		/// - DB Weight:
@@ -211,7 +211,7 @@ pub mod pallet {
 
 			Accounts::<T>::mutate(index, |maybe_value| {
 				if let Some((account, amount, _)) = maybe_value.take() {
-					T::Currency::unreserve(&account, amount);
+					let _ = T::Currency::release(&account, amount, true);
 				}
 				*maybe_value = Some((new.clone(), Zero::zero(), freeze));
 			});
@@ -244,7 +244,7 @@ pub mod pallet {
 				let (account, amount, perm) = maybe_value.take().ok_or(Error::<T>::NotAssigned)?;
 				ensure!(!perm, Error::<T>::Permanent);
 				ensure!(&account == &who, Error::<T>::NotOwner);
-				T::Currency::slash_reserved(&who, amount);
+				let _ = T::Currency::slash_held(&who, amount);
 				*maybe_value = Some((account, Zero::zero(), true));
 				Ok(())
 			})?;
@@ -62,6 +62,18 @@ fn freezing_should_work() {
 	});
 }
 
+#[test]
+fn freezing_burns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+		assert_ok!(Indices::claim(Some(1).into(), 0));
+		assert_ok!(Indices::freeze(Some(1).into(), 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::total_issuance(), issuance - 1);
+		assert_eq!(Indices::lookup_index(0), Some(1));
+	});
+}
+
 #[test]
 fn indexing_lookup_should_work() {
 	new_test_ext().execute_with(|| {
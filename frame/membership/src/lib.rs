@@ -173,7 +173,7 @@ decl_module! {
 		///
 		/// May only be called from `T::SwapOrigin`.
 		///
-		/// Prime membership is *not* passed from `remove` to `add`, if extant.
+		/// Prime membership is passed from `remove` to `add`, if extant.
 		#[weight = 50_000_000]
 		pub fn swap_member(origin, remove: T::AccountId, add: T::AccountId) {
 			T::SwapOrigin::ensure_origin(origin)?;
@@ -190,11 +190,17 @@ decl_module! {
 			<Members<T, I>>::put(&members);
 
 			T::MembershipChanged::change_members_sorted(
-				&[add],
-				&[remove],
+				&[add.clone()],
+				&[remove.clone()],
 				&members[..],
 			);
-			Self::rejig_prime(&members);
+
+			if Prime::<T, I>::get() == Some(remove) {
+				Prime::<T, I>::put(&add);
+				T::MembershipChanged::set_prime(Some(add));
+			} else {
+				Self::rejig_prime(&members);
+			}
 
 			Self::deposit_event(RawEvent::MembersSwapped);
 		}
@@ -650,7 +656,7 @@ mod tests {
 			assert_ok!(Membership::swap_member(Origin::signed(3), 10, 25));
 			assert_eq!(Membership::members(), vec![20, 25, 30]);
 			assert_eq!(MEMBERS.with(|m| m.borrow().clone()), Membership::members());
-			assert_eq!(Membership::prime(), None);
+			assert_eq!(Membership::prime(), Some(25));
 			assert_eq!(PRIME.with(|m| *m.borrow()), Membership::prime());
 		});
 	}
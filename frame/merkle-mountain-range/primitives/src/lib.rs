@@ -405,6 +405,10 @@ impl EncodableOpaqueLeaf {
 sp_api::decl_runtime_apis! {
 	/// API to interact with MMR pallet.
 	pub trait MmrApi<Hash: codec::Codec> {
+		/// Return the on-chain MMR root hash.
+		#[skip_initialize_block]
+		fn mmr_root() -> Result<Hash, Error>;
+
 		/// Generate MMR proof for a leaf under given index.
 		#[skip_initialize_block]
 		fn generate_proof(leaf_index: u64) -> Result<(EncodableOpaqueLeaf, Proof<Hash>), Error>;
@@ -255,14 +255,10 @@ benchmarks! {
 		let keys =  ImOnline::<T>::keys();
 		let validator_set_count = keys.len() as u32;
 
-		let slash_fraction = UnresponsivenessOffence::<T::AccountId>::slash_fraction(
+		let slash_fraction = UnresponsivenessOffence::<T::AccountId, T>::slash_fraction(
 			offenders.len() as u32, validator_set_count,
 		);
-		let offence = UnresponsivenessOffence {
-			session_index: 0,
-			validator_set_count,
-			offenders,
-		};
+		let offence = UnresponsivenessOffence::new(0, validator_set_count, offenders);
 		assert_eq!(System::<T>::event_count(), 0);
 	}: {
 		let _ = <T as ImOnlineConfig>::ReportUnresponsiveness::report_offence(
@@ -304,7 +300,7 @@ benchmarks! {
 				.chain(slash_rest.into_iter().map(Into::into))
 				.chain(std::iter::once(<T as OffencesConfig>::Event::from(
 					pallet_offences::Event::Offence(
-						UnresponsivenessOffence::<T>::ID,
+						UnresponsivenessOffence::<T, T>::ID,
 						0_u32.to_le_bytes().to_vec(),
 						true
 					)
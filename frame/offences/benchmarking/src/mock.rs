@@ -82,11 +82,13 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
 	pub const MinimumPeriod: u64 = 5;
+	pub const MaxDrift: u64 = 30_000;
 }
 impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 impl pallet_session::historical::Config for Test {
@@ -176,9 +178,15 @@ impl pallet_staking::Config for Test {
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const UnresponsivenessThreshold: Perbill = Perbill::from_percent(10);
+	pub const MaxUnresponsivenessSlash: Perbill = Perbill::from_percent(7);
+}
+
 impl pallet_im_online::Config for Test {
 	type AuthorityId = UintAuthorityId;
 	type Event = Event;
@@ -186,6 +194,8 @@ impl pallet_im_online::Config for Test {
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type ReportUnresponsiveness = Offences;
 	type UnsignedPriority = ();
+	type UnresponsivenessThreshold = UnresponsivenessThreshold;
+	type MaxUnresponsivenessSlash = MaxUnresponsivenessSlash;
 	type WeightInfo = ();
 }
 
@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Benchmarks for Parameters pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+use frame_system::{EventRecord, Pallet as System};
+
+use crate::Pallet as Parameters;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	set_parameter {
+		let origin = T::UpdateOrigin::successful_origin();
+		let parameter = T::Parameters::current().pop().ok_or("No parameters declared")?;
+	}: _<T::Origin>(origin, parameter.clone())
+	verify {
+		assert_last_event::<T>(Event::ParameterSet(parameter).into());
+	}
+}
+
+impl_benchmark_test_suite!(Parameters, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,62 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Tests for Parameters pallet.
+
+use super::{Event, mock::{self, *}};
+use frame_support::{assert_noop, assert_ok, traits::{DynamicParameters, Get}};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn parameters_default_to_their_declared_value() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<ByteDeposit as Get<u64>>::get(), 10);
+		assert_eq!(<MaxItems as Get<u32>>::get(), 100);
+		assert_eq!(
+			TestParameters::current(),
+			vec![TestParameters::ByteDeposit(10), TestParameters::MaxItems(100)],
+		);
+	});
+}
+
+#[test]
+fn set_parameter_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Parameters::set_parameter(Origin::root(), TestParameters::ByteDeposit(42)));
+
+		assert_eq!(<ByteDeposit as Get<u64>>::get(), 42);
+		assert_eq!(<MaxItems as Get<u32>>::get(), 100);
+		assert_eq!(
+			System::events().last().unwrap().event,
+			mock::Event::Parameters(Event::ParameterSet(TestParameters::ByteDeposit(42))),
+		);
+	});
+}
+
+#[test]
+fn set_parameter_requires_update_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Parameters::set_parameter(Origin::signed(1), TestParameters::MaxItems(1)),
+			BadOrigin,
+		);
+		assert_eq!(<MaxItems as Get<u32>>::get(), 100);
+	});
+}
@@ -0,0 +1,35 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the poll pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_poll::{PollIndex, PollResults};
+
+sp_api::decl_runtime_apis! {
+	pub trait PollApi<Balance> where
+		Balance: Codec,
+	{
+		/// The results of a poll, or `None` if there is no such poll.
+		///
+		/// The results of an ongoing poll are those it would have if it was closed now.
+		fn results(index: PollIndex) -> Option<PollResults<Balance>>;
+	}
+}
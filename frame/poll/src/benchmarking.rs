@@ -0,0 +1,112 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Poll pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use sp_std::vec;
+use sp_runtime::traits::Bounded;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+
+use crate::Pallet as Poll;
+
+const SEED: u32 = 0;
+
+/// Give `who` a balance, small enough that the total issuance does not overflow when every
+/// possible voter is funded.
+fn fund<T: Config>(who: &T::AccountId) {
+	let balance = BalanceOf::<T>::max_value() / (T::MaxVoters::get() + 2).into();
+	T::Currency::make_free_balance_be(who, balance);
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	fund::<T>(&who);
+	who
+}
+
+/// Create a poll with the largest description and number of choices, which ends in one block.
+fn create_poll<T: Config>() -> PollIndex {
+	let creator = funded_account::<T>("creator", 0);
+	let description = vec![0u8; T::MaxDescriptionLength::get() as usize];
+	Poll::<T>::create(
+		RawOrigin::Signed(creator).into(),
+		description,
+		T::MaxChoices::get(),
+		TallyStrategy::Quadratic,
+		1u32.into(),
+	).expect("creating a poll must be successful");
+	Poll::<T>::poll_count() - 1
+}
+
+benchmarks! {
+	create {
+		let d in 0 .. T::MaxDescriptionLength::get();
+
+		let caller = funded_account::<T>("caller", 0);
+		let description = vec![0u8; d as usize];
+	}: _(
+		RawOrigin::Signed(caller),
+		description,
+		T::MaxChoices::get(),
+		TallyStrategy::Quadratic,
+		1u32.into()
+	)
+	verify {
+		assert!(matches!(Poll::<T>::poll_info(0), Some(PollInfo::Ongoing(_))));
+	}
+
+	vote {
+		let index = create_poll::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), index, 1)
+	verify {
+		assert!(VotesFor::<T>::contains_key(index, &caller));
+	}
+
+	remove_vote {
+		let index = create_poll::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		Poll::<T>::vote(RawOrigin::Signed(caller.clone()).into(), index, 1)?;
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		assert!(!VotesFor::<T>::contains_key(index, &caller));
+	}
+
+	close {
+		let v in 0 .. T::MaxVoters::get();
+
+		let index = create_poll::<T>();
+		for i in 0 .. v {
+			let voter = funded_account::<T>("voter", i);
+			Poll::<T>::vote(RawOrigin::Signed(voter).into(), index, 1)?;
+		}
+		frame_system::Pallet::<T>::set_block_number(2u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert!(matches!(Poll::<T>::poll_info(index), Some(PollInfo::Finished { .. })));
+		assert_eq!(VotesFor::<T>::iter_prefix(index).count(), 0);
+	}
+}
+
+impl_benchmark_test_suite!(Poll, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,325 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Poll Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! Pallet for advisory polls of token holders. A poll has a free-form description and a number
+//! of choices, and nothing is dispatched whatever its outcome; it merely records the opinion of
+//! the token holders.
+//!
+//! Votes do not lock any funds. Instead the total issuance is snapshotted when a poll is created,
+//! and the balance of every voter is recorded when they vote. Once the poll has ended, each vote
+//! is counted with the smaller of the recorded balance and the voter's balance at that point, so
+//! funds which were moved to another account after voting can not be counted twice.
+//!
+//! How the balance behind a vote turns into voting power is chosen for each poll, see
+//! [`TallyStrategy`]. The results are available through the `PollApi` runtime API, also while a
+//! poll is still ongoing.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `create` - Create a poll, placing a deposit which is returned when it is closed.
+//! - `vote` - Vote for one of the choices of an ongoing poll, or change a previous vote.
+//! - `remove_vote` - Remove a vote from an ongoing poll.
+//! - `close` - Tally the votes of a poll which has ended.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::traits::{Saturating, Zero};
+use frame_support::traits::{Currency, ReservableCurrency};
+
+mod types;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::{OngoingPoll, PollIndex, PollInfo, PollResults, TallyStrategy};
+pub use weights::WeightInfo;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type PollInfoOf<T> = PollInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Currency type with which voting happens, and in which the poll deposit is held.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount held on deposit from the creator of a poll until it is closed.
+		#[pallet::constant]
+		type PollDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum length of the description of a poll.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+
+		/// The maximum number of choices of a poll.
+		#[pallet::constant]
+		type MaxChoices: Get<u8>;
+
+		/// The maximum number of accounts which may vote in a single poll.
+		///
+		/// Closing a poll iterates over all of its votes, so this bounds the weight of `close`.
+		#[pallet::constant]
+		type MaxVoters: Get<u32>;
+	}
+
+	/// The number of polls that have been created so far.
+	#[pallet::storage]
+	#[pallet::getter(fn poll_count)]
+	pub type PollCount<T> = StorageValue<_, PollIndex, ValueQuery>;
+
+	/// Information concerning any given poll.
+	#[pallet::storage]
+	#[pallet::getter(fn poll_info)]
+	pub type PollInfoFor<T: Config> = StorageMap<_, Twox64Concat, PollIndex, PollInfoOf<T>>;
+
+	/// The votes of an ongoing poll: the choice of every voter and their balance when they voted.
+	#[pallet::storage]
+	pub type VotesFor<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PollIndex,
+		Twox64Concat,
+		T::AccountId,
+		(u8, BalanceOf<T>),
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// A poll has been created. \[index, creator\]
+		Created(PollIndex, T::AccountId),
+		/// An account has voted in a poll. \[who, index, choice\]
+		Voted(T::AccountId, PollIndex, u8),
+		/// An account has removed its vote from a poll. \[who, index\]
+		VoteRemoved(T::AccountId, PollIndex),
+		/// A poll has been closed. \[index\]
+		Closed(PollIndex),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The description is longer than `MaxDescriptionLength`.
+		DescriptionTooLong,
+		/// A poll needs at least two and at most `MaxChoices` choices.
+		BadChoices,
+		/// A poll must last at least one block.
+		ZeroDuration,
+		/// The poll does not exist or has been closed.
+		NotOngoing,
+		/// The poll has ended.
+		Ended,
+		/// The poll has not yet ended.
+		NotEnded,
+		/// The choice is not one of those of the poll.
+		InvalidChoice,
+		/// The account has no balance to vote with.
+		NoVotingPower,
+		/// The poll already has `MaxVoters` voters.
+		TooManyVoters,
+		/// The account has not voted in the poll.
+		NotVoter,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a poll which is open for votes for the next `duration` blocks.
+		///
+		/// The origin must be signed, and `PollDeposit` is held from it until the poll is closed.
+		///
+		/// - `description`: A free-form description of the poll.
+		/// - `choices`: The number of choices of the poll.
+		/// - `strategy`: How the votes are tallied.
+		/// - `duration`: The number of blocks for which the poll is open for votes.
+		///
+		/// Emits `Created`.
+		#[pallet::weight(T::WeightInfo::create(description.len() as u32))]
+		pub fn create(
+			origin: OriginFor<T>,
+			description: Vec<u8>,
+			choices: u8,
+			strategy: TallyStrategy,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				description.len() as u32 <= T::MaxDescriptionLength::get(),
+				Error::<T>::DescriptionTooLong,
+			);
+			ensure!(choices >= 2 && choices <= T::MaxChoices::get(), Error::<T>::BadChoices);
+			ensure!(!duration.is_zero(), Error::<T>::ZeroDuration);
+
+			let deposit = T::PollDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+
+			let index = PollCount::<T>::mutate(|count| {
+				let index = *count;
+				*count += 1;
+				index
+			});
+			let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			let poll = OngoingPoll {
+				creator: who.clone(),
+				deposit,
+				description,
+				choices,
+				strategy,
+				end,
+				electorate: T::Currency::total_issuance(),
+				voters: 0,
+			};
+			PollInfoFor::<T>::insert(index, PollInfo::Ongoing(poll));
+
+			Self::deposit_event(Event::Created(index, who));
+			Ok(())
+		}
+
+		/// Vote for `choice` in an ongoing poll, replacing any previous vote of the origin.
+		///
+		/// The vote is counted with the free balance of the origin, which is not locked. Should
+		/// the balance have decreased by the time the poll is closed, only the remainder counts.
+		///
+		/// Emits `Voted`.
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(origin: OriginFor<T>, index: PollIndex, choice: u8) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut poll = Self::ongoing_poll(index)?;
+			ensure!(frame_system::Pallet::<T>::block_number() < poll.end, Error::<T>::Ended);
+			ensure!(choice < poll.choices, Error::<T>::InvalidChoice);
+			let balance = T::Currency::free_balance(&who);
+			ensure!(!balance.is_zero(), Error::<T>::NoVotingPower);
+
+			if !VotesFor::<T>::contains_key(index, &who) {
+				ensure!(poll.voters < T::MaxVoters::get(), Error::<T>::TooManyVoters);
+				poll.voters += 1;
+				PollInfoFor::<T>::insert(index, PollInfo::Ongoing(poll));
+			}
+			VotesFor::<T>::insert(index, &who, (choice, balance));
+
+			Self::deposit_event(Event::Voted(who, index, choice));
+			Ok(())
+		}
+
+		/// Remove the vote of the origin from an ongoing poll.
+		///
+		/// Emits `VoteRemoved`.
+		#[pallet::weight(T::WeightInfo::remove_vote())]
+		pub fn remove_vote(origin: OriginFor<T>, index: PollIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut poll = Self::ongoing_poll(index)?;
+			ensure!(frame_system::Pallet::<T>::block_number() < poll.end, Error::<T>::Ended);
+			VotesFor::<T>::take(index, &who).ok_or(Error::<T>::NotVoter)?;
+
+			poll.voters = poll.voters.saturating_sub(1);
+			PollInfoFor::<T>::insert(index, PollInfo::Ongoing(poll));
+
+			Self::deposit_event(Event::VoteRemoved(who, index));
+			Ok(())
+		}
+
+		/// Tally the votes of a poll which has ended, and return the deposit of its creator.
+		///
+		/// May be called by any signed origin.
+		///
+		/// Emits `Closed`.
+		#[pallet::weight(T::WeightInfo::close(T::MaxVoters::get()))]
+		pub fn close(origin: OriginFor<T>, index: PollIndex) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let poll = Self::ongoing_poll(index)?;
+			ensure!(frame_system::Pallet::<T>::block_number() >= poll.end, Error::<T>::NotEnded);
+
+			let results = Self::tally(&poll, VotesFor::<T>::drain_prefix(index));
+			T::Currency::unreserve(&poll.creator, poll.deposit);
+			PollInfoFor::<T>::insert(index, PollInfo::Finished { end: poll.end, results });
+
+			Self::deposit_event(Event::Closed(index));
+			Ok(Some(T::WeightInfo::close(poll.voters)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The results of a poll, so far if it is still ongoing.
+	///
+	/// Iterates over all votes of an ongoing poll; not meant to be called from within a block.
+	pub fn results(index: PollIndex) -> Option<PollResults<BalanceOf<T>>> {
+		match PollInfoFor::<T>::get(index)? {
+			PollInfo::Ongoing(poll) => Some(Self::tally(&poll, VotesFor::<T>::iter_prefix(index))),
+			PollInfo::Finished { results, .. } => Some(results),
+		}
+	}
+
+	/// Tally `votes`, counting each with the smaller of the balance recorded when voting and the
+	/// current free balance of the voter.
+	fn tally(
+		poll: &OngoingPoll<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		votes: impl Iterator<Item = (T::AccountId, (u8, BalanceOf<T>))>,
+	) -> PollResults<BalanceOf<T>> {
+		let mut results = PollResults::new(poll.choices, poll.electorate);
+		for (who, (choice, balance)) in votes {
+			let balance = balance.min(T::Currency::free_balance(&who));
+			results.add(choice, balance, poll.strategy);
+		}
+		results
+	}
+
+	fn ongoing_poll(
+		index: PollIndex,
+	) -> Result<OngoingPoll<T::AccountId, BalanceOf<T>, T::BlockNumber>, Error<T>> {
+		match PollInfoFor::<T>::get(index) {
+			Some(PollInfo::Ongoing(poll)) => Ok(poll),
+			_ => Err(Error::<T>::NotOngoing),
+		}
+	}
+}
@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Poll pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+
+fn create(choices: u8, strategy: TallyStrategy) -> PollIndex {
+	assert_ok!(Poll::create(Origin::signed(1), b"advice".to_vec(), choices, strategy, 5));
+	Poll::poll_count() - 1
+}
+
+fn results(tally: Vec<u64>, turnout: u64) -> PollResults<u64> {
+	PollResults { tally, turnout, electorate: 3000 }
+}
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		let index = create(2, TallyStrategy::OneTokenOneVote);
+		assert_eq!(index, 0);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(
+			Poll::poll_info(0),
+			Some(PollInfo::Ongoing(OngoingPoll {
+				creator: 1,
+				deposit: 10,
+				description: b"advice".to_vec(),
+				choices: 2,
+				strategy: TallyStrategy::OneTokenOneVote,
+				end: 6,
+				electorate: 3000,
+				voters: 0,
+			})),
+		);
+		assert_eq!(Poll::results(0), Some(results(vec![0, 0], 0)));
+	});
+}
+
+#[test]
+fn create_checks_parameters() {
+	new_test_ext().execute_with(|| {
+		let strategy = TallyStrategy::OneTokenOneVote;
+		assert_noop!(
+			Poll::create(Origin::signed(1), vec![0; 33], 2, strategy, 5),
+			Error::<Test>::DescriptionTooLong,
+		);
+		assert_noop!(
+			Poll::create(Origin::signed(1), vec![], 1, strategy, 5),
+			Error::<Test>::BadChoices,
+		);
+		assert_noop!(
+			Poll::create(Origin::signed(1), vec![], 5, strategy, 5),
+			Error::<Test>::BadChoices,
+		);
+		assert_noop!(
+			Poll::create(Origin::signed(1), vec![], 2, strategy, 0),
+			Error::<Test>::ZeroDuration,
+		);
+		assert_noop!(
+			Poll::create(Origin::signed(5), vec![], 2, strategy, 5),
+			pallet_balances::Error::<Test>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn one_token_one_vote_works() {
+	new_test_ext().execute_with(|| {
+		let index = create(3, TallyStrategy::OneTokenOneVote);
+		assert_ok!(Poll::vote(Origin::signed(2), index, 0));
+		assert_ok!(Poll::vote(Origin::signed(3), index, 1));
+		assert_ok!(Poll::vote(Origin::signed(4), index, 1));
+		assert_eq!(Poll::results(index), Some(results(vec![400, 2500, 0], 2900)));
+
+		assert_noop!(Poll::close(Origin::signed(5), index), Error::<Test>::NotEnded);
+		System::set_block_number(6);
+		assert_noop!(Poll::vote(Origin::signed(1), index, 0), Error::<Test>::Ended);
+		assert_ok!(Poll::close(Origin::signed(5), index));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(VotesFor::<Test>::iter_prefix(index).count(), 0);
+		assert_eq!(
+			Poll::poll_info(index),
+			Some(PollInfo::Finished { end: 6, results: results(vec![400, 2500, 0], 2900) }),
+		);
+		assert_noop!(Poll::close(Origin::signed(5), index), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn quadratic_works() {
+	new_test_ext().execute_with(|| {
+		let index = create(2, TallyStrategy::Quadratic);
+		assert_ok!(Poll::vote(Origin::signed(2), index, 0));
+		assert_ok!(Poll::vote(Origin::signed(3), index, 1));
+		assert_ok!(Poll::vote(Origin::signed(4), index, 0));
+
+		System::set_block_number(6);
+		assert_ok!(Poll::close(Origin::signed(5), index));
+		assert_eq!(Poll::results(index), Some(results(vec![60, 30], 2900)));
+	});
+}
+
+#[test]
+fn moved_funds_are_not_counted_twice() {
+	new_test_ext().execute_with(|| {
+		let index = create(2, TallyStrategy::OneTokenOneVote);
+		assert_ok!(Poll::vote(Origin::signed(2), index, 0));
+		// Voting does not lock anything.
+		assert_ok!(Balances::transfer(Origin::signed(2), 3, 300));
+		assert_ok!(Poll::vote(Origin::signed(3), index, 1));
+		// Funds received after voting do not count either.
+		assert_ok!(Balances::transfer(Origin::signed(4), 3, 500));
+
+		System::set_block_number(6);
+		assert_ok!(Poll::close(Origin::signed(5), index));
+		assert_eq!(Poll::results(index), Some(results(vec![100, 1200], 1300)));
+	});
+}
+
+#[test]
+fn votes_can_be_changed_and_removed() {
+	new_test_ext().execute_with(|| {
+		let index = create(2, TallyStrategy::OneTokenOneVote);
+		assert_noop!(Poll::vote(Origin::signed(2), index, 2), Error::<Test>::InvalidChoice);
+		assert_noop!(Poll::vote(Origin::signed(5), index, 0), Error::<Test>::NoVotingPower);
+		assert_noop!(Poll::vote(Origin::signed(2), 1, 0), Error::<Test>::NotOngoing);
+
+		assert_ok!(Poll::vote(Origin::signed(2), index, 0));
+		assert_ok!(Poll::vote(Origin::signed(2), index, 1));
+		assert_eq!(Poll::results(index), Some(results(vec![0, 400], 400)));
+
+		assert_ok!(Poll::remove_vote(Origin::signed(2), index));
+		assert_noop!(Poll::remove_vote(Origin::signed(2), index), Error::<Test>::NotVoter);
+		assert_eq!(Poll::results(index), Some(results(vec![0, 0], 0)));
+	});
+}
+
+#[test]
+fn voters_are_limited() {
+	new_test_ext().execute_with(|| {
+		let index = create(2, TallyStrategy::OneTokenOneVote);
+		assert_ok!(Poll::vote(Origin::signed(1), index, 0));
+		assert_ok!(Poll::vote(Origin::signed(2), index, 0));
+		assert_ok!(Poll::vote(Origin::signed(3), index, 0));
+		assert_noop!(Poll::vote(Origin::signed(4), index, 0), Error::<Test>::TooManyVoters);
+
+		// Changing a vote is still possible, and removing one makes room for another voter.
+		assert_ok!(Poll::vote(Origin::signed(3), index, 1));
+		assert_ok!(Poll::remove_vote(Origin::signed(1), index));
+		assert_ok!(Poll::vote(Origin::signed(4), index, 1));
+	});
+}
@@ -0,0 +1,101 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, traits::{IntegerSquareRoot, Saturating, Zero}};
+use sp_std::prelude::*;
+
+/// Identifier of a poll.
+pub type PollIndex = u32;
+
+/// How the balance behind a vote is turned into voting power.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TallyStrategy {
+	/// Every token is worth one vote.
+	OneTokenOneVote,
+	/// A balance of `b` tokens is worth `sqrt(b)` votes, which reduces the influence of large
+	/// holders.
+	Quadratic,
+}
+
+impl TallyStrategy {
+	/// The voting power of `balance`.
+	pub fn votes<Balance: IntegerSquareRoot>(self, balance: Balance) -> Balance {
+		match self {
+			TallyStrategy::OneTokenOneVote => balance,
+			TallyStrategy::Quadratic => balance.integer_sqrt(),
+		}
+	}
+}
+
+/// A poll which is still open for votes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct OngoingPoll<AccountId, Balance, BlockNumber> {
+	/// The account which created the poll and placed its deposit.
+	pub creator: AccountId,
+	/// The deposit held from `creator` until the poll is closed.
+	pub deposit: Balance,
+	/// A free-form description of the poll, usually of what each choice stands for.
+	pub description: Vec<u8>,
+	/// The number of choices the voters pick from.
+	pub choices: u8,
+	/// How the votes are tallied.
+	pub strategy: TallyStrategy,
+	/// The block from which on votes are no longer accepted and the poll may be closed.
+	pub end: BlockNumber,
+	/// The total issuance when the poll was created.
+	pub electorate: Balance,
+	/// The number of accounts which have voted.
+	pub voters: u32,
+}
+
+/// The outcome of a poll.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PollResults<Balance> {
+	/// The voting power behind each choice.
+	pub tally: Vec<Balance>,
+	/// The sum of the balances which voted.
+	pub turnout: Balance,
+	/// The total issuance when the poll was created.
+	pub electorate: Balance,
+}
+
+impl<Balance: Copy + Zero + Saturating + IntegerSquareRoot> PollResults<Balance> {
+	/// Empty results of a poll with `choices` choices.
+	pub fn new(choices: u8, electorate: Balance) -> Self {
+		Self { tally: vec![Zero::zero(); choices as usize], turnout: Zero::zero(), electorate }
+	}
+
+	/// Add the vote of `balance` for `choice`, weighed according to `strategy`.
+	pub fn add(&mut self, choice: u8, balance: Balance, strategy: TallyStrategy) {
+		if let Some(votes) = self.tally.get_mut(choice as usize) {
+			*votes = votes.saturating_add(strategy.votes(balance));
+			self.turnout = self.turnout.saturating_add(balance);
+		}
+	}
+}
+
+/// Info regarding a poll, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum PollInfo<AccountId, Balance, BlockNumber> {
+	/// Poll is open for votes, or has ended but not yet been closed.
+	Ongoing(OngoingPoll<AccountId, Balance, BlockNumber>),
+	/// Poll has ended at `end` and was closed with the given results.
+	Finished { end: BlockNumber, results: PollResults<Balance> },
+}
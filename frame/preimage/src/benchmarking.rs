@@ -0,0 +1,93 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Preimage pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use sp_std::{prelude::*, vec};
+use sp_runtime::traits::Bounded;
+use frame_system::RawOrigin;
+use frame_benchmarking::{account, benchmarks, whitelist_account, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+
+use crate::Pallet as Preimage;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, 0);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn preimage_and_hash(len: u32) -> (Vec<u8>, Hash) {
+	let preimage = vec![1u8; len as usize];
+	let hash = sp_io::hashing::blake2_256(&preimage).into();
+	(preimage, hash)
+}
+
+benchmarks! {
+	// Expensive note - will reserve.
+	note_preimage {
+		let s in 0 .. MAX_SIZE;
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let (preimage, hash) = preimage_and_hash(s);
+	}: _(RawOrigin::Signed(caller), preimage)
+	verify {
+		assert!(Preimage::<T>::have(&frame_support::traits::Bounded::<()>::lookup(hash, s)));
+	}
+
+	// Cheap unnote - will unreserve.
+	unnote_preimage {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let (preimage, hash) = preimage_and_hash(MAX_SIZE);
+		assert!(Preimage::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), preimage).is_ok());
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(Preimage::<T>::preimage_for(hash).is_none());
+	}
+
+	// Request a preimage which was noted by a user.
+	request_preimage {
+		let (preimage, hash) = preimage_and_hash(MAX_SIZE);
+		let noter = funded_account::<T>("noter", 0);
+		whitelist_account!(noter);
+		assert!(Preimage::<T>::note_preimage(RawOrigin::Signed(noter.clone()).into(), preimage).is_ok());
+		let origin = T::ManagerOrigin::successful_origin();
+	}: _<T::Origin>(origin, hash)
+	verify {
+		assert!(Preimage::<T>::is_requested(&hash));
+	}
+
+	// Unrequest the last request of a preimage, clearing it.
+	unrequest_preimage {
+		let (preimage, hash) = preimage_and_hash(MAX_SIZE);
+		let origin = T::ManagerOrigin::successful_origin();
+		assert!(Preimage::<T>::note_preimage(origin.clone(), preimage).is_ok());
+	}: _<T::Origin>(origin, hash)
+	verify {
+		assert!(Preimage::<T>::preimage_for(hash).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Preimage,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
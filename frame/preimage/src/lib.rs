@@ -0,0 +1,388 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Preimage Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! The Preimage pallet allows for the users and the runtime to store the preimage of a hash on
+//! chain. This can be used by other pallets for storing and managing large byte-blobs, such as
+//! calls, while only keeping their hash (as part of a `Bounded` value) in their own storage.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `note_preimage` - Store a preimage. A signed origin pays a deposit proportional to its
+//!   length, unless the preimage has already been requested.
+//! - `unnote_preimage` - Remove a preimage which was previously noted, returning any deposit.
+//! - `request_preimage` - Request that a preimage be kept around, and that it may be noted for
+//!   free. Requests are counted.
+//! - `unrequest_preimage` - Remove a previous request for a preimage.
+//!
+//! ### Public Traits
+//!
+//! Other pallets access the preimages through the [`QueryPreimage`] and [`StorePreimage`] traits,
+//! which this pallet implements.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+use sp_std::{prelude::*, borrow::Cow};
+use sp_runtime::traits::Saturating;
+use codec::{Encode, Decode};
+use frame_support::{
+	ensure, parameter_types, RuntimeDebug,
+	traits::{
+		Currency, ReservableCurrency,
+		preimages::{Hash, QueryPreimage, StorePreimage},
+	},
+	storage::bounded_vec::BoundedVec,
+};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Maximum size of preimage we can store is 4mb.
+pub const MAX_SIZE: u32 = 4 * 1024 * 1024;
+
+parameter_types! {
+	/// The bound of the stored preimage data.
+	pub const PreimageMaxSize: u32 = MAX_SIZE;
+}
+
+/// A type to note whether a preimage is owned by a user or the system.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum RequestStatus<AccountId, Balance> {
+	/// The associated preimage has not yet been requested by the system. The given deposit (if
+	/// some) is being held until either it becomes requested or the user retracts the preimage.
+	Unrequested {
+		/// The account which noted the preimage and the deposit it holds for it.
+		deposit: (AccountId, Balance),
+		/// The length of the preimage.
+		len: u32,
+	},
+	/// There are a non-zero number of outstanding requests for this hash by this chain. If there
+	/// is a preimage registered, then it may be removed iff this counter becomes zero.
+	Requested {
+		/// The deposit of the account which noted the preimage, if it was noted before being
+		/// requested.
+		deposit: Option<(AccountId, Balance)>,
+		/// The number of outstanding requests.
+		count: u32,
+		/// The length of the preimage, if it has been noted.
+		len: Option<u32>,
+	},
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The Weight information for this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Currency type for this pallet.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// An origin that can request a preimage be placed on-chain without a deposit or fee, or
+		/// manage existing preimages.
+		type ManagerOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The base deposit for placing a preimage on chain.
+		#[pallet::constant]
+		type BaseDeposit: Get<BalanceOf<Self>>;
+
+		/// The per-byte deposit for placing a preimage on chain.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A preimage has been noted. \[hash\]
+		Noted(Hash),
+		/// A preimage has been requested. \[hash\]
+		Requested(Hash),
+		/// A preimage has been cleared. \[hash\]
+		Cleared(Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Preimage is too large to store on-chain.
+		TooBig,
+		/// Preimage has already been noted on-chain.
+		AlreadyNoted,
+		/// The user is not authorized to perform this action.
+		NotAuthorized,
+		/// The preimage cannot be removed since it has not yet been noted.
+		NotNoted,
+		/// The preimage request cannot be removed since no outstanding requests exist.
+		NotRequested,
+	}
+
+	/// The request status of a given hash.
+	#[pallet::storage]
+	#[pallet::getter(fn status_for)]
+	pub(super) type StatusFor<T: Config> =
+		StorageMap<_, Identity, Hash, RequestStatus<T::AccountId, BalanceOf<T>>>;
+
+	/// The preimages stored by this pallet.
+	#[pallet::storage]
+	#[pallet::getter(fn preimage_for)]
+	pub(super) type PreimageFor<T: Config> =
+		StorageMap<_, Identity, Hash, BoundedVec<u8, PreimageMaxSize>>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a preimage on-chain.
+		///
+		/// If the preimage was previously requested, no fees or deposits are taken for providing
+		/// the preimage. Otherwise, a deposit is taken proportional to the size of the preimage.
+		#[pallet::weight(T::WeightInfo::note_preimage(bytes.len() as u32))]
+		pub fn note_preimage(origin: OriginFor<T>, bytes: Vec<u8>) -> DispatchResultWithPostInfo {
+			// We accept a signed origin which will pay a deposit, or a root origin where a deposit
+			// is not taken.
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			let was_requested = Self::note_bytes(bytes.into(), maybe_sender.as_ref())?;
+			if was_requested && maybe_sender.is_some() {
+				Ok(Pays::No.into())
+			} else {
+				Ok(().into())
+			}
+		}
+
+		/// Clear an unrequested preimage from the runtime storage.
+		///
+		/// If `origin` is the `ManagerOrigin`, then this removes the request that was made when
+		/// the preimage was noted by it.
+		#[pallet::weight(T::WeightInfo::unnote_preimage())]
+		pub fn unnote_preimage(origin: OriginFor<T>, hash: Hash) -> DispatchResult {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			Self::do_unnote_preimage(&hash, maybe_sender)
+		}
+
+		/// Request a preimage be uploaded to the chain without paying any fees or deposits.
+		///
+		/// If the preimage has already been noted by a user, it is kept on-chain for as long as it
+		/// is requested, even if the user unnotes it (which returns their deposit).
+		#[pallet::weight(T::WeightInfo::request_preimage())]
+		pub fn request_preimage(origin: OriginFor<T>, hash: Hash) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_request_preimage(&hash);
+			Ok(())
+		}
+
+		/// Clear a previously made request for a preimage.
+		///
+		/// NOTE: THIS MUST NOT BE CALLED ON `hash` MORE TIMES THAN `request_preimage`.
+		#[pallet::weight(T::WeightInfo::unrequest_preimage())]
+		pub fn unrequest_preimage(origin: OriginFor<T>, hash: Hash) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_unrequest_preimage(&hash)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure that the origin is either the `ManagerOrigin` or a signed origin.
+	fn ensure_signed_or_manager(
+		origin: T::Origin,
+	) -> Result<Option<T::AccountId>, sp_runtime::traits::BadOrigin> {
+		if T::ManagerOrigin::ensure_origin(origin.clone()).is_ok() {
+			return Ok(None)
+		}
+		let who = frame_system::ensure_signed(origin)?;
+		Ok(Some(who))
+	}
+
+	/// Store some preimage on chain.
+	///
+	/// If `maybe_depositor` is `None` then it is also requested. If `Some`, then it is not, and a
+	/// deposit is reserved from the depositor unless the preimage was already requested.
+	///
+	/// Returns whether the preimage was already requested.
+	fn note_bytes(
+		preimage: Cow<[u8]>,
+		maybe_depositor: Option<&T::AccountId>,
+	) -> Result<bool, sp_runtime::DispatchError> {
+		let hash: Hash = sp_io::hashing::blake2_256(&preimage).into();
+		let len = preimage.len() as u32;
+		ensure!(len <= MAX_SIZE, Error::<T>::TooBig);
+
+		let (status, was_requested) = match (StatusFor::<T>::get(&hash), maybe_depositor) {
+			// the preimage was requested, and is now provided.
+			(Some(RequestStatus::Requested { count, deposit, len: None }), Some(_)) =>
+				(RequestStatus::Requested { count, deposit, len: Some(len) }, true),
+			(Some(_), Some(_)) => return Err(Error::<T>::AlreadyNoted.into()),
+			// the system notes the preimage, which counts as a request.
+			(Some(RequestStatus::Requested { count, deposit, .. }), None) => (
+				RequestStatus::Requested { count: count.saturating_add(1), deposit, len: Some(len) },
+				true,
+			),
+			(Some(RequestStatus::Unrequested { deposit, len }), None) =>
+				(RequestStatus::Requested { count: 1, deposit: Some(deposit), len: Some(len) }, false),
+			(None, None) =>
+				(RequestStatus::Requested { count: 1, deposit: None, len: Some(len) }, false),
+			(None, Some(depositor)) => {
+				let deposit = T::BaseDeposit::get()
+					.saturating_add(T::ByteDeposit::get().saturating_mul(len.into()));
+				T::Currency::reserve(depositor, deposit)?;
+				(RequestStatus::Unrequested { deposit: (depositor.clone(), deposit), len }, false)
+			},
+		};
+		StatusFor::<T>::insert(&hash, status);
+
+		if !PreimageFor::<T>::contains_key(&hash) {
+			PreimageFor::<T>::insert(&hash, BoundedVec::force_from(preimage.into_owned(), None));
+			Self::deposit_event(Event::Noted(hash));
+		}
+
+		Ok(was_requested)
+	}
+
+	/// Clear a preimage noted by `maybe_check_owner`, or by the system if `None`.
+	fn do_unnote_preimage(
+		hash: &Hash,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> sp_runtime::DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotNoted)? {
+			RequestStatus::Requested { deposit: Some((owner, deposit)), count, len } => {
+				ensure!(maybe_check_owner.map_or(true, |c| c == owner), Error::<T>::NotAuthorized);
+				T::Currency::unreserve(&owner, deposit);
+				StatusFor::<T>::insert(hash, RequestStatus::Requested { deposit: None, count, len });
+				Ok(())
+			},
+			RequestStatus::Requested { deposit: None, .. } => {
+				ensure!(maybe_check_owner.is_none(), Error::<T>::NotAuthorized);
+				Self::do_unrequest_preimage(hash)
+			},
+			RequestStatus::Unrequested { deposit: (owner, deposit), .. } => {
+				ensure!(maybe_check_owner.map_or(true, |c| c == owner), Error::<T>::NotAuthorized);
+				T::Currency::unreserve(&owner, deposit);
+				StatusFor::<T>::remove(hash);
+				PreimageFor::<T>::remove(hash);
+				Self::deposit_event(Event::Cleared(*hash));
+				Ok(())
+			},
+		}
+	}
+
+	/// Add a request for the preimage of `hash`.
+	fn do_request_preimage(hash: &Hash) {
+		let (count, len, deposit) = StatusFor::<T>::get(hash).map_or((1, None, None), |x| match x {
+			RequestStatus::Requested { count, len, deposit } =>
+				(count.saturating_add(1), len, deposit),
+			RequestStatus::Unrequested { deposit, len } => (1, Some(len), Some(deposit)),
+		});
+		StatusFor::<T>::insert(hash, RequestStatus::Requested { count, len, deposit });
+		if count == 1 {
+			Self::deposit_event(Event::Requested(*hash));
+		}
+	}
+
+	/// Remove a request for the preimage of `hash`, clearing it if it was the last request and no
+	/// deposit is held for it.
+	fn do_unrequest_preimage(hash: &Hash) -> sp_runtime::DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotRequested)? {
+			RequestStatus::Requested { count, deposit, len } if count > 1 => {
+				StatusFor::<T>::insert(
+					hash,
+					RequestStatus::Requested { count: count - 1, deposit, len },
+				);
+			},
+			RequestStatus::Requested { deposit: Some(deposit), len: Some(len), .. } => {
+				StatusFor::<T>::insert(hash, RequestStatus::Unrequested { deposit, len });
+			},
+			RequestStatus::Requested { .. } => {
+				// the preimage has no deposit, so we remove it.
+				StatusFor::<T>::remove(hash);
+				PreimageFor::<T>::remove(hash);
+				Self::deposit_event(Event::Cleared(*hash));
+			},
+			RequestStatus::Unrequested { .. } => return Err(Error::<T>::NotRequested.into()),
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config> QueryPreimage for Pallet<T> {
+	fn len(hash: &Hash) -> Option<u32> {
+		match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested { len: Some(len), .. }) |
+			Some(RequestStatus::Unrequested { len, .. }) => Some(len),
+			_ => None,
+		}
+	}
+
+	fn fetch(hash: &Hash, _len: Option<u32>) -> Result<Cow<'static, [u8]>, sp_runtime::DispatchError> {
+		PreimageFor::<T>::get(hash)
+			.map(|preimage| Cow::Owned(preimage.into_inner()))
+			.ok_or_else(|| Error::<T>::NotNoted.into())
+	}
+
+	fn is_requested(hash: &Hash) -> bool {
+		matches!(StatusFor::<T>::get(hash), Some(RequestStatus::Requested { .. }))
+	}
+
+	fn request(hash: &Hash) {
+		Self::do_request_preimage(hash)
+	}
+
+	fn unrequest(hash: &Hash) {
+		let res = Self::do_unrequest_preimage(hash);
+		debug_assert!(res.is_ok(), "do_unrequest_preimage failed - counter underflow?");
+	}
+}
+
+impl<T: Config> StorePreimage for Pallet<T> {
+	const MAX_LENGTH: usize = MAX_SIZE as usize;
+
+	fn note(bytes: Cow<[u8]>) -> Result<Hash, sp_runtime::DispatchError> {
+		let hash = sp_io::hashing::blake2_256(&bytes).into();
+		Self::note_bytes(bytes, None)?;
+		Ok(hash)
+	}
+}
@@ -0,0 +1,258 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Preimage pallet.
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::Bounded,
+	weights::Pays,
+};
+use pallet_balances::Error as BalancesError;
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn user_note_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(Balances::free_balance(2), 97);
+
+		let h = hashed([1]);
+		assert!(Preimage::have(&Bounded::<()>::lookup(h, 1)));
+		assert_eq!(Preimage::len(&h), Some(1));
+		assert_eq!(Preimage::fetch(&h, None).unwrap().into_owned(), vec![1]);
+
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(2), vec![1]),
+			Error::<Test>::AlreadyNoted
+		);
+		assert_noop!(
+			Preimage::note_preimage(Origin::signed(0), vec![2]),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn manager_note_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		let h = hashed([1]);
+		assert!(Preimage::have(&Bounded::<()>::lookup(h, 1)));
+		assert!(Preimage::is_requested(&h));
+
+		// noting it again by the manager adds another request.
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1]));
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), h));
+		assert!(Preimage::have(&Bounded::<()>::lookup(h, 1)));
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), h));
+		assert!(!Preimage::have(&Bounded::<()>::lookup(h, 1)));
+	});
+}
+
+#[test]
+fn user_unnote_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_noop!(
+			Preimage::unnote_preimage(Origin::signed(3), hashed([1])),
+			Error::<Test>::NotAuthorized
+		);
+		assert_noop!(
+			Preimage::unnote_preimage(Origin::signed(2), hashed([2])),
+			Error::<Test>::NotNoted
+		);
+
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), hashed([1])));
+		assert_noop!(
+			Preimage::unnote_preimage(Origin::signed(2), hashed([1])),
+			Error::<Test>::NotNoted
+		);
+
+		let h = hashed([1]);
+		assert!(!Preimage::have(&Bounded::<()>::lookup(h, 1)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn manager_unnote_user_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), hashed([1])));
+
+		let h = hashed([1]);
+		assert!(!Preimage::have(&Bounded::<()>::lookup(h, 1)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn requested_then_noted_preimage_cannot_be_unnoted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), vec![1]));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(1), hashed([1])));
+		// it's still here.
+
+		let h = hashed([1]);
+		assert!(Preimage::have(&Bounded::<()>::lookup(h, 1)));
+
+		// now it's gone
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), hashed([1])));
+		assert!(!Preimage::have(&Bounded::<()>::lookup(h, 1)));
+	});
+}
+
+#[test]
+fn request_note_order_makes_no_difference() {
+	let one_way = new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		(
+			StatusFor::<Test>::iter().collect::<Vec<_>>(),
+			PreimageFor::<Test>::iter().collect::<Vec<_>>(),
+		)
+	});
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), hashed([1])));
+		let other_way = (
+			StatusFor::<Test>::iter().collect::<Vec<_>>(),
+			PreimageFor::<Test>::iter().collect::<Vec<_>>(),
+		);
+		assert_eq!(one_way, other_way);
+	});
+}
+
+#[test]
+fn requested_then_user_noted_preimage_is_free() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		let info = Preimage::note_preimage(Origin::signed(2), vec![1]).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+
+		let h = hashed([1]);
+		assert!(Preimage::have(&Bounded::<()>::lookup(h, 1)));
+		assert!(Preimage::is_requested(&h));
+	});
+}
+
+#[test]
+fn unrequest_preimage_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_noop!(
+			Preimage::unrequest_preimage(Origin::signed(1), hashed([2])),
+			Error::<Test>::NotRequested
+		);
+
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), hashed([1])));
+		let h = hashed([1]);
+		assert!(Preimage::have(&Bounded::<()>::lookup(h, 1)));
+
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), hashed([1])));
+		assert!(!Preimage::have(&Bounded::<()>::lookup(h, 1)));
+	});
+}
+
+#[test]
+fn user_noted_then_requested_preimage_is_refunded_once_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1; 3]));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_ok!(Preimage::request_preimage(Origin::signed(1), hashed([1])));
+		assert_ok!(Preimage::unrequest_preimage(Origin::signed(1), hashed([1])));
+		// Balance used was 3 + 5.
+		assert_eq!(Balances::reserved_balance(2), 8);
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), hashed([1])));
+		// Only the deposit of the preimage which was unnoted is returned.
+		assert_eq!(Balances::reserved_balance(2), 5);
+	});
+}
+
+#[test]
+fn origins_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Preimage::request_preimage(Origin::signed(2), hashed([1])), BadOrigin);
+		assert_noop!(Preimage::unrequest_preimage(Origin::signed(2), hashed([1])), BadOrigin);
+		assert_noop!(Preimage::note_preimage(Origin::none(), vec![1]), BadOrigin);
+		assert_noop!(Preimage::unnote_preimage(Origin::none(), hashed([1])), BadOrigin);
+	});
+}
+
+#[test]
+fn too_big_preimage_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let bytes = vec![0; MAX_SIZE as usize + 1];
+		assert_noop!(Preimage::note_preimage(Origin::signed(1), bytes), Error::<Test>::TooBig);
+	});
+}
+
+#[test]
+fn store_preimage_bound_works() {
+	new_test_ext().execute_with(|| {
+		// small values are kept inline.
+		let bounded = Preimage::bound(vec![1u8; 10]).unwrap();
+		assert!(!bounded.lookup_needed());
+		assert_eq!(Preimage::peek(&bounded).unwrap(), (vec![1u8; 10], None));
+
+		// large values are noted and requested.
+		let data = vec![2u8; 1000];
+		let bounded = Preimage::bound(data.clone()).unwrap();
+		let (hash, len) = bounded.lookup_hash().unwrap();
+		assert_eq!(hash, hashed(data.encode()));
+		assert!(Preimage::is_requested(&hash));
+		assert_eq!(Preimage::peek(&bounded).unwrap(), (data.clone(), Some(len)));
+
+		// realizing drops the request, and so clears the preimage.
+		assert_eq!(Preimage::realize(&bounded).unwrap(), (data, Some(len)));
+		assert!(!Preimage::have(&bounded));
+		assert!(Preimage::peek(&bounded).is_err());
+	});
+}
+
+#[test]
+fn hold_and_drop_keep_user_preimages_alive() {
+	new_test_ext().execute_with(|| {
+		let data = vec![2u8; 90];
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), data.encode()));
+		let bounded = Bounded::<Vec<u8>>::lookup(hashed(data.encode()), data.encode().len() as u32);
+
+		Preimage::hold(&bounded);
+		// the user can take their deposit back, but the preimage stays around.
+		assert_ok!(Preimage::unnote_preimage(Origin::signed(2), bounded.hash()));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Preimage::peek(&bounded).unwrap().0, data);
+
+		Preimage::drop(&bounded);
+		assert!(!Preimage::have(&bounded));
+	});
+}
@@ -301,6 +301,38 @@ fn proxy_announced_removes_announcement_and_returns_deposit() {
 	});
 }
 
+#[test]
+fn proxy_announced_respects_proxy_type() {
+	new_test_ext().execute_with(|| {
+		assert!(Balances::mutate_account(&1, |a| a.free = 1000).is_ok());
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 3, ProxyType::JustTransfer, 1));
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 4, ProxyType::JustUtility, 1));
+
+		let call = Box::new(Call::Balances(BalancesCall::transfer(6, 1)));
+		let call_hash = BlakeTwo256::hash_of(&call);
+		assert_ok!(Proxy::announce(Origin::signed(3), 1, call_hash));
+		assert_ok!(Proxy::announce(Origin::signed(4), 1, call_hash));
+		system::Pallet::<Test>::set_block_number(2);
+
+		// The announcement is consumed but the call is filtered by the proxy type.
+		assert_ok!(Proxy::proxy_announced(Origin::signed(0), 4, 1, None, call.clone()));
+		expect_event(ProxyEvent::ProxyExecuted(Err(DispatchError::BadOrigin)));
+		assert_eq!(Announcements::<Test>::get(4), (vec![], 0));
+		assert_eq!(Balances::reserved_balance(4), 0);
+
+		// Forcing a proxy type the delegate doesn't have fails.
+		let e = Error::<Test>::NotProxy;
+		assert_noop!(
+			Proxy::proxy_announced(Origin::signed(0), 3, 1, Some(ProxyType::Any), call.clone()),
+			e,
+		);
+
+		assert_ok!(Proxy::proxy_announced(Origin::signed(0), 3, 1, None, call.clone()));
+		expect_event(ProxyEvent::ProxyExecuted(Ok(())));
+		assert_eq!(Balances::free_balance(6), 1);
+	});
+}
+
 #[test]
 fn filtering_works() {
 	new_test_ext().execute_with(|| {
@@ -0,0 +1,168 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ranked collective pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as RankedCollective;
+
+use frame_benchmarking::{
+	account, benchmarks_instance_pallet, impl_benchmark_test_suite, whitelisted_caller,
+};
+use frame_support::traits::{EnsureOrigin, UnfilteredDispatchable};
+use frame_system::{EventRecord, RawOrigin as SystemOrigin};
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+fn make_member<T: Config<I>, I: 'static>(rank: Rank) -> T::AccountId {
+	let who = account::<T::AccountId>("member", MemberCount::<T, I>::get(0), SEED);
+	let origin = T::AdminOrigin::successful_origin();
+	RankedCollective::<T, I>::add_member(origin.clone(), who.clone())
+		.expect("admin origin can add a member");
+	for _ in 0..rank {
+		RankedCollective::<T, I>::promote_member(origin.clone(), who.clone())
+			.expect("admin origin can promote a member");
+	}
+	who
+}
+
+benchmarks_instance_pallet! {
+	add_member {
+		let who = account::<T::AccountId>("member", 0, SEED);
+		let origin = T::AdminOrigin::successful_origin();
+		let call = Call::<T, I>::add_member(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(MemberCount::<T, I>::get(0), 1);
+		assert_last_event::<T, I>(Event::MemberAdded(who).into());
+	}
+
+	remove_member {
+		let r in 0 .. 10;
+		let rank = r as Rank;
+		let first = make_member::<T, I>(rank);
+		let who = make_member::<T, I>(rank);
+		let last = make_member::<T, I>(rank);
+		let last_index = (0..=rank)
+			.map(|r| IdToIndex::<T, I>::get(r, &last).unwrap())
+			.collect::<Vec<_>>();
+		let origin = T::AdminOrigin::successful_origin();
+		let call = Call::<T, I>::remove_member(who.clone(), rank);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		for r in 0..=rank {
+			assert_eq!(MemberCount::<T, I>::get(r), 2);
+			assert_ne!(last_index[r as usize], IdToIndex::<T, I>::get(r, &last).unwrap());
+		}
+		assert_eq!(IdToIndex::<T, I>::get(0, &first), Some(0));
+		assert_last_event::<T, I>(Event::MemberRemoved(who, rank).into());
+	}
+
+	promote_member {
+		let rank: Rank = 0;
+		let who = make_member::<T, I>(rank);
+		let origin = T::AdminOrigin::successful_origin();
+		let call = Call::<T, I>::promote_member(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Members::<T, I>::get(&who).unwrap().rank(), rank + 1);
+		assert_last_event::<T, I>(Event::RankChanged(who, rank + 1).into());
+	}
+
+	demote_member {
+		let rank: Rank = 1;
+		let first = make_member::<T, I>(rank);
+		let who = make_member::<T, I>(rank);
+		let last = make_member::<T, I>(rank);
+		let last_index = IdToIndex::<T, I>::get(rank, &last).unwrap();
+		let origin = T::AdminOrigin::successful_origin();
+		let call = Call::<T, I>::demote_member(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Members::<T, I>::get(&who).map(|x| x.rank()), Some(rank - 1));
+		assert_eq!(MemberCount::<T, I>::get(rank), 2);
+		assert_ne!(last_index, IdToIndex::<T, I>::get(rank, &last).unwrap());
+		assert_eq!(IdToIndex::<T, I>::get(rank, &first), Some(0));
+		assert_last_event::<T, I>(Event::RankChanged(who, rank - 1).into());
+	}
+
+	vote {
+		let caller: T::AccountId = whitelisted_caller();
+		let origin = T::AdminOrigin::successful_origin();
+		RankedCollective::<T, I>::add_member(origin, caller.clone())
+			.expect("admin origin can add a member");
+		// Create a poll of the lowest class, which the caller is ranked to vote on.
+		let class = T::Polls::classes().into_iter().next().expect("at least one class");
+		let rank = class;
+		for _ in 0..rank {
+			let origin = T::AdminOrigin::successful_origin();
+			RankedCollective::<T, I>::promote_member(origin, caller.clone())
+				.expect("admin origin can promote a member");
+		}
+
+		let poll = T::Polls::create_ongoing(class).expect("must create poll");
+
+		// Vote once.
+		RankedCollective::<T, I>::vote(SystemOrigin::Signed(caller.clone()).into(), poll, true)?;
+	}: _(SystemOrigin::Signed(caller.clone()), poll, false)
+	verify {
+		let votes = T::VoteWeight::convert(0);
+		let tally = Tally::from_parts(0, 0, votes);
+		let ev = Event::Voted(caller, poll, VoteRecord::Nay(votes), tally);
+		assert_last_event::<T, I>(ev.into());
+	}
+
+	cleanup_poll {
+		let n in 1 .. 100;
+
+		// Create a poll.
+		let class = T::Polls::classes().into_iter().next().expect("at least one class");
+		let rank = class;
+		let poll = T::Polls::create_ongoing(class).expect("must create poll");
+
+		// Vote in the poll by each of `n` members.
+		for _ in 0..n {
+			let who = make_member::<T, I>(rank);
+			let origin = SystemOrigin::Signed(who).into();
+			RankedCollective::<T, I>::vote(origin, poll, true)?;
+		}
+
+		// End the poll.
+		T::Polls::end_ongoing(poll, false).expect("must be able to end poll");
+
+		assert_eq!(Voting::<T, I>::iter_prefix(poll).count(), n as usize);
+	}: _(SystemOrigin::Signed(whitelisted_caller()), poll, n)
+	verify {
+		assert_eq!(Voting::<T, I>::iter().count(), 0);
+	}
+}
+
+impl_benchmark_test_suite!(
+	RankedCollective,
+	crate::tests::new_test_ext(),
+	crate::tests::Test,
+);
@@ -0,0 +1,563 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Ranked collective system.
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! This is a membership pallet providing a `Polling`-based voting system for a collective whose
+//! members each have an integer rank. Members are added at rank zero by the `AdminOrigin`, which
+//! may then promote, demote or remove them.
+//!
+//! The class of a poll is interpreted as the minimum rank which a member must have in order to
+//! vote on it. Eligible members vote with a weight which derives from how far their rank exceeds
+//! that minimum, according to the `VoteWeight` conversion. The support of a poll is the proportion
+//! of eligible members who voted aye, regardless of their rank.
+//!
+//! The [`EnsureRanked`] origin may be used to gate privileged calls on a minimum rank.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `add_member` - Add a new member at rank zero.
+//! - `promote_member` - Increment the rank of a member by one.
+//! - `demote_member` - Decrement the rank of a member by one, removing it at rank zero.
+//! - `remove_member` - Remove a member entirely.
+//! - `vote` - Vote on an ongoing poll, as a member of sufficient rank.
+//! - `cleanup_poll` - Remove the votes of a poll which is no longer ongoing.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![recursion_limit = "128"]
+
+use sp_std::{marker::PhantomData, prelude::*};
+use codec::{Decode, Encode};
+use sp_runtime::{
+	Perbill, RuntimeDebug,
+	traits::Convert,
+};
+use frame_support::{
+	CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+	ensure,
+	traits::{EnsureOrigin, Get, PollStatus, Polling, VoteTally},
+	weights::PostDispatchInfo,
+};
+
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// A number of members.
+pub type MemberIndex = u32;
+
+/// Member rank.
+pub type Rank = u16;
+
+/// Votes.
+pub type Votes = u32;
+
+/// Aggregated votes for an ongoing poll by members of the ranked collective.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct Tally<M: GetMaxVoters> {
+	/// The number of members who voted aye, regardless of their rank.
+	bare_ayes: MemberIndex,
+	/// The weight of the aye votes.
+	ayes: Votes,
+	/// The weight of the nay votes.
+	nays: Votes,
+	/// Dummy.
+	#[codec(skip)]
+	dummy: PhantomData<M>,
+}
+
+impl<M: GetMaxVoters> Tally<M> {
+	/// Create a tally from its parts.
+	pub fn from_parts(bare_ayes: MemberIndex, ayes: Votes, nays: Votes) -> Self {
+		Tally { bare_ayes, ayes, nays, dummy: PhantomData }
+	}
+}
+
+pub type TallyOf<T, I = ()> = Tally<Pallet<T, I>>;
+pub type PollIndexOf<T, I = ()> = <<T as Config<I>>::Polls as Polling<TallyOf<T, I>>>::Index;
+
+impl<M: GetMaxVoters> VoteTally<Votes, Rank> for Tally<M> {
+	fn new(_: Rank) -> Self {
+		Self { bare_ayes: 0, ayes: 0, nays: 0, dummy: PhantomData }
+	}
+
+	fn ayes(&self, _: Rank) -> Votes {
+		self.ayes
+	}
+
+	fn support(&self, class: Rank) -> Perbill {
+		Perbill::from_rational(self.bare_ayes, M::get_max_voters(class))
+	}
+
+	fn approval(&self, _: Rank) -> Perbill {
+		Perbill::from_rational(self.ayes, 1.max(self.ayes + self.nays))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn unanimity(class: Rank) -> Self {
+		Self {
+			bare_ayes: M::get_max_voters(class),
+			ayes: M::get_max_voters(class),
+			nays: 0,
+			dummy: PhantomData,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn rejection(class: Rank) -> Self {
+		Self { bare_ayes: 0, ayes: 0, nays: M::get_max_voters(class), dummy: PhantomData }
+	}
+}
+
+/// Record needed for every member.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MemberRecord {
+	/// The rank of the member.
+	rank: Rank,
+}
+
+impl MemberRecord {
+	/// Create a record for a member of the given `rank`.
+	pub fn new(rank: Rank) -> Self {
+		Self { rank }
+	}
+
+	/// The rank of the member.
+	pub fn rank(&self) -> Rank {
+		self.rank
+	}
+}
+
+/// Record needed for every vote.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum VoteRecord {
+	/// Vote was an aye with given vote weight.
+	Aye(Votes),
+	/// Vote was a nay with given vote weight.
+	Nay(Votes),
+}
+
+impl From<(bool, Votes)> for VoteRecord {
+	fn from((aye, votes): (bool, Votes)) -> Self {
+		match aye {
+			true => VoteRecord::Aye(votes),
+			false => VoteRecord::Nay(votes),
+		}
+	}
+}
+
+/// Vote-weight scheme where all voters get one vote regardless of rank.
+pub struct Unit;
+impl Convert<Rank, Votes> for Unit {
+	fn convert(_: Rank) -> Votes {
+		1
+	}
+}
+
+/// Vote-weight scheme where all voters get one vote plus an additional vote for every excess rank
+/// they have. I.e.:
+///
+/// - Each member with an excess rank of 0 gets 1 vote;
+/// - ...with an excess rank of 1 gets 2 votes;
+/// - ...with an excess rank of 2 gets 3 votes;
+/// - ...with an excess rank of 3 gets 4 votes.
+pub struct Linear;
+impl Convert<Rank, Votes> for Linear {
+	fn convert(r: Rank) -> Votes {
+		(r as Votes).saturating_add(1)
+	}
+}
+
+/// Vote-weight scheme where all voters get one vote plus additional votes for every excess rank
+/// they have incrementing by one vote for each excess rank. I.e.:
+///
+/// - Each member with an excess rank of 0 gets 1 vote;
+/// - ...with an excess rank of 1 gets 3 votes;
+/// - ...with an excess rank of 2 gets 6 votes;
+/// - ...with an excess rank of 3 gets 10 votes.
+pub struct Geometric;
+impl Convert<Rank, Votes> for Geometric {
+	fn convert(r: Rank) -> Votes {
+		let v = (r as Votes).saturating_add(1);
+		v.saturating_mul(v.saturating_add(1)) / 2
+	}
+}
+
+/// Trait for getting the maximum number of voters for a given rank.
+pub trait GetMaxVoters {
+	/// Return the maximum number of voters for the rank `r`.
+	fn get_max_voters(r: Rank) -> MemberIndex;
+}
+impl<T: Config<I>, I: 'static> GetMaxVoters for Pallet<T, I> {
+	fn get_max_voters(r: Rank) -> MemberIndex {
+		MemberCount::<T, I>::get(r)
+	}
+}
+
+/// Guard to ensure that the given origin is a member of the collective whose rank is at least
+/// `MinRank`. The success value is the member's account.
+pub struct EnsureRanked<T, I, MinRank>(PhantomData<(T, I, MinRank)>);
+impl<T: Config<I>, I: 'static, MinRank: Get<Rank>> EnsureOrigin<T::Origin>
+	for EnsureRanked<T, I, MinRank>
+{
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+		let who = frame_system::EnsureSigned::<T::AccountId>::try_origin(o)?;
+		match Members::<T, I>::get(&who) {
+			Some(MemberRecord { rank, .. }) if rank >= MinRank::get() => Ok(who),
+			_ => Err(frame_system::RawOrigin::Signed(who).into()),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> T::Origin {
+		let who = IndexToId::<T, I>::get(MinRank::get(), 0)
+			.expect("Must be at least one member at rank for a successful origin");
+		frame_system::RawOrigin::Signed(who).into()
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, weights::Pays};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// The runtime event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin required to add, promote, demote or remove a member.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The polling system used for our voting.
+		type Polls: Polling<TallyOf<Self, I>, Votes = Votes, Class = Rank, Moment = Self::BlockNumber>;
+
+		/// Convert the excess of a member's rank over the class of a poll into the weight of the
+		/// member's vote on that poll.
+		type VoteWeight: Convert<Rank, Votes>;
+	}
+
+	/// The number of members in the collective who have at least the rank according to the index
+	/// of the vec.
+	#[pallet::storage]
+	pub type MemberCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, Rank, MemberIndex, ValueQuery>;
+
+	/// The current members of the collective.
+	#[pallet::storage]
+	pub type Members<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, MemberRecord>;
+
+	/// The index of each ranks's member into the group of members who have at least that rank.
+	#[pallet::storage]
+	pub type IdToIndex<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, Rank, Twox64Concat, T::AccountId, MemberIndex>;
+
+	/// The members in the collective by index. All indices in the range `0..MemberCount` will
+	/// return `Some`, however a member's index is not guaranteed to remain unchanged over time.
+	#[pallet::storage]
+	pub type IndexToId<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, Rank, Twox64Concat, MemberIndex, T::AccountId>;
+
+	/// Votes on a given proposal, if it is ongoing.
+	#[pallet::storage]
+	pub type Voting<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		PollIndexOf<T, I>,
+		Twox64Concat,
+		T::AccountId,
+		VoteRecord,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		PollIndexOf<T, I> = "PollIndex",
+		TallyOf<T, I> = "Tally",
+	)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A member has been added at rank zero. \[who\]
+		MemberAdded(T::AccountId),
+		/// The rank of a member has changed. \[who, rank\]
+		RankChanged(T::AccountId, Rank),
+		/// A member of the given rank has been removed from the collective. \[who, rank\]
+		MemberRemoved(T::AccountId, Rank),
+		/// A member has voted on a poll, resulting in the given tally.
+		/// \[who, poll, vote, tally\]
+		Voted(T::AccountId, PollIndexOf<T, I>, VoteRecord, TallyOf<T, I>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Account is already a member.
+		AlreadyMember,
+		/// Account is not a member.
+		NotMember,
+		/// The given poll index is unknown or has closed.
+		NotPolling,
+		/// The given poll is still ongoing.
+		Ongoing,
+		/// There are no further records to be removed.
+		NoneRemaining,
+		/// Unexpected error in state.
+		Corruption,
+		/// The member's rank is too low to vote.
+		RankTooLow,
+		/// The information provided is incorrect.
+		InvalidWitness,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Introduce a new member.
+		///
+		/// - `origin`: Must be the `AdminOrigin`.
+		/// - `who`: Account of non-member which will become a member.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::add_member())]
+		pub fn add_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(!Members::<T, I>::contains_key(&who), Error::<T, I>::AlreadyMember);
+			Self::add_to_rank(&who, 0);
+			Members::<T, I>::insert(&who, MemberRecord::new(0));
+			Self::deposit_event(Event::MemberAdded(who));
+			Ok(())
+		}
+
+		/// Increment the rank of an existing member by one.
+		///
+		/// - `origin`: Must be the `AdminOrigin`.
+		/// - `who`: Account of existing member.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::promote_member())]
+		pub fn promote_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let record = Members::<T, I>::get(&who).ok_or(Error::<T, I>::NotMember)?;
+			let rank = record.rank.saturating_add(1);
+			ensure!(rank > record.rank, Error::<T, I>::InvalidWitness);
+			Self::add_to_rank(&who, rank);
+			Members::<T, I>::insert(&who, MemberRecord::new(rank));
+			Self::deposit_event(Event::RankChanged(who, rank));
+			Ok(())
+		}
+
+		/// Decrement the rank of an existing member by one. If the member is already at rank
+		/// zero, then they are removed entirely.
+		///
+		/// - `origin`: Must be the `AdminOrigin`.
+		/// - `who`: Account of existing member of rank greater than zero.
+		///
+		/// Weight: `O(1)`, less if the member's index is highest in its rank.
+		#[pallet::weight(T::WeightInfo::demote_member())]
+		pub fn demote_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let record = Members::<T, I>::get(&who).ok_or(Error::<T, I>::NotMember)?;
+			let rank = record.rank;
+
+			Self::remove_from_rank(&who, rank)?;
+			match rank.checked_sub(1) {
+				None => {
+					Members::<T, I>::remove(&who);
+					Self::deposit_event(Event::MemberRemoved(who, 0));
+				},
+				Some(rank) => {
+					Members::<T, I>::insert(&who, MemberRecord::new(rank));
+					Self::deposit_event(Event::RankChanged(who, rank));
+				},
+			}
+			Ok(())
+		}
+
+		/// Remove the member entirely.
+		///
+		/// - `origin`: Must be the `AdminOrigin`.
+		/// - `who`: Account of existing member of rank greater than zero.
+		/// - `min_rank`: The rank of the member or greater.
+		///
+		/// Weight: `O(min_rank)`.
+		#[pallet::weight(T::WeightInfo::remove_member(*min_rank as u32))]
+		pub fn remove_member(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			min_rank: Rank,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let record = Members::<T, I>::get(&who).ok_or(Error::<T, I>::NotMember)?;
+			let rank = record.rank;
+			ensure!(min_rank >= rank, Error::<T, I>::InvalidWitness);
+
+			for r in 0..=rank {
+				Self::remove_from_rank(&who, r)?;
+			}
+			Members::<T, I>::remove(&who);
+			Self::deposit_event(Event::MemberRemoved(who, rank));
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::WeightInfo::remove_member(rank as u32)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Add an aye or nay vote for the sender to the given proposal.
+		///
+		/// - `origin`: Must be `Signed` by a member account.
+		/// - `poll`: Index of a poll which is ongoing.
+		/// - `aye`: `true` if the vote is to approve the proposal, `false` otherwise.
+		///
+		/// Transaction fees are be waived if the member is voting on any particular proposal
+		/// for the first time and the call is successful. Subsequent vote changes will charge a
+		/// fee.
+		///
+		/// Weight: `O(1)`, less if there was no previous vote on the poll by the member.
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll: PollIndexOf<T, I>,
+			aye: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let record = Members::<T, I>::get(&who).ok_or(Error::<T, I>::NotMember)?;
+			use VoteRecord::*;
+			let mut pays = Pays::Yes;
+
+			let (tally, vote) = T::Polls::try_access_poll(
+				poll,
+				|status| -> Result<(TallyOf<T, I>, VoteRecord), DispatchError> {
+					match status {
+						PollStatus::None | PollStatus::Completed(..) =>
+							Err(Error::<T, I>::NotPolling.into()),
+						PollStatus::Ongoing(tally, min_rank) => {
+							match Voting::<T, I>::get(&poll, &who) {
+								Some(Aye(votes)) => {
+									tally.bare_ayes = tally.bare_ayes.saturating_sub(1);
+									tally.ayes = tally.ayes.saturating_sub(votes);
+								},
+								Some(Nay(votes)) => tally.nays = tally.nays.saturating_sub(votes),
+								None => pays = Pays::No,
+							}
+							let votes = Self::rank_to_votes(record.rank, min_rank)?;
+							let vote = VoteRecord::from((aye, votes));
+							match aye {
+								true => {
+									tally.bare_ayes = tally.bare_ayes.saturating_add(1);
+									tally.ayes = tally.ayes.saturating_add(votes);
+								},
+								false => tally.nays = tally.nays.saturating_add(votes),
+							}
+							Voting::<T, I>::insert(&poll, &who, &vote);
+							Ok((tally.clone(), vote))
+						},
+					}
+				},
+			)?;
+			Self::deposit_event(Event::Voted(who, poll, vote, tally));
+			Ok(pays.into())
+		}
+
+		/// Remove votes from the given poll. It must have ended.
+		///
+		/// - `origin`: Must be `Signed` by any account.
+		/// - `poll_index`: Index of a poll which is completed and for which votes continue to
+		///   exist.
+		/// - `max`: Maximum number of vote items from remove in this call.
+		///
+		/// Transaction fees are waived if the operation is successful.
+		///
+		/// Weight `O(max)` (less if there are fewer items to remove than `max`).
+		#[pallet::weight(T::WeightInfo::cleanup_poll(*max))]
+		pub fn cleanup_poll(
+			origin: OriginFor<T>,
+			poll_index: PollIndexOf<T, I>,
+			max: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(T::Polls::as_ongoing(poll_index).is_none(), Error::<T, I>::Ongoing);
+
+			let count = Voting::<T, I>::drain_prefix(poll_index).take(max as usize).count() as u32;
+			ensure!(count > 0, Error::<T, I>::NoneRemaining);
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::WeightInfo::cleanup_poll(count)),
+				pays_fee: Pays::No,
+			})
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	fn rank_to_votes(rank: Rank, min: Rank) -> Result<Votes, DispatchError> {
+		let excess = rank.checked_sub(min).ok_or(Error::<T, I>::RankTooLow)?;
+		Ok(T::VoteWeight::convert(excess))
+	}
+
+	/// Append `who` to the group of members having at least `rank`.
+	fn add_to_rank(who: &T::AccountId, rank: Rank) {
+		let index = MemberCount::<T, I>::get(rank);
+		IdToIndex::<T, I>::insert(rank, who, index);
+		IndexToId::<T, I>::insert(rank, index, who);
+		MemberCount::<T, I>::insert(rank, index.saturating_add(1));
+	}
+
+	/// Remove `who` from the group of members having at least `rank`, moving the member with the
+	/// highest index of that group into its place.
+	fn remove_from_rank(who: &T::AccountId, rank: Rank) -> DispatchResult {
+		let last_index = MemberCount::<T, I>::get(rank).saturating_sub(1);
+		let index = IdToIndex::<T, I>::get(rank, who).ok_or(Error::<T, I>::Corruption)?;
+		if index != last_index {
+			let last = IndexToId::<T, I>::get(rank, last_index).ok_or(Error::<T, I>::Corruption)?;
+			IdToIndex::<T, I>::insert(rank, &last, index);
+			IndexToId::<T, I>::insert(rank, index, &last);
+		}
+		MemberCount::<T, I>::insert(rank, last_index);
+		IdToIndex::<T, I>::remove(rank, who);
+		IndexToId::<T, I>::remove(rank, last_index);
+		Ok(())
+	}
+
+	/// The rank of `who`, if a member.
+	pub fn rank_of(who: &T::AccountId) -> Option<Rank> {
+		Members::<T, I>::get(who).map(|r| r.rank)
+	}
+}
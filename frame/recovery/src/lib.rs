@@ -39,6 +39,8 @@
 //!   the account to be successfully recovered.
 //! * `delay_period` - The minimum number of blocks after the beginning of the recovery
 //!   process that need to pass before the account can be successfully recovered.
+//! * `filter` - The calls the rescuer may make through the recovered account during the
+//!   `RecoveryCooldown` after claiming it, for instance excluding balance transfers.
 //!
 //! There is a configurable deposit that all users need to pay to create a recovery
 //! configuration. This deposit is composed of a base deposit plus a multiplier for
@@ -64,7 +66,8 @@
 //!    when they initiated the recovery process.
 //! 6. Now the account owner is able to call `claim_recovery`, which subsequently
 //!    allows them to call `as_recovered` and directly make calls on-behalf-of the lost
-//!    account.
+//!    account. During the `RecoveryCooldown` only the calls allowed by the configured
+//!    `filter` can be made.
 //! 7. Using the now recovered account, the account owner can call `close_recovery`
 //!    on the recovery process they opened, reclaiming the recovery deposit they
 //!    placed.
@@ -153,7 +156,7 @@
 
 use sp_std::prelude::*;
 use sp_runtime::{
-	traits::{Dispatchable, SaturatedConversion, CheckedAdd, CheckedMul},
+	traits::{Dispatchable, SaturatedConversion, CheckedAdd, CheckedMul, Saturating, Zero, Member},
 	DispatchResult
 };
 use codec::{Encode, Decode};
@@ -161,7 +164,9 @@ use codec::{Encode, Decode};
 use frame_support::{
 	decl_module, decl_event, decl_storage, decl_error, ensure,
 	Parameter, RuntimeDebug, weights::GetDispatchInfo,
-	traits::{Currency, ReservableCurrency, Get, BalanceStatus},
+	traits::{
+		Currency, ReservableCurrency, Get, BalanceStatus, InstanceFilter, OriginTrait, IsType,
+	},
 	dispatch::PostDispatchInfo,
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
@@ -180,7 +185,8 @@ pub trait Config: frame_system::Config {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
 
 	/// The overarching call type.
-	type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo> + GetDispatchInfo;
+	type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo> + GetDispatchInfo
+		+ IsType<<Self as frame_system::Config>::Call>;
 
 	/// The currency mechanism.
 	type Currency: ReservableCurrency<Self::AccountId>;
@@ -207,6 +213,15 @@ pub trait Config: frame_system::Config {
 	/// `sizeof(BlockNumber, Balance + T * AccountId)` bytes. Where T is a configurable
 	/// threshold.
 	type RecoveryDeposit: Get<BalanceOf<Self>>;
+
+	/// A filter of the calls a rescuer may make through a freshly recovered account.
+	///
+	/// The owner of a recoverable account chooses one when creating its recovery configuration.
+	type RecoveryCallFilter: Parameter + Member + InstanceFilter<<Self as Config>::Call>;
+
+	/// The number of blocks after `claim_recovery` during which the rescuer may only make the
+	/// calls allowed by the `RecoveryCallFilter` of the recovered account.
+	type RecoveryCooldown: Get<Self::BlockNumber>;
 }
 
 /// An active recovery process.
@@ -256,6 +271,19 @@ decl_storage! {
 		/// Map from the user who can access it to the recovered account.
 		pub Proxy get(fn proxy):
 			map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+
+		/// The calls that may be made through a recoverable account during the cool-down after
+		/// it was recovered.
+		pub RecoveryFilter get(fn recovery_filter):
+			map hasher(twox_64_concat) T::AccountId => Option<T::RecoveryCallFilter>;
+
+		/// The restriction on the calls of a proxy account during its cool-down.
+		///
+		/// Map from the user who can access a recovered account to the filter of the calls they
+		/// may make through it, and the block at which the cool-down ends.
+		pub ProxyRestriction get(fn proxy_restriction):
+			map hasher(blake2_128_concat) T::AccountId
+			=> Option<(T::RecoveryCallFilter, T::BlockNumber)>;
 	}
 }
 
@@ -263,6 +291,7 @@ decl_event! {
 	/// Events type.
 	pub enum Event<T> where
 		AccountId = <T as system::Config>::AccountId,
+		BlockNumber = <T as system::Config>::BlockNumber,
 	{
 		/// A recovery process has been set up for an \[account\].
 		RecoveryCreated(AccountId),
@@ -272,12 +301,20 @@ decl_event! {
 		/// A recovery process for lost account by rescuer account has been vouched for by sender.
 		/// \[lost, rescuer, sender\]
 		RecoveryVouched(AccountId, AccountId, AccountId),
+		/// Enough friends have vouched for the recovery of lost account by rescuer account, which
+		/// can be claimed from the given block on.
+		/// \[lost, rescuer, claimable_at\]
+		RecoveryThresholdReached(AccountId, AccountId, BlockNumber),
 		/// A recovery process for lost account by rescuer account has been closed.
 		/// \[lost, rescuer\]
 		RecoveryClosed(AccountId, AccountId),
 		/// Lost account has been successfully recovered by rescuer account.
 		/// \[lost, rescuer\]
 		AccountRecovered(AccountId, AccountId),
+		/// Rescuer account may only make filtered calls through the recovered lost account
+		/// until the given block.
+		/// \[lost, rescuer, until\]
+		RecoveredCallsRestricted(AccountId, AccountId, BlockNumber),
 		/// A recovery process has been removed for an \[account\].
 		RecoveryRemoved(AccountId),
 	}
@@ -319,6 +356,8 @@ decl_error! {
 		AlreadyProxy,
 		/// Some internal state is broken.
 		BadState,
+		/// The call may not be made through the recovered account during its cool-down
+		CallFiltered,
 	}
 }
 
@@ -338,6 +377,10 @@ decl_module! {
 		/// The base amount of currency needed to reserve for starting a recovery.
 		const RecoveryDeposit: BalanceOf<T> = T::RecoveryDeposit::get();
 
+		/// The number of blocks after claiming a recovery during which the calls through the
+		/// recovered account are filtered.
+		const RecoveryCooldown: T::BlockNumber = T::RecoveryCooldown::get();
+
 		/// Deposit one of this module's events by using the default implementation.
 		fn deposit_event() = default;
 
@@ -346,6 +389,10 @@ decl_module! {
 		/// The dispatch origin for this call must be _Signed_ and registered to
 		/// be able to make calls on behalf of the recovered account.
 		///
+		/// During the cool-down after claiming the recovery, only the calls allowed by the
+		/// recovery call filter of `account` can be made. This includes the calls nested in
+		/// `call`, e.g. in a batch.
+		///
 		/// Parameters:
 		/// - `account`: The recovered account you want to make a call on-behalf-of.
 		/// - `call`: The call you want to make with the recovered account.
@@ -353,6 +400,7 @@ decl_module! {
 		/// # <weight>
 		/// - The weight of the `call` + 10,000.
 		/// - One storage lookup to check account is recovered by `who`. O(1)
+		/// - One storage lookup to check the restriction on the calls of `who`. O(1)
 		/// # </weight>
 		#[weight = {
 			let dispatch_info = call.get_dispatch_info();
@@ -360,7 +408,9 @@ decl_module! {
 				dispatch_info.weight
 					.saturating_add(10_000)
 					// AccountData for inner call origin accountdata.
-					.saturating_add(T::DbWeight::get().reads_writes(1, 1)),
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+					// Proxy restriction.
+					.saturating_add(T::DbWeight::get().reads(1)),
 				dispatch_info.class,
 			)
 		}]
@@ -372,8 +422,17 @@ decl_module! {
 			// Check `who` is allowed to make a call on behalf of `account`
 			let target = Self::proxy(&who).ok_or(Error::<T>::NotAllowed)?;
 			ensure!(&target == &account, Error::<T>::NotAllowed);
-			call.dispatch(frame_system::RawOrigin::Signed(account).into())
-				.map(|_| ()).map_err(|e| e.error)
+			let mut origin: T::Origin = frame_system::RawOrigin::Signed(account).into();
+			if let Some((filter, until)) = Self::proxy_restriction(&who) {
+				if <system::Pallet<T>>::block_number() < until {
+					ensure!(filter.filter(&call), Error::<T>::CallFiltered);
+					// The calls nested in `call`, e.g. in a batch, are filtered as well.
+					origin.add_filter(move |c: &<T as frame_system::Config>::Call| {
+						filter.filter(<T as Config>::Call::from_ref(c))
+					});
+				}
+			}
+			call.dispatch(origin).map(|_| ()).map_err(|e| e.error)
 		}
 
 		/// Allow ROOT to bypass the recovery process and set an a rescuer account
@@ -385,8 +444,10 @@ decl_module! {
 		/// - `lost`: The "lost account" to be recovered.
 		/// - `rescuer`: The "rescuer account" which can call as the lost account.
 		///
+		/// The rescuer can make any call through the lost account right away.
+		///
 		/// # <weight>
-		/// - One storage write O(1)
+		/// - Two storage writes O(1)
 		/// - One event
 		/// # </weight>
 		#[weight = 0]
@@ -394,6 +455,7 @@ decl_module! {
 			ensure_root(origin)?;
 			// Create the recovery storage item.
 			<Proxy<T>>::insert(&rescuer, &lost);
+			<ProxyRestriction<T>>::remove(&rescuer);
 			Self::deposit_event(RawEvent::AccountRecovered(lost, rescuer));
 		}
 
@@ -413,13 +475,15 @@ decl_module! {
 		///   the length of the list of friends.
 		/// - `delay_period`: The number of blocks after a recovery attempt is initialized
 		///   that needs to pass before the account can be recovered.
+		/// - `filter`: The calls the rescuer may make through the account during the
+		///   `RecoveryCooldown` after recovering it.
 		///
 		/// # <weight>
 		/// - Key: F (len of friends)
 		/// - One storage read to check that account is not already recoverable. O(1).
 		/// - A check that the friends list is sorted and unique. O(F)
 		/// - One currency reserve operation. O(X)
-		/// - One storage write. O(1). Codec O(F).
+		/// - Two storage writes. O(1). Codec O(F).
 		/// - One event.
 		///
 		/// Total Complexity: O(F + X)
@@ -428,7 +492,8 @@ decl_module! {
 		fn create_recovery(origin,
 			friends: Vec<T::AccountId>,
 			threshold: u16,
-			delay_period: T::BlockNumber
+			delay_period: T::BlockNumber,
+			filter: T::RecoveryCallFilter,
 		) {
 			let who = ensure_signed(origin)?;
 			// Check account is not already set up for recovery
@@ -458,6 +523,7 @@ decl_module! {
 			};
 			// Create the recovery configuration storage item
 			<Recoverable<T>>::insert(&who, recovery_config);
+			<RecoveryFilter<T>>::insert(&who, filter);
 
 			Self::deposit_event(RawEvent::RecoveryCreated(who));
 		}
@@ -526,7 +592,7 @@ decl_module! {
 		/// - One binary search to confirm caller is a friend. O(logF)
 		/// - One binary search to confirm caller has not already vouched. O(logV)
 		/// - One storage write. O(1), Codec O(V).
-		/// - One event.
+		/// - Up to two events.
 		///
 		/// Total Complexity: O(F + logF + V + logV)
 		/// # </weight>
@@ -544,9 +610,15 @@ decl_module! {
 				Ok(_pos) => Err(Error::<T>::AlreadyVouched)?,
 				Err(pos) => active_recovery.friends.insert(pos, who.clone()),
 			}
+			let threshold_reached =
+				active_recovery.friends.len() == recovery_config.threshold as usize;
+			let claimable_at = active_recovery.created.saturating_add(recovery_config.delay_period);
 			// Update storage with the latest details
 			<ActiveRecoveries<T>>::insert(&lost, &rescuer, active_recovery);
-			Self::deposit_event(RawEvent::RecoveryVouched(lost, rescuer, who));
+			Self::deposit_event(RawEvent::RecoveryVouched(lost.clone(), rescuer.clone(), who));
+			if threshold_reached {
+				Self::deposit_event(RawEvent::RecoveryThresholdReached(lost, rescuer, claimable_at));
+			}
 		}
 
 		/// Allow a successful rescuer to claim their recovered account.
@@ -555,6 +627,9 @@ decl_module! {
 		/// who has successfully completed the account recovery process: collected
 		/// `threshold` or more vouches, waited `delay_period` blocks since initiation.
 		///
+		/// For `RecoveryCooldown` blocks, the rescuer may then only make the calls allowed by the
+		/// recovery call filter of the account.
+		///
 		/// Parameters:
 		/// - `account`: The lost account that you want to claim has been successfully
 		///   recovered by you.
@@ -564,8 +639,9 @@ decl_module! {
 		/// - One storage read to get the recovery configuration. O(1), Codec O(F)
 		/// - One storage read to get the active recovery process. O(1), Codec O(V)
 		/// - One storage read to get the current block number. O(1)
-		/// - One storage write. O(1), Codec O(V).
-		/// - One event.
+		/// - One storage read to get the recovery call filter. O(1)
+		/// - Up to two storage writes. O(1), Codec O(V).
+		/// - Up to two events.
 		///
 		/// Total Complexity: O(F + V)
 		/// # </weight>
@@ -591,7 +667,16 @@ decl_module! {
 			system::Pallet::<T>::inc_consumers(&who).map_err(|_| Error::<T>::BadState)?;
 			// Create the recovery storage item
 			Proxy::<T>::insert(&who, &account);
-			Self::deposit_event(RawEvent::AccountRecovered(account, who));
+			Self::deposit_event(RawEvent::AccountRecovered(account.clone(), who.clone()));
+			// Restrict the calls of the rescuer during the cool-down
+			let cooldown = T::RecoveryCooldown::get();
+			if !cooldown.is_zero() {
+				if let Some(filter) = Self::recovery_filter(&account) {
+					let until = current_block_number.saturating_add(cooldown);
+					ProxyRestriction::<T>::insert(&who, (filter, until));
+					Self::deposit_event(RawEvent::RecoveredCallsRestricted(account, who, until));
+				}
+			}
 		}
 
 		/// As the controller of a recoverable account, close an active recovery
@@ -655,6 +740,7 @@ decl_module! {
 			ensure!(active_recoveries.next().is_none(), Error::<T>::StillActive);
 			// Take the recovery configuration for this account.
 			let recovery_config = <Recoverable<T>>::take(&who).ok_or(Error::<T>::NotRecoverable)?;
+			<RecoveryFilter<T>>::remove(&who);
 
 			// Unreserve the initial deposit for the recovery configuration.
 			T::Currency::unreserve(&who, recovery_config.deposit);
@@ -678,6 +764,7 @@ decl_module! {
 			// Check `who` is allowed to make a call on behalf of `account`
 			ensure!(Self::proxy(&who) == Some(account), Error::<T>::NotAllowed);
 			Proxy::<T>::remove(&who);
+			ProxyRestriction::<T>::remove(&who);
 			system::Pallet::<T>::dec_consumers(&who);
 		}
 	}
@@ -38,6 +38,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Recovery: recovery::{Pallet, Call, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
 	}
 );
 
@@ -87,11 +88,33 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_utility::Config for Test {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const ConfigDepositBase: u64 = 10;
 	pub const FriendDepositFactor: u64 = 1;
 	pub const MaxFriends: u16 = 3;
 	pub const RecoveryDeposit: u64 = 10;
+	pub const RecoveryCooldown: u64 = 10;
+}
+
+/// The calls that may be made through a recovered account during its cool-down.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum RecoveredCalls {
+	Any,
+	NoTransfers,
+}
+impl InstanceFilter<Call> for RecoveredCalls {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			RecoveredCalls::Any => true,
+			RecoveredCalls::NoTransfers => !matches!(c, Call::Balances(..)),
+		}
+	}
 }
 
 impl Config for Test {
@@ -102,10 +125,13 @@ impl Config for Test {
 	type FriendDepositFactor = FriendDepositFactor;
 	type MaxFriends = MaxFriends;
 	type RecoveryDeposit = RecoveryDeposit;
+	type RecoveryCallFilter = RecoveredCalls;
+	type RecoveryCooldown = RecoveryCooldown;
 }
 
 pub type BalancesCall = pallet_balances::Call<Test>;
 pub type RecoveryCall = super::Call<Test>;
+pub type UtilityCall = pallet_utility::Call<Test>;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
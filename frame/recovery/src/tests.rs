@@ -19,10 +19,10 @@
 
 use super::*;
 use mock::{
-	Recovery, Balances, Test, Origin, Call, BalancesCall, RecoveryCall,
-	new_test_ext, run_to_block
+	Recovery, Balances, System, Test, Origin, Call, Event, BalancesCall, RecoveryCall,
+	UtilityCall, RecoveredCalls, new_test_ext, run_to_block
 };
-use sp_runtime::traits::{BadOrigin};
+use sp_runtime::{DispatchError, traits::{BadOrigin}};
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency},
@@ -63,7 +63,10 @@ fn recovery_life_cycle_works() {
 		let threshold = 3;
 		let delay_period = 10;
 		// Account 5 sets up a recovery configuration on their account
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends, threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends, threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Some time has passed, and the user lost their keys!
 		run_to_block(10);
 		// Using account 1, the user begins the recovery process to recover the lost account
@@ -109,7 +112,10 @@ fn malicious_recovery_fails() {
 		let threshold = 3;
 		let delay_period = 10;
 		// Account 5 sets up a recovery configuration on their account
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends, threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends, threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Some time has passed, and account 1 wants to try and attack this account!
 		run_to_block(10);
 		// Using account 1, the malicious user begins the recovery process on account 5
@@ -136,7 +142,10 @@ fn malicious_recovery_fails() {
 		assert_noop!(Recovery::claim_recovery(Origin::signed(1), 5), Error::<Test>::NotStarted);
 		// Account 5 can remove their recovery config and pick some better friends
 		assert_ok!(Recovery::remove_recovery(Origin::signed(5)));
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![22, 33, 44], threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), vec![22, 33, 44], threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 	});
 }
 
@@ -145,40 +154,42 @@ fn create_recovery_handles_basic_errors() {
 	new_test_ext().execute_with(|| {
 		// No friends
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![], 1, 0),
+			Recovery::create_recovery(Origin::signed(5), vec![], 1, 0, RecoveredCalls::Any),
 			Error::<Test>::NotEnoughFriends
 		);
 		// Zero threshold
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![2], 0, 0),
+			Recovery::create_recovery(Origin::signed(5), vec![2], 0, 0, RecoveredCalls::Any),
 			Error::<Test>::ZeroThreshold
 		);
 		// Threshold greater than friends length
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 4, 0),
+			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 4, 0, RecoveredCalls::Any),
 			Error::<Test>::NotEnoughFriends
 		);
 		// Too many friends
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![1, 2, 3, 4], 4, 0),
+			Recovery::create_recovery(
+				Origin::signed(5), vec![1, 2, 3, 4], 4, 0, RecoveredCalls::Any,
+			),
 			Error::<Test>::MaxFriends
 		);
 		// Unsorted friends
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![3, 2, 4], 3, 0),
+			Recovery::create_recovery(Origin::signed(5), vec![3, 2, 4], 3, 0, RecoveredCalls::Any),
 			Error::<Test>::NotSorted
 		);
 		// Duplicate friends
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![2, 2, 4], 3, 0),
+			Recovery::create_recovery(Origin::signed(5), vec![2, 2, 4], 3, 0, RecoveredCalls::Any),
 			Error::<Test>::NotSorted
 		);
 		// Already configured
 		assert_ok!(
-			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10)
+			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10, RecoveredCalls::Any)
 		);
 		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10),
+			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10, RecoveredCalls::Any),
 			Error::<Test>::AlreadyRecoverable
 		);
 	});
@@ -191,7 +202,10 @@ fn create_recovery_works() {
 		let threshold = 3;
 		let delay_period = 10;
 		// Account 5 sets up a recovery configuration on their account
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Deposit is taken, and scales with the number of friends they pick
 		// Base 10 + 1 per friends = 13 total reserved
 		assert_eq!(Balances::reserved_balance(5), 13);
@@ -218,7 +232,10 @@ fn initiate_recovery_handles_basic_errors() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Same user cannot recover same account twice
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
 		assert_noop!(Recovery::initiate_recovery(Origin::signed(1), 5), Error::<Test>::AlreadyStarted);
@@ -234,7 +251,10 @@ fn initiate_recovery_works() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Recovery can be initiated
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
 		// Deposit is reserved
@@ -260,7 +280,10 @@ fn vouch_recovery_handles_basic_errors() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Cannot vouch a recovery process that has not started
 		assert_noop!(Recovery::vouch_recovery(Origin::signed(2), 5, 1), Error::<Test>::NotStarted);
 		// Initiate a recovery process
@@ -280,7 +303,10 @@ fn vouch_recovery_works() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
 		// Vouching works
 		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
@@ -306,7 +332,10 @@ fn claim_recovery_handles_basic_errors() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		// Cannot claim an account which has not started the recovery process
 		assert_noop!(Recovery::claim_recovery(Origin::signed(1), 5), Error::<Test>::NotStarted);
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
@@ -328,7 +357,10 @@ fn claim_recovery_works() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
 		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
 		assert_ok!(Recovery::vouch_recovery(Origin::signed(3), 5, 1));
@@ -355,6 +387,78 @@ fn claim_recovery_works() {
 	});
 }
 
+#[test]
+fn claimed_recovery_is_restricted_during_cooldown() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), vec![2, 3, 4], 2, 10,
+			RecoveredCalls::NoTransfers,
+		));
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(3), 5, 1));
+		System::assert_has_event(Event::recovery(RawEvent::RecoveryThresholdReached(5, 1, 11)));
+
+		run_to_block(11);
+		assert_ok!(Recovery::claim_recovery(Origin::signed(1), 5));
+		System::assert_last_event(Event::recovery(RawEvent::RecoveredCallsRestricted(5, 1, 21)));
+
+		// Transfers are filtered during the cool-down, other calls are not
+		let transfer = Box::new(Call::Balances(BalancesCall::transfer(1, 10)));
+		assert_noop!(
+			Recovery::as_recovered(Origin::signed(1), 5, transfer.clone()),
+			Error::<Test>::CallFiltered
+		);
+		let close = Box::new(Call::Recovery(RecoveryCall::close_recovery(1)));
+		assert_ok!(Recovery::as_recovered(Origin::signed(1), 5, close));
+
+		// Once the cool-down is over, all calls are allowed again
+		run_to_block(21);
+		assert_ok!(Recovery::as_recovered(Origin::signed(1), 5, transfer));
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn nested_calls_are_restricted_during_cooldown() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), vec![2, 3, 4], 2, 10,
+			RecoveredCalls::NoTransfers,
+		));
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(3), 5, 1));
+		run_to_block(11);
+		assert_ok!(Recovery::claim_recovery(Origin::signed(1), 5));
+
+		// The batch itself is allowed, but not the transfer it contains
+		let transfer = Call::Balances(BalancesCall::transfer(1, 10));
+		let batch = Box::new(Call::Utility(UtilityCall::batch(vec![transfer])));
+		let balance = Balances::free_balance(5);
+		assert_ok!(Recovery::as_recovered(Origin::signed(1), 5, batch));
+		System::assert_last_event(
+			pallet_utility::Event::BatchInterrupted(0, DispatchError::BadOrigin).into()
+		);
+		assert_eq!(Balances::free_balance(5), balance);
+	});
+}
+
+#[test]
+fn set_recovered_is_not_restricted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), vec![2, 3, 4], 2, 10,
+			RecoveredCalls::NoTransfers,
+		));
+		assert_ok!(Recovery::set_recovered(Origin::root(), 5, 1));
+		let transfer = Box::new(Call::Balances(BalancesCall::transfer(1, 10)));
+		assert_ok!(Recovery::as_recovered(Origin::signed(1), 5, transfer));
+	});
+}
+
 #[test]
 fn close_recovery_handles_basic_errors() {
 	new_test_ext().execute_with(|| {
@@ -372,7 +476,10 @@ fn remove_recovery_works() {
 		let friends = vec![2, 3, 4];
 		let threshold = 3;
 		let delay_period = 10;
-		assert_ok!(Recovery::create_recovery(Origin::signed(5), friends.clone(), threshold, delay_period));
+		assert_ok!(Recovery::create_recovery(
+			Origin::signed(5), friends.clone(), threshold, delay_period,
+			RecoveredCalls::Any,
+		));
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
 		assert_ok!(Recovery::initiate_recovery(Origin::signed(2), 5));
 		// Cannot remove a recovery when there are active recoveries.
@@ -0,0 +1,149 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Referenda pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use codec::Encode;
+use frame_benchmarking::{
+	account, benchmarks_instance_pallet, whitelist_account, impl_benchmark_test_suite,
+};
+use frame_support::traits::{EnsureOrigin, preimages::BoundedInline};
+use frame_system::RawOrigin as SystemOrigin;
+use sp_runtime::traits::Bounded as _;
+
+use crate::Pallet as Referenda;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T, I>::max_value());
+	caller
+}
+
+fn dummy_call<T: Config<I>, I: 'static>() -> Bounded<CallOf<T, I>> {
+	let call = CallOf::<T, I>::from(Call::nudge_referendum(0));
+	Bounded::Inline(BoundedInline::force_from(call.encode(), None))
+}
+
+/// Submit a referendum to be dispatched as `Root`. The runtime must have a track for it.
+fn create_referendum<T: Config<I>, I: 'static>() -> (T::AccountId, ReferendumIndex) {
+	let caller = funded_account::<T, I>("caller", 0);
+	whitelist_account!(caller);
+	let proposal_origin = T::Origin::root().caller().clone();
+	assert!(Referenda::<T, I>::submit(
+		SystemOrigin::Signed(caller.clone()).into(),
+		proposal_origin,
+		dummy_call::<T, I>(),
+		DispatchTime::After(0u32.into()),
+	).is_ok());
+	let index = ReferendumCount::<T, I>::get() - 1;
+	(caller, index)
+}
+
+fn place_deposit<T: Config<I>, I: 'static>(index: ReferendumIndex) {
+	let caller = funded_account::<T, I>("caller", 0);
+	whitelist_account!(caller);
+	assert!(Referenda::<T, I>::place_decision_deposit(
+		SystemOrigin::Signed(caller).into(),
+		index,
+	).is_ok());
+}
+
+fn is_deciding<T: Config<I>, I: 'static>(index: ReferendumIndex) -> bool {
+	Referenda::<T, I>::ensure_ongoing(index).map_or(false, |s| s.deciding.is_some())
+}
+
+benchmarks_instance_pallet! {
+	submit {
+		let caller = funded_account::<T, I>("caller", 0);
+		whitelist_account!(caller);
+		let proposal_origin = T::Origin::root().caller().clone();
+	}: _(
+		SystemOrigin::Signed(caller),
+		proposal_origin,
+		dummy_call::<T, I>(),
+		DispatchTime::After(0u32.into())
+	)
+	verify {
+		let index = ReferendumCount::<T, I>::get() - 1;
+		assert!(Referenda::<T, I>::ensure_ongoing(index).is_ok());
+	}
+
+	place_decision_deposit {
+		let (caller, index) = create_referendum::<T, I>();
+	}: _(SystemOrigin::Signed(caller), index)
+	verify {
+		let status = Referenda::<T, I>::ensure_ongoing(index).unwrap();
+		assert!(status.decision_deposit.is_some());
+	}
+
+	refund_decision_deposit {
+		let (caller, index) = create_referendum::<T, I>();
+		place_deposit::<T, I>(index);
+		let origin = T::CancelOrigin::successful_origin();
+		assert!(Referenda::<T, I>::cancel(origin, index).is_ok());
+	}: _(SystemOrigin::Signed(caller), index)
+	verify {
+		let mut info = ReferendumInfoFor::<T, I>::get(index).unwrap();
+		assert_eq!(info.take_decision_deposit(), Ok(None));
+	}
+
+	cancel {
+		let (_caller, index) = create_referendum::<T, I>();
+		place_deposit::<T, I>(index);
+		let origin = T::CancelOrigin::successful_origin();
+	}: _<T::Origin>(origin, index)
+	verify {
+		assert!(matches!(
+			ReferendumInfoFor::<T, I>::get(index),
+			Some(ReferendumInfo::Cancelled(..))
+		));
+	}
+
+	kill {
+		let (_caller, index) = create_referendum::<T, I>();
+		place_deposit::<T, I>(index);
+		let origin = T::KillOrigin::successful_origin();
+	}: _<T::Origin>(origin, index)
+	verify {
+		assert!(matches!(ReferendumInfoFor::<T, I>::get(index), Some(ReferendumInfo::Killed(..))));
+	}
+
+	// A referendum which has finished preparing begins being decided.
+	nudge_referendum {
+		let (_caller, index) = create_referendum::<T, I>();
+		place_deposit::<T, I>(index);
+		let status = Referenda::<T, I>::ensure_ongoing(index).unwrap();
+		let track = T::Tracks::info(status.track).unwrap();
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + track.prepare_period);
+		assert!(!is_deciding::<T, I>(index));
+	}: _(SystemOrigin::Root, index)
+	verify {
+		assert!(is_deciding::<T, I>(index));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Referenda,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
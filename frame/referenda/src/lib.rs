@@ -0,0 +1,931 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Referenda Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! A pallet for executing referenda. No voting logic is present here, and the `Polling` and
+//! `PollStatus` traits are used to allow the voting logic (likely in a pallet) to be utilized.
+//!
+//! A referendum is a vote on whether a proposal should be dispatched from a particular origin. The
+//! origin is used to determine which one of several _tracks_ that a referendum happens under.
+//! Tracks each have their own configuration which governs the voting process and parameters.
+//!
+//! A referendum's lifecycle has three main stages: Preparation, deciding and conclusion.
+//! Referenda are considered "ongoing" immediately after submission until their eventual
+//! conclusion, and votes may be cast throughout.
+//!
+//! In order to progress from preparation to being decided, three things must be in place:
+//! - There must have been a *Decision Deposit* placed, an amount determined by the track. Anyone
+//!   may place this deposit.
+//! - A period must have elapsed since submission of the referendum. This period is known as the
+//!   *Preparation Period* and is determined by the track.
+//! - The track must not already be at capacity with referendum being decided. The maximum number
+//!   of referenda which may be being decided simultaneously is determined by the track.
+//!
+//! In order to become concluded, one of three things must happen:
+//! - The referendum should remain in an unbroken _Passing_ state for a period of time. This
+//!   is known as the _Confirmation Period_ and is determined by the track. A referendum is
+//!   considered _Passing_ when there is a sufficiently high support and approval, given the
+//!   amount of time it has been being decided. Generally the threshold for what counts as being
+//!   "sufficiently high" will reduce over time. The curves setting these thresholds are
+//!   determined by the track. In this case, the referendum is considered _Approved_ and the
+//!   proposal is scheduled for dispatch.
+//! - The referendum reaches the end of its deciding phase while not _Passing_. It ends in
+//!   _Rejection_ and the proposal is not dispatched.
+//! - The referendum is cancelled.
+//!
+//! A general time-out is also in place and referenda which exist in preparation for too long may
+//! conclude without ever entering into a deciding stage.
+//!
+//! Once a referendum is concluded, the decision deposit may be refunded.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `submit` - Submit a referendum to be dispatched from a given origin, taking a deposit.
+//! - `place_decision_deposit` - Place the deposit needed for a referendum to be decided.
+//! - `refund_decision_deposit` - Return the decision deposit of a concluded referendum.
+//! - `cancel` - Cancel an ongoing referendum, refunding its deposits.
+//! - `kill` - Cancel an ongoing referendum, slashing its deposits.
+//! - `nudge_referendum` - Advance a referendum onto its next logical state. Only used internally.
+
+#![recursion_limit = "256"]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::{
+	Perbill, DispatchError,
+	traits::{Dispatchable, Saturating, Zero, One, AtLeast32BitUnsigned, Member, BadOrigin},
+};
+use frame_support::{
+	ensure, Parameter,
+	dispatch::DispatchResult,
+	traits::{
+		Currency, Get, ReservableCurrency, OnUnbalanced, EnsureOrigin, EnsureOriginWithArg,
+		OriginTrait,
+		VoteTally, PollStatus, Polling,
+		schedule::{Anon as ScheduleAnon, DispatchTime},
+		preimages::{Bounded, Hash as PreimageHash, QueryPreimage},
+	},
+};
+
+mod types;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::{
+	BalanceOf, CallOf, Curve, DecidingStatus, DecidingStatusOf, Deposit, NegativeImbalanceOf,
+	PalletsOriginOf, ReferendaTally, ReferendumIndex, ReferendumInfo, ReferendumInfoOf,
+	ReferendumStatus, ReferendumStatusOf, ScheduleAddressOf, TallyOf, TrackIdOf, TrackInfo,
+	TrackInfoOf, TracksInfo, VotesOf,
+};
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, storage::bounded_vec::BoundedVec};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config + Sized {
+		/// The overarching call type; the type of proposals which may be referended.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin>
+			+ From<Call<Self, I>>
+			+ IsType<<Self as frame_system::Config>::Call>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// The scheduler used for waking up referenda and for enacting approved proposals.
+		type Scheduler: ScheduleAnon<Self::BlockNumber, CallOf<Self, I>, PalletsOriginOf<Self>>;
+
+		/// Currency type for this pallet.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Origin from which referenda may be submitted, given the origin from which the proposal
+		/// should be executed. Its success value is the account which pays the submission deposit.
+		type SubmitOrigin: EnsureOriginWithArg<
+			Self::Origin,
+			PalletsOriginOf<Self>,
+			Success = Self::AccountId,
+		>;
+
+		/// Origin from which any referendum may be cancelled, refunding its deposits.
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin from which any referendum may be killed, slashing its deposits.
+		type KillOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the unbalanced reduction when slashing a deposit.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+		/// The counting type for votes. Usually just balance.
+		type Votes: AtLeast32BitUnsigned + Copy + Parameter + Member;
+
+		/// The tallying type.
+		type Tally: ReferendaTally<Self::Votes, TrackIdOf<Self, I>>;
+
+		/// The minimum amount to be used as a deposit for a public referendum proposal.
+		#[pallet::constant]
+		type SubmissionDeposit: Get<BalanceOf<Self, I>>;
+
+		/// Maximum size of the referendum queue for a single track.
+		#[pallet::constant]
+		type MaxQueued: Get<u32>;
+
+		/// The number of blocks after submission that a referendum must begin being decided by.
+		/// Once this passes, the referendum times out and its submission deposit is slashed.
+		#[pallet::constant]
+		type UndecidingTimeout: Get<Self::BlockNumber>;
+
+		/// Quantization level for the referendum wakeup scheduler. A higher number will result in
+		/// fewer storage reads/writes needed for smaller voters, but also result in delays to the
+		/// automatic referendum status changes. Explicit servicing instructions are unaffected.
+		#[pallet::constant]
+		type AlarmInterval: Get<Self::BlockNumber>;
+
+		/// Information concerning the different referendum tracks.
+		type Tracks: TracksInfo<
+			BalanceOf<Self, I>,
+			Self::BlockNumber,
+			Origin = PalletsOriginOf<Self>,
+		>;
+
+		/// The preimage provider, through which the proposals of referenda are looked up.
+		type Preimages: QueryPreimage;
+	}
+
+	/// The next free referendum index, aka the number of referenda started so far.
+	#[pallet::storage]
+	pub type ReferendumCount<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ReferendumIndex, ValueQuery>;
+
+	/// Information concerning any given referendum.
+	#[pallet::storage]
+	pub type ReferendumInfoFor<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, ReferendumIndex, ReferendumInfoOf<T, I>>;
+
+	/// The sorted list of referenda ready to be decided but not yet being decided, ordered by
+	/// conviction-weighted approvals.
+	///
+	/// This should be empty if `DecidingCount` is less than `TrackInfo::max_deciding`.
+	#[pallet::storage]
+	pub type TrackQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TrackIdOf<T, I>,
+		BoundedVec<(ReferendumIndex, T::Votes), T::MaxQueued>,
+		ValueQuery,
+	>;
+
+	/// The number of referenda being decided currently.
+	#[pallet::storage]
+	pub type DecidingCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TrackIdOf<T, I>, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		BalanceOf<T, I> = "Balance",
+		TrackIdOf<T, I> = "TrackId",
+		T::Tally = "Tally",
+	)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A referendum has been submitted. \[index, track, proposal_hash\]
+		Submitted(ReferendumIndex, TrackIdOf<T, I>, PreimageHash),
+		/// The decision deposit has been placed. \[index, who, amount\]
+		DecisionDepositPlaced(ReferendumIndex, T::AccountId, BalanceOf<T, I>),
+		/// The decision deposit has been refunded. \[index, who, amount\]
+		DecisionDepositRefunded(ReferendumIndex, T::AccountId, BalanceOf<T, I>),
+		/// A deposit has been slashed. \[who, amount\]
+		DepositSlashed(T::AccountId, BalanceOf<T, I>),
+		/// A referendum has moved into the deciding phase. \[index, track, tally\]
+		DecisionStarted(ReferendumIndex, TrackIdOf<T, I>, T::Tally),
+		/// A referendum has entered its confirmation period. \[index\]
+		ConfirmStarted(ReferendumIndex),
+		/// A referendum has left its confirmation period without being confirmed. \[index\]
+		ConfirmAborted(ReferendumIndex),
+		/// A referendum has ended its confirmation phase and is ready for approval.
+		/// \[index, tally\]
+		Confirmed(ReferendumIndex, T::Tally),
+		/// A referendum has been approved and its proposal has been scheduled. \[index\]
+		Approved(ReferendumIndex),
+		/// The proposal of an approved referendum could not be looked up or decoded, so it could
+		/// not be scheduled. \[index, proposal_hash\]
+		ProposalUnavailable(ReferendumIndex, PreimageHash),
+		/// A proposal has been rejected by referendum. \[index, tally\]
+		Rejected(ReferendumIndex, T::Tally),
+		/// A referendum has been timed out without being decided. \[index, tally\]
+		TimedOut(ReferendumIndex, T::Tally),
+		/// A referendum has been cancelled. \[index, tally\]
+		Cancelled(ReferendumIndex, T::Tally),
+		/// A referendum has been killed. \[index, tally\]
+		Killed(ReferendumIndex, T::Tally),
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Referendum is not ongoing.
+		NotOngoing,
+		/// Referendum's decision deposit is already paid.
+		HasDeposit,
+		/// There was no deposit to refund.
+		NoDeposit,
+		/// The referendum index provided is invalid in this context.
+		BadReferendum,
+		/// The referendum has not yet concluded.
+		Unfinished,
+		/// The track identifier given was invalid.
+		BadTrack,
+		/// There is no track for the origin of the proposal.
+		NoTrack,
+		/// The preimage of the proposal does not exist.
+		PreimageNotExist,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Propose a referendum on a privileged action.
+		///
+		/// - `origin`: must be `SubmitOrigin` for `proposal_origin` and the account must have
+		///   `SubmissionDeposit` funds available.
+		/// - `proposal_origin`: The origin from which the proposal should be executed.
+		/// - `proposal`: The proposal. Its preimage must be available if it is not inline.
+		/// - `enactment_moment`: The moment that the proposal should be enacted.
+		///
+		/// Emits `Submitted`.
+		#[pallet::weight(T::WeightInfo::submit())]
+		pub fn submit(
+			origin: OriginFor<T>,
+			proposal_origin: PalletsOriginOf<T>,
+			proposal: Bounded<CallOf<T, I>>,
+			enactment_moment: DispatchTime<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = T::SubmitOrigin::ensure_origin(origin, &proposal_origin)?;
+
+			let track = T::Tracks::track_for(&proposal_origin)
+				.map_err(|_| Error::<T, I>::NoTrack)?;
+			ensure!(T::Preimages::have(&proposal), Error::<T, I>::PreimageNotExist);
+			let submission_deposit = Self::take_deposit(who, T::SubmissionDeposit::get())?;
+
+			let index = ReferendumCount::<T, I>::mutate(|x| {
+				let r = *x;
+				*x += 1;
+				r
+			});
+			let now = frame_system::Pallet::<T>::block_number();
+			T::Preimages::hold(&proposal);
+			let proposal_hash = proposal.hash();
+			let mut status = ReferendumStatus {
+				track,
+				origin: proposal_origin,
+				proposal,
+				enactment: enactment_moment,
+				submitted: now,
+				submission_deposit,
+				decision_deposit: None,
+				deciding: None,
+				tally: TallyOf::<T, I>::new(track),
+				in_queue: false,
+				alarm: None,
+			};
+			Self::set_alarm(&mut status, index, Some(now.saturating_add(T::UndecidingTimeout::get())));
+			ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+
+			Self::deposit_event(Event::<T, I>::Submitted(index, track, proposal_hash));
+			Ok(())
+		}
+
+		/// Post the Decision Deposit for a referendum.
+		///
+		/// - `origin`: must be `Signed` and the account must have funds available for the
+		///   referendum's track's Decision Deposit.
+		/// - `index`: The index of the submitted referendum whose Decision Deposit is yet to be
+		///   posted.
+		///
+		/// Emits `DecisionDepositPlaced`.
+		#[pallet::weight(T::WeightInfo::place_decision_deposit())]
+		pub fn place_decision_deposit(
+			origin: OriginFor<T>,
+			index: ReferendumIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			ensure!(status.decision_deposit.is_none(), Error::<T, I>::HasDeposit);
+			let track = Self::track(status.track).ok_or(Error::<T, I>::NoTrack)?;
+			status.decision_deposit = Some(Self::take_deposit(who.clone(), track.decision_deposit)?);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let info = Self::service_referendum(now, index, status);
+			ReferendumInfoFor::<T, I>::insert(index, info);
+
+			Self::deposit_event(
+				Event::<T, I>::DecisionDepositPlaced(index, who, track.decision_deposit)
+			);
+			Ok(())
+		}
+
+		/// Refund the Decision Deposit for a closed referendum back to the depositor.
+		///
+		/// - `origin`: must be `Signed` or `Root`.
+		/// - `index`: The index of a closed referendum whose Decision Deposit has not yet been
+		///   refunded.
+		///
+		/// Emits `DecisionDepositRefunded`.
+		#[pallet::weight(T::WeightInfo::refund_decision_deposit())]
+		pub fn refund_decision_deposit(
+			origin: OriginFor<T>,
+			index: ReferendumIndex,
+		) -> DispatchResult {
+			ensure_signed_or_root(origin)?;
+			let mut info = ReferendumInfoFor::<T, I>::get(index)
+				.ok_or(Error::<T, I>::BadReferendum)?;
+			let deposit = info.take_decision_deposit()
+				.map_err(|_| Error::<T, I>::Unfinished)?
+				.ok_or(Error::<T, I>::NoDeposit)?;
+			Self::refund_deposit(Some(deposit.clone()));
+			ReferendumInfoFor::<T, I>::insert(index, info);
+
+			Self::deposit_event(
+				Event::<T, I>::DecisionDepositRefunded(index, deposit.who, deposit.amount)
+			);
+			Ok(())
+		}
+
+		/// Cancel an ongoing referendum, refunding its submission deposit.
+		///
+		/// - `origin`: must be the `CancelOrigin`.
+		/// - `index`: The index of the referendum to be cancelled.
+		///
+		/// Emits `Cancelled`.
+		#[pallet::weight(T::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			Self::note_concluded(index, &mut status);
+			T::Preimages::drop(&status.proposal);
+			Self::refund_deposit(Some(status.submission_deposit));
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ReferendumInfoFor::<T, I>::insert(
+				index,
+				ReferendumInfo::Cancelled(now, status.decision_deposit),
+			);
+
+			Self::deposit_event(Event::<T, I>::Cancelled(index, status.tally));
+			Ok(())
+		}
+
+		/// Cancel an ongoing referendum and slash its deposits.
+		///
+		/// - `origin`: must be the `KillOrigin`.
+		/// - `index`: The index of the referendum to be cancelled.
+		///
+		/// Emits `Killed` and `DepositSlashed`.
+		#[pallet::weight(T::WeightInfo::kill())]
+		pub fn kill(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			T::KillOrigin::ensure_origin(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			Self::note_concluded(index, &mut status);
+			T::Preimages::drop(&status.proposal);
+			Self::slash_deposit(Some(status.submission_deposit));
+			Self::slash_deposit(status.decision_deposit);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Killed(now));
+
+			Self::deposit_event(Event::<T, I>::Killed(index, status.tally));
+			Ok(())
+		}
+
+		/// Advance a referendum onto its next logical state. Only used internally.
+		///
+		/// - `origin`: must be `Root`.
+		/// - `index`: the referendum to be advanced.
+		#[pallet::weight(T::WeightInfo::nudge_referendum())]
+		pub fn nudge_referendum(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			ensure_root(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut status = Self::ensure_ongoing(index)?;
+			// If our alarm has gone off, then it no longer needs cancelling.
+			if status.alarm.as_ref().map_or(false, |(when, _)| *when <= now) {
+				status.alarm = None;
+			}
+			let info = Self::service_referendum(now, index, status);
+			ReferendumInfoFor::<T, I>::insert(index, info);
+			Ok(())
+		}
+	}
+}
+
+/// Ensure that the origin is either `Signed` or `Root`.
+fn ensure_signed_or_root<O, AccountId>(o: O) -> Result<Option<AccountId>, BadOrigin>
+	where O: Into<Result<frame_system::RawOrigin<AccountId>, O>>
+{
+	match o.into() {
+		Ok(frame_system::RawOrigin::Root) => Ok(None),
+		Ok(frame_system::RawOrigin::Signed(who)) => Ok(Some(who)),
+		_ => Err(BadOrigin),
+	}
+}
+
+impl<T: Config<I>, I: 'static> Polling<T::Tally> for Pallet<T, I> {
+	type Index = ReferendumIndex;
+	type Votes = VotesOf<T, I>;
+	type Class = TrackIdOf<T, I>;
+	type Moment = T::BlockNumber;
+
+	fn classes() -> Vec<Self::Class> {
+		T::Tracks::tracks().iter().map(|x| x.0).collect()
+	}
+
+	fn as_ongoing(index: Self::Index) -> Option<(T::Tally, Self::Class)> {
+		Self::ensure_ongoing(index).ok().map(|x| (x.tally, x.track))
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut T::Tally, T::BlockNumber, TrackIdOf<T, I>>) -> R,
+	) -> R {
+		match ReferendumInfoFor::<T, I>::get(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track));
+				let now = frame_system::Pallet::<T>::block_number();
+				Self::set_alarm(&mut status, index, Some(now.saturating_add(One::one())));
+				ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+				result
+			},
+			Some(ReferendumInfo::Approved(end, ..)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end, ..)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(
+			PollStatus<&mut T::Tally, T::BlockNumber, TrackIdOf<T, I>>,
+		) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		match ReferendumInfoFor::<T, I>::get(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track))?;
+				let now = frame_system::Pallet::<T>::block_number();
+				Self::set_alarm(&mut status, index, Some(now.saturating_add(One::one())));
+				ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+				Ok(result)
+			},
+			Some(ReferendumInfo::Approved(end, ..)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end, ..)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()> {
+		let index = ReferendumCount::<T, I>::mutate(|x| {
+			let r = *x;
+			*x += 1;
+			r
+		});
+		let now = frame_system::Pallet::<T>::block_number();
+		let dummy_account_id = codec::Decode::decode(
+			&mut sp_runtime::traits::TrailingZeroInput::new(&b"dummy"[..])
+		).map_err(|_| ())?;
+		let status = ReferendumStatusOf::<T, I> {
+			track: class,
+			origin: T::Origin::root().caller().clone(),
+			proposal: Bounded::Inline(Default::default()),
+			enactment: DispatchTime::After(Zero::zero()),
+			submitted: now,
+			submission_deposit: Deposit { who: dummy_account_id, amount: Zero::zero() },
+			decision_deposit: None,
+			deciding: None,
+			tally: TallyOf::<T, I>::new(class),
+			in_queue: false,
+			alarm: None,
+		};
+		ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+		Ok(index)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()> {
+		let mut status = Self::ensure_ongoing(index).map_err(|_| ())?;
+		Self::note_concluded(index, &mut status);
+		let now = frame_system::Pallet::<T>::block_number();
+		let info = if approved {
+			ReferendumInfo::Approved(now, status.decision_deposit)
+		} else {
+			ReferendumInfo::Rejected(now, status.decision_deposit)
+		};
+		ReferendumInfoFor::<T, I>::insert(index, info);
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32) {
+		let r = T::Tracks::tracks()
+			.iter()
+			.max_by_key(|(_, info)| info.max_deciding)
+			.expect("Always one class");
+		(r.0, r.1.max_deciding)
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Check that referendum `index` is in the `Ongoing` state and return the `ReferendumStatus`
+	/// value, or `Err` otherwise.
+	pub fn ensure_ongoing(index: ReferendumIndex)
+		-> Result<ReferendumStatusOf<T, I>, DispatchError>
+	{
+		match ReferendumInfoFor::<T, I>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) => Ok(status),
+			_ => Err(Error::<T, I>::NotOngoing.into()),
+		}
+	}
+
+	/// The information of the track `id`, if it exists.
+	fn track(id: TrackIdOf<T, I>) -> Option<&'static TrackInfoOf<T, I>> {
+		T::Tracks::info(id)
+	}
+
+	/// Reserve a deposit of `amount` from `who`.
+	fn take_deposit(
+		who: T::AccountId,
+		amount: BalanceOf<T, I>,
+	) -> Result<Deposit<T::AccountId, BalanceOf<T, I>>, DispatchError> {
+		T::Currency::reserve(&who, amount)?;
+		Ok(Deposit { who, amount })
+	}
+
+	/// Return a deposit, if `Some`.
+	fn refund_deposit(deposit: Option<Deposit<T::AccountId, BalanceOf<T, I>>>) {
+		if let Some(Deposit { who, amount }) = deposit {
+			T::Currency::unreserve(&who, amount);
+		}
+	}
+
+	/// Slash a deposit, if `Some`.
+	fn slash_deposit(deposit: Option<Deposit<T::AccountId, BalanceOf<T, I>>>) {
+		if let Some(Deposit { who, amount }) = deposit {
+			T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
+			Self::deposit_event(Event::<T, I>::DepositSlashed(who, amount));
+		}
+	}
+
+	/// Cancel the alarm of `status`, if any.
+	fn ensure_no_alarm(status: &mut ReferendumStatusOf<T, I>) {
+		if let Some((_, address)) = status.alarm.take() {
+			// Incorrect alarm - cancel it.
+			let _ = T::Scheduler::cancel(address);
+		}
+	}
+
+	/// Set the alarm of `status` to `maybe_when`, rounded up to the next `AlarmInterval`, or
+	/// remove it if `None`. An existing alarm for the same block is left as it is.
+	fn set_alarm(
+		status: &mut ReferendumStatusOf<T, I>,
+		index: ReferendumIndex,
+		maybe_when: Option<T::BlockNumber>,
+	) {
+		let when = match maybe_when {
+			Some(when) => when,
+			None => return Self::ensure_no_alarm(status),
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+		let interval = T::AlarmInterval::get().max(One::one());
+		let when = when.max(now.saturating_add(One::one()))
+			.saturating_add(interval)
+			.saturating_sub(One::one()) / interval * interval;
+		if status.alarm.as_ref().map_or(false, |(w, _)| *w == when) {
+			return
+		}
+		Self::ensure_no_alarm(status);
+		let call = CallOf::<T, I>::from(Call::nudge_referendum(index));
+		let maybe_address = T::Scheduler::schedule(
+			DispatchTime::At(when),
+			None,
+			128u8,
+			T::Origin::root().caller().clone(),
+			call,
+		);
+		debug_assert!(maybe_address.is_ok(), "Unable to schedule a new alarm at #{:?}", when);
+		status.alarm = maybe_address.ok().map(|address| (when, address));
+	}
+
+	/// Schedule the enactment of the approved proposal of referendum `index`.
+	fn schedule_enactment(
+		index: ReferendumIndex,
+		track: &TrackInfoOf<T, I>,
+		desired: DispatchTime<T::BlockNumber>,
+		origin: PalletsOriginOf<T>,
+		proposal: Bounded<CallOf<T, I>>,
+	) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let earliest_allowed = now.saturating_add(track.min_enactment_period.max(One::one()));
+		let when = match desired {
+			DispatchTime::At(x) => x,
+			DispatchTime::After(x) => now.saturating_add(x),
+		}.max(earliest_allowed);
+		match T::Preimages::realize(&proposal) {
+			Ok((call, _)) => {
+				let ok = T::Scheduler::schedule(DispatchTime::At(when), None, 63, origin, call)
+					.is_ok();
+				debug_assert!(ok, "LOGIC ERROR: schedule_enactment failed");
+			},
+			// The proposal may be unavailable or undecodable by now, in which case there is
+			// nothing we can enact.
+			Err(_) => {
+				T::Preimages::drop(&proposal);
+				Self::deposit_event(Event::<T, I>::ProposalUnavailable(index, proposal.hash()));
+			},
+		}
+	}
+
+	/// Is the tally passing after `elapsed` of a decision period of `period`?
+	fn is_passing(
+		tally: &T::Tally,
+		elapsed: T::BlockNumber,
+		period: T::BlockNumber,
+		id: TrackIdOf<T, I>,
+		track: &TrackInfoOf<T, I>,
+	) -> bool {
+		let x = Perbill::from_rational(elapsed.min(period), period);
+		track.min_support.passing(x, tally.support(id)) &&
+			track.min_approval.passing(x, tally.approval(id))
+	}
+
+	/// The block at which a deciding referendum should next be serviced: the end of its
+	/// confirmation period if confirming, else the point at which its current tally would pass.
+	fn decision_time(
+		deciding: &DecidingStatusOf<T>,
+		tally: &T::Tally,
+		id: TrackIdOf<T, I>,
+		track: &TrackInfoOf<T, I>,
+	) -> T::BlockNumber {
+		deciding.confirming.unwrap_or_else(|| {
+			let until_approval = track.min_approval.delay(tally.approval(id));
+			let until_support = track.min_support.delay(tally.support(id));
+			let offset = until_support.max(until_approval);
+			deciding.since.saturating_add(offset * track.decision_period)
+		})
+	}
+
+	/// Begin deciding the referendum `index`, returning the block at which it should next be
+	/// serviced.
+	fn begin_deciding(
+		status: &mut ReferendumStatusOf<T, I>,
+		index: ReferendumIndex,
+		now: T::BlockNumber,
+		track: &TrackInfoOf<T, I>,
+	) -> T::BlockNumber {
+		status.in_queue = false;
+		Self::deposit_event(Event::<T, I>::DecisionStarted(index, status.track, status.tally.clone()));
+		let is_passing = Self::is_passing(
+			&status.tally,
+			Zero::zero(),
+			track.decision_period,
+			status.track,
+			track,
+		);
+		let confirming = if is_passing {
+			Self::deposit_event(Event::<T, I>::ConfirmStarted(index));
+			Some(now.saturating_add(track.confirm_period))
+		} else {
+			None
+		};
+		let deciding_status = DecidingStatus { since: now, confirming };
+		let alarm = Self::decision_time(&deciding_status, &status.tally, status.track, track);
+		status.deciding = Some(deciding_status);
+		alarm
+	}
+
+	/// Place the prepared referendum `index` into the deciding phase if the track has capacity,
+	/// or otherwise into the track's queue. Returns the block at which it should next be
+	/// serviced, if any.
+	fn ready_for_deciding(
+		now: T::BlockNumber,
+		track: &TrackInfoOf<T, I>,
+		index: ReferendumIndex,
+		status: &mut ReferendumStatusOf<T, I>,
+	) -> Option<T::BlockNumber> {
+		let deciding_count = DecidingCount::<T, I>::get(status.track);
+		if deciding_count < track.max_deciding {
+			// Begin deciding.
+			DecidingCount::<T, I>::insert(status.track, deciding_count.saturating_add(1));
+			Some(Self::begin_deciding(status, index, now, track))
+		} else {
+			// Add to queue.
+			status.in_queue = Self::queue_insert(status.track, index, status.tally.ayes(status.track));
+			None
+		}
+	}
+
+	/// Insert `index` into the queue of track `id`, keeping it sorted. If the queue is full, the
+	/// referendum with the fewest approvals is removed from it, unless that would be `index`
+	/// itself. Returns `true` if `index` is now queued.
+	fn queue_insert(id: TrackIdOf<T, I>, index: ReferendumIndex, ayes: T::Votes) -> bool {
+		let mut queue = TrackQueue::<T, I>::get(id);
+		let mut pos = queue.binary_search_by_key(&ayes, |x| x.1).unwrap_or_else(|x| x);
+		if queue.len() as u32 >= T::MaxQueued::get() {
+			if pos == 0 {
+				return false
+			}
+			let (kicked, _) = queue[0];
+			queue.remove(0);
+			pos -= 1;
+			// The kicked referendum must be serviced again, so that it may time out.
+			if let Ok(mut status) = Self::ensure_ongoing(kicked) {
+				status.in_queue = false;
+				let now = frame_system::Pallet::<T>::block_number();
+				Self::set_alarm(&mut status, kicked, Some(now.saturating_add(One::one())));
+				ReferendumInfoFor::<T, I>::insert(kicked, ReferendumInfo::Ongoing(status));
+			}
+		}
+		let queued = queue.try_insert(pos, (index, ayes)).is_ok();
+		TrackQueue::<T, I>::insert(id, queue);
+		queued
+	}
+
+	/// Remove `index` from the queue of track `id`, returning `true` if it was there.
+	fn queue_remove(id: TrackIdOf<T, I>, index: ReferendumIndex) -> bool {
+		TrackQueue::<T, I>::mutate(id, |queue| {
+			match queue.iter().position(|x| x.0 == index) {
+				Some(pos) => {
+					queue.remove(pos);
+					true
+				},
+				None => false,
+			}
+		})
+	}
+
+	/// Note that a referendum of track `id` has stopped being decided: begin deciding the one at
+	/// the top of its queue if there is one, otherwise reduce the deciding count.
+	fn note_one_fewer_deciding(id: TrackIdOf<T, I>) {
+		let mut queue = TrackQueue::<T, I>::get(id);
+		let next = queue.last().map(|x| x.0);
+		match (next, Self::track(id)) {
+			(Some(index), Some(track)) => {
+				queue.remove(queue.len() - 1);
+				TrackQueue::<T, I>::insert(id, queue);
+				if let Ok(mut status) = Self::ensure_ongoing(index) {
+					let now = frame_system::Pallet::<T>::block_number();
+					let alarm = Self::begin_deciding(&mut status, index, now, track);
+					Self::set_alarm(&mut status, index, Some(alarm));
+					ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+				}
+			},
+			_ => DecidingCount::<T, I>::mutate(id, |x| *x = x.saturating_sub(1)),
+		}
+	}
+
+	/// Clean up after a referendum which is being concluded: remove its alarm and take it out of
+	/// the queue or the deciding referenda of its track.
+	fn note_concluded(index: ReferendumIndex, status: &mut ReferendumStatusOf<T, I>) {
+		Self::ensure_no_alarm(status);
+		if status.in_queue {
+			Self::queue_remove(status.track, index);
+		}
+		if status.deciding.is_some() {
+			Self::note_one_fewer_deciding(status.track);
+		}
+	}
+
+	/// Advance the state of a referendum, which comes down to:
+	/// - If it's ready to be decided, start deciding;
+	/// - If it's not ready to be decided and non-deciding timeout has passed, fail;
+	/// - If it's ongoing and passing, ensure confirming; if at end of confirmation period, pass.
+	/// - If it's ongoing and not passing, stop confirming; if it has reached end time, fail.
+	///
+	/// Weight will be a bit different depending on what it does, but it's designed so as not to
+	/// differ dramatically, especially if `MaxQueued` is kept small. In particular _there are no
+	/// balance operations in here_.
+	///
+	/// In terms of storage, at most a single referendum and its track's queue are touched.
+	fn service_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		mut status: ReferendumStatusOf<T, I>,
+	) -> ReferendumInfoOf<T, I> {
+		let track = match Self::track(status.track) {
+			Some(x) => x,
+			None => return ReferendumInfo::Ongoing(status),
+		};
+		// Default the alarm to the end of the undeciding timeout.
+		let timeout = status.submitted.saturating_add(T::UndecidingTimeout::get());
+		let mut alarm = None;
+		match status.deciding.clone() {
+			None => {
+				if status.in_queue {
+					// Our position in the queue may need updating.
+					let ayes = status.tally.ayes(status.track);
+					status.in_queue = Self::queue_remove(status.track, index) &&
+						Self::queue_insert(status.track, index, ayes);
+				} else if status.decision_deposit.is_some() {
+					let prepare_end = status.submitted.saturating_add(track.prepare_period);
+					if now >= prepare_end {
+						alarm = Self::ready_for_deciding(now, track, index, &mut status);
+					} else {
+						alarm = Some(prepare_end.min(timeout));
+					}
+				} else {
+					alarm = Some(timeout);
+				}
+				// If we didn't move into being decided, then check the timeout.
+				if status.deciding.is_none() && !status.in_queue {
+					if now >= timeout {
+						// Too long without being decided - end it.
+						Self::ensure_no_alarm(&mut status);
+						T::Preimages::drop(&status.proposal);
+						Self::slash_deposit(Some(status.submission_deposit));
+						Self::deposit_event(Event::<T, I>::TimedOut(index, status.tally));
+						return ReferendumInfo::TimedOut(now, status.decision_deposit)
+					}
+					alarm = Some(alarm.map_or(timeout, |a| a.min(timeout)));
+				}
+			},
+			Some(mut deciding) => {
+				let elapsed = now.saturating_sub(deciding.since);
+				if Self::is_passing(&status.tally, elapsed, track.decision_period, status.track, track) {
+					if deciding.confirming.map_or(false, |c| now >= c) {
+						// Passed!
+						Self::note_concluded(index, &mut status);
+						Self::schedule_enactment(
+							index,
+							track,
+							status.enactment,
+							status.origin,
+							status.proposal,
+						);
+						Self::refund_deposit(Some(status.submission_deposit));
+						Self::deposit_event(Event::<T, I>::Confirmed(index, status.tally));
+						Self::deposit_event(Event::<T, I>::Approved(index));
+						return ReferendumInfo::Approved(now, status.decision_deposit)
+					}
+					if deciding.confirming.is_none() {
+						Self::deposit_event(Event::<T, I>::ConfirmStarted(index));
+						deciding.confirming = Some(now.saturating_add(track.confirm_period));
+					}
+				} else {
+					if now >= deciding.since.saturating_add(track.decision_period) {
+						// Failed!
+						Self::note_concluded(index, &mut status);
+						T::Preimages::drop(&status.proposal);
+						Self::refund_deposit(Some(status.submission_deposit));
+						Self::deposit_event(Event::<T, I>::Rejected(index, status.tally));
+						return ReferendumInfo::Rejected(now, status.decision_deposit)
+					}
+					if deciding.confirming.is_some() {
+						// Stop confirming.
+						deciding.confirming = None;
+						Self::deposit_event(Event::<T, I>::ConfirmAborted(index));
+					}
+				}
+				alarm = Some(Self::decision_time(&deciding, &status.tally, status.track, track));
+				status.deciding = Some(deciding);
+			},
+		}
+		Self::set_alarm(&mut status, index, alarm);
+		ReferendumInfo::Ongoing(status)
+	}
+}
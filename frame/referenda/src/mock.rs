@@ -0,0 +1,350 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for Referenda pallet.
+
+use super::*;
+use crate as pallet_referenda;
+use codec::{Encode, Decode};
+use frame_support::{
+	parameter_types, ord_parameter_types, assert_ok,
+	traits::{AsEnsureOriginWithArg, EqualPrivilegeOnly, OnInitialize, preimages::StorePreimage},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill, PerThing, RuntimeDebug,
+	traits::{BlakeTwo256, IdentityLookup},
+	testing::Header,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		Referenda: pallet_referenda::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(1_000_000);
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 10;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = MaxLocks;
+}
+
+parameter_types! {
+	pub const PreimageBaseDeposit: u64 = 2;
+	pub const PreimageByteDeposit: u64 = 0;
+}
+
+impl pallet_preimage::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 100;
+}
+
+impl pallet_scheduler::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const SubmissionDeposit: u64 = 2;
+	pub const MaxQueued: u32 = 3;
+	pub const UndecidingTimeout: u64 = 20;
+	pub const AlarmInterval: u64 = 1;
+}
+ord_parameter_types! {
+	pub const Four: u64 = 4;
+}
+
+/// The tracks of the test runtime: a `Root` track on which one referendum may be decided at
+/// once, and a `None` track on which up to three may be.
+pub struct TestTracksInfo;
+impl TracksInfo<u64, u64> for TestTracksInfo {
+	type Id = u8;
+	type Origin = OriginCaller;
+	fn tracks() -> &'static [(Self::Id, TrackInfo<u64, u64>)] {
+		static DATA: [(u8, TrackInfo<u64, u64>); 2] = [
+			(
+				0u8,
+				TrackInfo {
+					name: "root",
+					max_deciding: 1,
+					decision_deposit: 10,
+					prepare_period: 4,
+					decision_period: 4,
+					confirm_period: 2,
+					min_enactment_period: 4,
+					min_approval: Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(50),
+						ceil: Perbill::from_percent(100),
+					},
+					min_support: Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(0),
+						ceil: Perbill::from_percent(100),
+					},
+				},
+			),
+			(
+				1u8,
+				TrackInfo {
+					name: "none",
+					max_deciding: 3,
+					decision_deposit: 1,
+					prepare_period: 2,
+					decision_period: 2,
+					confirm_period: 1,
+					min_enactment_period: 2,
+					min_approval: Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(95),
+						ceil: Perbill::from_percent(100),
+					},
+					min_support: Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(90),
+						ceil: Perbill::from_percent(100),
+					},
+				},
+			),
+		];
+		&DATA[..]
+	}
+	fn track_for(id: &Self::Origin) -> Result<Self::Id, ()> {
+		match id {
+			OriginCaller::system(frame_system::RawOrigin::Root) => Ok(0),
+			OriginCaller::system(frame_system::RawOrigin::None) => Ok(1),
+			_ => Err(()),
+		}
+	}
+}
+
+impl Config for Test {
+	type Call = Call;
+	type Event = Event;
+	type WeightInfo = ();
+	type Scheduler = Scheduler;
+	type Currency = pallet_balances::Pallet<Self>;
+	type SubmitOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type CancelOrigin = EnsureSignedBy<Four, u64>;
+	type KillOrigin = EnsureRoot<u64>;
+	type Slash = ();
+	type Votes = u32;
+	type Tally = Tally;
+	type SubmissionDeposit = SubmissionDeposit;
+	type MaxQueued = MaxQueued;
+	type UndecidingTimeout = UndecidingTimeout;
+	type AlarmInterval = AlarmInterval;
+	type Tracks = TestTracksInfo;
+	type Preimages = Preimage;
+}
+
+/// A simple tally in which each account has one vote, out of an electorate of 100.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Tally {
+	pub ayes: u32,
+	pub nays: u32,
+}
+
+impl<Class> VoteTally<u32, Class> for Tally {
+	fn new(_: Class) -> Self {
+		Self { ayes: 0, nays: 0 }
+	}
+
+	fn ayes(&self, _: Class) -> u32 {
+		self.ayes
+	}
+
+	fn support(&self, _: Class) -> Perbill {
+		Perbill::from_percent(self.ayes)
+	}
+
+	fn approval(&self, _: Class) -> Perbill {
+		if self.ayes + self.nays > 0 {
+			Perbill::from_rational(self.ayes, self.ayes + self.nays)
+		} else {
+			Perbill::zero()
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn unanimity(_: Class) -> Self {
+		Self { ayes: 100, nays: 0 }
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn rejection(_: Class) -> Self {
+		Self { ayes: 0, nays: 100 }
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let balances = vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100), (6, 100)];
+	pallet_balances::GenesisConfig::<Test> { balances }.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Scheduler::on_initialize(next);
+	}
+}
+
+/// A proposal which sets the balance of account 42 to `value`.
+pub fn set_balance_proposal_bounded(value: u64) -> Bounded<CallOf<Test, ()>> {
+	let c = Call::Balances(pallet_balances::Call::set_balance(42, value, 0));
+	<Preimage as StorePreimage>::bound(c).unwrap()
+}
+
+/// Submit a referendum on the `None` track, placing its decision deposit, and return its index.
+pub fn prepared_referendum(value: u64) -> ReferendumIndex {
+	let index = ReferendumCount::<Test>::get();
+	assert_ok!(Referenda::submit(
+		Origin::signed(2),
+		frame_system::RawOrigin::None.into(),
+		set_balance_proposal_bounded(value),
+		DispatchTime::At(10),
+	));
+	assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), index));
+	index
+}
+
+pub fn set_tally(index: ReferendumIndex, ayes: u32, nays: u32) {
+	<Referenda as Polling<Tally>>::access_poll(index, |status| {
+		let tally = status.ensure_ongoing().unwrap().0;
+		tally.ayes = ayes;
+		tally.nays = nays;
+	});
+}
+
+pub fn deciding_and_queued(track: u8) -> (u32, Vec<ReferendumIndex>) {
+	let deciding = DecidingCount::<Test>::get(track);
+	let queued = TrackQueue::<Test>::get(track).iter().map(|x| x.0).collect();
+	(deciding, queued)
+}
+
+pub fn is_deciding(index: ReferendumIndex) -> bool {
+	matches!(
+		ReferendumInfoFor::<Test>::get(index),
+		Some(ReferendumInfo::Ongoing(ReferendumStatus { deciding: Some(_), .. }))
+	)
+}
+
+pub fn is_confirming(index: ReferendumIndex) -> bool {
+	matches!(
+		ReferendumInfoFor::<Test>::get(index),
+		Some(ReferendumInfo::Ongoing(ReferendumStatus {
+			deciding: Some(DecidingStatus { confirming: Some(_), .. }),
+			..
+		}))
+	)
+}
+
+pub fn approved_since(index: ReferendumIndex) -> u64 {
+	match ReferendumInfoFor::<Test>::get(index).unwrap() {
+		ReferendumInfo::Approved(since, ..) => since,
+		_ => panic!("Not approved"),
+	}
+}
+
+pub fn rejected_since(index: ReferendumIndex) -> u64 {
+	match ReferendumInfoFor::<Test>::get(index).unwrap() {
+		ReferendumInfo::Rejected(since, ..) => since,
+		_ => panic!("Not rejected"),
+	}
+}
+
+pub fn timed_out_since(index: ReferendumIndex) -> u64 {
+	match ReferendumInfoFor::<Test>::get(index).unwrap() {
+		ReferendumInfo::TimedOut(since, ..) => since,
+		_ => panic!("Not timed out"),
+	}
+}
@@ -0,0 +1,353 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The tests for the referenda pallet.
+
+use super::*;
+use crate::mock::{*, Call};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use pallet_balances::Error as BalancesError;
+use sp_core::H256;
+use sp_runtime::traits::BadOrigin;
+
+fn submit_root(who: u64, value: u64) -> ReferendumIndex {
+	let index = ReferendumCount::<Test>::get();
+	assert_ok!(Referenda::submit(
+		Origin::signed(who),
+		frame_system::RawOrigin::Root.into(),
+		set_balance_proposal_bounded(value),
+		DispatchTime::At(10),
+	));
+	index
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ReferendumCount::<Test>::get(), 0);
+		assert_eq!(Balances::free_balance(42), 0);
+		assert_eq!(Balances::total_issuance(), 600);
+	});
+}
+
+#[test]
+fn basic_happy_path_works() {
+	new_test_ext().execute_with(|| {
+		// #1: submit
+		let i = submit_root(1, 1);
+		assert_eq!(Balances::reserved_balance(&1), 2);
+		assert_eq!(ReferendumCount::<Test>::get(), 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		assert_eq!(Balances::reserved_balance(&2), 10);
+		set_tally(i, 100, 0);
+		run_to(4);
+		assert!(!is_deciding(i));
+
+		// #5: the preparation period is over and the referendum starts confirming right away.
+		run_to(5);
+		assert!(is_deciding(i));
+		assert!(is_confirming(i));
+		assert_noop!(
+			Referenda::refund_decision_deposit(Origin::signed(2), i),
+			Error::<Test>::Unfinished
+		);
+
+		// #7: confirmed; the submission deposit is returned.
+		run_to(7);
+		assert_eq!(approved_since(i), 7);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(
+			<Referenda as Polling<Tally>>::access_poll(i, |s| match s {
+				PollStatus::Completed(end, approved) => Some((end, approved)),
+				_ => None,
+			}),
+			Some((7, true)),
+		);
+
+		// #11: enacted, after the track's minimum enactment period.
+		run_to(10);
+		assert_eq!(Balances::free_balance(42), 0);
+		run_to(11);
+		assert_eq!(Balances::free_balance(42), 1);
+
+		assert_ok!(Referenda::refund_decision_deposit(Origin::signed(3), i));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_noop!(
+			Referenda::refund_decision_deposit(Origin::signed(2), i),
+			Error::<Test>::NoDeposit
+		);
+	});
+}
+
+#[test]
+fn confirming_can_be_aborted_and_rejected() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		set_tally(i, 100, 0);
+		run_to(5);
+		assert!(is_confirming(i));
+
+		set_tally(i, 0, 100);
+		run_to(6);
+		assert!(is_deciding(i));
+		assert!(!is_confirming(i));
+
+		run_to(8);
+		assert!(is_deciding(i));
+		run_to(9);
+		assert_eq!(rejected_since(i), 9);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 10);
+
+		run_to(20);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn submit_checks_origin_track_and_funds() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Referenda::submit(
+				Origin::signed(1),
+				frame_system::RawOrigin::Signed(1).into(),
+				set_balance_proposal_bounded(1),
+				DispatchTime::At(10),
+			),
+			Error::<Test>::NoTrack
+		);
+		assert_noop!(
+			Referenda::submit(
+				Origin::signed(10),
+				frame_system::RawOrigin::Root.into(),
+				set_balance_proposal_bounded(1),
+				DispatchTime::At(10),
+			),
+			BalancesError::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			Referenda::submit(
+				Origin::none(),
+				frame_system::RawOrigin::Root.into(),
+				set_balance_proposal_bounded(1),
+				DispatchTime::At(10),
+			),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn decision_deposit_can_only_be_placed_once() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_noop!(
+			Referenda::place_decision_deposit(Origin::signed(10), i),
+			BalancesError::<Test>::InsufficientBalance
+		);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		assert_noop!(
+			Referenda::place_decision_deposit(Origin::signed(3), i),
+			Error::<Test>::HasDeposit
+		);
+		assert_noop!(
+			Referenda::place_decision_deposit(Origin::signed(3), 1),
+			Error::<Test>::NotOngoing
+		);
+	});
+}
+
+#[test]
+fn undecided_referendum_times_out() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		run_to(20);
+		assert!(matches!(ReferendumInfoFor::<Test>::get(i), Some(ReferendumInfo::Ongoing(_))));
+
+		run_to(21);
+		assert_eq!(timed_out_since(i), 21);
+		// The submission deposit is slashed.
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::free_balance(&1), 98);
+		assert_noop!(
+			Referenda::refund_decision_deposit(Origin::signed(1), i),
+			Error::<Test>::NoDeposit
+		);
+	});
+}
+
+#[test]
+fn queueing_works() {
+	new_test_ext().execute_with(|| {
+		// Seven referenda on the `None` track, which decides three at once and queues three.
+		let ayes = [1, 2, 3, 10, 20, 5, 7];
+		for (i, a) in ayes.iter().enumerate() {
+			assert_eq!(prepared_referendum(1), i as u32);
+			set_tally(i as u32, *a, 0);
+		}
+
+		// #3: the first three start being decided, the rest are queued ordered by approvals. The
+		// one with the fewest approvals is kicked from the full queue.
+		run_to(3);
+		assert!((0..3).all(is_deciding));
+		assert_eq!(deciding_and_queued(1), (3, vec![6, 3, 4]));
+
+		// #4: the kicked referendum cannot get back into the queue.
+		run_to(4);
+		assert_eq!(deciding_and_queued(1), (3, vec![6, 3, 4]));
+		assert!(!is_deciding(5));
+
+		// #5: the first three are rejected and the queue is drained in order of approvals.
+		run_to(5);
+		assert!((0..3).all(|i| rejected_since(i) == 5));
+		assert!([3, 4, 6].iter().cloned().all(is_deciding));
+		assert_eq!(deciding_and_queued(1), (3, vec![]));
+
+		// #7: the rest are rejected and the track is free again.
+		run_to(7);
+		assert!([3, 4, 6].iter().all(|i| rejected_since(*i) == 7));
+		assert_eq!(deciding_and_queued(1), (0, vec![]));
+
+		// A change in its tally gets the remaining referendum going.
+		set_tally(5, 6, 0);
+		run_to(8);
+		assert!(is_deciding(5));
+		assert_eq!(deciding_and_queued(1), (1, vec![]));
+	});
+}
+
+#[test]
+fn queue_is_reordered_when_tally_changes() {
+	new_test_ext().execute_with(|| {
+		for i in 0..5 {
+			assert_eq!(prepared_referendum(1), i);
+			set_tally(i, i + 1, 0);
+		}
+		run_to(3);
+		assert_eq!(deciding_and_queued(1), (3, vec![3, 4]));
+
+		set_tally(3, 50, 0);
+		run_to(4);
+		assert_eq!(deciding_and_queued(1), (3, vec![4, 3]));
+	});
+}
+
+#[test]
+fn cancel_works() {
+	new_test_ext().execute_with(|| {
+		let i = prepared_referendum(1);
+		run_to(3);
+		assert_eq!(deciding_and_queued(1), (1, vec![]));
+
+		assert_noop!(Referenda::cancel(Origin::signed(3), i), BadOrigin);
+		assert_ok!(Referenda::cancel(Origin::signed(4), i));
+		assert_eq!(deciding_and_queued(1), (0, vec![]));
+		assert!(matches!(
+			ReferendumInfoFor::<Test>::get(i),
+			Some(ReferendumInfo::Cancelled(3, Some(_)))
+		));
+		assert_noop!(Referenda::cancel(Origin::signed(4), i), Error::<Test>::NotOngoing);
+
+		// Both deposits can be returned.
+		assert_eq!(Balances::reserved_balance(&2), 1);
+		assert_ok!(Referenda::refund_decision_deposit(Origin::signed(2), i));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn kill_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		run_to(5);
+		assert_eq!(deciding_and_queued(0), (1, vec![]));
+
+		assert_noop!(Referenda::kill(Origin::signed(4), i), BadOrigin);
+		assert_ok!(Referenda::kill(Origin::root(), i));
+		assert_eq!(deciding_and_queued(0), (0, vec![]));
+		assert!(matches!(ReferendumInfoFor::<Test>::get(i), Some(ReferendumInfo::Killed(5))));
+
+		// Both deposits are slashed.
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::free_balance(&1), 98);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::free_balance(&2), 90);
+		assert_noop!(
+			Referenda::refund_decision_deposit(Origin::signed(2), i),
+			Error::<Test>::NoDeposit
+		);
+	});
+}
+
+#[test]
+fn proposal_preimage_is_held_until_conclusion() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Referenda::submit(
+				Origin::signed(1),
+				frame_system::RawOrigin::Root.into(),
+				Bounded::lookup(H256::repeat_byte(1), 100),
+				DispatchTime::At(10),
+			),
+			Error::<Test>::PreimageNotExist
+		);
+
+		let call = Call::System(frame_system::Call::remark(vec![0u8; 200])).encode();
+		assert_ok!(Preimage::note_preimage(Origin::signed(3), call.clone()));
+		let hash = sp_io::hashing::blake2_256(&call).into();
+		let proposal = Bounded::lookup(hash, call.len() as u32);
+		assert_ok!(Referenda::submit(
+			Origin::signed(1),
+			frame_system::RawOrigin::Root.into(),
+			proposal,
+			DispatchTime::At(10),
+		));
+		assert!(Preimage::is_requested(&hash));
+
+		assert_ok!(Referenda::cancel(Origin::signed(4), 0));
+		assert!(!Preimage::is_requested(&hash));
+	});
+}
+
+#[test]
+fn unavailable_proposal_is_reported_on_approval() {
+	new_test_ext().execute_with(|| {
+		// A preimage which can not be decoded as a call.
+		let data = vec![255u8; 200];
+		assert_ok!(Preimage::note_preimage(Origin::signed(3), data.clone()));
+		let hash: H256 = sp_io::hashing::blake2_256(&data).into();
+		assert_ok!(Referenda::submit(
+			Origin::signed(1),
+			frame_system::RawOrigin::Root.into(),
+			Bounded::lookup(hash, data.len() as u32),
+			DispatchTime::At(10),
+		));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		set_tally(0, 100, 0);
+
+		run_to(7);
+		assert_eq!(approved_since(0), 7);
+		let event = <Test as frame_system::Config>::Event::from(
+			crate::Event::<Test>::ProposalUnavailable(0, hash)
+		);
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!Preimage::is_requested(&hash));
+	});
+}
@@ -0,0 +1,375 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use super::*;
+use codec::{Encode, Decode, Codec};
+use sp_std::fmt::Debug;
+use sp_arithmetic::{Perbill, PerThing, traits::Saturating};
+use sp_runtime::RuntimeDebug;
+use frame_support::{
+	Parameter,
+	traits::{Currency, OriginTrait, schedule::{Anon, DispatchTime}, preimages::Bounded},
+};
+
+pub type BalanceOf<T, I = ()> =
+	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T, I> = <<T as Config<I>>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+pub type CallOf<T, I> = <T as Config<I>>::Call;
+pub type VotesOf<T, I> = <T as Config<I>>::Votes;
+pub type TallyOf<T, I> = <T as Config<I>>::Tally;
+pub type PalletsOriginOf<T> =
+	<<T as frame_system::Config>::Origin as OriginTrait>::PalletsOrigin;
+pub type ReferendumInfoOf<T, I> = ReferendumInfo<
+	TrackIdOf<T, I>,
+	PalletsOriginOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	Bounded<CallOf<T, I>>,
+	BalanceOf<T, I>,
+	TallyOf<T, I>,
+	<T as frame_system::Config>::AccountId,
+	ScheduleAddressOf<T, I>,
+>;
+pub type ReferendumStatusOf<T, I> = ReferendumStatus<
+	TrackIdOf<T, I>,
+	PalletsOriginOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	Bounded<CallOf<T, I>>,
+	BalanceOf<T, I>,
+	TallyOf<T, I>,
+	<T as frame_system::Config>::AccountId,
+	ScheduleAddressOf<T, I>,
+>;
+pub type DecidingStatusOf<T> = DecidingStatus<<T as frame_system::Config>::BlockNumber>;
+pub type TrackInfoOf<T, I> = TrackInfo<BalanceOf<T, I>, <T as frame_system::Config>::BlockNumber>;
+pub type ScheduleAddressOf<T, I> = <<T as Config<I>>::Scheduler as Anon<
+	<T as frame_system::Config>::BlockNumber,
+	CallOf<T, I>,
+	PalletsOriginOf<T>,
+>>::Address;
+pub type TrackIdOf<T, I> = <<T as Config<I>>::Tracks as TracksInfo<
+	BalanceOf<T, I>,
+	<T as frame_system::Config>::BlockNumber,
+>>::Id;
+
+/// A referendum index.
+pub type ReferendumIndex = u32;
+
+/// A deposit held by some account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Deposit<AccountId, Balance> {
+	/// The depositor.
+	pub who: AccountId,
+	/// The amount held.
+	pub amount: Balance,
+}
+
+/// The status of a referendum which is deciding.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DecidingStatus<BlockNumber> {
+	/// When this referendum began being "decided". If confirming, then the end will actually be
+	/// delayed until the end of the confirmation period.
+	pub since: BlockNumber,
+	/// If `Some`, then the referendum has entered confirmation stage and will end at
+	/// the block number as long as it doesn't lose its approval in the meantime.
+	pub confirming: Option<BlockNumber>,
+}
+
+/// Information on a track: a class of referenda which share an origin and parameters.
+#[derive(Clone, RuntimeDebug)]
+pub struct TrackInfo<Balance, Moment> {
+	/// Name of this track.
+	pub name: &'static str,
+	/// A limit for the number of referenda on this track that can be being decided at once.
+	/// For Root origin this should generally be just one.
+	pub max_deciding: u32,
+	/// Amount that must be placed on deposit before a decision can be made.
+	pub decision_deposit: Balance,
+	/// Amount of time this must be submitted for before a decision can be made.
+	pub prepare_period: Moment,
+	/// Amount of time that a decision may take to be approved prior to cancellation.
+	pub decision_period: Moment,
+	/// Amount of time that the approval criteria must hold before it can be approved.
+	pub confirm_period: Moment,
+	/// Minimum amount of time that an approved proposal must be in the dispatch queue.
+	pub min_enactment_period: Moment,
+	/// Minimum aye votes as percentage of overall conviction-weighted votes needed for
+	/// approval as a function of time into decision period.
+	pub min_approval: Curve,
+	/// Minimum pre-conviction aye-votes ("support") as percentage of overall population that is
+	/// needed for approval as a function of time into decision period.
+	pub min_support: Curve,
+}
+
+/// Information on the voting tracks.
+pub trait TracksInfo<Balance, Moment> {
+	/// The identifier for a track.
+	type Id: Copy + Parameter + Ord + PartialOrd + Send + Sync + 'static;
+
+	/// The origin type from which a track is implied.
+	type Origin;
+
+	/// Return the array of known tracks and their information.
+	fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, Moment>)];
+
+	/// Determine the voting track for the given `origin`.
+	fn track_for(origin: &Self::Origin) -> Result<Self::Id, ()>;
+
+	/// Return the track info for track `id`, by default this just looks it up in `Self::tracks()`.
+	fn info(id: Self::Id) -> Option<&'static TrackInfo<Balance, Moment>> {
+		Self::tracks().iter().find(|x| x.0 == id).map(|x| &x.1)
+	}
+}
+
+/// Info regarding an ongoing referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReferendumStatus<
+	TrackId: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Origin: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Moment: Parameter + Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Call: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Balance: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Tally: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	AccountId: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	ScheduleAddress: Eq + PartialEq + Debug + Encode + Decode + Clone,
+> {
+	/// The track of this referendum.
+	pub track: TrackId,
+	/// The origin for this referendum.
+	pub origin: Origin,
+	/// The hash of the proposal up for referendum.
+	pub proposal: Call,
+	/// The time the proposal should be scheduled for enactment.
+	pub enactment: DispatchTime<Moment>,
+	/// The time of submission. Once `UndecidingTimeout` passes, it may be closed by anyone if it
+	/// `deciding` is `None`.
+	pub submitted: Moment,
+	/// The deposit reserved for the submission of this referendum.
+	pub submission_deposit: Deposit<AccountId, Balance>,
+	/// The deposit reserved for this referendum to be decided.
+	pub decision_deposit: Option<Deposit<AccountId, Balance>>,
+	/// The status of a decision being made. If `None`, it has not entered the deciding period.
+	pub deciding: Option<DecidingStatus<Moment>>,
+	/// The current tally of votes in this referendum.
+	pub tally: Tally,
+	/// Whether we have been placed in the queue for being decided or not.
+	pub in_queue: bool,
+	/// The next scheduled wake-up and the address of the scheduled task, if `Some`.
+	pub alarm: Option<(Moment, ScheduleAddress)>,
+}
+
+/// Info regarding a referendum, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ReferendumInfo<
+	TrackId: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Origin: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Moment: Parameter + Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Call: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Balance: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Tally: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	AccountId: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	ScheduleAddress: Eq + PartialEq + Debug + Encode + Decode + Clone,
+> {
+	/// Referendum has been submitted and is being voted on.
+	Ongoing(
+		ReferendumStatus<TrackId, Origin, Moment, Call, Balance, Tally, AccountId, ScheduleAddress>
+	),
+	/// Referendum finished with approval. Its decision deposit may be refunded.
+	Approved(Moment, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished with rejection. Its decision deposit may be refunded.
+	Rejected(Moment, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished with cancellation. Its decision deposit may be refunded.
+	Cancelled(Moment, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished and was never decided. Its decision deposit may be refunded.
+	TimedOut(Moment, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished with a kill.
+	Killed(Moment),
+}
+
+impl<
+	TrackId: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Origin: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Moment: Parameter + Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Call: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Balance: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	Tally: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	AccountId: Eq + PartialEq + Debug + Encode + Decode + Clone,
+	ScheduleAddress: Eq + PartialEq + Debug + Encode + Decode + Clone,
+> ReferendumInfo<TrackId, Origin, Moment, Call, Balance, Tally, AccountId, ScheduleAddress> {
+	/// Take the decision deposit from `self`, if there is one. Returns an `Err` if `self` is not
+	/// in a valid state for the decision deposit to be refunded.
+	pub fn take_decision_deposit(&mut self) -> Result<Option<Deposit<AccountId, Balance>>, ()> {
+		use ReferendumInfo::*;
+		match self {
+			Ongoing(x) if x.decision_deposit.is_none() => Ok(None),
+			// Cannot refund deposit if Ongoing as this breaks assumptions.
+			Ongoing(_) => Err(()),
+			Approved(_, d) | Rejected(_, d) | Cancelled(_, d) | TimedOut(_, d) => Ok(d.take()),
+			Killed(_) => Ok(None),
+		}
+	}
+}
+
+/// Type for describing a curve over the 2-dimensional space of axes between 0-1, as represented
+/// by `(Perbill, Perbill)`.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum Curve {
+	/// Linear curve starting at `(0, ceil)`, proceeding linearly to `(length, floor)`, then
+	/// remaining at `floor` until the end of the period.
+	LinearDecreasing { length: Perbill, floor: Perbill, ceil: Perbill },
+	/// Stepped curve, beginning at `(0, begin)`, then remaining constant for `period`, at which
+	/// point it steps down to `(period, begin - step)`. It then remains constant for another
+	/// `period` before stepping down to `(period * 2, begin - step * 2)`. This pattern continues
+	/// but the `y` component has a lower limit of `end`.
+	SteppedDecreasing { begin: Perbill, end: Perbill, step: Perbill, period: Perbill },
+}
+
+impl Curve {
+	/// Determine the `y` value for the given `x` value.
+	pub fn threshold(&self, x: Perbill) -> Perbill {
+		match self {
+			Self::LinearDecreasing { length, floor, ceil } =>
+				ceil.saturating_sub(x.min(*length).div_ratio(*length) * ceil.saturating_sub(*floor)),
+			Self::SteppedDecreasing { begin, end, step, period } =>
+				begin.saturating_sub(step.int_mul(x.int_div(*period))).max(*end),
+		}
+	}
+
+	/// Determine the smallest `x` value such that `passing` returns `true` when passed along with
+	/// the given `y` value.
+	///
+	/// If `passing` never returns `true` for any value of `x` when paired with `y`, then
+	/// `Perbill::one` may be returned.
+	pub fn delay(&self, y: Perbill) -> Perbill {
+		match self {
+			Self::LinearDecreasing { length, floor, ceil } =>
+				if y < *floor {
+					Perbill::one()
+				} else if y > *ceil {
+					Perbill::zero()
+				} else {
+					ceil.saturating_sub(y).div_ratio(ceil.saturating_sub(*floor)) * *length
+				},
+			Self::SteppedDecreasing { begin, end, step, period } =>
+				if y < *end {
+					Perbill::one()
+				} else {
+					period.int_mul(
+						begin.saturating_sub(y).saturating_add(step.less_epsilon()).int_div(*step)
+					)
+				},
+		}
+	}
+
+	/// Return `true` iff the `y` value is greater than the curve at the `x`.
+	pub fn passing(&self, x: Perbill, y: Perbill) -> bool {
+		y >= self.threshold(x)
+	}
+}
+
+/// Extra arithmetic on `Perbill` values used by the curves.
+trait PerbillExt: Sized {
+	fn div_ratio(self, rhs: Self) -> Self;
+	fn int_mul(self, n: u32) -> Self;
+	fn int_div(self, rhs: Self) -> u32;
+	fn less_epsilon(self) -> Self;
+}
+
+impl PerbillExt for Perbill {
+	fn div_ratio(self, rhs: Self) -> Self {
+		if rhs.is_zero() {
+			Perbill::one()
+		} else {
+			Perbill::from_rational(self.deconstruct(), rhs.deconstruct())
+		}
+	}
+
+	fn int_mul(self, n: u32) -> Self {
+		Perbill::from_parts(self.deconstruct().saturating_mul(n))
+	}
+
+	fn int_div(self, rhs: Self) -> u32 {
+		if rhs.is_zero() {
+			u32::max_value()
+		} else {
+			self.deconstruct() / rhs.deconstruct()
+		}
+	}
+
+	fn less_epsilon(self) -> Self {
+		Perbill::from_parts(self.deconstruct().saturating_sub(1))
+	}
+}
+
+/// Something which can tally votes and be a `Tally` of referenda.
+pub trait ReferendaTally<Votes, Class>: VoteTally<Votes, Class>
+	+ Codec + Eq + PartialEq + Debug + Clone {}
+impl<T, Votes, Class> ReferendaTally<Votes, Class> for T
+	where T: VoteTally<Votes, Class> + Codec + Eq + PartialEq + Debug + Clone {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn percent(x: u32) -> Perbill {
+		Perbill::from_percent(x)
+	}
+
+	const LINEAR: Curve = Curve::LinearDecreasing {
+		length: Perbill::from_percent(50),
+		floor: Perbill::from_percent(10),
+		ceil: Perbill::from_percent(90),
+	};
+	const STEPPED: Curve = Curve::SteppedDecreasing {
+		begin: Perbill::from_percent(80),
+		end: Perbill::from_percent(30),
+		step: Perbill::from_percent(10),
+		period: Perbill::from_percent(15),
+	};
+
+	#[test]
+	fn linear_curve_works() {
+		assert_eq!(LINEAR.threshold(percent(0)), percent(90));
+		assert_eq!(LINEAR.threshold(percent(25)), percent(50));
+		assert_eq!(LINEAR.threshold(percent(50)), percent(10));
+		assert_eq!(LINEAR.threshold(percent(100)), percent(10));
+
+		assert_eq!(LINEAR.delay(percent(100)), percent(0));
+		assert_eq!(LINEAR.delay(percent(50)), percent(25));
+		assert_eq!(LINEAR.delay(percent(10)), percent(50));
+		assert_eq!(LINEAR.delay(percent(9)), percent(100));
+	}
+
+	#[test]
+	fn stepped_curve_works() {
+		assert_eq!(STEPPED.threshold(percent(0)), percent(80));
+		assert_eq!(STEPPED.threshold(percent(15)), percent(70));
+		assert_eq!(STEPPED.threshold(percent(29)), percent(70));
+		assert_eq!(STEPPED.threshold(percent(30)), percent(60));
+		assert_eq!(STEPPED.threshold(percent(100)), percent(30));
+
+		assert_eq!(STEPPED.delay(percent(100)), percent(0));
+		assert_eq!(STEPPED.delay(percent(80)), percent(0));
+		assert_eq!(STEPPED.delay(percent(75)), percent(15));
+		assert_eq!(STEPPED.delay(percent(70)), percent(15));
+		assert_eq!(STEPPED.delay(percent(30)), percent(75));
+		assert_eq!(STEPPED.delay(percent(29)), percent(100));
+	}
+}
@@ -0,0 +1,52 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for remark pallet.
+
+use super::{Error, Event, mock::*};
+use frame_support::{assert_ok, assert_noop};
+use frame_system::RawOrigin;
+
+#[test]
+fn generates_event() {
+	new_test_ext().execute_with(|| {
+		let caller = 1;
+		let data = vec![0u8; 100];
+		// Events are not registered at genesis.
+		System::set_block_number(1);
+		assert_ok!(Remark::store(RawOrigin::Signed(caller).into(), data.clone()));
+		let events = System::events();
+		assert_eq!(events.len(), 1);
+		assert_eq!(
+			events[0].event,
+			Event::Stored(caller, sp_io::hashing::blake2_256(&data).into()).into(),
+		);
+	});
+}
+
+#[test]
+fn does_not_store_empty() {
+	new_test_ext().execute_with(|| {
+		let caller = 1;
+		System::set_block_number(1);
+		assert_noop!(
+			Remark::store(RawOrigin::Signed(caller).into(), vec![]),
+			Error::<Test>::Empty
+		);
+		assert!(System::events().is_empty());
+	});
+}
@@ -124,6 +124,48 @@ benchmarks! {
 		);
 	}
 
+	set_retry {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+	}: _(RawOrigin::Root, (when, 0), 10, T::BlockNumber::one())
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_some(), "didn't set retry");
+	}
+
+	set_retry_named {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+	}: _(RawOrigin::Root, 0.encode(), 10, T::BlockNumber::one())
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_some(), "didn't set retry");
+	}
+
+	cancel_retry {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		Scheduler::<T>::set_retry(RawOrigin::Root.into(), (when, 0), 10, T::BlockNumber::one())?;
+	}: _(RawOrigin::Root, (when, 0))
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_none(), "didn't cancel retry");
+	}
+
+	cancel_retry_named {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		Scheduler::<T>::set_retry(RawOrigin::Root.into(), (when, 0), 10, T::BlockNumber::one())?;
+	}: _(RawOrigin::Root, 0.encode())
+	verify {
+		ensure!(Retries::<T>::get((when, 0)).is_none(), "didn't cancel retry");
+	}
+
 	// TODO [#7141]: Make this more complex and flexible so it can be used in automation.
 	#[extra]
 	on_initialize {
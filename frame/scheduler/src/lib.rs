@@ -47,6 +47,16 @@
 //! * `schedule_named` - augments the `schedule` interface with an additional
 //!   `Vec<u8>` parameter that can be used for identification.
 //! * `cancel_named` - the named complement to the cancel function.
+//! * `set_retry` - configure a scheduled dispatch to be retried a number of times, with a given
+//!   period between attempts, if it fails.
+//! * `set_retry_named` - the named complement to the `set_retry` function.
+//! * `cancel_retry` - remove the retry configuration of a scheduled dispatch.
+//! * `cancel_retry_named` - the named complement to the `cancel_retry` function.
+//!
+//! A scheduled dispatch may only be canceled, or have its retry configuration changed, by an
+//! origin that has at least the privilege of the origin it was scheduled with, as decided by
+//! `Config::OriginPrivilegeCmp`. This allows e.g. governance to cancel a call it scheduled
+//! through a more privileged origin.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -54,13 +64,15 @@
 mod benchmarking;
 pub mod weights;
 
-use sp_std::{prelude::*, marker::PhantomData, borrow::Borrow};
+use sp_std::{prelude::*, marker::PhantomData, borrow::Borrow, cmp::Ordering};
 use codec::{Encode, Decode, Codec};
 use sp_runtime::{RuntimeDebug, traits::{Zero, One, BadOrigin, Saturating}};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, IterableStorageMap,
 	dispatch::{Dispatchable, DispatchError, DispatchResult, Parameter},
-	traits::{Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType},
+	traits::{
+		Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType, PrivilegeCmp,
+	},
 	weights::{GetDispatchInfo, Weight},
 };
 use frame_system::{self as system, ensure_signed};
@@ -96,6 +108,15 @@ pub trait Config: system::Config {
 	/// Not strictly enforced, but used for weight estimation.
 	type MaxScheduledPerBlock: Get<u32>;
 
+	/// Compare the privileges of origins.
+	///
+	/// This will be used when canceling a task or changing its retry configuration, to ensure
+	/// that the origin doing so has at least the privilege of the origin that scheduled the task.
+	///
+	/// For simplicity [`EqualPrivilegeOnly`](frame_support::traits::EqualPrivilegeOnly) can be
+	/// used. This will only allow an origin to alter a task it scheduled itself.
+	type OriginPrivilegeCmp: PrivilegeCmp<Self::PalletsOrigin>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -131,6 +152,17 @@ pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	_phantom: PhantomData<AccountId>,
 }
 
+/// The configuration of the retry mechanism for a given task.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, Encode, Decode)]
+pub struct RetryConfig<BlockNumber> {
+	/// Initial amount of retries allowed.
+	pub total_retries: u8,
+	/// Amount of retries left.
+	pub remaining: u8,
+	/// Number of blocks between a failed attempt and the next retry.
+	pub period: BlockNumber,
+}
+
 /// The current version of Scheduled struct.
 pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> = ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId>;
 
@@ -158,6 +190,11 @@ decl_storage! {
 		/// Lookup from identity to the block number and index of the task.
 		Lookup: map hasher(twox_64_concat) Vec<u8> => Option<TaskAddress<T::BlockNumber>>;
 
+		/// Retry configurations for items to be executed, indexed by task address.
+		pub Retries get(fn retries):
+			map hasher(blake2_128_concat) TaskAddress<T::BlockNumber>
+			=> Option<RetryConfig<T::BlockNumber>>;
+
 		/// Storage version of the pallet.
 		///
 		/// New networks start with last version.
@@ -173,6 +210,10 @@ decl_event!(
 		Canceled(BlockNumber, u32),
 		/// Dispatched some task. \[task, id, result\]
 		Dispatched(TaskAddress<BlockNumber>, Option<Vec<u8>>, DispatchResult),
+		/// Set a retry configuration for some task. \[task, id, period, retries\]
+		RetrySet(TaskAddress<BlockNumber>, Option<Vec<u8>>, BlockNumber, u8),
+		/// Canceled the retry configuration of some task. \[task, id\]
+		RetryCancelled(TaskAddress<BlockNumber>, Option<Vec<u8>>),
 	}
 );
 
@@ -186,6 +227,8 @@ decl_error! {
 		TargetBlockNumberInPast,
 		/// Reschedule failed because it does not change scheduled time.
 		RescheduleNoChange,
+		/// The period between retries must be at least one block.
+		InvalidRetryPeriod,
 	}
 }
 
@@ -315,16 +358,70 @@ decl_module! {
 			)?;
 		}
 
+		/// Set a retry configuration for a task so that, in case its scheduled run fails, it will
+		/// be retried after `period` blocks, for a total amount of `retries` retries or until it
+		/// succeeds.
+		///
+		/// Tasks which need to be scheduled for a retry are still subject to weight metering and
+		/// agenda space, same as a regular task. If a periodic task fails, it will be scheduled
+		/// normally while the task is retrying.
+		///
+		/// Tasks scheduled as a result of a retry for a periodic task are unnamed, non-periodic
+		/// clones of the original task. Their retry configuration will be derived from the
+		/// original task's configuration, but will have a lower value for `remaining` than the
+		/// original `total_retries`.
+		#[weight = T::WeightInfo::set_retry()]
+		fn set_retry(origin,
+			task: TaskAddress<T::BlockNumber>,
+			retries: u8,
+			period: T::BlockNumber,
+		) {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::Origin::from(origin);
+			Self::do_set_retry(Some(origin.caller().clone()), task, retries, period)?;
+		}
+
+		/// Set a retry configuration for a named task so that, in case its scheduled run fails,
+		/// it will be retried after `period` blocks, for a total amount of `retries` retries or
+		/// until it succeeds.
+		///
+		/// See [`set_retry`] for details.
+		#[weight = T::WeightInfo::set_retry_named()]
+		fn set_retry_named(origin, id: Vec<u8>, retries: u8, period: T::BlockNumber) {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::Origin::from(origin);
+			let task = Lookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			Self::do_set_retry(Some(origin.caller().clone()), task, retries, period)?;
+		}
+
+		/// Remove the retry configuration of a task.
+		#[weight = T::WeightInfo::cancel_retry()]
+		fn cancel_retry(origin, task: TaskAddress<T::BlockNumber>) {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::Origin::from(origin);
+			Self::do_cancel_retry(Some(origin.caller().clone()), task)?;
+		}
+
+		/// Remove the retry configuration of a named task.
+		#[weight = T::WeightInfo::cancel_retry_named()]
+		fn cancel_retry_named(origin, id: Vec<u8>) {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::Origin::from(origin);
+			let task = Lookup::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			Self::do_cancel_retry(Some(origin.caller().clone()), task)?;
+		}
+
 		/// Execute the scheduled calls
 		///
 		/// # <weight>
 		/// - S = Number of already scheduled calls
 		/// - N = Named scheduled calls
 		/// - P = Periodic Calls
+		/// - R = Calls with a retry configuration
 		/// - Base Weight: 9.243 + 23.45 * S µs
 		/// - DB Weight:
-		///     - Read: Agenda + Lookup * N + Agenda(Future) * P
-		///     - Write: Agenda + Lookup * N  + Agenda(future) * P
+		///     - Read: Agenda + Lookup * N + Agenda(Future) * P + Retries * S
+		///     - Write: Agenda + Lookup * N  + Agenda(future) * P + Retries * R
 		/// # </weight>
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			let limit = T::MaximumWeight::get();
@@ -341,6 +438,8 @@ decl_module! {
 			}
 			queued.sort_by_key(|(_, s)| s.priority);
 			let base_weight: Weight = T::DbWeight::get().reads_writes(1, 2); // Agenda + Agenda(next)
+			// Retries
+			let retry_weight: Weight = T::DbWeight::get().reads_writes(1, 2);
 			let mut total_weight: Weight = 0;
 			queued.into_iter()
 				.enumerate()
@@ -366,6 +465,8 @@ decl_module! {
 						// Read/Write Agenda for future block
 						*cumulative_weight = cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 					}
+					// Read/Remove the retry configuration, and possibly schedule a retry.
+					*cumulative_weight = cumulative_weight.saturating_add(retry_weight);
 
 					Some((order, index, *cumulative_weight, s))
 				})
@@ -377,6 +478,21 @@ decl_module! {
 					if s.priority <= schedule::HARD_DEADLINE || cumulative_weight <= limit || order == 0 {
 						let r = s.call.clone().dispatch(s.origin.clone().into());
 						let maybe_id = s.maybe_id.clone();
+						let maybe_retry = Retries::<T>::take((now, index));
+						// A failed task with retries left is attempted again later. The retry of
+						// a periodic task is an unnamed, non-periodic clone of it, since the
+						// original keeps being scheduled on its own.
+						let maybe_retry_task = match maybe_retry {
+							Some(retry) if r.is_err() && retry.remaining > 0 => {
+								let mut task = s.clone();
+								if task.maybe_periodic.is_some() {
+									task.maybe_id = None;
+									task.maybe_periodic = None;
+								}
+								Some((task, retry))
+							},
+							_ => None,
+						};
 						if let &Some((period, count)) = &s.maybe_periodic {
 							if count > 1 {
 								s.maybe_periodic = Some((period, count - 1));
@@ -384,10 +500,17 @@ decl_module! {
 								s.maybe_periodic = None;
 							}
 							let next = now + period;
+							let next_index = Agenda::<T>::decode_len(next).unwrap_or(0) as u32;
 							// If scheduled is named, place it's information in `Lookup`
 							if let Some(ref id) = s.maybe_id {
-								let next_index = Agenda::<T>::decode_len(now + period).unwrap_or(0);
-								Lookup::<T>::insert(id, (next, next_index as u32));
+								Lookup::<T>::insert(id, (next, next_index));
+							}
+							// The next run of a periodic task starts with all its retries again.
+							if let Some(retry) = maybe_retry {
+								Retries::<T>::insert(
+									(next, next_index),
+									RetryConfig { remaining: retry.total_retries, ..retry },
+								);
 							}
 							Agenda::<T>::append(next, Some(s));
 						} else {
@@ -395,6 +518,9 @@ decl_module! {
 								Lookup::<T>::remove(id);
 							}
 						}
+						if let Some((task, retry)) = maybe_retry_task {
+							Self::schedule_retry(now, task, retry);
+						}
 						Self::deposit_event(RawEvent::Dispatched(
 							(now, index),
 							maybe_id,
@@ -403,12 +529,17 @@ decl_module! {
 						total_weight = cumulative_weight;
 						None
 					} else {
-						Some(Some(s))
+						Some((index, s))
 					}
 				})
-				.for_each(|unused| {
+				.for_each(|(index, unused)| {
 					let next = now + One::one();
-					Agenda::<T>::append(next, unused);
+					let next_index = Agenda::<T>::decode_len(next).unwrap_or(0) as u32;
+					// The retry configuration moves along with the postponed task.
+					if let Some(retry) = Retries::<T>::take((now, index)) {
+						Retries::<T>::insert((next, next_index), retry);
+					}
+					Agenda::<T>::append(next, Some(unused));
 				});
 
 			total_weight
@@ -522,9 +653,7 @@ impl<T: Config> Module<T> {
 				agenda.get_mut(index as usize)
 					.map_or(Ok(None), |s| -> Result<Option<Scheduled<_, _, _, _>>, DispatchError> {
 						if let (Some(ref o), Some(ref s)) = (origin, s.borrow()) {
-							if *o != s.origin {
-								return Err(BadOrigin.into());
-							}
+							Self::ensure_privilege(o, &s.origin)?;
 						};
 						Ok(s.take())
 					})
@@ -534,6 +663,7 @@ impl<T: Config> Module<T> {
 			if let Some(id) = s.maybe_id {
 				Lookup::<T>::remove(id);
 			}
+			Retries::<T>::remove((when, index));
 			Self::deposit_event(RawEvent::Canceled(when, index));
 			Ok(())
 		} else {
@@ -559,6 +689,9 @@ impl<T: Config> Module<T> {
 		})?;
 
 		let new_index = Agenda::<T>::decode_len(new_time).unwrap_or(1) as u32 - 1;
+		if let Some(retry) = Retries::<T>::take((when, index)) {
+			Retries::<T>::insert((new_time, new_index), retry);
+		}
 		Self::deposit_event(RawEvent::Canceled(when, index));
 		Self::deposit_event(RawEvent::Scheduled(new_time, new_index));
 
@@ -612,14 +745,13 @@ impl<T: Config> Module<T> {
 				Agenda::<T>::try_mutate(when, |agenda| -> DispatchResult {
 					if let Some(s) = agenda.get_mut(i) {
 						if let (Some(ref o), Some(ref s)) = (origin, s.borrow()) {
-							if *o != s.origin {
-								return Err(BadOrigin.into());
-							}
+							Self::ensure_privilege(o, &s.origin)?;
 						}
 						*s = None;
 					}
 					Ok(())
 				})?;
+				Retries::<T>::remove((when, index));
 				Self::deposit_event(RawEvent::Canceled(when, index));
 				Ok(())
 			} else {
@@ -650,6 +782,9 @@ impl<T: Config> Module<T> {
 			})?;
 
 			let new_index = Agenda::<T>::decode_len(new_time).unwrap_or(1) as u32 - 1;
+			if let Some(retry) = Retries::<T>::take((when, index)) {
+				Retries::<T>::insert((new_time, new_index), retry);
+			}
 			Self::deposit_event(RawEvent::Canceled(when, index));
 			Self::deposit_event(RawEvent::Scheduled(new_time, new_index));
 
@@ -660,6 +795,75 @@ impl<T: Config> Module<T> {
 	}
 }
 
+impl<T: Config> Module<T> {
+	/// Ensure that `origin` has at least the privilege of `scheduled_origin`.
+	fn ensure_privilege(
+		origin: &T::PalletsOrigin,
+		scheduled_origin: &T::PalletsOrigin,
+	) -> Result<(), DispatchError> {
+		match T::OriginPrivilegeCmp::cmp_privilege(origin, scheduled_origin) {
+			Some(Ordering::Greater) | Some(Ordering::Equal) => Ok(()),
+			Some(Ordering::Less) | None => Err(BadOrigin.into()),
+		}
+	}
+
+	fn do_set_retry(
+		origin: Option<T::PalletsOrigin>,
+		(when, index): TaskAddress<T::BlockNumber>,
+		retries: u8,
+		period: T::BlockNumber,
+	) -> DispatchResult {
+		if period.is_zero() {
+			return Err(Error::<T>::InvalidRetryPeriod.into())
+		}
+		let agenda = Agenda::<T>::get(when);
+		let task = agenda.get(index as usize)
+			.and_then(Option::as_ref)
+			.ok_or(Error::<T>::NotFound)?;
+		if let Some(ref o) = origin {
+			Self::ensure_privilege(o, &task.origin)?;
+		}
+		Retries::<T>::insert(
+			(when, index),
+			RetryConfig { total_retries: retries, remaining: retries, period },
+		);
+		Self::deposit_event(RawEvent::RetrySet((when, index), task.maybe_id.clone(), period, retries));
+		Ok(())
+	}
+
+	fn do_cancel_retry(
+		origin: Option<T::PalletsOrigin>,
+		(when, index): TaskAddress<T::BlockNumber>,
+	) -> DispatchResult {
+		let agenda = Agenda::<T>::get(when);
+		let task = agenda.get(index as usize)
+			.and_then(Option::as_ref)
+			.ok_or(Error::<T>::NotFound)?;
+		if let Some(ref o) = origin {
+			Self::ensure_privilege(o, &task.origin)?;
+		}
+		Retries::<T>::take((when, index)).ok_or(Error::<T>::NotFound)?;
+		Self::deposit_event(RawEvent::RetryCancelled((when, index), task.maybe_id.clone()));
+		Ok(())
+	}
+
+	/// Schedule `task`, which failed at block `now`, to be retried according to `retry`.
+	fn schedule_retry(
+		now: T::BlockNumber,
+		task: Scheduled<<T as Config>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>,
+		retry: RetryConfig<T::BlockNumber>,
+	) {
+		let when = now.saturating_add(retry.period);
+		let index = Agenda::<T>::decode_len(when).unwrap_or(0) as u32;
+		if let Some(ref id) = task.maybe_id {
+			Lookup::<T>::insert(id, (when, index));
+		}
+		Agenda::<T>::append(when, Some(task));
+		Retries::<T>::insert((when, index), RetryConfig { remaining: retry.remaining - 1, ..retry });
+		Self::deposit_event(RawEvent::Scheduled(when, index));
+	}
+}
+
 impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin> for Module<T> {
 	type Address = TaskAddress<T::BlockNumber>;
 
@@ -737,6 +941,7 @@ mod tests {
 	};
 	use frame_system::{EnsureOneOf, EnsureRoot, EnsureSignedBy};
 	use substrate_test_utils::assert_eq_uvec;
+	use std::cell::RefCell;
 	use crate as scheduler;
 
 	mod logger {
@@ -745,10 +950,15 @@ mod tests {
 
 		thread_local! {
 			static LOG: RefCell<Vec<(OriginCaller, u32)>> = RefCell::new(Vec::new());
+			static FAILURES_LEFT: RefCell<u32> = RefCell::new(0);
 		}
 		pub fn log() -> Vec<(OriginCaller, u32)> {
 			LOG.with(|log| log.borrow().clone())
 		}
+		/// Make the next `n` calls to `log_fallible` fail.
+		pub fn set_failures(n: u32) {
+			FAILURES_LEFT.with(|f| *f.borrow_mut() = n);
+		}
 		pub trait Config: system::Config {
 			type Event: From<Event> + Into<<Self as system::Config>::Event>;
 		}
@@ -780,6 +990,24 @@ mod tests {
 						log.borrow_mut().push((origin.caller().clone(), i));
 					})
 				}
+
+				#[weight = *weight]
+				fn log_fallible(origin, i: u32, weight: Weight) -> DispatchResult {
+					let fail = FAILURES_LEFT.with(|f| {
+						let mut f = f.borrow_mut();
+						let fail = *f > 0;
+						*f = f.saturating_sub(1);
+						fail
+					});
+					if fail {
+						return Err(DispatchError::Other("failure requested"))
+					}
+					Self::deposit_event(Event::Logged(i, weight));
+					LOG.with(|log| {
+						log.borrow_mut().push((origin.caller().clone(), i));
+					});
+					Ok(())
+				}
 			}
 		}
 	}
@@ -848,6 +1076,29 @@ mod tests {
 		pub const One: u64 = 1;
 	}
 
+	thread_local! {
+		static ROOT_IS_PRIVILEGED: RefCell<bool> = RefCell::new(false);
+	}
+
+	/// Behaves as `EqualPrivilegeOnly`, unless `ROOT_IS_PRIVILEGED` is set, in which case root
+	/// has a higher privilege than all other origins.
+	pub struct TestPrivilegeCmp;
+	impl PrivilegeCmp<OriginCaller> for TestPrivilegeCmp {
+		fn cmp_privilege(left: &OriginCaller, right: &OriginCaller) -> Option<Ordering> {
+			if left == right {
+				return Some(Ordering::Equal)
+			}
+			let root_is_privileged = ROOT_IS_PRIVILEGED.with(|r| *r.borrow());
+			if root_is_privileged && *left == root() {
+				return Some(Ordering::Greater)
+			}
+			if root_is_privileged && *right == root() {
+				return Some(Ordering::Less)
+			}
+			None
+		}
+	}
+
 	impl Config for Test {
 		type Event = Event;
 		type Origin = Origin;
@@ -856,6 +1107,7 @@ mod tests {
 		type MaximumWeight = MaximumSchedulerWeight;
 		type ScheduleOrigin = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
 		type MaxScheduledPerBlock = MaxScheduledPerBlock;
+		type OriginPrivilegeCmp = TestPrivilegeCmp;
 		type WeightInfo = ();
 	}
 
@@ -1198,6 +1450,7 @@ mod tests {
 			let base_multiplier = 0;
 			let named_multiplier = <Test as frame_system::Config>::DbWeight::get().writes(1);
 			let periodic_multiplier = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1);
+			let retry_multiplier = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 2);
 
 			// Named
 			assert_ok!(
@@ -1232,20 +1485,28 @@ mod tests {
 			let actual_weight = Scheduler::on_initialize(1);
 			let call_weight = MaximumSchedulerWeight::get() / 2;
 			assert_eq!(
-				actual_weight, call_weight + base_weight + base_multiplier + named_multiplier + periodic_multiplier
+				actual_weight,
+				call_weight + base_weight + base_multiplier + named_multiplier + periodic_multiplier +
+					retry_multiplier
 			);
 			assert_eq!(logger::log(), vec![(root(), 2600u32)]);
 
 			// Will include anon and anon periodic
 			let actual_weight = Scheduler::on_initialize(2);
 			let call_weight = MaximumSchedulerWeight::get() / 2 + MaximumSchedulerWeight::get() / 3;
-			assert_eq!(actual_weight, call_weight + base_weight + base_multiplier * 2 + periodic_multiplier);
+			assert_eq!(
+				actual_weight,
+				call_weight + base_weight + base_multiplier * 2 + periodic_multiplier + retry_multiplier * 2
+			);
 			assert_eq!(logger::log(), vec![(root(), 2600u32), (root(), 69u32), (root(), 42u32)]);
 
 			// Will include named only
 			let actual_weight = Scheduler::on_initialize(3);
 			let call_weight = MaximumSchedulerWeight::get() / 3;
-			assert_eq!(actual_weight, call_weight + base_weight + base_multiplier + named_multiplier);
+			assert_eq!(
+				actual_weight,
+				call_weight + base_weight + base_multiplier + named_multiplier + retry_multiplier
+			);
 			assert_eq!(logger::log(), vec![(root(), 2600u32), (root(), 69u32), (root(), 42u32), (root(), 3u32)]);
 
 			// Will contain none
@@ -1357,6 +1618,194 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn privileged_origin_can_cancel() {
+		new_test_ext().execute_with(|| {
+			ROOT_IS_PRIVILEGED.with(|r| *r.borrow_mut() = true);
+			let call = Box::new(Call::Logger(logger::Call::log_without_filter(69, 1000)));
+			let call2 = Box::new(Call::Logger(logger::Call::log_without_filter(42, 1000)));
+			assert_ok!(
+				Scheduler::schedule_named(system::RawOrigin::Signed(1).into(), 1u32.encode(), 4, None, 127, call)
+			);
+			assert_ok!(Scheduler::schedule(Origin::root(), 4, None, 127, call2));
+
+			// a less privileged origin still cannot cancel a root task.
+			assert_noop!(Scheduler::cancel(system::RawOrigin::Signed(1).into(), 4, 1), BadOrigin);
+			// but root can cancel the task scheduled by a signed origin.
+			assert_ok!(Scheduler::cancel_named(Origin::root(), 1u32.encode()));
+			assert_ok!(Scheduler::cancel(Origin::root(), 4, 1));
+
+			run_to_block(5);
+			assert!(logger::log().is_empty());
+		});
+	}
+
+	#[test]
+	fn retry_works() {
+		new_test_ext().execute_with(|| {
+			let call = Box::new(Call::Logger(logger::Call::log_fallible(42, 1000)));
+			assert_ok!(Scheduler::schedule(Origin::root(), 4, None, 127, call));
+			assert_ok!(Scheduler::set_retry(Origin::root(), (4, 0), 3, 2));
+			assert_eq!(
+				Scheduler::retries((4, 0)),
+				Some(RetryConfig { total_retries: 3, remaining: 3, period: 2 }),
+			);
+
+			logger::set_failures(2);
+			run_to_block(4);
+			assert!(logger::log().is_empty());
+			assert!(Retries::<Test>::get((4, 0)).is_none());
+			assert_eq!(
+				Scheduler::retries((6, 0)),
+				Some(RetryConfig { total_retries: 3, remaining: 2, period: 2 }),
+			);
+
+			run_to_block(6);
+			assert!(logger::log().is_empty());
+			assert_eq!(
+				Scheduler::retries((8, 0)),
+				Some(RetryConfig { total_retries: 3, remaining: 1, period: 2 }),
+			);
+
+			// the third attempt succeeds, and no further retry is scheduled.
+			run_to_block(8);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert_eq!(Retries::<Test>::iter().count(), 0);
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn retries_run_out() {
+		new_test_ext().execute_with(|| {
+			let call = Box::new(Call::Logger(logger::Call::log_fallible(42, 1000)));
+			assert_ok!(Scheduler::schedule_named(Origin::root(), 1u32.encode(), 4, None, 127, call));
+			assert_ok!(Scheduler::set_retry_named(Origin::root(), 1u32.encode(), 1, 3));
+
+			logger::set_failures(10);
+			run_to_block(4);
+			// the named task keeps its name while being retried.
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), Some((7, 0)));
+
+			run_to_block(7);
+			assert!(Lookup::<Test>::get(1u32.encode()).is_none());
+			assert_eq!(Retries::<Test>::iter().count(), 0);
+			assert_eq!(Agenda::<Test>::iter().flat_map(|(_, a)| a).flatten().count(), 0);
+			assert!(logger::log().is_empty());
+		});
+	}
+
+	#[test]
+	fn periodic_task_retries_are_anonymous_clones() {
+		new_test_ext().execute_with(|| {
+			let call = Box::new(Call::Logger(logger::Call::log_fallible(42, 1000)));
+			assert_ok!(Scheduler::schedule_named(
+				Origin::root(), 1u32.encode(), 4, Some((10, 2)), 127, call,
+			));
+			assert_ok!(Scheduler::set_retry_named(Origin::root(), 1u32.encode(), 2, 1));
+
+			logger::set_failures(1);
+			run_to_block(4);
+			// the periodic task is scheduled as usual, with a fresh retry configuration.
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), Some((14, 0)));
+			assert_eq!(
+				Scheduler::retries((14, 0)),
+				Some(RetryConfig { total_retries: 2, remaining: 2, period: 1 }),
+			);
+			// and an anonymous, non-periodic retry is scheduled for the failed run.
+			assert_eq!(
+				Scheduler::retries((5, 0)),
+				Some(RetryConfig { total_retries: 2, remaining: 1, period: 1 }),
+			);
+
+			run_to_block(5);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			run_to_block(14);
+			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32)]);
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn retry_configuration_checks_origin_and_task() {
+		new_test_ext().execute_with(|| {
+			let call = Box::new(Call::Logger(logger::Call::log_fallible(42, 1000)));
+			assert_ok!(Scheduler::schedule_named(
+				system::RawOrigin::Signed(1).into(), 1u32.encode(), 4, None, 127, call,
+			));
+
+			assert_noop!(Scheduler::set_retry(Origin::root(), (4, 1), 1, 1), Error::<Test>::NotFound);
+			assert_noop!(
+				Scheduler::set_retry_named(Origin::root(), 2u32.encode(), 1, 1),
+				Error::<Test>::NotFound,
+			);
+			assert_noop!(
+				Scheduler::set_retry(system::RawOrigin::Signed(1).into(), (4, 0), 1, 0),
+				Error::<Test>::InvalidRetryPeriod,
+			);
+			assert_noop!(Scheduler::set_retry(Origin::root(), (4, 0), 1, 1), BadOrigin);
+			assert_noop!(Scheduler::cancel_retry(system::RawOrigin::Signed(1).into(), (4, 0)), Error::<Test>::NotFound);
+
+			assert_ok!(Scheduler::set_retry(system::RawOrigin::Signed(1).into(), (4, 0), 1, 1));
+			assert_noop!(Scheduler::cancel_retry_named(Origin::root(), 1u32.encode()), BadOrigin);
+			assert_ok!(Scheduler::cancel_retry_named(system::RawOrigin::Signed(1).into(), 1u32.encode()));
+			assert!(Scheduler::retries((4, 0)).is_none());
+		});
+	}
+
+	#[test]
+	fn cancel_and_reschedule_handle_retries() {
+		new_test_ext().execute_with(|| {
+			let call = Box::new(Call::Logger(logger::Call::log_fallible(42, 1000)));
+			assert_ok!(Scheduler::schedule_named(Origin::root(), 1u32.encode(), 4, None, 127, call.clone()));
+			assert_ok!(Scheduler::schedule(Origin::root(), 4, None, 127, call));
+			assert_ok!(Scheduler::set_retry(Origin::root(), (4, 0), 1, 1));
+			assert_ok!(Scheduler::set_retry(Origin::root(), (4, 1), 1, 1));
+
+			assert_ok!(Scheduler::do_reschedule_named(1u32.encode(), DispatchTime::At(6)));
+			assert!(Scheduler::retries((4, 0)).is_none());
+			assert!(Scheduler::retries((6, 0)).is_some());
+
+			assert_ok!(Scheduler::cancel(Origin::root(), 4, 1));
+			assert_eq!(Retries::<Test>::iter().count(), 1);
+			assert_ok!(Scheduler::cancel_named(Origin::root(), 1u32.encode()));
+			assert_eq!(Retries::<Test>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn postponed_task_keeps_its_retries() {
+		new_test_ext().execute_with(|| {
+			let half = MaximumSchedulerWeight::get() / 2;
+			let call = Box::new(Call::Logger(logger::Call::log(7, 10)));
+			assert_ok!(Scheduler::schedule(Origin::root(), 5, None, 127, call));
+			let call = Box::new(Call::Logger(logger::Call::log(42, half)));
+			assert_ok!(Scheduler::schedule(Origin::root(), 4, None, 127, call));
+			let call = Box::new(Call::Logger(logger::Call::log_fallible(69, half)));
+			assert_ok!(Scheduler::schedule(Origin::root(), 4, None, 127, call));
+			assert_ok!(Scheduler::set_retry(Origin::root(), (4, 1), 1, 1));
+
+			logger::set_failures(1);
+			// 69 does not fit and is postponed together with its retry configuration.
+			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert!(Scheduler::retries((4, 1)).is_none());
+			assert_eq!(
+				Scheduler::retries((5, 1)),
+				Some(RetryConfig { total_retries: 1, remaining: 1, period: 1 }),
+			);
+
+			// so its failure is still retried.
+			run_to_block(5);
+			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 7u32)]);
+			run_to_block(6);
+			assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 7u32), (root(), 69u32)]);
+			assert_eq!(Retries::<Test>::iter().count(), 0);
+		});
+	}
+
 	#[test]
 	fn migration_to_v2_works() {
 		new_test_ext().execute_with(|| {
@@ -23,11 +23,9 @@
 mod mock;
 
 use sp_std::prelude::*;
-use sp_std::vec;
 
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
 use frame_support::{
-	codec::Decode,
 	storage::StorageValue,
 	traits::{KeyOwnerProofSystem, OnInitialize},
 };
@@ -37,12 +35,15 @@ use pallet_staking::{
 	benchmarking::create_validator_with_nominators, testing_utils::create_validators,
 	RewardDestination,
 };
-use sp_runtime::traits::{One, StaticLookup};
+use sp_runtime::traits::{One, OpaqueKeys, StaticLookup};
 
 const MAX_VALIDATORS: u32 = 1000;
 
 pub struct Pallet<T: Config>(pallet_session::Module<T>);
-pub trait Config: pallet_session::Config + pallet_session::historical::Config + pallet_staking::Config {}
+pub trait Config: pallet_session::Config + pallet_session::historical::Config + pallet_staking::Config {
+	/// Generate a set of session keys, stored in the keystore, and a proof that `owner` holds them.
+	fn generate_session_keys_and_proof(owner: Self::AccountId) -> (Self::Keys, Vec<u8>);
+}
 
 impl<T: Config> OnInitialize<T::BlockNumber> for Pallet<T> {
 	fn on_initialize(n: T::BlockNumber) -> frame_support::weights::Weight {
@@ -60,8 +61,7 @@ benchmarks! {
 			RewardDestination::Staked,
 		)?;
 		let v_controller = pallet_staking::Module::<T>::bonded(&v_stash).ok_or("not stash")?;
-		let keys = T::Keys::default();
-		let proof: Vec<u8> = vec![0,1,2,3];
+		let (keys, proof) = T::generate_session_keys_and_proof(v_controller.clone());
 		// Whitelist controller account from further DB operations.
 		let v_controller_key = frame_system::Account::<T>::hashed_key_for(&v_controller);
 		frame_benchmarking::benchmarking::add_to_whitelist(v_controller_key.into());
@@ -76,8 +76,7 @@ benchmarks! {
 			RewardDestination::Staked
 		)?;
 		let v_controller = pallet_staking::Module::<T>::bonded(&v_stash).ok_or("not stash")?;
-		let keys = T::Keys::default();
-		let proof: Vec<u8> = vec![0,1,2,3];
+		let (keys, proof) = T::generate_session_keys_and_proof(v_controller.clone());
 		Session::<T>::set_keys(RawOrigin::Signed(v_controller.clone()).into(), keys, proof)?;
 		// Whitelist controller account from further DB operations.
 		let v_controller_key = frame_system::Account::<T>::hashed_key_for(&v_controller);
@@ -91,7 +90,7 @@ benchmarks! {
 		let (key, key_owner_proof1) = check_membership_proof_setup::<T>(n);
 		let key_owner_proof2 = key_owner_proof1.clone();
 	}: {
-		Historical::<T>::check_proof(key, key_owner_proof1);
+		Historical::<T>::check_proof(key.clone(), key_owner_proof1);
 	}
 	verify {
 		assert!(Historical::<T>::check_proof(key, key_owner_proof2).is_some());
@@ -109,7 +108,7 @@ benchmarks! {
 
 		let key_owner_proof2 = key_owner_proof1.clone();
 	}: {
-		Historical::<T>::check_proof(key, key_owner_proof1);
+		Historical::<T>::check_proof(key.clone(), key_owner_proof1);
 	}
 	verify {
 		assert!(Historical::<T>::check_proof(key, key_owner_proof2).is_some());
@@ -117,42 +116,26 @@ benchmarks! {
 }
 
 /// Sets up the benchmark for checking a membership proof. It creates the given
-/// number of validators, sets random session keys and then creates a membership
-/// proof for the first authority and returns its key and the proof.
+/// number of validators, sets new session keys and then creates a membership
+/// proof for the first key of the first authority and returns its key and the proof.
 fn check_membership_proof_setup<T: Config>(
 	n: u32,
 ) -> (
-	(sp_runtime::KeyTypeId, &'static [u8; 32]),
+	(sp_runtime::KeyTypeId, Vec<u8>),
 	sp_session::MembershipProof,
 ) {
 	pallet_staking::ValidatorCount::put(n);
 
-	// create validators and set random session keys
-	for (n, who) in create_validators::<T>(n, 1000)
-		.unwrap()
-		.into_iter()
-		.enumerate()
-	{
-		use rand::RngCore;
-		use rand::SeedableRng;
+	let key_type = T::Keys::key_ids()[0];
+	let mut key = None;
 
+	// create validators and set new session keys
+	for who in create_validators::<T>(n, 1000).unwrap() {
 		let validator = T::Lookup::lookup(who).unwrap();
 		let controller = pallet_staking::Module::<T>::bonded(validator).unwrap();
 
-		let keys = {
-			let mut keys = [0u8; 128];
-
-			// we keep the keys for the first validator as 0x00000...
-			if n > 0 {
-				let mut rng = rand::rngs::StdRng::seed_from_u64(n as u64);
-				rng.fill_bytes(&mut keys);
-			}
-
-			keys
-		};
-
-		let keys: T::Keys = Decode::decode(&mut &keys[..]).unwrap();
-		let proof: Vec<u8> = vec![];
+		let (keys, proof) = T::generate_session_keys_and_proof(controller.clone());
+		key.get_or_insert_with(|| keys.get_raw(key_type).to_vec());
 
 		Session::<T>::set_keys(RawOrigin::Signed(controller).into(), keys, proof).unwrap();
 	}
@@ -164,14 +147,7 @@ fn check_membership_proof_setup<T: Config>(
 		Session::<T>::rotate_session();
 	}
 
-	let key = (sp_runtime::KeyTypeId(*b"babe"), &[0u8; 32]);
+	let key = (key_type, key.expect("at least one validator is created; qed"));
 
-	(key, Historical::<T>::prove(key).unwrap())
+	(key.clone(), Historical::<T>::prove(key).unwrap())
 }
-
-impl_benchmark_test_suite!(
-	Pallet,
-	crate::mock::new_test_ext(),
-	crate::mock::Test,
-	extra = false,
-);
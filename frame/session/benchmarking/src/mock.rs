@@ -84,11 +84,13 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
 	pub const MinimumPeriod: u64 = 5;
+	pub const MaxDrift: u64 = 30_000;
 }
 impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 impl pallet_session::historical::Config for Test {
@@ -181,10 +183,17 @@ impl pallet_staking::Config for Test {
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
 	type WeightInfo = ();
 }
 
-impl crate::Config for Test {}
+impl crate::Config for Test {
+	fn generate_session_keys_and_proof(owner: AccountId) -> (SessionKeys, Vec<u8>) {
+		use codec::{Decode, Encode};
+		let (keys, proof) = SessionKeys::generate_with_ownership_proof(&owner.encode(), None);
+		(SessionKeys::decode(&mut &keys[..]).unwrap(), proof)
+	}
+}
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
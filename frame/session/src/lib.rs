@@ -43,10 +43,12 @@
 //! - **Session key configuration process:** Session keys are set using `set_keys` for use not in
 //!   the next session, but the session after next. They are stored in `NextKeys`, a mapping between
 //!   the caller's `ValidatorId` and the session keys provided. `set_keys` allows users to set their
-//!   session key prior to being selected as validator. It is a public call since it uses
-//!   `ensure_signed`, which checks that the origin is a signed account. As such, the account ID of
-//!   the origin stored in `NextKeys` may not necessarily be associated with a block author or a
-//!   validator. The session keys of accounts are removed once their account balance is zero.
+//!   session key prior to being selected as validator, along with a proof that it holds the
+//!   private keys, so nobody can register the public keys of someone else. It is a public call
+//!   since it uses `ensure_signed`, which checks that the origin is a signed account. As such, the
+//!   account ID of the origin stored in `NextKeys` may not necessarily be associated with a block
+//!   author or a validator. The session keys of accounts are removed once their account balance
+//!   is zero.
 //!
 //! - **Session length:** This pallet does not assume anything about the length of each session.
 //!   Rather, it relies on an implementation of `ShouldEndSession` to dictate a new session's start.
@@ -115,7 +117,7 @@ pub mod historical;
 pub mod weights;
 
 use sp_std::{prelude::*, marker::PhantomData, ops::{Sub, Rem}};
-use codec::Decode;
+use codec::{Decode, Encode};
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Convert, Member, One, OpaqueKeys, Zero},
 	KeyTypeId, Perbill, Percent, RuntimeAppPublic,
@@ -350,6 +352,12 @@ impl<AId> SessionHandler<AId> for TestSessionHandler {
 	fn on_disabled(_: usize) {}
 }
 
+impl<T: Config> frame_support::traits::DisabledValidators for Module<T> {
+	fn is_disabled(index: u32) -> bool {
+		DisabledValidators::get().binary_search(&index).is_ok()
+	}
+}
+
 impl<T: Config> ValidatorRegistration<T::ValidatorId> for Module<T> {
 	fn is_registered(id: &T::ValidatorId) -> bool {
 		Self::load_keys(id).is_some()
@@ -486,6 +494,8 @@ decl_event!(
 		/// New session has happened. Note that the argument is the \[session_index\], not the block
 		/// number as the type might suggest.
 		NewSession(SessionIndex),
+		/// A validator was disabled until the end of the current session. \[validator_index\]
+		ValidatorDisabled(u32),
 	}
 );
 
@@ -517,6 +527,10 @@ decl_module! {
 		///
 		/// The dispatch origin of this function must be signed.
 		///
+		/// `proof` must prove that the caller holds the private keys of `keys`, e.g. as returned
+		/// together with the keys by the `SessionKeys::generate_session_keys` runtime API for the
+		/// SCALE encoded caller account.
+		///
 		/// # <weight>
 		/// - Complexity: `O(1)`
 		///   Actual cost depends on the number of length of `T::Keys::key_ids()` which is fixed.
@@ -529,7 +543,7 @@ decl_module! {
 		pub fn set_keys(origin, keys: T::Keys, proof: Vec<u8>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			ensure!(keys.ownership_proof_is_valid(&proof), Error::<T>::InvalidProof);
+			ensure!(keys.ownership_proof_is_valid(&who.encode(), &proof), Error::<T>::InvalidProof);
 
 			Self::do_set_keys(&who, keys)?;
 
@@ -678,6 +692,7 @@ impl<T: Config> Module<T> {
 
 		if fire_event {
 			T::SessionHandler::on_disabled(i);
+			Self::deposit_event(Event::ValidatorDisabled(i as u32));
 		}
 
 		threshold_reached
@@ -222,6 +222,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 parameter_types! {
 	pub const MinimumPeriod: u64 = 5;
+	pub const MaxDrift: u64 = 30_000;
 	pub const BlockHashCount: u64 = 250;
 	pub BlockWeights: frame_system::limits::BlockWeights =
 		frame_system::limits::BlockWeights::simple_max(1024);
@@ -257,6 +258,7 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 
@@ -19,7 +19,7 @@
 
 use super::*;
 use mock::Test;
-use codec::Decode;
+use codec::{Decode, Encode};
 use frame_support::{traits::OnInitialize, assert_ok, assert_noop};
 use sp_core::crypto::key_types::DUMMY;
 use sp_runtime::testing::UintAuthorityId;
@@ -30,6 +30,12 @@ use mock::{
 	PreUpgradeMockSessionKeys,
 };
 
+fn set_keys(who: u64, key: u64) -> DispatchResult {
+	let keys: mock::MockSessionKeys = UintAuthorityId(key).into();
+	let proof = keys.create_ownership_proof(&who.encode()).unwrap();
+	Session::set_keys(Origin::signed(who), keys, proof)
+}
+
 fn initialize_block(block: u64) {
 	SESSION_CHANGED.with(|l| *l.borrow_mut() = false);
 	System::set_block_number(block);
@@ -100,7 +106,7 @@ fn authorities_should_track_validators() {
 		reset_before_session_end_called();
 
 		set_next_validators(vec![1, 2, 4]);
-		assert_ok!(Session::set_keys(Origin::signed(4), UintAuthorityId(4).into(), vec![]));
+		assert_ok!(set_keys(4, 4));
 		force_new_session();
 		initialize_block(3);
 		assert_eq!(Session::queued_keys(), vec![
@@ -160,7 +166,7 @@ fn session_change_should_work() {
 
 		// Block 3: Set new key for validator 2; no visible change.
 		initialize_block(3);
-		assert_ok!(Session::set_keys(Origin::signed(2), UintAuthorityId(5).into(), vec![]));
+		assert_ok!(set_keys(2, 5));
 		assert_eq!(authorities(), vec![UintAuthorityId(1), UintAuthorityId(2), UintAuthorityId(3)]);
 
 		// Block 4: Session rollover; no visible change.
@@ -183,13 +189,13 @@ fn duplicates_are_not_allowed() {
 		System::set_block_number(1);
 		Session::on_initialize(1);
 		assert_noop!(
-			Session::set_keys(Origin::signed(4), UintAuthorityId(1).into(), vec![]),
+			set_keys(4, 1),
 			Error::<Test>::DuplicatedKey,
 		);
-		assert_ok!(Session::set_keys(Origin::signed(1), UintAuthorityId(10).into(), vec![]));
+		assert_ok!(set_keys(1, 10));
 
 		// is fine now that 1 has migrated off.
-		assert_ok!(Session::set_keys(Origin::signed(4), UintAuthorityId(1).into(), vec![]));
+		assert_ok!(set_keys(4, 1));
 	});
 }
 
@@ -231,7 +237,7 @@ fn session_changed_flag_works() {
 		assert!(before_session_end_called());
 		reset_before_session_end_called();
 
-		assert_ok!(Session::set_keys(Origin::signed(2), UintAuthorityId(5).into(), vec![]));
+		assert_ok!(set_keys(2, 5));
 		force_new_session();
 		initialize_block(6);
 		assert!(!session_changed());
@@ -239,7 +245,7 @@ fn session_changed_flag_works() {
 		reset_before_session_end_called();
 
 		// changing the keys of a validator leads to change.
-		assert_ok!(Session::set_keys(Origin::signed(69), UintAuthorityId(69).into(), vec![]));
+		assert_ok!(set_keys(69, 69));
 		force_new_session();
 		initialize_block(7);
 		assert!(session_changed());
@@ -330,17 +336,37 @@ fn periodic_session_works() {
 #[test]
 fn session_keys_generate_output_works_as_set_keys_input() {
 	new_test_ext().execute_with(|| {
-		let new_keys = mock::MockSessionKeys::generate(None);
+		let (new_keys, proof) =
+			mock::MockSessionKeys::generate_with_ownership_proof(&2u64.encode(), None);
 		assert_ok!(
 			Session::set_keys(
 				Origin::signed(2),
 				<mock::Test as Config>::Keys::decode(&mut &new_keys[..]).expect("Decode keys"),
-				vec![],
+				proof,
 			)
 		);
 	});
 }
 
+#[test]
+fn set_keys_requires_ownership_proof() {
+	new_test_ext().execute_with(|| {
+		let keys: mock::MockSessionKeys = UintAuthorityId(10).into();
+		assert_noop!(
+			Session::set_keys(Origin::signed(1), keys.clone(), vec![]),
+			Error::<Test>::InvalidProof,
+		);
+
+		// The proof is only valid for the account it was created for.
+		let proof = keys.create_ownership_proof(&2u64.encode()).unwrap();
+		assert_noop!(
+			Session::set_keys(Origin::signed(1), keys.clone(), proof.clone()),
+			Error::<Test>::InvalidProof,
+		);
+		assert_ok!(Session::set_keys(Origin::signed(2), keys, proof));
+	});
+}
+
 #[test]
 fn return_true_if_more_than_third_is_disabled() {
 	new_test_ext().execute_with(|| {
@@ -358,6 +384,44 @@ fn return_true_if_more_than_third_is_disabled() {
 	});
 }
 
+#[test]
+fn disabled_validators_are_tracked_until_the_validator_set_changes() {
+	use frame_support::traits::DisabledValidators;
+
+	new_test_ext().execute_with(|| {
+		set_next_validators(vec![1, 2, 3, 4, 5, 6, 7]);
+		force_new_session();
+		initialize_block(1);
+		// apply the new validator set
+		force_new_session();
+		initialize_block(2);
+
+		assert!(!Session::is_disabled(2));
+
+		Session::disable_index(2);
+		assert!(Session::is_disabled(2));
+		assert!(!Session::is_disabled(1));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			mock::Event::pallet_session(crate::Event::ValidatorDisabled(2)),
+		);
+
+		// disabling again doesn't emit another event
+		let events = System::events().len();
+		Session::disable_index(2);
+		assert_eq!(System::events().len(), events);
+
+		// the disabled validators are reset once the validator set changes
+		set_next_validators(vec![1, 2, 3]);
+		force_new_session();
+		initialize_block(3);
+		force_new_session();
+		initialize_block(4);
+
+		assert!(!Session::is_disabled(2));
+	});
+}
+
 #[test]
 fn upgrade_keys() {
 	use frame_support::storage;
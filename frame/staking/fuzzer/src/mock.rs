@@ -87,11 +87,13 @@ impl pallet_indices::Config for Test {
 }
 parameter_types! {
 	pub const MinimumPeriod: u64 = 5;
+	pub const MaxDrift: u64 = 30_000;
 }
 impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 impl pallet_session::historical::Config for Test {
@@ -197,4 +199,5 @@ impl pallet_staking::Config for Test {
 	type OffchainSolutionWeightLimit = ();
 	type WeightInfo = ();
 	type ElectionProvider = MockElectionProvider;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
 }
@@ -291,12 +291,48 @@ benchmarks! {
 
 	set_controller {
 		let (stash, _) = create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
-		let new_controller = create_funded_user::<T>("new_controller", USER_SEED, 100);
-		let new_controller_lookup = T::Lookup::unlookup(new_controller.clone());
 		whitelist_account!(stash);
-	}: _(RawOrigin::Signed(stash), new_controller_lookup)
+	}: _(RawOrigin::Signed(stash.clone()))
 	verify {
-		assert!(Ledger::<T>::contains_key(&new_controller));
+		assert!(Ledger::<T>::contains_key(&stash));
+	}
+
+	deprecate_controller_batch {
+		let c in 1 .. MAX_VALIDATORS;
+		let mut pairs = Vec::new();
+		for i in 0 .. c {
+			pairs.push(create_stash_controller::<T>(i, 100, Default::default())?);
+		}
+		let controllers = pairs.iter().map(|(_, controller)| controller.clone()).collect();
+	}: _(RawOrigin::Root, controllers)
+	verify {
+		for (stash, _) in pairs {
+			assert_eq!(Bonded::<T>::get(&stash), Some(stash.clone()));
+		}
+	}
+
+	set_staking_limits {
+		let min_bond = T::Currency::minimum_balance() * 10u32.into();
+	}: _(RawOrigin::Root, min_bond, min_bond)
+	verify {
+		assert_eq!(MinValidatorBond::<T>::get(), min_bond);
+	}
+
+	set_min_commission {
+		let min_commission = Perbill::from_percent(10);
+	}: _(RawOrigin::Root, min_commission)
+	verify {
+		assert_eq!(MinCommission::get(), min_commission);
+	}
+
+	chill_other {
+		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
+		Staking::<T>::validate(RawOrigin::Signed(controller.clone()).into(), Default::default())?;
+		MinValidatorBond::<T>::put(T::Currency::minimum_balance() * 1000u32.into());
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), controller)
+	verify {
+		assert!(!Validators::<T>::contains_key(&stash));
 	}
 
 	set_validator_count {
@@ -577,7 +613,7 @@ benchmarks! {
 			add_slashing_spans::<T>(&validators[index as usize], 10);
 		});
 	}: {
-		let voters = <Staking<T>>::get_npos_voters();
+		let voters = <Staking<T>>::get_npos_voters(None);
 		assert_eq!(voters.len() as u32, v + n);
 	}
 
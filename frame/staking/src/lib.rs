@@ -67,8 +67,9 @@
 //!
 //! An account pair can become bonded using the [`bond`](Call::bond) call.
 //!
-//! Stash accounts can change their associated controller using the
-//! [`set_controller`](Call::set_controller) call.
+//! Separate controller accounts are deprecated: stash accounts can make themselves their own
+//! controller using the [`set_controller`](Call::set_controller) call, and governance can do so
+//! for existing pairs with [`deprecate_controller_batch`](Call::deprecate_controller_batch).
 //!
 //! There are three possible roles that any staked account pair can be in: `Validator`, `Nominator`
 //! and `Idle` (defined in [`StakerStatus`]). There are three
@@ -127,7 +128,10 @@
 //! This means that if they are a nominator, they will not be considered as voters anymore and if
 //! they are validators, they will no longer be a candidate for the next election.
 //!
-//! An account can step back via the [`chill`](Call::chill) call.
+//! An account can step back via the [`chill`](Call::chill) call. Participants whose bond is below
+//! the minimum of their role, e.g. because governance raised it with
+//! [`set_staking_limits`](Call::set_staking_limits), can be chilled by anyone via the
+//! [`chill_other`](Call::chill_other) call.
 //!
 //! ### Session managing
 //!
@@ -297,7 +301,7 @@ use frame_support::{
 	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	traits::{
 		Currency, LockIdentifier, LockableCurrency, WithdrawReasons, OnUnbalanced, Imbalance, Get,
-		UnixTime, EstimateNextNewSession, EnsureOrigin, CurrencyToVote,
+		UnixTime, EstimateNextNewSession, EnsureOrigin, CurrencyToVote, Defensive,
 	},
 };
 use pallet_session::historical;
@@ -317,7 +321,9 @@ use frame_system::{
 	self as system, ensure_signed, ensure_root,
 	offchain::SendTransactionTypes,
 };
-use frame_election_provider_support::{ElectionProvider, VoteWeight, Supports, data_provider};
+use frame_election_provider_support::{
+	ElectionProvider, SortedListProvider, VoteWeight, Supports, data_provider,
+};
 pub use weights::WeightInfo;
 
 const STAKING_ID: LockIdentifier = *b"staking ";
@@ -738,6 +744,12 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 		DataProvider = Module<Self>,
 	>;
 
+	/// Something that keeps the nominators sorted by their vote weight. Used to select the
+	/// nominators that make it into a voter snapshot of bounded length.
+	///
+	/// Use [`UseNominatorsMap`] if the nominators don't need to be sorted.
+	type SortedListProvider: SortedListProvider<Self::AccountId>;
+
 	/// Maximum number of nominations per nominator.
 	const MAX_NOMINATIONS: u32;
 
@@ -951,6 +963,15 @@ decl_storage! {
 		/// canceled by extraordinary circumstances (e.g. governance).
 		pub CanceledSlashPayout get(fn canceled_payout) config(): BalanceOf<T>;
 
+		/// The minimum active bond to become and remain a nominator.
+		pub MinNominatorBond get(fn min_nominator_bond): BalanceOf<T>;
+
+		/// The minimum active bond to become and remain a validator.
+		pub MinValidatorBond get(fn min_validator_bond): BalanceOf<T>;
+
+		/// The minimum commission of validators.
+		pub MinCommission get(fn min_commission): Perbill;
+
 		/// All unapplied slashes that are queued for later.
 		pub UnappliedSlashes:
 			map hasher(twox_64_concat) EraIndex => Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>;
@@ -1083,9 +1104,18 @@ decl_event!(
 		EraPayout(EraIndex, Balance, Balance),
 		/// The staker has been rewarded by this amount. \[stash, amount\]
 		Reward(AccountId, Balance),
-		/// One validator (and its nominators) has been slashed by the given amount.
-		/// \[validator, amount\]
+		/// A validator has been slashed by the given amount. \[validator, amount\]
 		Slash(AccountId, Balance),
+		/// A nominator has been slashed by the given amount for the offence of a validator.
+		/// \[nominator, validator, amount\]
+		NominatorSlashed(AccountId, AccountId, Balance),
+		/// The slash of a validator and its nominators has been deferred. It can be cancelled
+		/// with `cancel_deferred_slash` using the given era and index until it is applied.
+		/// \[validator, era, index\]
+		SlashDeferred(AccountId, EraIndex, u32),
+		/// A deferred slash of a validator and its nominators has been cancelled.
+		/// \[validator, era\]
+		SlashCancelled(AccountId, EraIndex),
 		/// An old slashing report from a prior era was discarded because it could
 		/// not be processed. \[session_index\]
 		OldSlashingReportDiscarded(SessionIndex),
@@ -1149,6 +1179,12 @@ decl_error! {
 		TooManyTargets,
 		/// A nomination target was supplied that was blocked or otherwise not a validator.
 		BadTarget,
+		/// The active bond is below the minimum of the role.
+		InsufficientBond,
+		/// The commission is below the minimum commission.
+		CommissionTooLow,
+		/// The stash is neither under-bonded nor a validator or nominator.
+		CannotChillOther,
 	}
 }
 
@@ -1220,6 +1256,9 @@ decl_module! {
 		/// Take the origin account as a stash and lock up `value` of its balance. `controller` will
 		/// be the account that controls it.
 		///
+		/// Separate controller accounts are deprecated, new stashes should be their own
+		/// `controller`.
+		///
 		/// `value` must be more than the `minimum_balance` specified by `T::Currency`.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the stash account.
@@ -1469,15 +1508,17 @@ decl_module! {
 		/// -----------
 		/// Weight: O(1)
 		/// DB Weight:
-		/// - Read: Era Election Status, Ledger
+		/// - Read: Era Election Status, Ledger, Min Validator Bond, Min Commission
 		/// - Write: Nominators, Validators
 		/// # </weight>
 		#[weight = T::WeightInfo::validate()]
 		pub fn validate(origin, prefs: ValidatorPrefs) {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			ensure!(ledger.active >= MinValidatorBond::<T>::get(), Error::<T>::InsufficientBond);
+			ensure!(prefs.commission >= MinCommission::get(), Error::<T>::CommissionTooLow);
 			let stash = &ledger.stash;
-			<Nominators<T>>::remove(stash);
+			Self::do_remove_nominator(stash);
 			<Validators<T>>::insert(stash, prefs);
 		}
 
@@ -1497,13 +1538,14 @@ decl_module! {
 		/// Weight: O(N)
 		/// where N is the number of targets
 		/// DB Weight:
-		/// - Reads: Era Election Status, Ledger, Current Era
+		/// - Reads: Era Election Status, Ledger, Current Era, Min Nominator Bond
 		/// - Writes: Validators, Nominators
 		/// # </weight>
 		#[weight = T::WeightInfo::nominate(targets.len() as u32)]
 		pub fn nominate(origin, targets: Vec<<T::Lookup as StaticLookup>::Source>) {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			ensure!(ledger.active >= MinNominatorBond::<T>::get(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
 			ensure!(!targets.is_empty(), Error::<T>::EmptyTargets);
 			ensure!(targets.len() <= T::MAX_NOMINATIONS as usize, Error::<T>::TooManyTargets);
@@ -1527,7 +1569,7 @@ decl_module! {
 			};
 
 			<Validators<T>>::remove(stash);
-			<Nominators<T>>::insert(stash, &nominations);
+			Self::do_add_nominator(stash, nominations);
 		}
 
 		/// Declare no desire to either validate or nominate.
@@ -1578,7 +1620,10 @@ decl_module! {
 			<Payee<T>>::insert(stash, payee);
 		}
 
-		/// (Re-)set the controller of a stash.
+		/// Make the stash its own controller.
+		///
+		/// Separate controller accounts are deprecated, so this is the only controller a stash
+		/// can switch to. Does nothing if the stash is already its own controller.
 		///
 		/// Effects will be felt at the beginning of the next era.
 		///
@@ -1595,21 +1640,101 @@ decl_module! {
 		/// - Write: Bonded, Ledger New Controller, Ledger Old Controller
 		/// # </weight>
 		#[weight = T::WeightInfo::set_controller()]
-		fn set_controller(origin, controller: <T::Lookup as StaticLookup>::Source) {
+		fn set_controller(origin) {
 			let stash = ensure_signed(origin)?;
 			let old_controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
-			let controller = T::Lookup::lookup(controller)?;
-			if <Ledger<T>>::contains_key(&controller) {
-				Err(Error::<T>::AlreadyPaired)?
+			if old_controller != stash {
+				ensure!(!<Ledger<T>>::contains_key(&stash), Error::<T>::AlreadyPaired);
+				Self::move_ledger_to_stash(&stash, &old_controller);
 			}
-			if controller != old_controller {
-				<Bonded<T>>::insert(&stash, &controller);
-				if let Some(l) = <Ledger<T>>::take(&old_controller) {
-					<Ledger<T>>::insert(&controller, l);
+		}
+
+		/// Make the stashes of the given controllers their own controllers.
+		///
+		/// Controllers whose stash already controls another stash, or is already its own
+		/// controller, are skipped. This allows to lazily migrate existing pairs in batches.
+		///
+		/// The dispatch origin must be Root.
+		///
+		/// # <weight>
+		/// Weight: O(C) where C is the number of controllers.
+		/// # </weight>
+		#[weight = T::WeightInfo::deprecate_controller_batch(controllers.len() as u32)]
+		fn deprecate_controller_batch(origin, controllers: Vec<T::AccountId>) {
+			ensure_root(origin)?;
+			for controller in controllers {
+				if let Some(ledger) = Self::ledger(&controller) {
+					if ledger.stash != controller && !<Ledger<T>>::contains_key(&ledger.stash) {
+						Self::move_ledger_to_stash(&ledger.stash, &controller);
+					}
 				}
 			}
 		}
 
+		/// Set the minimum active bonds of nominators and validators.
+		///
+		/// Participants below the new minimum of their role can be chilled with
+		/// [`Call::chill_other`].
+		///
+		/// The dispatch origin must be Root.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// Write: MinNominatorBond, MinValidatorBond
+		/// # </weight>
+		#[weight = T::WeightInfo::set_staking_limits()]
+		fn set_staking_limits(
+			origin,
+			min_nominator_bond: BalanceOf<T>,
+			min_validator_bond: BalanceOf<T>,
+		) {
+			ensure_root(origin)?;
+			MinNominatorBond::<T>::put(min_nominator_bond);
+			MinValidatorBond::<T>::put(min_validator_bond);
+		}
+
+		/// Set the minimum commission that validators have to charge.
+		///
+		/// It is enforced when validators declare their preferences with [`Call::validate`].
+		///
+		/// The dispatch origin must be Root.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// Write: MinCommission
+		/// # </weight>
+		#[weight = T::WeightInfo::set_min_commission()]
+		fn set_min_commission(origin, new: Perbill) {
+			ensure_root(origin)?;
+			MinCommission::put(new);
+		}
+
+		/// Chill the stash of `controller` if its active bond is below the minimum of its role.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// # <weight>
+		/// Weight: O(1)
+		/// DB Weight:
+		/// - Read: Ledger, Nominators, Validators, MinNominatorBond, MinValidatorBond
+		/// - Write: Validators, Nominators
+		/// # </weight>
+		#[weight = T::WeightInfo::chill_other()]
+		fn chill_other(origin, controller: T::AccountId) {
+			ensure_signed(origin)?;
+			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let stash = &ledger.stash;
+			let min_bond = if Nominators::<T>::contains_key(stash) {
+				MinNominatorBond::<T>::get()
+			} else if Validators::<T>::contains_key(stash) {
+				MinValidatorBond::<T>::get()
+			} else {
+				Err(Error::<T>::CannotChillOther)?
+			};
+			ensure!(ledger.active < min_bond, Error::<T>::CannotChillOther);
+			Self::chill_stash(stash);
+		}
+
 		/// Sets the ideal number of validators.
 		///
 		/// The dispatch origin must be Root.
@@ -1756,7 +1881,8 @@ decl_module! {
 
 			for (removed, index) in slash_indices.into_iter().enumerate() {
 				let index = (index as usize) - removed;
-				unapplied.remove(index);
+				let slash = unapplied.remove(index);
+				Self::deposit_event(RawEvent::SlashCancelled(slash.validator, era));
 			}
 
 			<Self as Store>::UnappliedSlashes::insert(&era, &unapplied);
@@ -2081,12 +2207,50 @@ impl<T: Config> Module<T> {
 			WithdrawReasons::all(),
 		);
 		<Ledger<T>>::insert(controller, ledger);
+
+		if Nominators::<T>::contains_key(&ledger.stash) {
+			T::SortedListProvider::on_update(&ledger.stash, Self::weight_of(&ledger.stash));
+		}
+	}
+
+	/// Move the ledger of `controller` to `stash`, making the stash its own controller.
+	fn move_ledger_to_stash(stash: &T::AccountId, controller: &T::AccountId) {
+		<Bonded<T>>::insert(stash, stash);
+		if let Some(ledger) = <Ledger<T>>::take(controller) {
+			<Ledger<T>>::insert(stash, ledger);
+		}
 	}
 
 	/// Chill a stash account.
 	fn chill_stash(stash: &T::AccountId) {
 		<Validators<T>>::remove(stash);
-		<Nominators<T>>::remove(stash);
+		Self::do_remove_nominator(stash);
+	}
+
+	/// The vote weight of `stash` in the elections.
+	fn weight_of(stash: &T::AccountId) -> VoteWeight {
+		Self::slashable_balance_of_vote_weight(stash, T::Currency::total_issuance())
+	}
+
+	/// Set the nominations of `stash`, adding it to [`Config::SortedListProvider`] if it was not
+	/// a nominator before.
+	///
+	/// All writes of new nominations must go through this, to keep the sorted list in sync.
+	fn do_add_nominator(stash: &T::AccountId, nominations: Nominations<T::AccountId>) {
+		if !Nominators::<T>::contains_key(stash) {
+			let _ = T::SortedListProvider::on_insert(stash.clone(), Self::weight_of(stash))
+				.defensive();
+		}
+		Nominators::<T>::insert(stash, nominations);
+	}
+
+	/// Remove `stash` from the nominators and from [`Config::SortedListProvider`], if it is a
+	/// nominator.
+	fn do_remove_nominator(stash: &T::AccountId) {
+		if Nominators::<T>::contains_key(stash) {
+			T::SortedListProvider::on_remove(stash);
+			Nominators::<T>::remove(stash);
+		}
 	}
 
 	/// Actually make a payment to a staker. This uses the currency's reward function
@@ -2404,7 +2568,7 @@ impl<T: Config> Module<T> {
 
 		<Payee<T>>::remove(stash);
 		<Validators<T>>::remove(stash);
-		<Nominators<T>>::remove(stash);
+		Self::do_remove_nominator(stash);
 
 		system::Pallet::<T>::dec_consumers(stash);
 
@@ -2484,9 +2648,11 @@ impl<T: Config> Module<T> {
 		SlashRewardFraction::put(fraction);
 	}
 
-	/// Get all of the voters that are eligible for the npos election.
+	/// Get the voters that are eligible for the npos election.
 	///
-	/// This will use all on-chain nominators, and all the validators will inject a self vote.
+	/// All the validators will inject a self vote, and the nominators are taken in the order of
+	/// [`Config::SortedListProvider`]. If `maybe_max_len` is `Some(max)`, at most `max` voters are
+	/// returned, validators first.
 	///
 	/// ### Slashing
 	///
@@ -2494,11 +2660,14 @@ impl<T: Config> Module<T> {
 	/// auto-chilled.
 	///
 	/// Note that this is VERY expensive. Use with care.
-	pub fn get_npos_voters() -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+	pub fn get_npos_voters(
+		maybe_max_len: Option<usize>,
+	) -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+		let max_len = maybe_max_len.unwrap_or(usize::max_value());
 		let weight_of = Self::slashable_balance_of_fn();
 		let mut all_voters = Vec::new();
 
-		for (validator, _) in <Validators<T>>::iter() {
+		for (validator, _) in <Validators<T>>::iter().take(max_len) {
 			// append self vote
 			let self_vote = (validator.clone(), weight_of(&validator), vec![validator.clone()]);
 			all_voters.push(self_vote);
@@ -2507,7 +2676,11 @@ impl<T: Config> Module<T> {
 		// collect all slashing spans into a BTreeMap for further queries.
 		let slashing_spans = <SlashingSpans<T>>::iter().collect::<BTreeMap<_, _>>();
 
-		for (nominator, nominations) in <Nominators<T>>::iter() {
+		let nominators_quota = max_len.saturating_sub(all_voters.len());
+		let nominators = T::SortedListProvider::iter()
+			.filter_map(|nominator| Some((<Nominators<T>>::get(&nominator)?, nominator)))
+			.take(nominators_quota);
+		for (nominations, nominator) in nominators {
 			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
 
 			// Filter out nomination targets which were nominated before the most recent
@@ -2541,16 +2714,12 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 	fn voters(
 		maybe_max_len: Option<usize>,
 	) -> data_provider::Result<(Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)>, Weight)> {
-		// NOTE: reading these counts already needs to iterate a lot of storage keys, but they get
-		// cached. This is okay for the case of `Ok(_)`, but bad for `Err(_)`, as the trait does not
-		// report weight in failures.
-		let nominator_count = <Nominators<T>>::iter().count();
-		let validator_count = <Validators<T>>::iter().count();
-		let voter_count = nominator_count.saturating_add(validator_count);
-
-		if maybe_max_len.map_or(false, |max_len| voter_count > max_len) {
-			return Err("Voter snapshot too big");
-		}
+		// NOTE: reading the validator count needs to iterate a lot of storage keys, but they get
+		// cached.
+		let max_len = maybe_max_len.unwrap_or(usize::max_value());
+		let validator_count = <Validators<T>>::iter().count().min(max_len);
+		let nominator_count = (T::SortedListProvider::count() as usize)
+			.min(max_len.saturating_sub(validator_count));
 
 		let slashing_span_count = <SlashingSpans<T>>::iter().count();
 		let weight = T::WeightInfo::get_npos_voters(
@@ -2558,7 +2727,7 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 			validator_count as u32,
 			slashing_span_count as u32,
 		);
-		Ok((Self::get_npos_voters(), weight))
+		Ok((Self::get_npos_voters(maybe_max_len), weight))
 	}
 
 	fn targets(maybe_max_len: Option<usize>) -> data_provider::Result<(Vec<T::AccountId>, Weight)> {
@@ -2642,14 +2811,43 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 					claimed_rewards: vec![],
 				},
 			);
-			<Nominators<T>>::insert(
-				v,
+			Self::do_add_nominator(
+				&v,
 				Nominations { targets: t, submitted_in: 0, suppressed: false },
 			);
 		});
 	}
 }
 
+/// A [`SortedListProvider`] that simply iterates over the [`Nominators`] map, in no particular
+/// order.
+///
+/// This keeps no state of its own, so all hooks are no-ops.
+pub struct UseNominatorsMap<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> SortedListProvider<T::AccountId> for UseNominatorsMap<T> {
+	type Error = ();
+
+	fn iter() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(<Nominators<T>>::iter().map(|(nominator, _)| nominator))
+	}
+	fn count() -> u32 {
+		<Nominators<T>>::iter().count() as u32
+	}
+	fn contains(id: &T::AccountId) -> bool {
+		<Nominators<T>>::contains_key(id)
+	}
+	fn on_insert(_: T::AccountId, _: VoteWeight) -> Result<(), Self::Error> {
+		Ok(())
+	}
+	fn on_update(_: &T::AccountId, _: VoteWeight) {}
+	fn on_remove(_: &T::AccountId) {}
+	fn clear() {}
+	fn sanity_check() -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
 /// In this implementation `new_session(session)` must be called before `end_session(session-1)`
 /// i.e. the new session must be planned before the ending of the previous session.
 ///
@@ -2863,10 +3061,15 @@ where
 					}
 				} else {
 					// defer to end of some `slash_defer_duration` from now.
-					<Self as Store>::UnappliedSlashes::mutate(
+					let validator = unapplied.validator.clone();
+					let index = <Self as Store>::UnappliedSlashes::mutate(
 						active_era,
-						move |for_later| for_later.push(unapplied),
+						move |for_later| {
+							for_later.push(unapplied);
+							for_later.len() as u32 - 1
+						},
 					);
+					Self::deposit_event(RawEvent::SlashDeferred(validator, active_era, index));
 					add_db_reads_writes(1, 1);
 				}
 			} else {
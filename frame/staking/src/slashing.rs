@@ -148,6 +148,13 @@ impl SlashingSpans {
 		self.last_nonzero_slash
 	}
 
+	/// The number of slashing spans, including the ongoing one.
+	///
+	/// This is the minimum `num_slashing_spans` to pass to calls that kill the stash.
+	pub fn num_spans(&self) -> u32 {
+		self.prior.len() as u32 + 1
+	}
+
 	// prune the slashing spans against a window, whose start era index is given.
 	//
 	// If this returns `Some`, then it includes a range start..end of all the span
@@ -576,20 +583,22 @@ pub(crate) fn clear_stash_metadata<T: Config>(
 // apply the slash to a stash account, deducting any missing funds from the reward
 // payout, saturating at 0. this is mildly unfair but also an edge-case that
 // can only occur when overlapping locked funds have been slashed.
+//
+// returns the value that was actually slashed.
 pub fn do_slash<T: Config>(
 	stash: &T::AccountId,
 	value: BalanceOf<T>,
 	reward_payout: &mut BalanceOf<T>,
 	slashed_imbalance: &mut NegativeImbalanceOf<T>,
-) {
+) -> BalanceOf<T> {
 	let controller = match <Module<T>>::bonded(stash) {
-		None => return, // defensive: should always exist.
+		None => return Zero::zero(), // defensive: should always exist.
 		Some(c) => c,
 	};
 
 	let mut ledger = match <Module<T>>::ledger(&controller) {
 		Some(ledger) => ledger,
-		None => return, // nothing to do.
+		None => return Zero::zero(), // nothing to do.
 	};
 
 	let value = ledger.slash(value, T::Currency::minimum_balance());
@@ -604,12 +613,9 @@ pub fn do_slash<T: Config>(
 		}
 
 		<Module<T>>::update_ledger(&controller, &ledger);
-
-		// trigger the event
-		<Module<T>>::deposit_event(
-			super::RawEvent::Slash(stash.clone(), value)
-		);
 	}
+
+	value
 }
 
 /// Apply a previously-unapplied slash.
@@ -617,20 +623,29 @@ pub(crate) fn apply_slash<T: Config>(unapplied_slash: UnappliedSlash<T::AccountI
 	let mut slashed_imbalance = NegativeImbalanceOf::<T>::zero();
 	let mut reward_payout = unapplied_slash.payout;
 
-	do_slash::<T>(
-		&unapplied_slash.validator,
+	let validator = &unapplied_slash.validator;
+	let value = do_slash::<T>(
+		validator,
 		unapplied_slash.own,
 		&mut reward_payout,
 		&mut slashed_imbalance,
 	);
+	if !value.is_zero() {
+		<Module<T>>::deposit_event(super::RawEvent::Slash(validator.clone(), value));
+	}
 
 	for &(ref nominator, nominator_slash) in &unapplied_slash.others {
-		do_slash::<T>(
+		let value = do_slash::<T>(
 			&nominator,
 			nominator_slash,
 			&mut reward_payout,
 			&mut slashed_imbalance,
 		);
+		if !value.is_zero() {
+			<Module<T>>::deposit_event(
+				super::RawEvent::NominatorSlashed(nominator.clone(), validator.clone(), value)
+			);
+		}
 	}
 
 	pay_reporters::<T>(reward_payout, slashed_imbalance, &unapplied_slash.reporters);
@@ -31,6 +31,7 @@ const SEED: u32 = 0;
 pub fn clear_validators_and_nominators<T: Config>() {
 	Validators::<T>::remove_all();
 	Nominators::<T>::remove_all();
+	T::SortedListProvider::clear();
 }
 
 /// Grab a funded user.
@@ -158,9 +158,9 @@ fn change_controller_works() {
 		// 10 can control 11 who is initially a validator.
 		assert_ok!(Staking::chill(Origin::signed(10)));
 
-		// change controller
-		assert_ok!(Staking::set_controller(Origin::signed(11), 5));
-		assert_eq!(Staking::bonded(&11), Some(5));
+		// change controller to the stash itself
+		assert_ok!(Staking::set_controller(Origin::signed(11)));
+		assert_eq!(Staking::bonded(&11), Some(11));
 		mock::start_active_era(1);
 
 		// 10 is no longer in control.
@@ -168,7 +168,96 @@ fn change_controller_works() {
 			Staking::validate(Origin::signed(10), ValidatorPrefs::default()),
 			Error::<Test>::NotController,
 		);
-		assert_ok!(Staking::validate(Origin::signed(5), ValidatorPrefs::default()));
+		assert_ok!(Staking::validate(Origin::signed(11), ValidatorPrefs::default()));
+
+		// only stashes can do this, and doing it again is fine.
+		assert_noop!(Staking::set_controller(Origin::signed(10)), Error::<Test>::NotStash);
+		assert_ok!(Staking::set_controller(Origin::signed(11)));
+		assert_eq!(Staking::bonded(&11), Some(11));
+	})
+}
+
+#[test]
+fn deprecate_controller_batch_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(Staking::bonded(&11), Some(10));
+		assert_eq!(Staking::bonded(&21), Some(20));
+
+		assert_noop!(
+			Staking::deprecate_controller_batch(Origin::signed(10), vec![10]),
+			BadOrigin,
+		);
+
+		// unknown controllers are skipped.
+		assert_ok!(Staking::deprecate_controller_batch(Origin::root(), vec![10, 20, 1337]));
+		assert_eq!(Staking::bonded(&11), Some(11));
+		assert_eq!(Staking::bonded(&21), Some(21));
+		assert_eq!(Staking::ledger(&11).unwrap().stash, 11);
+		assert!(Staking::ledger(&10).is_none());
+	})
+}
+
+#[test]
+fn min_commission_is_enforced() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			Staking::set_min_commission(Origin::signed(10), Perbill::from_percent(10)),
+			BadOrigin,
+		);
+		assert_ok!(Staking::set_min_commission(Origin::root(), Perbill::from_percent(10)));
+
+		let prefs = |percent| ValidatorPrefs {
+			commission: Perbill::from_percent(percent),
+			..Default::default()
+		};
+		assert_noop!(
+			Staking::validate(Origin::signed(10), prefs(5)),
+			Error::<Test>::CommissionTooLow,
+		);
+		assert_ok!(Staking::validate(Origin::signed(10), prefs(10)));
+	})
+}
+
+#[test]
+fn chill_other_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		// 11 validates with 1000 and 101 nominates with 500.
+		assert!(Validators::<Test>::contains_key(&11));
+		assert!(Nominators::<Test>::contains_key(&101));
+
+		// nobody is under-bonded yet.
+		assert_noop!(
+			Staking::chill_other(Origin::signed(1337), 10),
+			Error::<Test>::CannotChillOther,
+		);
+		assert_noop!(
+			Staking::chill_other(Origin::signed(1337), 100),
+			Error::<Test>::CannotChillOther,
+		);
+
+		assert_ok!(Staking::set_staking_limits(Origin::root(), 1000, 2000));
+
+		// new participants have to respect the limits.
+		assert_noop!(
+			Staking::nominate(Origin::signed(100), vec![11]),
+			Error::<Test>::InsufficientBond,
+		);
+		assert_noop!(
+			Staking::validate(Origin::signed(10), ValidatorPrefs::default()),
+			Error::<Test>::InsufficientBond,
+		);
+
+		// and existing ones can be chilled by anyone.
+		assert_ok!(Staking::chill_other(Origin::signed(1337), 10));
+		assert_ok!(Staking::chill_other(Origin::signed(1337), 100));
+		assert!(!Validators::<Test>::contains_key(&11));
+		assert!(!Nominators::<Test>::contains_key(&101));
+
+		// chilled stashes can't be chilled again.
+		assert_noop!(
+			Staking::chill_other(Origin::signed(1337), 10),
+			Error::<Test>::CannotChillOther,
+		);
 	})
 }
 
@@ -2722,6 +2811,7 @@ fn deferred_slashes_are_deferred() {
 
 			assert_eq!(Balances::free_balance(11), 1000);
 			assert_eq!(Balances::free_balance(101), 2000);
+			assert_eq!(staking_events().last(), Some(&RawEvent::SlashDeferred(11, 1, 0)));
 
 			mock::start_active_era(2);
 
@@ -2739,6 +2829,10 @@ fn deferred_slashes_are_deferred() {
 
 			assert_eq!(Balances::free_balance(11), 900);
 			assert_eq!(Balances::free_balance(101), 2000 - (nominated_value / 10));
+
+			let events = staking_events();
+			assert!(events.contains(&RawEvent::Slash(11, 100)));
+			assert!(events.contains(&RawEvent::NominatorSlashed(101, 11, nominated_value / 10)));
 		})
 }
 
@@ -2788,6 +2882,7 @@ fn remove_deferred() {
 		);
 
 			assert_ok!(Staking::cancel_deferred_slash(Origin::root(), 1, vec![0]));
+			assert_eq!(staking_events().last(), Some(&RawEvent::SlashCancelled(11, 1)));
 
 			assert_eq!(Balances::free_balance(11), 1000);
 			assert_eq!(Balances::free_balance(101), 2000);
@@ -3934,11 +4029,33 @@ mod election_data_provider {
 	#[test]
 	fn respects_len_limits() {
 		ExtBuilder::default().build().execute_with(|| {
-			assert_eq!(Staking::voters(Some(1)).unwrap_err(), "Voter snapshot too big");
+			assert_eq!(Staking::voters(Some(1)).unwrap().0.len(), 1);
 			assert_eq!(Staking::targets(Some(1)).unwrap_err(), "Target snapshot too big");
 		});
 	}
 
+	#[test]
+	fn voters_fill_remaining_len_with_nominators() {
+		ExtBuilder::default().nominate(true).build().execute_with(|| {
+			let validator_count = <Validators<Test>>::iter().count();
+			let nominator_count = <Nominators<Test>>::iter().count();
+			assert!(nominator_count > 0);
+
+			// only room for the validators' self votes.
+			let voters = Staking::voters(Some(validator_count)).unwrap().0;
+			assert_eq!(voters.len(), validator_count);
+			assert!(voters.iter().all(|(who, _, targets)| targets == &vec![*who]));
+
+			// room for one nominator.
+			let voters = Staking::voters(Some(validator_count + 1)).unwrap().0;
+			assert_eq!(voters.len(), validator_count + 1);
+
+			// no limit.
+			let voters = Staking::voters(None).unwrap().0;
+			assert_eq!(voters.len(), validator_count + nominator_count);
+		});
+	}
+
 	#[test]
 	fn estimate_next_election_works() {
 		ExtBuilder::default().session_per_era(5).period(5).build().execute_with(|| {
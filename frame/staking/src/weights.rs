@@ -20,6 +20,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
 //! DATE: 2021-03-25, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weights of `deprecate_controller_batch`, `set_staking_limits`, `set_min_commission` and
+//! `chill_other` are hand-written estimates which were not generated by the benchmark CLI.
 
 // Executed Command:
 // target/release/substrate
@@ -56,6 +59,10 @@ pub trait WeightInfo {
 	fn set_payee() -> Weight;
 	fn set_controller() -> Weight;
 	fn set_validator_count() -> Weight;
+	fn deprecate_controller_batch(c: u32, ) -> Weight;
+	fn set_staking_limits() -> Weight;
+	fn set_min_commission() -> Weight;
+	fn chill_other() -> Weight;
 	fn force_no_eras() -> Weight;
 	fn force_new_era() -> Weight;
 	fn force_new_era_always() -> Weight;
@@ -107,7 +114,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	fn validate() -> Weight {
 		(16_345_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	fn kick(k: u32, ) -> Weight {
@@ -122,7 +129,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		(29_101_000 as Weight)
 			// Standard Error: 23_000
 			.saturating_add((5_670_000 as Weight).saturating_mul(n as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
@@ -145,6 +152,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		(2_323_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Estimate, not benchmarked.
+	fn deprecate_controller_batch(c: u32, ) -> Weight {
+		(2_781_000 as Weight)
+			.saturating_add((17_246_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	// Estimate, not benchmarked.
+	fn set_staking_limits() -> Weight {
+		(3_104_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Estimate, not benchmarked.
+	fn set_min_commission() -> Weight {
+		(2_395_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Estimate, not benchmarked.
+	fn chill_other() -> Weight {
+		(21_432_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 	fn force_no_eras() -> Weight {
 		(2_528_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
@@ -287,7 +317,7 @@ impl WeightInfo for () {
 	}
 	fn validate() -> Weight {
 		(16_345_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	fn kick(k: u32, ) -> Weight {
@@ -302,7 +332,7 @@ impl WeightInfo for () {
 		(29_101_000 as Weight)
 			// Standard Error: 23_000
 			.saturating_add((5_670_000 as Weight).saturating_mul(n as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
@@ -325,6 +355,29 @@ impl WeightInfo for () {
 		(2_323_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Estimate, not benchmarked.
+	fn deprecate_controller_batch(c: u32, ) -> Weight {
+		(2_781_000 as Weight)
+			.saturating_add((17_246_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	// Estimate, not benchmarked.
+	fn set_staking_limits() -> Weight {
+		(3_104_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Estimate, not benchmarked.
+	fn set_min_commission() -> Weight {
+		(2_395_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimate, not benchmarked.
+	fn chill_other() -> Weight {
+		(21_432_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 	fn force_no_eras() -> Weight {
 		(2_528_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
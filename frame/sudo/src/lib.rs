@@ -35,6 +35,7 @@
 //!
 //! * `sudo` - Make a `Root` call to a dispatchable function.
 //! * `set_key` - Assign a new account to be the sudo key.
+//! * `remove_key` - Permanently remove the sudo key. No sudo call can be made afterwards.
 //!
 //! ## Usage
 //!
@@ -100,6 +101,7 @@ use sp_std::prelude::*;
 use sp_runtime::{DispatchResult, traits::StaticLookup};
 
 use frame_support::{
+	dispatch::PostDispatchInfo,
 	weights::GetDispatchInfo,
 	traits::UnfilteredDispatchable,
 };
@@ -154,8 +156,7 @@ pub mod pallet {
 			call: Box<<T as Config>::Call>,
 		) -> DispatchResultWithPostInfo {
 			// This is a public call, so we ensure that the origin is some signed account.
-			let sender = ensure_signed(origin)?;
-			ensure!(sender == Self::key(), Error::<T>::RequireSudo);
+			Self::ensure_sudo(origin)?;
 
 			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
 			Self::deposit_event(Event::Sudid(res.map(|_| ()).map_err(|e| e.error)));
@@ -167,6 +168,9 @@ pub mod pallet {
 		/// This function does not check the weight of the call, and instead allows the
 		/// Sudo user to specify the weight of the call.
 		///
+		/// If the dispatched call reports its actual weight, it is used as the actual weight of
+		/// this call, so the unused part of the specified weight is refunded to the block.
+		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// # <weight>
@@ -180,13 +184,16 @@ pub mod pallet {
 			_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			// This is a public call, so we ensure that the origin is some signed account.
-			let sender = ensure_signed(origin)?;
-			ensure!(sender == Self::key(), Error::<T>::RequireSudo);
+			Self::ensure_sudo(origin)?;
 
 			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+			let actual_weight = match res {
+				Ok(post_info) => post_info.actual_weight,
+				Err(ref err) => err.post_info.actual_weight,
+			};
 			Self::deposit_event(Event::Sudid(res.map(|_| ()).map_err(|e| e.error)));
 			// Sudo user does not pay a fee.
-			Ok(Pays::No.into())
+			Ok(PostDispatchInfo { actual_weight, pays_fee: Pays::No })
 		}
 
 		/// Authenticates the current sudo key and sets the given AccountId (`new`) as the new sudo key.
@@ -204,16 +211,37 @@ pub mod pallet {
 			new: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResultWithPostInfo {
 			// This is a public call, so we ensure that the origin is some signed account.
-			let sender = ensure_signed(origin)?;
-			ensure!(sender == Self::key(), Error::<T>::RequireSudo);
+			let sender = Self::ensure_sudo(origin)?;
 			let new = T::Lookup::lookup(new)?;
 
-			Self::deposit_event(Event::KeyChanged(Self::key()));
+			Self::deposit_event(Event::KeyChanged(sender));
 			<Key<T>>::put(new);
 			// Sudo user does not pay a fee.
 			Ok(Pays::No.into())
 		}
 
+		/// Authenticates the current sudo key and permanently removes it.
+		///
+		/// After this, no sudo call can be made anymore, including `set_key`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// # </weight>
+		#[pallet::weight(0)]
+		pub(crate) fn remove_key(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			// This is a public call, so we ensure that the origin is some signed account.
+			let sender = Self::ensure_sudo(origin)?;
+
+			Self::deposit_event(Event::KeyRemoved(sender));
+			<Key<T>>::kill();
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
 		/// Authenticates the sudo key and dispatches a function call with `Signed` origin from
 		/// a given account.
 		///
@@ -241,8 +269,7 @@ pub mod pallet {
 			call: Box<<T as Config>::Call>
 		) -> DispatchResultWithPostInfo {
 			// This is a public call, so we ensure that the origin is some signed account.
-			let sender = ensure_signed(origin)?;
-			ensure!(sender == Self::key(), Error::<T>::RequireSudo);
+			Self::ensure_sudo(origin)?;
 
 			let who = T::Lookup::lookup(who)?;
 
@@ -254,6 +281,15 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> Pallet<T> {
+		/// Ensure that `origin` is signed by the sudo key, and return the key.
+		fn ensure_sudo(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::key().as_ref() == Some(&sender), Error::<T>::RequireSudo);
+			Ok(sender)
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	#[pallet::metadata(T::AccountId = "AccountId")]
@@ -264,6 +300,8 @@ pub mod pallet {
 		KeyChanged(T::AccountId),
 		/// A sudo just took place. \[result\]
 		SudoAsDone(DispatchResult),
+		/// The sudo key was removed; the old key is supplied. \[sudoer\]
+		KeyRemoved(T::AccountId),
 	}
 
 	#[pallet::error]
@@ -273,10 +311,10 @@ pub mod pallet {
 		RequireSudo,
 	}
 
-	/// The `AccountId` of the sudo key.
+	/// The `AccountId` of the sudo key, if there is one.
 	#[pallet::storage]
 	#[pallet::getter(fn key)]
-	pub(super) type Key<T: Config> = StorageValue<_, T::AccountId, ValueQuery>;
+	pub(super) type Key<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -60,6 +60,20 @@ pub mod logger {
 			Ok(().into())
 		}
 
+		#[pallet::weight(*weight)]
+		pub(crate) fn privileged_refunding_log(
+			origin: OriginFor<T>,
+			i: i32,
+			weight: Weight,
+			actual_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			// Ensure that the `origin` is `Root`.
+			ensure_root(origin)?;
+			<I32Log<T>>::append(i);
+			Self::deposit_event(Event::AppendI32(i, actual_weight));
+			Ok(Some(actual_weight).into())
+		}
+
 		#[pallet::weight(*weight)]
 		pub(crate) fn non_privileged_log(
 			origin: OriginFor<T>,
@@ -22,13 +22,13 @@ use mock::{
 	Sudo, SudoCall, Origin, Call, Test, new_test_ext, LoggerCall, Logger, System,
 	Event as TestEvent,
 };
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, weights::Pays};
 
 #[test]
 fn test_setup_works() {
 	// Environment setup, logger storage, and sudo `key` retrieval should work as expected.
 	new_test_ext(1).execute_with(|| {
-		assert_eq!(Sudo::key(),  1u64);
+		assert_eq!(Sudo::key(), Some(1u64));
 		assert!(Logger::i32_log().is_empty());
 		assert!(Logger::account_log().is_empty());
 	});
@@ -102,12 +102,28 @@ fn sudo_unchecked_weight_emits_events_correctly() {
 	})
 }
 
+#[test]
+fn sudo_unchecked_weight_refunds_unused_weight() {
+	new_test_ext(1).execute_with(|| {
+		// The actual weight of the inner call is passed through.
+		let call = Box::new(Call::Logger(LoggerCall::privileged_refunding_log(42, 1_000, 400)));
+		let post_info = Sudo::sudo_unchecked_weight(Origin::signed(1), call, 1_000).unwrap();
+		assert_eq!(post_info.actual_weight, Some(400));
+		assert_eq!(post_info.pays_fee, Pays::No);
+
+		// An inner call that doesn't report its actual weight consumes the specified weight.
+		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log(42, 1_000)));
+		let post_info = Sudo::sudo_unchecked_weight(Origin::signed(1), call, 1_000).unwrap();
+		assert_eq!(post_info.actual_weight, None);
+	});
+}
+
 #[test]
 fn set_key_basics() {
 	new_test_ext(1).execute_with(|| {
 		// A root `key` can change the root `key`
 		assert_ok!(Sudo::set_key(Origin::signed(1), 2));
-		assert_eq!(Sudo::key(),  2u64);
+		assert_eq!(Sudo::key(), Some(2u64));
 	});
 
 	new_test_ext(1).execute_with(|| {
@@ -133,6 +149,27 @@ fn set_key_emits_events_correctly() {
 	});
 }
 
+#[test]
+fn remove_key_works() {
+	new_test_ext(1).execute_with(|| {
+		// Set block number to 1 because events are not emitted on block 0.
+		System::set_block_number(1);
+
+		// A non-root `key` cannot remove the root `key`.
+		assert_noop!(Sudo::remove_key(Origin::signed(2)), Error::<Test>::RequireSudo);
+
+		assert_ok!(Sudo::remove_key(Origin::signed(1)));
+		assert_eq!(Sudo::key(), None);
+		System::assert_last_event(TestEvent::sudo(Event::KeyRemoved(1)));
+
+		// No sudo call can be made anymore.
+		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log(42, 1_000)));
+		assert_noop!(Sudo::sudo(Origin::signed(1), call), Error::<Test>::RequireSudo);
+		assert_noop!(Sudo::set_key(Origin::signed(1), 1), Error::<Test>::RequireSudo);
+		assert_noop!(Sudo::remove_key(Origin::signed(1)), Error::<Test>::RequireSudo);
+	});
+}
+
 #[test]
 fn sudo_as_basics() {
 	new_test_ext(1).execute_with(|| {
@@ -49,11 +49,20 @@ pub struct RuntimeDefinition {
 
 impl Parse for RuntimeDefinition {
 	fn parse(input: ParseStream) -> Result<Self> {
+		let visibility_token = input.parse()?;
+		let enum_token = input.parse()?;
+		let name: Ident = input.parse()?;
+		let where_section = if input.peek(token::Where) {
+			input.parse()?
+		} else {
+			WhereSection::conventional(name.span())
+		};
+
 		Ok(Self {
-			visibility_token: input.parse()?,
-			enum_token: input.parse()?,
-			name: input.parse()?,
-			where_section: input.parse()?,
+			visibility_token,
+			enum_token,
+			name,
+			where_section,
 			pallets: input.parse()?,
 		})
 	}
@@ -66,6 +75,23 @@ pub struct WhereSection {
 	pub unchecked_extrinsic: syn::TypePath,
 }
 
+impl WhereSection {
+	/// The where section used when it is omitted: `Block = Block, NodeBlock = Block,
+	/// UncheckedExtrinsic = UncheckedExtrinsic`, referring to the type aliases of the runtime.
+	fn conventional(span: Span) -> Self {
+		let type_path = |name| syn::TypePath {
+			qself: None,
+			path: Ident::new(name, span).into(),
+		};
+
+		Self {
+			block: type_path("Block"),
+			node_block: type_path("Block"),
+			unchecked_extrinsic: type_path("UncheckedExtrinsic"),
+		}
+	}
+}
+
 impl Parse for WhereSection {
 	fn parse(input: ParseStream) -> Result<Self> {
 		input.parse::<token::Where>()?;
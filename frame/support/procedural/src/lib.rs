@@ -28,6 +28,7 @@ mod debug_no_bound;
 mod clone_no_bound;
 mod partial_eq_no_bound;
 mod default_no_bound;
+mod max_encoded_len;
 
 pub(crate) use storage::INHERENT_INSTANCE_NAME;
 use proc_macro::TokenStream;
@@ -250,6 +251,15 @@ pub fn decl_storage(input: TokenStream) -> TokenStream {
 /// `Block` is the block type that is used in the runtime and `NodeBlock` is the block type
 /// that is used in the node. For instance they can differ in the extrinsics type.
 ///
+/// The `where` section can be omitted if the runtime defines `Block` and `UncheckedExtrinsic`
+/// type aliases, in which case it defaults to:
+///
+/// ```nocompile
+/// Block = Block,
+/// NodeBlock = Block,
+/// UncheckedExtrinsic = UncheckedExtrinsic
+/// ```
+///
 /// # Example:
 ///
 /// ```nocompile
@@ -419,6 +429,12 @@ pub fn derive_default_no_bound(input: TokenStream) -> TokenStream {
 	default_no_bound::derive_default_no_bound(input)
 }
 
+/// Derive `MaxEncodedLen`. Docs are at `frame_support::MaxEncodedLen`.
+#[proc_macro_derive(MaxEncodedLen, attributes(codec))]
+pub fn derive_max_encoded_len(input: TokenStream) -> TokenStream {
+	max_encoded_len::derive_max_encoded_len(input)
+}
+
 #[proc_macro_attribute]
 pub fn require_transactional(attr: TokenStream, input: TokenStream) -> TokenStream {
 	transactional::require_transactional(attr, input).unwrap_or_else(|e| e.to_compile_error().into())
@@ -0,0 +1,135 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support_procedural_tools::generate_crate_access_2018;
+use proc_macro2::TokenTree;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+/// Derive `MaxEncodedLen`. Docs are at `frame_support::MaxEncodedLen`.
+pub fn derive_max_encoded_len(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input: syn::DeriveInput = match syn::parse(input) {
+		Ok(input) => input,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	let frame_support = match generate_crate_access_2018("frame-support") {
+		Ok(frame_support) => frame_support,
+		Err(e) => return e.to_compile_error().into(),
+	};
+
+	let name = &input.ident;
+	let type_params = input.generics.type_params().map(|p| p.ident.clone()).collect::<Vec<_>>();
+	let mut bounds = Vec::new();
+
+	let data_expr = match &input.data {
+		syn::Data::Struct(struct_) => {
+			fields_length_expr(&frame_support, &struct_.fields, &type_params, &mut bounds)
+		},
+		syn::Data::Enum(enum_) => {
+			let variants = enum_.variants.iter()
+				.map(|variant| {
+					fields_length_expr(&frame_support, &variant.fields, &type_params, &mut bounds)
+				})
+				.collect::<Vec<_>>();
+
+			if variants.is_empty() {
+				quote!( 0_usize )
+			} else {
+				// The variant index is encoded as one byte.
+				quote!( 0_usize #( .max(#variants) )* .saturating_add(1) )
+			}
+		},
+		syn::Data::Union(union_) => {
+			let msg = "Union type not supported by `derive(MaxEncodedLen)`";
+			return syn::Error::new(union_.union_token.span(), msg).to_compile_error().into()
+		},
+	};
+
+	let mut generics = input.generics.clone();
+	let (_, ty_generics, _) = input.generics.split_for_impl();
+	// `Encode` is a supertrait, its implementation can require bounds that are not implied by the
+	// ones on the fields.
+	bounds.push(syn::parse_quote!(#name #ty_generics: #frame_support::codec::Encode));
+	generics.make_where_clause().predicates.extend(bounds);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	quote!(
+		const _: () = {
+			impl #impl_generics #frame_support::traits::MaxEncodedLen for #name #ty_generics
+				#where_clause
+			{
+				fn max_encoded_len() -> usize {
+					#data_expr
+				}
+			}
+		};
+	).into()
+}
+
+/// Expression of the maximum encoded length of all fields, the bound required on the types of
+/// fields using a generic parameter are pushed to `bounds`.
+fn fields_length_expr(
+	frame_support: &syn::Ident,
+	fields: &syn::Fields,
+	type_params: &[syn::Ident],
+	bounds: &mut Vec<syn::WherePredicate>,
+) -> proc_macro2::TokenStream {
+	let lengths = fields.iter()
+		.filter(|field| !has_codec_attr(&field.attrs, "skip"))
+		.map(|field| {
+			let field_ty = &field.ty;
+			let ty = if has_codec_attr(&field.attrs, "compact") {
+				quote_spanned!(field_ty.span() => #frame_support::codec::Compact<#field_ty>)
+			} else {
+				quote!(#field_ty)
+			};
+
+			if contains_ident(quote!(#field_ty), type_params) {
+				bounds.push(syn::parse_quote!(#ty: #frame_support::traits::MaxEncodedLen));
+			}
+
+			quote_spanned!(field_ty.span() =>
+				.saturating_add(<#ty as #frame_support::traits::MaxEncodedLen>::max_encoded_len())
+			)
+		});
+
+	quote!( 0_usize #( #lengths )* )
+}
+
+/// Whether the attributes contain `#[codec(<name>)]`.
+fn has_codec_attr(attrs: &[syn::Attribute], name: &str) -> bool {
+	attrs.iter()
+		.filter(|attr| attr.path.is_ident("codec"))
+		.filter_map(|attr| attr.parse_meta().ok())
+		.any(|meta| match meta {
+			syn::Meta::List(list) => list.nested.iter().any(|nested| matches!(
+				nested,
+				syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident(name)
+			)),
+			_ => false,
+		})
+}
+
+/// Whether the token stream contains one of the idents.
+fn contains_ident(tokens: proc_macro2::TokenStream, idents: &[syn::Ident]) -> bool {
+	tokens.into_iter().any(|token| match token {
+		TokenTree::Ident(ident) => idents.contains(&ident),
+		TokenTree::Group(group) => contains_ident(group.stream(), idents),
+		_ => false,
+	})
+}
@@ -165,6 +165,22 @@ pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
 			}
 		}
 
+		impl<#type_impl_gen>
+			#frame_support::traits::TryState<<T as #frame_system::Config>::BlockNumber>
+			for #pallet_ident<#type_use_gen> #where_clause
+		{
+			#[cfg(feature = "try-runtime")]
+			fn try_state(
+				n: <T as #frame_system::Config>::BlockNumber
+			) -> Result<(), &'static str> {
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Config>::BlockNumber
+					>
+				>::try_state(n)
+			}
+		}
+
 		impl<#type_impl_gen>
 			#frame_support::traits::IntegrityTest
 			for #pallet_ident<#type_use_gen> #where_clause
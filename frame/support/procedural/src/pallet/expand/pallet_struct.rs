@@ -24,6 +24,7 @@ use crate::pallet::{Def, parse::helper::get_doc_literals};
 /// * declare Module type alias for construct_runtime
 /// * replace the first field type of `struct Pallet` with `PhantomData` if it is `_`
 /// * implementation of `PalletInfoAccess` information
+/// * implementation of `StorageInfoTrait` on Pallet
 pub fn expand_pallet_struct(def: &mut Def) -> proc_macro2::TokenStream {
 	let frame_support = &def.frame_support;
 	let frame_system = &def.frame_system;
@@ -97,9 +98,69 @@ pub fn expand_pallet_struct(def: &mut Def) -> proc_macro2::TokenStream {
 		)
 	};
 
+	let storage_info_span = def.pallet_struct.generate_storage_info
+		.unwrap_or(def.pallet_struct.attr_span);
+
+	let storage_names = &def.storages.iter().map(|storage| &storage.ident).collect::<Vec<_>>();
+	let storage_cfg_attrs = &def.storages.iter()
+		.map(|storage| &storage.cfg_attrs)
+		.collect::<Vec<_>>();
+
+	// Unless the pallet opts in with `#[pallet::generate_storage_info]`, or the storage is marked
+	// `#[pallet::unbounded]`, the storages are not required to have a max encoded len and the
+	// partial storage info is used.
+	let storage_info_traits = &def.storages.iter()
+		.map(|storage| if def.pallet_struct.generate_storage_info.is_some() && !storage.unbounded {
+			quote::quote_spanned!(storage_info_span => StorageInfoTrait)
+		} else {
+			quote::quote_spanned!(storage_info_span => PartialStorageInfoTrait)
+		})
+		.collect::<Vec<_>>();
+
+	let storage_info_methods = &def.storages.iter()
+		.map(|storage| if def.pallet_struct.generate_storage_info.is_some() && !storage.unbounded {
+			quote::quote_spanned!(storage_info_span => storage_info)
+		} else {
+			quote::quote_spanned!(storage_info_span => partial_storage_info)
+		})
+		.collect::<Vec<_>>();
+
+	let mut where_clauses = vec![&def.config.where_clause];
+	where_clauses.extend(def.storages.iter().map(|storage| &storage.where_clause));
+	let storages_where_clauses = super::merge_where_clauses(&where_clauses);
+
+	let storage_info = quote::quote_spanned!(storage_info_span =>
+		impl<#type_impl_gen> #frame_support::traits::StorageInfoTrait
+			for #pallet_ident<#type_use_gen>
+			#storages_where_clauses
+		{
+			fn storage_info()
+				-> #frame_support::sp_std::vec::Vec<#frame_support::traits::StorageInfo>
+			{
+				#[allow(unused_mut)]
+				let mut res = #frame_support::sp_std::vec![];
+
+				#(
+					#(#storage_cfg_attrs)*
+					{
+						let mut storage_info = <
+							#storage_names<#type_use_gen>
+							as #frame_support::traits::#storage_info_traits
+						>::#storage_info_methods();
+						res.append(&mut storage_info);
+					}
+				)*
+
+				res
+			}
+		}
+	);
+
 	quote::quote_spanned!(def.pallet_struct.attr_span =>
 		#module_error_metadata
 
+		#storage_info
+
 		/// Type alias to `Pallet`, to be used by `construct_runtime`.
 		///
 		/// Generated by `pallet` attribute macro.
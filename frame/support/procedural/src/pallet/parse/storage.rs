@@ -24,13 +24,17 @@ mod keyword {
 	syn::custom_keyword!(Error);
 	syn::custom_keyword!(pallet);
 	syn::custom_keyword!(getter);
+	syn::custom_keyword!(unbounded);
 	syn::custom_keyword!(OptionQuery);
 	syn::custom_keyword!(ValueQuery);
 }
 
-/// Parse for `#[pallet::getter(fn dummy)]`
-pub struct PalletStorageAttr {
-	getter: syn::Ident,
+/// Parse for one of:
+/// * `#[pallet::getter(fn dummy)]`
+/// * `#[pallet::unbounded]`
+pub enum PalletStorageAttr {
+	Getter(syn::Ident),
+	Unbounded(proc_macro2::Span),
 }
 
 impl syn::parse::Parse for PalletStorageAttr {
@@ -40,12 +44,21 @@ impl syn::parse::Parse for PalletStorageAttr {
 		syn::bracketed!(content in input);
 		content.parse::<keyword::pallet>()?;
 		content.parse::<syn::Token![::]>()?;
-		content.parse::<keyword::getter>()?;
 
-		let generate_content;
-		syn::parenthesized!(generate_content in content);
-		generate_content.parse::<syn::Token![fn]>()?;
-		Ok(Self { getter: generate_content.parse::<syn::Ident>()? })
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::getter) {
+			content.parse::<keyword::getter>()?;
+
+			let generate_content;
+			syn::parenthesized!(generate_content in content);
+			generate_content.parse::<syn::Token![fn]>()?;
+			Ok(Self::Getter(generate_content.parse::<syn::Ident>()?))
+		} else if lookahead.peek(keyword::unbounded) {
+			let span = content.parse::<keyword::unbounded>()?.span();
+			Ok(Self::Unbounded(span))
+		} else {
+			Err(lookahead.error())
+		}
 	}
 }
 
@@ -93,6 +106,9 @@ pub struct StorageDef {
 	pub attr_span: proc_macro2::Span,
 	/// The `cfg` attributes.
 	pub cfg_attrs: Vec<syn::Attribute>,
+	/// Whether the storage is excluded from the max encoded len requirement of
+	/// `#[pallet::generate_storage_info]`.
+	pub unbounded: bool,
 }
 
 /// In `Foo<A, B, C>` retrieve the argument at given position, i.e. A is argument at position 0.
@@ -127,12 +143,29 @@ impl StorageDef {
 			return Err(syn::Error::new(item.span(), "Invalid pallet::storage, expected item type"));
 		};
 
-		let mut attrs: Vec<PalletStorageAttr> = helper::take_item_pallet_attrs(&mut item.attrs)?;
-		if attrs.len() > 1 {
-			let msg = "Invalid pallet::storage, multiple argument pallet::getter found";
-			return Err(syn::Error::new(attrs[1].getter.span(), msg));
+		let mut getter = None;
+		let mut unbounded = false;
+
+		let attrs: Vec<PalletStorageAttr> = helper::take_item_pallet_attrs(&mut item.attrs)?;
+		for attr in attrs {
+			match attr {
+				PalletStorageAttr::Getter(ident) => {
+					if getter.is_some() {
+						let msg = "Invalid pallet::storage, multiple argument pallet::getter found";
+						return Err(syn::Error::new(ident.span(), msg));
+					}
+					getter = Some(ident);
+				},
+				PalletStorageAttr::Unbounded(span) => {
+					if unbounded {
+						let msg = "Invalid pallet::storage, multiple argument pallet::unbounded \
+							found";
+						return Err(syn::Error::new(span, msg));
+					}
+					unbounded = true;
+				},
+			}
 		}
-		let getter = attrs.pop().map(|attr| attr.getter);
 
 		let cfg_attrs = helper::get_item_cfg_attrs(&item.attrs);
 
@@ -228,6 +261,7 @@ impl StorageDef {
 			query_kind,
 			where_clause,
 			cfg_attrs,
+			unbounded,
 		})
 	}
 }
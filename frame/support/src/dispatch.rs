@@ -1903,6 +1903,11 @@ macro_rules! decl_module {
 			{ $( $other_where_bounds )* }
 			$( $offchain )*
 		}
+		impl<$trait_instance: $system::Config + $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::TryState<<$trait_instance as $system::Config>::BlockNumber>
+			for $mod_type<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{}
+
 		$crate::decl_module! {
 			@impl_deposit_event
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
@@ -442,6 +442,81 @@ macro_rules! parameter_types_impl_thread_local {
 	};
 }
 
+/// Create parameter types whose values can be updated at runtime, together with an enum of all
+/// of them.
+///
+/// Every parameter is declared like a `storage` parameter of [`parameter_types`]: its value is
+/// read from the storage and falls back to the given default. The generated enum has a variant
+/// per parameter holding a value for it, and implements
+/// [`DynamicParameters`](traits::DynamicParameters), so that a pallet like `pallet-parameters`
+/// can update any of the parameters with a single call.
+///
+/// # Example
+///
+/// ```
+/// # use frame_support::traits::{DynamicParameters, Get};
+/// # use frame_support::dynamic_params;
+/// dynamic_params! {
+///    pub enum RuntimeParameters {
+///       /// Deposit for every byte stored.
+///       pub ByteDeposit: u64 = 10;
+///       pub MaxItems: u32 = 100;
+///    }
+/// }
+///
+/// sp_io::TestExternalities::default().execute_with(|| {
+///    assert_eq!(<ByteDeposit as Get<u64>>::get(), 10);
+///    RuntimeParameters::ByteDeposit(20).set();
+///    assert_eq!(<ByteDeposit as Get<u64>>::get(), 20);
+///    assert_eq!(
+///       RuntimeParameters::current(),
+///       vec![RuntimeParameters::ByteDeposit(20), RuntimeParameters::MaxItems(100)],
+///    );
+/// });
+/// ```
+#[macro_export]
+macro_rules! dynamic_params {
+	(
+		$( #[ $enum_attr:meta ] )*
+		$enum_vis:vis enum $enum_name:ident {
+			$(
+				$( #[ $attr:meta ] )*
+				$vis:vis $name:ident: $type:ty = $value:expr;
+			)*
+		}
+	) => {
+		$crate::parameter_types! {
+			$(
+				$( #[ $attr ] )*
+				$vis storage $name: $type = $value;
+			)*
+		}
+
+		$( #[ $enum_attr ] )*
+		#[derive(
+			Clone, PartialEq, Eq, $crate::codec::Encode, $crate::codec::Decode, $crate::RuntimeDebug,
+		)]
+		$enum_vis enum $enum_name {
+			$(
+				$( #[ $attr ] )*
+				$name($type),
+			)*
+		}
+
+		impl $crate::traits::DynamicParameters for $enum_name {
+			fn set(self) {
+				match self {
+					$( Self::$name(value) => $name::set(&value), )*
+				}
+			}
+
+			fn current() -> $crate::sp_std::vec::Vec<Self> {
+				$crate::sp_std::vec![ $( Self::$name($name::get()), )* ]
+			}
+		}
+	};
+}
+
 /// Macro for easily creating a new implementation of both the `Get` and `Contains` traits. Use
 /// exactly as with `parameter_types`, only the type must be `Ord`.
 #[macro_export]
@@ -593,6 +668,37 @@ pub use frame_support_procedural::DebugNoBound;
 /// ```
 pub use frame_support_procedural::DefaultNoBound;
 
+/// Derive [`MaxEncodedLen`](traits::MaxEncodedLen).
+///
+/// The maximum encoded length of a struct is the sum of the one of its fields, and the one of an
+/// enum is the maximum of its variants plus one byte for the variant index. Fields annotated with
+/// `#[codec(skip)]` are ignored and fields annotated with `#[codec(compact)]` are counted with
+/// their compact encoding.
+///
+/// A bound is added for the type of every field using a generic parameter:
+/// ```
+/// # use frame_support::{MaxEncodedLen, traits::MaxEncodedLen};
+/// # use codec::Encode;
+/// trait Config {
+///		type Balance: Encode;
+/// }
+///
+/// #[derive(Encode, MaxEncodedLen)]
+/// struct Deposit<T: Config> {
+///		#[codec(compact)]
+///		amount: T::Balance,
+///		reserved: bool,
+/// }
+///
+/// struct Runtime;
+/// impl Config for Runtime {
+///		type Balance = u64;
+/// }
+///
+/// assert_eq!(Deposit::<Runtime>::max_encoded_len(), 9 + 1);
+/// ```
+pub use frame_support_procedural::MaxEncodedLen;
+
 /// Assert the annotated function is executed within a storage transaction.
 ///
 /// The assertion is enabled for native execution and when `debug_assertions` are enabled.
@@ -661,6 +767,22 @@ macro_rules! ensure {
 	}}
 }
 
+/// Report a defensive failure, i.e. that a branch which should never be reached was reached.
+///
+/// Panics in tests, or if the `defensive` feature of `frame-support` is enabled, and logs an error
+/// otherwise. The code after it is the fallback path taken in production.
+///
+/// Used as `defensive!()` or `defensive!(error)`, where `error` implements `Debug`.
+#[macro_export]
+macro_rules! defensive {
+	() => {
+		$crate::traits::defensive_failure("unreachable branch reached")
+	};
+	( $error:expr $(,)? ) => {
+		$crate::traits::defensive_failure($error)
+	};
+}
+
 /// Evaluate an expression, assert it returns an expected `Err` value and that
 /// runtime storage has not been mutated (i.e. expression is a no-operation).
 ///
@@ -1342,6 +1464,18 @@ pub mod pallet_prelude {
 /// Thus when defining a storage named `Foo`, it can later be accessed from `Pallet` using
 /// `<Pallet as Store>::Foo`.
 ///
+/// To generate the full storage info (used for PoV calculation) use the attribute
+/// `#[pallet::generate_storage_info]`, e.g.:
+/// ```ignore
+/// #[pallet::pallet]
+/// #[pallet::generate_storage_info]
+/// pub struct Pallet<T>(_);
+/// ```
+///
+/// This require all storage to implement the trait [`traits::StorageInfoTrait`], thus all keys
+/// and value types must bound [`traits::MaxEncodedLen`]. A storage can be excluded from this
+/// requirement with `#[pallet::unbounded]`, its size is then left unspecified.
+///
 /// ### Macro expansion:
 ///
 /// The macro add this attribute to the struct definition:
@@ -1368,6 +1502,10 @@ pub mod pallet_prelude {
 ///
 /// If attribute generate_store then macro create the trait `Store` and implement it on `Pallet`.
 ///
+/// It implements [`traits::StorageInfoTrait`] on `Pallet` which give information about all
+/// storages. If the attribute generate_storage_info is used then the information includes the max
+/// size of the storages, otherwise it is only partial, see [`traits::PartialStorageInfoTrait`].
+///
 /// # Hooks: `#[pallet::hooks]` mandatory
 ///
 /// Implementation of `Hooks` on `Pallet` allowing to define some specific pallet logic.
@@ -1560,6 +1698,7 @@ pub mod pallet_prelude {
 /// ```ignore
 /// #[pallet::storage]
 /// #[pallet::getter(fn $getter_name)] // optional
+/// #[pallet::unbounded] // optional
 /// $vis type $StorageName<$some_generic> $optional_where_clause
 /// 	= $StorageType<_, $some_generics, ...>;
 /// ```
@@ -1582,6 +1721,16 @@ pub mod pallet_prelude {
 /// pub(super) type MyStorage<T> = StorageMap<_, Blake2_128Concat, u32, u32>;
 /// ```
 ///
+/// The optional attribute `#[pallet::unbounded]` exempts the storage from the max encoded len
+/// requirement of `#[pallet::generate_storage_info]`.
+///
+/// E.g:
+/// ```ignore
+/// #[pallet::storage]
+/// #[pallet::unbounded]
+/// pub(super) type MyStorage<T> = StorageValue<_, Vec<u8>>;
+/// ```
+///
 /// The optional attributes `#[cfg(..)]` allow conditional compilation for the storage.
 ///
 /// E.g:
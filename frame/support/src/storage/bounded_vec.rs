@@ -22,7 +22,7 @@ use sp_std::prelude::*;
 use sp_std::{convert::TryFrom, marker::PhantomData};
 use codec::{FullCodec, Encode, EncodeLike, Decode};
 use crate::{
-	traits::Get,
+	traits::{Get, MaxEncodedLen},
 	storage::{generator, StorageDecodeLength, StorageValue, StorageMap, StorageDoubleMap},
 };
 
@@ -214,6 +214,19 @@ impl<T: BoundedVecValue, S: Get<u32>> codec::DecodeLength for BoundedVec<T, S> {
 
 impl<T: BoundedVecValue, S: Get<u32>> StorageDecodeLength for BoundedVec<T, S> {}
 
+impl<T: BoundedVecValue + MaxEncodedLen, S: Get<u32>> MaxEncodedLen for BoundedVec<T, S>
+where
+	BoundedVec<T, S>: Encode,
+{
+	fn max_encoded_len() -> usize {
+		// `BoundedVec<T, _>` is encoded as a `Vec<T>`: the length in `Compact` form followed by the
+		// items.
+		codec::Compact(S::get())
+			.encoded_size()
+			.saturating_add(Self::bound().saturating_mul(T::max_encoded_len()))
+	}
+}
+
 /// Storage value that is *maybe* capable of [`StorageAppend`](crate::storage::StorageAppend).
 pub trait TryAppendValue<T: BoundedVecValue, S: Get<u32>> {
 	/// Try and append the `item` into the storage item.
@@ -338,6 +351,13 @@ pub mod test {
 		FooDoubleMap => DoubleMap<(u32, Twox128), (u32, Twox128), BoundedVec<u32, Seven>>
 	}
 
+	#[test]
+	fn max_encoded_len_works() {
+		let full: BoundedVec<u32, Seven> = vec![u32::max_value(); 7].try_into().unwrap();
+		assert_eq!(BoundedVec::<u32, Seven>::max_encoded_len(), 1 + 7 * 4);
+		assert_eq!(full.encode().len(), BoundedVec::<u32, Seven>::max_encoded_len());
+	}
+
 	#[test]
 	fn decode_len_works() {
 		TestExternalities::default().execute_with(|| {
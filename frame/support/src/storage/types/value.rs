@@ -24,8 +24,10 @@ use crate::{
 		bounded_vec::{BoundedVec, BoundedVecValue},
 		types::{OptionQuery, QueryKindTrait, OnEmptyGetter},
 	},
-	traits::{GetDefault, StorageInstance, Get},
+	traits::{GetDefault, StorageInstance, Get, MaxEncodedLen, StorageInfo},
 };
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
 use frame_metadata::{DefaultByteGetter, StorageEntryModifier};
 
 /// A type that allow to store a value.
@@ -212,6 +214,49 @@ impl<Prefix, Value, QueryKind, OnEmpty> StorageValueMetadata
 		DefaultByteGetter(&OnEmptyGetter::<QueryKind::Query, OnEmpty>(core::marker::PhantomData));
 }
 
+impl<Prefix, Value, QueryKind, OnEmpty> crate::traits::StorageInfoTrait
+	for StorageValue<Prefix, Value, QueryKind, OnEmpty>
+where
+	Prefix: StorageInstance,
+	Value: FullCodec + MaxEncodedLen,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: crate::traits::Get<QueryKind::Query> + 'static,
+{
+	fn storage_info() -> Vec<StorageInfo> {
+		sp_std::vec![
+			StorageInfo {
+				pallet_name: Prefix::pallet_prefix().as_bytes().to_vec(),
+				storage_name: Prefix::STORAGE_PREFIX.as_bytes().to_vec(),
+				prefix: Self::hashed_key().to_vec(),
+				max_values: Some(1),
+				max_size: Some(Value::max_encoded_len().saturated_into()),
+			}
+		]
+	}
+}
+
+/// It doesn't require to implement `MaxEncodedLen` and give no information for `max_size`.
+impl<Prefix, Value, QueryKind, OnEmpty> crate::traits::PartialStorageInfoTrait
+	for StorageValue<Prefix, Value, QueryKind, OnEmpty>
+where
+	Prefix: StorageInstance,
+	Value: FullCodec,
+	QueryKind: QueryKindTrait<Value, OnEmpty>,
+	OnEmpty: crate::traits::Get<QueryKind::Query> + 'static,
+{
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		sp_std::vec![
+			StorageInfo {
+				pallet_name: Prefix::pallet_prefix().as_bytes().to_vec(),
+				storage_name: Prefix::STORAGE_PREFIX.as_bytes().to_vec(),
+				prefix: Self::hashed_key().to_vec(),
+				max_values: Some(1),
+				max_size: None,
+			}
+		]
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -22,6 +22,8 @@
 pub mod tokens;
 pub use tokens::fungible;
 pub use tokens::fungibles;
+pub use tokens::nonfungible;
+pub use tokens::nonfungibles;
 pub use tokens::currency::{
 	Currency, LockIdentifier, LockableCurrency, ReservableCurrency, VestingSchedule,
 };
@@ -38,7 +40,7 @@ mod validation;
 pub use validation::{
 	ValidatorSet, ValidatorSetWithIdentification, OneSessionHandler, FindAuthor, VerifySeal,
 	EstimateNextNewSession, EstimateNextSessionRotation, KeyOwnerProofSystem, ValidatorRegistration,
-	Lateness,
+	Lateness, DisabledValidators,
 };
 
 mod filter;
@@ -50,7 +52,8 @@ mod misc;
 pub use misc::{
 	Len, Get, GetDefault, HandleLifetime, TryDrop, Time, UnixTime, IsType, IsSubType, ExecuteBlock,
 	SameOrOther, OnNewAccount, OnKilledAccount, OffchainWorker, GetBacking, Backing, ExtrinsicCall,
-	EnsureInherentsAreFirst,
+	EnsureInherentsAreFirst, Defensive, DefensiveOption, DefensiveSaturating, defensive_failure,
+	DEFENSIVE_OP_PUBLIC_ERROR, DEFENSIVE_OP_INTERNAL_ERROR, DynamicParameters,
 };
 
 mod stored_map;
@@ -65,7 +68,9 @@ pub use metadata::{
 };
 
 mod hooks;
-pub use hooks::{Hooks, OnGenesis, OnInitialize, OnFinalize, OnIdle, OnRuntimeUpgrade, OnTimestampSet};
+pub use hooks::{
+	Hooks, OnGenesis, OnInitialize, OnFinalize, OnIdle, OnRuntimeUpgrade, OnTimestampSet, TryState,
+};
 #[cfg(feature = "try-runtime")]
 pub use hooks::{OnRuntimeUpgradeHelpersExt, ON_RUNTIME_UPGRADE_PREFIX};
 #[cfg(feature = "std")]
@@ -73,10 +78,23 @@ pub use hooks::GenesisBuild;
 
 pub mod schedule;
 mod storage;
-pub use storage::{Instance, StorageInstance};
+pub use storage::{
+	Instance, StorageInstance, StorageInfo, StorageInfoTrait, PartialStorageInfoTrait,
+};
 
 mod dispatch;
-pub use dispatch::{EnsureOrigin, OriginTrait, UnfilteredDispatchable};
+pub use dispatch::{
+	EnsureOrigin, EnsureOriginWithArg, AsEnsureOriginWithArg, MapSuccess, TryMapSuccess, EitherOf,
+	EitherOfDiverse, OriginTrait, UnfilteredDispatchable, PrivilegeCmp, EqualPrivilegeOnly,
+};
+
+mod max_encoded_len;
+pub use max_encoded_len::MaxEncodedLen;
+
+pub mod preimages;
+pub use preimages::{Bounded, QueryPreimage, StorePreimage};
 
 mod voting;
-pub use voting::{CurrencyToVote, SaturatingCurrencyToVote, U128CurrencyToVote};
+pub use voting::{
+	CurrencyToVote, SaturatingCurrencyToVote, U128CurrencyToVote, VoteTally, PollStatus, Polling,
+};
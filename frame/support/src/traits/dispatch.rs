@@ -17,8 +17,9 @@
 
 //! Traits for dealing with dispatching calls and the origin from which they are dispatched.
 
-use crate::dispatch::DispatchResultWithPostInfo;
-use sp_runtime::traits::BadOrigin;
+use crate::dispatch::{DispatchResultWithPostInfo, Parameter};
+use sp_runtime::{traits::{BadOrigin, Morph, TryMorph}, Either};
+use sp_std::{cmp::Ordering, marker::PhantomData};
 
 /// Some sort of check on the origin is performed by this object.
 pub trait EnsureOrigin<OuterOrigin> {
@@ -38,6 +39,140 @@ pub trait EnsureOrigin<OuterOrigin> {
 	fn successful_origin() -> OuterOrigin;
 }
 
+/// Some sort of check on the origin is performed by this object, which may depend on an argument
+/// of the call, e.g. the asset or the track the call is about.
+pub trait EnsureOriginWithArg<OuterOrigin, Argument> {
+	/// A return type.
+	type Success;
+	/// Perform the origin check.
+	fn ensure_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, BadOrigin> {
+		Self::try_origin(o, a).map_err(|_| BadOrigin)
+	}
+	/// Perform the origin check.
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin>;
+
+	/// Returns an outer origin capable of passing `try_origin` check.
+	///
+	/// ** Should be used for benchmarking only!!! **
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(a: &Argument) -> OuterOrigin;
+}
+
+/// Implementation of [`EnsureOriginWithArg`] which ignores the argument and performs the check of
+/// the [`EnsureOrigin`] `EO`.
+pub struct AsEnsureOriginWithArg<EO>(PhantomData<EO>);
+impl<OuterOrigin, Argument, EO: EnsureOrigin<OuterOrigin>>
+	EnsureOriginWithArg<OuterOrigin, Argument> for AsEnsureOriginWithArg<EO>
+{
+	type Success = EO::Success;
+	fn try_origin(o: OuterOrigin, _: &Argument) -> Result<Self::Success, OuterOrigin> {
+		EO::try_origin(o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin(_: &Argument) -> OuterOrigin {
+		EO::successful_origin()
+	}
+}
+
+/// Implementation of [`EnsureOrigin`] which performs the check of `Original` and converts its
+/// success value with `Mutator`.
+pub struct MapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<
+	O,
+	Original: EnsureOrigin<O>,
+	Mutator: Morph<Original::Success>,
+> EnsureOrigin<O> for MapSuccess<Original, Mutator> {
+	type Success = Mutator::Outcome;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		Original::try_origin(o).map(Mutator::morph)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		Original::successful_origin()
+	}
+}
+
+/// Implementation of [`EnsureOrigin`] which performs the check of `Original` and converts its
+/// success value with `Mutator`.
+///
+/// The check fails if `Mutator` cannot convert the success value.
+pub struct TryMapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<
+	O: Clone,
+	Original: EnsureOrigin<O>,
+	Mutator: TryMorph<Original::Success>,
+> EnsureOrigin<O> for TryMapSuccess<Original, Mutator> {
+	type Success = Mutator::Outcome;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		let original = o.clone();
+		Mutator::try_morph(Original::try_origin(o)?).map_err(|()| original)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		Original::successful_origin()
+	}
+}
+
+/// The "OR gate" implementation of [`EnsureOrigin`] for checks with the same success type.
+///
+/// Origin check will pass if `L` or `R` origin check passes. `L` is tested first.
+pub struct EitherOf<L, R>(PhantomData<(L, R)>);
+impl<
+	O,
+	L: EnsureOrigin<O>,
+	R: EnsureOrigin<O, Success = L::Success>,
+> EnsureOrigin<O> for EitherOf<L, R> {
+	type Success = L::Success;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		L::try_origin(o).or_else(|o| R::try_origin(o))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		L::successful_origin()
+	}
+}
+
+/// The "OR gate" implementation of [`EnsureOrigin`] for checks with different success types.
+///
+/// Origin check will pass if `L` or `R` origin check passes. `L` is tested first.
+pub struct EitherOfDiverse<L, R>(PhantomData<(L, R)>);
+impl<O, L: EnsureOrigin<O>, R: EnsureOrigin<O>> EnsureOrigin<O> for EitherOfDiverse<L, R> {
+	type Success = Either<L::Success, R::Success>;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		L::try_origin(o).map(Either::Left).or_else(|o| R::try_origin(o).map(Either::Right))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		L::successful_origin()
+	}
+}
+
+/// Some sort of check on two origins to determine which has the higher privilege.
+///
+/// This is used, for example, to decide whether an origin may cancel or alter an operation which
+/// was initiated by another origin.
+pub trait PrivilegeCmp<Origin> {
+	/// Compare the privileges of `left` and `right`.
+	///
+	/// Return `None` if the origins are not comparable, otherwise the ordering of the privilege
+	/// of `left` relative to the privilege of `right`.
+	fn cmp_privilege(left: &Origin, right: &Origin) -> Option<Ordering>;
+}
+
+/// Implementation of [`PrivilegeCmp`] that only considers equal origins as having the same
+/// privilege, and all other pairs of origins as not comparable.
+pub struct EqualPrivilegeOnly;
+impl<Origin: PartialEq> PrivilegeCmp<Origin> for EqualPrivilegeOnly {
+	fn cmp_privilege(left: &Origin, right: &Origin) -> Option<Ordering> {
+		if left == right { Some(Ordering::Equal) } else { None }
+	}
+}
+
 /// Type that can be dispatched with an origin but without checking the origin filter.
 ///
 /// Implemented for pallet dispatchable type by `decl_module` and for runtime dispatchable by
@@ -56,7 +191,7 @@ pub trait OriginTrait: Sized {
 	type Call;
 
 	/// The caller origin, overarching type of all pallets origins.
-	type PalletsOrigin;
+	type PalletsOrigin: Parameter;
 
 	/// The AccountId used across the system.
 	type AccountId;
@@ -91,3 +226,72 @@ pub trait OriginTrait: Sized {
 	/// Create with system signed origin and `frame-system::Config::BaseCallFilter`.
 	fn signed(by: Self::AccountId) -> Self;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	macro_rules! ensure_origin {
+		($name:ident, $result:expr) => {
+			struct $name;
+			impl EnsureOrigin<()> for $name {
+				type Success = u64;
+				fn try_origin(o: ()) -> Result<u64, ()> {
+					$result.ok_or(o)
+				}
+				#[cfg(feature = "runtime-benchmarks")]
+				fn successful_origin() {}
+			}
+		};
+	}
+
+	ensure_origin!(EnsureZero, Some(0));
+	ensure_origin!(EnsureOne, Some(1));
+	ensure_origin!(EnsureTwo, Some(2));
+	ensure_origin!(EnsureFail, None);
+
+	struct Double;
+	impl Morph<u64> for Double {
+		type Outcome = u64;
+		fn morph(v: u64) -> u64 {
+			v * 2
+		}
+	}
+
+	struct NonZero;
+	impl TryMorph<u64> for NonZero {
+		type Outcome = u64;
+		fn try_morph(v: u64) -> Result<u64, ()> {
+			if v == 0 { Err(()) } else { Ok(v) }
+		}
+	}
+
+	#[test]
+	fn either_of_works() {
+		assert_eq!(EitherOf::<EnsureOne, EnsureTwo>::try_origin(()), Ok(1));
+		assert_eq!(EitherOf::<EnsureFail, EnsureTwo>::try_origin(()), Ok(2));
+		assert_eq!(EitherOf::<EnsureFail, EnsureFail>::try_origin(()), Err(()));
+
+		assert_eq!(
+			EitherOfDiverse::<EnsureFail, EnsureTwo>::try_origin(()),
+			Ok(Either::Right(2)),
+		);
+		assert_eq!(EitherOfDiverse::<EnsureFail, EnsureFail>::try_origin(()), Err(()));
+	}
+
+	#[test]
+	fn map_success_works() {
+		assert_eq!(MapSuccess::<EnsureOne, Double>::try_origin(()), Ok(2));
+		assert_eq!(MapSuccess::<EnsureFail, Double>::try_origin(()), Err(()));
+
+		assert_eq!(TryMapSuccess::<EnsureOne, NonZero>::try_origin(()), Ok(1));
+		assert_eq!(TryMapSuccess::<EnsureZero, NonZero>::try_origin(()), Err(()));
+		assert_eq!(TryMapSuccess::<EnsureFail, NonZero>::try_origin(()), Err(()));
+	}
+
+	#[test]
+	fn as_ensure_origin_with_arg_works() {
+		assert_eq!(AsEnsureOriginWithArg::<EnsureOne>::try_origin((), &42u32), Ok(1));
+		assert_eq!(AsEnsureOriginWithArg::<EnsureFail>::try_origin((), &42u32), Err(()));
+	}
+}
@@ -202,6 +202,27 @@ impl OnRuntimeUpgrade for Tuple {
 	}
 }
 
+/// The state invariants check trait.
+///
+/// Implementing this lets you express the invariants of your pallet's storage that must hold at
+/// the end of every block. It is implemented for every pallet, by default as a no-op.
+///
+/// This hook is never meant to be executed on-chain but is meant to be used by testing tools,
+/// e.g. `try-runtime` or the unit tests of a pallet.
+pub trait TryState<BlockNumber> {
+	/// Check the state invariants of the pallet at block `n`.
+	fn try_state(_n: BlockNumber) -> Result<(), &'static str> { Ok(()) }
+}
+
+#[impl_for_tuples(30)]
+impl<BlockNumber: Clone> TryState<BlockNumber> for Tuple {
+	fn try_state(n: BlockNumber) -> Result<(), &'static str> {
+		let mut result = Ok(());
+		for_tuples!( #( result = result.and(Tuple::try_state(n.clone())); )* );
+		result
+	}
+}
+
 /// The pallet hooks trait. Implementing this lets you express some logic to execute.
 pub trait Hooks<BlockNumber> {
 	/// The block is being finalized. Implement to have something happen.
@@ -256,6 +277,15 @@ pub trait Hooks<BlockNumber> {
 		Ok(())
 	}
 
+	/// Check the state invariants of the pallet at block `n`.
+	///
+	/// Return an error if any invariant is broken. This hook is never meant to be executed
+	/// on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn try_state(_n: BlockNumber) -> Result<(), &'static str> {
+		Ok(())
+	}
+
 	/// Implementing this function on a module allows you to perform long-running tasks
 	/// that make (by default) validators generate transactions that feed results
 	/// of those long-running computations back on chain.
@@ -333,6 +363,22 @@ mod tests {
 		assert_eq!(<(Test, Test)>::on_runtime_upgrade(), 40);
 	}
 
+	#[test]
+	fn try_state_aggregates_all_results() {
+		struct Sound;
+		impl TryState<u8> for Sound {}
+		struct Broken;
+		impl TryState<u8> for Broken {
+			fn try_state(n: u8) -> Result<(), &'static str> {
+				if n > 1 { Err("broken invariant") } else { Ok(()) }
+			}
+		}
+
+		assert_eq!(<(Sound, Sound)>::try_state(5), Ok(()));
+		assert_eq!(<(Sound, Broken)>::try_state(1), Ok(()));
+		assert_eq!(<(Broken, Sound)>::try_state(2), Err("broken invariant"));
+	}
+
 	#[test]
 	fn check_pallet_version_ordering() {
 		let version = PalletVersion::new(1, 0, 0);
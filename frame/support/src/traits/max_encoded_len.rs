@@ -0,0 +1,167 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `trait MaxEncodedLen` bounds the max encoded length of items.
+
+use codec::{Compact, Encode};
+use impl_trait_for_tuples::impl_for_tuples;
+use sp_std::{mem, marker::PhantomData, boxed::Box};
+use sp_core::{H160, H256, H512};
+use sp_runtime::AccountId32;
+use sp_arithmetic::{Percent, PerU16, Permill, Perbill, Perquintill};
+
+/// Items implementing `MaxEncodedLen` have a statically known maximum encoded size.
+///
+/// Some containers, such as `BoundedVec`, have enforced size limits and this trait
+/// can be implemented accurately. Other containers, such as `Vec` or `BTreeMap`, are unbounded and
+/// don't implement it.
+///
+/// It can be derived for structs and enums whose fields all implement it, see
+/// [`frame_support::MaxEncodedLen`](crate::MaxEncodedLen).
+pub trait MaxEncodedLen: Encode {
+	/// Upper bound, in bytes, of the maximum encoded size of this item.
+	fn max_encoded_len() -> usize;
+}
+
+macro_rules! impl_primitives {
+	( $($t:ty),+ ) => {
+		$(
+			impl MaxEncodedLen for $t {
+				fn max_encoded_len() -> usize {
+					mem::size_of::<$t>()
+				}
+			}
+		)+
+	};
+}
+
+impl_primitives!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, bool);
+impl_primitives!(H160, H256, H512, AccountId32);
+
+macro_rules! impl_compact {
+	($( $t:ty => $e:expr; )*) => {
+		$(
+			impl MaxEncodedLen for Compact<$t> {
+				fn max_encoded_len() -> usize {
+					$e
+				}
+			}
+		)*
+	};
+}
+
+impl_compact!(
+	// The compact encoding uses up to two bits of the first byte to store the mode, and in the
+	// "big integer" mode the whole value follows a one byte prefix.
+	u8 => 2;
+	u16 => 4;
+	u32 => 5;
+	u64 => 9;
+	u128 => 17;
+);
+
+macro_rules! impl_per_things {
+	( $($t:ty => $inner:ty),+ ) => {
+		$(
+			impl MaxEncodedLen for $t {
+				fn max_encoded_len() -> usize {
+					<$inner>::max_encoded_len()
+				}
+			}
+		)+
+	};
+}
+
+impl_per_things!(Percent => u8, PerU16 => u16, Permill => u32, Perbill => u32, Perquintill => u64);
+
+// The encoding of a fixed size array is the concatenation of the encoding of its items, there is
+// no length prefix.
+impl<T: MaxEncodedLen, const N: usize> MaxEncodedLen for [T; N] {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len().saturating_mul(N)
+	}
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for Box<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len()
+	}
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for Option<T> {
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len().saturating_add(1)
+	}
+}
+
+impl<T, E> MaxEncodedLen for Result<T, E>
+where
+	T: MaxEncodedLen,
+	E: MaxEncodedLen,
+{
+	fn max_encoded_len() -> usize {
+		T::max_encoded_len().max(E::max_encoded_len()).saturating_add(1)
+	}
+}
+
+impl<T> MaxEncodedLen for PhantomData<T> {
+	fn max_encoded_len() -> usize {
+		0
+	}
+}
+
+#[impl_for_tuples(18)]
+impl MaxEncodedLen for Tuple {
+	fn max_encoded_len() -> usize {
+		let mut len: usize = 0;
+		for_tuples!( #( len = len.saturating_add(Tuple::max_encoded_len()); )* );
+		len
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	macro_rules! test_compact_length {
+		($(fn $name:ident($t:ty);)*) => {
+			$(
+				#[test]
+				fn $name() {
+					assert_eq!(Compact(<$t>::MAX).encode().len(), Compact::<$t>::max_encoded_len());
+				}
+			)*
+		};
+	}
+
+	test_compact_length!(
+		compact_u8(u8);
+		compact_u16(u16);
+		compact_u32(u32);
+		compact_u64(u64);
+		compact_u128(u128);
+	);
+
+	#[test]
+	fn collections_max_encoded_len() {
+		assert_eq!(<[u16; 4]>::max_encoded_len(), 8);
+		assert_eq!(Option::<u32>::max_encoded_len(), 5);
+		assert_eq!(Result::<u8, u64>::max_encoded_len(), 9);
+		assert_eq!(<(u8, Option<H256>, ())>::max_encoded_len(), 34);
+		assert_eq!(Perbill::max_encoded_len(), Perbill::one().encode().len());
+	}
+}
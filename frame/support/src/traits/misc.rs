@@ -18,9 +18,171 @@
 //! Smaller traits used in FRAME which don't need their own file.
 
 use sp_runtime::traits::{StoredMapError, Block as BlockT};
-use sp_arithmetic::traits::AtLeast32Bit;
+use sp_arithmetic::traits::{AtLeast32Bit, CheckedAdd, CheckedMul, CheckedSub, Saturating};
+use sp_std::fmt::Debug;
 use crate::dispatch::Parameter;
 
+/// The error message logged when a defensive failure is triggered.
+pub const DEFENSIVE_OP_PUBLIC_ERROR: &str =
+	"a defensive failure has been triggered; please report the block number";
+
+/// The message with which a defensive failure panics in tests or with the `defensive` feature.
+pub const DEFENSIVE_OP_INTERNAL_ERROR: &str = "Defensive failure has been triggered!";
+
+/// Report a defensive failure, i.e. a branch that should never be reached.
+///
+/// Panics if debug assertions or the `defensive` feature of this crate are enabled, so that tests
+/// catch the failure. Otherwise only logs an error, so that the caller can take its fallback path
+/// instead of halting the chain.
+///
+/// Prefer the [`defensive!`](crate::defensive) macro or the `Defensive*` traits over calling this
+/// directly.
+pub fn defensive_failure(error: impl Debug) {
+	log::error!(target: "runtime::defensive", "{}: {:?}", DEFENSIVE_OP_PUBLIC_ERROR, error);
+	if cfg!(any(debug_assertions, feature = "defensive")) {
+		panic!("{}: {:?}", DEFENSIVE_OP_INTERNAL_ERROR, error);
+	}
+}
+
+/// Unwrapping that is defensive: the failure case is reported through [`defensive_failure`] and
+/// a fallback value is used.
+pub trait Defensive<T> {
+	/// Unwrap the value, or report a defensive failure and return `other`.
+	fn defensive_unwrap_or(self, other: T) -> T;
+
+	/// Unwrap the value, or report a defensive failure and return the result of `f`.
+	fn defensive_unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T;
+
+	/// Unwrap the value, or report a defensive failure and return the default value.
+	fn defensive_unwrap_or_default(self) -> T where T: Default;
+
+	/// Report a defensive failure if the value is the failure case, and return it as it is.
+	fn defensive(self) -> Self;
+
+	/// Same as [`Defensive::defensive`], but reports `proof` as the reason why the failure case
+	/// was not expected.
+	fn defensive_proof(self, proof: &'static str) -> Self;
+}
+
+impl<T> Defensive<T> for Option<T> {
+	fn defensive_unwrap_or(self, other: T) -> T {
+		self.defensive_unwrap_or_else(|| other)
+	}
+
+	fn defensive_unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T {
+		self.unwrap_or_else(|| {
+			defensive_failure("unexpected None");
+			f()
+		})
+	}
+
+	fn defensive_unwrap_or_default(self) -> T where T: Default {
+		self.defensive_unwrap_or_else(Default::default)
+	}
+
+	fn defensive(self) -> Self {
+		self.defensive_proof("unexpected None")
+	}
+
+	fn defensive_proof(self, proof: &'static str) -> Self {
+		if self.is_none() {
+			defensive_failure(proof);
+		}
+		self
+	}
+}
+
+impl<T, E: Debug> Defensive<T> for Result<T, E> {
+	fn defensive_unwrap_or(self, other: T) -> T {
+		self.defensive_unwrap_or_else(|| other)
+	}
+
+	fn defensive_unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T {
+		self.unwrap_or_else(|error| {
+			defensive_failure(error);
+			f()
+		})
+	}
+
+	fn defensive_unwrap_or_default(self) -> T where T: Default {
+		self.defensive_unwrap_or_else(Default::default)
+	}
+
+	fn defensive(self) -> Self {
+		self.map_err(|error| {
+			defensive_failure(&error);
+			error
+		})
+	}
+
+	fn defensive_proof(self, proof: &'static str) -> Self {
+		self.map_err(|error| {
+			defensive_failure((proof, &error));
+			error
+		})
+	}
+}
+
+/// Conversions of an `Option` that are defensive: the `None` case is reported through
+/// [`defensive_failure`].
+pub trait DefensiveOption<T> {
+	/// Like [`Option::ok_or`], but reports a defensive failure on `None`.
+	fn defensive_ok_or<E>(self, error: E) -> Result<T, E>;
+
+	/// Like [`Option::ok_or_else`], but reports a defensive failure on `None`.
+	fn defensive_ok_or_else<E, F: FnOnce() -> E>(self, f: F) -> Result<T, E>;
+
+	/// Like [`Option::map_or`], but reports a defensive failure on `None`.
+	fn defensive_map_or<U, F: FnOnce(T) -> U>(self, default: U, f: F) -> U;
+}
+
+impl<T> DefensiveOption<T> for Option<T> {
+	fn defensive_ok_or<E>(self, error: E) -> Result<T, E> {
+		self.defensive_ok_or_else(|| error)
+	}
+
+	fn defensive_ok_or_else<E, F: FnOnce() -> E>(self, f: F) -> Result<T, E> {
+		self.defensive().ok_or_else(f)
+	}
+
+	fn defensive_map_or<U, F: FnOnce(T) -> U>(self, default: U, f: F) -> U {
+		self.defensive().map_or(default, f)
+	}
+}
+
+/// Saturating arithmetic that is defensive: an overflow is reported through
+/// [`defensive_failure`] and the result saturates.
+pub trait DefensiveSaturating {
+	/// Saturating addition, reporting a defensive failure on overflow.
+	fn defensive_saturating_add(self, other: Self) -> Self;
+
+	/// Saturating subtraction, reporting a defensive failure on underflow.
+	fn defensive_saturating_sub(self, other: Self) -> Self;
+
+	/// Saturating multiplication, reporting a defensive failure on overflow.
+	fn defensive_saturating_mul(self, other: Self) -> Self;
+}
+
+impl<T: Saturating + CheckedAdd + CheckedSub + CheckedMul + Copy> DefensiveSaturating for T {
+	fn defensive_saturating_add(self, other: Self) -> Self {
+		self.checked_add(&other)
+			.defensive_proof("addition overflow")
+			.unwrap_or_else(|| self.saturating_add(other))
+	}
+
+	fn defensive_saturating_sub(self, other: Self) -> Self {
+		self.checked_sub(&other)
+			.defensive_proof("subtraction underflow")
+			.unwrap_or_else(|| self.saturating_sub(other))
+	}
+
+	fn defensive_saturating_mul(self, other: Self) -> Self {
+		self.checked_mul(&other)
+			.defensive_proof("multiplication overflow")
+			.unwrap_or_else(|| self.saturating_mul(other))
+	}
+}
+
 /// Anything that can have a `::len()` method.
 pub trait Len {
 	/// Return the length of data type.
@@ -321,3 +483,48 @@ where
 		&self.function
 	}
 }
+
+/// Runtime parameters whose values are kept in storage, as declared with
+/// [`dynamic_params`](crate::dynamic_params).
+///
+/// Every value of the implementing type is one of the parameters together with a value for it.
+pub trait DynamicParameters: Sized {
+	/// Store the value held by `self` as the new value of its parameter.
+	fn set(self);
+
+	/// All the parameters with their current value.
+	fn current() -> sp_std::vec::Vec<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn defensive_passes_through_the_success_case() {
+		assert_eq!(Some(1).defensive_unwrap_or(2), 1);
+		assert_eq!(Ok::<_, ()>(1).defensive_unwrap_or_default(), 1);
+		assert_eq!(Some(1).defensive_ok_or(()), Ok(1));
+		assert_eq!(1u32.defensive_saturating_add(2), 3);
+		assert_eq!(3u32.defensive_saturating_sub(2), 1);
+		assert_eq!(3u32.defensive_saturating_mul(2), 6);
+	}
+
+	#[test]
+	#[should_panic(expected = "Defensive failure has been triggered!")]
+	fn defensive_unwrap_panics_in_tests() {
+		None::<u32>.defensive_unwrap_or(2);
+	}
+
+	#[test]
+	#[should_panic(expected = "Defensive failure has been triggered!")]
+	fn defensive_result_panics_in_tests() {
+		let _ = Err::<u32, _>("error").defensive();
+	}
+
+	#[test]
+	#[should_panic(expected = "addition overflow")]
+	fn defensive_saturating_panics_in_tests() {
+		u32::max_value().defensive_saturating_add(1);
+	}
+}
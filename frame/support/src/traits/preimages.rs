@@ -0,0 +1,236 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stuff for dealing with preimages: the full data of values which are referred to by hash.
+
+use sp_std::{borrow::Cow, marker::PhantomData};
+use codec::{Encode, Decode};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+use crate::{storage::bounded_vec::BoundedVec, traits::Get};
+
+/// The hash type used to identify preimages.
+pub type Hash = H256;
+
+/// The maximum length of encoded data which is kept inline in a [`Bounded`] value rather than
+/// being placed in a preimage registry.
+pub struct MaxInlineLen;
+impl Get<u32> for MaxInlineLen {
+	fn get() -> u32 {
+		128
+	}
+}
+
+/// Encoded data which is small enough to be kept inline.
+pub type BoundedInline = BoundedVec<u8, MaxInlineLen>;
+
+/// A value of type `T` whose encoded size in storage is bounded.
+///
+/// Small values are kept inline. Larger values are only referred to by their hash and length, and
+/// their data must be made available through a preimage provider (see [`QueryPreimage`]).
+#[derive(
+	Encode, Decode, crate::CloneNoBound, crate::PartialEqNoBound, crate::EqNoBound,
+	crate::RuntimeDebugNoBound,
+)]
+pub enum Bounded<T> {
+	/// The encoded value, kept inline.
+	Inline(BoundedInline),
+	/// The hash and length of the encoded value, whose data must be looked up.
+	Lookup { hash: Hash, len: u32, _phantom: PhantomData<T> },
+}
+
+impl<T> Bounded<T> {
+	/// Cast the type of the value held by this to a different type, keeping the encoded data.
+	pub fn transmute<S>(self) -> Bounded<S> where T: Encode + codec::EncodeLike<S> {
+		match self {
+			Bounded::Inline(x) => Bounded::Inline(x),
+			Bounded::Lookup { hash, len, .. } => Bounded::Lookup { hash, len, _phantom: PhantomData },
+		}
+	}
+
+	/// Create a `Lookup` reference to a preimage of `hash` with `len` bytes.
+	pub fn lookup(hash: Hash, len: u32) -> Self {
+		Bounded::Lookup { hash, len, _phantom: PhantomData }
+	}
+
+	/// The hash of the encoded value.
+	pub fn hash(&self) -> Hash {
+		match self {
+			Bounded::Inline(x) => sp_io::hashing::blake2_256(x.as_slice()).into(),
+			Bounded::Lookup { hash, .. } => *hash,
+		}
+	}
+
+	/// The hash and length of the encoded value, if it needs to be looked up.
+	pub fn lookup_hash(&self) -> Option<(Hash, u32)> {
+		match self {
+			Bounded::Inline(_) => None,
+			Bounded::Lookup { hash, len, .. } => Some((*hash, *len)),
+		}
+	}
+
+	/// Whether the data of this value must be looked up from a preimage provider.
+	pub fn lookup_needed(&self) -> bool {
+		self.lookup_hash().is_some()
+	}
+
+	/// The length of the encoded value.
+	pub fn encoded_len(&self) -> u32 {
+		match self {
+			Bounded::Inline(x) => x.len() as u32,
+			Bounded::Lookup { len, .. } => *len,
+		}
+	}
+}
+
+/// A provider of preimages which can be queried and asked to keep a preimage around.
+pub trait QueryPreimage {
+	/// The length of the preimage of `hash`, if it is known.
+	fn len(hash: &Hash) -> Option<u32>;
+
+	/// Fetch the preimage of `hash`. `len` may be provided as a hint of its length.
+	fn fetch(hash: &Hash, len: Option<u32>) -> Result<Cow<'static, [u8]>, DispatchError>;
+
+	/// Whether the preimage of `hash` has been requested.
+	fn is_requested(hash: &Hash) -> bool;
+
+	/// Request that the preimage of `hash` be kept available until it is unrequested. Requests
+	/// are counted, so each call must eventually be matched by a call to `unrequest`.
+	fn request(hash: &Hash);
+
+	/// Remove a previously made request for the preimage of `hash`.
+	fn unrequest(hash: &Hash);
+
+	/// Request that the data behind `bounded` be kept available, if it needs to be looked up.
+	fn hold<T>(bounded: &Bounded<T>) {
+		if let Some((hash, _)) = bounded.lookup_hash() {
+			Self::request(&hash)
+		}
+	}
+
+	/// Remove a request previously made through `hold`.
+	fn drop<T>(bounded: &Bounded<T>) {
+		if let Some((hash, _)) = bounded.lookup_hash() {
+			Self::unrequest(&hash)
+		}
+	}
+
+	/// Whether the data behind `bounded` is available.
+	fn have<T>(bounded: &Bounded<T>) -> bool {
+		match bounded.lookup_hash() {
+			Some((hash, _)) => Self::len(&hash).is_some(),
+			None => true,
+		}
+	}
+
+	/// Decode the value behind `bounded`, without making any changes to the requests of its
+	/// preimage. Also returns the length of the looked-up preimage, if any.
+	fn peek<T: Decode>(bounded: &Bounded<T>) -> Result<(T, Option<u32>), DispatchError> {
+		let (data, len) = match bounded {
+			Bounded::Inline(data) => (Cow::Owned(data.clone().into_inner()), None),
+			Bounded::Lookup { hash, len, .. } => (Self::fetch(hash, Some(*len))?, Some(*len)),
+		};
+		let value = T::decode(&mut &data[..])
+			.map_err(|_| DispatchError::Other("Preimage could not be decoded"))?;
+		Ok((value, len))
+	}
+
+	/// Decode the value behind `bounded` and drop the request for its preimage, if any.
+	fn realize<T: Decode>(bounded: &Bounded<T>) -> Result<(T, Option<u32>), DispatchError> {
+		let r = Self::peek(bounded)?;
+		Self::drop(bounded);
+		Ok(r)
+	}
+}
+
+/// A provider of preimages which can also store new preimages.
+pub trait StorePreimage: QueryPreimage {
+	/// The maximum length of a preimage which can be stored.
+	const MAX_LENGTH: usize;
+
+	/// Store `bytes` as a requested preimage, returning its hash. Must be matched by a call to
+	/// `unnote` once it is no longer needed.
+	fn note(bytes: Cow<[u8]>) -> Result<Hash, DispatchError>;
+
+	/// Remove a preimage previously stored through `note`.
+	fn unnote(hash: &Hash) {
+		Self::unrequest(hash)
+	}
+
+	/// Bound `t`, storing its encoding as a preimage if it is too large to be kept inline.
+	fn bound<T: Encode>(t: T) -> Result<Bounded<T>, DispatchError> {
+		let data = t.encode();
+		let len = data.len() as u32;
+		if len <= MaxInlineLen::get() {
+			Ok(Bounded::Inline(BoundedInline::force_from(data, None)))
+		} else {
+			Ok(Bounded::lookup(Self::note(data.into())?, len))
+		}
+	}
+}
+
+impl QueryPreimage for () {
+	fn len(_: &Hash) -> Option<u32> {
+		None
+	}
+	fn fetch(_: &Hash, _: Option<u32>) -> Result<Cow<'static, [u8]>, DispatchError> {
+		Err(DispatchError::Other("No preimage provider"))
+	}
+	fn is_requested(_: &Hash) -> bool {
+		false
+	}
+	fn request(_: &Hash) {}
+	fn unrequest(_: &Hash) {}
+}
+
+impl StorePreimage for () {
+	const MAX_LENGTH: usize = 0;
+	fn note(_: Cow<[u8]>) -> Result<Hash, DispatchError> {
+		Err(DispatchError::Other("No preimage provider"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_std::convert::TryFrom;
+
+	#[test]
+	fn bounded_basic_works() {
+		let data = BoundedInline::try_from(vec![1u8, 2, 3]).unwrap();
+		let inline: Bounded<Vec<u8>> = Bounded::Inline(data);
+		assert_eq!(inline.encoded_len(), 3);
+		assert!(!inline.lookup_needed());
+		assert_eq!(inline.hash(), H256::from(sp_io::hashing::blake2_256(&[1u8, 2, 3])));
+
+		let lookup: Bounded<Vec<u8>> = Bounded::lookup(H256::repeat_byte(1), 200);
+		assert_eq!(lookup.encoded_len(), 200);
+		assert_eq!(lookup.lookup_hash(), Some((H256::repeat_byte(1), 200)));
+		assert_eq!(lookup.hash(), H256::repeat_byte(1));
+	}
+
+	#[test]
+	fn bounded_inline_is_peeked_without_provider() {
+		let bounded = <() as StorePreimage>::bound(vec![42u8; 10]).unwrap();
+		assert!(!bounded.lookup_needed());
+		assert!(<() as QueryPreimage>::have(&bounded));
+		assert_eq!(<() as QueryPreimage>::peek(&bounded).unwrap(), (vec![42u8; 10], None));
+
+		// too large to be kept inline, and `()` cannot store it.
+		assert!(<() as StorePreimage>::bound(vec![42u8; 200]).is_err());
+	}
+}
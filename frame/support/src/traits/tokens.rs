@@ -19,10 +19,15 @@
 
 pub mod fungible;
 pub mod fungibles;
+pub mod nonfungible;
+pub mod nonfungibles;
 pub mod currency;
 pub mod imbalance;
+pub mod pay;
 mod misc;
 pub use misc::{
 	WithdrawConsequence, DepositConsequence, ExistenceRequirement, BalanceStatus, WithdrawReasons,
+	ConversionFromAssetBalance, UnityAssetBalanceConversion,
 };
+pub use pay::{Pay, PaymentStatus, PayFromAccount};
 pub use imbalance::Imbalance;
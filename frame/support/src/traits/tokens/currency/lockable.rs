@@ -84,8 +84,8 @@ pub trait VestingSchedule<AccountId> {
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
-	/// and nothing is updated.
+	/// If the account can't hold any more vesting schedules, an `Err` is returned and nothing
+	/// is updated.
 	///
 	/// Is a no-op if the amount to be vested is zero.
 	///
@@ -97,7 +97,7 @@ pub trait VestingSchedule<AccountId> {
 		starting_block: Self::Moment,
 	) -> DispatchResult;
 
-	/// Remove a vesting schedule for a given account.
+	/// Remove all vesting schedules of a given account.
 	///
 	/// NOTE: This doesn't alter the free balance of the account.
 	fn remove_vesting_schedule(who: &AccountId);
@@ -166,3 +166,28 @@ impl<T: FullCodec + Copy + Default + Eq + PartialEq> AssetId for T {}
 /// Simple amalgamation trait to collect together properties for a Balance under one roof.
 pub trait Balance: AtLeast32BitUnsigned + FullCodec + Copy + Default {}
 impl<T: AtLeast32BitUnsigned + FullCodec + Copy + Default> Balance for T {}
+
+/// Converts an asset balance value into a balance of some other kind, e.g. the native balance.
+pub trait ConversionFromAssetBalance<AssetBalance, AssetId, OutBalance> {
+	/// The error which may be returned by the conversion.
+	type Error;
+	/// Convert `balance` of the asset `asset_id` into the output balance.
+	fn from_asset_balance(
+		balance: AssetBalance,
+		asset_id: AssetId,
+	) -> Result<OutBalance, Self::Error>;
+}
+
+/// Implements [`ConversionFromAssetBalance`], enabling a 1:1 conversion of the asset balance
+/// value to the balance.
+pub struct UnityAssetBalanceConversion;
+impl<AssetBalance, AssetId, OutBalance> ConversionFromAssetBalance<AssetBalance, AssetId, OutBalance>
+	for UnityAssetBalanceConversion
+where
+	AssetBalance: Into<OutBalance>,
+{
+	type Error = ();
+	fn from_asset_balance(balance: AssetBalance, _: AssetId) -> Result<OutBalance, Self::Error> {
+		Ok(balance.into())
+	}
+}
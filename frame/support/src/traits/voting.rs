@@ -18,7 +18,11 @@
 //! Traits and associated data structures concerned with voting, and moving between tokens and
 //! votes.
 
-use sp_arithmetic::traits::{UniqueSaturatedInto, UniqueSaturatedFrom, SaturatedConversion};
+use sp_std::prelude::*;
+use sp_arithmetic::{Perbill, traits::{UniqueSaturatedInto, UniqueSaturatedFrom, SaturatedConversion}};
+use sp_runtime::traits::Member;
+use codec::HasCompact;
+use crate::dispatch::{DispatchError, Parameter};
 
 /// A trait similar to `Convert` to convert values from `B` an abstract balance type
 /// into u64 and back from u128. (This conversion is used in election and other places where complex
@@ -86,3 +90,84 @@ impl<B: UniqueSaturatedInto<u64> + UniqueSaturatedFrom<u128>> CurrencyToVote<B>
 		B::unique_saturated_from(value)
 	}
 }
+
+/// The tally of votes for a poll of some class.
+pub trait VoteTally<Votes, Class> {
+	/// A tally with no votes at all, for a poll of `class`.
+	fn new(class: Class) -> Self;
+	/// The amount of approving votes.
+	fn ayes(&self, class: Class) -> Votes;
+	/// The proportion of the total possible votes which are expressed in this tally.
+	fn support(&self, class: Class) -> Perbill;
+	/// The proportion of the expressed votes which are approving.
+	fn approval(&self, class: Class) -> Perbill;
+	/// A tally which passes with any support or approval threshold.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn unanimity(class: Class) -> Self;
+	/// A tally which fails with any non-zero support or approval threshold.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn rejection(class: Class) -> Self;
+}
+
+/// The status of a poll, giving access to its tally while it is ongoing.
+pub enum PollStatus<Tally, Moment, Class> {
+	/// The poll does not exist.
+	None,
+	/// The poll is ongoing, with the given tally and class.
+	Ongoing(Tally, Class),
+	/// The poll was completed at the given moment, and was approved iff the `bool` is `true`.
+	Completed(Moment, bool),
+}
+
+impl<Tally, Moment, Class> PollStatus<Tally, Moment, Class> {
+	/// The tally and class of the poll, if it is ongoing.
+	pub fn ensure_ongoing(self) -> Option<(Tally, Class)> {
+		match self {
+			Self::Ongoing(t, c) => Some((t, c)),
+			_ => None,
+		}
+	}
+}
+
+/// A source of polls, such as referenda, whose tallies may be accessed in order to vote on them.
+pub trait Polling<Tally> {
+	/// The index by which a poll is identified.
+	type Index: Parameter + Member + Ord + PartialOrd + Copy + HasCompact;
+	/// The type of votes which are tallied.
+	type Votes: Parameter + Member + Ord + PartialOrd + Copy + HasCompact;
+	/// The class of a poll.
+	type Class: Parameter + Member + Ord + PartialOrd;
+	/// The type in which the completion of polls is measured.
+	type Moment;
+
+	/// All classes that polls may be of.
+	fn classes() -> Vec<Self::Class>;
+
+	/// The tally and class of the poll `index`, if it is ongoing.
+	fn as_ongoing(index: Self::Index) -> Option<(Tally, Self::Class)>;
+
+	/// Provide `f` with the status of the poll `index`, allowing it to alter the tally of an
+	/// ongoing poll.
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> R,
+	) -> R;
+
+	/// As `access_poll`, except that any changes to the tally are only kept if `f` succeeds.
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError>;
+
+	/// Create an ongoing poll of `class`, for benchmarking.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()>;
+
+	/// End the ongoing poll `index`, with the given outcome, for benchmarking.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()>;
+
+	/// The class with the most ongoing polls allowed at once, along with that number.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32);
+}
@@ -1,7 +0,0 @@
-use frame_support::construct_runtime;
-
-construct_runtime! {
-	pub enum Runtime {}
-}
-
-fn main() {}
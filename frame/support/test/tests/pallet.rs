@@ -324,6 +324,7 @@ pub mod pallet2 {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(crate) trait Store)]
+	#[pallet::generate_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
@@ -344,6 +345,17 @@ pub mod pallet2 {
 		Something(u32),
 	}
 
+	#[pallet::storage]
+	pub type SomeValue<T> = StorageValue<_, u64>;
+
+	#[pallet::storage]
+	pub type SomeDoubleMap<T> =
+		StorageDoubleMap<_, Twox64Concat, u8, Blake2_128Concat, u16, (u32, Option<u8>)>;
+
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type SomeUnboundedValue<T> = StorageValue<_, Vec<u32>>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config>
 	where T::AccountId: From<SomeType1> + SomeAssociation1,
@@ -872,3 +884,47 @@ fn test_pallet_info_access() {
 	assert_eq!(<Example as frame_support::traits::PalletInfoAccess>::index(), 1);
 	assert_eq!(<Example2 as frame_support::traits::PalletInfoAccess>::index(), 2);
 }
+
+#[test]
+fn test_storage_info() {
+	use frame_support::traits::{StorageInfoTrait, StorageInfo};
+
+	let prefix = |pallet_name: &[u8], storage_name: &[u8]| {
+		let mut res = twox_128(pallet_name).to_vec();
+		res.extend_from_slice(&twox_128(storage_name));
+		res
+	};
+
+	assert_eq!(
+		Example2::storage_info(),
+		vec![
+			StorageInfo {
+				pallet_name: b"Example2".to_vec(),
+				storage_name: b"SomeValue".to_vec(),
+				prefix: prefix(b"Example2", b"SomeValue"),
+				max_values: Some(1),
+				max_size: Some(8),
+			},
+			StorageInfo {
+				pallet_name: b"Example2".to_vec(),
+				storage_name: b"SomeDoubleMap".to_vec(),
+				prefix: prefix(b"Example2", b"SomeDoubleMap"),
+				max_values: None,
+				max_size: Some(8 + 1 + 16 + 2 + 4 + 2),
+			},
+			StorageInfo {
+				pallet_name: b"Example2".to_vec(),
+				storage_name: b"SomeUnboundedValue".to_vec(),
+				prefix: prefix(b"Example2", b"SomeUnboundedValue"),
+				max_values: Some(1),
+				max_size: None,
+			},
+		],
+	);
+
+	// Without `generate_storage_info` only the partial storage info is given.
+	let example_info = Example::storage_info();
+	assert_eq!(example_info[0].storage_name, b"ValueWhereClause".to_vec());
+	assert_eq!(example_info[2].prefix, prefix(b"Example", b"Map"));
+	assert!(example_info.iter().all(|info| info.max_size.is_none()));
+}
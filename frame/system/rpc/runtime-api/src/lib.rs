@@ -23,13 +23,45 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use sp_runtime::{generic::Era, traits::NumberFor, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Everything an external signer needs to build the signed extensions of a valid transaction
+/// of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SigningInfo<Hash, BlockNumber, Index> {
+	/// The nonce the next transaction of the account has to use.
+	pub nonce: Index,
+	/// The hash of the genesis block.
+	pub genesis_hash: Hash,
+	/// The recommended mortality of the transaction.
+	pub era: Era,
+	/// The number and hash of the block the mortality of `era` starts at.
+	pub checkpoint: (BlockNumber, Hash),
+	/// The spec version of the runtime.
+	pub spec_version: u32,
+	/// The transaction version of the runtime.
+	pub transaction_version: u32,
+	/// The identifiers of the signed extensions of the runtime, in the order their data is
+	/// encoded.
+	///
+	/// Signers should refuse to sign if they don't know all of them.
+	pub extensions: Vec<Vec<u8>>,
+}
+
 sp_api::decl_runtime_apis! {
 	/// The API to query account nonce (aka transaction index).
+	#[api_version(2)]
 	pub trait AccountNonceApi<AccountId, Index> where
 		AccountId: codec::Codec,
 		Index: codec::Codec,
 	{
 		/// Get current account nonce of given `AccountId`.
 		fn account_nonce(account: AccountId) -> Index;
+
+		/// Get what the signed extensions of a transaction of `account` signed on top of the
+		/// current block have to contain.
+		fn signing_info(account: AccountId) -> SigningInfo<Block::Hash, NumberFor<Block>, Index>;
 	}
 }
@@ -71,6 +71,8 @@ use sp_std::prelude::*;
 use sp_std::map;
 use sp_std::marker::PhantomData;
 use sp_std::fmt::Debug;
+#[cfg(any(feature = "std", feature = "runtime-benchmarks", test))]
+use sp_std::convert::TryInto;
 use sp_version::RuntimeVersion;
 use sp_runtime::{
 	RuntimeDebug, Perbill, DispatchError, Either, generic,
@@ -78,7 +80,7 @@ use sp_runtime::{
 		self, CheckEqual, AtLeast32Bit, Zero, Lookup, LookupError,
 		SimpleBitOps, Hash, Member, MaybeDisplay, BadOrigin,
 		MaybeSerializeDeserialize, MaybeMallocSizeOf, StaticLookup, One, Bounded,
-		Dispatchable, AtLeast32BitUnsigned, Saturating, StoredMapError,
+		Dispatchable, AtLeast32BitUnsigned, Saturating, StoredMapError, SaturatedConversion,
 	},
 	offchain::storage_lock::BlockNumberProvider,
 };
@@ -848,6 +850,30 @@ impl<
 	}
 }
 
+/// Ensure the origin is `Root` and return the value of type `T` provided by `Success`.
+pub struct EnsureRootWithSuccess<AccountId, T, Success>(
+	sp_std::marker::PhantomData<(AccountId, T, Success)>,
+);
+impl<
+	O: Into<Result<RawOrigin<AccountId>, O>> + From<RawOrigin<AccountId>>,
+	AccountId,
+	T,
+	Success: Get<T>,
+> EnsureOrigin<O> for EnsureRootWithSuccess<AccountId, T, Success> {
+	type Success = T;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Root => Ok(Success::get()),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::Root)
+	}
+}
+
 pub struct EnsureSigned<AccountId>(sp_std::marker::PhantomData<AccountId>);
 impl<
 	O: Into<Result<RawOrigin<AccountId>, O>> + From<RawOrigin<AccountId>>,
@@ -1449,6 +1475,40 @@ impl<T: Config> Pallet<T> {
 		<EventTopics<T>>::remove_all();
 	}
 
+	/// Get the events deposited so far whose outer event converts into `E`, e.g. the events of a
+	/// single pallet, in the order they were deposited.
+	#[cfg(any(feature = "std", feature = "runtime-benchmarks", test))]
+	pub fn read_events_for_pallet<E>() -> Vec<E>
+	where
+		T::Event: TryInto<E>,
+	{
+		Self::events()
+			.into_iter()
+			.filter_map(|record| record.event.try_into().ok())
+			.collect()
+	}
+
+	/// Assert that `event` was deposited.
+	#[cfg(any(feature = "std", feature = "runtime-benchmarks", test))]
+	pub fn assert_has_event(event: T::Event) {
+		assert!(
+			Self::events().iter().any(|record| record.event == event),
+			"expected event {:?} not found",
+			event,
+		);
+	}
+
+	/// Assert that `event` is the last event deposited.
+	#[cfg(any(feature = "std", feature = "runtime-benchmarks", test))]
+	pub fn assert_last_event(event: T::Event) {
+		assert_eq!(
+			Self::events().last().map(|record| &record.event),
+			Some(&event),
+			"expected event {:?} is not the last event",
+			event,
+		);
+	}
+
 	/// Return the chain's current runtime version.
 	pub fn runtime_version() -> RuntimeVersion { T::Version::get() }
 
@@ -1457,6 +1517,22 @@ impl<T: Config> Pallet<T> {
 		Account::<T>::get(who).nonce
 	}
 
+	/// Return the mortality new transactions should be signed with, along with the number and
+	/// hash of its birth block.
+	///
+	/// The era starts at the parent block, the most recent one whose hash is known to the state,
+	/// and lasts for at most `max_period` blocks. It is short enough for the hash of its birth
+	/// block to be kept in `BlockHash` until the transaction dies.
+	pub fn signing_era(max_period: u64) -> (generic::Era, T::BlockNumber, T::Hash) {
+		let limit = max_period.min(T::BlockHashCount::get().saturated_into());
+		// `Era::mortal` rounds the period up to a power of two.
+		let period = if limit.is_power_of_two() { limit } else { limit.next_power_of_two() / 2 };
+		let current = Self::block_number().saturating_sub(One::one()).saturated_into();
+		let era = generic::Era::mortal(period, current);
+		let birth = era.birth(current).saturated_into::<T::BlockNumber>();
+		(era, birth, Self::block_hash(birth))
+	}
+
 	/// Increment a particular account's nonce by 1.
 	pub fn inc_account_nonce(who: impl EncodeLike<T::AccountId>) {
 		Account::<T>::mutate(who, |a| a.nonce += T::Index::one());
@@ -18,6 +18,7 @@
 //! Provide types to help defining a mock environment when testing pallets.
 
 use sp_runtime::generic;
+use frame_support::traits::Get;
 
 /// An unchecked extrinsic type to be used in tests.
 pub type MockUncheckedExtrinsic<T, Signature = (), Extra = ()> = generic::UncheckedExtrinsic<
@@ -29,3 +30,225 @@ pub type MockBlock<T> = generic::Block<
 	generic::Header<<T as crate::Config>::BlockNumber, sp_runtime::traits::BlakeTwo256>,
 	MockUncheckedExtrinsic<T>,
 >;
+
+/// The `BlockHashCount` used by [`impl_mock_config!`](crate::impl_mock_config) by default.
+pub struct MockBlockHashCount;
+impl<BlockNumber: From<u32>> Get<BlockNumber> for MockBlockHashCount {
+	fn get() -> BlockNumber {
+		250u32.into()
+	}
+}
+
+#[doc(hidden)]
+pub mod __private {
+	pub use sp_core::H256;
+	pub use sp_runtime::{generic::Header, traits::{BlakeTwo256, IdentityLookup}};
+}
+
+/// Implement `frame_system::Config` for a mock runtime, with defaults suitable for tests.
+///
+/// Only the types that differ from the defaults need to be given. The defaults are the
+/// `Origin`, `Call`, `Event` and `PalletInfo` types generated by `construct_runtime!`, `u64`
+/// for `Index`, `BlockNumber` and `AccountId`, `H256` hashes with `BlakeTwo256`, an
+/// `IdentityLookup`, a `BlockHashCount` of 250 and `()` for everything else.
+///
+/// ```ignore
+/// frame_system::impl_mock_config! {
+/// 	impl frame_system::Config for Test {
+/// 		type AccountData = pallet_balances::AccountData<u64>;
+/// 	}
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_mock_config {
+	(
+		impl $( $config:ident )::+ for $runtime:ident {
+			$( type $name:ident = $value:ty; )*
+		}
+	) => {
+		$( $crate::__mock_config_check!($name); )*
+
+		impl $crate::Config for $runtime {
+			type BaseCallFilter = $crate::__mock_config_select!(
+				BaseCallFilter,
+				{ () },
+				$( $name = $value; )*
+			);
+			type BlockWeights = $crate::__mock_config_select!(
+				BlockWeights,
+				{ () },
+				$( $name = $value; )*
+			);
+			type BlockLength = $crate::__mock_config_select!(
+				BlockLength,
+				{ () },
+				$( $name = $value; )*
+			);
+			type DbWeight = $crate::__mock_config_select!(
+				DbWeight,
+				{ () },
+				$( $name = $value; )*
+			);
+			type Origin = $crate::__mock_config_select!(
+				Origin,
+				{ Origin },
+				$( $name = $value; )*
+			);
+			type Call = $crate::__mock_config_select!(
+				Call,
+				{ Call },
+				$( $name = $value; )*
+			);
+			type Index = $crate::__mock_config_select!(
+				Index,
+				{ u64 },
+				$( $name = $value; )*
+			);
+			type BlockNumber = $crate::__mock_config_select!(
+				BlockNumber,
+				{ u64 },
+				$( $name = $value; )*
+			);
+			type Hash = $crate::__mock_config_select!(
+				Hash,
+				{ $crate::mocking::__private::H256 },
+				$( $name = $value; )*
+			);
+			type Hashing = $crate::__mock_config_select!(
+				Hashing,
+				{ $crate::mocking::__private::BlakeTwo256 },
+				$( $name = $value; )*
+			);
+			type AccountId = $crate::__mock_config_select!(
+				AccountId,
+				{ u64 },
+				$( $name = $value; )*
+			);
+			type Lookup = $crate::__mock_config_select!(
+				Lookup,
+				{ $crate::mocking::__private::IdentityLookup<Self::AccountId> },
+				$( $name = $value; )*
+			);
+			type Header = $crate::__mock_config_select!(
+				Header,
+				{ $crate::mocking::__private::Header<Self::BlockNumber, Self::Hashing> },
+				$( $name = $value; )*
+			);
+			type Event = $crate::__mock_config_select!(
+				Event,
+				{ Event },
+				$( $name = $value; )*
+			);
+			type BlockHashCount = $crate::__mock_config_select!(
+				BlockHashCount,
+				{ $crate::mocking::MockBlockHashCount },
+				$( $name = $value; )*
+			);
+			type Version = $crate::__mock_config_select!(
+				Version,
+				{ () },
+				$( $name = $value; )*
+			);
+			type PalletInfo = $crate::__mock_config_select!(
+				PalletInfo,
+				{ PalletInfo },
+				$( $name = $value; )*
+			);
+			type AccountData = $crate::__mock_config_select!(
+				AccountData,
+				{ () },
+				$( $name = $value; )*
+			);
+			type OnNewAccount = $crate::__mock_config_select!(
+				OnNewAccount,
+				{ () },
+				$( $name = $value; )*
+			);
+			type OnKilledAccount = $crate::__mock_config_select!(
+				OnKilledAccount,
+				{ () },
+				$( $name = $value; )*
+			);
+			type SystemWeightInfo = $crate::__mock_config_select!(
+				SystemWeightInfo,
+				{ () },
+				$( $name = $value; )*
+			);
+			type SS58Prefix = $crate::__mock_config_select!(
+				SS58Prefix,
+				{ () },
+				$( $name = $value; )*
+			);
+			type OnSetCode = $crate::__mock_config_select!(
+				OnSetCode,
+				{ () },
+				$( $name = $value; )*
+			);
+		}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mock_config_select {
+	($item:ident, { $default:ty }, ) => { $default };
+	(BaseCallFilter, { $default:ty }, BaseCallFilter = $value:ty; $( $rest:tt )*) => { $value };
+	(BlockWeights, { $default:ty }, BlockWeights = $value:ty; $( $rest:tt )*) => { $value };
+	(BlockLength, { $default:ty }, BlockLength = $value:ty; $( $rest:tt )*) => { $value };
+	(DbWeight, { $default:ty }, DbWeight = $value:ty; $( $rest:tt )*) => { $value };
+	(Origin, { $default:ty }, Origin = $value:ty; $( $rest:tt )*) => { $value };
+	(Call, { $default:ty }, Call = $value:ty; $( $rest:tt )*) => { $value };
+	(Index, { $default:ty }, Index = $value:ty; $( $rest:tt )*) => { $value };
+	(BlockNumber, { $default:ty }, BlockNumber = $value:ty; $( $rest:tt )*) => { $value };
+	(Hash, { $default:ty }, Hash = $value:ty; $( $rest:tt )*) => { $value };
+	(Hashing, { $default:ty }, Hashing = $value:ty; $( $rest:tt )*) => { $value };
+	(AccountId, { $default:ty }, AccountId = $value:ty; $( $rest:tt )*) => { $value };
+	(Lookup, { $default:ty }, Lookup = $value:ty; $( $rest:tt )*) => { $value };
+	(Header, { $default:ty }, Header = $value:ty; $( $rest:tt )*) => { $value };
+	(Event, { $default:ty }, Event = $value:ty; $( $rest:tt )*) => { $value };
+	(BlockHashCount, { $default:ty }, BlockHashCount = $value:ty; $( $rest:tt )*) => { $value };
+	(Version, { $default:ty }, Version = $value:ty; $( $rest:tt )*) => { $value };
+	(PalletInfo, { $default:ty }, PalletInfo = $value:ty; $( $rest:tt )*) => { $value };
+	(AccountData, { $default:ty }, AccountData = $value:ty; $( $rest:tt )*) => { $value };
+	(OnNewAccount, { $default:ty }, OnNewAccount = $value:ty; $( $rest:tt )*) => { $value };
+	(OnKilledAccount, { $default:ty }, OnKilledAccount = $value:ty; $( $rest:tt )*) => { $value };
+	(SystemWeightInfo, { $default:ty }, SystemWeightInfo = $value:ty; $( $rest:tt )*) => { $value };
+	(SS58Prefix, { $default:ty }, SS58Prefix = $value:ty; $( $rest:tt )*) => { $value };
+	(OnSetCode, { $default:ty }, OnSetCode = $value:ty; $( $rest:tt )*) => { $value };
+	($item:ident, { $default:ty }, $other:ident = $value:ty; $( $rest:tt )*) => {
+		$crate::__mock_config_select!($item, { $default }, $( $rest )*)
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mock_config_check {
+	(BaseCallFilter) => {};
+	(BlockWeights) => {};
+	(BlockLength) => {};
+	(DbWeight) => {};
+	(Origin) => {};
+	(Call) => {};
+	(Index) => {};
+	(BlockNumber) => {};
+	(Hash) => {};
+	(Hashing) => {};
+	(AccountId) => {};
+	(Lookup) => {};
+	(Header) => {};
+	(Event) => {};
+	(BlockHashCount) => {};
+	(Version) => {};
+	(PalletInfo) => {};
+	(AccountData) => {};
+	(OnNewAccount) => {};
+	(OnKilledAccount) => {};
+	(SystemWeightInfo) => {};
+	(SS58Prefix) => {};
+	(OnSetCode) => {};
+	($other:ident) => {
+		compile_error!(concat!(
+			"`", stringify!($other), "` is not an item of `frame_system::Config`",
+		));
+	};
+}
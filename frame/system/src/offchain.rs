@@ -61,9 +61,20 @@ use sp_std::collections::btree_set::BTreeSet;
 use sp_std::convert::{TryInto, TryFrom};
 use sp_std::prelude::{Box, Vec};
 use sp_runtime::app_crypto::RuntimeAppPublic;
-use sp_runtime::traits::{Extrinsic as ExtrinsicT, IdentifyAccount, One};
+use sp_runtime::offchain::{storage::StorageValueRef, storage_lock::{StorageLock, Time}};
+use sp_runtime::traits::{Extrinsic as ExtrinsicT, IdentifyAccount, One, Saturating};
 use frame_support::RuntimeDebug;
 
+/// Prefix of the persistent offchain storage key under which the next nonce of an account is
+/// kept by [`SendSignedTransaction`].
+const NONCE_KEY_PREFIX: &[u8] = b"frame_system::offchain::next_nonce::";
+
+/// Number of blocks after which a nonce kept by [`SendSignedTransaction`] is discarded.
+///
+/// This makes workers fall back to the account nonce if a submitted transaction got dropped
+/// from the pool.
+const NONCE_VALIDITY: u32 = 10;
+
 /// Marker struct used to flag using all supported keys to sign a payload.
 pub struct ForAll {}
 /// Marker struct used to flag using any of the supported keys to sign a payload.
@@ -543,12 +554,32 @@ pub trait SendSignedTransaction<
 	) -> Self::Result;
 
 	/// Wraps the call into transaction, signs using given account and submits to the pool.
+	///
+	/// The nonce of the transaction is the account nonce, unless a worker already submitted a
+	/// transaction from the same account that isn't included yet. The last used nonce is kept
+	/// in the persistent offchain storage for `NONCE_VALIDITY` blocks, so that workers of
+	/// concurrent blocks don't submit conflicting transactions.
 	fn send_single_signed_transaction(
 		&self,
 		account: &Account<T>,
 		call: LocalCall,
 	) -> Option<Result<(), ()>> {
+		let nonce_key = [NONCE_KEY_PREFIX, &account.id.encode()].concat();
+		let lock_key = [&nonce_key[..], b"::lock"].concat();
+		let mut lock = StorageLock::<Time>::new(&lock_key);
+		let _guard = lock.lock();
+
 		let mut account_data = crate::Account::<T>::get(&account.id);
+		let block_number = crate::Pallet::<T>::block_number();
+		let next_nonce = StorageValueRef::persistent(&nonce_key);
+		match next_nonce.get::<(T::Index, T::BlockNumber)>() {
+			Some(Some((nonce, at)))
+				if nonce > account_data.nonce
+					&& at.saturating_add(NONCE_VALIDITY.into()) >= block_number
+				=> account_data.nonce = nonce,
+			_ => {},
+		}
+
 		log::debug!(
 			target: "runtime::offchain",
 			"Creating signed transaction from account: {:?} (nonce: {:?})",
@@ -568,6 +599,7 @@ pub trait SendSignedTransaction<
 			// increment the nonce. This is fine, since the code should always
 			// be running in off-chain context, so we NEVER persists data.
 			account_data.nonce += One::one();
+			next_nonce.set(&(account_data.nonce, block_number));
 			crate::Account::<T>::insert(&account.id, account_data);
 		}
 
@@ -637,7 +669,8 @@ mod tests {
 	use super::*;
 	use codec::Decode;
 	use crate::mock::{Test as TestRuntime, Call, CALL};
-	use sp_core::offchain::{testing, TransactionPoolExt};
+	use frame_support::storage::{with_transaction, TransactionOutcome};
+	use sp_core::offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
 	use sp_runtime::testing::{UintAuthorityId, TestSignature, TestXt};
 
 	impl SigningTypes for TestRuntime {
@@ -843,4 +876,52 @@ mod tests {
 		});
 	}
 
+	impl CreateSignedTransaction<Call> for TestRuntime {
+		fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+			call: Call,
+			_public: UintAuthorityId,
+			_account: u64,
+			nonce: u64,
+		) -> Option<(Call, (u64, ()))> {
+			Some((call, (nonce, ())))
+		}
+	}
+
+	#[test]
+	fn should_not_reuse_nonce_of_pending_signed_transaction() {
+		let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+		let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+
+		let mut t = sp_io::TestExternalities::default();
+		t.register_extension(TransactionPoolExt::new(pool));
+		t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+		t.register_extension(OffchainDbExt::new(offchain));
+
+		// given
+		UintAuthorityId::set_all_keys(vec![0xf0]);
+
+		t.execute_with(|| {
+			// every worker runs on top of its own state, only the offchain storage is shared.
+			let send_from_worker = || with_transaction(|| {
+				let result = Signer::<TestRuntime, DummyAppCrypto>
+					::any_account()
+					.send_signed_transaction(|_account| CALL.clone());
+				assert_eq!(result.map(|(_, res)| res), Some(Ok(())));
+
+				let tx = pool_state.write().transactions.pop().unwrap();
+				let tx = Extrinsic::decode(&mut &*tx).unwrap();
+				TransactionOutcome::Rollback(tx.signature.unwrap().0)
+			});
+
+			// when the first transaction is not included yet
+			assert_eq!(send_from_worker(), 0);
+			// then the next worker uses the following nonce
+			assert_eq!(send_from_worker(), 1);
+
+			// when the transactions are not included for too long
+			crate::Pallet::<TestRuntime>::set_block_number(NONCE_VALIDITY as u64 + 1);
+			// then the workers fall back to the account nonce
+			assert_eq!(send_from_worker(), 0);
+		});
+	}
 }
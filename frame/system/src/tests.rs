@@ -18,7 +18,9 @@
 use crate::*;
 use mock::{*, Origin};
 use sp_core::H256;
-use sp_runtime::{DispatchError, DispatchErrorWithPostInfo, traits::{Header, BlakeTwo256}};
+use sp_runtime::{
+	DispatchError, DispatchErrorWithPostInfo, traits::{Header, BlakeTwo256, SignedExtension},
+};
 use frame_support::{
 	assert_noop, assert_ok, weights::WithPostDispatchInfo, dispatch::PostDispatchInfo
 };
@@ -227,6 +229,47 @@ fn deposit_event_should_work() {
 	});
 }
 
+#[test]
+fn event_assertions_work() {
+	new_test_ext().execute_with(|| {
+		System::initialize(
+			&1,
+			&[0u8; 32].into(),
+			&Default::default(),
+			InitKind::Full,
+		);
+		System::deposit_event(SysEvent::NewAccount(32));
+		System::deposit_event(SysEvent::KilledAccount(42));
+
+		System::assert_has_event(SysEvent::NewAccount(32).into());
+		System::assert_last_event(SysEvent::KilledAccount(42).into());
+		assert_eq!(
+			System::read_events_for_pallet::<SysEvent>(),
+			vec![SysEvent::NewAccount(32), SysEvent::KilledAccount(42)],
+		);
+
+		System::reset_events();
+		assert!(System::read_events_for_pallet::<SysEvent>().is_empty());
+	});
+}
+
+#[test]
+#[should_panic(expected = "is not the last event")]
+fn assert_last_event_panics_on_other_event() {
+	new_test_ext().execute_with(|| {
+		System::initialize(
+			&1,
+			&[0u8; 32].into(),
+			&Default::default(),
+			InitKind::Full,
+		);
+		System::deposit_event(SysEvent::NewAccount(32));
+		System::deposit_event(SysEvent::KilledAccount(42));
+
+		System::assert_last_event(SysEvent::NewAccount(32).into());
+	});
+}
+
 #[test]
 fn deposit_event_uses_actual_weight() {
 	new_test_ext().execute_with(|| {
@@ -407,6 +450,32 @@ fn prunes_block_hash_mappings() {
 	})
 }
 
+#[test]
+fn signing_era_works() {
+	new_test_ext().execute_with(|| {
+		for n in 1..=15 {
+			System::initialize(
+				&n,
+				&[n as u8 - 1; 32].into(),
+				&Default::default(),
+				InitKind::Full,
+			);
+
+			System::finalize();
+		}
+
+		// The period is bounded by `BlockHashCount` and starts at the parent block.
+		let (era, birth, hash) = System::signing_era(64);
+		assert_eq!(era, generic::Era::mortal(8, 14));
+		assert_eq!((birth, hash), (14, [14u8; 32].into()));
+		assert_eq!(System::signing_era(5).0, generic::Era::mortal(4, 14));
+
+		// The transaction is valid in the next block.
+		System::initialize(&16, &[15u8; 32].into(), &Default::default(), InitKind::Full);
+		assert_eq!(CheckMortality::<Test>::from(era).additional_signed(), Ok(hash));
+	})
+}
+
 #[test]
 fn set_code_checks_works() {
 	struct CallInWasm(Vec<u8>);
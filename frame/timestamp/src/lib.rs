@@ -49,6 +49,8 @@
 //! ### Config Getters
 //!
 //! * `MinimumPeriod` - Gets the minimum (and advised) period between blocks for the chain.
+//! * `MaxDrift` - Gets the maximum amount by which a block's timestamp may be ahead of the local
+//! time of the node checking it.
 //!
 //! ## Usage
 //!
@@ -134,6 +136,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinimumPeriod: Get<Self::Moment>;
 
+		/// The maximum amount by which the timestamp of a block may be ahead of the local time of
+		/// the node checking it. Blocks with a timestamp further in the future are rejected.
+		#[pallet::constant]
+		type MaxDrift: Get<Self::Moment>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -166,6 +173,15 @@ pub mod pallet {
 		fn on_finalize(_n: BlockNumberFor<T>) {
 			assert!(DidUpdate::<T>::take(), "Timestamp must be updated once in the block");
 		}
+
+		fn integrity_test() {
+			assert!(!T::MinimumPeriod::get().is_zero(), "`MinimumPeriod` must not be zero");
+			assert!(
+				T::MaxDrift::get() >= T::MinimumPeriod::get(),
+				"`MaxDrift` must not be smaller than `MinimumPeriod`, otherwise blocks authored \
+				with a clock slightly behind are rejected",
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -222,9 +238,6 @@ pub mod pallet {
 		}
 
 		fn check_inherent(call: &Self::Call, data: &InherentData) -> result::Result<(), Self::Error> {
-			const MAX_TIMESTAMP_DRIFT_MILLIS: sp_timestamp::Timestamp =
-				sp_timestamp::Timestamp::new(30 * 1000);
-
 			let t: u64 = match call {
 				Call::set(ref t) => t.clone().saturated_into::<u64>(),
 				_ => return Ok(()),
@@ -233,8 +246,9 @@ pub mod pallet {
 			let data = extract_inherent_data(data).map_err(|e| InherentError::Other(e))?;
 
 			let minimum = (Self::now() + T::MinimumPeriod::get()).saturated_into::<u64>();
-			if t > *(data + MAX_TIMESTAMP_DRIFT_MILLIS) {
-				Err(InherentError::Other("Timestamp too far in future to accept".into()))
+			let maximum = data + T::MaxDrift::get().saturated_into::<u64>();
+			if t > *maximum {
+				Err(InherentError::TooFarInFuture(maximum))
 			} else if t < minimum {
 				Err(InherentError::ValidAtTimestamp(minimum.into()))
 			} else {
@@ -304,7 +318,7 @@ mod tests {
 	use crate as pallet_timestamp;
 	use super::*;
 
-	use frame_support::{assert_ok, parameter_types};
+	use frame_support::{assert_ok, parameter_types, inherent::ProvideInherent};
 	use sp_io::TestExternalities;
 	use sp_core::H256;
 	use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
@@ -360,11 +374,13 @@ mod tests {
 	}
 	parameter_types! {
 		pub const MinimumPeriod: u64 = 5;
+		pub const MaxDrift: u64 = 30_000;
 	}
 	impl Config for Test {
 		type Moment = u64;
 		type OnTimestampSet = ();
 		type MinimumPeriod = MinimumPeriod;
+		type MaxDrift = MaxDrift;
 		type WeightInfo = ();
 	}
 
@@ -395,4 +411,19 @@ mod tests {
 			let _ = Timestamp::set(Origin::none(), 46);
 		});
 	}
+
+	#[test]
+	fn timestamp_beyond_max_drift_is_rejected() {
+		new_test_ext().execute_with(|| {
+			Timestamp::set_timestamp(42);
+			let mut data = InherentData::new();
+			data.put_data(INHERENT_IDENTIFIER, &sp_timestamp::Timestamp::new(100)).unwrap();
+
+			assert!(Timestamp::check_inherent(&Call::set(100 + MaxDrift::get()), &data).is_ok());
+			assert!(matches!(
+				Timestamp::check_inherent(&Call::set(101 + MaxDrift::get()), &data),
+				Err(InherentError::TooFarInFuture(maximum)) if *maximum == 100 + MaxDrift::get()
+			));
+		});
+	}
 }
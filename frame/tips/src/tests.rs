@@ -25,7 +25,7 @@ use std::cell::RefCell;
 use frame_support::{
 	assert_noop, assert_ok, parameter_types,
 	weights::Weight, traits::SortedMembers,
-	PalletId
+	traits::tokens::{PayFromAccount, UnityAssetBalanceConversion}, PalletId
 };
 use sp_runtime::Permill;
 use sp_core::H256;
@@ -128,6 +128,8 @@ parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
 	pub const MaximumReasonLength: u32 = 16384;
 	pub const MaxApprovals: u32 = 100;
+	pub const SpendPayoutPeriod: u64 = 5;
+	pub TreasuryAccount: u128 = Treasury::account_id();
 }
 impl pallet_treasury::Config for Test {
 	type PalletId = TreasuryPalletId;
@@ -144,6 +146,13 @@ impl pallet_treasury::Config for Test {
 	type WeightInfo = ();
 	type SpendFunds = ();
 	type MaxApprovals = MaxApprovals;
+	type SpendOrigin = frame_system::EnsureNever<u64>;
+	type AssetKind = ();
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type Paymaster = PayFromAccount<Balances, TreasuryAccount, u128>;
+	type BalanceConverter = UnityAssetBalanceConversion;
+	type PayoutPeriod = SpendPayoutPeriod;
 }
 parameter_types! {
 	pub const TipCountdown: u64 = 1;
@@ -21,9 +21,10 @@
 
 use super::*;
 
-use frame_system::RawOrigin;
+use frame_system::{EventRecord, RawOrigin};
 use frame_benchmarking::{benchmarks_instance, account, impl_benchmark_test_suite};
-use frame_support::traits::OnInitialize;
+use frame_support::traits::{OnInitialize, UnfilteredDispatchable};
+use sp_runtime::traits::TrailingZeroInput;
 
 use crate::Module as Treasury;
 
@@ -65,6 +66,36 @@ fn setup_pot_account<T: Config<I>, I: Instance>() {
 	let _ = T::Currency::make_free_balance_be(&pot_account, value);
 }
 
+fn assert_last_event<T: Config<I>, I: Instance>(generic_event: <T as Config<I>>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+// Derive a value of some type from a name and a seed, in the manner of `account`.
+fn derive_value<V: Decode>(name: &'static str, seed: u32) -> V {
+	let entropy = (name, seed).encode();
+	V::decode(&mut TrailingZeroInput::new(&entropy[..]))
+		.expect("infinite input; no invalid input; qed")
+}
+
+// Create the arguments for a treasury `spend`, making sure that it can be paid out.
+fn create_spend_arguments<T: Config<I>, I: Instance>(seed: u32) -> (
+	T::AssetKind,
+	AssetBalanceOf<T, I>,
+	T::Beneficiary,
+	BeneficiaryLookupOf<T, I>,
+) {
+	let asset_kind: T::AssetKind = derive_value("asset_kind", seed);
+	let beneficiary: T::Beneficiary = derive_value("beneficiary", seed);
+	let beneficiary_lookup = T::BeneficiaryLookup::unlookup(beneficiary.clone());
+	let amount: AssetBalanceOf<T, I> = 100u32.into();
+	T::Paymaster::ensure_successful(&beneficiary, asset_kind.clone(), amount);
+	(asset_kind, amount, beneficiary, beneficiary_lookup)
+}
+
 benchmarks_instance! {
 
 	propose_spend {
@@ -103,6 +134,68 @@ benchmarks_instance! {
 	}: {
 		Treasury::<T, _>::on_initialize(T::BlockNumber::zero());
 	}
+
+	spend {
+		let origin = T::SpendOrigin::successful_origin();
+		let (asset_kind, amount, beneficiary, beneficiary_lookup) =
+			create_spend_arguments::<T, _>(SEED);
+		let call = Call::<T, I>::spend(
+			asset_kind.clone(),
+			amount,
+			beneficiary_lookup,
+			None,
+		);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		let valid_from = frame_system::Pallet::<T>::block_number();
+		let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+		assert_last_event::<T, I>(RawEvent::AssetSpendApproved(
+			0, asset_kind, amount, beneficiary, valid_from, expire_at,
+		).into());
+	}
+
+	payout {
+		let origin = T::SpendOrigin::successful_origin();
+		let (asset_kind, amount, _, beneficiary_lookup) = create_spend_arguments::<T, _>(SEED);
+		Treasury::<T, _>::spend(origin, asset_kind, amount, beneficiary_lookup, None)?;
+		let caller: T::AccountId = account("caller", 0, SEED);
+	}: _(RawOrigin::Signed(caller), 0u32)
+	verify {
+		let id = match Treasury::<T, I>::spends(0).unwrap().status {
+			PaymentState::Attempted { id, .. } => id,
+			_ => panic!("No payout attempt made"),
+		};
+		assert_last_event::<T, I>(RawEvent::Paid(0, id).into());
+	}
+
+	check_status {
+		let origin = T::SpendOrigin::successful_origin();
+		let (asset_kind, amount, _, beneficiary_lookup) = create_spend_arguments::<T, _>(SEED);
+		Treasury::<T, _>::spend(origin, asset_kind, amount, beneficiary_lookup, None)?;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		Treasury::<T, _>::payout(RawOrigin::Signed(caller.clone()).into(), 0u32)?;
+		match Treasury::<T, I>::spends(0).unwrap().status {
+			PaymentState::Attempted { id, .. } => T::Paymaster::ensure_concluded(id),
+			_ => panic!("No payout attempt made"),
+		};
+	}: _(RawOrigin::Signed(caller), 0u32)
+	verify {
+		if let Some(s) = Treasury::<T, I>::spends(0) {
+			assert!(!matches!(s.status, PaymentState::Attempted { .. }));
+		}
+	}
+
+	void_spend {
+		let origin = T::SpendOrigin::successful_origin();
+		let (asset_kind, amount, _, beneficiary_lookup) = create_spend_arguments::<T, _>(SEED);
+		Treasury::<T, _>::spend(origin, asset_kind, amount, beneficiary_lookup, None)?;
+		assert!(Treasury::<T, I>::spends(0).is_some());
+		let origin = T::RejectOrigin::successful_origin();
+		let call = Call::<T, I>::void_spend(0u32);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Treasury::<T, I>::spends(0).is_none());
+	}
 }
 
 impl_benchmark_test_suite!(
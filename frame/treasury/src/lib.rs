@@ -41,6 +41,8 @@
 //! - **Deposit:** Funds that a proposer must lock when making a proposal. The deposit will be
 //!   returned or slashed if the proposal is approved or rejected respectively.
 //! - **Pot:** Unspent funds accumulated by the treasury module.
+//! - **Spend:** An approved allocation of some asset to a beneficiary, which has to be paid out
+//!   within the payout period.
 //!
 //! ## Interface
 //!
@@ -51,6 +53,14 @@
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
 //!
+//! Direct spending protocol:
+//! - `spend` - Approve a spend of some asset from the treasury, up to the maximum amount allowed
+//!   for the origin. The payout is made through the configured `Paymaster`.
+//! - `payout` - Attempt the payout of an approved spend.
+//! - `check_status` - Check the status of an attempted payout and clean up the spend once it has
+//!   concluded.
+//! - `void_spend` - Void an approved spend which has not yet been paid out.
+//!
 //! ## GenesisConfig
 //!
 //! The Treasury module depends on the [`GenesisConfig`].
@@ -66,11 +76,12 @@ pub mod weights;
 use sp_std::prelude::*;
 use frame_support::{
 	decl_module, decl_storage, decl_event, ensure, print, decl_error,
-	PalletId, BoundedVec, bounded_vec::TryAppendValue,
+	PalletId, BoundedVec, bounded_vec::TryAppendValue, Parameter,
 };
 use frame_support::traits::{
 	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::KeepAlive,
 	ReservableCurrency, WithdrawReasons,
+	tokens::{ConversionFromAssetBalance, Pay, PaymentStatus},
 };
 use sp_runtime::{
 	Permill, RuntimeDebug,
@@ -78,7 +89,8 @@ use sp_runtime::{
 		Zero, StaticLookup, AccountIdConversion, Saturating
 	}
 };
-use frame_support::weights::{Weight, DispatchClass};
+use frame_support::weights::{Weight, DispatchClass, Pays};
+use frame_support::dispatch::DispatchResultWithPostInfo;
 use frame_support::traits::EnsureOrigin;
 use codec::{Encode, Decode};
 use frame_system::ensure_signed;
@@ -90,6 +102,10 @@ pub type PositiveImbalanceOf<T, I=DefaultInstance> =
 	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::PositiveImbalance;
 pub type NegativeImbalanceOf<T, I=DefaultInstance> =
 	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+pub type AssetBalanceOf<T, I=DefaultInstance> = <<T as Config<I>>::Paymaster as Pay>::Balance;
+pub type PayIdOf<T, I=DefaultInstance> = <<T as Config<I>>::Paymaster as Pay>::Id;
+pub type BeneficiaryLookupOf<T, I=DefaultInstance> =
+	<<T as Config<I>>::BeneficiaryLookup as StaticLookup>::Source;
 
 pub trait Config<I=DefaultInstance>: frame_system::Config {
 	/// The treasury's module id, used for deriving its sovereign account ID.
@@ -134,6 +150,33 @@ pub trait Config<I=DefaultInstance>: frame_system::Config {
 
 	/// The maximum number of approvals that can wait in the spending queue.
 	type MaxApprovals: Get<u32>;
+
+	/// Origin from which spends may be approved directly. The `Success` value is the maximum
+	/// amount, in the native balance, that the origin is allowed to spend at a time.
+	type SpendOrigin: EnsureOrigin<Self::Origin, Success = BalanceOf<Self, I>>;
+
+	/// The kinds of asset which may be spent from the treasury.
+	type AssetKind: Parameter;
+
+	/// The identity of the beneficiaries which may receive spends of the treasury.
+	type Beneficiary: Parameter;
+
+	/// Converts the source of a beneficiary into a [`Config::Beneficiary`].
+	type BeneficiaryLookup: StaticLookup<Target = Self::Beneficiary>;
+
+	/// The means of paying out spends of an [`Config::AssetKind`] to a [`Config::Beneficiary`].
+	type Paymaster: Pay<Beneficiary = Self::Beneficiary, AssetKind = Self::AssetKind>;
+
+	/// Converts the balance of an [`Config::AssetKind`] into the native balance, so that the
+	/// amount of a spend can be checked against the limit of the [`Config::SpendOrigin`].
+	type BalanceConverter: ConversionFromAssetBalance<
+		AssetBalanceOf<Self, I>,
+		Self::AssetKind,
+		BalanceOf<Self, I>,
+	>;
+
+	/// The period during which an approved spend has to be paid out.
+	type PayoutPeriod: Get<Self::BlockNumber>;
 }
 
 /// A trait to allow the Treasury Pallet to spend it's funds for other purposes.
@@ -175,6 +218,45 @@ pub struct Proposal<AccountId, Balance> {
 	bond: Balance,
 }
 
+/// An index of an approved spend. Just a `u32`.
+pub type SpendIndex = u32;
+
+/// The state of the payout of a spend.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum PaymentState<Id> {
+	/// Pending claim.
+	Pending,
+	/// Payment attempted with a payment identifier.
+	Attempted { id: Id },
+	/// Payment failed.
+	Failed,
+}
+
+/// An approved spend of some asset from the treasury.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SpendStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, PaymentId> {
+	/// The kind of asset to be spent.
+	pub asset_kind: AssetKind,
+	/// The amount of the asset to be paid out.
+	pub amount: AssetBalance,
+	/// The beneficiary of the spend.
+	pub beneficiary: Beneficiary,
+	/// The block number from which the spend can be paid out.
+	pub valid_from: BlockNumber,
+	/// The block number after which the spend can no longer be paid out.
+	pub expire_at: BlockNumber,
+	/// The state of the payout.
+	pub status: PaymentState<PaymentId>,
+}
+
+pub type SpendStatusOf<T, I=DefaultInstance> = SpendStatus<
+	<T as Config<I>>::AssetKind,
+	AssetBalanceOf<T, I>,
+	<T as Config<I>>::Beneficiary,
+	<T as frame_system::Config>::BlockNumber,
+	PayIdOf<T, I>,
+>;
+
 decl_storage! {
 	trait Store for Module<T: Config<I>, I: Instance=DefaultInstance> as Treasury {
 		/// Number of proposals that have been made.
@@ -187,6 +269,13 @@ decl_storage! {
 
 		/// Proposal indices that have been approved but not yet awarded.
 		pub Approvals get(fn approvals): BoundedVec<ProposalIndex, T::MaxApprovals>;
+
+		/// Number of spends that have been approved.
+		SpendCount get(fn spend_count): SpendIndex;
+
+		/// Spends that have been approved and are being processed.
+		pub Spends get(fn spends):
+			map hasher(twox_64_concat) SpendIndex => Option<SpendStatusOf<T, I>>;
 	}
 	add_extra_genesis {
 		build(|_config| {
@@ -205,6 +294,11 @@ decl_event!(
 	where
 		Balance = BalanceOf<T, I>,
 		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		AssetKind = <T as Config<I>>::AssetKind,
+		AssetBalance = AssetBalanceOf<T, I>,
+		Beneficiary = <T as Config<I>>::Beneficiary,
+		PaymentId = PayIdOf<T, I>,
 	{
 		/// New proposal. \[proposal_index\]
 		Proposed(ProposalIndex),
@@ -221,6 +315,18 @@ decl_event!(
 		Rollover(Balance),
 		/// Some funds have been deposited. \[deposit\]
 		Deposit(Balance),
+		/// A spend of some asset has been approved.
+		/// \[index, asset_kind, amount, beneficiary, valid_from, expire_at\]
+		AssetSpendApproved(SpendIndex, AssetKind, AssetBalance, Beneficiary, BlockNumber, BlockNumber),
+		/// An approved spend was voided. \[index\]
+		AssetSpendVoided(SpendIndex),
+		/// A payment happened. \[index, payment_id\]
+		Paid(SpendIndex, PaymentId),
+		/// A payment failed and can be retried. \[index, payment_id\]
+		PaymentFailed(SpendIndex, PaymentId),
+		/// A spend was processed and removed from the storage. It might have been successfully
+		/// paid or it may have expired. \[index\]
+		SpendProcessed(SpendIndex),
 	}
 );
 
@@ -233,6 +339,23 @@ decl_error! {
 		InvalidIndex,
 		/// Too many approvals in the queue.
 		TooManyApprovals,
+		/// The spend origin is valid but the amount it is allowed to spend is lower than the
+		/// amount to be spent.
+		InsufficientPermission,
+		/// The balance of the asset kind is not convertible to the balance of the native asset.
+		FailedToConvertBalance,
+		/// The spend has expired and cannot be paid out.
+		SpendExpired,
+		/// The spend is not yet eligible for payout.
+		EarlyPayout,
+		/// The payment has already been attempted.
+		AlreadyAttempted,
+		/// There was some issue with the mechanism of payment.
+		PayoutError,
+		/// The payout was not yet attempted.
+		NotAttempted,
+		/// The payment has neither failed nor succeeded yet.
+		Inconclusive,
 	}
 }
 
@@ -257,6 +380,9 @@ decl_module! {
 		/// The treasury's module id, used for deriving its sovereign account ID.
 		const PalletId: PalletId = T::PalletId::get();
 
+		/// The period during which an approved spend has to be paid out.
+		const PayoutPeriod: T::BlockNumber = T::PayoutPeriod::get();
+
 		type Error = Error<T, I>;
 
 		fn deposit_event() = default;
@@ -329,6 +455,157 @@ decl_module! {
 			Approvals::<T, I>::try_append(proposal_id).map_err(|_| Error::<T, I>::TooManyApprovals)?;
 		}
 
+		/// Approve a spend of `amount` of `asset_kind` to `beneficiary`, to be paid out through
+		/// `T::Paymaster` by a later call to `payout`.
+		///
+		/// May only be called from `T::SpendOrigin`, and the `amount`, converted to the native
+		/// balance, must not exceed the maximum amount allowed for the origin.
+		///
+		/// The spend can be paid out from `valid_from`, which defaults to the current block, until
+		/// `T::PayoutPeriod` blocks later.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `SpendCount`
+		/// - DbWrites: `SpendCount`, `Spends`
+		/// # </weight>
+		#[weight = T::WeightInfo::spend()]
+		pub fn spend(
+			origin,
+			asset_kind: T::AssetKind,
+			#[compact] amount: AssetBalanceOf<T, I>,
+			beneficiary: BeneficiaryLookupOf<T, I>,
+			valid_from: Option<T::BlockNumber>,
+		) {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(beneficiary)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let valid_from = valid_from.unwrap_or(now);
+			let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+			ensure!(expire_at > now, Error::<T, I>::SpendExpired);
+
+			let native_amount = T::BalanceConverter::from_asset_balance(amount, asset_kind.clone())
+				.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
+			ensure!(native_amount <= max_amount, Error::<T, I>::InsufficientPermission);
+
+			let index = Self::spend_count();
+			<SpendCount<I>>::put(index + 1);
+			<Spends<T, I>>::insert(index, SpendStatus {
+				asset_kind: asset_kind.clone(),
+				amount,
+				beneficiary: beneficiary.clone(),
+				valid_from,
+				expire_at,
+				status: PaymentState::Pending,
+			});
+
+			Self::deposit_event(RawEvent::AssetSpendApproved(
+				index, asset_kind, amount, beneficiary, valid_from, expire_at,
+			));
+		}
+
+		/// Attempt the payout of the approved spend `index`.
+		///
+		/// Any signed origin may call this once the spend is valid and before it expires. The
+		/// status of the payment must afterwards be checked with `check_status`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `Spends`
+		/// - DbWrites: `Spends`
+		/// # </weight>
+		#[weight = T::WeightInfo::payout()]
+		pub fn payout(origin, #[compact] index: SpendIndex) {
+			ensure_signed(origin)?;
+
+			let mut spend = Self::spends(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= spend.valid_from, Error::<T, I>::EarlyPayout);
+			ensure!(spend.expire_at > now, Error::<T, I>::SpendExpired);
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted,
+			);
+
+			let id = T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
+				.map_err(|_| Error::<T, I>::PayoutError)?;
+
+			spend.status = PaymentState::Attempted { id };
+			<Spends<T, I>>::insert(index, spend);
+
+			Self::deposit_event(RawEvent::Paid(index, id));
+		}
+
+		/// Check the status of the payout of the spend `index`.
+		///
+		/// Any signed origin may call this. The spend is removed once its payment succeeded or
+		/// once it expired, and is marked as failed if the payment failed, so that the payout
+		/// can be retried. The call is free whenever it makes progress.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `Spends`
+		/// - DbWrites: `Spends`
+		/// # </weight>
+		#[weight = T::WeightInfo::check_status()]
+		pub fn check_status(origin, #[compact] index: SpendIndex) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let mut spend = Self::spends(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			if now > spend.expire_at && !matches!(spend.status, PaymentState::Attempted { .. }) {
+				<Spends<T, I>>::remove(index);
+				Self::deposit_event(RawEvent::SpendProcessed(index));
+				return Ok(Pays::No.into())
+			}
+
+			let payment_id = match spend.status {
+				PaymentState::Attempted { id } => id,
+				_ => return Err(Error::<T, I>::NotAttempted.into()),
+			};
+
+			match T::Paymaster::check_payment(payment_id) {
+				PaymentStatus::Failure => {
+					spend.status = PaymentState::Failed;
+					<Spends<T, I>>::insert(index, spend);
+					Self::deposit_event(RawEvent::PaymentFailed(index, payment_id));
+				},
+				PaymentStatus::Success | PaymentStatus::Unknown => {
+					<Spends<T, I>>::remove(index);
+					Self::deposit_event(RawEvent::SpendProcessed(index));
+				},
+				PaymentStatus::InProgress => return Err(Error::<T, I>::Inconclusive.into()),
+			}
+
+			Ok(Pays::No.into())
+		}
+
+		/// Void the approved spend `index`, provided its payout has not been attempted, or has
+		/// failed.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `Spends`
+		/// - DbWrites: `Spends`
+		/// # </weight>
+		#[weight = T::WeightInfo::void_spend()]
+		pub fn void_spend(origin, #[compact] index: SpendIndex) {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let spend = Self::spends(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted,
+			);
+
+			<Spends<T, I>>::remove(index);
+			Self::deposit_event(RawEvent::AssetSpendVoided(index));
+		}
+
 		/// # <weight>
 		/// - Complexity: `O(A)` where `A` is the number of approvals
 		/// - Db reads and writes: `Approvals`, `pot account data`
@@ -21,16 +21,17 @@
 
 use crate as treasury;
 use super::*;
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::BTreeMap};
 use frame_support::{
 	assert_noop, assert_ok, parameter_types,
-	traits::OnInitialize, PalletId
+	traits::{OnInitialize, tokens::{ConversionFromAssetBalance, Pay, PaymentStatus}},
+	PalletId,
 };
 
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -103,7 +104,95 @@ parameter_types! {
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: u64 = 1;
 	pub const MaxApprovals: u32 = 100;
+	pub const SpendPayoutPeriod: u64 = 5;
 }
+
+thread_local! {
+	pub static PAID: RefCell<BTreeMap<(u128, u32), u64>> = RefCell::new(BTreeMap::new());
+	pub static STATUS: RefCell<BTreeMap<u64, PaymentStatus>> = RefCell::new(BTreeMap::new());
+	pub static LAST_ID: RefCell<u64> = RefCell::new(0u64);
+}
+
+/// Paid balance for a given account and asset ids
+fn paid(who: u128, asset_id: u32) -> u64 {
+	PAID.with(|p| p.borrow().get(&(who, asset_id)).cloned().unwrap_or(0))
+}
+
+/// Reduce paid balance for a given account and asset ids
+fn unpay(who: u128, asset_id: u32, amount: u64) {
+	PAID.with(|p| {
+		let mut paid = p.borrow_mut();
+		let balance = paid.entry((who, asset_id)).or_default();
+		*balance = balance.saturating_sub(amount);
+	})
+}
+
+/// Set status for a given payment id
+fn set_status(id: u64, s: PaymentStatus) {
+	STATUS.with(|m| m.borrow_mut().insert(id, s));
+}
+
+pub struct TestPay;
+impl Pay for TestPay {
+	type Beneficiary = u128;
+	type Balance = u64;
+	type Id = u64;
+	type AssetKind = u32;
+	type Error = ();
+
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> Result<Self::Id, Self::Error> {
+		PAID.with(|paid| *paid.borrow_mut().entry((*who, asset_kind)).or_default() += amount);
+		Ok(LAST_ID.with(|lid| {
+			let x = *lid.borrow();
+			lid.replace(x + 1);
+			x
+		}))
+	}
+	fn check_payment(id: Self::Id) -> PaymentStatus {
+		STATUS.with(|s| s.borrow().get(&id).cloned().unwrap_or(PaymentStatus::Unknown))
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_: &Self::Beneficiary, _: Self::AssetKind, _: Self::Balance) {}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(id: Self::Id) {
+		set_status(id, PaymentStatus::Failure)
+	}
+}
+
+/// Allows `Root` to spend any amount, and the signed accounts 10 to 13 to spend increasing
+/// amounts.
+pub struct TestSpendOrigin;
+impl frame_support::traits::EnsureOrigin<Origin> for TestSpendOrigin {
+	type Success = u64;
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		Result::<frame_system::RawOrigin<_>, Origin>::from(o).and_then(|o| match o {
+			frame_system::RawOrigin::Root => Ok(u64::max_value()),
+			frame_system::RawOrigin::Signed(10) => Ok(5),
+			frame_system::RawOrigin::Signed(11) => Ok(10),
+			frame_system::RawOrigin::Signed(12) => Ok(20),
+			frame_system::RawOrigin::Signed(13) => Ok(50),
+			r => Err(Origin::from(r)),
+		})
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::root()
+	}
+}
+
+/// Converts an asset balance into the native balance by doubling it.
+pub struct MulByTwo;
+impl ConversionFromAssetBalance<u64, u32, u64> for MulByTwo {
+	type Error = ();
+	fn from_asset_balance(balance: u64, _asset_id: u32) -> Result<u64, Self::Error> {
+		balance.checked_mul(2).ok_or(())
+	}
+}
+
 impl Config for Test {
 	type PalletId = TreasuryPalletId;
 	type Currency = pallet_balances::Pallet<Test>;
@@ -119,6 +208,22 @@ impl Config for Test {
 	type WeightInfo = ();
 	type SpendFunds = ();
 	type MaxApprovals = MaxApprovals;
+	type SpendOrigin = TestSpendOrigin;
+	type AssetKind = u32;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type Paymaster = TestPay;
+	type BalanceConverter = MulByTwo;
+	type PayoutPeriod = SpendPayoutPeriod;
+}
+
+fn last_event() -> treasury::Event<Test> {
+	System::events().into_iter().map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::treasury(inner) = e { Some(inner) } else { None }
+		})
+		.last()
+		.unwrap()
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -378,3 +483,163 @@ fn max_approvals_limited() {
 		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), Error::<Test, _>::TooManyApprovals);
 	});
 }
+
+#[test]
+fn spend_origin_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 1, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		// The converted amount exceeds the limit of the origin.
+		assert_noop!(
+			Treasury::spend(Origin::signed(10), 1, 3, 6, None),
+			Error::<Test, _>::InsufficientPermission,
+		);
+		assert_ok!(Treasury::spend(Origin::signed(11), 1, 5, 6, None));
+		assert_noop!(
+			Treasury::spend(Origin::signed(11), 1, 6, 6, None),
+			Error::<Test, _>::InsufficientPermission,
+		);
+		assert_ok!(Treasury::spend(Origin::signed(12), 1, 10, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(13), 1, 25, 6, None));
+		assert_ok!(Treasury::spend(Origin::root(), 1, 1_000, 6, None));
+		// Unknown origins may not spend at all.
+		assert_noop!(Treasury::spend(Origin::signed(1), 1, 1, 6, None), BadOrigin);
+
+		assert_eq!(Treasury::spend_count(), 6);
+	});
+}
+
+#[test]
+fn spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+
+		assert_eq!(Treasury::spend_count(), 1);
+		assert_eq!(
+			Treasury::spends(0),
+			Some(SpendStatus {
+				asset_kind: 1,
+				amount: 2,
+				beneficiary: 6,
+				valid_from: 1,
+				expire_at: 6,
+				status: PaymentState::Pending,
+			})
+		);
+		assert_eq!(last_event(), RawEvent::AssetSpendApproved(0, 1, 2, 6, 1, 6));
+	});
+}
+
+#[test]
+fn spend_expires() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(6);
+		// A spend whose payout period has already passed can not be approved.
+		assert_noop!(
+			Treasury::spend(Origin::signed(10), 1, 2, 6, Some(0)),
+			Error::<Test, _>::SpendExpired,
+		);
+
+		// A spend which is not paid out before it expires can no longer be paid out.
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		System::set_block_number(11);
+		assert_noop!(Treasury::payout(Origin::signed(1), 0), Error::<Test, _>::SpendExpired);
+	});
+}
+
+#[test]
+fn valid_from_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, Some(2)));
+		assert_noop!(Treasury::payout(Origin::signed(1), 0), Error::<Test, _>::EarlyPayout);
+
+		System::set_block_number(2);
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+	});
+}
+
+#[test]
+fn payout_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+		assert_eq!(last_event(), RawEvent::Paid(0, 0));
+		// A payout can only be attempted once.
+		assert_noop!(Treasury::payout(Origin::signed(1), 0), Error::<Test, _>::AlreadyAttempted);
+
+		set_status(0, PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::SpendProcessed(0));
+		assert_eq!(Treasury::spends(0), None);
+		assert_noop!(Treasury::payout(Origin::signed(1), 0), Error::<Test, _>::InvalidIndex);
+	});
+}
+
+#[test]
+fn payout_retry_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_noop!(
+			Treasury::check_status(Origin::signed(1), 0),
+			Error::<Test, _>::NotAttempted,
+		);
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+
+		set_status(0, PaymentStatus::InProgress);
+		assert_noop!(
+			Treasury::check_status(Origin::signed(1), 0),
+			Error::<Test, _>::Inconclusive,
+		);
+
+		// The payment fails, so it can be retried.
+		unpay(6, 1, 2);
+		set_status(0, PaymentStatus::Failure);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::PaymentFailed(0, 0));
+		assert_eq!(Treasury::spends(0).unwrap().status, PaymentState::Failed);
+
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+		assert_eq!(last_event(), RawEvent::Paid(0, 1));
+	});
+}
+
+#[test]
+fn expired_spend_is_cleaned_up() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+
+		System::set_block_number(7);
+		let info = Treasury::check_status(Origin::signed(1), 0).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		assert_eq!(last_event(), RawEvent::SpendProcessed(0));
+		assert_eq!(Treasury::spends(0), None);
+	});
+}
+
+#[test]
+fn void_spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::payout(Origin::signed(1), 1));
+
+		assert_noop!(Treasury::void_spend(Origin::signed(10), 0), BadOrigin);
+		assert_ok!(Treasury::void_spend(Origin::root(), 0));
+		assert_eq!(last_event(), RawEvent::AssetSpendVoided(0));
+		assert_eq!(Treasury::spends(0), None);
+		assert_noop!(Treasury::payout(Origin::signed(1), 0), Error::<Test, _>::InvalidIndex);
+
+		// A spend whose payout has already been attempted can not be voided.
+		assert_noop!(Treasury::void_spend(Origin::root(), 1), Error::<Test, _>::AlreadyAttempted);
+	});
+}
@@ -0,0 +1,391 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Uniques pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use sp_std::{prelude::*, convert::TryInto};
+use super::*;
+use sp_runtime::traits::Bounded;
+use frame_system::{EventRecord, RawOrigin as SystemOrigin};
+use frame_benchmarking::{
+	benchmarks_instance_pallet, account, whitelisted_caller, whitelist_account,
+	impl_benchmark_test_suite,
+};
+use frame_support::{traits::{Get, EnsureOrigin}, dispatch::UnfilteredDispatchable, BoundedVec};
+
+use crate::Pallet as Uniques;
+
+const SEED: u32 = 0;
+
+fn create_collection<T: Config<I>, I: 'static>()
+	-> (T::CollectionId, T::AccountId, <T::Lookup as StaticLookup>::Source)
+{
+	let caller: T::AccountId = whitelisted_caller();
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	let collection = Default::default();
+	T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T, I>::max_value());
+	assert!(Uniques::<T, I>::create(
+		SystemOrigin::Signed(caller.clone()).into(),
+		collection,
+		caller_lookup.clone(),
+	).is_ok());
+	(collection, caller, caller_lookup)
+}
+
+fn add_collection_metadata<T: Config<I>, I: 'static>()
+	-> (T::AccountId, <T::Lookup as StaticLookup>::Source)
+{
+	let caller = Collection::<T, I>::get(T::CollectionId::default()).unwrap().owner;
+	if caller != whitelisted_caller() {
+		whitelist_account!(caller);
+	}
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	assert!(Uniques::<T, I>::set_collection_metadata(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Default::default(),
+		vec![0; T::StringLimit::get() as usize],
+		false,
+	).is_ok());
+	(caller, caller_lookup)
+}
+
+fn mint_item<T: Config<I>, I: 'static>(index: u16)
+	-> (T::ItemId, T::AccountId, <T::Lookup as StaticLookup>::Source)
+	where T::ItemId: From<u16>
+{
+	let caller = Collection::<T, I>::get(T::CollectionId::default()).unwrap().admin;
+	if caller != whitelisted_caller() {
+		whitelist_account!(caller);
+	}
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	let item = index.into();
+	assert!(Uniques::<T, I>::mint(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Default::default(),
+		item,
+		caller_lookup.clone(),
+	).is_ok());
+	(item, caller, caller_lookup)
+}
+
+fn add_item_metadata<T: Config<I>, I: 'static>(item: T::ItemId)
+	-> (T::AccountId, <T::Lookup as StaticLookup>::Source)
+{
+	let caller = Collection::<T, I>::get(T::CollectionId::default()).unwrap().owner;
+	if caller != whitelisted_caller() {
+		whitelist_account!(caller);
+	}
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	assert!(Uniques::<T, I>::set_metadata(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Default::default(),
+		item,
+		vec![0; T::StringLimit::get() as usize],
+		false,
+	).is_ok());
+	(caller, caller_lookup)
+}
+
+fn add_item_attribute<T: Config<I>, I: 'static>(item: T::ItemId)
+	-> (BoundedVec<u8, T::KeyLimit>, T::AccountId, <T::Lookup as StaticLookup>::Source)
+{
+	let caller = Collection::<T, I>::get(T::CollectionId::default()).unwrap().owner;
+	if caller != whitelisted_caller() {
+		whitelist_account!(caller);
+	}
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	let key: BoundedVec<u8, T::KeyLimit> =
+		vec![0; T::KeyLimit::get() as usize].try_into().unwrap();
+	assert!(Uniques::<T, I>::set_attribute(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Default::default(),
+		Some(item),
+		key.to_vec(),
+		vec![0; T::ValueLimit::get() as usize],
+	).is_ok());
+	(key, caller, caller_lookup)
+}
+
+fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks_instance_pallet! {
+	where_clause { where T::ItemId: From<u16> }
+
+	create {
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T, I>::max_value());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		let event = Event::Created(Default::default(), caller.clone(), caller);
+		assert_last_event::<T, I>(event.into());
+	}
+
+	force_create {
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+	}: _(SystemOrigin::Root, Default::default(), caller_lookup, true)
+	verify {
+		assert_last_event::<T, I>(Event::ForceCreated(Default::default(), caller).into());
+	}
+
+	destroy {
+		let n in 0 .. 1_000;
+		let m in 0 .. 1_000;
+		let a in 0 .. 1_000;
+
+		let (collection, caller, _) = create_collection::<T, I>();
+		add_collection_metadata::<T, I>();
+		for i in 0..n {
+			mint_item::<T, I>(i as u16);
+		}
+		for i in 0..m {
+			add_item_metadata::<T, I>((i as u16).into());
+		}
+		for i in 0..a {
+			add_item_attribute::<T, I>((i as u16).into());
+		}
+		let witness = Collection::<T, I>::get(collection).unwrap().destroy_witness();
+	}: _(SystemOrigin::Signed(caller), collection, witness)
+	verify {
+		assert_last_event::<T, I>(Event::Destroyed(collection).into());
+	}
+
+	mint {
+		let (collection, caller, caller_lookup) = create_collection::<T, I>();
+		let item = Default::default();
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, caller_lookup)
+	verify {
+		assert_last_event::<T, I>(Event::Issued(collection, item, caller).into());
+	}
+
+	burn {
+		let (collection, caller, caller_lookup) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, Some(caller_lookup))
+	verify {
+		assert_last_event::<T, I>(Event::Burned(collection, item, caller).into());
+	}
+
+	transfer {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, target_lookup)
+	verify {
+		assert_last_event::<T, I>(Event::Transferred(collection, item, caller, target).into());
+	}
+
+	redeposit {
+		let i in 0 .. 5_000;
+		let (collection, caller, caller_lookup) = create_collection::<T, I>();
+		let items = (0..i).map(|x| mint_item::<T, I>(x as u16).0).collect::<Vec<_>>();
+		Uniques::<T, I>::force_item_status(
+			SystemOrigin::Root.into(),
+			collection,
+			caller_lookup.clone(),
+			caller_lookup.clone(),
+			caller_lookup.clone(),
+			caller_lookup.clone(),
+			true,
+			false,
+		)?;
+	}: _(SystemOrigin::Signed(caller.clone()), collection, items.clone())
+	verify {
+		assert_last_event::<T, I>(Event::Redeposited(collection, items).into());
+	}
+
+	freeze {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::Frozen(collection, item).into());
+	}
+
+	thaw {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		Uniques::<T, I>::freeze(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			item,
+		)?;
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::Thawed(collection, item).into());
+	}
+
+	freeze_collection {
+		let (collection, caller, _) = create_collection::<T, I>();
+	}: _(SystemOrigin::Signed(caller.clone()), collection)
+	verify {
+		assert_last_event::<T, I>(Event::CollectionFrozen(collection).into());
+	}
+
+	thaw_collection {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let origin = SystemOrigin::Signed(caller.clone()).into();
+		Uniques::<T, I>::freeze_collection(origin, collection)?;
+	}: _(SystemOrigin::Signed(caller.clone()), collection)
+	verify {
+		assert_last_event::<T, I>(Event::CollectionThawed(collection).into());
+	}
+
+	transfer_ownership {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+	}: _(SystemOrigin::Signed(caller), collection, target_lookup)
+	verify {
+		assert_last_event::<T, I>(Event::OwnerChanged(collection, target).into());
+	}
+
+	set_team {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let target0 = T::Lookup::unlookup(account("target", 0, SEED));
+		let target1 = T::Lookup::unlookup(account("target", 1, SEED));
+		let target2 = T::Lookup::unlookup(account("target", 2, SEED));
+	}: _(SystemOrigin::Signed(caller), collection, target0, target1, target2)
+	verify {
+		assert_last_event::<T, I>(Event::TeamChanged(
+			collection,
+			account("target", 0, SEED),
+			account("target", 1, SEED),
+			account("target", 2, SEED),
+		).into());
+	}
+
+	force_item_status {
+		let (collection, _, caller_lookup) = create_collection::<T, I>();
+		let origin = T::ForceOrigin::successful_origin();
+		let call = Call::<T, I>::force_item_status(
+			collection,
+			caller_lookup.clone(),
+			caller_lookup.clone(),
+			caller_lookup.clone(),
+			caller_lookup.clone(),
+			true,
+			false,
+		);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T, I>(Event::ItemStatusChanged(collection).into());
+	}
+
+	set_attribute {
+		let key = vec![0u8; T::KeyLimit::get() as usize];
+		let value = vec![0u8; T::ValueLimit::get() as usize];
+
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		add_item_metadata::<T, I>(item);
+	}: _(SystemOrigin::Signed(caller), collection, Some(item), key.clone(), value.clone())
+	verify {
+		assert_last_event::<T, I>(Event::AttributeSet(
+			collection,
+			Some(item),
+			key.try_into().unwrap(),
+			value.try_into().unwrap(),
+		).into());
+	}
+
+	clear_attribute {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		add_item_metadata::<T, I>(item);
+		let (key, ..) = add_item_attribute::<T, I>(item);
+	}: _(SystemOrigin::Signed(caller), collection, Some(item), key.to_vec())
+	verify {
+		assert_last_event::<T, I>(Event::AttributeCleared(collection, Some(item), key).into());
+	}
+
+	set_metadata {
+		let data = vec![0u8; T::StringLimit::get() as usize];
+
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+	}: _(SystemOrigin::Signed(caller), collection, item, data.clone(), false)
+	verify {
+		let data = data.try_into().unwrap();
+		assert_last_event::<T, I>(Event::MetadataSet(collection, item, data, false).into());
+	}
+
+	clear_metadata {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		add_item_metadata::<T, I>(item);
+	}: _(SystemOrigin::Signed(caller), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::MetadataCleared(collection, item).into());
+	}
+
+	set_collection_metadata {
+		let data = vec![0u8; T::StringLimit::get() as usize];
+
+		let (collection, caller, _) = create_collection::<T, I>();
+	}: _(SystemOrigin::Signed(caller), collection, data.clone(), false)
+	verify {
+		let data = data.try_into().unwrap();
+		assert_last_event::<T, I>(Event::CollectionMetadataSet(collection, data, false).into());
+	}
+
+	clear_collection_metadata {
+		let (collection, caller, _) = create_collection::<T, I>();
+		add_collection_metadata::<T, I>();
+	}: _(SystemOrigin::Signed(caller), collection)
+	verify {
+		assert_last_event::<T, I>(Event::CollectionMetadataCleared(collection).into());
+	}
+
+	approve_transfer {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, delegate_lookup)
+	verify {
+		let event = Event::ApprovedTransfer(collection, item, caller, delegate);
+		assert_last_event::<T, I>(event.into());
+	}
+
+	cancel_approval {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		let origin = SystemOrigin::Signed(caller.clone()).into();
+		Uniques::<T, I>::approve_transfer(origin, collection, item, delegate_lookup.clone())?;
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, Some(delegate_lookup))
+	verify {
+		let event = Event::ApprovalCancelled(collection, item, caller, delegate);
+		assert_last_event::<T, I>(event.into());
+	}
+}
+
+impl_benchmark_test_suite!(Uniques, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,123 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Various pieces of common functionality.
+
+use super::*;
+use frame_support::{ensure, traits::Get};
+use sp_runtime::{DispatchResult, DispatchError};
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	pub(crate) fn do_transfer(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		dest: T::AccountId,
+		with_details: impl FnOnce(
+			&CollectionDetailsFor<T, I>,
+			&mut ItemDetailsFor<T, I>,
+		) -> DispatchResult,
+	) -> DispatchResult {
+		let collection_details = Collection::<T, I>::get(&collection)
+			.ok_or(Error::<T, I>::UnknownCollection)?;
+		ensure!(!collection_details.is_frozen, Error::<T, I>::Frozen);
+
+		let mut details = Item::<T, I>::get(collection, item)
+			.ok_or(Error::<T, I>::UnknownCollection)?;
+		ensure!(!details.is_frozen, Error::<T, I>::Frozen);
+		with_details(&collection_details, &mut details)?;
+
+		Account::<T, I>::remove(&details.owner, (collection, item));
+		Account::<T, I>::insert(&dest, (collection, item), ());
+		let origin = details.owner;
+		details.owner = dest;
+		details.approved = None;
+		Item::<T, I>::insert(&collection, &item, &details);
+
+		Self::deposit_event(Event::Transferred(collection, item, origin, details.owner));
+		Ok(())
+	}
+
+	pub(crate) fn do_mint(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		owner: T::AccountId,
+		with_details: impl FnOnce(&CollectionDetailsFor<T, I>) -> DispatchResult,
+	) -> DispatchResult {
+		ensure!(!Item::<T, I>::contains_key(collection, item), Error::<T, I>::AlreadyExists);
+
+		Collection::<T, I>::try_mutate(&collection, |maybe_collection_details| -> DispatchResult {
+			let collection_details = maybe_collection_details
+				.as_mut()
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+
+			with_details(&collection_details)?;
+
+			collection_details.items = collection_details.items.saturating_add(1);
+
+			let deposit = match collection_details.free_holding {
+				true => Zero::zero(),
+				false => T::ItemDeposit::get(),
+			};
+			T::Currency::reserve(&collection_details.owner, deposit)?;
+			collection_details.total_deposit =
+				collection_details.total_deposit.saturating_add(deposit);
+
+			let owner = owner.clone();
+			Account::<T, I>::insert(&owner, (collection, item), ());
+			let details = ItemDetails { owner, approved: None, is_frozen: false, deposit };
+			Item::<T, I>::insert(&collection, &item, details);
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::Issued(collection, item, owner));
+		Ok(())
+	}
+
+	pub(crate) fn do_burn(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		with_details: impl FnOnce(
+			&CollectionDetailsFor<T, I>,
+			&ItemDetailsFor<T, I>,
+		) -> DispatchResult,
+	) -> DispatchResult {
+		let owner = Collection::<T, I>::try_mutate(
+			&collection,
+			|maybe_collection_details| -> Result<T::AccountId, DispatchError> {
+				let collection_details = maybe_collection_details
+					.as_mut()
+					.ok_or(Error::<T, I>::UnknownCollection)?;
+				let details = Item::<T, I>::get(collection, item)
+					.ok_or(Error::<T, I>::UnknownCollection)?;
+				with_details(&collection_details, &details)?;
+
+				// Return the deposit.
+				T::Currency::unreserve(&collection_details.owner, details.deposit);
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_sub(details.deposit);
+				collection_details.items = collection_details.items.saturating_sub(1);
+				Ok(details.owner)
+			},
+		)?;
+
+		Item::<T, I>::remove(collection, item);
+		Account::<T, I>::remove(&owner, (collection, item));
+
+		Self::deposit_event(Event::Burned(collection, item, owner));
+		Ok(())
+	}
+}
@@ -0,0 +1,147 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations for `nonfungibles` traits.
+
+use super::*;
+use sp_std::convert::TryFrom;
+use frame_support::traits::tokens::nonfungibles::{Inspect, InspectEnumerable, Mutate, Transfer};
+use frame_support::BoundedVec;
+use sp_runtime::DispatchResult;
+
+impl<T: Config<I>, I: 'static> Inspect<<T as SystemConfig>::AccountId> for Pallet<T, I> {
+	type ItemId = T::ItemId;
+	type CollectionId = T::CollectionId;
+
+	fn owner(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+	) -> Option<<T as SystemConfig>::AccountId> {
+		Item::<T, I>::get(collection, item).map(|a| a.owner)
+	}
+
+	fn collection_owner(collection: &Self::CollectionId) -> Option<<T as SystemConfig>::AccountId> {
+		Collection::<T, I>::get(collection).map(|a| a.owner)
+	}
+
+	/// Returns the attribute value of `item` of `collection` corresponding to `key`.
+	///
+	/// When `key` is empty, we return the item metadata value.
+	///
+	/// By default this is `None`; no attributes are defined.
+	fn attribute(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		if key.is_empty() {
+			// We make the empty key map to the item metadata value.
+			ItemMetadataOf::<T, I>::get(collection, item).map(|m| m.data.into_inner())
+		} else {
+			let key = BoundedVec::<_, _>::try_from(key.to_vec()).ok()?;
+			Attribute::<T, I>::get(collection, (Some(*item), key)).map(|a| a.0.into_inner())
+		}
+	}
+
+	/// Returns the attribute value of `collection` corresponding to `key`.
+	///
+	/// When `key` is empty, we return the collection metadata value.
+	///
+	/// By default this is `None`; no attributes are defined.
+	fn collection_attribute(collection: &Self::CollectionId, key: &[u8]) -> Option<Vec<u8>> {
+		if key.is_empty() {
+			// We make the empty key map to the collection metadata value.
+			CollectionMetadataOf::<T, I>::get(collection).map(|m| m.data.into_inner())
+		} else {
+			let key = BoundedVec::<_, _>::try_from(key.to_vec()).ok()?;
+			Attribute::<T, I>::get(collection, (Option::<T::ItemId>::None, key))
+				.map(|a| a.0.into_inner())
+		}
+	}
+
+	/// Returns `true` if the `item` of `collection` may be transferred.
+	///
+	/// Default implementation is that all items are transferable.
+	fn can_transfer(collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		match (Collection::<T, I>::get(collection), Item::<T, I>::get(collection, item)) {
+			(Some(cd), Some(id)) if !cd.is_frozen && !id.is_frozen => true,
+			_ => false,
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> InspectEnumerable<T::AccountId> for Pallet<T, I> {
+	/// Returns an iterator of the collections in existence.
+	///
+	/// NOTE: iterating this list invokes a storage read per item.
+	fn collections() -> Box<dyn Iterator<Item = Self::CollectionId>> {
+		Box::new(Collection::<T, I>::iter().map(|(collection, _)| collection))
+	}
+
+	/// Returns an iterator of the items of a `collection` in existence.
+	///
+	/// NOTE: iterating this list invokes a storage read per item.
+	fn items(collection: &Self::CollectionId) -> Box<dyn Iterator<Item = Self::ItemId>> {
+		Box::new(Item::<T, I>::iter_prefix(collection).map(|(item, _)| item))
+	}
+
+	/// Returns an iterator of the items of all collections owned by `who`.
+	///
+	/// NOTE: iterating this list invokes a storage read per item.
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (Self::CollectionId, Self::ItemId)>> {
+		Box::new(Account::<T, I>::iter_prefix(who).map(|(key, _)| key))
+	}
+
+	/// Returns an iterator of the items of `collection` owned by `who`.
+	///
+	/// NOTE: iterating this list invokes a storage read per item.
+	fn owned_in_collection(
+		collection: &Self::CollectionId,
+		who: &T::AccountId,
+	) -> Box<dyn Iterator<Item = Self::ItemId>> {
+		let collection = *collection;
+		Box::new(
+			Account::<T, I>::iter_prefix(who)
+				.filter(move |((c, _), _)| *c == collection)
+				.map(|((_, item), _)| item)
+		)
+	}
+}
+
+impl<T: Config<I>, I: 'static> Mutate<<T as SystemConfig>::AccountId> for Pallet<T, I> {
+	fn mint_into(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		Self::do_mint(*collection, *item, who.clone(), |_| Ok(()))
+	}
+
+	fn burn_from(collection: &Self::CollectionId, item: &Self::ItemId) -> DispatchResult {
+		Self::do_burn(*collection, *item, |_, _| Ok(()))
+	}
+}
+
+impl<T: Config<I>, I: 'static> Transfer<T::AccountId> for Pallet<T, I> {
+	fn transfer(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		Self::do_transfer(*collection, *item, destination.clone(), |_, _| Ok(()))
+	}
+}
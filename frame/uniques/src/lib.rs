@@ -0,0 +1,1359 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Unique (Items) Module
+//!
+//! A simple, secure module for dealing with non-fungible items.
+//!
+//! ## Overview
+//!
+//! The Uniques module provides functionality for management of collections of non-fungible
+//! items, including:
+//!
+//! * Collection Creation
+//! * Item Minting
+//! * Item Transfers
+//! * Item Trading methods ("Approval API")
+//! * Item and Collection Freezing
+//! * Item Burning
+//! * Attributes and Metadata of Items and Collections
+//!
+//! To use it in your runtime, you need to implement the uniques [`Config`].
+//!
+//! The supported dispatchable functions are documented in the [`Call`] enum.
+//!
+//! ### Terminology
+//!
+//! * **Collection**: A set of items which share an identifier, an owner and a management team.
+//! * **Item**: A single non-fungible token, identified within its collection.
+//! * **Owner**: An account ID uniquely privileged to be able to destroy a particular collection,
+//!   or to set the Issuer, Freezer or Admin of that collection.
+//! * **Issuer**: An account ID uniquely privileged to be able to mint items of a particular
+//!   collection.
+//! * **Admin**: An account ID uniquely privileged to be able to thaw items, forcibly transfer
+//!   items and burn items of a particular collection.
+//! * **Freezer**: An account ID uniquely privileged to be able to freeze items of a particular
+//!   collection.
+//! * **Approval**: The act of allowing an account the permission to transfer a single item on
+//!   behalf of its owner.
+//!
+//! ## Interface
+//!
+//! ### Permissionless Functions
+//!
+//! * `create`: Create a new collection, taking the required deposit.
+//! * `transfer`: Transfer an item owned by the sender to another account.
+//! * `approve_transfer`: Allow a delegate to transfer a single item of the sender.
+//! * `cancel_approval`: Rescind a previous approval.
+//! * `redeposit`: Re-evaluate the deposits held for some items of a collection.
+//!
+//! ### Permissioned Functions
+//!
+//! * `force_create`: Create a new collection without taking any deposit.
+//! * `force_item_status`: Alter the attributes of a collection.
+//!
+//! ### Privileged Functions
+//!
+//! * `destroy`: Destroy a collection; called by the collection's Owner.
+//! * `mint`: Mint a new item of a collection; called by the collection's Issuer.
+//! * `burn`: Burn an item of a collection; called by the collection's Admin or the item's owner.
+//! * `freeze`: Prevent an item from being transferred; called by the collection's Freezer.
+//! * `thaw`: Allow a frozen item to be transferred again; called by the collection's Admin.
+//! * `freeze_collection`: Prevent all items of a collection from being transferred; called by the
+//!   collection's Freezer.
+//! * `thaw_collection`: Allow the items of a frozen collection to be transferred again; called by
+//!   the collection's Admin.
+//! * `transfer_ownership`: Change the Owner of a collection; called by the collection's Owner.
+//! * `set_team`: Change the Issuer, Admin and Freezer of a collection; called by the collection's
+//!   Owner.
+//! * `set_attribute`: Set an attribute of an item or collection; called by the collection's Owner.
+//! * `clear_attribute`: Remove an attribute of an item or collection; called by the collection's
+//!   Owner.
+//! * `set_metadata`: Set the metadata of an item; called by the collection's Owner.
+//! * `clear_metadata`: Remove the metadata of an item; called by the collection's Owner.
+//! * `set_collection_metadata`: Set the metadata of a collection; called by the collection's
+//!   Owner.
+//! * `clear_collection_metadata`: Remove the metadata of a collection; called by the collection's
+//!   Owner.
+//!
+//! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for
+//! documentation on each function.
+//!
+//! The pallet also implements the `nonfungibles` traits of `frame_support`, so that other pallets
+//! can integrate with it.
+//!
+//! ## Related Modules
+//!
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+//! * [`Assets`](../pallet_assets/index.html)
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod weights;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+pub mod mock;
+#[cfg(test)]
+mod tests;
+
+mod types;
+mod functions;
+mod impl_nonfungibles;
+pub use types::*;
+
+use sp_std::{prelude::*, convert::TryInto};
+use sp_runtime::{RuntimeDebug, traits::{Zero, StaticLookup, Saturating}};
+use codec::{Encode, Decode, HasCompact};
+use frame_support::traits::{Currency, ReservableCurrency, BalanceStatus::Reserved};
+use frame_system::Config as SystemConfig;
+
+pub use weights::WeightInfo;
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::config]
+	/// The module configuration trait.
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Identifier for the collection of item.
+		type CollectionId: Member + Parameter + Default + Copy + HasCompact;
+
+		/// The type used to identify a unique item within a collection.
+		type ItemId: Member + Parameter + Default + Copy + HasCompact;
+
+		/// The currency mechanism, used for paying for reserves.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The origin which may forcibly create or destroy an item or otherwise alter privileged
+		/// attributes.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The basic amount of funds that must be reserved for collection.
+		type CollectionDeposit: Get<DepositBalanceOf<Self, I>>;
+
+		/// The basic amount of funds that must be reserved for an item.
+		type ItemDeposit: Get<DepositBalanceOf<Self, I>>;
+
+		/// The basic amount of funds that must be reserved when adding metadata to your item.
+		type MetadataDepositBase: Get<DepositBalanceOf<Self, I>>;
+
+		/// The basic amount of funds that must be reserved when adding an attribute to an item.
+		type AttributeDepositBase: Get<DepositBalanceOf<Self, I>>;
+
+		/// The additional funds that must be reserved for the number of bytes store in metadata,
+		/// either "normal" metadata or attribute metadata.
+		type DepositPerByte: Get<DepositBalanceOf<Self, I>>;
+
+		/// The maximum length of data stored on-chain.
+		type StringLimit: Get<u32>;
+
+		/// The maximum length of an attribute key.
+		type KeyLimit: Get<u32>;
+
+		/// The maximum length of an attribute value.
+		type ValueLimit: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::storage]
+	/// Details of a collection.
+	pub(super) type Collection<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		CollectionDetailsFor<T, I>,
+	>;
+
+	#[pallet::storage]
+	/// The items held by any given account; set out this way so that items owned by a single
+	/// account can be enumerated.
+	pub(super) type Account<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::CollectionId, T::ItemId),
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// The items in existence and their ownership details.
+	pub(super) type Item<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		ItemDetailsFor<T, I>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Metadata of a collection.
+	pub(super) type CollectionMetadataOf<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		CollectionMetadata<DepositBalanceOf<T, I>, T::StringLimit>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Metadata of an item.
+	pub(super) type ItemMetadataOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		ItemMetadata<DepositBalanceOf<T, I>, T::StringLimit>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Attributes of a collection, or of an item of it.
+	pub(super) type Attribute<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		(Option<T::ItemId>, BoundedVec<u8, T::KeyLimit>),
+		(BoundedVec<u8, T::ValueLimit>, DepositBalanceOf<T, I>),
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::CollectionId = "CollectionId",
+		T::ItemId = "ItemId"
+	)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A collection was created. \[ collection, creator, owner \]
+		Created(T::CollectionId, T::AccountId, T::AccountId),
+		/// A collection was force-created. \[ collection, owner \]
+		ForceCreated(T::CollectionId, T::AccountId),
+		/// A collection was destroyed. \[ collection \]
+		Destroyed(T::CollectionId),
+		/// An item was issued. \[ collection, item, owner \]
+		Issued(T::CollectionId, T::ItemId, T::AccountId),
+		/// An item was transferred. \[ collection, item, from, to \]
+		Transferred(T::CollectionId, T::ItemId, T::AccountId, T::AccountId),
+		/// An item was destroyed. \[ collection, item, owner \]
+		Burned(T::CollectionId, T::ItemId, T::AccountId),
+		/// Some item was frozen. \[ collection, item \]
+		Frozen(T::CollectionId, T::ItemId),
+		/// Some item was thawed. \[ collection, item \]
+		Thawed(T::CollectionId, T::ItemId),
+		/// Some collection was frozen. \[ collection \]
+		CollectionFrozen(T::CollectionId),
+		/// Some collection was thawed. \[ collection \]
+		CollectionThawed(T::CollectionId),
+		/// The owner changed \[ collection, new_owner \]
+		OwnerChanged(T::CollectionId, T::AccountId),
+		/// The management team changed \[ collection, issuer, admin, freezer \]
+		TeamChanged(T::CollectionId, T::AccountId, T::AccountId, T::AccountId),
+		/// An `item` of a `collection` has been approved by the `owner` for transfer by a
+		/// `delegate`.
+		/// \[ collection, item, owner, delegate \]
+		ApprovedTransfer(T::CollectionId, T::ItemId, T::AccountId, T::AccountId),
+		/// An approval for a `delegate` account to transfer the `item` of an item
+		/// `collection` was cancelled by its `owner`.
+		/// \[ collection, item, owner, delegate \]
+		ApprovalCancelled(T::CollectionId, T::ItemId, T::AccountId, T::AccountId),
+		/// A collection has had its attributes changed by the `Force` origin.
+		/// \[ collection \]
+		ItemStatusChanged(T::CollectionId),
+		/// New metadata has been set for a collection. \[ collection, data, is_frozen \]
+		CollectionMetadataSet(T::CollectionId, BoundedVec<u8, T::StringLimit>, bool),
+		/// Metadata has been cleared for a collection. \[ collection \]
+		CollectionMetadataCleared(T::CollectionId),
+		/// New metadata has been set for an item.
+		/// \[ collection, item, data, is_frozen \]
+		MetadataSet(T::CollectionId, T::ItemId, BoundedVec<u8, T::StringLimit>, bool),
+		/// Metadata has been cleared for an item. \[ collection, item \]
+		MetadataCleared(T::CollectionId, T::ItemId),
+		/// Metadata has been cleared for an item. \[ collection, successful_items \]
+		Redeposited(T::CollectionId, Vec<T::ItemId>),
+		/// New attribute metadata has been set for a `collection` or `item`.
+		/// \[ collection, maybe_item, key, value \]
+		AttributeSet(
+			T::CollectionId,
+			Option<T::ItemId>,
+			BoundedVec<u8, T::KeyLimit>,
+			BoundedVec<u8, T::ValueLimit>,
+		),
+		/// Attribute metadata has been cleared for a `collection` or `item`.
+		/// \[ collection, maybe_item, key \]
+		AttributeCleared(T::CollectionId, Option<T::ItemId>, BoundedVec<u8, T::KeyLimit>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The signing account has no permission to do the operation.
+		NoPermission,
+		/// The given collection ID is unknown.
+		UnknownCollection,
+		/// The item ID has already been used for an item.
+		AlreadyExists,
+		/// The owner turned out to be different to what was expected.
+		WrongOwner,
+		/// Invalid witness data given.
+		BadWitness,
+		/// The collection ID is already taken.
+		InUse,
+		/// The item or collection is frozen.
+		Frozen,
+		/// The delegate turned out to be different to what was expected.
+		WrongDelegate,
+		/// There is no delegate approved.
+		NoDelegate,
+		/// No approval exists that would allow the transfer.
+		Unapproved,
+		/// The given data is longer than the limit allowed for it.
+		TooLong,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Issue a new collection of non-fungible items from a public origin.
+		///
+		/// This new collection has no items initially and its owner is the origin.
+		///
+		/// The origin must be Signed and the sender must have sufficient funds free.
+		///
+		/// `CollectionDeposit` funds of sender are reserved.
+		///
+		/// Parameters:
+		/// - `collection`: The identifier of the new collection. This must not be currently in use.
+		/// - `admin`: The admin of this collection. The admin is the initial address of each
+		/// member of the collection's admin team.
+		///
+		/// Emits `Created` event when successful.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::create())]
+		pub(super) fn create(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			admin: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			ensure!(!Collection::<T, I>::contains_key(collection), Error::<T, I>::InUse);
+
+			let deposit = T::CollectionDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			Collection::<T, I>::insert(
+				collection,
+				CollectionDetails {
+					owner: owner.clone(),
+					issuer: admin.clone(),
+					admin: admin.clone(),
+					freezer: admin.clone(),
+					total_deposit: deposit,
+					free_holding: false,
+					items: 0,
+					item_metadatas: 0,
+					attributes: 0,
+					is_frozen: false,
+				},
+			);
+			Self::deposit_event(Event::Created(collection, owner, admin));
+			Ok(())
+		}
+
+		/// Issue a new collection of non-fungible items from a privileged origin.
+		///
+		/// This new collection has no items initially.
+		///
+		/// The origin must conform to `ForceOrigin`.
+		///
+		/// Unlike `create`, no funds are reserved.
+		///
+		/// - `collection`: The identifier of the new collection. This must not be currently in use.
+		/// - `owner`: The owner of this collection of items. The owner has full superuser
+		/// permissions over this collection, but may later change and configure the permissions
+		/// using `transfer_ownership` and `set_team`.
+		/// - `free_holding`: Whether items of this collection may be held without any deposit.
+		///
+		/// Emits `ForceCreated` event when successful.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::force_create())]
+		pub(super) fn force_create(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			free_holding: bool,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			ensure!(!Collection::<T, I>::contains_key(collection), Error::<T, I>::InUse);
+
+			Collection::<T, I>::insert(
+				collection,
+				CollectionDetails {
+					owner: owner.clone(),
+					issuer: owner.clone(),
+					admin: owner.clone(),
+					freezer: owner.clone(),
+					total_deposit: Zero::zero(),
+					free_holding,
+					items: 0,
+					item_metadatas: 0,
+					attributes: 0,
+					is_frozen: false,
+				},
+			);
+			Self::deposit_event(Event::ForceCreated(collection, owner));
+			Ok(())
+		}
+
+		/// Destroy a collection of non-fungible items.
+		///
+		/// The origin must conform to `ForceOrigin` or must be `Signed` and the sender must be the
+		/// owner of the `collection`.
+		///
+		/// - `collection`: The identifier of the collection to be destroyed.
+		/// - `witness`: Information on the items minted in the collection. This must be
+		/// correct.
+		///
+		/// Emits `Destroyed` event when successful.
+		///
+		/// Weight: `O(n + m)` where:
+		/// - `n = witness.items`
+		/// - `m = witness.item_metadatas`
+		/// - `a = witness.attributes`
+		#[pallet::weight(T::WeightInfo::destroy(
+			witness.items,
+			witness.item_metadatas,
+			witness.attributes,
+		))]
+		pub(super) fn destroy(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			witness: DestroyWitness,
+		) -> DispatchResult {
+			let maybe_check_owner = match T::ForceOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			Collection::<T, I>::try_mutate_exists(collection, |maybe_details| {
+				let collection_details = maybe_details.take()
+					.ok_or(Error::<T, I>::UnknownCollection)?;
+				if let Some(check_owner) = maybe_check_owner {
+					ensure!(collection_details.owner == check_owner, Error::<T, I>::NoPermission);
+				}
+				ensure!(collection_details.items == witness.items, Error::<T, I>::BadWitness);
+				ensure!(
+					collection_details.item_metadatas == witness.item_metadatas,
+					Error::<T, I>::BadWitness,
+				);
+				ensure!(
+					collection_details.attributes == witness.attributes,
+					Error::<T, I>::BadWitness,
+				);
+
+				for (item, details) in Item::<T, I>::drain_prefix(&collection) {
+					Account::<T, I>::remove(&details.owner, &(collection, item));
+				}
+				ItemMetadataOf::<T, I>::remove_prefix(&collection);
+				CollectionMetadataOf::<T, I>::remove(&collection);
+				Attribute::<T, I>::remove_prefix(&collection);
+				T::Currency::unreserve(&collection_details.owner, collection_details.total_deposit);
+
+				Self::deposit_event(Event::Destroyed(collection));
+
+				// NOTE: could use postinfo to reflect the actual number of
+				// accounts/sufficient/approvals
+				Ok(())
+			})
+		}
+
+		/// Mint an item of a particular collection.
+		///
+		/// The origin must be Signed and the sender must be the Issuer of the `collection`.
+		///
+		/// - `collection`: The collection of the item to be minted.
+		/// - `item`: The item value of the item to be minted.
+		/// - `owner`: The initial owner of the minted item.
+		///
+		/// Emits `Issued` event when successful.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub(super) fn mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Self::do_mint(collection, item, owner, |collection_details| {
+				ensure!(collection_details.issuer == origin, Error::<T, I>::NoPermission);
+				Ok(())
+			})
+		}
+
+		/// Destroy a single item.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the `collection`, or the
+		/// owner of the `item`.
+		///
+		/// - `collection`: The collection of the item to be burned.
+		/// - `item`: The item of the item to be burned.
+		/// - `check_owner`: If `Some` then the operation will fail with `WrongOwner` unless the
+		///   item is owned by this value.
+		///
+		/// Emits `Burned` with the actual amount burned.
+		///
+		/// Weight: `O(1)`
+		/// Modes: `check_owner.is_some()`.
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub(super) fn burn(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+			check_owner: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let check_owner = check_owner.map(T::Lookup::lookup).transpose()?;
+
+			Self::do_burn(collection, item, |collection_details, details| {
+				let is_permitted = collection_details.admin == origin || details.owner == origin;
+				ensure!(is_permitted, Error::<T, I>::NoPermission);
+				ensure!(
+					check_owner.map_or(true, |o| o == details.owner),
+					Error::<T, I>::WrongOwner,
+				);
+				Ok(())
+			})
+		}
+
+		/// Move an item from the sender account to another.
+		///
+		/// Origin must be Signed and the signing account must be either:
+		/// - the Admin of the `collection`;
+		/// - the Owner of the `item`;
+		/// - the approved delegate for the `item` (in this case, the approval is reset).
+		///
+		/// Arguments:
+		/// - `collection`: The collection of the item to be transferred.
+		/// - `item`: The item of the item to be transferred.
+		/// - `dest`: The account to receive ownership of the item.
+		///
+		/// Emits `Transferred`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub(super) fn transfer(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer(collection, item, dest, |collection_details, details| {
+				if details.owner != origin && collection_details.admin != origin {
+					let approved = details.approved.take().map_or(false, |i| i == origin);
+					ensure!(approved, Error::<T, I>::NoPermission);
+				}
+				Ok(())
+			})
+		}
+
+		/// Reevaluate the deposits on some items.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the `collection`.
+		///
+		/// - `collection`: The collection to be frozen.
+		/// - `items`: The items of the collection whose deposits will be reevaluated.
+		///
+		/// NOTE: This exists as a best-effort function. Any items which are unknown or
+		/// in the case that the owner account does not have reservable funds to pay for a
+		/// deposit increase are ignored. Generally the owner isn't going to call this on items
+		/// whose existing deposit is less than the refreshed deposit as it would only cost them,
+		/// so it's of little consequence.
+		///
+		/// It will still return an error in the case that the collection is unknown of the
+		/// signer is not permitted to call it.
+		///
+		/// Weight: `O(items.len())`
+		#[pallet::weight(T::WeightInfo::redeposit(items.len() as u32))]
+		pub(super) fn redeposit(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			items: Vec<T::ItemId>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			let mut collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			ensure!(collection_details.owner == origin, Error::<T, I>::NoPermission);
+			let deposit = match collection_details.free_holding {
+				true => Zero::zero(),
+				false => T::ItemDeposit::get(),
+			};
+
+			let mut successful = Vec::with_capacity(items.len());
+			for item in items.into_iter() {
+				let mut details = match Item::<T, I>::get(&collection, &item) {
+					Some(x) => x,
+					None => continue,
+				};
+				let old = details.deposit;
+				if old > deposit {
+					T::Currency::unreserve(&collection_details.owner, old - deposit);
+				} else if deposit > old {
+					if T::Currency::reserve(&collection_details.owner, deposit - old).is_err() {
+						// NOTE: No alterations made to collection_details in this iteration so far,
+						// so this is OK to do.
+						continue
+					}
+				} else {
+					continue
+				}
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_add(deposit);
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_sub(old);
+				details.deposit = deposit;
+				Item::<T, I>::insert(&collection, &item, &details);
+				successful.push(item);
+			}
+			Collection::<T, I>::insert(&collection, &collection_details);
+
+			Self::deposit_event(Event::<T, I>::Redeposited(collection, successful));
+
+			Ok(())
+		}
+
+		/// Disallow further unprivileged transfer of an item.
+		///
+		/// Origin must be Signed and the sender should be the Freezer of the `collection`.
+		///
+		/// - `collection`: The collection of the item to be frozen.
+		/// - `item`: The item of the item to be frozen.
+		///
+		/// Emits `Frozen`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::freeze())]
+		pub(super) fn freeze(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			let mut details = Item::<T, I>::get(&collection, &item)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			let collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			ensure!(collection_details.freezer == origin, Error::<T, I>::NoPermission);
+
+			details.is_frozen = true;
+			Item::<T, I>::insert(&collection, &item, &details);
+
+			Self::deposit_event(Event::<T, I>::Frozen(collection, item));
+			Ok(())
+		}
+
+		/// Re-allow unprivileged transfer of an item.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the `collection`.
+		///
+		/// - `collection`: The collection of the item to be thawed.
+		/// - `item`: The item of the item to be thawed.
+		///
+		/// Emits `Thawed`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::thaw())]
+		pub(super) fn thaw(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			let mut details = Item::<T, I>::get(&collection, &item)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			let collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			ensure!(collection_details.admin == origin, Error::<T, I>::NoPermission);
+
+			details.is_frozen = false;
+			Item::<T, I>::insert(&collection, &item, &details);
+
+			Self::deposit_event(Event::<T, I>::Thawed(collection, item));
+			Ok(())
+		}
+
+		/// Disallow further unprivileged transfers for a whole collection.
+		///
+		/// Origin must be Signed and the sender should be the Freezer of the `collection`.
+		///
+		/// - `collection`: The collection to be frozen.
+		///
+		/// Emits `CollectionFrozen`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::freeze_collection())]
+		pub(super) fn freeze_collection(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			Collection::<T, I>::try_mutate(collection, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T, I>::UnknownCollection)?;
+				ensure!(&origin == &details.freezer, Error::<T, I>::NoPermission);
+
+				details.is_frozen = true;
+
+				Self::deposit_event(Event::<T, I>::CollectionFrozen(collection));
+				Ok(())
+			})
+		}
+
+		/// Re-allow unprivileged transfers for a whole collection.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the `collection`.
+		///
+		/// - `collection`: The collection to be thawed.
+		///
+		/// Emits `CollectionThawed`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::thaw_collection())]
+		pub(super) fn thaw_collection(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			Collection::<T, I>::try_mutate(collection, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T, I>::UnknownCollection)?;
+				ensure!(&origin == &details.admin, Error::<T, I>::NoPermission);
+
+				details.is_frozen = false;
+
+				Self::deposit_event(Event::<T, I>::CollectionThawed(collection));
+				Ok(())
+			})
+		}
+
+		/// Change the Owner of a collection.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the `collection`.
+		///
+		/// - `collection`: The collection whose owner should be changed.
+		/// - `owner`: The new Owner of this collection.
+		///
+		/// Emits `OwnerChanged`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::transfer_ownership())]
+		pub(super) fn transfer_ownership(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Collection::<T, I>::try_mutate(collection, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T, I>::UnknownCollection)?;
+				ensure!(&origin == &details.owner, Error::<T, I>::NoPermission);
+				if details.owner == owner {
+					return Ok(());
+				}
+
+				// Move the deposit to the new owner.
+				T::Currency::repatriate_reserved(
+					&details.owner,
+					&owner,
+					details.total_deposit,
+					Reserved,
+				)?;
+				details.owner = owner.clone();
+
+				Self::deposit_event(Event::OwnerChanged(collection, owner));
+				Ok(())
+			})
+		}
+
+		/// Change the Issuer, Admin and Freezer of a collection.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the `collection`.
+		///
+		/// - `collection`: The collection whose team should be changed.
+		/// - `issuer`: The new Issuer of this collection.
+		/// - `admin`: The new Admin of this collection.
+		/// - `freezer`: The new Freezer of this collection.
+		///
+		/// Emits `TeamChanged`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::set_team())]
+		pub(super) fn set_team(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			Collection::<T, I>::try_mutate(collection, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T, I>::UnknownCollection)?;
+				ensure!(&origin == &details.owner, Error::<T, I>::NoPermission);
+
+				details.issuer = issuer.clone();
+				details.admin = admin.clone();
+				details.freezer = freezer.clone();
+
+				Self::deposit_event(Event::TeamChanged(collection, issuer, admin, freezer));
+				Ok(())
+			})
+		}
+
+		/// Approve an item to be transferred by a delegated third-party account.
+		///
+		/// Origin must be Signed and must be the owner of the `item`.
+		///
+		/// - `collection`: The collection of the item to be approved for delegated transfer.
+		/// - `item`: The item of the item to be approved for delegated transfer.
+		/// - `delegate`: The account to delegate permission to transfer the item.
+		///
+		/// Emits `ApprovedTransfer` on success.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::approve_transfer())]
+		pub(super) fn approve_transfer(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let maybe_check: Option<T::AccountId> = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			let collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			let mut details = Item::<T, I>::get(&collection, &item)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+
+			if let Some(check) = maybe_check {
+				let permitted = &check == &collection_details.admin || &check == &details.owner;
+				ensure!(permitted, Error::<T, I>::NoPermission);
+			}
+
+			details.approved = Some(delegate.clone());
+			Item::<T, I>::insert(&collection, &item, &details);
+
+			Self::deposit_event(Event::ApprovedTransfer(collection, item, details.owner, delegate));
+
+			Ok(())
+		}
+
+		/// Cancel the prior approval for the transfer of an item by a delegate.
+		///
+		/// Origin must be either:
+		/// - the `Force` origin;
+		/// - `Signed` with the signer being the Admin of the `collection`;
+		/// - `Signed` with the signer being the Owner of the `item`;
+		///
+		/// Arguments:
+		/// - `collection`: The collection of the item of whose approval will be cancelled.
+		/// - `item`: The item of the item of whose approval will be cancelled.
+		/// - `maybe_check_delegate`: If `Some` will ensure that the given account is the one to
+		///   which permission of transfer is delegated.
+		///
+		/// Emits `ApprovalCancelled` on success.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub(super) fn cancel_approval(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+			maybe_check_delegate: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			let maybe_check: Option<T::AccountId> = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			let mut details = Item::<T, I>::get(&collection, &item)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			if let Some(check) = maybe_check {
+				let permitted = &check == &collection_details.admin || &check == &details.owner;
+				ensure!(permitted, Error::<T, I>::NoPermission);
+			}
+			let maybe_check_delegate = maybe_check_delegate.map(T::Lookup::lookup).transpose()?;
+			let old = details.approved.take().ok_or(Error::<T, I>::NoDelegate)?;
+			if let Some(check_delegate) = maybe_check_delegate {
+				ensure!(check_delegate == old, Error::<T, I>::WrongDelegate);
+			}
+
+			Item::<T, I>::insert(&collection, &item, &details);
+			Self::deposit_event(Event::ApprovalCancelled(collection, item, details.owner, old));
+
+			Ok(())
+		}
+
+		/// Alter the attributes of a given collection.
+		///
+		/// Origin must be `ForceOrigin`.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `owner`: The new Owner of this collection.
+		/// - `issuer`: The new Issuer of this collection.
+		/// - `admin`: The new Admin of this collection.
+		/// - `freezer`: The new Freezer of this collection.
+		/// - `free_holding`: Whether a deposit is taken for holding an item of this collection.
+		/// - `is_frozen`: Whether this collection is frozen except for permissioned/admin
+		/// instructions.
+		///
+		/// Emits `ItemStatusChanged` with the identity of the collection.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::force_item_status())]
+		pub(super) fn force_item_status(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source,
+			free_holding: bool,
+			is_frozen: bool,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			Collection::<T, I>::try_mutate(collection, |maybe_collection| {
+				let mut collection_info = maybe_collection.take()
+					.ok_or(Error::<T, I>::UnknownCollection)?;
+				collection_info.owner = T::Lookup::lookup(owner)?;
+				collection_info.issuer = T::Lookup::lookup(issuer)?;
+				collection_info.admin = T::Lookup::lookup(admin)?;
+				collection_info.freezer = T::Lookup::lookup(freezer)?;
+				collection_info.free_holding = free_holding;
+				collection_info.is_frozen = is_frozen;
+				*maybe_collection = Some(collection_info);
+
+				Self::deposit_event(Event::ItemStatusChanged(collection));
+				Ok(())
+			})
+		}
+
+		/// Set an attribute for a collection or item.
+		///
+		/// Origin must be either `ForceOrigin` or Signed and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// If the origin is Signed, then funds of signer are reserved according to the formula:
+		/// `MetadataDepositBase + DepositPerByte * (key.len + value.len)` taking into
+		/// account any already reserved funds.
+		///
+		/// - `collection`: The identifier of the collection whose item's metadata to set.
+		/// - `maybe_item`: The identifier of the item whose metadata to set.
+		/// - `key`: The key of the attribute.
+		/// - `value`: The value to which to set the attribute.
+		///
+		/// Emits `AttributeSet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::set_attribute())]
+		pub(super) fn set_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let key: BoundedVec<u8, T::KeyLimit> =
+				key.try_into().map_err(|_| Error::<T, I>::TooLong)?;
+			let value: BoundedVec<u8, T::ValueLimit> =
+				value.try_into().map_err(|_| Error::<T, I>::TooLong)?;
+
+			let mut collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &collection_details.owner, Error::<T, I>::NoPermission);
+			}
+			let maybe_is_frozen = match maybe_item {
+				None => CollectionMetadataOf::<T, I>::get(collection).map(|v| v.is_frozen),
+				Some(item) => ItemMetadataOf::<T, I>::get(collection, item).map(|v| v.is_frozen),
+			};
+			ensure!(!maybe_is_frozen.unwrap_or(false), Error::<T, I>::Frozen);
+
+			let attribute = Attribute::<T, I>::get(collection, (maybe_item, key.clone()));
+			if attribute.is_none() {
+				collection_details.attributes = collection_details.attributes.saturating_add(1);
+			}
+			let old_deposit = attribute.map_or(Zero::zero(), |m| m.1);
+			collection_details.total_deposit =
+				collection_details.total_deposit.saturating_sub(old_deposit);
+			let mut deposit = Zero::zero();
+			if !collection_details.free_holding && maybe_check_owner.is_some() {
+				deposit = T::DepositPerByte::get()
+					.saturating_mul(((key.len() + value.len()) as u32).into())
+					.saturating_add(T::AttributeDepositBase::get());
+			}
+			collection_details.total_deposit =
+				collection_details.total_deposit.saturating_add(deposit);
+			if deposit > old_deposit {
+				T::Currency::reserve(&collection_details.owner, deposit - old_deposit)?;
+			} else if deposit < old_deposit {
+				T::Currency::unreserve(&collection_details.owner, old_deposit - deposit);
+			}
+
+			let attribute_key = (maybe_item, key.clone());
+			Attribute::<T, I>::insert(&collection, attribute_key, (value.clone(), deposit));
+			Collection::<T, I>::insert(collection, &collection_details);
+			Self::deposit_event(Event::AttributeSet(collection, maybe_item, key, value));
+			Ok(())
+		}
+
+		/// Clear an attribute for a collection or item.
+		///
+		/// Origin must be either `ForceOrigin` or Signed and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// Any deposit is freed for the collection's owner.
+		///
+		/// - `collection`: The identifier of the collection whose item's metadata to clear.
+		/// - `maybe_item`: The identifier of the item whose metadata to clear.
+		/// - `key`: The key of the attribute.
+		///
+		/// Emits `AttributeCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::clear_attribute())]
+		pub(super) fn clear_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+			key: Vec<u8>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let key: BoundedVec<u8, T::KeyLimit> =
+				key.try_into().map_err(|_| Error::<T, I>::TooLong)?;
+
+			let mut collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &collection_details.owner, Error::<T, I>::NoPermission);
+			}
+			let maybe_is_frozen = match maybe_item {
+				None => CollectionMetadataOf::<T, I>::get(collection).map(|v| v.is_frozen),
+				Some(item) => ItemMetadataOf::<T, I>::get(collection, item).map(|v| v.is_frozen),
+			};
+			ensure!(!maybe_is_frozen.unwrap_or(false), Error::<T, I>::Frozen);
+
+			let maybe_attribute = Attribute::<T, I>::take(collection, (maybe_item, key.clone()));
+			if let Some((_, deposit)) = maybe_attribute {
+				collection_details.attributes = collection_details.attributes.saturating_sub(1);
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_sub(deposit);
+				T::Currency::unreserve(&collection_details.owner, deposit);
+				Collection::<T, I>::insert(collection, &collection_details);
+				Self::deposit_event(Event::AttributeCleared(collection, maybe_item, key));
+			}
+			Ok(())
+		}
+
+		/// Set the metadata for an item.
+		///
+		/// Origin must be either `ForceOrigin` or Signed and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// If the origin is Signed, then funds of signer are reserved according to the formula:
+		/// `MetadataDepositBase + DepositPerByte * data.len` taking into
+		/// account any already reserved funds.
+		///
+		/// - `collection`: The identifier of the collection whose item's metadata to set.
+		/// - `item`: The identifier of the item whose metadata to set.
+		/// - `data`: The general information of this item. Limited in length by `StringLimit`.
+		/// - `is_frozen`: Whether the metadata should be frozen against further changes.
+		///
+		/// Emits `MetadataSet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub(super) fn set_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+			data: Vec<u8>,
+			is_frozen: bool,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let data: BoundedVec<u8, T::StringLimit> =
+				data.try_into().map_err(|_| Error::<T, I>::TooLong)?;
+
+			let mut collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &collection_details.owner, Error::<T, I>::NoPermission);
+			}
+
+			ItemMetadataOf::<T, I>::try_mutate_exists(collection, item, |metadata| {
+				let was_frozen = metadata.as_ref().map_or(false, |m| m.is_frozen);
+				ensure!(maybe_check_owner.is_none() || !was_frozen, Error::<T, I>::Frozen);
+
+				if metadata.is_none() {
+					collection_details.item_metadatas =
+						collection_details.item_metadatas.saturating_add(1);
+				}
+				let old_deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_sub(old_deposit);
+				let mut deposit = Zero::zero();
+				if !collection_details.free_holding && maybe_check_owner.is_some() {
+					deposit = T::DepositPerByte::get()
+						.saturating_mul(((data.len()) as u32).into())
+						.saturating_add(T::MetadataDepositBase::get());
+				}
+				if deposit > old_deposit {
+					T::Currency::reserve(&collection_details.owner, deposit - old_deposit)?;
+				} else if deposit < old_deposit {
+					T::Currency::unreserve(&collection_details.owner, old_deposit - deposit);
+				}
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_add(deposit);
+
+				*metadata = Some(ItemMetadata {
+					deposit,
+					data: data.clone(),
+					is_frozen,
+				});
+
+				Collection::<T, I>::insert(&collection, &collection_details);
+				Self::deposit_event(Event::MetadataSet(collection, item, data, is_frozen));
+				Ok(())
+			})
+		}
+
+		/// Clear the metadata for an item.
+		///
+		/// Origin must be either `ForceOrigin` or Signed and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// Any deposit is freed for the collection's owner.
+		///
+		/// - `collection`: The identifier of the collection whose item's metadata to clear.
+		/// - `item`: The identifier of the item whose metadata to clear.
+		///
+		/// Emits `MetadataCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::clear_metadata())]
+		pub(super) fn clear_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			#[pallet::compact] item: T::ItemId,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let mut collection_details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &collection_details.owner, Error::<T, I>::NoPermission);
+			}
+
+			ItemMetadataOf::<T, I>::try_mutate_exists(collection, item, |metadata| {
+				let was_frozen = metadata.as_ref().map_or(false, |m| m.is_frozen);
+				ensure!(maybe_check_owner.is_none() || !was_frozen, Error::<T, I>::Frozen);
+
+				if metadata.is_some() {
+					collection_details.item_metadatas =
+						collection_details.item_metadatas.saturating_sub(1);
+				}
+				let deposit = metadata.take().ok_or(Error::<T, I>::UnknownCollection)?.deposit;
+				T::Currency::unreserve(&collection_details.owner, deposit);
+				collection_details.total_deposit =
+					collection_details.total_deposit.saturating_sub(deposit);
+
+				Collection::<T, I>::insert(&collection, &collection_details);
+				Self::deposit_event(Event::MetadataCleared(collection, item));
+				Ok(())
+			})
+		}
+
+		/// Set the metadata for a collection.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// If the origin is `Signed`, then funds of signer are reserved according to the formula:
+		/// `MetadataDepositBase + DepositPerByte * data.len` taking into
+		/// account any already reserved funds.
+		///
+		/// - `collection`: The identifier of the collection whose metadata to update.
+		/// - `data`: The general information of this collection. Limited in length by
+		///   `StringLimit`.
+		/// - `is_frozen`: Whether the metadata should be frozen against further changes.
+		///
+		/// Emits `CollectionMetadataSet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::set_collection_metadata())]
+		pub(super) fn set_collection_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+			data: Vec<u8>,
+			is_frozen: bool,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let data: BoundedVec<u8, T::StringLimit> =
+				data.try_into().map_err(|_| Error::<T, I>::TooLong)?;
+
+			let mut details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+			}
+
+			CollectionMetadataOf::<T, I>::try_mutate_exists(collection, |metadata| {
+				let was_frozen = metadata.as_ref().map_or(false, |m| m.is_frozen);
+				ensure!(maybe_check_owner.is_none() || !was_frozen, Error::<T, I>::Frozen);
+
+				let old_deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
+				details.total_deposit = details.total_deposit.saturating_sub(old_deposit);
+				let mut deposit = Zero::zero();
+				if maybe_check_owner.is_some() && !details.free_holding {
+					deposit = T::DepositPerByte::get()
+						.saturating_mul(((data.len()) as u32).into())
+						.saturating_add(T::MetadataDepositBase::get());
+				}
+				if deposit > old_deposit {
+					T::Currency::reserve(&details.owner, deposit - old_deposit)?;
+				} else if deposit < old_deposit {
+					T::Currency::unreserve(&details.owner, old_deposit - deposit);
+				}
+				details.total_deposit = details.total_deposit.saturating_add(deposit);
+
+				Collection::<T, I>::insert(&collection, details);
+
+				*metadata = Some(CollectionMetadata {
+					deposit,
+					data: data.clone(),
+					is_frozen,
+				});
+
+				Self::deposit_event(Event::CollectionMetadataSet(collection, data, is_frozen));
+				Ok(())
+			})
+		}
+
+		/// Clear the metadata for a collection.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// Any deposit is freed for the collection's owner.
+		///
+		/// - `collection`: The identifier of the collection whose metadata to clear.
+		///
+		/// Emits `CollectionMetadataCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::WeightInfo::clear_collection_metadata())]
+		pub(super) fn clear_collection_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection: T::CollectionId,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let mut details = Collection::<T, I>::get(&collection)
+				.ok_or(Error::<T, I>::UnknownCollection)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+			}
+
+			CollectionMetadataOf::<T, I>::try_mutate_exists(collection, |metadata| {
+				let was_frozen = metadata.as_ref().map_or(false, |m| m.is_frozen);
+				ensure!(maybe_check_owner.is_none() || !was_frozen, Error::<T, I>::Frozen);
+
+				let deposit = metadata.take().ok_or(Error::<T, I>::UnknownCollection)?.deposit;
+				T::Currency::unreserve(&details.owner, deposit);
+				details.total_deposit = details.total_deposit.saturating_sub(deposit);
+				Collection::<T, I>::insert(&collection, details);
+				Self::deposit_event(Event::CollectionMetadataCleared(collection));
+				Ok(())
+			})
+		}
+	}
+}
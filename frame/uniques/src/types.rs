@@ -0,0 +1,131 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Various basic types for use in the uniques pallet.
+
+use super::*;
+use sp_std::fmt::Debug;
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+
+pub(super) type DepositBalanceOf<T, I = ()> =
+	<<T as Config<I>>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
+pub(super) type CollectionDetailsFor<T, I> =
+	CollectionDetails<<T as SystemConfig>::AccountId, DepositBalanceOf<T, I>>;
+pub(super) type ItemDetailsFor<T, I> =
+	ItemDetails<<T as SystemConfig>::AccountId, DepositBalanceOf<T, I>>;
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct CollectionDetails<
+	AccountId,
+	DepositBalance,
+> {
+	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+	pub(super) owner: AccountId,
+	/// Can mint tokens.
+	pub(super) issuer: AccountId,
+	/// Can thaw tokens, force transfers and burn tokens from any account.
+	pub(super) admin: AccountId,
+	/// Can freeze tokens.
+	pub(super) freezer: AccountId,
+	/// The total balance deposited for the all storage associated with this collection. Used by
+	/// `destroy`.
+	pub(super) total_deposit: DepositBalance,
+	/// If `true`, then no deposit is needed to hold items of this collection.
+	pub(super) free_holding: bool,
+	/// The total number of outstanding items of this collection.
+	pub(super) items: u32,
+	/// The total number of outstanding item metadata of this collection.
+	pub(super) item_metadatas: u32,
+	/// The total number of attributes for this collection.
+	pub(super) attributes: u32,
+	/// Whether the collection is frozen for non-admin transfers.
+	pub(super) is_frozen: bool,
+}
+
+/// Witness data for the destroy transactions.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct DestroyWitness {
+	/// The total number of outstanding items of this collection.
+	#[codec(compact)]
+	pub(super) items: u32,
+	/// The total number of outstanding item metadata of this collection.
+	#[codec(compact)]
+	pub(super) item_metadatas: u32,
+	/// The total number of attributes for this collection.
+	#[codec(compact)]
+	pub(super) attributes: u32,
+}
+
+impl<AccountId, DepositBalance> CollectionDetails<AccountId, DepositBalance> {
+	pub fn destroy_witness(&self) -> DestroyWitness {
+		DestroyWitness {
+			items: self.items,
+			item_metadatas: self.item_metadatas,
+			attributes: self.attributes,
+		}
+	}
+}
+
+/// Information concerning the ownership of a single unique item.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default)]
+pub struct ItemDetails<AccountId, DepositBalance> {
+	/// The owner of this item.
+	pub(super) owner: AccountId,
+	/// The approved transferrer of this item, if one is set.
+	pub(super) approved: Option<AccountId>,
+	/// Whether the item can be transferred or not.
+	pub(super) is_frozen: bool,
+	/// The amount held in the pallet's default account for this item. Free-hold items will have
+	/// this as zero.
+	pub(super) deposit: DepositBalance,
+}
+
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct CollectionMetadata<
+	DepositBalance: Clone + PartialEq + Eq + Debug,
+	StringLimit: Get<u32>,
+> {
+	/// The balance deposited for this metadata.
+	///
+	/// This pays for the data stored in this struct.
+	pub(super) deposit: DepositBalance,
+	/// General information concerning this collection. Limited in length by `StringLimit`. This
+	/// will generally be either a JSON dump or the hash of some JSON which can be found on a
+	/// hash-addressable global publication system such as IPFS.
+	pub(super) data: BoundedVec<u8, StringLimit>,
+	/// Whether the collection's metadata may be changed by a non Force origin.
+	pub(super) is_frozen: bool,
+}
+
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct ItemMetadata<
+	DepositBalance: Clone + PartialEq + Eq + Debug,
+	StringLimit: Get<u32>,
+> {
+	/// The balance deposited for this metadata.
+	///
+	/// This pays for the data stored in this struct.
+	pub(super) deposit: DepositBalance,
+	/// General information concerning this item. Limited in length by `StringLimit`. This will
+	/// generally be either a JSON dump or the hash of some JSON which can be found on a
+	/// hash-addressable global publication system such as IPFS.
+	pub(super) data: BoundedVec<u8, StringLimit>,
+	/// Whether the item metadata may be changed by a non Force origin.
+	pub(super) is_frozen: bool,
+}
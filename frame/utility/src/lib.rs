@@ -44,7 +44,11 @@
 //! ### Dispatchable Functions
 //!
 //! #### For batch dispatch
-//! * `batch` - Dispatch multiple calls from the sender's origin.
+//! * `batch` - Dispatch multiple calls from the sender's origin. Stops at the first failing call.
+//! * `batch_all` - Dispatch multiple calls from the sender's origin and revert all of them if
+//!   any call fails.
+//! * `force_batch` - Dispatch multiple calls from the sender's origin and continue with the
+//!   remaining calls if one of them fails.
 //!
 //! #### For pseudonymal dispatch
 //! * `as_derivative` - Dispatch a call from a derivative signed origin.
@@ -62,6 +66,7 @@ use sp_core::TypeId;
 use sp_io::hashing::blake2_256;
 use frame_support::{
 	transactional,
+	storage::{with_transaction, TransactionOutcome},
 	traits::{OriginTrait, UnfilteredDispatchable},
 	weights::{GetDispatchInfo, extract_actual_weight},
 	dispatch::PostDispatchInfo,
@@ -105,6 +110,12 @@ pub mod pallet {
 		BatchInterrupted(u32, DispatchError),
 		/// Batch of dispatches completed fully with no error.
 		BatchCompleted,
+		/// Batch of dispatches completed but has errors.
+		BatchCompletedWithErrors,
+		/// A single item within a batch of dispatches has completed with no error.
+		ItemCompleted,
+		/// A single item within a batch of dispatches has completed with error. \[error\]
+		ItemFailed(DispatchError),
 	}
 
 	#[pallet::hooks]
@@ -285,6 +296,85 @@ pub mod pallet {
 			let base_weight = T::WeightInfo::batch_all(calls_len as u32);
 			Ok(Some(base_weight + weight).into())
 		}
+
+		/// Send a batch of dispatch calls.
+		/// Unlike `batch`, it allows errors and won't interrupt.
+		///
+		/// May be called from any origin.
+		///
+		/// - `calls`: The calls to be dispatched from the same origin.
+		///
+		/// If origin is root then call are dispatch without checking origin filter. (This includes
+		/// bypassing `frame_system::Config::BaseCallFilter`).
+		///
+		/// Every call is dispatched in its own storage layer. The changes of a failing call are
+		/// reverted while the changes of all other calls are kept. An `ItemCompleted` or
+		/// `ItemFailed` event is deposited for each call, followed by `BatchCompleted` or
+		/// `BatchCompletedWithErrors`.
+		///
+		/// # <weight>
+		/// - Complexity: O(C) where C is the number of calls to be batched.
+		/// # </weight>
+		#[pallet::weight({
+			let dispatch_infos = calls.iter().map(|call| call.get_dispatch_info()).collect::<Vec<_>>();
+			let dispatch_weight = dispatch_infos.iter()
+				.map(|di| di.weight)
+				.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
+				.saturating_add(T::WeightInfo::force_batch(calls.len() as u32));
+			let dispatch_class = {
+				let all_operational = dispatch_infos.iter()
+					.map(|di| di.class)
+					.all(|class| class == DispatchClass::Operational);
+				if all_operational {
+					DispatchClass::Operational
+				} else {
+					DispatchClass::Normal
+				}
+			};
+			(dispatch_weight, dispatch_class)
+		})]
+		pub fn force_batch(
+			origin: OriginFor<T>,
+			calls: Vec<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let is_root = ensure_root(origin.clone()).is_ok();
+			let calls_len = calls.len();
+			// Track the actual weight of each of the batch calls.
+			let mut weight: Weight = 0;
+			// Track failed dispatch occur.
+			let mut has_error = false;
+			for call in calls.into_iter() {
+				let info = call.get_dispatch_info();
+				// Dispatch in a new storage layer so that a failing call doesn't leave any changes.
+				let result = with_transaction(|| {
+					// If origin is root, don't apply any dispatch filters; root can call anything.
+					let result = if is_root {
+						call.dispatch_bypass_filter(origin.clone())
+					} else {
+						call.dispatch(origin.clone())
+					};
+					match result {
+						Ok(_) => TransactionOutcome::Commit(result),
+						Err(_) => TransactionOutcome::Rollback(result),
+					}
+				});
+				// Add the weight of this call.
+				weight = weight.saturating_add(extract_actual_weight(&result, &info));
+				if let Err(e) = result {
+					has_error = true;
+					Self::deposit_event(Event::ItemFailed(e.error));
+				} else {
+					Self::deposit_event(Event::ItemCompleted);
+				}
+			}
+			if has_error {
+				Self::deposit_event(Event::BatchCompletedWithErrors);
+			} else {
+				Self::deposit_event(Event::BatchCompleted);
+			}
+			let base_weight = T::WeightInfo::force_batch(calls_len as u32);
+			Ok(Some(base_weight + weight).into())
+		}
 	}
 
 }
@@ -527,3 +527,55 @@ fn batch_all_handles_weight_refund() {
 		);
 	});
 }
+
+#[test]
+fn force_batch_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert_ok!(
+			Utility::force_batch(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 5)),
+				Call::Balances(BalancesCall::transfer(2, 10)),
+				Call::Balances(BalancesCall::transfer(2, 5)),
+			]),
+		);
+		let item_failed: Event = utility::Event::ItemFailed(
+			pallet_balances::Error::<Test, _>::InsufficientBalance.into()
+		).into();
+		assert!(System::events().iter().any(|record| record.event == item_failed));
+		expect_event(utility::Event::BatchCompletedWithErrors);
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 20);
+
+		assert_ok!(
+			Utility::force_batch(Origin::signed(2), vec![
+				Call::Balances(BalancesCall::transfer(1, 5)),
+				Call::Balances(BalancesCall::transfer(1, 5)),
+			]),
+		);
+		expect_event(utility::Event::BatchCompleted);
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+	});
+}
+
+#[test]
+fn force_batch_reverts_failed_call() {
+	new_test_ext().execute_with(|| {
+		let k = b"a".to_vec();
+		assert_ok!(
+			Utility::force_batch(Origin::root(), vec![
+				Call::Utility(UtilityCall::batch_all(vec![
+					Call::System(SystemCall::set_storage(vec![(k.clone(), k.clone())])),
+					Call::Balances(BalancesCall::force_transfer(1, 2, 20)),
+				])),
+				Call::Balances(BalancesCall::force_transfer(1, 2, 5)),
+			]),
+		);
+		expect_event(utility::Event::BatchCompletedWithErrors);
+		assert_eq!(storage::unhashed::get_raw(&k), None);
+		assert_eq!(Balances::free_balance(1), 5);
+		assert_eq!(Balances::free_balance(2), 15);
+	});
+}
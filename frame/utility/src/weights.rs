@@ -19,6 +19,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
 //! DATE: 2020-10-27, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+//!
+//! The weight of `force_batch` is a hand-written placeholder and was not generated by the benchmark
+//! CLI.
 
 // Executed Command:
 // target/release/substrate
@@ -46,6 +49,7 @@ pub trait WeightInfo {
 	fn batch(c: u32, ) -> Weight;
 	fn as_derivative() -> Weight;
 	fn batch_all(c: u32, ) -> Weight;
+	fn force_batch(c: u32, ) -> Weight;
 	
 }
 
@@ -66,6 +70,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add((2_738_000 as Weight).saturating_mul(c as Weight))
 			
 	}
+	fn force_batch(c: u32, ) -> Weight {
+		(20_071_000 as Weight)
+			.saturating_add((2_739_000 as Weight).saturating_mul(c as Weight))
+			
+	}
 	
 }
 
@@ -85,5 +94,10 @@ impl WeightInfo for () {
 			.saturating_add((2_738_000 as Weight).saturating_mul(c as Weight))
 			
 	}
+	fn force_batch(c: u32, ) -> Weight {
+		(20_071_000 as Weight)
+			.saturating_add((2_739_000 as Weight).saturating_mul(c as Weight))
+			
+	}
 	
 }
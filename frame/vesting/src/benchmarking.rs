@@ -40,36 +40,44 @@ fn add_locks<T: Config>(who: &T::AccountId, n: u8) {
 	}
 }
 
-fn add_vesting_schedule<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
+fn add_vesting_schedules<T: Config>(
+	who: &T::AccountId,
+	n: u32,
+) -> Result<BalanceOf<T>, &'static str> {
 	let locked = 100u32;
 	let per_block = 10u32;
 	let starting_block = 1u32;
 
 	System::<T>::set_block_number(0u32.into());
 
-	// Add schedule to avoid `NotVesting` error.
-	Vesting::<T>::add_vesting_schedule(
-		&who,
-		locked.into(),
-		per_block.into(),
-		starting_block.into(),
-	)?;
-	Ok(())
+	// Add schedules to avoid `NotVesting` error.
+	let mut total_locked: BalanceOf<T> = Zero::zero();
+	for _ in 0 .. n {
+		Vesting::<T>::add_vesting_schedule(
+			&who,
+			locked.into(),
+			per_block.into(),
+			starting_block.into(),
+		)?;
+		total_locked = total_locked.saturating_add(locked.into());
+	}
+	Ok(total_locked)
 }
 
 benchmarks! {
 	vest_locked {
 		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let caller = whitelisted_caller();
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		add_locks::<T>(&caller, l as u8);
-		add_vesting_schedule::<T>(&caller)?;
+		let expected_balance = add_vesting_schedules::<T>(&caller, s)?;
 		// At block zero, everything is vested.
 		System::<T>::set_block_number(T::BlockNumber::zero());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
-			Some(100u32.into()),
+			Some(expected_balance),
 			"Vesting schedule not added",
 		);
 	}: vest(RawOrigin::Signed(caller.clone()))
@@ -77,18 +85,19 @@ benchmarks! {
 		// Nothing happened since everything is still vested.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
-			Some(100u32.into()),
+			Some(expected_balance),
 			"Vesting schedule was removed",
 		);
 	}
 
 	vest_unlocked {
 		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let caller = whitelisted_caller();
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		add_locks::<T>(&caller, l as u8);
-		add_vesting_schedule::<T>(&caller)?;
+		add_vesting_schedules::<T>(&caller, s)?;
 		// At block 20, everything is unvested.
 		System::<T>::set_block_number(20u32.into());
 		assert_eq!(
@@ -108,17 +117,18 @@ benchmarks! {
 
 	vest_other_locked {
 		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let other: T::AccountId = account("other", 0, SEED);
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
 		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		let expected_balance = add_vesting_schedules::<T>(&other, s)?;
 		// At block zero, everything is vested.
 		System::<T>::set_block_number(T::BlockNumber::zero());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
-			Some(100u32.into()),
+			Some(expected_balance),
 			"Vesting schedule not added",
 		);
 
@@ -128,19 +138,20 @@ benchmarks! {
 		// Nothing happened since everything is still vested.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
-			Some(100u32.into()),
+			Some(expected_balance),
 			"Vesting schedule was removed",
 		);
 	}
 
 	vest_other_unlocked {
 		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let other: T::AccountId = account("other", 0, SEED);
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
 		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		add_vesting_schedules::<T>(&other, s)?;
 		// At block 20, everything is unvested.
 		System::<T>::set_block_number(20u32.into());
 		assert_eq!(
@@ -162,6 +173,7 @@ benchmarks! {
 
 	vested_transfer {
 		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
 
 		let caller: T::AccountId = whitelisted_caller();
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
@@ -169,6 +181,8 @@ benchmarks! {
 		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
 		// Give target existing locks
 		add_locks::<T>(&target, l as u8);
+		// Give target the maximum number of schedules that still allows adding another one.
+		add_vesting_schedules::<T>(&target, s)?;
 
 		let transfer_amount = T::MinVestedTransfer::get();
 
@@ -185,14 +199,15 @@ benchmarks! {
 			"Transfer didn't happen",
 		);
 		assert_eq!(
-			Vesting::<T>::vesting_balance(&target),
-			Some(T::MinVestedTransfer::get()),
-			"Lock not created",
+			Vesting::<T>::vesting(&target).map(|schedules| schedules.len()),
+			Some(s as usize + 1),
+			"Schedule not added",
 		);
 	}
 
 	force_vested_transfer {
 		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
 
 		let source: T::AccountId = account("source", 0, SEED);
 		let source_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(source.clone());
@@ -201,6 +216,8 @@ benchmarks! {
 		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
 		// Give target existing locks
 		add_locks::<T>(&target, l as u8);
+		// Give target the maximum number of schedules that still allows adding another one.
+		add_vesting_schedules::<T>(&target, s)?;
 
 		let transfer_amount = T::MinVestedTransfer::get();
 
@@ -217,9 +234,32 @@ benchmarks! {
 			"Transfer didn't happen",
 		);
 		assert_eq!(
-			Vesting::<T>::vesting_balance(&target),
-			Some(T::MinVestedTransfer::get()),
-			"Lock not created",
+			Vesting::<T>::vesting(&target).map(|schedules| schedules.len()),
+			Some(s as usize + 1),
+			"Schedule not added",
+		);
+	}
+
+	merge_schedules {
+		let l in 0 .. MaxLocksOf::<T>::get();
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		let expected_balance = add_vesting_schedules::<T>(&caller, s)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, 1)
+	verify {
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).map(|schedules| schedules.len()),
+			Some(s as usize - 1),
+			"Schedules not merged",
+		);
+		// Nothing vested since block zero.
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(expected_balance),
+			"Locked amount changed",
 		);
 	}
 }
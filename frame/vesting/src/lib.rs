@@ -26,6 +26,9 @@
 //! pallet ensures that there is a lock in place preventing the balance to drop below the *unvested*
 //! amount for any reason other than transaction fee payment.
 //!
+//! An account can have up to `MaxVestingSchedules` vesting schedules at the same time. The amounts
+//! locked by each of them are added up and enforced by a single lock.
+//!
 //! As the amount vested increases over time, the amount unvested reduces. However, locks remain in
 //! place and explicit action is needed on behalf of the user to ensure that the amount locked is
 //! equivalent to the amount remaining to be vested. This is done through a dispatchable function,
@@ -41,19 +44,25 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer funds to an account and add a vesting schedule for them.
+//! - `force_vested_transfer` - Same as `vested_transfer` but with the source account given by
+//!   _Root_.
+//! - `merge_schedules` - Merge two vesting schedules of the sender into one.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod migrations;
 pub mod weights;
 
 use sp_std::prelude::*;
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode};
 use sp_runtime::{RuntimeDebug, traits::{
-	StaticLookup, Zero, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert
+	StaticLookup, Zero, One, Bounded, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert,
+	Saturating,
 }};
-use frame_support::{ensure, pallet_prelude::*};
+use frame_support::{ensure, pallet_prelude::*, BoundedVec};
 use frame_support::traits::{
 	Currency, LockableCurrency, VestingSchedule, WithdrawReasons, LockIdentifier,
 	ExistenceRequirement, Get,
@@ -67,6 +76,23 @@ type MaxLocksOf<T> = <<T as Config>::Currency as LockableCurrency<<T as frame_sy
 
 const VESTING_ID: LockIdentifier = *b"vesting ";
 
+// A value placed in storage that represents the current version of the Vesting storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	/// A single vesting schedule per account.
+	V0,
+	/// A bounded list of vesting schedules per account.
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
 /// Struct to encode the vesting schedule of an individual account.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct VestingInfo<Balance, BlockNumber> {
@@ -98,6 +124,36 @@ impl<
 			Zero::zero()
 		}
 	}
+
+	/// Whether the schedule is able to vest anything at all.
+	///
+	/// This does not check the amount against `MinVestedTransfer`.
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+
+	/// The first block (as `Balance`) at which nothing is locked by this schedule anymore.
+	///
+	/// A schedule that doesn't unlock anything per block never ends.
+	pub fn ending_block_as_balance<
+		BlockNumberToBalance: Convert<BlockNumber, Balance>
+	>(&self) -> Balance {
+		if self.per_block.is_zero() {
+			return Balance::max_value()
+		}
+		let starting_block = BlockNumberToBalance::convert(self.starting_block);
+		let duration = if self.per_block >= self.locked {
+			One::one()
+		} else {
+			let remainder = if (self.locked % self.per_block).is_zero() {
+				Zero::zero()
+			} else {
+				One::one()
+			};
+			(self.locked / self.per_block).saturating_add(remainder)
+		};
+		starting_block.saturating_add(duration)
+	}
 }
 
 #[frame_support::pallet]
@@ -119,6 +175,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinVestedTransfer: Get<BalanceOf<Self>>;
 
+		/// The maximum number of vesting schedules an account can have at the same time.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -130,9 +190,15 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		BoundedVec<VestingInfo<BalanceOf<T>, T::BlockNumber>, T::MaxVestingSchedules>,
 	>;
 
+	/// Storage version of the pallet.
+	///
+	/// This is set to v1 for new networks.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
@@ -154,8 +220,6 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
-			use sp_runtime::traits::Saturating;
-
 			// Generate initial vesting configuration
 			// * who - Account which we are generating vesting configuration for
 			// * begin - Block when the account will start to vest
@@ -169,14 +233,24 @@ pub mod pallet {
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
 				let per_block = locked / length_as_balance.max(sp_runtime::traits::One::one());
 
-				Vesting::<T>::insert(who, VestingInfo {
+				let schedule = VestingInfo {
 					locked: locked,
 					per_block: per_block,
 					starting_block: begin
+				};
+				let total_locked = Vesting::<T>::mutate(who, |schedules| {
+					let schedules = schedules.get_or_insert_with(Default::default);
+					schedules.try_push(schedule)
+						.expect("Too many vesting schedules at genesis.");
+					schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+						total.saturating_add(schedule.locked)
+					})
 				});
 				let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
-				T::Currency::set_lock(VESTING_ID, who, locked, reasons);
+				T::Currency::set_lock(VESTING_ID, who, total_locked, reasons);
 			}
+
+			StorageVersion::<T>::put(Releases::V1);
 		}
 	}
 
@@ -197,14 +271,27 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// An existing vesting schedule already exists for this account that cannot be clobbered.
-		ExistingVestingSchedule,
+		/// The account already has `MaxVestingSchedules` vesting schedules.
+		AtMaxVestingSchedules,
 		/// Amount being transferred is too low to create a vesting schedule.
 		AmountLow,
+		/// An index was out of bounds of the vesting schedules.
+		ScheduleIndexOutOfBounds,
+		/// Failed to create a new schedule because some parameter was invalid.
+		InvalidScheduleParams,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V0 {
+				StorageVersion::<T>::put(Releases::V1);
+				migrations::v1::migrate::<T>().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -216,13 +303,17 @@ pub mod pallet {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender.
 		/// - DbWeight: 2 Reads, 2 Writes
 		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::vest_locked(MaxLocksOf::<T>::get())
-			.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get()))
+		#[pallet::weight(
+			T::WeightInfo::vest_locked(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+				.max(T::WeightInfo::vest_unlocked(
+					MaxLocksOf::<T>::get(),
+					T::MaxVestingSchedules::get(),
+				))
 		)]
 		pub fn vest(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -239,13 +330,17 @@ pub mod pallet {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`.
 		/// - DbWeight: 3 Reads, 3 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get())
-			.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get()))
+		#[pallet::weight(
+			T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+				.max(T::WeightInfo::vest_other_unlocked(
+					MaxLocksOf::<T>::get(),
+					T::MaxVestingSchedules::get(),
+				))
 		)]
 		pub fn vest_other(origin: OriginFor<T>, target: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			ensure_signed(origin)?;
@@ -260,32 +355,28 @@ pub mod pallet {
 		/// - `amount`: The amount of funds to transfer and will be vested.
 		/// - `schedule`: The vesting schedule attached to the transfer.
 		///
-		/// Emits `VestingCreated`.
+		/// The schedule is added to the existing schedules of `target`. Fails if `target`
+		/// already has `MaxVestingSchedules` schedules.
+		///
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`.
 		/// - DbWeight: 3 Reads, 3 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get()))]
+		#[pallet::weight(
+			T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+		)]
 		pub fn vested_transfer(
 			origin: OriginFor<T>,
 			target: <T::Lookup as StaticLookup>::Source,
 			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
 		) -> DispatchResult {
 			let transactor = ensure_signed(origin)?;
-			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
-
 			let who = T::Lookup::lookup(target)?;
-			ensure!(!Vesting::<T>::contains_key(&who), Error::<T>::ExistingVestingSchedule);
-
-			T::Currency::transfer(&transactor, &who, schedule.locked, ExistenceRequirement::AllowDeath)?;
-
-			Self::add_vesting_schedule(&who, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
-
-			Ok(())
+			Self::do_vested_transfer(transactor, who, schedule)
 		}
 
 		/// Force a vested transfer.
@@ -297,15 +388,23 @@ pub mod pallet {
 		/// - `amount`: The amount of funds to transfer and will be vested.
 		/// - `schedule`: The vesting schedule attached to the transfer.
 		///
-		/// Emits `VestingCreated`.
+		/// The schedule is added to the existing schedules of `target`. Fails if `target`
+		/// already has `MaxVestingSchedules` schedules.
+		///
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`.
 		/// - DbWeight: 4 Reads, 4 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, Source Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, Source Account
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get()))]
+		#[pallet::weight(
+			T::WeightInfo::force_vested_transfer(
+				MaxLocksOf::<T>::get(),
+				T::MaxVestingSchedules::get(),
+			)
+		)]
 		pub fn force_vested_transfer(
 			origin: OriginFor<T>,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -313,18 +412,73 @@ pub mod pallet {
 			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
-
 			let target = T::Lookup::lookup(target)?;
 			let source = T::Lookup::lookup(source)?;
-			ensure!(!Vesting::<T>::contains_key(&target), Error::<T>::ExistingVestingSchedule);
+			Self::do_vested_transfer(source, target, schedule)
+		}
 
-			T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+		/// Merge two vesting schedules of the sender into a single one.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `schedule1_index`: The index of the first schedule to merge.
+		/// - `schedule2_index`: The index of the second schedule to merge.
+		///
+		/// After merging, the lock is updated in line with the amount vested so far and all
+		/// schedules that have completely vested are removed.
+		///
+		/// The merged schedule locks the amount that is still locked by both schedules. It starts
+		/// at the current block or the later of both starting blocks, whichever is later, and ends
+		/// at the later of both ending blocks. If one of the schedules has already ended, the
+		/// other schedule is kept as it is. The merged schedule is appended to the end of the
+		/// list of schedules.
+		///
+		/// Merging a schedule with itself is a no-op.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender.
+		/// - DbWeight: 2 Reads, 2 Writes
+		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
+		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::merge_schedules(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+		)]
+		pub fn merge_schedules(
+			origin: OriginFor<T>,
+			schedule1_index: u32,
+			schedule2_index: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index {
+				return Ok(())
+			}
+			let schedule1_index = schedule1_index as usize;
+			let schedule2_index = schedule2_index as usize;
+
+			let mut schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+			ensure!(
+				schedule1_index < schedules.len() && schedule2_index < schedules.len(),
+				Error::<T>::ScheduleIndexOutOfBounds,
+			);
+
+			let schedule1 = schedules[schedule1_index];
+			let schedule2 = schedules[schedule2_index];
+			// Remove the higher index first so that the lower index still refers to the same
+			// schedule.
+			schedules.remove(schedule1_index.max(schedule2_index));
+			schedules.remove(schedule1_index.min(schedule2_index));
 
-			Self::add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+			let now = <frame_system::Pallet<T>>::block_number();
+			if let Some(merged) = Self::merge_vesting_info(now, schedule1, schedule2) {
+				schedules.try_push(merged)
+					.expect("Two schedules were removed before one is added; qed");
+			}
+			Vesting::<T>::insert(&who, schedules);
 
-			Ok(())
+			Self::update_lock(who)
 		}
 	}
 }
@@ -332,22 +486,100 @@ pub mod pallet {
 impl<T: Config> Pallet<T> {
 	/// (Re)set or remove the pallet's currency lock on `who`'s account in accordance with their
 	/// current unvested amount.
+	///
+	/// Schedules which have completely vested are removed.
 	fn update_lock(who: T::AccountId) -> DispatchResult {
-		let vesting = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		let mut schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
 		let now = <frame_system::Pallet<T>>::block_number();
-		let locked_now = vesting.locked_at::<T::BlockNumberToBalance>(now);
+		schedules.retain(|schedule| !schedule.locked_at::<T::BlockNumberToBalance>(now).is_zero());
+		let locked_now = Self::locked_at(&schedules, now);
 
 		if locked_now.is_zero() {
 			T::Currency::remove_lock(VESTING_ID, &who);
 			Vesting::<T>::remove(&who);
 			Self::deposit_event(Event::<T>::VestingCompleted(who));
 		} else {
+			Vesting::<T>::insert(&who, schedules);
 			let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
 			T::Currency::set_lock(VESTING_ID, &who, locked_now, reasons);
 			Self::deposit_event(Event::<T>::VestingUpdated(who, locked_now));
 		}
 		Ok(())
 	}
+
+	/// The total amount locked by all `schedules` at block `now`.
+	fn locked_at(
+		schedules: &[VestingInfo<BalanceOf<T>, T::BlockNumber>],
+		now: T::BlockNumber,
+	) -> BalanceOf<T> {
+		schedules.iter().fold(Zero::zero(), |total, schedule| {
+			total.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+		})
+	}
+
+	/// Transfer `schedule.locked` from `source` to `target` and add `schedule` to the vesting
+	/// schedules of `target`.
+	fn do_vested_transfer(
+		source: T::AccountId,
+		target: T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+		ensure!(
+			Self::vesting(&target).map_or(true, |s| s.len() < T::MaxVestingSchedules::get() as usize),
+			Error::<T>::AtMaxVestingSchedules,
+		);
+
+		T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+
+		Self::add_vesting_schedule(
+			&target,
+			schedule.locked,
+			schedule.per_block,
+			schedule.starting_block,
+		)
+			.expect("user does not have the maximum number of vesting schedules; q.e.d.");
+
+		Ok(())
+	}
+
+	/// Create a new schedule that locks what is still locked by `schedule1` and `schedule2`.
+	///
+	/// Returns `None` if both schedules have ended. If only one of them has ended, the other
+	/// one is returned unchanged.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+		let schedule1_ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>();
+		let schedule2_ending_block = schedule2.ending_block_as_balance::<T::BlockNumberToBalance>();
+		let now_as_balance = T::BlockNumberToBalance::convert(now);
+
+		match (schedule1_ending_block <= now_as_balance, schedule2_ending_block <= now_as_balance) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			(false, false) => (),
+		}
+
+		let locked = schedule1.locked_at::<T::BlockNumberToBalance>(now)
+			.saturating_add(schedule2.locked_at::<T::BlockNumberToBalance>(now));
+		if locked.is_zero() {
+			return None
+		}
+
+		let ending_block = schedule1_ending_block.max(schedule2_ending_block);
+		let starting_block = now.max(schedule1.starting_block).max(schedule2.starting_block);
+		let duration = ending_block
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+			.max(One::one());
+		// Due to rounding the merged schedule might end slightly later than the original ones.
+		let per_block = (locked / duration).max(One::one());
+
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
 }
 
 impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
@@ -358,9 +590,9 @@ impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Self::vesting(who) {
+		if let Some(schedules) = Self::vesting(who) {
 			let now = <frame_system::Pallet<T>>::block_number();
-			let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
+			let locked_now = Self::locked_at(&schedules, now);
 			Some(T::Currency::free_balance(who).min(locked_now))
 		} else {
 			None
@@ -369,7 +601,7 @@ impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
+	/// If the account already has `MaxVestingSchedules` vesting schedules, an `Err` is returned
 	/// and nothing is updated.
 	///
 	/// On success, a linearly reducing amount of funds will be locked. In order to realise any
@@ -384,22 +616,23 @@ impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
 		starting_block: T::BlockNumber
 	) -> DispatchResult {
 		if locked.is_zero() { return Ok(()) }
-		if Vesting::<T>::contains_key(who) {
-			Err(Error::<T>::ExistingVestingSchedule)?
-		}
 		let vesting_schedule = VestingInfo {
 			locked,
 			per_block,
 			starting_block
 		};
-		Vesting::<T>::insert(who, vesting_schedule);
+		Vesting::<T>::try_mutate(who, |schedules| -> DispatchResult {
+			schedules.get_or_insert_with(Default::default)
+				.try_push(vesting_schedule)
+				.map_err(|_| Error::<T>::AtMaxVestingSchedules.into())
+		})?;
 		// it can't fail, but even if somehow it did, we don't really care.
 		let res = Self::update_lock(who.clone());
 		debug_assert!(res.is_ok());
 		Ok(())
 	}
 
-	/// Remove a vesting schedule for a given account.
+	/// Remove all vesting schedules of a given account.
 	fn remove_vesting_schedule(who: &T::AccountId) {
 		Vesting::<T>::remove(who);
 		// it can't fail, but even if somehow it did, we don't really care.
@@ -480,6 +713,7 @@ mod tests {
 	}
 	parameter_types! {
 		pub const MinVestedTransfer: u64 = 256 * 2;
+		pub const MaxVestingSchedules: u32 = 3;
 		pub static ExistentialDeposit: u64 = 0;
 	}
 	impl Config for Test {
@@ -487,9 +721,14 @@ mod tests {
 		type Currency = Balances;
 		type BlockNumberToBalance = Identity;
 		type MinVestedTransfer = MinVestedTransfer;
+		type MaxVestingSchedules = MaxVestingSchedules;
 		type WeightInfo = ();
 	}
 
+	fn vesting_of(who: u64) -> Option<Vec<VestingInfo<u64, u64>>> {
+		Vesting::vesting(&who).map(|schedules| schedules.to_vec())
+	}
+
 	pub struct ExtBuilder {
 		existential_deposit: u64,
 	}
@@ -557,9 +796,9 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&1), Some(user1_vesting_schedule)); // Account 1 has a vesting schedule
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule)); // Account 2 has a vesting schedule
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule)); // Account 12 has a vesting schedule
+				assert_eq!(vesting_of(1), Some(vec![user1_vesting_schedule])); // Account 1 has a vesting schedule
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule])); // Account 2 has a vesting schedule
+				assert_eq!(vesting_of(12), Some(vec![user12_vesting_schedule])); // Account 12 has a vesting schedule
 
 				// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
 				assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
@@ -680,7 +919,7 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule));
+				assert_eq!(vesting_of(12), Some(vec![user12_vesting_schedule]));
 
 				// Account 12 can still send liquid funds
 				assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
@@ -698,7 +937,7 @@ mod tests {
 				assert_eq!(user3_free_balance, 256 * 30);
 				assert_eq!(user4_free_balance, 256 * 40);
 				// Account 4 should not have any vesting yet.
-				assert_eq!(Vesting::vesting(&4), None);
+				assert_eq!(vesting_of(4), None);
 				// Make the schedule for the new transfer.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
@@ -707,7 +946,7 @@ mod tests {
 				};
 				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(vesting_of(4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -746,17 +985,17 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// The vesting schedule we will try to create, fails because it never vests.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
-					per_block: 64, // Vesting over 20 blocks
+					per_block: 0,
 					starting_block: 10,
 				};
 				assert_noop!(
 					Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::InvalidScheduleParams,
 				);
 
 				// Fails due to too low transfer amount.
@@ -787,7 +1026,7 @@ mod tests {
 				assert_eq!(user3_free_balance, 256 * 30);
 				assert_eq!(user4_free_balance, 256 * 40);
 				// Account 4 should not have any vesting yet.
-				assert_eq!(Vesting::vesting(&4), None);
+				assert_eq!(vesting_of(4), None);
 				// Make the schedule for the new transfer.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
@@ -797,7 +1036,7 @@ mod tests {
 				assert_noop!(Vesting::force_vested_transfer(Some(4).into(), 3, 4, new_vesting_schedule), BadOrigin);
 				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(vesting_of(4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -836,17 +1075,17 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// The vesting schedule we will try to create, fails because it never vests.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
-					per_block: 64, // Vesting over 20 blocks
+					per_block: 0,
 					starting_block: 10,
 				};
 				assert_noop!(
 					Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::InvalidScheduleParams,
 				);
 
 				// Fails due to too low transfer amount.
@@ -865,4 +1104,178 @@ mod tests {
 				assert_eq!(user4_free_balance, 256 * 40);
 			});
 	}
+
+	#[test]
+	fn multiple_schedules_lock_combined_amount() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 256, // Vesting over 5 blocks
+					starting_block: 1,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule, new_vesting_schedule]));
+				assert_eq!(Balances::free_balance(&2), 256 * 25);
+				// Both schedules lock their full amount.
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+
+				System::set_block_number(6);
+				// The second schedule has completely vested.
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 20));
+				assert_ok!(Vesting::vest(Some(2).into()));
+				// Completely vested schedules are removed.
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule]));
+				assert_ok!(Balances::transfer(Some(2).into(), 3, 256 * 5));
+
+				System::set_block_number(30);
+				assert_ok!(Vesting::vest(Some(2).into()));
+				assert_eq!(vesting_of(2), None);
+			});
+	}
+
+	#[test]
+	fn vested_transfer_respects_max_vesting_schedules() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 2,
+					per_block: 64,
+					starting_block: 10,
+				};
+				// Account 2 already has one schedule.
+				for _ in 1 .. MaxVestingSchedules::get() {
+					assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+				}
+				assert_noop!(
+					Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule),
+					Error::<Test>::AtMaxVestingSchedules,
+				);
+				assert_noop!(
+					Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 2, new_vesting_schedule),
+					Error::<Test>::AtMaxVestingSchedules,
+				);
+				assert_noop!(
+					Vesting::add_vesting_schedule(&2, 256, 64, 10),
+					Error::<Test>::AtMaxVestingSchedules,
+				);
+			});
+	}
+
+	#[test]
+	fn merge_ongoing_schedules() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Account 2 vests 256 * 20 over 20 blocks, ending at block 30.
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 10,
+					per_block: 64, // Vesting over 40 blocks, ends at block 45
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule));
+
+				System::set_block_number(15);
+				let locked_now = 256 * 15 + (256 * 10 - 64 * 10);
+				assert_eq!(Vesting::vesting_balance(&2), Some(locked_now));
+
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				let merged = VestingInfo {
+					locked: locked_now,
+					per_block: locked_now / 30, // From block 15 to block 45
+					starting_block: 15,
+				};
+				assert_eq!(vesting_of(2), Some(vec![merged]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(locked_now));
+
+				System::set_block_number(60);
+				assert_ok!(Vesting::vest(Some(2).into()));
+				assert_eq!(vesting_of(2), None);
+			});
+	}
+
+	#[test]
+	fn merge_with_ended_schedule_keeps_the_other_one() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 2,
+					per_block: 256, // Vesting over 2 blocks
+					starting_block: 1,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule));
+
+				System::set_block_number(5);
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 1, 0));
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 20));
+			});
+	}
+
+	#[test]
+	fn merge_schedules_correctly_fails() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				assert_noop!(
+					Vesting::merge_schedules(Some(4).into(), 0, 1),
+					Error::<Test>::NotVesting,
+				);
+				assert_noop!(
+					Vesting::merge_schedules(Some(2).into(), 0, 1),
+					Error::<Test>::ScheduleIndexOutOfBounds,
+				);
+				// Merging a schedule with itself does nothing.
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256,
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 0));
+				assert_eq!(vesting_of(2), Some(vec![user2_vesting_schedule]));
+			});
+	}
+
+	#[test]
+	fn migrate_to_multiple_schedules_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64,
+					starting_block: 10,
+				};
+				// Write a schedule in the old format.
+				let key = super::Vesting::<Test>::hashed_key_for(&4);
+				frame_support::storage::unhashed::put(&key, &schedule);
+				StorageVersion::<Test>::put(Releases::V0);
+
+				<Vesting as Hooks<u64>>::on_runtime_upgrade();
+
+				assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+				assert_eq!(vesting_of(4), Some(vec![schedule]));
+				// Schedules that already have the new format are untouched.
+				assert_eq!(vesting_of(2).map(|schedules| schedules.len()), Some(1));
+			});
+	}
 }
@@ -0,0 +1,37 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the vesting pallet.
+
+use super::*;
+
+/// Migrate from a single vesting schedule per account to a bounded list of schedules.
+pub mod v1 {
+	use super::*;
+
+	/// Wrap the vesting schedule of every account into a list containing only that schedule.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads_writes = 0;
+
+		Vesting::<T>::translate::<VestingInfo<BalanceOf<T>, T::BlockNumber>, _>(|_, schedule| {
+			reads_writes += 1;
+			Some(BoundedVec::force_from(vec![schedule], Some("vesting::migrations::v1")))
+		});
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+}
@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Whitelist pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use sp_std::vec;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+
+use crate::Pallet as Whitelist;
+
+/// The largest remark which is benchmarked.
+const MAX_REMARK_LEN: u32 = 4 * 1024 * 1024 - 10;
+
+/// A call of roughly `len` bytes once encoded.
+fn remark_call<T: Config>(len: u32) -> <T as Config>::Call {
+	frame_system::Call::<T>::remark(vec![1u8; len as usize]).into()
+}
+
+benchmarks! {
+	whitelist_call {
+		let origin = T::WhitelistOrigin::successful_origin();
+		let call_hash: Hash = Default::default();
+	}: _<T::Origin>(origin, call_hash)
+	verify {
+		assert!(WhitelistedCall::<T>::contains_key(call_hash));
+		assert!(T::Preimages::is_requested(&call_hash));
+	}
+
+	remove_whitelisted_call {
+		let origin = T::WhitelistOrigin::successful_origin();
+		let call_hash: Hash = Default::default();
+		Whitelist::<T>::whitelist_call(origin.clone(), call_hash)
+			.expect("whitelisting call must be successful");
+	}: _<T::Origin>(origin, call_hash)
+	verify {
+		assert!(!WhitelistedCall::<T>::contains_key(call_hash));
+		assert!(!T::Preimages::is_requested(&call_hash));
+	}
+
+	// The weight of the dispatched call itself is not included; it is added on top of this.
+	dispatch_whitelisted_call {
+		let n in 1 .. MAX_REMARK_LEN;
+
+		let origin = T::DispatchWhitelistedOrigin::successful_origin();
+		let call = remark_call::<T>(n);
+		let call_weight = call.get_dispatch_info().weight;
+		let encoded_call = call.encode();
+		let call_encoded_len = encoded_call.len() as u32;
+		let call_hash = T::Preimages::note(encoded_call.into())
+			.expect("noting the preimage must be successful");
+
+		Whitelist::<T>::whitelist_call(T::WhitelistOrigin::successful_origin(), call_hash)
+			.expect("whitelisting call must be successful");
+	}: _<T::Origin>(origin, call_hash, call_encoded_len, call_weight)
+	verify {
+		assert!(!WhitelistedCall::<T>::contains_key(call_hash));
+	}
+
+	dispatch_whitelisted_call_with_preimage {
+		let n in 1 .. 10_000;
+
+		let origin = T::DispatchWhitelistedOrigin::successful_origin();
+		let call = remark_call::<T>(n);
+		let call_hash: Hash = sp_io::hashing::blake2_256(&call.encode()).into();
+
+		Whitelist::<T>::whitelist_call(T::WhitelistOrigin::successful_origin(), call_hash)
+			.expect("whitelisting call must be successful");
+	}: _<T::Origin>(origin, Box::new(call))
+	verify {
+		assert!(!WhitelistedCall::<T>::contains_key(call_hash));
+		assert!(!T::Preimages::is_requested(&call_hash));
+	}
+}
+
+impl_benchmark_test_suite!(
+	Whitelist,
+	crate::mock::new_test_ext(),
+	crate::mock::Test,
+);
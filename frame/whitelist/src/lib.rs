@@ -0,0 +1,271 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Whitelist Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! Allow some configurable origin: [`Config::WhitelistOrigin`] to whitelist some hash of a call,
+//! and allow another configurable origin: [`Config::DispatchWhitelistedOrigin`] to dispatch them
+//! with the root origin.
+//!
+//! In the meantime the call corresponding to the hash must have been submitted to the preimage
+//! handler, which is requested for as long as the call is whitelisted.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `whitelist_call` - Whitelist the hash of a call.
+//! - `remove_whitelisted_call` - Remove the hash of a call from the whitelist.
+//! - `dispatch_whitelisted_call` - Dispatch a whitelisted call, whose preimage is known to the
+//!   preimage handler, with the root origin.
+//! - `dispatch_whitelisted_call_with_preimage` - Dispatch a whitelisted call, given in full,
+//!   with the root origin.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+pub mod weights;
+
+use sp_std::prelude::*;
+use codec::{Decode, Encode};
+use sp_runtime::traits::Dispatchable;
+use frame_support::{
+	ensure,
+	dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+	traits::{
+		UnfilteredDispatchable,
+		preimages::{Hash, QueryPreimage, StorePreimage},
+	},
+	weights::{GetDispatchInfo, Weight},
+};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The overarching call type.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ UnfilteredDispatchable<Origin = Self::Origin>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// Required origin for whitelisting a call.
+		type WhitelistOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Required origin for dispatching a whitelisted call with root origin.
+		type DispatchWhitelistedOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The handler of pre-images.
+		type Preimages: QueryPreimage + StorePreimage;
+
+		/// The weight information for this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// A call hash has been whitelisted. \[call_hash\]
+		CallWhitelisted(Hash),
+		/// A call hash has been removed from the whitelist. \[call_hash\]
+		WhitelistedCallRemoved(Hash),
+		/// A whitelisted call has been dispatched. \[call_hash, result\]
+		WhitelistedCallDispatched(Hash, DispatchResult),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The preimage of the call hash could not be loaded.
+		UnavailablePreImage,
+		/// The call could not be decoded.
+		UndecodableCall,
+		/// The weight of the decoded call was higher than the witness.
+		InvalidCallWeightWitness,
+		/// The call was not whitelisted.
+		CallIsNotWhitelisted,
+		/// The call was already whitelisted; No-Op.
+		CallAlreadyWhitelisted,
+	}
+
+	/// The hashes of the calls which are currently whitelisted.
+	#[pallet::storage]
+	pub type WhitelistedCall<T: Config> = StorageMap<_, Twox64Concat, Hash, (), OptionQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Whitelist the hash of a call, requesting its preimage.
+		///
+		/// The dispatch origin of this call must be `WhitelistOrigin`.
+		#[pallet::weight(T::WeightInfo::whitelist_call())]
+		pub fn whitelist_call(origin: OriginFor<T>, call_hash: Hash) -> DispatchResult {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				!WhitelistedCall::<T>::contains_key(call_hash),
+				Error::<T>::CallAlreadyWhitelisted,
+			);
+
+			WhitelistedCall::<T>::insert(call_hash, ());
+			T::Preimages::request(&call_hash);
+
+			Self::deposit_event(Event::CallWhitelisted(call_hash));
+
+			Ok(())
+		}
+
+		/// Remove the hash of a call from the whitelist, along with the request for its preimage.
+		///
+		/// The dispatch origin of this call must be `WhitelistOrigin`.
+		#[pallet::weight(T::WeightInfo::remove_whitelisted_call())]
+		pub fn remove_whitelisted_call(origin: OriginFor<T>, call_hash: Hash) -> DispatchResult {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+
+			WhitelistedCall::<T>::take(call_hash).ok_or(Error::<T>::CallIsNotWhitelisted)?;
+
+			T::Preimages::unrequest(&call_hash);
+
+			Self::deposit_event(Event::WhitelistedCallRemoved(call_hash));
+
+			Ok(())
+		}
+
+		/// Dispatch the whitelisted call of hash `call_hash` with the root origin, removing it
+		/// from the whitelist. Its preimage must be known to the preimage handler.
+		///
+		/// The dispatch origin of this call must be `DispatchWhitelistedOrigin`.
+		///
+		/// - `call_encoded_len`: The length of the encoded call.
+		/// - `call_weight_witness`: An upper bound of the weight of the call.
+		#[pallet::weight(
+			T::WeightInfo::dispatch_whitelisted_call(*call_encoded_len)
+				.saturating_add(*call_weight_witness)
+		)]
+		pub fn dispatch_whitelisted_call(
+			origin: OriginFor<T>,
+			call_hash: Hash,
+			call_encoded_len: u32,
+			call_weight_witness: Weight,
+		) -> DispatchResultWithPostInfo {
+			T::DispatchWhitelistedOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				WhitelistedCall::<T>::contains_key(call_hash),
+				Error::<T>::CallIsNotWhitelisted,
+			);
+
+			let call = T::Preimages::fetch(&call_hash, Some(call_encoded_len))
+				.map_err(|_| Error::<T>::UnavailablePreImage)?;
+
+			let call = <T as Config>::Call::decode(&mut &call[..])
+				.map_err(|_| Error::<T>::UndecodableCall)?;
+
+			ensure!(
+				call.get_dispatch_info().weight <= call_weight_witness,
+				Error::<T>::InvalidCallWeightWitness
+			);
+
+			let actual_weight = Self::clean_and_dispatch(call_hash, call).map(|w| {
+				w.saturating_add(T::WeightInfo::dispatch_whitelisted_call(call_encoded_len))
+			});
+
+			Ok(actual_weight.into())
+		}
+
+		/// Dispatch the whitelisted `call` with the root origin, removing it from the whitelist.
+		///
+		/// The dispatch origin of this call must be `DispatchWhitelistedOrigin`.
+		#[pallet::weight({
+			let call_weight = call.get_dispatch_info().weight;
+			let call_len = call.encoded_size() as u32;
+
+			T::WeightInfo::dispatch_whitelisted_call_with_preimage(call_len)
+				.saturating_add(call_weight)
+		})]
+		pub fn dispatch_whitelisted_call_with_preimage(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			T::DispatchWhitelistedOrigin::ensure_origin(origin)?;
+
+			let call_hash: Hash = sp_io::hashing::blake2_256(&call.encode()).into();
+
+			ensure!(
+				WhitelistedCall::<T>::contains_key(call_hash),
+				Error::<T>::CallIsNotWhitelisted,
+			);
+
+			let call_len = call.encoded_size() as u32;
+			let actual_weight = Self::clean_and_dispatch(call_hash, *call).map(|w| {
+				w.saturating_add(T::WeightInfo::dispatch_whitelisted_call_with_preimage(call_len))
+			});
+
+			Ok(actual_weight.into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Clean whitelisting/preimage and dispatch call.
+	///
+	/// Return the actual weight of the dispatched call, if it is known.
+	fn clean_and_dispatch(call_hash: Hash, call: <T as Config>::Call) -> Option<Weight> {
+		WhitelistedCall::<T>::remove(call_hash);
+
+		T::Preimages::unrequest(&call_hash);
+
+		let result = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+
+		let call_actual_weight = match result {
+			Ok(call_post_info) => call_post_info.actual_weight,
+			Err(call_err) => call_err.post_info.actual_weight,
+		};
+
+		Self::deposit_event(Event::WhitelistedCallDispatched(
+			call_hash,
+			result.map(|_| ()).map_err(|e| e.error),
+		));
+
+		call_actual_weight
+	}
+}
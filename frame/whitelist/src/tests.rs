@@ -0,0 +1,222 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Whitelist pallet.
+
+use super::*;
+use crate::mock::{*, Call};
+
+use frame_support::{assert_noop, assert_ok, traits::Bounded};
+use sp_runtime::{DispatchError, traits::BadOrigin};
+
+fn hashed(data: impl AsRef<[u8]>) -> Hash {
+	sp_io::hashing::blake2_256(data.as_ref()).into()
+}
+
+fn set_balance_call(who: u64, free: u64) -> Call {
+	Call::Balances(pallet_balances::Call::set_balance(who, free, 0))
+}
+
+fn last_event() -> crate::Event {
+	System::events().into_iter().filter_map(|r| match r.event {
+		mock::Event::pallet_whitelist(e) => Some(e),
+		_ => None,
+	}).last().expect("an event was deposited")
+}
+
+#[test]
+fn test_whitelist_call_and_remove() {
+	new_test_ext().execute_with(|| {
+		let call = set_balance_call(3, 50);
+		let call_hash = hashed(call.encode());
+
+		assert_noop!(
+			Whitelist::remove_whitelisted_call(Origin::root(), call_hash),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+
+		assert_noop!(Whitelist::whitelist_call(Origin::signed(1), call_hash), BadOrigin);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), call_hash));
+		assert!(Preimage::is_requested(&call_hash));
+		assert_eq!(last_event(), crate::Event::CallWhitelisted(call_hash));
+
+		assert_noop!(
+			Whitelist::whitelist_call(Origin::root(), call_hash),
+			Error::<Test>::CallAlreadyWhitelisted,
+		);
+
+		assert_noop!(Whitelist::remove_whitelisted_call(Origin::signed(1), call_hash), BadOrigin);
+
+		assert_ok!(Whitelist::remove_whitelisted_call(Origin::root(), call_hash));
+		assert!(!Preimage::is_requested(&call_hash));
+		assert_eq!(last_event(), crate::Event::WhitelistedCallRemoved(call_hash));
+
+		assert_noop!(
+			Whitelist::remove_whitelisted_call(Origin::root(), call_hash),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+	});
+}
+
+#[test]
+fn test_whitelist_call_and_execute() {
+	new_test_ext().execute_with(|| {
+		let call = set_balance_call(3, 50);
+		let call_weight = call.get_dispatch_info().weight;
+		let encoded_call = call.encode();
+		let call_encoded_len = encoded_call.len() as u32;
+		let call_hash = hashed(&encoded_call);
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(
+				Origin::signed(1),
+				call_hash,
+				call_encoded_len,
+				call_weight,
+			),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), call_hash));
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(
+				Origin::signed(2),
+				call_hash,
+				call_encoded_len,
+				call_weight,
+			),
+			BadOrigin,
+		);
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(
+				Origin::signed(1),
+				call_hash,
+				call_encoded_len,
+				call_weight,
+			),
+			Error::<Test>::UnavailablePreImage,
+		);
+
+		// The preimage is requested, so noting it is free.
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), encoded_call));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(Preimage::have(&Bounded::<()>::lookup(call_hash, call_encoded_len)));
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(
+				Origin::signed(1),
+				call_hash,
+				call_encoded_len,
+				call_weight - 1,
+			),
+			Error::<Test>::InvalidCallWeightWitness,
+		);
+
+		assert_ok!(Whitelist::dispatch_whitelisted_call(
+			Origin::signed(1),
+			call_hash,
+			call_encoded_len,
+			call_weight,
+		));
+		assert_eq!(Balances::free_balance(3), 50);
+		assert_eq!(last_event(), crate::Event::WhitelistedCallDispatched(call_hash, Ok(())));
+
+		assert!(!WhitelistedCall::<Test>::contains_key(call_hash));
+		assert!(!Preimage::is_requested(&call_hash));
+		assert!(!Preimage::have(&Bounded::<()>::lookup(call_hash, call_encoded_len)));
+	});
+}
+
+#[test]
+fn test_whitelist_call_and_execute_failing_call() {
+	new_test_ext().execute_with(|| {
+		// A transfer cannot be made with the root origin.
+		let call = Call::Balances(pallet_balances::Call::transfer(3, 50));
+		let call_weight = call.get_dispatch_info().weight;
+		let encoded_call = call.encode();
+		let call_encoded_len = encoded_call.len() as u32;
+		let call_hash = hashed(&encoded_call);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), call_hash));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), encoded_call));
+		assert_ok!(Whitelist::dispatch_whitelisted_call(
+			Origin::signed(1),
+			call_hash,
+			call_encoded_len,
+			call_weight,
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::WhitelistedCallDispatched(call_hash, Err(DispatchError::BadOrigin)),
+		);
+		assert!(!WhitelistedCall::<Test>::contains_key(call_hash));
+		assert!(!Preimage::is_requested(&call_hash));
+	});
+}
+
+#[test]
+fn test_whitelist_call_and_execute_without_note_preimage() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(set_balance_call(3, 50));
+		let call_hash = hashed(call.encode());
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call_with_preimage(Origin::signed(1), call.clone()),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), call_hash));
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call_with_preimage(Origin::signed(2), call.clone()),
+			BadOrigin,
+		);
+
+		assert_ok!(Whitelist::dispatch_whitelisted_call_with_preimage(
+			Origin::signed(1),
+			call.clone(),
+		));
+		assert_eq!(Balances::free_balance(3), 50);
+
+		assert!(!WhitelistedCall::<Test>::contains_key(call_hash));
+		assert!(!Preimage::is_requested(&call_hash));
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call_with_preimage(Origin::signed(1), call),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+	});
+}
+
+#[test]
+fn test_whitelist_call_and_execute_decode_fails() {
+	new_test_ext().execute_with(|| {
+		let encoded_call = vec![255u8; 10];
+		let call_hash = hashed(&encoded_call);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), call_hash));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), encoded_call));
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(1), call_hash, 10, 10_000),
+			Error::<Test>::UndecodableCall,
+		);
+	});
+}
@@ -697,10 +697,49 @@ impl<'a> ToClientSideDecl<'a> {
 
 		let function_name = method.sig.ident.to_string();
 
+		// If the method was added in a later version of the api, we need to check that the
+		// runtime at the given block supports it, before calling into the runtime.
+		let version_check = match get_method_api_version(&found_attributes) {
+			Ok(Some(version)) => {
+				// Make sure that the method version is not greater than the `api_version`.
+				if get_api_version(&self.found_attributes).ok() < Some(version) {
+					self.errors.push(
+						Error::new(
+							method.span(),
+							"method `api_version` can not be greater than the trait `api_version`",
+						).to_compile_error()
+					);
+				}
+
+				let trait_id = generate_runtime_api_id_bytes(&self.trait_.to_string());
+				let version = version as u32;
+
+				quote! {
+					let runtime_version = #crate_::Core::<Block>::version(
+						self,
+						__runtime_api_at_param__,
+					)?;
+					if !runtime_version.has_api_with(&#trait_id, |v| v >= #version) {
+						return Err(#crate_::ApiError::UnsupportedApiVersion {
+							function: #function_name,
+							version: #version,
+						})
+					}
+				}
+			},
+			Ok(None) => quote!(),
+			Err(e) => {
+				self.errors.push(e.to_compile_error());
+				quote!()
+			}
+		};
+
 		// Generate the default implementation that calls the `method_runtime_api_impl` method.
 		method.default = Some(
 			parse_quote! {
 				{
+					#version_check
+
 					let runtime_api_impl_params_encoded =
 						#crate_::Encode::encode(&( #( &#params ),* ));
 
@@ -793,10 +832,17 @@ fn parse_runtime_api_version(version: &Attribute) -> Result<u64> {
 /// Generates the identifier as const variable for the given `trait_name`
 /// by hashing the `trait_name`.
 fn generate_runtime_api_id(trait_name: &str) -> TokenStream {
+	let id = generate_runtime_api_id_bytes(trait_name);
+
+	quote!( const ID: [u8; 8] = #id; )
+}
+
+/// Generates the identifier of the given `trait_name` as array expression.
+fn generate_runtime_api_id_bytes(trait_name: &str) -> TokenStream {
 	let mut res = [0; 8];
 	res.copy_from_slice(blake2_rfc::blake2b::blake2b(8, &[], trait_name.as_bytes()).as_bytes());
 
-	quote!( const ID: [u8; 8] = [ #( #res ),* ]; )
+	quote!( [ #( #res ),* ] )
 }
 
 /// Generates the const variable that holds the runtime api version.
@@ -847,6 +893,16 @@ fn get_api_version(found_attributes: &HashMap<&'static str, Attribute>) -> Resul
 	found_attributes.get(&API_VERSION_ATTRIBUTE).map(parse_runtime_api_version).unwrap_or(Ok(1))
 }
 
+/// Get the version a method was added in from the user given attribute or `Ok(None)`, if no
+/// attribute was given.
+fn get_method_api_version(
+	found_attributes: &HashMap<&'static str, Attribute>,
+) -> Result<Option<u64>> {
+	found_attributes.get(&API_VERSION_ATTRIBUTE)
+		.map(|v| parse_runtime_api_version(v).map(Some))
+		.unwrap_or(Ok(None))
+}
+
 /// Generate the declaration of the trait for the client side.
 fn generate_client_side_decls(decls: &[ItemTrait]) -> Result<TokenStream> {
 	let mut result = Vec::new();
@@ -913,6 +969,17 @@ impl CheckTraitDecl {
 				Err(e) => { self.errors.push(e); return; },
 			};
 
+			if changed_in.is_some() && attributes.contains_key(API_VERSION_ATTRIBUTE) {
+				self.errors.push(Error::new(
+					method.span(),
+					format!(
+						"`{}` and `{}` are not supported at once.",
+						API_VERSION_ATTRIBUTE,
+						CHANGED_IN_ATTRIBUTE,
+					),
+				));
+			}
+
 			method_to_signature_changed
 				.entry(method.sig.ident.clone())
 				.or_default()
@@ -159,6 +159,28 @@ pub const MAX_EXTRINSIC_DEPTH: u32 = 256;
 /// # fn main() {}
 /// ```
 ///
+/// It is also possible to add a new method to a runtime api trait, without requiring that all
+/// runtimes the client needs to call into know about it. Such a method is tagged with the
+/// `#[api_version(N)]` attribute, where `N` is the version of the api the method was added in
+/// and can not be greater than the version of the trait. Before calling into the runtime, the
+/// client side checks that the runtime at the given block implements the api with at least
+/// version `N` and returns [`ApiError::UnsupportedApiVersion`] otherwise.
+///
+/// ```rust
+/// sp_api::decl_runtime_apis! {
+///     #[api_version(3)]
+///     pub trait Balance {
+///         /// Get the balance.
+///         fn get_balance() -> u64;
+///         /// Only callable on runtimes that implement at least version 3 of this api.
+///         #[api_version(3)]
+///         fn get_free_balance() -> u64;
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+///
 /// To check if a given runtime implements a runtime api trait, the `RuntimeVersion` has the
 /// function `has_api<A>()`. Also the `ApiExt` provides a function `has_api<A>(at: &BlockId)` to
 /// check if the runtime at the given block id implements the requested runtime api trait.
@@ -428,6 +450,11 @@ pub enum ApiError {
 		#[source]
 		error: codec::Error,
 	},
+	#[error("The runtime doesn't support {function}, it was added in version {version} of the api")]
+	UnsupportedApiVersion {
+		function: &'static str,
+		version: u32,
+	},
 	#[error(transparent)]
 	Application(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
@@ -45,6 +45,8 @@ decl_runtime_apis! {
 		fn same_name();
 		#[changed_in(2)]
 		fn same_name() -> String;
+		#[api_version(2)]
+		fn added_in_version_2() -> u64;
 	}
 }
 
@@ -69,6 +71,10 @@ impl_runtime_apis! {
 
 	impl self::ApiWithCustomVersion<Block> for Runtime {
 		fn same_name() {}
+
+		fn added_in_version_2() -> u64 {
+			2
+		}
 	}
 
 	impl sp_api::Core<Block> for Runtime {
@@ -130,6 +136,10 @@ mock_impl_runtime_apis! {
 
 	impl ApiWithCustomVersion<Block> for MockApi {
 		fn same_name() {}
+
+		fn added_in_version_2() -> u64 {
+			2
+		}
 	}
 }
 
@@ -41,7 +41,9 @@ pub mod fixed_point;
 pub mod rational;
 
 pub use fixed_point::{FixedPointNumber, FixedPointOperand, FixedI64, FixedI128, FixedU128};
-pub use per_things::{PerThing, InnerOf, UpperOf, Percent, PerU16, Permill, Perbill, Perquintill};
+pub use per_things::{
+	PerThing, InnerOf, UpperOf, Percent, PerU16, Permill, Perbill, Perquintill, Rounding,
+};
 pub use rational::{Rational128, RationalInfinite};
 
 use sp_std::{prelude::*, cmp::Ordering, fmt::Debug, convert::TryInto};
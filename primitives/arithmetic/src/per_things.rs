@@ -151,7 +151,7 @@ pub trait PerThing:
 			Unsigned,
 		Self::Inner: Into<N>,
 	{
-		saturating_reciprocal_mul::<N, Self>(b, self.deconstruct(), Rounding::Nearest)
+		saturating_reciprocal_mul::<N, Self>(b, self.deconstruct(), Rounding::NearestPrefDown)
 	}
 
 	/// Saturating multiplication by the reciprocal of `self`.	The result is rounded down to the
@@ -237,6 +237,81 @@ pub trait PerThing:
 			ops::Div<N, Output=N> + ops::Rem<N, Output=N> + ops::Add<N, Output=N> + Unsigned,
 		Self::Inner: Into<N>;
 
+	/// Compute the fraction `p/q` exactly, rounding the result according to `rounding`.
+	///
+	/// Unlike [`Self::from_rational`], the result is not approximated by scaling `p` and `q`
+	/// down first, so it is always the correctly rounded value of `p/q`, for any size of `N`.
+	///
+	/// Returns `Err(())` if `q` is zero or `p` is greater than `q`.
+	///
+	/// ```rust
+	/// # use sp_arithmetic::{Percent, PerThing, Rounding};
+	/// # fn main () {
+	/// assert_eq!(
+	/// 	Percent::from_rational_with_rounding(989u64, 1000, Rounding::Down),
+	/// 	Ok(Percent::from_parts(98)),
+	/// );
+	/// assert_eq!(
+	/// 	Percent::from_rational_with_rounding(985u64, 1000, Rounding::NearestPrefUp),
+	/// 	Ok(Percent::from_parts(99)),
+	/// );
+	/// assert_eq!(
+	/// 	Percent::from_rational_with_rounding(985u64, 1000, Rounding::NearestPrefDown),
+	/// 	Ok(Percent::from_parts(98)),
+	/// );
+	/// # }
+	/// ```
+	fn from_rational_with_rounding<N>(p: N, q: N, rounding: Rounding) -> Result<Self, ()>
+	where
+		N: Clone + Ord + ops::Add<N, Output=N> + ops::Sub<N, Output=N> + Zero + Unsigned,
+	{
+		if q.is_zero() || p > q {
+			return Err(())
+		}
+		if p == q {
+			return Ok(Self::one())
+		}
+
+		// Long multiplication of `p` by the bits of `ACCURACY`, keeping the running quotient
+		// `parts` and remainder `rem < q` of the division by `q`. Since `p < q` the quotient never
+		// exceeds `ACCURACY`, and no intermediate value of type `N` exceeds `q`.
+		let accuracy: u128 = Self::ACCURACY.into();
+		let mut parts = Self::Inner::zero();
+		let mut rem = N::zero();
+		for bit in (0..128 - accuracy.leading_zeros()).rev() {
+			parts = parts + parts;
+			if rem >= q.clone() - rem.clone() {
+				rem = rem.clone() - (q.clone() - rem);
+				parts = parts + One::one();
+			} else {
+				rem = rem.clone() + rem;
+			}
+
+			if (accuracy >> bit) & 1 == 1 {
+				if rem >= q.clone() - p.clone() {
+					rem = rem - (q.clone() - p.clone());
+					parts = parts + One::one();
+				} else {
+					rem = rem + p.clone();
+				}
+			}
+		}
+
+		// `p < q`, so if `rem` is not zero the exact result is below `ACCURACY`, and rounding up
+		// cannot overflow it.
+		let round_up = match rounding {
+			Rounding::Down => false,
+			Rounding::Up => !rem.is_zero(),
+			Rounding::NearestPrefUp => !rem.is_zero() && rem >= q - rem.clone(),
+			Rounding::NearestPrefDown => rem > q - rem.clone(),
+		};
+		if round_up {
+			parts = parts + One::one();
+		}
+
+		Ok(Self::from_parts(parts))
+	}
+
 	/// Same as `Self::from_rational`.
 	#[deprecated = "Use from_rational instead"]
 	fn from_rational_approximation<N>(p: N, q: N) -> Self
@@ -253,11 +328,16 @@ pub trait PerThing:
 /// The rounding method to use.
 ///
 /// `PerThing`s are unsigned so `Up` means towards infinity and `Down` means towards zero.
-/// `Nearest` will round an exact half down.
-enum Rounding {
+#[derive(Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Rounding {
+	/// Round towards infinity.
 	Up,
+	/// Round towards zero.
 	Down,
-	Nearest,
+	/// Round to the nearest value, an exact half is rounded up.
+	NearestPrefUp,
+	/// Round to the nearest value, an exact half is rounded down.
+	NearestPrefDown,
 }
 
 /// Saturating reciprocal multiplication. Compute `x / self`, saturating at the numeric
@@ -330,10 +410,18 @@ where
 		},
 		// Round up if the fractional part of the result is greater than a half. An exact half is
 		// rounded down.
-		Rounding::Nearest => if rem_mul_upper % denom_upper > denom_upper / 2.into() {
+		Rounding::NearestPrefDown => if rem_mul_upper % denom_upper > denom_upper / 2.into() {
 			// `rem * numer / denom` is less than `numer`, so this will not overflow.
 			rem_mul_div_inner += 1.into();
 		},
+		// Round up if the fractional part of the result is at least a half.
+		Rounding::NearestPrefUp => {
+			let frac = rem_mul_upper % denom_upper;
+			if frac > 0.into() && frac >= denom_upper - frac {
+				// `rem * numer / denom` is less than `numer`, so this will not overflow.
+				rem_mul_div_inner += 1.into();
+			}
+		},
 	}
 	rem_mul_div_inner.into()
 }
@@ -517,6 +605,14 @@ macro_rules! implement_per_thing {
 				<Self as PerThing>::from_rational(p, q)
 			}
 
+			/// See [`PerThing::from_rational_with_rounding`].
+			pub fn from_rational_with_rounding<N>(p: N, q: N, rounding: Rounding) -> Result<Self, ()>
+				where N: Clone + Ord + ops::Add<N, Output=N> + ops::Sub<N, Output=N> + Zero +
+					Unsigned,
+			{
+				<Self as PerThing>::from_rational_with_rounding(p, q, rounding)
+			}
+
 			/// See [`PerThing::mul_floor`].
 			pub fn mul_floor<N>(self, b: N) -> N
 				where
@@ -685,7 +781,7 @@ macro_rules! implement_per_thing {
 		{
 			type Output = N;
 			fn mul(self, b: N) -> Self::Output {
-				overflow_prune_mul::<N, Self>(b, self.deconstruct(), Rounding::Nearest)
+				overflow_prune_mul::<N, Self>(b, self.deconstruct(), Rounding::NearestPrefDown)
 			}
 		}
 
@@ -989,6 +1085,40 @@ macro_rules! implement_per_thing {
 				$(per_thing_from_rationale_approx_test!($test_units);)*
 			}
 
+			#[test]
+			fn from_rational_with_rounding_works() {
+				use super::Rounding::*;
+				let max = $max as u128;
+
+				// `$max` is not a multiple of 7 for any of the types.
+				let seventh = |rounding| $name::from_rational_with_rounding(1u128, 7, rounding);
+				assert_eq!(seventh(Down), Ok($name::from_parts($max / 7)));
+				assert_eq!(seventh(Up), Ok($name::from_parts($max / 7 + 1)));
+
+				// exactly half a part.
+				let half = |rounding| $name::from_rational_with_rounding(1u128, 2 * max, rounding);
+				assert_eq!(half(Down), Ok($name::zero()));
+				assert_eq!(half(Up), Ok($name::from_parts(1)));
+				assert_eq!(half(NearestPrefDown), Ok($name::zero()));
+				assert_eq!(half(NearestPrefUp), Ok($name::from_parts(1)));
+
+				// the whole range of `N` is usable.
+				assert_eq!(
+					$name::from_rational_with_rounding(u128::MAX - 1, u128::MAX, Down),
+					Ok($name::from_parts($max - 1)),
+				);
+				assert_eq!(
+					$name::from_rational_with_rounding(u128::MAX - 1, u128::MAX, NearestPrefDown),
+					Ok($name::one()),
+				);
+				assert_eq!($name::from_rational_with_rounding(5u32, 5, Down), Ok($name::one()));
+				assert_eq!($name::from_rational_with_rounding(0u32, 5, Up), Ok($name::zero()));
+
+				// invalid fractions.
+				assert_eq!($name::from_rational_with_rounding(1u32, 0, Down), Err(()));
+				assert_eq!($name::from_rational_with_rounding(6u32, 5, Down), Err(()));
+			}
+
 			#[test]
 			fn per_things_mul_operates_in_output_type() {
 				// assert_eq!($name::from_float(0.5) * 100u32, 50u32);
@@ -1194,7 +1324,7 @@ macro_rules! implement_per_thing {
 						<$type>::max_value(),
 						<$type>::max_value(),
 						<$type>::max_value(),
-						super::Rounding::Nearest,
+						super::Rounding::NearestPrefDown,
 					),
 					0,
 				);
@@ -1203,7 +1333,7 @@ macro_rules! implement_per_thing {
 						<$type>::max_value() - 1,
 						<$type>::max_value(),
 						<$type>::max_value(),
-						super::Rounding::Nearest,
+						super::Rounding::NearestPrefDown,
 					),
 					<$type>::max_value() - 1,
 				);
@@ -1212,7 +1342,7 @@ macro_rules! implement_per_thing {
 						((<$type>::max_value() - 1) as $upper_type).pow(2),
 						<$type>::max_value(),
 						<$type>::max_value(),
-						super::Rounding::Nearest,
+						super::Rounding::NearestPrefDown,
 					),
 					1,
 				);
@@ -1222,7 +1352,7 @@ macro_rules! implement_per_thing {
 						(<$type>::max_value() as $upper_type).pow(2) - 1,
 						<$type>::max_value(),
 						<$type>::max_value(),
-						super::Rounding::Nearest,
+						super::Rounding::NearestPrefDown,
 					),
 					<$upper_type>::from((<$type>::max_value() - 1)),
 				);
@@ -1232,7 +1362,7 @@ macro_rules! implement_per_thing {
 						(<$type>::max_value() as $upper_type).pow(2),
 						<$type>::max_value(),
 						2 as $type,
-						super::Rounding::Nearest,
+						super::Rounding::NearestPrefDown,
 					),
 					<$type>::max_value() as $upper_type / 2,
 				);
@@ -1242,7 +1372,7 @@ macro_rules! implement_per_thing {
 						(<$type>::max_value() as $upper_type).pow(2) - 1,
 						2 as $type,
 						<$type>::max_value(),
-						super::Rounding::Nearest,
+						super::Rounding::NearestPrefDown,
 					),
 					2,
 				);
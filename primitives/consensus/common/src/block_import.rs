@@ -96,6 +96,24 @@ pub enum ForkChoiceStrategy {
 	Custom(bool),
 }
 
+/// State of a block that was downloaded without its history, e.g. by state sync.
+#[derive(PartialEq, Eq, Clone)]
+pub struct ImportedState<B: BlockT> {
+	/// Hash of the block the state belongs to.
+	pub block: B::Hash,
+	/// Storage keys and values of the state. Child tries are not supported.
+	pub state: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl<B: BlockT> std::fmt::Debug for ImportedState<B> {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fmt.debug_struct("ImportedState")
+			.field("block", &self.block)
+			.field("keys", &self.state.len())
+			.finish()
+	}
+}
+
 /// Data required to check validity of a Block.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BlockCheckParams<Block: BlockT> {
@@ -162,6 +180,9 @@ pub struct BlockImportParams<Block: BlockT, Transaction> {
 	pub allow_missing_state: bool,
 	/// Re-validate existing block.
 	pub import_existing: bool,
+	/// State to import for this block instead of executing it. The state root is checked
+	/// against the header.
+	pub state: Option<ImportedState<Block>>,
 	/// Cached full header hash (with post-digests applied).
 	pub post_hash: Option<Block::Hash>,
 }
@@ -184,6 +205,7 @@ impl<Block: BlockT, Transaction> BlockImportParams<Block, Transaction> {
 			fork_choice: None,
 			allow_missing_state: false,
 			import_existing: false,
+			state: None,
 			post_hash: None,
 		}
 	}
@@ -229,6 +251,7 @@ impl<Block: BlockT, Transaction> BlockImportParams<Block, Transaction> {
 			allow_missing_state: self.allow_missing_state,
 			fork_choice: self.fork_choice,
 			import_existing: self.import_existing,
+			state: self.state,
 			post_hash: self.post_hash,
 		}
 	}
@@ -42,6 +42,8 @@ pub struct BasicQueue<B: BlockT, Transaction> {
 	block_import_sender: TracingUnboundedSender<worker_messages::ImportBlocks<B>>,
 	/// Results coming from the worker task.
 	result_port: BufferedLinkReceiver<B>,
+	/// Prometheus metrics, shared with the background task.
+	metrics: Option<Metrics>,
 	_phantom: PhantomData<Transaction>,
 }
 
@@ -80,7 +82,7 @@ impl<B: BlockT, Transaction: Send + 'static> BasicQueue<B, Transaction> {
 			verifier,
 			block_import,
 			justification_import,
-			metrics,
+			metrics.clone(),
 		);
 
 		spawner.spawn_essential_blocking("basic-block-import-worker", future.boxed());
@@ -89,6 +91,7 @@ impl<B: BlockT, Transaction: Send + 'static> BasicQueue<B, Transaction> {
 			justification_sender,
 			block_import_sender,
 			result_port,
+			metrics,
 			_phantom: PhantomData,
 		}
 	}
@@ -101,6 +104,9 @@ impl<B: BlockT, Transaction: Send> ImportQueue<B> for BasicQueue<B, Transaction>
 		}
 
 		trace!(target: "sync", "Scheduling {} blocks for import", blocks.len());
+		if let Some(metrics) = self.metrics.as_ref() {
+			metrics.import_queue_pending_blocks.add(blocks.len() as u64);
+		}
 		let res =
 			self.block_import_sender.unbounded_send(worker_messages::ImportBlocks(origin, blocks));
 
@@ -129,6 +135,8 @@ impl<B: BlockT, Transaction: Send> ImportQueue<B> for BasicQueue<B, Transaction>
 					target: "sync",
 					"import_justification: Background import task is no longer alive"
 				);
+			} else if let Some(metrics) = self.metrics.as_ref() {
+				metrics.import_queue_pending_justifications.inc();
 			}
 		}
 	}
@@ -303,6 +311,7 @@ impl<B: BlockT> BlockImportWorker<B> {
 
 		if let Some(metrics) = self.metrics.as_ref() {
 			metrics.justification_import_time.observe(started.elapsed().as_secs_f64());
+			metrics.import_queue_pending_justifications.dec();
 		}
 
 		self.result_sender.justification_imported(who, &hash, number, success);
@@ -376,6 +385,10 @@ async fn import_many_blocks<B: BlockT, V: Verifier<B>, Transaction: Send + 'stat
 
 		if let Some(metrics) = metrics.as_ref() {
 			metrics.report_import::<B>(&import_result);
+			metrics.import_queue_pending_blocks.dec();
+			if import_result.is_ok() {
+				metrics.report_imported_block(&blocks_origin);
+			}
 		}
 
 		if import_result.is_ok() {
@@ -549,6 +562,7 @@ mod tests {
 					origin: None,
 					allow_missing_state: false,
 					import_existing: false,
+					state: None,
 				}],
 			)))
 			.unwrap();
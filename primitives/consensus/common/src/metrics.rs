@@ -18,17 +18,21 @@
 //! Metering tools for consensus
 
 use prometheus_endpoint::{
-	register, U64, Registry, PrometheusError, Opts, CounterVec, Histogram, HistogramVec, HistogramOpts
+	register, U64, Registry, PrometheusError, Opts, CounterVec, Gauge, Histogram, HistogramVec,
+	HistogramOpts,
 };
 
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
-use crate::import_queue::{BlockImportResult, BlockImportError};
+use crate::{BlockOrigin, import_queue::{BlockImportResult, BlockImportError}};
 
 /// Generic Prometheus metrics for common consensus functionality.
 #[derive(Clone)]
 pub(crate) struct Metrics {
 	pub import_queue_processed: CounterVec<U64>,
+	pub import_queue_blocks_imported: CounterVec<U64>,
+	pub import_queue_pending_blocks: Gauge<U64>,
+	pub import_queue_pending_justifications: Gauge<U64>,
 	pub block_verification_time: HistogramVec,
 	pub block_verification_and_import_time: Histogram,
 	pub justification_import_time: Histogram,
@@ -44,6 +48,30 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			import_queue_blocks_imported: register(
+				CounterVec::new(
+					Opts::new(
+						"import_queue_blocks_imported_total",
+						"Blocks successfully imported by import queue",
+					),
+					&["origin"],
+				)?,
+				registry,
+			)?,
+			import_queue_pending_blocks: register(
+				Gauge::new(
+					"import_queue_pending_blocks",
+					"Number of blocks waiting in the import queue",
+				)?,
+				registry,
+			)?,
+			import_queue_pending_justifications: register(
+				Gauge::new(
+					"import_queue_pending_justifications",
+					"Number of justifications waiting in the import queue",
+				)?,
+				registry,
+			)?,
 			block_verification_time: register(
 				HistogramVec::new(
 					HistogramOpts::new(
@@ -95,6 +123,21 @@ impl Metrics {
 		).inc();
 	}
 
+	pub fn report_imported_block(&self, origin: &BlockOrigin) {
+		let label = match origin {
+			BlockOrigin::Genesis => "genesis",
+			BlockOrigin::NetworkInitialSync => "network_initial_sync",
+			BlockOrigin::NetworkBroadcast => "network_broadcast",
+			BlockOrigin::ConsensusBroadcast => "consensus_broadcast",
+			BlockOrigin::Own => "own",
+			BlockOrigin::File => "file",
+		};
+
+		self.import_queue_blocks_imported.with_label_values(
+			&[label]
+		).inc();
+	}
+
 	pub fn report_verification(&self, success: bool, time: std::time::Duration) {
 		self.block_verification_time.with_label_values(
 			&[if success { "success" } else { "verification_failed" }]
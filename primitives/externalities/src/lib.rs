@@ -228,13 +228,13 @@ pub trait Externalities: ExtensionStore {
 	/// no transaction is open that can be closed.
 	fn storage_commit_transaction(&mut self) -> Result<(), ()>;
 
-	/// Index specified transaction slice and store it.
-	fn storage_index_transaction(&mut self, _index: u32, _offset: u32) {
+	/// Index the last `size` bytes of the specified transaction under `hash` and store them.
+	fn storage_index_transaction(&mut self, _index: u32, _hash: &[u8], _size: u32) {
 		unimplemented!("storage_index_transaction");
 	}
 
 	/// Renew existing piece of transaction storage.
-	fn storage_renew_transaction_index(&mut self, _index: u32, _hash: &[u8], _size: u32) {
+	fn storage_renew_transaction_index(&mut self, _index: u32, _hash: &[u8]) {
 		unimplemented!("storage_renew_transaction_index");
 	}
 
@@ -282,12 +282,10 @@ pub trait Externalities: ExtensionStore {
 	/// Adds new storage keys to the DB tracking whitelist.
 	fn set_whitelist(&mut self, new: Vec<TrackedStorageKey>);
 
-	/// !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
-	/// Benchmarking related functionality and shouldn't be used anywhere else!
-	/// !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
-	///
 	/// Returns estimated proof size for the state queries so far.
-	/// Proof is reset on commit and wipe.
+	///
+	/// Returns `None` if no proof is being recorded. When benchmarking, the proof is reset on
+	/// commit and wipe.
 	fn proof_size(&self) -> Option<u32> {
 		None
 	}
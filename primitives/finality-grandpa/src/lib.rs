@@ -496,7 +496,7 @@ sp_api::decl_runtime_apis! {
 	/// applied in the runtime after those N blocks have passed.
 	///
 	/// The consensus protocol will coordinate the handoff externally.
-	#[api_version(2)]
+	#[api_version(3)]
 	pub trait GrandpaApi {
 		/// Get the current GRANDPA authorities and weights. This should not change except
 		/// for when changes are scheduled and the corresponding delay has passed.
@@ -506,6 +506,27 @@ sp_api::decl_runtime_apis! {
 		/// is finalized by the authorities from block B-1.
 		fn grandpa_authorities() -> AuthorityList;
 
+		/// Get the id of the current GRANDPA authority set.
+		///
+		/// This is the set id of the authorities returned by [`Self::grandpa_authorities`].
+		fn current_set_id() -> SetId;
+
+		/// Get the standard authority set change which was signaled but not yet enacted.
+		///
+		/// Returns the block number at which the change was signaled along with the change.
+		/// The change is enacted `delay` blocks after that block.
+		fn pending_change() -> Option<(NumberFor<Block>, ScheduledChange<NumberFor<Block>>)>;
+
+		/// Get the forced authority set change which was signaled but not yet enacted.
+		///
+		/// Returns the block number at which the change was signaled, the median last
+		/// finalized block number that the new voters should start from, and the change.
+		fn pending_forced_change() -> Option<(
+			NumberFor<Block>,
+			NumberFor<Block>,
+			ScheduledChange<NumberFor<Block>>,
+		)>;
+
 		/// Submits an unsigned extrinsic to report an equivocation. The caller
 		/// must provide the equivocation proof and a key ownership proof
 		/// (should be obtained using `generate_key_ownership_proof`). The
@@ -81,6 +81,36 @@ pub enum EcdsaVerifyError {
 	BadSignature,
 }
 
+/// Error reading a value from a storage proof.
+#[derive(Encode, Decode, PartialEq, sp_core::RuntimeDebug)]
+pub enum ReadProofError {
+	/// The proof doesn't match the root, or lacks some of the trie nodes needed to read the key.
+	InvalidProof,
+}
+
+#[cfg(feature = "std")]
+fn read_proof_check<H>(
+	root: H::Out,
+	proof: Vec<Vec<u8>>,
+	child_info: Option<ChildInfo>,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, ReadProofError>
+where
+	H: sp_core::Hasher,
+	H::Out: Ord + codec::Codec,
+{
+	let proof = sp_trie::StorageProof::new(proof);
+	let values = match child_info {
+		Some(child_info) =>
+			sp_state_machine::read_child_proof_check::<H, _>(root, proof, &child_info, &[key]),
+		None => sp_state_machine::read_proof_check::<H, _>(root, proof, &[key]),
+	};
+	values
+		.ok()
+		.and_then(|mut values| values.remove(key))
+		.ok_or(ReadProofError::InvalidProof)
+}
+
 /// The outcome of calling `storage_kill`. Returned value is the number of storage items
 /// removed from the trie from making the `storage_kill` call.
 #[derive(PassByCodec, Encode, Decode)]
@@ -211,6 +241,15 @@ pub trait Storage {
 		self.storage_commit_transaction()
 			.expect("No open transaction that can be committed.");
 	}
+
+	/// Returns the estimated encoded size of the storage proof recorded so far.
+	///
+	/// Returns `None` if the node is not recording a proof while executing the runtime, e.g.
+	/// when importing a block. Block authors that record a proof can use this to stop including
+	/// extrinsics when the proof size limit is reached.
+	fn proof_size(&self) -> Option<u32> {
+		Externalities::proof_size(*self)
+	}
 }
 
 /// Interface for accessing the child storage for default child trie,
@@ -427,6 +466,75 @@ pub trait Trie {
 	fn keccak_256_ordered_root(input: Vec<Vec<u8>>) -> H256 {
 		Layout::<sp_core::KeccakHasher>::ordered_trie_root(input)
 	}
+
+	/// Verify that `proof` proves `key` to be mapped to `value` in a Blake2-256 trie with `root`.
+	fn blake2_256_verify_proof(root: H256, proof: &[Vec<u8>], key: &[u8], value: &[u8]) -> bool {
+		sp_trie::verify_trie_proof::<Layout<sp_core::Blake2Hasher>, _, _, _>(
+			&root,
+			proof,
+			&[(key, Some(value))],
+		).is_ok()
+	}
+
+	/// Verify that `proof` proves `key` to be mapped to `value` in a Keccak-256 trie with `root`.
+	fn keccak_256_verify_proof(root: H256, proof: &[Vec<u8>], key: &[u8], value: &[u8]) -> bool {
+		sp_trie::verify_trie_proof::<Layout<sp_core::KeccakHasher>, _, _, _>(
+			&root,
+			proof,
+			&[(key, Some(value))],
+		).is_ok()
+	}
+
+	/// Read the value of `key` from a state `proof` of a Blake2-256 trie with `root`.
+	///
+	/// `proof` is the list of trie nodes of a [`sp_trie::StorageProof`], as generated when reading
+	/// from the state of another chain. Returns `Ok(None)` if the proof shows the key is absent.
+	fn blake2_256_read_proof(
+		root: H256,
+		proof: Vec<Vec<u8>>,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, ReadProofError> {
+		read_proof_check::<sp_core::Blake2Hasher>(root, proof, None, key)
+	}
+
+	/// Read the value of `key` in the default child trie `storage_key` from a state `proof` of a
+	/// Blake2-256 trie with `root`.
+	///
+	/// The proof has to contain the path to the root of the child trie in the top trie as well.
+	fn blake2_256_read_child_proof(
+		root: H256,
+		proof: Vec<Vec<u8>>,
+		storage_key: &[u8],
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, ReadProofError> {
+		let child_info = ChildInfo::new_default(storage_key);
+		read_proof_check::<sp_core::Blake2Hasher>(root, proof, Some(child_info), key)
+	}
+
+	/// Read the value of `key` from a state `proof` of a Keccak-256 trie with `root`.
+	///
+	/// See [`Self::blake2_256_read_proof`].
+	fn keccak_256_read_proof(
+		root: H256,
+		proof: Vec<Vec<u8>>,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, ReadProofError> {
+		read_proof_check::<sp_core::KeccakHasher>(root, proof, None, key)
+	}
+
+	/// Read the value of `key` in the default child trie `storage_key` from a state `proof` of a
+	/// Keccak-256 trie with `root`.
+	///
+	/// See [`Self::blake2_256_read_child_proof`].
+	fn keccak_256_read_child_proof(
+		root: H256,
+		proof: Vec<Vec<u8>>,
+		storage_key: &[u8],
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, ReadProofError> {
+		let child_info = ChildInfo::new_default(storage_key);
+		read_proof_check::<sp_core::KeccakHasher>(root, proof, Some(child_info), key)
+	}
 }
 
 /// Interface that provides miscellaneous functions for communicating between the runtime and the node.
@@ -813,6 +921,22 @@ pub trait Hashing {
 	}
 }
 
+/// Interface that provides transaction indexing API.
+#[runtime_interface]
+pub trait TransactionIndex {
+	/// Index the last `size` bytes of extrinsic number `extrinsic` in the current block under
+	/// `context_hash`.
+	fn index(&mut self, extrinsic: u32, size: u32, context_hash: [u8; 32]) {
+		self.storage_index_transaction(extrinsic, &context_hash, size);
+	}
+
+	/// Renew the storage of the data indexed under `context_hash` as part of extrinsic number
+	/// `extrinsic` in the current block.
+	fn renew(&mut self, extrinsic: u32, context_hash: [u8; 32]) {
+		self.storage_renew_transaction_index(extrinsic, &context_hash);
+	}
+}
+
 /// Interface that provides functions to access the Offchain DB.
 #[runtime_interface]
 pub trait OffchainIndex {
@@ -1423,6 +1547,7 @@ pub type SubstrateHostFunctions = (
 	crate::trie::HostFunctions,
 	offchain_index::HostFunctions,
 	runtime_tasks::HostFunctions,
+	transaction_index::HostFunctions,
 );
 
 #[cfg(test)]
@@ -1430,7 +1555,7 @@ mod tests {
 	use super::*;
 	use sp_state_machine::BasicExternalities;
 	use sp_core::{
-		storage::Storage, map, traits::TaskExecutorExt, testing::TaskExecutor,
+		storage::{Storage, StorageChild}, map, traits::TaskExecutorExt, testing::TaskExecutor,
 	};
 	use std::any::TypeId;
 
@@ -1474,6 +1599,46 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn read_proof_works() {
+		use sp_state_machine::{InMemoryBackend, prove_read, prove_child_read};
+
+		let child_info = ChildInfo::new_default(b"child");
+		let backend: InMemoryBackend<sp_core::Blake2Hasher> = Storage {
+			top: map![b"foo".to_vec() => b"bar".to_vec()],
+			children_default: map![
+				child_info.storage_key().to_vec() => StorageChild {
+					data: map![b"baz".to_vec() => b"qux".to_vec()],
+					child_info: child_info.clone(),
+				}
+			],
+		}.into();
+		let root = *backend.root();
+
+		let proof: Vec<_> = prove_read(backend.clone(), &[b"foo", b"nop"])
+			.unwrap()
+			.iter_nodes()
+			.collect();
+		assert_eq!(
+			trie::blake2_256_read_proof(root, proof.clone(), b"foo"),
+			Ok(Some(b"bar".to_vec())),
+		);
+		assert_eq!(trie::blake2_256_read_proof(root, proof.clone(), b"nop"), Ok(None));
+		assert_eq!(
+			trie::blake2_256_read_proof(H256::repeat_byte(1), proof, b"foo"),
+			Err(ReadProofError::InvalidProof),
+		);
+
+		let proof = prove_child_read(backend, &child_info, &[b"baz"])
+			.unwrap()
+			.iter_nodes()
+			.collect();
+		assert_eq!(
+			trie::blake2_256_read_child_proof(root, proof, b"child", b"baz"),
+			Ok(Some(b"qux".to_vec())),
+		);
+	}
+
 	#[test]
 	fn clear_prefix_works() {
 		let mut t = BasicExternalities::new(Storage {
@@ -76,7 +76,7 @@ pub use sp_core::RuntimeDebug;
 
 /// Re-export top-level arithmetic stuff.
 pub use sp_arithmetic::{
-	PerThing, Perquintill, Perbill, Permill, Percent, PerU16, InnerOf, UpperOf,
+	PerThing, Perquintill, Perbill, Permill, Percent, PerU16, InnerOf, UpperOf, Rounding,
 	Rational128, FixedI64, FixedI128, FixedU128, FixedPointNumber, FixedPointOperand,
 	traits::SaturatedConversion,
 };
@@ -546,6 +546,8 @@ pub enum TokenError {
 	Underflow,
 	/// An overflow would occur.
 	Overflow,
+	/// Operation is not supported by the asset.
+	Unsupported,
 }
 
 impl From<TokenError> for &'static str {
@@ -559,6 +561,7 @@ impl From<TokenError> for &'static str {
 			TokenError::Frozen => "Funds exist but are frozen",
 			TokenError::Underflow => "An underflow would occur",
 			TokenError::Overflow => "An overflow would occur",
+			TokenError::Unsupported => "Operation is not supported by the asset",
 		}
 	}
 }
@@ -275,11 +275,39 @@ impl<A, B: Default> Convert<A, B> for () {
 	fn convert(_: A) -> B { Default::default() }
 }
 
+/// Extensible conversion trait. Generic over only the source type, the destination type is
+/// associated.
+pub trait Morph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion.
+	fn morph(a: A) -> Self::Outcome;
+}
+
+/// Extensible fallible conversion trait. Generic over only the source type, the destination type
+/// is associated.
+pub trait TryMorph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion.
+	fn try_morph(a: A) -> Result<Self::Outcome, ()>;
+}
+
 /// A structure that performs identity conversion.
 pub struct Identity;
 impl<T> Convert<T, T> for Identity {
 	fn convert(a: T) -> T { a }
 }
+impl<T> Morph<T> for Identity {
+	type Outcome = T;
+	fn morph(a: T) -> T { a }
+}
+impl<T> TryMorph<T> for Identity {
+	type Outcome = T;
+	fn try_morph(a: T) -> Result<T, ()> { Ok(a) }
+}
 
 /// A structure that performs standard conversion using the standard Rust conversion traits.
 pub struct ConvertInto;
@@ -1038,8 +1066,24 @@ pub trait OpaqueKeys: Clone {
 	fn get<T: Decode>(&self, i: super::KeyTypeId) -> Option<T> {
 		T::decode(&mut self.get_raw(i)).ok()
 	}
-	/// Verify a proof of ownership for the keys.
-	fn ownership_proof_is_valid(&self, _proof: &[u8]) -> bool { true }
+	/// Create a proof that `owner` holds the private keys of this set, by signing the
+	/// [`session_keys_ownership_statement`] with every key.
+	///
+	/// Returns `None` if any of the private keys is not available.
+	fn create_ownership_proof(&self, _owner: &[u8]) -> Option<Vec<u8>> { None }
+	/// Verify a proof that `owner` holds the private keys of this set.
+	///
+	/// Rejects every proof by default, so implementations that can not verify one do not accept
+	/// any keys that require it.
+	fn ownership_proof_is_valid(&self, _owner: &[u8], _proof: &[u8]) -> bool { false }
+}
+
+/// The message that every key of an [`OpaqueKeys`] set signs to prove that `owner` holds it.
+///
+/// `owner` is usually the SCALE encoded account that registers the keys. The prefix keeps these
+/// signatures from being mistaken for signatures of anything else.
+pub fn session_keys_ownership_statement(owner: &[u8]) -> Vec<u8> {
+	(b"POP_", owner).encode()
 }
 
 /// Input that adds infinite number of zero after wrapped input.
@@ -1256,6 +1300,31 @@ macro_rules! impl_opaque_keys {
 				$crate::codec::Encode::encode(&keys)
 			}
 
+			/// Generate a set of keys with optionally using the given seed, together with a proof
+			/// that `owner` holds them.
+			///
+			/// The generated key pairs are stored in the keystore.
+			///
+			/// Returns the concatenated SCALE encoded public keys and the ownership proof, which
+			/// are the concatenated SCALE encoded signatures of the keys.
+			pub fn generate_with_ownership_proof(
+				owner: &[u8],
+				seed: Option<$crate::sp_std::vec::Vec<u8>>,
+			) -> ($crate::sp_std::vec::Vec<u8>, $crate::sp_std::vec::Vec<u8>) {
+				let keys = Self{
+					$(
+						$field: <
+							<
+								$type as $crate::BoundToRuntimeAppPublic
+							>::Public as $crate::RuntimeAppPublic
+						>::generate_pair(seed.clone()),
+					)*
+				};
+				let proof = $crate::traits::OpaqueKeys::create_ownership_proof(&keys, owner)
+					.expect("The key pairs were just generated and stored in the keystore; qed");
+				($crate::codec::Encode::encode(&keys), proof)
+			}
+
 			/// Converts `Self` into a `Vec` of `(raw public key, KeyTypeId)`.
 			pub fn into_raw_public_keys(
 				self,
@@ -1316,6 +1385,43 @@ macro_rules! impl_opaque_keys {
 					_ => &[],
 				}
 			}
+
+			fn create_ownership_proof(
+				&self,
+				owner: &[u8],
+			) -> Option<$crate::sp_std::vec::Vec<u8>> {
+				let statement = $crate::traits::session_keys_ownership_statement(owner);
+				let mut proof = $crate::sp_std::vec::Vec::new();
+				$(
+					$crate::codec::Encode::encode_to(
+						&$crate::RuntimeAppPublic::sign(&self.$field, &statement)?,
+						&mut proof,
+					);
+				)*
+				Some(proof)
+			}
+
+			fn ownership_proof_is_valid(&self, owner: &[u8], mut proof: &[u8]) -> bool {
+				let statement = $crate::traits::session_keys_ownership_statement(owner);
+				$(
+					let signature = <
+						<
+							<
+								$type as $crate::BoundToRuntimeAppPublic
+							>::Public as $crate::RuntimeAppPublic
+						>::Signature as $crate::codec::Decode
+					>::decode(&mut proof);
+					match signature {
+						Ok(signature) if $crate::RuntimeAppPublic::verify(
+							&self.$field,
+							&statement,
+							&signature,
+						) => (),
+						_ => return false,
+					}
+				)*
+				proof.is_empty()
+			}
 		}
 	};
 }
@@ -1422,6 +1528,31 @@ mod tests {
 		app_crypto!(sr25519, KeyTypeId(*b"test"));
 	}
 
+	crate::impl_opaque_keys! {
+		pub struct TestKeys {
+			pub first: crate::testing::UintAuthorityId,
+			pub second: crate::testing::UintAuthorityId,
+		}
+	}
+
+	#[test]
+	fn opaque_keys_ownership_proof_works() {
+		let keys = TestKeys { first: 1.into(), second: 2.into() };
+		let proof = keys.create_ownership_proof(b"owner").unwrap();
+
+		assert!(keys.ownership_proof_is_valid(b"owner", &proof));
+		assert!(!keys.ownership_proof_is_valid(b"other owner", &proof));
+		assert!(!keys.ownership_proof_is_valid(b"owner", &proof[..proof.len() - 1]));
+		assert!(!keys.ownership_proof_is_valid(b"owner", &[&proof[..], &[0]].concat()));
+
+		let other_keys = TestKeys { first: 1.into(), second: 3.into() };
+		assert!(!other_keys.ownership_proof_is_valid(b"owner", &proof));
+
+		let (encoded, proof) = TestKeys::generate_with_ownership_proof(b"owner", None);
+		let keys = TestKeys::decode(&mut &encoded[..]).unwrap();
+		assert!(keys.ownership_proof_is_valid(b"owner", &proof));
+	}
+
 	#[test]
 	fn app_verify_works() {
 		use t::*;
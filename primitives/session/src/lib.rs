@@ -24,15 +24,27 @@ use codec::{Encode, Decode};
 #[cfg(feature = "std")]
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 #[cfg(feature = "std")]
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 
 use sp_core::RuntimeDebug;
 use sp_core::crypto::KeyTypeId;
 use sp_staking::SessionIndex;
 use sp_std::vec::Vec;
 
+/// Session keys generated by the runtime, together with a proof of their ownership.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct OpaqueGeneratedSessionKeys {
+	/// The concatenated SCALE encoded public keys.
+	pub keys: Vec<u8>,
+	/// The proof that the owner the keys were generated for holds them.
+	///
+	/// It is passed alongside the keys when registering them, e.g. to `pallet_session::set_keys`.
+	pub proof: Vec<u8>,
+}
+
 sp_api::decl_runtime_apis! {
 	/// Session keys runtime api.
+	#[api_version(2)]
 	pub trait SessionKeys {
 		/// Generate a set of session keys with optionally using the given seed.
 		/// The keys should be stored within the keystore exposed via runtime
@@ -41,8 +53,21 @@ sp_api::decl_runtime_apis! {
 		/// The seed needs to be a valid `utf8` string.
 		///
 		/// Returns the concatenated SCALE encoded public keys.
+		#[changed_in(2)]
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8>;
 
+		/// Generate a set of session keys with optionally using the given seed, together with a
+		/// proof that `owner` holds them.
+		/// The keys should be stored within the keystore exposed via runtime
+		/// externalities.
+		///
+		/// `owner` is the SCALE encoded account that is going to register the keys. The seed
+		/// needs to be a valid `utf8` string.
+		fn generate_session_keys(
+			owner: Vec<u8>,
+			seed: Option<Vec<u8>>,
+		) -> OpaqueGeneratedSessionKeys;
+
 		/// Decode the given public session keys.
 		///
 		/// Returns the list of public raw public keys + key type.
@@ -120,9 +145,17 @@ where
 	T::Api: SessionKeys<Block>,
 {
 	let runtime_api = client.runtime_api();
+	let has_api_v2 = runtime_api.has_api_with::<dyn SessionKeys<Block>, _>(at, |v| v >= 2)?;
 
 	for seed in seeds {
-		runtime_api.generate_session_keys(at, Some(seed.as_bytes().to_vec()))?;
+		let seed = Some(seed.as_bytes().to_vec());
+		// The ownership proof is not needed, as the initial keys are not registered by an account.
+		if has_api_v2 {
+			runtime_api.generate_session_keys(at, Vec::new(), seed)?;
+		} else {
+			#[allow(deprecated)]
+			runtime_api.generate_session_keys_before_version_2(at, seed)?;
+		}
 	}
 
 	Ok(())
@@ -246,9 +246,11 @@ pub trait Backend<H: Hasher>: sp_std::fmt::Debug {
 	/// Update the whitelist for tracking db reads/writes
 	fn set_whitelist(&self, _: Vec<TrackedStorageKey>) {}
 
-	/// Estimate proof size
+	/// Estimate the encoded size of the proof recorded for the state queries so far.
+	///
+	/// Returns `None` if this backend doesn't record a proof.
 	fn proof_size(&self) -> Option<u32> {
-		unimplemented!()
+		None
 	}
 }
 
@@ -141,7 +141,9 @@ mod changes_trie {
 
 #[cfg(feature = "std")]
 mod std_reexport {
-	pub use sp_trie::{trie_types::{Layout, TrieDBMut}, StorageProof, TrieMut, DBValue, MemoryDB};
+	pub use sp_trie::{
+		trie_types::{Layout, TrieDBMut}, StorageProof, CompactProof, TrieMut, DBValue, MemoryDB,
+	};
 	pub use crate::testing::TestExternalities;
 	pub use crate::basic::BasicExternalities;
 	pub use crate::read_only::{ReadOnlyExternalities, InspectState};
@@ -742,6 +744,70 @@ mod execution {
 		prove_child_read_on_trie_backend(trie_backend, child_info, keys)
 	}
 
+	/// Generate range storage read proof.
+	///
+	/// Reads the storage keys that follow `start_at` (or all keys, when `None`) in lexicographic
+	/// order until the recorded proof reaches `size_limit` bytes. Returns the proof together with
+	/// the number of keys that were read.
+	pub fn prove_range_read_with_size<B, H>(
+		mut backend: B,
+		size_limit: usize,
+		start_at: Option<&[u8]>,
+	) -> Result<(StorageProof, u32), Box<dyn Error>>
+	where
+		B: Backend<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let trie_backend = backend.as_trie_backend()
+			.ok_or_else(|| Box::new(ExecutionError::UnableToGenerateProof) as Box<dyn Error>)?;
+		prove_range_read_with_size_on_trie_backend(trie_backend, size_limit, start_at)
+	}
+
+	/// Generate range storage read proof on pre-created trie backend.
+	pub fn prove_range_read_with_size_on_trie_backend<S, H>(
+		trie_backend: &TrieBackend<S, H>,
+		size_limit: usize,
+		start_at: Option<&[u8]>,
+	) -> Result<(StorageProof, u32), Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let proving_backend = proving_backend::ProvingBackend::<_, H>::new(trie_backend);
+		let mut count = 0;
+		let mut key = match start_at {
+			Some(start_at) => start_at.to_vec(),
+			None => {
+				// `next_storage_key` never returns the empty key, so check it explicitly.
+				let value = proving_backend.storage(&[])
+					.map_err(|e| Box::new(e) as Box<dyn Error>)?;
+				if value.is_some() {
+					count += 1;
+				}
+				Vec::new()
+			},
+		};
+
+		// Always include at least one key, so that every proof makes progress.
+		loop {
+			let next_key = match proving_backend.next_storage_key(&key)
+				.map_err(|e| Box::new(e) as Box<dyn Error>)?
+			{
+				Some(next_key) => next_key,
+				None => break,
+			};
+			proving_backend.storage(&next_key).map_err(|e| Box::new(e) as Box<dyn Error>)?;
+			count += 1;
+			key = next_key;
+			if proving_backend.estimate_encoded_size() >= size_limit {
+				break;
+			}
+		}
+		Ok((proving_backend.extract_proof(), count))
+	}
+
 	/// Generate storage read proof on pre-created trie backend.
 	pub fn prove_read_on_trie_backend<S, H, I>(
 		trie_backend: &TrieBackend<S, H>,
@@ -806,6 +872,23 @@ mod execution {
 		Ok(result)
 	}
 
+	/// Check storage range proof, generated by `prove_range_read_with_size` call.
+	///
+	/// Returns the proven key-value pairs that follow `start_at` and whether they reach the end
+	/// of the state.
+	pub fn read_range_proof_check<H>(
+		root: H::Out,
+		proof: StorageProof,
+		start_at: Option<&[u8]>,
+	) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, bool), Box<dyn Error>>
+	where
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let proving_backend = create_proof_check_backend::<H>(root, proof)?;
+		read_range_proof_check_on_proving_backend(&proving_backend, start_at)
+	}
+
 	/// Check child storage read proof, generated by `prove_child_read` call.
 	pub fn read_child_proof_check<H, I>(
 		root: H::Out,
@@ -844,6 +927,45 @@ mod execution {
 		proving_backend.storage(key).map_err(|e| Box::new(e) as Box<dyn Error>)
 	}
 
+	/// Check storage range proof on pre-created proving backend.
+	///
+	/// The proven range ends at the first trie node that is missing from the proof.
+	pub fn read_range_proof_check_on_proving_backend<H>(
+		proving_backend: &TrieBackend<MemoryDB<H>, H>,
+		start_at: Option<&[u8]>,
+	) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, bool), Box<dyn Error>>
+	where
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let mut values = Vec::new();
+		let mut key = match start_at {
+			Some(start_at) => start_at.to_vec(),
+			None => {
+				match proving_backend.storage(&[]) {
+					Ok(Some(value)) => values.push((Vec::new(), value)),
+					Ok(None) => {},
+					Err(_) => return Ok((values, false)),
+				}
+				Vec::new()
+			},
+		};
+
+		loop {
+			let next_key = match proving_backend.next_storage_key(&key) {
+				Ok(Some(next_key)) => next_key,
+				Ok(None) => return Ok((values, true)),
+				Err(_) => return Ok((values, false)),
+			};
+			match proving_backend.storage(&next_key) {
+				Ok(Some(value)) => values.push((next_key.clone(), value)),
+				Ok(None) => return Err(Box::new(ExecutionError::InvalidProof)),
+				Err(_) => return Ok((values, false)),
+			}
+			key = next_key;
+		}
+	}
+
 	/// Check child storage read proof on pre-created proving backend.
 	pub fn read_child_proof_check_on_proving_backend<H>(
 		proving_backend: &TrieBackend<MemoryDB<H>, H>,
@@ -1459,6 +1581,92 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn compact_proof_round_trip_and_is_smaller() {
+		let child_info = ChildInfo::new_default(b"sub1");
+		let remote_backend = trie_backend::tests::test_trie();
+		let remote_root = remote_backend.storage_root(::std::iter::empty()).0;
+		let remote_proof = prove_child_read(
+			remote_backend,
+			&child_info,
+			&[b"value3"],
+		).unwrap();
+
+		let compact_proof = remote_proof.clone()
+			.into_compact_proof::<BlakeTwo256>(remote_root)
+			.unwrap();
+		assert!(compact_proof.encoded_size() < remote_proof.encoded_size());
+
+		// decoding checks the root and gives back an equivalent proof
+		let (decoded_proof, root) = StorageProof::from_compact_proof::<BlakeTwo256>(
+			&compact_proof,
+			Some(&remote_root),
+		).unwrap();
+		assert_eq!(root, remote_root);
+		let local_result = read_child_proof_check::<BlakeTwo256, _>(
+			remote_root,
+			decoded_proof,
+			&child_info,
+			&[b"value3"],
+		).unwrap();
+		assert_eq!(
+			local_result.into_iter().collect::<Vec<_>>(),
+			vec![(b"value3".to_vec(), Some(vec![142]))],
+		);
+
+		// a wrong expected root is rejected
+		assert!(StorageProof::from_compact_proof::<BlakeTwo256>(
+			&compact_proof,
+			Some(&Default::default()),
+		).is_err());
+
+		// compression round trips
+		let compressed = compact_proof.compress(1024 * 1024).unwrap();
+		assert_eq!(
+			sp_trie::CompactProof::decompress(&compressed, 1024 * 1024).unwrap(),
+			compact_proof,
+		);
+	}
+
+	#[test]
+	fn prove_range_read_and_proof_check_works() {
+		let state: BTreeMap<Vec<u8>, Vec<u8>> = (0u32..100)
+			.map(|i| (i.to_be_bytes().to_vec(), vec![i as u8; 32]))
+			.collect();
+		let remote_backend: InMemoryBackend<BlakeTwo256> = state.clone().into();
+		let remote_root = remote_backend.storage_root(std::iter::empty()).0;
+
+		let mut start_at: Option<Vec<u8>> = None;
+		let mut read = Vec::new();
+		let mut completed = false;
+		let mut requests = 0;
+		while !completed {
+			let (proof, count) = prove_range_read_with_size_on_trie_backend(
+				&remote_backend,
+				256,
+				start_at.as_ref().map(|k| k.as_slice()),
+			).unwrap();
+			let (values, complete) = read_range_proof_check::<BlakeTwo256>(
+				remote_root,
+				proof,
+				start_at.as_ref().map(|k| k.as_slice()),
+			).unwrap();
+			assert!(values.len() >= count as usize);
+			start_at = values.last().map(|(k, _)| k.clone());
+			read.extend(values);
+			completed = complete;
+			requests += 1;
+		}
+
+		// the size limit splits the state into several chunks
+		assert!(requests > 1);
+		assert_eq!(read, state.into_iter().collect::<Vec<_>>());
+
+		// a proof for a different root is rejected
+		let (proof, _) = prove_range_read_with_size(remote_backend, 256, None).unwrap();
+		assert!(read_range_proof_check::<BlakeTwo256>(Default::default(), proof, None).is_err());
+	}
+
 	#[test]
 	fn child_storage_uuid() {
 
@@ -212,6 +212,11 @@ impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> ProvingBackend<'a, S, H>
 	pub fn extract_proof(&self) -> StorageProof {
 		self.0.essence().backend_storage().proof_recorder.to_storage_proof()
 	}
+
+	/// Returns the estimated encoded size of the proof recorded so far.
+	pub fn estimate_encoded_size(&self) -> usize {
+		self.0.essence().backend_storage().proof_recorder.estimate_encoded_size()
+	}
 }
 
 impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> TrieBackendStorage<H>
@@ -333,6 +338,10 @@ impl<'a, S, H> Backend<H> for ProvingBackend<'a, S, H>
 	fn usage_info(&self) -> crate::stats::UsageInfo {
 		self.0.usage_info()
 	}
+
+	fn proof_size(&self) -> Option<u32> {
+		Some(self.estimate_encoded_size() as u32)
+	}
 }
 
 /// Create proof check backend.
@@ -527,4 +536,28 @@ mod tests {
 		assert!(backend.storage(b"doesnotexist2").unwrap().is_none());
 		check_estimation(&backend);
 	}
+
+	#[test]
+	fn proof_size_is_exposed_through_externalities() {
+		let trie_backend = test_trie();
+		let backend = test_proving(&trie_backend);
+		let mut overlay = crate::OverlayedChanges::default();
+		let mut cache = crate::StorageTransactionCache::default();
+		let ext = crate::Ext::<BlakeTwo256, u64, _>::new(
+			&mut overlay,
+			&mut cache,
+			&backend,
+			None,
+			None,
+		);
+
+		assert_eq!(sp_externalities::Externalities::proof_size(&ext), Some(1));
+
+		assert_eq!(sp_externalities::Externalities::storage(&ext, b"value1"), Some(vec![42]));
+		let recorded = sp_externalities::Externalities::proof_size(&ext).unwrap();
+		assert_eq!(recorded as usize, backend.extract_proof().encoded_size());
+		assert!(recorded > 1);
+
+		assert_eq!(crate::Backend::<BlakeTwo256>::proof_size(&trie_backend), None);
+	}
 }
@@ -151,6 +151,9 @@ pub struct StorageChangeSet<Hash> {
 	pub block: Hash,
 	/// A list of changes
 	pub changes: Vec<(StorageKey, Option<StorageData>)>,
+	/// A list of changes of default child tries, by the prefixed storage key of the child trie
+	#[cfg_attr(feature = "std", serde(default, skip_serializing_if = "Vec::is_empty"))]
+	pub child_changes: Vec<(PrefixedStorageKey, Vec<(StorageKey, Option<StorageData>)>)>,
 }
 
 /// List of all well known keys and prefixes in storage.
@@ -121,6 +121,8 @@ pub enum InherentError {
 	ValidAtTimestamp(InherentType),
 	/// Some other error.
 	Other(RuntimeString),
+	/// The timestamp is further in the future than the allowed drift, which ends at the argument.
+	TooFarInFuture(InherentType),
 }
 
 impl IsFatalError for InherentError {
@@ -128,6 +130,7 @@ impl IsFatalError for InherentError {
 		match self {
 			InherentError::ValidAtTimestamp(_) => false,
 			InherentError::Other(_) => true,
+			InherentError::TooFarInFuture(_) => true,
 		}
 	}
 }
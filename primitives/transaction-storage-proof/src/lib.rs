@@ -0,0 +1,255 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage proof primitives. Contains types and basic code to extract storage
+//! proofs for indexed transactions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::{result::Result, prelude::*};
+
+use codec::{Encode, Decode};
+use sp_inherents::{InherentIdentifier, IsFatalError, InherentData};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+pub use sp_inherents::Error;
+
+/// The identifier for the proof inherent.
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"tx_proof";
+/// Storage period for data.
+pub const DEFAULT_STORAGE_PERIOD: u32 = 100800;
+/// Proof trie value size.
+pub const CHUNK_SIZE: usize = 256;
+
+/// Errors that can occur while checking the storage proof.
+#[derive(Encode, sp_runtime::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode))]
+pub enum InherentError {
+	/// The proof does not match the stored chunk root.
+	InvalidProof,
+	/// The proof could not be decoded or verified.
+	TrieError,
+}
+
+impl IsFatalError for InherentError {
+	fn is_fatal_error(&self) -> bool {
+		true
+	}
+}
+
+/// Proof that a randomly selected chunk of the data stored in a past block is still available.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, sp_runtime::RuntimeDebug)]
+pub struct TransactionStorageProof {
+	/// Data chunk that is proved to exist.
+	pub chunk: Vec<u8>,
+	/// Trie nodes that compose the proof.
+	pub proof: Vec<Vec<u8>>,
+}
+
+/// Auxiliary trait to extract the storage proof from the inherent data.
+pub trait TransactionStorageProofInherentData {
+	/// Get the proof.
+	fn storage_proof(&self) -> Result<Option<TransactionStorageProof>, Error>;
+}
+
+impl TransactionStorageProofInherentData for InherentData {
+	fn storage_proof(&self) -> Result<Option<TransactionStorageProof>, Error> {
+		self.get_data(&INHERENT_IDENTIFIER)
+	}
+}
+
+/// Number of chunks that `size` bytes of data are split into.
+pub fn num_chunks(size: u32) -> u32 {
+	((size as u64 + CHUNK_SIZE as u64 - 1) / CHUNK_SIZE as u64) as u32
+}
+
+/// Select a chunk index out of `total_chunks` using `random_hash` as the source of randomness.
+///
+/// `random_hash` must be at least 8 bytes long and `total_chunks` must not be zero.
+pub fn random_chunk(random_hash: &[u8], total_chunks: u32) -> u32 {
+	let mut buf = [0u8; 8];
+	buf.copy_from_slice(&random_hash[0..8]);
+	let random_u64 = u64::from_be_bytes(buf);
+	(random_u64 % total_chunks as u64) as u32
+}
+
+/// Encode a chunk index as trie key.
+pub fn encode_index(input: u32) -> Vec<u8> {
+	codec::Encode::encode(&codec::Compact(input))
+}
+
+/// An interface to request indexed data from the client.
+pub trait IndexedBody<B: BlockT> {
+	/// Get all indexed transactions of the block with the given number, in the order they were
+	/// indexed. Returns `None` if the block or its body is unknown.
+	fn block_indexed_body(&self, number: NumberFor<B>) -> Result<Option<Vec<Vec<u8>>>, Error>;
+}
+
+#[cfg(feature = "std")]
+pub mod registration {
+	use std::{sync::Arc, marker::PhantomData};
+	use sp_blockchain::HeaderBackend;
+	use sp_inherents::ProvideInherentData;
+	use sp_runtime::traits::{Saturating, Zero, One};
+	use sp_trie::TrieMut;
+	use super::*;
+
+	type Hasher = sp_core::Blake2Hasher;
+	type TrieLayout = sp_trie::Layout<Hasher>;
+
+	/// Provides the storage proof required by the block built on top of the current best block.
+	pub struct InherentDataProvider<B, C> {
+		client: Arc<C>,
+		_phantom: PhantomData<B>,
+	}
+
+	impl<B, C> InherentDataProvider<B, C> {
+		/// Create a new instance that reads indexed transactions from `client`.
+		pub fn new(client: Arc<C>) -> Self {
+			InherentDataProvider { client, _phantom: PhantomData }
+		}
+	}
+
+	impl<B, C> ProvideInherentData for InherentDataProvider<B, C> where
+		B: BlockT,
+		C: IndexedBody<B> + HeaderBackend<B> + Send + Sync,
+	{
+		fn inherent_identifier(&self) -> &'static InherentIdentifier {
+			&INHERENT_IDENTIFIER
+		}
+
+		fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), Error> {
+			let info = self.client.info();
+			match storage_proof(&*self.client, &info.best_hash, info.best_number)? {
+				Some(proof) => inherent_data.put_data(INHERENT_IDENTIFIER, &proof),
+				None => Ok(()),
+			}
+		}
+
+		fn error_to_string(&self, error: &[u8]) -> Option<String> {
+			InherentError::decode(&mut &error[..]).ok().map(|e| format!("{:?}", e))
+		}
+	}
+
+	/// Build the storage proof required by the block built on top of `parent`, if any.
+	pub fn storage_proof<B, C>(
+		client: &C,
+		parent: &B::Hash,
+		parent_number: NumberFor<B>,
+	) -> Result<Option<TransactionStorageProof>, Error> where
+		B: BlockT,
+		C: IndexedBody<B>,
+	{
+		let number = parent_number
+			.saturating_add(One::one())
+			.saturating_sub(DEFAULT_STORAGE_PERIOD.into());
+		if number.is_zero() {
+			// Too early to collect proofs.
+			return Ok(None);
+		}
+
+		match client.block_indexed_body(number)? {
+			Some(transactions) if !transactions.is_empty() =>
+				build_proof(parent.as_ref(), transactions).map(Some),
+			// Nothing was indexed in that block.
+			_ => Ok(None),
+		}
+	}
+
+	/// Build a proof for a chunk of `transactions` selected with `random_hash`.
+	pub fn build_proof(
+		random_hash: &[u8],
+		transactions: Vec<Vec<u8>>,
+	) -> Result<TransactionStorageProof, Error> {
+		let total_chunks = transactions.iter().map(|t| num_chunks(t.len() as u32)).sum();
+		if total_chunks == 0 {
+			return Err("No data to prove".into());
+		}
+
+		let mut chunk_index = random_chunk(random_hash, total_chunks);
+		for transaction in transactions {
+			let chunks = num_chunks(transaction.len() as u32);
+			if chunk_index >= chunks {
+				chunk_index -= chunks;
+				continue;
+			}
+
+			let mut db = sp_trie::MemoryDB::<Hasher>::default();
+			let mut root = sp_trie::empty_trie_root::<TrieLayout>();
+			{
+				let mut trie = sp_trie::TrieDBMut::<TrieLayout>::new(&mut db, &mut root);
+				for (index, chunk) in transaction.chunks(CHUNK_SIZE).enumerate() {
+					trie.insert(&encode_index(index as u32), chunk)
+						.map_err(|e| Error::from(format!("{:?}", e)))?;
+				}
+				trie.commit();
+			}
+
+			let proof = sp_trie::generate_trie_proof::<TrieLayout, _, _, _>(
+				&db,
+				root,
+				&[encode_index(chunk_index)],
+			).map_err(|e| Error::from(format!("{:?}", e)))?;
+			let chunk = transaction.chunks(CHUNK_SIZE)
+				.nth(chunk_index as usize)
+				.expect("`chunk_index` is less than the number of chunks; qed")
+				.to_vec();
+
+			return Ok(TransactionStorageProof { chunk, proof });
+		}
+
+		Err("Selected chunk is out of range".into())
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		#[test]
+		fn build_proof_check() {
+			let random = [0u8; 32];
+			let data = vec![42u8; CHUNK_SIZE * 2 + 1];
+			let proof = build_proof(&random, vec![vec![1], data.clone()]).unwrap();
+			let chunks = data.chunks(CHUNK_SIZE).map(|c| c.to_vec()).collect::<Vec<_>>();
+			let root = <TrieLayout as sp_trie::TrieConfiguration>::ordered_trie_root(chunks);
+			// The first chunk of the first transaction is selected.
+			assert_eq!(proof.chunk, vec![1]);
+			assert!(sp_trie::verify_trie_proof::<TrieLayout, _, _, _>(
+				&root,
+				&proof.proof,
+				&[(encode_index(0), Some(proof.chunk.clone()))],
+			).is_err());
+
+			let random = [0u8, 0, 0, 0, 0, 0, 0, 2];
+			let proof = build_proof(&random, vec![vec![1], data]).unwrap();
+			assert_eq!(proof.chunk, chunks[1]);
+			sp_trie::verify_trie_proof::<TrieLayout, _, _, _>(
+				&root,
+				&proof.proof,
+				&[(encode_index(1), Some(proof.chunk))],
+			).unwrap();
+		}
+
+		#[test]
+		fn num_chunks_rounds_up() {
+			assert_eq!(num_chunks(0), 0);
+			assert_eq!(num_chunks(1), 1);
+			assert_eq!(num_chunks(CHUNK_SIZE as u32), 1);
+			assert_eq!(num_chunks(CHUNK_SIZE as u32 + 1), 2);
+		}
+	}
+}
@@ -18,6 +18,7 @@
 use sp_std::vec::Vec;
 use codec::{Encode, Decode};
 use hash_db::{Hasher, HashDB};
+use crate::Layout;
 
 /// A proof that some set of key-value pairs are included in the storage trie. The proof contains
 /// the storage values so that the partial storage backend can be reconstructed by a verifier that
@@ -31,6 +32,16 @@ pub struct StorageProof {
 	trie_nodes: Vec<Vec<u8>>,
 }
 
+/// Storage proof in compact form.
+///
+/// Nodes that can be recomputed by the verifier (the hashes of the proof's own nodes) are
+/// omitted, see [`crate::encode_compact`] for details.
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
+pub struct CompactProof {
+	/// The compact encoded trie nodes.
+	pub encoded_nodes: Vec<Vec<u8>>,
+}
+
 impl StorageProof {
 	/// Constructs a storage proof from a subset of encoded trie nodes in a storage backend.
 	pub fn new(trie_nodes: Vec<Vec<u8>>) -> Self {
@@ -75,6 +86,73 @@ impl StorageProof {
 
 		Self { trie_nodes }
 	}
+
+	/// Encode as a compact proof with the default trie layout.
+	pub fn into_compact_proof<H: Hasher>(
+		self,
+		root: H::Out,
+	) -> Result<CompactProof, crate::CompactProofError<Layout<H>>> {
+		crate::encode_compact::<Layout<H>>(self, root)
+	}
+
+	/// Decode a compact proof to a full storage proof with the default trie layout.
+	///
+	/// If `expected_root` is given, the root of the decoded proof is checked against it.
+	/// Returns the full storage proof together with its root.
+	pub fn from_compact_proof<H: Hasher>(
+		proof: &CompactProof,
+		expected_root: Option<&H::Out>,
+	) -> Result<(StorageProof, H::Out), crate::CompactProofError<Layout<H>>> {
+		let mut db = crate::MemoryDB::<H>::new(&[]);
+		let root = crate::decode_compact::<Layout<H>, _, _>(
+			&mut db,
+			proof.iter_compact_encoded_nodes(),
+			expected_root,
+		)?;
+		Ok((
+			StorageProof::new(
+				db.drain()
+					.into_iter()
+					.filter_map(|(_, (value, rc))| if rc > 0 { Some(value) } else { None })
+					.collect(),
+			),
+			root,
+		))
+	}
+
+	/// Returns the estimated encoded size of the compact proof.
+	///
+	/// Running this operation is a slow operation (build the whole compact proof) and should only
+	/// be used for debugging or measurement. Returns `None` if the proof can not be compacted.
+	#[cfg(feature = "std")]
+	pub fn encoded_compact_size<H: Hasher>(self, root: H::Out) -> Option<usize> {
+		let compact_proof = self.into_compact_proof::<H>(root);
+		compact_proof.ok().map(|p| p.encoded_size())
+	}
+}
+
+impl CompactProof {
+	/// Return an iterator on the compact encoded nodes.
+	pub fn iter_compact_encoded_nodes(&self) -> impl Iterator<Item = &[u8]> {
+		self.encoded_nodes.iter().map(Vec::as_slice)
+	}
+
+	/// Compress the compact proof with zstd.
+	///
+	/// Returns `None` if the encoded proof is bigger than the given `bomb_limit`, as the
+	/// decompressing side would not be able to distinguish it from a compression bomb.
+	#[cfg(feature = "std")]
+	pub fn compress(&self, bomb_limit: usize) -> Option<Vec<u8>> {
+		sp_maybe_compressed_blob::compress(&self.encode(), bomb_limit)
+	}
+
+	/// Decode a compact proof that was optionally compressed with [`CompactProof::compress`].
+	#[cfg(feature = "std")]
+	pub fn decompress(blob: &[u8], bomb_limit: usize) -> Result<Self, codec::Error> {
+		let encoded = sp_maybe_compressed_blob::decompress(blob, bomb_limit)
+			.map_err(|_| codec::Error::from("Invalid compressed compact proof"))?;
+		Self::decode(&mut &encoded[..])
+	}
 }
 
 /// An iterator over trie nodes constructed from a storage proof. The nodes are not guaranteed to
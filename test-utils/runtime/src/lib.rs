@@ -482,6 +482,7 @@ impl frame_support::traits::PalletInfo for Runtime {
 parameter_types! {
 	pub const BlockHashCount: BlockNumber = 2400;
 	pub const MinimumPeriod: u64 = 5;
+	pub const MaxDrift: u64 = 30_000;
 	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight {
 		read: 100,
 		write: 1000,
@@ -523,6 +524,7 @@ impl pallet_timestamp::Config for Runtime {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = MinimumPeriod;
+	type MaxDrift = MaxDrift;
 	type WeightInfo = ();
 }
 
@@ -551,6 +553,7 @@ impl pallet_babe::Config for Runtime {
 
 	type HandleEquivocation = ();
 
+	type DisabledValidators = ();
 	type WeightInfo = ();
 }
 
@@ -773,6 +776,23 @@ cfg_if! {
 						AuraId::from(authority)
 					}).collect()
 				}
+
+				fn generate_key_ownership_proof(
+					_slot: sp_consensus_aura::Slot,
+					_authority_id: AuraId,
+				) -> Option<sp_consensus_aura::OpaqueKeyOwnershipProof> {
+					None
+				}
+
+				fn submit_report_equivocation_unsigned_extrinsic(
+					_equivocation_proof: sp_consensus_aura::EquivocationProof<
+						<Block as BlockT>::Header,
+						AuraId,
+					>,
+					_key_owner_proof: sp_consensus_aura::OpaqueKeyOwnershipProof,
+				) -> Option<()> {
+					None
+				}
 			}
 
 			impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -825,8 +845,12 @@ cfg_if! {
 			}
 
 			impl sp_session::SessionKeys<Block> for Runtime {
-				fn generate_session_keys(_: Option<Vec<u8>>) -> Vec<u8> {
-					SessionKeys::generate(None)
+				fn generate_session_keys(
+					owner: Vec<u8>,
+					_: Option<Vec<u8>>,
+				) -> sp_session::OpaqueGeneratedSessionKeys {
+					let (keys, proof) = SessionKeys::generate_with_ownership_proof(&owner, None);
+					sp_session::OpaqueGeneratedSessionKeys { keys, proof }
 				}
 
 				fn decode_session_keys(
@@ -841,6 +865,25 @@ cfg_if! {
 					Vec::new()
 				}
 
+				fn current_set_id() -> sp_finality_grandpa::SetId {
+					0
+				}
+
+				fn pending_change() -> Option<(
+					NumberFor<Block>,
+					sp_finality_grandpa::ScheduledChange<NumberFor<Block>>,
+				)> {
+					None
+				}
+
+				fn pending_forced_change() -> Option<(
+					NumberFor<Block>,
+					NumberFor<Block>,
+					sp_finality_grandpa::ScheduledChange<NumberFor<Block>>,
+				)> {
+					None
+				}
+
 				fn submit_report_equivocation_unsigned_extrinsic(
 					_equivocation_proof: sp_finality_grandpa::EquivocationProof<
 						<Block as BlockT>::Hash,
@@ -863,6 +906,20 @@ cfg_if! {
 				fn account_nonce(_account: AccountId) -> Index {
 					0
 				}
+
+				fn signing_info(
+					_account: AccountId,
+				) -> frame_system_rpc_runtime_api::SigningInfo<Hash, BlockNumber, Index> {
+					frame_system_rpc_runtime_api::SigningInfo {
+						nonce: 0,
+						genesis_hash: Default::default(),
+						era: sp_runtime::generic::Era::Immortal,
+						checkpoint: (0, Default::default()),
+						spec_version: VERSION.spec_version,
+						transaction_version: VERSION.transaction_version,
+						extensions: Vec::new(),
+					}
+				}
 			}
 		}
 	} else {
@@ -1034,6 +1091,23 @@ cfg_if! {
 						AuraId::from(authority)
 					}).collect()
 				}
+
+				fn generate_key_ownership_proof(
+					_slot: sp_consensus_aura::Slot,
+					_authority_id: AuraId,
+				) -> Option<sp_consensus_aura::OpaqueKeyOwnershipProof> {
+					None
+				}
+
+				fn submit_report_equivocation_unsigned_extrinsic(
+					_equivocation_proof: sp_consensus_aura::EquivocationProof<
+						<Block as BlockT>::Header,
+						AuraId,
+					>,
+					_key_owner_proof: sp_consensus_aura::OpaqueKeyOwnershipProof,
+				) -> Option<()> {
+					None
+				}
 			}
 
 			impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -1086,8 +1160,12 @@ cfg_if! {
 			}
 
 			impl sp_session::SessionKeys<Block> for Runtime {
-				fn generate_session_keys(_: Option<Vec<u8>>) -> Vec<u8> {
-					SessionKeys::generate(None)
+				fn generate_session_keys(
+					owner: Vec<u8>,
+					_: Option<Vec<u8>>,
+				) -> sp_session::OpaqueGeneratedSessionKeys {
+					let (keys, proof) = SessionKeys::generate_with_ownership_proof(&owner, None);
+					sp_session::OpaqueGeneratedSessionKeys { keys, proof }
 				}
 
 				fn decode_session_keys(
@@ -1101,6 +1179,20 @@ cfg_if! {
 				fn account_nonce(_account: AccountId) -> Index {
 					0
 				}
+
+				fn signing_info(
+					_account: AccountId,
+				) -> frame_system_rpc_runtime_api::SigningInfo<Hash, BlockNumber, Index> {
+					frame_system_rpc_runtime_api::SigningInfo {
+						nonce: 0,
+						genesis_hash: Default::default(),
+						era: sp_runtime::generic::Era::Immortal,
+						checkpoint: (0, Default::default()),
+						spec_version: VERSION.spec_version,
+						transaction_version: VERSION.transaction_version,
+						extensions: Vec::new(),
+					}
+				}
 			}
 		}
 	}
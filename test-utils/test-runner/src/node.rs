@@ -204,7 +204,7 @@ impl<T: ChainInfo> Node<T> {
 		// spawn the authorship task as an essential task.
 		task_manager
 			.spawn_essential_handle()
-			.spawn("manual-seal", authorship_future);
+			.spawn("manual-seal", Some("block-authoring"), authorship_future);
 
 		network_starter.start_network();
 		let rpc_handler = rpc_handlers.io_handler();
@@ -85,7 +85,7 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		Default::default(),
 		None,
 	);
-	let informant_output_format = OutputFormat { enable_color: false };
+	let informant_output_format = OutputFormat { enable_color: false, ..Default::default() };
 	network_config.allow_non_globals_in_dht = true;
 
 	network_config
@@ -124,8 +124,10 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		rpc_ws: None,
 		rpc_ipc: None,
 		rpc_ws_max_connections: None,
+		rpc_shutdown_timeout: None,
 		rpc_cors: None,
 		rpc_methods: Default::default(),
+		rpc_policy: Default::default(),
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		telemetry_external_transport: None,
@@ -140,7 +142,10 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		announce_block: true,
 		base_path: Some(base_path),
 		wasm_runtime_overrides: None,
+		wasm_runtime_cache: None,
 		informant_output_format,
+		storage_monitor: Default::default(),
+		hardware_benchmarks: false,
 		disable_log_reloading: false,
 		keystore_remote: None,
 		keep_blocks: KeepBlocks::All,
@@ -101,7 +101,9 @@ where
 		rpc_ipc: Default::default(),
 		rpc_ws: Default::default(),
 		rpc_ws_max_connections: Default::default(),
+		rpc_shutdown_timeout: Default::default(),
 		rpc_methods: Default::default(),
+		rpc_policy: Default::default(),
 		state_cache_child_ratio: Default::default(),
 		state_cache_size: Default::default(),
 		tracing_receiver: Default::default(),
@@ -109,12 +111,16 @@ where
 		transaction_pool: Default::default(),
 		wasm_method: Default::default(),
 		wasm_runtime_overrides: Default::default(),
+		wasm_runtime_cache: Default::default(),
 		max_runtime_instances: 8,
 		announce_block: true,
 		base_path: None,
 		informant_output_format: sc_informant::OutputFormat {
 			enable_color: false,
+			..Default::default()
 		},
+		storage_monitor: Default::default(),
+		hardware_benchmarks: false,
 		disable_log_reloading: false,
 	};
 